@@ -50,6 +50,8 @@ pub enum MigrationCommands {
     Make(MigrationMakeArgs),
     /// Create a new empty migration
     New(MigrationNewArgs),
+    /// Squash a range of migrations into a single one
+    Squash(MigrationSquashArgs),
 }
 
 #[derive(Debug, Args)]
@@ -85,6 +87,20 @@ pub struct MigrationMakeArgs {
     pub output_dir: Option<PathBuf>,
 }
 
+#[derive(Debug, Args)]
+pub struct MigrationSquashArgs {
+    /// Name of the app (crate) to squash the migrations of
+    pub app: String,
+    /// First migration to squash, as a full name or a number
+    pub from: String,
+    /// Last migration to squash, as a full name or a number
+    pub to: String,
+    /// Path to the crate directory to squash the migrations in [default:
+    /// current directory]
+    #[arg(long)]
+    pub path: Option<PathBuf>,
+}
+
 #[derive(Debug, Args)]
 #[group(multiple = false)]
 pub struct CotSourceArgs {
@@ -5,10 +5,11 @@ use clap::CommandFactory;
 
 use crate::args::{
     Cli, CompletionsArgs, ManpagesArgs, MigrationListArgs, MigrationMakeArgs, MigrationNewArgs,
-    ProjectNewArgs,
+    MigrationSquashArgs, ProjectNewArgs,
 };
 use crate::migration_generator::{
     MigrationGeneratorOptions, create_new_migration, list_migrations, make_migrations,
+    squash_migrations,
 };
 use crate::new_project::{CotSource, new_project};
 
@@ -77,6 +78,18 @@ pub fn handle_migration_new(
     create_new_migration(&path, &name, options).with_context(|| "unable to create migration")
 }
 
+pub fn handle_migration_squash(
+    MigrationSquashArgs {
+        app,
+        from,
+        to,
+        path,
+    }: MigrationSquashArgs,
+) -> anyhow::Result<()> {
+    let path = path.unwrap_or(PathBuf::from("."));
+    squash_migrations(&path, &app, &from, &to).with_context(|| "unable to squash migrations")
+}
+
 pub fn handle_cli_manpages(
     ManpagesArgs { output_dir, create }: ManpagesArgs,
 ) -> anyhow::Result<()> {
@@ -158,6 +171,20 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn migration_squash_wrong_directory() {
+        let args = MigrationSquashArgs {
+            app: "test_app".to_string(),
+            from: "0001".to_string(),
+            to: "0002".to_string(),
+            path: Some(PathBuf::from("nonexistent")),
+        };
+
+        let result = handle_migration_squash(args);
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn generate_manpages() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -26,6 +26,7 @@ fn main() -> anyhow::Result<()> {
             MigrationCommands::List(args) => handlers::handle_migration_list(args),
             MigrationCommands::Make(args) => handlers::handle_migration_make(args),
             MigrationCommands::New(args) => handlers::handle_migration_new(args),
+            MigrationCommands::Squash(args) => handlers::handle_migration_squash(args),
         },
     }
 }
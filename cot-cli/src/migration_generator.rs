@@ -8,15 +8,21 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, bail};
 use cot::db::migrations::{DynMigration, MigrationEngine};
 use cot::utils::cli::{StatusType, print_status_msg};
-use cot_codegen::model::{Field, Model, ModelArgs, ModelOpts, ModelType};
+use cot_codegen::db_enum::{DbEnum, DbEnumOpts};
+use cot_codegen::model::{
+    ConstraintSpec, DefaultSpec, Field, IndexSpec, ManyToManyField, Model, ModelArgs, ModelOpts,
+    ModelType,
+};
 use cot_codegen::symbol_resolver::SymbolResolver;
-use darling::FromMeta;
+use darling::{FromDeriveInput, FromMeta};
 use heck::ToSnakeCase;
 use petgraph::graph::DiGraph;
 use petgraph::visit::EdgeRef;
 use proc_macro2::TokenStream;
 use quote::{ToTokens, format_ident, quote};
-use syn::{Meta, parse_quote};
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::{Meta, Token, parse_quote};
 use tracing::{debug, trace};
 
 use crate::utils::{CargoTomlManager, PackageManager};
@@ -141,6 +147,57 @@ pub fn list_migrations(path: &Path) -> anyhow::Result<HashMap<String, Vec<String
     }
 }
 
+pub fn squash_migrations(path: &Path, app: &str, from: &str, to: &str) -> anyhow::Result<()> {
+    let Some(manager) = CargoTomlManager::from_path(path)? else {
+        bail!("Cargo.toml not found in the specified directory or any parent directory.")
+    };
+
+    let package = match &manager {
+        CargoTomlManager::Workspace(workspace) => workspace
+            .get_package_manager(app)
+            .or_else(|| workspace.get_current_package_manager()),
+        CargoTomlManager::Package(package) => Some(package),
+    };
+    let Some(package) = package else {
+        bail!("App `{app}` not found in the workspace.")
+    };
+    squash_package_migrations(package, app, from, to)
+}
+
+fn squash_package_migrations(
+    manager: &PackageManager,
+    app: &str,
+    from: &str,
+    to: &str,
+) -> anyhow::Result<()> {
+    let crate_name = manager.get_package_name().to_string();
+    let manifest_path = manager.get_manifest_path();
+
+    let generator = MigrationGenerator::new(
+        manifest_path,
+        crate_name,
+        MigrationGeneratorOptions::default(),
+    );
+    let migration = generator
+        .generate_squashed_migration(app, from, to)
+        .context("unable to squash migrations")?;
+
+    generator
+        .write_migrations(&migration)
+        .context("unable to write migrations")?;
+    generator
+        .write_migrations_module()
+        .context("unable to write migrations.rs")?;
+
+    print_status_msg(
+        StatusType::Notice,
+        "The squashed migrations can be removed once the new migration has been applied to \
+        all the databases",
+    );
+
+    Ok(())
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct MigrationGeneratorOptions {
     pub app_name: Option<String>,
@@ -196,7 +253,9 @@ impl MigrationGenerator {
         &self,
         source_files: Vec<SourceFile>,
     ) -> anyhow::Result<Option<GeneratedMigration>> {
-        let AppState { models, migrations } = self.process_source_files(source_files)?;
+        let AppState {
+            models, migrations, ..
+        } = self.process_source_files(source_files)?;
         let migration_processor = MigrationProcessor::new(migrations)?;
         let migration_models = migration_processor.latest_models();
 
@@ -267,6 +326,150 @@ impl MigrationGenerator {
         ))
     }
 
+    /// Generates a migration replacing the migrations of the app from `from` to
+    /// `to` (inclusive), read from the migrations directory.
+    pub fn generate_squashed_migration(
+        &self,
+        app: &str,
+        from: &str,
+        to: &str,
+    ) -> anyhow::Result<MigrationAsSource> {
+        let migrations_dir = self.get_src_path().join(MIGRATIONS_MODULE_NAME);
+        let source_files = Self::get_migration_list(&migrations_dir)?
+            .into_iter()
+            .map(|name| {
+                let path = PathBuf::from(format!("{name}.rs"));
+                Self::parse_file(&migrations_dir, path)
+                    .with_context(|| format!("unable to parse migration: {name}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        self.generate_squashed_migration_from_files(app, from, to, source_files)
+    }
+
+    /// Generates a migration replacing the migrations of the app from `from` to
+    /// `to` (inclusive), given as the parsed migration files.
+    ///
+    /// The migrations can be referred to either by their full names, or by
+    /// their numbers. The operations of all the migrations are concatenated,
+    /// the dependencies between them are dropped, and the migration models
+    /// are taken in their latest versions. The squashed migration gets the
+    /// next free migration number, so that it doesn't clash with any of the
+    /// existing migrations.
+    pub fn generate_squashed_migration_from_files(
+        &self,
+        app: &str,
+        from: &str,
+        to: &str,
+        source_files: Vec<SourceFile>,
+    ) -> anyhow::Result<MigrationAsSource> {
+        let mut migrations = source_files
+            .into_iter()
+            .map(MigrationInFile::from_source_file)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        migrations.sort_by(|a, b| a.name.cmp(&b.name));
+        let next_number = match migrations.last() {
+            Some(migration) => migration.number()? + 1,
+            None => 1,
+        };
+
+        let find_migration = |name: &str| {
+            migrations
+                .iter()
+                .position(|migration| migration.is_called(name))
+                .with_context(|| format!("migration `{name}` not found"))
+        };
+        let from_index = find_migration(from)?;
+        let to_index = find_migration(to)?;
+        if from_index >= to_index {
+            bail!("migration `{from}` must come before migration `{to}`");
+        }
+        let migrations = &migrations[from_index..=to_index];
+
+        let app_name = &migrations[0].app_name;
+        if app != app_name && app != self.crate_name {
+            bail!("the migrations belong to app `{app_name}`, not `{app}`");
+        }
+        for migration in migrations {
+            migration.check_squashable(app_name)?;
+        }
+
+        let replaces: Vec<&str> = migrations
+            .iter()
+            .map(|migration| migration.name.as_str())
+            .collect();
+        let mut dependencies: Vec<&syn::Expr> = Vec::new();
+        for dependency in migrations
+            .iter()
+            .flat_map(|migration| &migration.dependencies)
+        {
+            let is_replaced = migration_dependency(dependency)
+                .is_some_and(|(app, name)| &app == app_name && replaces.contains(&name.as_str()));
+            // the models of the app are created either by the squashed migration
+            // itself, or by the migrations it still depends on
+            if !is_replaced
+                && !is_crate_model_dependency(dependency)
+                && !dependencies.contains(&dependency)
+            {
+                dependencies.push(dependency);
+            }
+        }
+        let operations = migrations
+            .iter()
+            .flat_map(|migration| &migration.operations);
+
+        let mut models: Vec<&syn::ItemStruct> = Vec::new();
+        for model in migrations.iter().flat_map(|migration| &migration.models) {
+            match models
+                .iter_mut()
+                .find(|existing| existing.ident == model.ident)
+            {
+                Some(existing) => *existing = model,
+                None => models.push(model),
+            }
+        }
+
+        // the squashed migration can't be atomic if any of the migrations it
+        // replaces can't
+        let atomic_def = migrations
+            .iter()
+            .any(|migration| !migration.atomic)
+            .then(|| quote! { const ATOMIC: bool = false; });
+
+        let migration_name = format!(
+            "{MIGRATIONS_MODULE_PREFIX}{next_number:04}_squashed_{:04}_{:04}",
+            migrations[0].number()?,
+            migrations[migrations.len() - 1].number()?
+        );
+        let migration_def = quote! {
+            #[derive(Debug, Copy, Clone)]
+            pub(super) struct Migration;
+
+            impl ::cot::db::migrations::Migration for Migration {
+                const APP_NAME: &'static str = #app_name;
+                const MIGRATION_NAME: &'static str = #migration_name;
+                const DEPENDENCIES: &'static [::cot::db::migrations::MigrationDependency] = &[
+                    #(#dependencies,)*
+                ];
+                const OPERATIONS: &'static [::cot::db::migrations::Operation] = &[
+                    #(#operations,)*
+                ];
+                const REPLACES: &'static [&'static str] = &[
+                    #(#replaces,)*
+                ];
+                #atomic_def
+            }
+        };
+        let models_def = quote! {
+            #(#models)*
+        };
+
+        Ok(MigrationAsSource::new(
+            migration_name,
+            Self::generate_migration(migration_def, models_def),
+        ))
+    }
+
     pub fn write_migrations(&self, migration: &MigrationAsSource) -> anyhow::Result<()> {
         print_status_msg(
             StatusType::Creating,
@@ -347,6 +550,12 @@ impl MigrationGenerator {
                 .with_context(|| format!("unable to find models in file: {}", path.display()))?;
         }
 
+        // enums can be defined in a different file than the models using them, so
+        // the constraints can only be added once all the files are processed
+        for model in &mut app_state.models {
+            model.add_db_enum_constraints(&app_state.db_enums);
+        }
+
         Ok(app_state)
     }
 
@@ -376,6 +585,20 @@ impl MigrationGenerator {
 
         let mut migration_models = Vec::new();
         for item in file.items {
+            if let syn::Item::Enum(item) = &item
+                && is_db_enum(item)
+            {
+                let input: syn::DeriveInput = item.clone().into();
+                let db_enum = DbEnumOpts::from_derive_input(&input)
+                    .map_err(|e| anyhow::anyhow!("cannot parse enum: {e}"))?
+                    .as_db_enum()?;
+                trace!("Found a database enum: {}", db_enum.name);
+                app_state
+                    .db_enums
+                    .insert(db_enum.name.unraw().to_string(), db_enum);
+                continue;
+            }
+
             if let syn::Item::Struct(mut item) = item {
                 for attr in &item.attrs.clone() {
                     if is_model_attr(attr) {
@@ -464,24 +687,49 @@ impl MigrationGenerator {
             all_model_names.insert(model.model.table_name.clone());
             migration_models_map.insert(model.model.table_name.clone(), model);
         }
+
+        // the old table names of renamed models are handled together with the new ones
+        let renamed_models = Self::find_renamed_models(app_models, migration_models);
+        for migration_model in renamed_models.values() {
+            all_model_names.remove(&migration_model.model.table_name);
+        }
+
         let mut all_model_names: Vec<_> = all_model_names.into_iter().collect();
         all_model_names.sort();
 
+        let mut created_models = Vec::new();
+        let mut removed_models = Vec::new();
         for model_name in all_model_names {
             let app_model = app_models_map.get(&model_name);
             let migration_model = migration_models_map.get(&model_name);
 
             match (app_model, migration_model) {
                 (Some(&app_model), None) => {
-                    operations.push(MigrationOperationGenerator::make_create_model_operation(
+                    if let Some(&migration_model) = renamed_models.get(&model_name) {
+                        modified_models.push(app_model.clone());
+                        operations.extend(
+                            MigrationOperationGenerator::make_rename_model_operations(
+                                app_model,
+                                migration_model,
+                            ),
+                        );
+                        continue;
+                    }
+
+                    created_models.push(app_model);
+                    operations.extend(MigrationOperationGenerator::make_create_model_operations(
                         app_model,
                     ));
                     modified_models.push(app_model.clone());
                 }
                 (Some(&app_model), Some(&migration_model)) => {
                     if app_model.model.table_name != migration_model.model.table_name
-                        || app_model.model.pk_field != migration_model.model.pk_field
+                        || app_model.model.pk_fields != migration_model.model.pk_fields
                         || app_model.model.fields != migration_model.model.fields
+                        || app_model.model.many_to_many_fields
+                            != migration_model.model.many_to_many_fields
+                        || app_model.model.indexes != migration_model.model.indexes
+                        || app_model.model.constraints != migration_model.model.constraints
                     {
                         modified_models.push(app_model.clone());
                         operations.extend(
@@ -493,6 +741,16 @@ impl MigrationGenerator {
                     }
                 }
                 (None, Some(&migration_model)) => {
+                    removed_models.push(migration_model);
+                    // join tables reference the model, so they need to be removed first
+                    for field in &migration_model.model.many_to_many_fields {
+                        operations.extend(
+                            MigrationOperationGenerator::make_remove_join_table_operations(
+                                migration_model,
+                                field,
+                            ),
+                        );
+                    }
                     operations.push(MigrationOperationGenerator::make_remove_model_operation(
                         migration_model,
                     ));
@@ -501,9 +759,55 @@ impl MigrationGenerator {
             }
         }
 
+        if let ([app_model], [migration_model]) = (&created_models[..], &removed_models[..])
+            && app_model.model.fields == migration_model.model.fields
+        {
+            print_status_msg(
+                StatusType::Warning,
+                &format!(
+                    "Model '{}' looks like it was renamed to '{}'; add \
+                    `#[model(renamed_from = \"{}\")]` to keep its data",
+                    migration_model.model.original_name,
+                    app_model.model.original_name,
+                    migration_model.model.original_name,
+                ),
+            );
+        }
+
         (modified_models, operations)
     }
 
+    /// Returns the models renamed with the `#[model(renamed_from = "...")]`
+    /// attribute, as a map of the new table names to the migration models
+    /// the app models have been renamed from.
+    ///
+    /// The hint is only taken into account if neither the new table exists
+    /// in the migrations nor the old table exists in the app.
+    #[must_use]
+    fn find_renamed_models<'a>(
+        app_models: &[ModelInSource],
+        migration_models: &'a [ModelInSource],
+    ) -> HashMap<String, &'a ModelInSource> {
+        let table_exists = |models: &[ModelInSource], table_name: &str| {
+            models
+                .iter()
+                .any(|model| model.model.table_name == table_name)
+        };
+
+        app_models
+            .iter()
+            .filter(|model| !table_exists(migration_models, &model.model.table_name))
+            .filter_map(|model| {
+                let renamed_from = model.model.renamed_from.as_ref()?;
+                let migration_model = migration_models.iter().find(|migration_model| {
+                    &migration_model.model.original_name == renamed_from
+                        && !table_exists(app_models, &migration_model.model.table_name)
+                })?;
+                Some((model.model.table_name.clone(), migration_model))
+            })
+            .collect()
+    }
+
     fn generate_migration_file_content(&self, migration: GeneratedMigration) -> String {
         let operations: Vec<_> = migration
             .operations
@@ -604,18 +908,40 @@ impl MigrationGenerator {
         let mut model_source = model.model_item.clone();
         model_source.vis = syn::Visibility::Inherited;
         model_source.ident = format_ident!("_{}", model_source.ident);
+        // the model-level indexes and constraints are a part of the model's
+        // state, so they need to be kept in the migration model
+        let schema_args = Self::model_schema_args(&model_source.attrs);
         model_source.attrs.clear();
         model_source
             .attrs
             .push(syn::parse_quote! {#[derive(::core::fmt::Debug)]});
-        model_source
-            .attrs
-            .push(syn::parse_quote! {#[::cot::db::model(model_type = "migration")]});
+        model_source.attrs.push(
+            syn::parse_quote! {#[::cot::db::model(model_type = "migration" #(, #schema_args)*)]},
+        );
         quote! {
             #model_source
         }
     }
 
+    /// Returns the `index(...)`, `unique_together(...)` and `check(...)`
+    /// arguments of the model attribute.
+    #[must_use]
+    fn model_schema_args(attrs: &[syn::Attribute]) -> Vec<Meta> {
+        attrs
+            .iter()
+            .filter(|attr| is_model_attr(attr))
+            .filter_map(|attr| {
+                attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .ok()
+            })
+            .flatten()
+            .filter(|meta| {
+                let path = meta.path();
+                path.is_ident("index") || path.is_ident("unique_together") || path.is_ident("check")
+            })
+            .collect()
+    }
+
     fn get_migration_list(migrations_dir: &PathBuf) -> anyhow::Result<Vec<String>> {
         let dir = match std::fs::read_dir(migrations_dir) {
             Ok(dir) => dir,
@@ -686,6 +1012,23 @@ impl MigrationGenerator {
 struct MigrationOperationGenerator;
 
 impl MigrationOperationGenerator {
+    /// Returns the operations creating given model together with its
+    /// indexes, constraints, and join tables.
+    #[must_use]
+    fn make_create_model_operations(app_model: &ModelInSource) -> Vec<DynOperation> {
+        let mut operations = vec![Self::make_create_model_operation(app_model)];
+        for index in &app_model.model.indexes {
+            operations.push(Self::make_add_index_operation(app_model, index));
+        }
+        for constraint in &app_model.model.constraints {
+            operations.push(Self::make_add_constraint_operation(app_model, constraint));
+        }
+        for field in &app_model.model.many_to_many_fields {
+            operations.extend(Self::make_create_join_table_operations(app_model, field));
+        }
+        operations
+    }
+
     #[must_use]
     fn make_create_model_operation(app_model: &ModelInSource) -> DynOperation {
         print_status_msg(
@@ -726,17 +1069,51 @@ impl MigrationOperationGenerator {
             migration_model_fields.insert(field.column_name.clone(), field);
         }
 
+        // fields renamed with `#[model(renamed_from = "...")]`, keyed by the new
+        // column name; the old column names are handled together with the new ones
+        let mut renamed_fields = HashMap::new();
+        for (column_name, renamed_from) in &app_model.model.renamed_fields {
+            if migration_model_fields.contains_key(column_name) {
+                continue;
+            }
+            let migration_field = migration_model.model.fields.iter().find(|field| {
+                (&field.column_name == renamed_from
+                    || &field.name.unraw().to_string() == renamed_from)
+                    && !app_model_fields.contains_key(&field.column_name)
+            });
+            if let Some(migration_field) = migration_field {
+                all_field_names.remove(&migration_field.column_name);
+                renamed_fields.insert(column_name.clone(), migration_field);
+            }
+        }
+
         let mut all_field_names: Vec<_> = all_field_names.into_iter().collect();
         // sort to ensure deterministic order
         all_field_names.sort();
 
-        let mut operations = Vec::new();
+        // indexes and constraints are removed before, and added after the fields
+        // they refer to are changed
+        let (mut operations, added_indexes_and_constraints) =
+            Self::make_alter_indexes_and_constraints_operations(app_model, migration_model);
+        let mut added_fields = Vec::new();
+        let mut removed_fields = Vec::new();
         for field_name in all_field_names {
             let app_field = app_model_fields.get(&field_name);
             let migration_field = migration_model_fields.get(&field_name);
 
             match (app_field, migration_field) {
                 (Some(app_field), None) => {
+                    if let Some(migration_field) = renamed_fields.get(&field_name) {
+                        operations.extend(Self::make_rename_field_operations(
+                            app_model,
+                            app_field,
+                            migration_model,
+                            migration_field,
+                        ));
+                        continue;
+                    }
+
+                    added_fields.push(*app_field);
                     operations.push(Self::make_add_field_operation(app_model, app_field));
                 }
                 (Some(app_field), Some(migration_field)) => {
@@ -751,6 +1128,7 @@ impl MigrationOperationGenerator {
                     }
                 }
                 (None, Some(migration_field)) => {
+                    removed_fields.push(*migration_field);
                     operations.push(Self::make_remove_field_operation(
                         migration_model,
                         migration_field,
@@ -759,6 +1137,26 @@ impl MigrationOperationGenerator {
                 (None, None) => unreachable!(),
             }
         }
+        if let ([app_field], [migration_field]) = (&added_fields[..], &removed_fields[..])
+            && app_field.ty == migration_field.ty
+        {
+            print_status_msg(
+                StatusType::Warning,
+                &format!(
+                    "Field '{}' from Model '{}' looks like it was renamed to '{}'; add \
+                    `#[model(renamed_from = \"{}\")]` to keep its data",
+                    migration_field.name,
+                    migration_model.model.name,
+                    app_field.name,
+                    migration_field.name,
+                ),
+            );
+        }
+        operations.extend(Self::make_alter_many_to_many_operations(
+            app_model,
+            migration_model,
+        ));
+        operations.extend(added_indexes_and_constraints);
         print_status_msg(
             StatusType::Modified,
             &format!("Model '{}'", app_model.model.table_name),
@@ -767,6 +1165,71 @@ impl MigrationOperationGenerator {
         operations
     }
 
+    #[must_use]
+    fn make_alter_many_to_many_operations(
+        app_model: &ModelInSource,
+        migration_model: &ModelInSource,
+    ) -> Vec<DynOperation> {
+        let app_fields: HashMap<_, _> = app_model
+            .model
+            .many_to_many_fields
+            .iter()
+            .map(|field| (field.column_name.clone(), field))
+            .collect();
+        let migration_fields: HashMap<_, _> = migration_model
+            .model
+            .many_to_many_fields
+            .iter()
+            .map(|field| (field.column_name.clone(), field))
+            .collect();
+
+        let mut all_field_names: Vec<_> = app_fields
+            .keys()
+            .chain(migration_fields.keys())
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        // sort to ensure deterministic order
+        all_field_names.sort();
+
+        let mut operations = Vec::new();
+        for field_name in all_field_names {
+            let app_field = app_fields.get(&field_name);
+            let migration_field = migration_fields.get(&field_name);
+
+            match (app_field, migration_field) {
+                (Some(app_field), None) => {
+                    operations.extend(Self::make_create_join_table_operations(
+                        app_model, app_field,
+                    ));
+                }
+                (Some(app_field), Some(migration_field)) => {
+                    // the join table only depends on the related model, so it has to be
+                    // recreated if the related model has changed
+                    if app_field.to_model != migration_field.to_model {
+                        operations.extend(Self::make_remove_join_table_operations(
+                            app_model,
+                            migration_field,
+                        ));
+                        operations.extend(Self::make_create_join_table_operations(
+                            app_model, app_field,
+                        ));
+                    }
+                }
+                (None, Some(migration_field)) => {
+                    operations.extend(Self::make_remove_join_table_operations(
+                        app_model,
+                        migration_field,
+                    ));
+                }
+                (None, None) => unreachable!(),
+            }
+        }
+
+        operations
+    }
+
     #[must_use]
     fn make_remove_model_operation(migration_model: &ModelInSource) -> DynOperation {
         print_status_msg(
@@ -788,80 +1251,507 @@ impl MigrationOperationGenerator {
         op
     }
 
+    /// Returns the operations renaming given model, its join tables, and
+    /// applying any other changes made to the model.
     #[must_use]
-    fn make_add_field_operation(app_model: &ModelInSource, field: &Field) -> DynOperation {
-        print_status_msg(
-            StatusType::Adding,
-            &format!("Field '{}' to Model '{}'", field.name, app_model.model.name),
-        );
-
-        let op = DynOperation::AddField {
-            table_name: app_model.model.table_name.clone(),
-            model_ty: app_model.model.resolved_ty.clone(),
-            field: Box::new(field.clone()),
-        };
-
-        print_status_msg(
-            StatusType::Added,
-            &format!("Field '{}' to Model '{}'", field.name, app_model.model.name),
-        );
-
-        op
-    }
-
-    #[must_use]
-    fn make_alter_field_operation(
-        _app_model: &ModelInSource,
-        app_field: &Field,
+    fn make_rename_model_operations(
+        app_model: &ModelInSource,
         migration_model: &ModelInSource,
-        migration_field: &Field,
-    ) -> Option<DynOperation> {
-        if app_field == migration_field {
-            return None;
-        }
+    ) -> Vec<DynOperation> {
         print_status_msg(
-            StatusType::Modifying,
+            StatusType::Renaming,
             &format!(
-                "Field '{}' from Model '{}'",
-                migration_field.name, migration_model.model.name
+                "Model '{}' to '{}'",
+                migration_model.model.table_name, app_model.model.table_name
             ),
         );
 
-        todo!();
+        // the names of indexes and constraints are derived from the table name, so
+        // they are recreated after renaming the table
+        let mut operations: Vec<_> = migration_model
+            .model
+            .indexes
+            .iter()
+            .map(|index| Self::make_remove_index_operation(migration_model, index))
+            .chain(migration_model.model.constraints.iter().map(|constraint| {
+                Self::make_remove_constraint_operation(migration_model, constraint)
+            }))
+            .collect();
+        operations.push(DynOperation::RenameModel {
+            model_ty: app_model.model.resolved_ty.clone(),
+            old_table_name: migration_model.model.table_name.clone(),
+            new_table_name: app_model.model.table_name.clone(),
+        });
+        for app_field in &app_model.model.many_to_many_fields {
+            let migration_field = migration_model
+                .model
+                .many_to_many_fields
+                .iter()
+                .find(|field| field.column_name == app_field.column_name);
+            if let Some(migration_field) = migration_field {
+                operations.extend(Self::make_rename_join_table_operations(
+                    app_model,
+                    app_field,
+                    migration_model,
+                    migration_field,
+                ));
+            }
+        }
 
-        #[expect(unreachable_code)]
         print_status_msg(
-            StatusType::Modified,
+            StatusType::Renamed,
             &format!(
-                "Field '{}' from Model '{}'",
-                migration_field.name, migration_model.model.name
+                "Model '{}' to '{}'",
+                migration_model.model.table_name, app_model.model.table_name
             ),
         );
+
+        // the rest of the changes are applied to the model under its new name
+        let mut renamed_model = migration_model.clone();
+        renamed_model
+            .model
+            .table_name
+            .clone_from(&app_model.model.table_name);
+        renamed_model
+            .model
+            .resolved_ty
+            .clone_from(&app_model.model.resolved_ty);
+        for field in &mut renamed_model.model.many_to_many_fields {
+            if field.to_model == migration_model.model.resolved_ty {
+                field.to_model = app_model.model.resolved_ty.clone();
+            }
+        }
+        renamed_model.model.indexes.clear();
+        renamed_model.model.constraints.clear();
+        if app_model.model.pk_fields != renamed_model.model.pk_fields
+            || app_model.model.fields != renamed_model.model.fields
+            || app_model.model.many_to_many_fields != renamed_model.model.many_to_many_fields
+            || app_model.model.indexes != renamed_model.model.indexes
+            || app_model.model.constraints != renamed_model.model.constraints
+        {
+            operations.extend(Self::make_alter_model_operations(app_model, &renamed_model));
+        }
+
+        operations
     }
 
+    /// Returns the operations renaming the join table backing given
+    /// many-to-many field after the model containing it has been renamed.
     #[must_use]
-    fn make_remove_field_operation(
+    fn make_rename_join_table_operations(
+        app_model: &ModelInSource,
+        app_field: &ManyToManyField,
         migration_model: &ModelInSource,
-        migration_field: &Field,
-    ) -> DynOperation {
-        print_status_msg(
-            StatusType::Removing,
-            &format!(
-                "Field '{}' from Model '{}'",
-                migration_field.name, migration_model.model.name
-            ),
+        migration_field: &ManyToManyField,
+    ) -> Vec<DynOperation> {
+        let old_join_table = migration_field.join_table(
+            &migration_model.model.original_name,
+            &migration_model.model.table_name,
         );
+        let (new_table_name, model_ty, _fields) = Self::join_table_definition(app_model, app_field);
+        let new_join_table =
+            app_field.join_table(&app_model.model.original_name, &app_model.model.table_name);
 
-        let op = DynOperation::RemoveField {
-            table_name: migration_model.model.table_name.clone(),
-            model_ty: migration_model.model.resolved_ty.clone(),
-            field: Box::new(migration_field.clone()),
-        };
+        if old_join_table == new_join_table {
+            return Vec::new();
+        }
 
-        print_status_msg(
-            StatusType::Removed,
-            &format!(
-                "Field '{}' from Model '{}'",
+        // the name of the unique constraint is derived from the names of the
+        // table and the columns, so it's recreated after renaming them
+        let new_constraint = new_join_table.unique_constraint();
+        let mut operations = vec![DynOperation::RemoveConstraint {
+            table_name: old_join_table.table_name.clone(),
+            model_ty: model_ty.clone(),
+            constraint: old_join_table.unique_constraint(),
+        }];
+        if old_join_table.table_name != new_table_name {
+            operations.push(DynOperation::RenameModel {
+                model_ty: model_ty.clone(),
+                old_table_name: old_join_table.table_name,
+                new_table_name: new_table_name.clone(),
+            });
+        }
+        if old_join_table.source_column != new_join_table.source_column {
+            operations.push(DynOperation::RenameField {
+                table_name: new_table_name.clone(),
+                model_ty: model_ty.clone(),
+                old_name: old_join_table.source_column,
+                new_name: new_join_table.source_column,
+            });
+        }
+        if old_join_table.target_column != new_join_table.target_column {
+            operations.push(DynOperation::RenameField {
+                table_name: new_table_name.clone(),
+                model_ty: model_ty.clone(),
+                old_name: old_join_table.target_column,
+                new_name: new_join_table.target_column,
+            });
+        }
+        operations.push(DynOperation::AddConstraint {
+            table_name: new_table_name,
+            model_ty,
+            constraint: new_constraint,
+        });
+
+        operations
+    }
+
+    /// Returns the operations creating the join table backing given
+    /// many-to-many field, together with its unique constraint.
+    #[must_use]
+    fn make_create_join_table_operations(
+        app_model: &ModelInSource,
+        field: &ManyToManyField,
+    ) -> Vec<DynOperation> {
+        let (table_name, model_ty, fields) = Self::join_table_definition(app_model, field);
+        let join_table =
+            field.join_table(&app_model.model.original_name, &app_model.model.table_name);
+        print_status_msg(StatusType::Creating, &format!("Join table '{table_name}'"));
+        let operations = vec![
+            DynOperation::CreateModel {
+                table_name: table_name.clone(),
+                model_ty: model_ty.clone(),
+                fields,
+            },
+            DynOperation::AddConstraint {
+                table_name: table_name.clone(),
+                model_ty,
+                constraint: join_table.unique_constraint(),
+            },
+        ];
+        print_status_msg(StatusType::Created, &format!("Join table '{table_name}'"));
+        operations
+    }
+
+    /// Returns the operations removing the join table backing given
+    /// many-to-many field, together with its unique constraint (so that the
+    /// constraint is restored when the migration is rolled back).
+    #[must_use]
+    fn make_remove_join_table_operations(
+        model: &ModelInSource,
+        field: &ManyToManyField,
+    ) -> Vec<DynOperation> {
+        let (table_name, model_ty, fields) = Self::join_table_definition(model, field);
+        let join_table = field.join_table(&model.model.original_name, &model.model.table_name);
+        print_status_msg(StatusType::Removing, &format!("Join table '{table_name}'"));
+        let operations = vec![
+            DynOperation::RemoveConstraint {
+                table_name: table_name.clone(),
+                model_ty: model_ty.clone(),
+                constraint: join_table.unique_constraint(),
+            },
+            DynOperation::RemoveModel {
+                table_name: table_name.clone(),
+                model_ty,
+                fields,
+            },
+        ];
+        print_status_msg(StatusType::Removed, &format!("Join table '{table_name}'"));
+        operations
+    }
+
+    /// Returns the table name, the type and the fields of the join table
+    /// backing given many-to-many field.
+    ///
+    /// Join tables don't have model types of their own; instead, they are
+    /// identified by the tuple of the linked model types. This is only used to
+    /// order the operations, and nothing ever references a join table, so the
+    /// type doesn't need to be unique.
+    fn join_table_definition(
+        model: &ModelInSource,
+        field: &ManyToManyField,
+    ) -> (String, syn::Type, Vec<Field>) {
+        let join_table = field.join_table(&model.model.original_name, &model.model.table_name);
+        let source_ty = &model.model.resolved_ty;
+        let target_ty = &field.to_model;
+
+        let fields = join_table.fields(source_ty, target_ty);
+        (
+            join_table.table_name,
+            parse_quote!((#source_ty, #target_ty)),
+            fields,
+        )
+    }
+
+    #[must_use]
+    fn make_add_field_operation(app_model: &ModelInSource, field: &Field) -> DynOperation {
+        print_status_msg(
+            StatusType::Adding,
+            &format!("Field '{}' to Model '{}'", field.name, app_model.model.name),
+        );
+
+        let op = DynOperation::AddField {
+            table_name: app_model.model.table_name.clone(),
+            model_ty: app_model.model.resolved_ty.clone(),
+            field: Box::new(field.clone()),
+        };
+
+        print_status_msg(
+            StatusType::Added,
+            &format!("Field '{}' to Model '{}'", field.name, app_model.model.name),
+        );
+
+        op
+    }
+
+    #[must_use]
+    fn make_alter_field_operation(
+        app_model: &ModelInSource,
+        app_field: &Field,
+        migration_model: &ModelInSource,
+        migration_field: &Field,
+    ) -> Option<DynOperation> {
+        if app_field == migration_field {
+            return None;
+        }
+        print_status_msg(
+            StatusType::Modifying,
+            &format!(
+                "Field '{}' from Model '{}'",
+                migration_field.name, migration_model.model.name
+            ),
+        );
+
+        let op = DynOperation::AlterField {
+            table_name: migration_model.model.table_name.clone(),
+            model_ty: app_model.model.resolved_ty.clone(),
+            old_field: Box::new(migration_field.clone()),
+            new_field: Box::new(app_field.clone()),
+        };
+
+        print_status_msg(
+            StatusType::Modified,
+            &format!(
+                "Field '{}' from Model '{}'",
+                migration_field.name, migration_model.model.name
+            ),
+        );
+
+        Some(op)
+    }
+
+    /// Returns the operation renaming given field, followed by an operation
+    /// altering it if its definition has changed as well.
+    #[must_use]
+    fn make_rename_field_operations(
+        app_model: &ModelInSource,
+        app_field: &Field,
+        migration_model: &ModelInSource,
+        migration_field: &Field,
+    ) -> Vec<DynOperation> {
+        print_status_msg(
+            StatusType::Renaming,
+            &format!(
+                "Field '{}' to '{}' in Model '{}'",
+                migration_field.name, app_field.name, app_model.model.name
+            ),
+        );
+
+        let mut operations = vec![DynOperation::RenameField {
+            table_name: migration_model.model.table_name.clone(),
+            model_ty: app_model.model.resolved_ty.clone(),
+            old_name: migration_field.column_name.clone(),
+            new_name: app_field.column_name.clone(),
+        }];
+
+        print_status_msg(
+            StatusType::Renamed,
+            &format!(
+                "Field '{}' to '{}' in Model '{}'",
+                migration_field.name, app_field.name, app_model.model.name
+            ),
+        );
+
+        let renamed_field = Field {
+            name: app_field.name.clone(),
+            column_name: app_field.column_name.clone(),
+            ..migration_field.clone()
+        };
+        operations.extend(Self::make_alter_field_operation(
+            app_model,
+            app_field,
+            migration_model,
+            &renamed_field,
+        ));
+
+        operations
+    }
+
+    /// Returns the operations removing the indexes and constraints that are no
+    /// longer declared on the model, and the operations adding the new ones.
+    #[must_use]
+    fn make_alter_indexes_and_constraints_operations(
+        app_model: &ModelInSource,
+        migration_model: &ModelInSource,
+    ) -> (Vec<DynOperation>, Vec<DynOperation>) {
+        let mut removed = Vec::new();
+        for index in &migration_model.model.indexes {
+            if !app_model.model.indexes.contains(index) {
+                removed.push(Self::make_remove_index_operation(migration_model, index));
+            }
+        }
+        for constraint in &migration_model.model.constraints {
+            if !app_model.model.constraints.contains(constraint) {
+                removed.push(Self::make_remove_constraint_operation(
+                    migration_model,
+                    constraint,
+                ));
+            }
+        }
+
+        let mut added = Vec::new();
+        for index in &app_model.model.indexes {
+            if !migration_model.model.indexes.contains(index) {
+                added.push(Self::make_add_index_operation(app_model, index));
+            }
+        }
+        for constraint in &app_model.model.constraints {
+            if !migration_model.model.constraints.contains(constraint) {
+                added.push(Self::make_add_constraint_operation(app_model, constraint));
+            }
+        }
+
+        (removed, added)
+    }
+
+    #[must_use]
+    fn make_add_index_operation(app_model: &ModelInSource, index: &IndexSpec) -> DynOperation {
+        let index_name = index.name(&app_model.model.table_name);
+        print_status_msg(
+            StatusType::Adding,
+            &format!("Index '{index_name}' to Model '{}'", app_model.model.name),
+        );
+
+        let op = DynOperation::AddIndex {
+            table_name: app_model.model.table_name.clone(),
+            model_ty: app_model.model.resolved_ty.clone(),
+            index: index.clone(),
+        };
+
+        print_status_msg(
+            StatusType::Added,
+            &format!("Index '{index_name}' to Model '{}'", app_model.model.name),
+        );
+
+        op
+    }
+
+    #[must_use]
+    fn make_remove_index_operation(
+        migration_model: &ModelInSource,
+        index: &IndexSpec,
+    ) -> DynOperation {
+        let index_name = index.name(&migration_model.model.table_name);
+        print_status_msg(
+            StatusType::Removing,
+            &format!(
+                "Index '{index_name}' from Model '{}'",
+                migration_model.model.name
+            ),
+        );
+
+        let op = DynOperation::RemoveIndex {
+            table_name: migration_model.model.table_name.clone(),
+            model_ty: migration_model.model.resolved_ty.clone(),
+            index: index.clone(),
+        };
+
+        print_status_msg(
+            StatusType::Removed,
+            &format!(
+                "Index '{index_name}' from Model '{}'",
+                migration_model.model.name
+            ),
+        );
+
+        op
+    }
+
+    #[must_use]
+    fn make_add_constraint_operation(
+        app_model: &ModelInSource,
+        constraint: &ConstraintSpec,
+    ) -> DynOperation {
+        let constraint_name = constraint.name(&app_model.model.table_name);
+        print_status_msg(
+            StatusType::Adding,
+            &format!(
+                "Constraint '{constraint_name}' to Model '{}'",
+                app_model.model.name
+            ),
+        );
+
+        let op = DynOperation::AddConstraint {
+            table_name: app_model.model.table_name.clone(),
+            model_ty: app_model.model.resolved_ty.clone(),
+            constraint: constraint.clone(),
+        };
+
+        print_status_msg(
+            StatusType::Added,
+            &format!(
+                "Constraint '{constraint_name}' to Model '{}'",
+                app_model.model.name
+            ),
+        );
+
+        op
+    }
+
+    #[must_use]
+    fn make_remove_constraint_operation(
+        migration_model: &ModelInSource,
+        constraint: &ConstraintSpec,
+    ) -> DynOperation {
+        let constraint_name = constraint.name(&migration_model.model.table_name);
+        print_status_msg(
+            StatusType::Removing,
+            &format!(
+                "Constraint '{constraint_name}' from Model '{}'",
+                migration_model.model.name
+            ),
+        );
+
+        let op = DynOperation::RemoveConstraint {
+            table_name: migration_model.model.table_name.clone(),
+            model_ty: migration_model.model.resolved_ty.clone(),
+            constraint: constraint.clone(),
+        };
+
+        print_status_msg(
+            StatusType::Removed,
+            &format!(
+                "Constraint '{constraint_name}' from Model '{}'",
+                migration_model.model.name
+            ),
+        );
+
+        op
+    }
+
+    #[must_use]
+    fn make_remove_field_operation(
+        migration_model: &ModelInSource,
+        migration_field: &Field,
+    ) -> DynOperation {
+        print_status_msg(
+            StatusType::Removing,
+            &format!(
+                "Field '{}' from Model '{}'",
+                migration_field.name, migration_model.model.name
+            ),
+        );
+
+        let op = DynOperation::RemoveField {
+            table_name: migration_model.model.table_name.clone(),
+            model_ty: migration_model.model.resolved_ty.clone(),
+            field: Box::new(migration_field.clone()),
+        };
+
+        print_status_msg(
+            StatusType::Removed,
+            &format!(
+                "Field '{}' from Model '{}'",
                 migration_field.name, migration_model.model.name
             ),
         );
@@ -900,6 +1790,8 @@ struct AppState {
     models: Vec<ModelInSource>,
     /// All the migrations found in the source
     migrations: Vec<Migration>,
+    /// All the enums deriving `DbEnum` found in the source, by their names
+    db_enums: HashMap<String, DbEnum>,
 }
 
 impl AppState {
@@ -908,6 +1800,7 @@ impl AppState {
         Self {
             models: Vec::new(),
             migrations: Vec::new(),
+            db_enums: HashMap::new(),
         }
     }
 }
@@ -1023,26 +1916,104 @@ impl ModelInSource {
             .map_err(|e| anyhow::anyhow!("cannot parse model: {e}"))?;
         let mut model = opts.as_model(args, symbol_resolver)?;
         model.table_name = format!("{}__{}", app_name.to_snake_case(), model.table_name);
+        expand_composite_foreign_keys(&mut model);
 
         Ok(Self {
             model_item: item,
             model,
         })
     }
-}
+    /// Adds the `CHECK` constraints limiting the values of the fields storing
+    /// enums deriving `DbEnum` to the values of the enum variants.
+    ///
+    /// The constraints are also added to the model attribute, so that they're
+    /// kept in the migration model the same way as the ones declared with
+    /// `#[model(check(...))]`.
+    fn add_db_enum_constraints(&mut self, db_enums: &HashMap<String, DbEnum>) {
+        for field in &self.model.fields {
+            let Some(db_enum) = db_enum_name(&field.ty).and_then(|name| db_enums.get(&name)) else {
+                continue;
+            };
 
-/// A migration generated by the CLI and before converting to a Rust
-/// source code and writing to a file.
-#[derive(Debug, Clone)]
-pub struct GeneratedMigration {
-    pub migration_name: String,
-    pub modified_models: Vec<ModelInSource>,
-    pub dependencies: Vec<DynDependency>,
-    pub operations: Vec<DynOperation>,
+            let constraint = db_enum.check_constraint(&field.column_name);
+            if self.model.constraints.contains(&constraint) {
+                continue;
+            }
+            if let ConstraintSpec::Check { name, expr } = &constraint {
+                self.model_item
+                    .attrs
+                    .push(parse_quote! {#[model(check(name = #name, expr = #expr))]});
+            }
+            self.model.constraints.push(constraint);
+        }
+    }
 }
 
-impl GeneratedMigration {
-    #[must_use]
+/// Replaces the foreign keys referencing models with a composite primary key
+/// with a field for each of the columns they are stored in.
+///
+/// The columns are created and altered like any other field, while the
+/// foreign key constraint spanning all of them is one of the model's
+/// constraints.
+fn expand_composite_foreign_keys(model: &mut Model) {
+    model.fields = std::mem::take(&mut model.fields)
+        .into_iter()
+        .flat_map(|field| match &field.foreign_key {
+            Some(foreign_key) if foreign_key.is_composite() => {
+                let to_model = &foreign_key.to_model;
+                foreign_key
+                    .columns
+                    .iter()
+                    .enumerate()
+                    .map(|(index, column)| Field {
+                        name: format_ident!("{}", column),
+                        column_name: column.clone(),
+                        ty: parse_quote!(
+                            <<#to_model as ::cot::db::Model>::PrimaryKey
+                                as ::cot::db::CompositePrimaryKeyColumn<#index>>::Type
+                        ),
+                        auto_value: false,
+                        primary_key: false,
+                        foreign_key: None,
+                        unique: false,
+                        default: None,
+                    })
+                    .collect()
+            }
+            _ => vec![field],
+        })
+        .collect();
+}
+
+/// Returns the name of the type of a field with [`Option`] unwrapped, which is
+/// used to look up the enums deriving `DbEnum`.
+fn db_enum_name(ty: &syn::Type) -> Option<String> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident == "Option"
+        && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(syn::GenericArgument::Type(inner)) = args.args.first()
+    {
+        return db_enum_name(inner);
+    }
+
+    Some(segment.ident.unraw().to_string())
+}
+
+/// A migration generated by the CLI and before converting to a Rust
+/// source code and writing to a file.
+#[derive(Debug, Clone)]
+pub struct GeneratedMigration {
+    pub migration_name: String,
+    pub modified_models: Vec<ModelInSource>,
+    pub dependencies: Vec<DynDependency>,
+    pub operations: Vec<DynOperation>,
+}
+
+impl GeneratedMigration {
+    #[must_use]
     fn new(
         migration_name: String,
         modified_models: Vec<ModelInSource>,
@@ -1124,7 +2095,8 @@ impl GeneratedMigration {
                 fields,
             } => {
                 let to_type = match to {
-                    DynOperation::CreateModel { model_ty, .. } => model_ty,
+                    DynOperation::CreateModel { model_ty, .. }
+                    | DynOperation::RenameModel { model_ty, .. } => model_ty,
                     DynOperation::AddField { .. } => {
                         unreachable!(
                             "AddField operation shouldn't be a dependency of CreateModel \
@@ -1137,6 +2109,27 @@ impl GeneratedMigration {
                         because it doesn't create a new model"
                         )
                     }
+                    DynOperation::AlterField { .. } => {
+                        unreachable!(
+                            "AlterField operation shouldn't be a dependency of CreateModel \
+                        because it doesn't create a new model"
+                        )
+                    }
+                    DynOperation::RenameField { .. } => {
+                        unreachable!(
+                            "RenameField operation shouldn't be a dependency of CreateModel \
+                        because it doesn't create a new model"
+                        )
+                    }
+                    DynOperation::AddIndex { .. }
+                    | DynOperation::RemoveIndex { .. }
+                    | DynOperation::AddConstraint { .. }
+                    | DynOperation::RemoveConstraint { .. } => {
+                        unreachable!(
+                            "Index and constraint operations shouldn't be a dependency of \
+                        CreateModel because they don't create a new model"
+                        )
+                    }
                     DynOperation::RemoveModel { .. } => {
                         unreachable!(
                             "RemoveModel operation shouldn't be a dependency of CreateModel \
@@ -1175,6 +2168,30 @@ impl GeneratedMigration {
                 // RemoveField doesn't create dependencies, it only removes a field
                 unreachable!("RemoveField operation should never create cycles")
             }
+            DynOperation::AlterField { .. } => {
+                // AlterField only changes a field of an already existing model, so
+                // removing it shouldn't ever affect whether a graph is cyclic
+                unreachable!("AlterField operation should never create cycles")
+            }
+            DynOperation::RenameField { .. } => {
+                // RenameField only changes a field of an already existing model, so
+                // removing it shouldn't ever affect whether a graph is cyclic
+                unreachable!("RenameField operation should never create cycles")
+            }
+            DynOperation::RenameModel { .. } => {
+                // RenameModel doesn't add foreign keys, so nothing it depends on can
+                // depend on it
+                unreachable!("RenameModel operation should never create cycles")
+            }
+            DynOperation::AddIndex { .. }
+            | DynOperation::RemoveIndex { .. }
+            | DynOperation::AddConstraint { .. }
+            | DynOperation::RemoveConstraint { .. } => {
+                // index and constraint operations only change an already existing
+                // model, so removing them shouldn't ever affect whether a graph is
+                // cyclic
+                unreachable!("Index and constraint operations should never create cycles")
+            }
             DynOperation::RemoveModel { .. } => {
                 // RemoveModel doesn't create dependencies, it only removes a model
                 unreachable!("RemoveModel operation should never create cycles")
@@ -1211,7 +2228,9 @@ impl GeneratedMigration {
     ///
     /// The graph is directed and has an edge from operation A to operation B
     /// if operation B creates a foreign key that points to a model created by
-    /// operation A.
+    /// operation A, if operation B refers to a table or a field renamed by
+    /// operation A, or if operation A removes an index or a constraint from
+    /// the table that operation B changes.
     #[must_use]
     fn construct_dependency_graph(operations: &[DynOperation]) -> DiGraph<usize, (), usize> {
         let create_ops = Self::get_create_ops_map(operations);
@@ -1231,24 +2250,178 @@ impl GeneratedMigration {
                 );
             }
         }
+        for (dependency, i) in Self::get_ops_depending_on_creations(operations)
+            .into_iter()
+            .chain(Self::get_ops_depending_on_renames(operations))
+            .chain(Self::get_ops_depending_on_removals(operations))
+        {
+            graph.add_edge(
+                petgraph::graph::NodeIndex::new(dependency),
+                petgraph::graph::NodeIndex::new(i),
+                (),
+            );
+        }
 
         graph
     }
 
     /// Return a map of (resolved) model types to the index of the
     /// operation that creates given model.
+    ///
+    /// Renaming a model is treated as creating it, as the model can't be
+    /// referenced by its new name before it's renamed.
     #[must_use]
     fn get_create_ops_map(operations: &[DynOperation]) -> HashMap<syn::Type, usize> {
         operations
             .iter()
             .enumerate()
             .filter_map(|(i, op)| match op {
-                DynOperation::CreateModel { model_ty, .. } => Some((model_ty.clone(), i)),
+                DynOperation::CreateModel { model_ty, .. }
+                | DynOperation::RenameModel { model_ty, .. } => Some((model_ty.clone(), i)),
                 _ => None,
             })
             .collect()
     }
 
+    /// Return a list of operations that add an index or a constraint to a
+    /// table created in this migration, as tuples of the index of the
+    /// creating operation and the index of the dependent operation.
+    ///
+    /// The join tables of many-to-many fields are not uniquely identified by
+    /// their model types, so the table names are compared instead.
+    #[must_use]
+    fn get_ops_depending_on_creations(operations: &[DynOperation]) -> Vec<(usize, usize)> {
+        let mut dependencies = Vec::new();
+
+        for (i, op) in operations.iter().enumerate() {
+            for (j, other_op) in operations.iter().enumerate() {
+                if let (
+                    DynOperation::CreateModel { table_name, .. },
+                    DynOperation::AddIndex {
+                        table_name: other_table_name,
+                        ..
+                    }
+                    | DynOperation::AddConstraint {
+                        table_name: other_table_name,
+                        ..
+                    },
+                ) = (op, other_op)
+                    && table_name == other_table_name
+                {
+                    dependencies.push((i, j));
+                }
+            }
+        }
+
+        dependencies
+    }
+
+    /// Return a list of operations that depend on a rename done in this
+    /// migration as tuples of the index of the renaming operation and the
+    /// index of the dependent operation.
+    ///
+    /// These are the operations that refer to a renamed table or field by
+    /// its new name, so they have to be applied after the rename.
+    #[must_use]
+    fn get_ops_depending_on_renames(operations: &[DynOperation]) -> Vec<(usize, usize)> {
+        let mut dependencies = Vec::new();
+
+        for (i, op) in operations.iter().enumerate() {
+            for (j, other_op) in operations.iter().enumerate() {
+                let depends_on_rename = match (op, other_op) {
+                    (
+                        DynOperation::RenameModel { new_table_name, .. },
+                        DynOperation::AddField { table_name, .. }
+                        | DynOperation::RemoveField { table_name, .. }
+                        | DynOperation::AlterField { table_name, .. }
+                        | DynOperation::RenameField { table_name, .. }
+                        | DynOperation::AddIndex { table_name, .. }
+                        | DynOperation::AddConstraint { table_name, .. },
+                    ) => new_table_name == table_name,
+                    (
+                        DynOperation::RenameField {
+                            table_name,
+                            new_name,
+                            ..
+                        },
+                        DynOperation::AlterField {
+                            table_name: other_table_name,
+                            new_field,
+                            ..
+                        },
+                    ) => table_name == other_table_name && *new_name == new_field.column_name,
+                    (
+                        DynOperation::RenameField { table_name, .. },
+                        DynOperation::AddIndex {
+                            table_name: other_table_name,
+                            ..
+                        }
+                        | DynOperation::AddConstraint {
+                            table_name: other_table_name,
+                            ..
+                        },
+                    ) => table_name == other_table_name,
+                    _ => false,
+                };
+
+                if depends_on_rename {
+                    dependencies.push((i, j));
+                }
+            }
+        }
+
+        dependencies
+    }
+
+    /// Return a list of operations that have to be applied after an index or
+    /// a constraint is removed from the same table, as tuples of the index
+    /// of the removing operation and the index of the dependent operation.
+    ///
+    /// Indexes and constraints can refer to fields that are changed or
+    /// removed later in the migration, and their names can be reused by
+    /// the indexes and constraints added in their place.
+    #[must_use]
+    fn get_ops_depending_on_removals(operations: &[DynOperation]) -> Vec<(usize, usize)> {
+        let mut dependencies = Vec::new();
+
+        for (i, op) in operations.iter().enumerate() {
+            let (DynOperation::RemoveIndex {
+                table_name: removed_from,
+                ..
+            }
+            | DynOperation::RemoveConstraint {
+                table_name: removed_from,
+                ..
+            }) = op
+            else {
+                continue;
+            };
+
+            for (j, other_op) in operations.iter().enumerate() {
+                let (DynOperation::AddField { table_name, .. }
+                | DynOperation::RemoveField { table_name, .. }
+                | DynOperation::AlterField { table_name, .. }
+                | DynOperation::RenameField { table_name, .. }
+                | DynOperation::AddIndex { table_name, .. }
+                | DynOperation::AddConstraint { table_name, .. }
+                | DynOperation::RemoveModel { table_name, .. }
+                | DynOperation::RenameModel {
+                    old_table_name: table_name,
+                    ..
+                }) = other_op
+                else {
+                    continue;
+                };
+
+                if table_name == removed_from {
+                    dependencies.push((i, j));
+                }
+            }
+        }
+
+        dependencies
+    }
+
     /// Return a list of operations that add foreign keys as tuples of
     /// operation index and the type of the model that foreign key points to.
     #[must_use]
@@ -1273,10 +2446,46 @@ impl GeneratedMigration {
 
                     ops
                 }
+                DynOperation::AlterField {
+                    new_field,
+                    model_ty,
+                    ..
+                } => {
+                    let mut ops = vec![(i, model_ty.clone())];
+
+                    if let Some(to_type) = foreign_key_for_field(new_field) {
+                        ops.push((i, to_type));
+                    }
+
+                    ops
+                }
+                DynOperation::AddConstraint {
+                    model_ty,
+                    constraint,
+                    ..
+                } => {
+                    let mut ops = vec![(i, model_ty.clone())];
+
+                    if let ConstraintSpec::ForeignKey { to_model, .. } = constraint {
+                        ops.push((i, (**to_model).clone()));
+                    }
+
+                    ops
+                }
+                DynOperation::RenameField { model_ty, .. }
+                | DynOperation::AddIndex { model_ty, .. } => vec![(i, model_ty.clone())],
+                DynOperation::RemoveIndex { .. } | DynOperation::RemoveConstraint { .. } => {
+                    // removing indexes and constraints doesn't add foreign keys
+                    Vec::new()
+                }
                 DynOperation::RemoveField { .. } => {
                     // RemoveField Doesnt Add Foreign Keys
                     Vec::new()
                 }
+                DynOperation::RenameModel { .. } => {
+                    // RenameModel Doesnt Add Foreign Keys
+                    Vec::new()
+                }
                 DynOperation::RemoveModel { .. } => {
                     // RemoveModel Doesnt Add Foreign Keys
                     Vec::new()
@@ -1286,6 +2495,206 @@ impl GeneratedMigration {
     }
 }
 
+/// An existing migration, as read from its source file.
+#[derive(Debug, Clone)]
+struct MigrationInFile {
+    name: String,
+    app_name: String,
+    dependencies: Vec<syn::Expr>,
+    operations: Vec<syn::Expr>,
+    has_replaces: bool,
+    atomic: bool,
+    models: Vec<syn::ItemStruct>,
+}
+
+impl MigrationInFile {
+    fn from_source_file(source_file: SourceFile) -> anyhow::Result<Self> {
+        let name = source_file
+            .path
+            .file_stem()
+            .with_context(|| {
+                format!(
+                    "unable to get migration file name: {}",
+                    source_file.path.display()
+                )
+            })?
+            .to_string_lossy()
+            .into_owned();
+
+        let mut migration_impl = None;
+        let mut models = Vec::new();
+        for item in source_file.content.items {
+            match item {
+                syn::Item::Impl(item)
+                    if item.trait_.as_ref().is_some_and(|(path, _)| {
+                        path.segments
+                            .last()
+                            .is_some_and(|segment| segment.ident == "Migration")
+                    }) =>
+                {
+                    migration_impl = Some(item);
+                }
+                syn::Item::Struct(item) if item.attrs.iter().any(is_model_attr) => {
+                    models.push(item);
+                }
+                _ => {}
+            }
+        }
+        let migration_impl = migration_impl
+            .with_context(|| format!("`Migration` implementation not found in: {name}"))?;
+
+        let find_const = |const_name: &str| {
+            migration_impl.items.iter().find_map(|item| match item {
+                syn::ImplItem::Const(item) if item.ident == const_name => Some(&item.expr),
+                _ => None,
+            })
+        };
+        let array_elements = |const_name: &str| -> anyhow::Result<Vec<syn::Expr>> {
+            match find_const(const_name) {
+                Some(syn::Expr::Reference(syn::ExprReference { expr, .. })) => match &**expr {
+                    syn::Expr::Array(array) => Ok(array.elems.iter().cloned().collect()),
+                    _ => bail!("`{const_name}` is not an array in: {name}"),
+                },
+                _ => bail!("`{const_name}` not found in: {name}"),
+            }
+        };
+        let app_name = match find_const("APP_NAME") {
+            Some(syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(app_name),
+                ..
+            })) => app_name.value(),
+            _ => bail!("`APP_NAME` is not a string literal in: {name}"),
+        };
+
+        Ok(Self {
+            app_name,
+            dependencies: array_elements("DEPENDENCIES")?,
+            operations: array_elements("OPERATIONS")?,
+            has_replaces: find_const("REPLACES").is_some(),
+            atomic: !matches!(
+                find_const("ATOMIC"),
+                Some(syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Bool(syn::LitBool { value: false, .. }),
+                    ..
+                }))
+            ),
+            models,
+            name,
+        })
+    }
+
+    /// Returns an error if the migration can't be squashed into a migration of
+    /// given app.
+    fn check_squashable(&self, app_name: &str) -> anyhow::Result<()> {
+        if self.app_name != app_name {
+            bail!(
+                "migration `{}` belongs to app `{}`, not `{app_name}`",
+                self.name,
+                self.app_name
+            );
+        }
+        if self.has_replaces {
+            bail!(
+                "migration `{}` is a squashed migration; squash the original migrations instead",
+                self.name
+            );
+        }
+        if self.operations.iter().any(is_custom_operation) {
+            bail!(
+                "migration `{}` contains custom operations, which can't be squashed",
+                self.name
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns whether the migration can be referred to with given name,
+    /// either the full one, or just the migration number.
+    fn is_called(&self, name: &str) -> bool {
+        self.name == name || self.name.split('_').nth(1) == Some(name)
+    }
+
+    fn number(&self) -> anyhow::Result<u32> {
+        self.name
+            .split('_')
+            .nth(1)
+            .and_then(|number| number.parse().ok())
+            .with_context(|| format!("unable to parse the migration number: {}", self.name))
+    }
+}
+
+/// Returns whether the expression creates a custom operation, i.e. it's a
+/// chain of method calls on `Operation::custom(...)`.
+fn is_custom_operation(expr: &syn::Expr) -> bool {
+    match expr {
+        syn::Expr::MethodCall(call) => is_custom_operation(&call.receiver),
+        syn::Expr::Call(call) => matches!(
+            &*call.func,
+            syn::Expr::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "custom")
+        ),
+        _ => false,
+    }
+}
+
+/// Returns the app and migration name of a
+/// `MigrationDependency::migration(app, name)` expression.
+fn migration_dependency(expr: &syn::Expr) -> Option<(String, String)> {
+    let syn::Expr::Call(call) = expr else {
+        return None;
+    };
+    let syn::Expr::Path(path) = &*call.func else {
+        return None;
+    };
+    if path.path.segments.last()?.ident != "migration" {
+        return None;
+    }
+
+    let string_arg = |index: usize| match call.args.get(index)? {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(value),
+            ..
+        }) => Some(value.value()),
+        _ => None,
+    };
+    Some((string_arg(0)?, string_arg(1)?))
+}
+
+/// Returns whether the expression is a dependency on a model of the crate the
+/// migrations are generated for, i.e.
+/// `MigrationDependency::model(<crate::... as Model>::APP_NAME, ...)`.
+fn is_crate_model_dependency(expr: &syn::Expr) -> bool {
+    let syn::Expr::Call(call) = expr else {
+        return false;
+    };
+    let syn::Expr::Path(path) = &*call.func else {
+        return false;
+    };
+    if path
+        .path
+        .segments
+        .last()
+        .is_none_or(|segment| segment.ident != "model")
+    {
+        return false;
+    }
+
+    match call.args.first() {
+        Some(syn::Expr::Path(syn::ExprPath {
+            qself: Some(qself), ..
+        })) => matches!(
+            &*qself.ty,
+            syn::Type::Path(type_path)
+                if type_path.qself.is_none()
+                    && type_path
+                        .path
+                        .segments
+                        .first()
+                        .is_some_and(|segment| segment.ident == "crate")
+        ),
+        _ => false,
+    }
+}
+
 /// A migration represented as a generated and ready to write Rust source code.
 #[derive(Debug, Clone)]
 pub struct MigrationAsSource {
@@ -1300,6 +2709,23 @@ impl MigrationAsSource {
     }
 }
 
+#[must_use]
+fn is_db_enum(item: &syn::ItemEnum) -> bool {
+    item.attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|path| {
+            path.segments
+                .last()
+                .is_some_and(|segment| segment.ident == "DbEnum")
+        })
+}
+
 #[must_use]
 fn is_model_attr(attr: &syn::Attribute) -> bool {
     let path = attr.path();
@@ -1350,10 +2776,95 @@ impl Repr for Field {
         if self.unique {
             tokens = quote! { #tokens.unique() }
         }
+        if let Some(default) = &self.default {
+            let default = default.repr();
+            tokens = quote! { #tokens.default(#default) }
+        }
         tokens
     }
 }
 
+impl Repr for DefaultSpec {
+    fn repr(&self) -> TokenStream {
+        match self {
+            DefaultSpec::Bool(value) => quote! {
+                ::cot::db::migrations::DefaultValue::Bool(#value)
+            },
+            DefaultSpec::Int(value) => quote! {
+                ::cot::db::migrations::DefaultValue::Int(#value)
+            },
+            DefaultSpec::Float(value) => {
+                let value: f64 = value
+                    .parse()
+                    .expect("float defaults are parsed from float literals");
+                quote! {
+                    ::cot::db::migrations::DefaultValue::Float(#value)
+                }
+            }
+            DefaultSpec::String(value) => quote! {
+                ::cot::db::migrations::DefaultValue::String(#value)
+            },
+            DefaultSpec::Expr(expr) => quote! {
+                ::cot::db::migrations::DefaultValue::Expr(#expr)
+            },
+        }
+    }
+}
+
+/// The index is represented together with the full name of the table it's
+/// created on, as its name is derived from it.
+impl Repr for (&str, &IndexSpec) {
+    fn repr(&self) -> TokenStream {
+        let (table_name, index) = *self;
+        let name = index.name(table_name);
+        let columns = &index.columns;
+        quote! {
+            ::cot::db::migrations::Index::new(
+                ::cot::db::Identifier::new(#name),
+                &[#(::cot::db::Identifier::new(#columns),)*],
+            )
+        }
+    }
+}
+
+/// The constraint is represented together with the full name of the table
+/// it's added to, as its name is derived from it.
+impl Repr for (&str, &ConstraintSpec) {
+    fn repr(&self) -> TokenStream {
+        let (table_name, constraint) = *self;
+        let name = constraint.name(table_name);
+        match constraint {
+            ConstraintSpec::Unique { columns } => quote! {
+                ::cot::db::migrations::Constraint::unique(
+                    ::cot::db::Identifier::new(#name),
+                    &[#(::cot::db::Identifier::new(#columns),)*],
+                )
+            },
+            ConstraintSpec::Check { expr, .. } => quote! {
+                ::cot::db::migrations::Constraint::check(
+                    ::cot::db::Identifier::new(#name),
+                    #expr,
+                )
+            },
+            ConstraintSpec::ForeignKey {
+                columns,
+                to_model,
+                on_delete,
+                on_update,
+            } => quote! {
+                ::cot::db::migrations::Constraint::foreign_key(
+                    ::cot::db::Identifier::new(#name),
+                    &[#(::cot::db::Identifier::new(#columns),)*],
+                    <#to_model as ::cot::db::Model>::TABLE_NAME,
+                    <#to_model as ::cot::db::Model>::PRIMARY_KEY_NAMES,
+                    #on_delete,
+                    #on_update,
+                )
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Migration {
     app_name: String,
@@ -1434,6 +2945,44 @@ pub enum DynOperation {
         // boxed to reduce size difference between enum variations
         field: Box<Field>,
     },
+    AlterField {
+        table_name: String,
+        model_ty: syn::Type,
+        // boxed to reduce size difference between enum variations
+        old_field: Box<Field>,
+        new_field: Box<Field>,
+    },
+    RenameField {
+        table_name: String,
+        model_ty: syn::Type,
+        old_name: String,
+        new_name: String,
+    },
+    RenameModel {
+        model_ty: syn::Type,
+        old_table_name: String,
+        new_table_name: String,
+    },
+    AddIndex {
+        table_name: String,
+        model_ty: syn::Type,
+        index: IndexSpec,
+    },
+    RemoveIndex {
+        table_name: String,
+        model_ty: syn::Type,
+        index: IndexSpec,
+    },
+    AddConstraint {
+        table_name: String,
+        model_ty: syn::Type,
+        constraint: ConstraintSpec,
+    },
+    RemoveConstraint {
+        table_name: String,
+        model_ty: syn::Type,
+        constraint: ConstraintSpec,
+    },
     RemoveModel {
         table_name: String,
         model_ty: syn::Type,
@@ -1456,6 +3005,7 @@ fn foreign_key_for_field(field: &Field) -> Option<syn::Type> {
 }
 
 impl Repr for DynOperation {
+    #[expect(clippy::too_many_lines)] // it's one arm per operation
     fn repr(&self) -> TokenStream {
         match self {
             Self::CreateModel {
@@ -1493,33 +3043,123 @@ impl Repr for DynOperation {
                         .build()
                 }
             }
-            Self::RemoveModel {
-                table_name, fields, ..
+            Self::AlterField {
+                table_name,
+                old_field,
+                new_field,
+                ..
             } => {
-                let fields = fields.iter().map(Repr::repr).collect::<Vec<_>>();
+                let old_field = old_field.repr();
+                let new_field = new_field.repr();
                 quote! {
-                    ::cot::db::migrations::Operation::remove_model()
+                    ::cot::db::migrations::Operation::alter_field()
                         .table_name(::cot::db::Identifier::new(#table_name))
-                        .fields(&[
-                            #(#fields,)*
-                        ])
+                        .old_field(#old_field)
+                        .new_field(#new_field)
                         .build()
                 }
             }
-        }
-    }
-}
-
-#[derive(Debug)]
-struct ParsingError {
-    message: String,
-    path: PathBuf,
-    location: String,
-    source: Option<String>,
-}
-
-impl ParsingError {
-    fn from_darling(message: &str, path: PathBuf, error: &darling::Error) -> Self {
+            Self::RenameField {
+                table_name,
+                old_name,
+                new_name,
+                ..
+            } => {
+                quote! {
+                    ::cot::db::migrations::Operation::rename_field()
+                        .table_name(::cot::db::Identifier::new(#table_name))
+                        .old_name(::cot::db::Identifier::new(#old_name))
+                        .new_name(::cot::db::Identifier::new(#new_name))
+                        .build()
+                }
+            }
+            Self::RenameModel {
+                old_table_name,
+                new_table_name,
+                ..
+            } => {
+                quote! {
+                    ::cot::db::migrations::Operation::rename_model()
+                        .old_table_name(::cot::db::Identifier::new(#old_table_name))
+                        .new_table_name(::cot::db::Identifier::new(#new_table_name))
+                        .build()
+                }
+            }
+            Self::AddIndex {
+                table_name, index, ..
+            } => {
+                let index = (table_name.as_str(), index).repr();
+                quote! {
+                    ::cot::db::migrations::Operation::add_index()
+                        .table_name(::cot::db::Identifier::new(#table_name))
+                        .index(#index)
+                        .build()
+                }
+            }
+            Self::RemoveIndex {
+                table_name, index, ..
+            } => {
+                let index = (table_name.as_str(), index).repr();
+                quote! {
+                    ::cot::db::migrations::Operation::remove_index()
+                        .table_name(::cot::db::Identifier::new(#table_name))
+                        .index(#index)
+                        .build()
+                }
+            }
+            Self::AddConstraint {
+                table_name,
+                constraint,
+                ..
+            } => {
+                let constraint = (table_name.as_str(), constraint).repr();
+                quote! {
+                    ::cot::db::migrations::Operation::add_constraint()
+                        .table_name(::cot::db::Identifier::new(#table_name))
+                        .constraint(#constraint)
+                        .build()
+                }
+            }
+            Self::RemoveConstraint {
+                table_name,
+                constraint,
+                ..
+            } => {
+                let constraint = (table_name.as_str(), constraint).repr();
+                quote! {
+                    ::cot::db::migrations::Operation::remove_constraint()
+                        .table_name(::cot::db::Identifier::new(#table_name))
+                        .constraint(#constraint)
+                        .build()
+                }
+            }
+            Self::RemoveModel {
+                table_name, fields, ..
+            } => {
+                let fields = fields.iter().map(Repr::repr).collect::<Vec<_>>();
+                quote! {
+                    ::cot::db::migrations::Operation::remove_model()
+                        .table_name(::cot::db::Identifier::new(#table_name))
+                        .fields(&[
+                            #(#fields,)*
+                        ])
+                        .build()
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ParsingError {
+    message: String,
+    path: PathBuf,
+    location: String,
+    source: Option<String>,
+}
+
+impl ParsingError {
+    fn from_darling(message: &str, path: PathBuf, error: &darling::Error) -> Self {
         let message = format!("{message}: {error}");
         let span = error.span();
         let location = format!("{}:{}", span.start().line, span.start().column);
@@ -1548,6 +3188,8 @@ impl Error for ParsingError {}
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use cot_codegen::model::{ForeignKeyOnDeletePolicy, ForeignKeyOnUpdatePolicy, ForeignKeySpec};
 
     use super::*;
@@ -1570,10 +3212,12 @@ mod tests {
                         auto_value: false,
                         primary_key: false,
                         unique: false,
+                        default: None,
                         foreign_key: Some(ForeignKeySpec {
                             to_model: parse_quote!(crate::OtherModel),
                             on_delete: Some($on_delete),
                             on_update: Some($on_update),
+                            columns: Vec::new(),
                         }),
                     }),
                 };
@@ -1695,10 +3339,12 @@ mod tests {
                     auto_value: false,
                     primary_key: false,
                     unique: false,
+                    default: None,
                     foreign_key: Some(ForeignKeySpec {
                         to_model: parse_quote!(Table1),
                         on_delete: Some(ForeignKeyOnDeletePolicy::Cascade),
                         on_update: Some(ForeignKeyOnUpdatePolicy::Cascade),
+                        columns: Vec::new(),
                     }),
                 }),
             },
@@ -1737,10 +3383,12 @@ mod tests {
                     auto_value: false,
                     primary_key: false,
                     unique: false,
+                    default: None,
                     foreign_key: Some(ForeignKeySpec {
                         to_model: parse_quote!(Table2),
                         on_delete: Some(ForeignKeyOnDeletePolicy::Cascade),
                         on_update: Some(ForeignKeyOnUpdatePolicy::Cascade),
+                        columns: Vec::new(),
                     }),
                 }],
             },
@@ -1754,10 +3402,12 @@ mod tests {
                     auto_value: false,
                     primary_key: false,
                     unique: false,
+                    default: None,
                     foreign_key: Some(ForeignKeySpec {
                         to_model: parse_quote!(Table1),
                         on_delete: Some(ForeignKeyOnDeletePolicy::Cascade),
                         on_update: Some(ForeignKeyOnUpdatePolicy::Cascade),
+                        columns: Vec::new(),
                     }),
                 }],
             },
@@ -1803,10 +3453,12 @@ mod tests {
                 auto_value: false,
                 primary_key: false,
                 unique: false,
+                default: None,
                 foreign_key: Some(ForeignKeySpec {
                     to_model: parse_quote!(Table2),
                     on_delete: Some(ForeignKeyOnDeletePolicy::Cascade),
                     on_update: Some(ForeignKeyOnUpdatePolicy::Cascade),
+                    columns: Vec::new(),
                 }),
             }],
         };
@@ -1860,10 +3512,12 @@ mod tests {
                 auto_value: false,
                 primary_key: false,
                 unique: false,
+                default: None,
                 foreign_key: Some(ForeignKeySpec {
                     to_model: parse_quote!(crate::Table2),
                     on_delete: Some(ForeignKeyOnDeletePolicy::Cascade),
                     on_update: Some(ForeignKeyOnUpdatePolicy::Cascade),
+                    columns: Vec::new(),
                 }),
             }],
         }];
@@ -1891,10 +3545,12 @@ mod tests {
                     auto_value: false,
                     primary_key: false,
                     unique: false,
+                    default: None,
                     foreign_key: Some(ForeignKeySpec {
                         to_model: parse_quote!(my_crate::Table2),
                         on_delete: Some(ForeignKeyOnDeletePolicy::Cascade),
                         on_update: Some(ForeignKeyOnUpdatePolicy::Cascade),
+                        columns: Vec::new(),
                     }),
                 }],
             },
@@ -1908,10 +3564,12 @@ mod tests {
                     auto_value: false,
                     primary_key: false,
                     unique: false,
+                    default: None,
                     foreign_key: Some(ForeignKeySpec {
                         to_model: parse_quote!(crate::Table4),
                         on_delete: Some(ForeignKeyOnDeletePolicy::Cascade),
                         on_update: Some(ForeignKeyOnUpdatePolicy::Cascade),
+                        columns: Vec::new(),
                     }),
                 }],
             },
@@ -1943,15 +3601,17 @@ mod tests {
                 resolved_ty: parse_quote!(TestModel),
                 model_type: ModelType::default(),
                 table_name: "test_model".to_string(),
-                pk_field: Field {
+                renamed_from: None,
+                pk_fields: vec![Field {
                     name: format_ident!("id"),
                     column_name: "id".to_string(),
                     ty: parse_quote!(i32),
                     auto_value: true,
                     primary_key: true,
                     unique: false,
+                    default: None,
                     foreign_key: None,
-                },
+                }],
                 fields: vec![Field {
                     name: format_ident!("field1"),
                     column_name: "field1".to_string(),
@@ -1959,8 +3619,14 @@ mod tests {
                     auto_value: false,
                     primary_key: false,
                     unique: false,
+                    default: None,
                     foreign_key: None,
                 }],
+                many_to_many_fields: Vec::new(),
+                reverse_relations: Vec::new(),
+                renamed_fields: BTreeMap::new(),
+                indexes: Vec::new(),
+                constraints: Vec::new(),
             },
         }
     }
@@ -1981,15 +3647,17 @@ mod tests {
                 resolved_ty: parse_quote!(TestModel),
                 model_type: ModelType::default(),
                 table_name: "test_model".to_string(),
-                pk_field: Field {
+                renamed_from: None,
+                pk_fields: vec![Field {
                     name: format_ident!("id"),
                     column_name: "id".to_string(),
                     ty: parse_quote!(i32),
                     auto_value: true,
                     primary_key: true,
                     unique: false,
+                    default: None,
                     foreign_key: None,
-                },
+                }],
                 fields: vec![
                     Field {
                         name: format_ident!("field1"),
@@ -1998,6 +3666,7 @@ mod tests {
                         auto_value: false,
                         primary_key: false,
                         unique: false,
+                        default: None,
                         foreign_key: None,
                     },
                     Field {
@@ -2007,9 +3676,15 @@ mod tests {
                         auto_value: false,
                         primary_key: false,
                         unique: false,
+                        default: None,
                         foreign_key: None,
                     },
                 ],
+                many_to_many_fields: Vec::new(),
+                reverse_relations: Vec::new(),
+                renamed_fields: BTreeMap::new(),
+                indexes: Vec::new(),
+                constraints: Vec::new(),
             },
         }
     }
@@ -2024,6 +3699,7 @@ mod tests {
             auto_value: false,
             primary_key: false,
             unique: false,
+            default: None,
             foreign_key: None,
         };
 
@@ -2084,6 +3760,110 @@ mod tests {
         }
     }
 
+    fn with_tags_field(mut model: ModelInSource) -> ModelInSource {
+        model.model.many_to_many_fields.push(ManyToManyField {
+            name: format_ident!("tags"),
+            column_name: "tags".to_string(),
+            ty: parse_quote!(ManyToMany<Tag>),
+            to_model: parse_quote!(Tag),
+        });
+        model
+    }
+
+    #[test]
+    fn generate_operations_with_new_many_to_many_model() {
+        let app_models = vec![with_tags_field(get_test_model())];
+
+        let (_modified_models, operations) =
+            MigrationGenerator::generate_operations(&app_models, &Vec::new());
+
+        assert_eq!(operations.len(), 3);
+        match &operations[1] {
+            DynOperation::CreateModel {
+                table_name,
+                model_ty,
+                fields,
+            } => {
+                assert_eq!(table_name, "test_model_tags");
+                assert_eq!(model_ty, &parse_quote!((TestModel, Tag)));
+                let column_names: Vec<_> = fields.iter().map(|f| f.column_name.as_str()).collect();
+                assert_eq!(column_names, ["id", "test_model", "tag"]);
+                assert!(fields[1].foreign_key.is_some());
+                assert!(fields[2].foreign_key.is_some());
+            }
+            _ => panic!("Expected CreateModel operation"),
+        }
+        match &operations[2] {
+            DynOperation::AddConstraint {
+                table_name,
+                constraint,
+                ..
+            } => {
+                assert_eq!(table_name, "test_model_tags");
+                assert_eq!(
+                    constraint,
+                    &ConstraintSpec::Unique {
+                        columns: vec!["test_model".to_string(), "tag".to_string()],
+                    }
+                );
+            }
+            _ => panic!("Expected AddConstraint operation"),
+        }
+    }
+
+    #[test]
+    fn generate_operations_with_added_many_to_many_field() {
+        let app_models = vec![with_tags_field(get_test_model())];
+        let migration_models = vec![get_test_model()];
+
+        let (modified_models, operations) =
+            MigrationGenerator::generate_operations(&app_models, &migration_models);
+
+        assert_eq!(modified_models.len(), 1);
+        assert_eq!(operations.len(), 2);
+        match (&operations[0], &operations[1]) {
+            (
+                DynOperation::CreateModel { table_name, .. },
+                DynOperation::AddConstraint {
+                    table_name: constraint_table_name,
+                    ..
+                },
+            ) => {
+                assert_eq!(table_name, "test_model_tags");
+                assert_eq!(constraint_table_name, "test_model_tags");
+            }
+            _ => panic!("Expected CreateModel and AddConstraint operations"),
+        }
+    }
+
+    #[test]
+    fn generate_operations_with_removed_many_to_many_model() {
+        let migration_models = vec![with_tags_field(get_test_model())];
+
+        let (_modified_models, operations) =
+            MigrationGenerator::generate_operations(&Vec::new(), &migration_models);
+
+        assert_eq!(operations.len(), 3);
+        match (&operations[0], &operations[1], &operations[2]) {
+            (
+                DynOperation::RemoveConstraint {
+                    table_name: constraint_table_name,
+                    ..
+                },
+                DynOperation::RemoveModel {
+                    table_name: join_table_name,
+                    ..
+                },
+                DynOperation::RemoveModel { table_name, .. },
+            ) => {
+                assert_eq!(constraint_table_name, "test_model_tags");
+                assert_eq!(join_table_name, "test_model_tags");
+                assert_eq!(table_name, "test_model");
+            }
+            _ => panic!("Expected RemoveConstraint and two RemoveModel operations"),
+        }
+    }
+
     #[test]
     fn make_remove_model_operation() {
         let migration_model = get_test_model();
@@ -2121,6 +3901,194 @@ mod tests {
             _ => panic!("Expected DynOperation::RemoveField"),
         }
     }
+    #[test]
+    fn make_alter_field_operation() {
+        let migration_model = get_test_model();
+        let old_field = &migration_model.model.fields[0];
+        let mut app_model = migration_model.clone();
+        app_model.model.fields[0].ty = parse_quote!(Option<String>);
+        app_model.model.fields[0].unique = true;
+        let new_field = &app_model.model.fields[0];
+
+        let operation = MigrationOperationGenerator::make_alter_field_operation(
+            &app_model,
+            new_field,
+            &migration_model,
+            old_field,
+        );
+
+        match &operation {
+            Some(DynOperation::AlterField {
+                table_name,
+                model_ty,
+                old_field,
+                new_field,
+            }) => {
+                assert_eq!(table_name, "test_model");
+                assert_eq!(model_ty, &parse_quote!(TestModel));
+                assert_eq!(old_field.ty, parse_quote!(String));
+                assert!(!old_field.unique);
+                assert_eq!(new_field.ty, parse_quote!(Option<String>));
+                assert!(new_field.unique);
+            }
+            _ => panic!("Expected DynOperation::AlterField"),
+        }
+    }
+
+    #[test]
+    fn make_alter_field_operation_unchanged() {
+        let migration_model = get_test_model();
+        let field = &migration_model.model.fields[0];
+
+        let operation = MigrationOperationGenerator::make_alter_field_operation(
+            &migration_model,
+            field,
+            &migration_model,
+            field,
+        );
+
+        assert!(operation.is_none());
+    }
+
+    #[test]
+    fn generate_operations_with_renamed_field() {
+        let migration_model = get_test_model();
+        let mut app_model = migration_model.clone();
+        app_model.model.fields[0].name = format_ident!("title");
+        app_model.model.fields[0].column_name = "title".to_string();
+        app_model.model.renamed_fields =
+            BTreeMap::from([("title".to_string(), "field1".to_string())]);
+
+        let (modified_models, operations) =
+            MigrationGenerator::generate_operations(&vec![app_model], &vec![migration_model]);
+
+        assert_eq!(modified_models.len(), 1);
+        assert_eq!(
+            operations,
+            vec![DynOperation::RenameField {
+                table_name: "test_model".to_string(),
+                model_ty: parse_quote!(TestModel),
+                old_name: "field1".to_string(),
+                new_name: "title".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn generate_operations_with_renamed_and_altered_field() {
+        let migration_model = get_test_model();
+        let mut app_model = migration_model.clone();
+        app_model.model.fields[0].name = format_ident!("title");
+        app_model.model.fields[0].column_name = "title".to_string();
+        app_model.model.fields[0].unique = true;
+        app_model.model.renamed_fields =
+            BTreeMap::from([("title".to_string(), "field1".to_string())]);
+
+        let (_modified_models, mut operations) =
+            MigrationGenerator::generate_operations(&vec![app_model], &vec![migration_model]);
+        operations.reverse();
+        GeneratedMigration::toposort_operations(&mut operations);
+
+        assert_eq!(operations.len(), 2);
+        assert!(matches!(&operations[0], DynOperation::RenameField { .. }));
+        match &operations[1] {
+            DynOperation::AlterField {
+                old_field,
+                new_field,
+                ..
+            } => {
+                assert_eq!(old_field.column_name, "title");
+                assert!(!old_field.unique);
+                assert_eq!(new_field.column_name, "title");
+                assert!(new_field.unique);
+            }
+            _ => panic!("Expected DynOperation::AlterField"),
+        }
+    }
+
+    #[test]
+    fn generate_operations_with_renamed_field_without_hint() {
+        let migration_model = get_test_model();
+        let mut app_model = migration_model.clone();
+        app_model.model.fields[0].name = format_ident!("title");
+        app_model.model.fields[0].column_name = "title".to_string();
+
+        let (_modified_models, operations) =
+            MigrationGenerator::generate_operations(&vec![app_model], &vec![migration_model]);
+
+        assert_eq!(operations.len(), 2);
+        assert!(matches!(&operations[0], DynOperation::RemoveField { .. }));
+        assert!(matches!(&operations[1], DynOperation::AddField { .. }));
+    }
+
+    #[test]
+    fn generate_operations_with_renamed_model() {
+        let migration_model = get_test_model();
+        let mut app_model = migration_model.clone();
+        app_model.model.name = format_ident!("NewModel");
+        app_model.model.original_name = "NewModel".to_string();
+        app_model.model.resolved_ty = parse_quote!(NewModel);
+        app_model.model.table_name = "new_model".to_string();
+        app_model.model.renamed_from = Some("TestModel".to_string());
+        app_model.model.fields.push(Field {
+            name: format_ident!("field2"),
+            column_name: "field2".to_string(),
+            ty: parse_quote!(i32),
+            auto_value: false,
+            primary_key: false,
+            unique: false,
+            default: None,
+            foreign_key: None,
+        });
+
+        let (modified_models, operations) =
+            MigrationGenerator::generate_operations(&vec![app_model], &vec![migration_model]);
+
+        assert_eq!(modified_models.len(), 1);
+        assert_eq!(operations.len(), 2);
+        assert_eq!(
+            operations[0],
+            DynOperation::RenameModel {
+                model_ty: parse_quote!(NewModel),
+                old_table_name: "test_model".to_string(),
+                new_table_name: "new_model".to_string(),
+            }
+        );
+        match &operations[1] {
+            DynOperation::AddField {
+                table_name,
+                model_ty,
+                field,
+            } => {
+                assert_eq!(table_name, "new_model");
+                assert_eq!(model_ty, &parse_quote!(NewModel));
+                assert_eq!(field.column_name, "field2");
+            }
+            _ => panic!("Expected DynOperation::AddField"),
+        }
+    }
+
+    #[test]
+    fn toposort_operations_after_model_rename() {
+        let mut operations = vec![
+            DynOperation::RemoveField {
+                table_name: "new_model".to_string(),
+                model_ty: parse_quote!(NewModel),
+                field: Box::new(get_test_model().model.fields[0].clone()),
+            },
+            DynOperation::RenameModel {
+                model_ty: parse_quote!(NewModel),
+                old_table_name: "test_model".to_string(),
+                new_table_name: "new_model".to_string(),
+            },
+        ];
+
+        GeneratedMigration::toposort_operations(&mut operations);
+
+        assert!(matches!(&operations[0], DynOperation::RenameModel { .. }));
+        assert!(matches!(&operations[1], DynOperation::RemoveField { .. }));
+    }
+
     #[test]
     fn generate_operations_with_removed_model() {
         let app_models = vec![];
@@ -2161,6 +4129,231 @@ mod tests {
 
         assert!(has_add_field, "Expected an AddField operation for 'field2'");
     }
+    #[test]
+    fn generate_operations_with_new_model_with_indexes() {
+        let mut app_model = get_test_model();
+        app_model.model.indexes = vec![IndexSpec {
+            columns: vec!["field1".to_string()],
+        }];
+        app_model.model.constraints = vec![ConstraintSpec::Check {
+            name: "field1_not_empty".to_string(),
+            expr: "field1 <> ''".to_string(),
+        }];
+
+        let (_modified_models, operations) =
+            MigrationGenerator::generate_operations(&vec![app_model], &vec![]);
+
+        assert_eq!(operations.len(), 3);
+        assert!(matches!(&operations[0], DynOperation::CreateModel { .. }));
+        assert!(matches!(&operations[1], DynOperation::AddIndex { .. }));
+        assert!(matches!(&operations[2], DynOperation::AddConstraint { .. }));
+    }
+
+    #[test]
+    fn generate_operations_with_changed_indexes() {
+        let mut app_model = get_test_model();
+        app_model.model.constraints = vec![ConstraintSpec::Unique {
+            columns: vec!["id".to_string(), "field1".to_string()],
+        }];
+        let mut migration_model = get_test_model();
+        migration_model.model.indexes = vec![IndexSpec {
+            columns: vec!["field1".to_string()],
+        }];
+
+        let (modified_models, operations) =
+            MigrationGenerator::generate_operations(&vec![app_model], &vec![migration_model]);
+
+        assert_eq!(modified_models.len(), 1);
+        assert_eq!(operations.len(), 2);
+        assert!(matches!(
+            &operations[0],
+            DynOperation::RemoveIndex { index, .. } if index.columns == ["field1"]
+        ));
+        assert!(matches!(
+            &operations[1],
+            DynOperation::AddConstraint {
+                constraint: ConstraintSpec::Unique { columns },
+                ..
+            } if columns == &["id", "field1"]
+        ));
+    }
+
+    #[test]
+    fn generate_operations_with_removed_indexed_field() {
+        let app_model = get_test_model();
+        let mut migration_model = get_bigger_test_model();
+        migration_model.model.indexes = vec![IndexSpec {
+            columns: vec!["field2".to_string()],
+        }];
+
+        let (_modified_models, operations) =
+            MigrationGenerator::generate_operations(&vec![app_model], &vec![migration_model]);
+
+        // the index has to be removed before the column it's created on
+        assert_eq!(operations.len(), 2);
+        assert!(matches!(&operations[0], DynOperation::RemoveIndex { .. }));
+        assert!(matches!(&operations[1], DynOperation::RemoveField { .. }));
+    }
+
+    #[test]
+    fn model_to_migration_model_keeps_indexes_and_constraints() {
+        let mut model = get_test_model();
+        model.model_item.attrs.push(parse_quote! {
+            #[model(
+                table_name = "test_model",
+                index(field1),
+                unique_together(id, field1),
+                check(name = "field1_not_empty", expr = "field1 <> ''")
+            )]
+        });
+
+        let tokens = MigrationGenerator::model_to_migration_model(&model);
+        let item: syn::ItemStruct = syn::parse2(tokens).unwrap();
+
+        let expected: syn::Attribute = parse_quote! {
+            #[::cot::db::model(
+                model_type = "migration",
+                index(field1),
+                unique_together(id, field1),
+                check(name = "field1_not_empty", expr = "field1 <> ''")
+            )]
+        };
+        assert_eq!(item.attrs[1], expected);
+    }
+
+    #[test]
+    fn repr_for_add_index_operation() {
+        let op = DynOperation::AddIndex {
+            table_name: "test_table".to_string(),
+            model_ty: parse_quote!(TestModel),
+            index: IndexSpec {
+                columns: vec!["first_name".to_string(), "last_name".to_string()],
+            },
+        };
+
+        let tokens_str = remove_whitespace(&op.repr().to_string());
+
+        assert_eq!(
+            tokens_str,
+            remove_whitespace(
+                &"::cot::db::migrations::Operation::add_index()\
+                .table_name(::cot::db::Identifier::new(\"test_table\"))\
+                .index(::cot::db::migrations::Index::new(\
+                    ::cot::db::Identifier::new(\"test_table_first_name_last_name_idx\"),\
+                    &[\
+                        ::cot::db::Identifier::new(\"first_name\"),\
+                        ::cot::db::Identifier::new(\"last_name\"),\
+                    ],\
+                ))\
+                .build()"
+            )
+        );
+    }
+
+    #[test]
+    fn repr_for_remove_constraint_operation() {
+        let op = DynOperation::RemoveConstraint {
+            table_name: "test_table".to_string(),
+            model_ty: parse_quote!(TestModel),
+            constraint: ConstraintSpec::Check {
+                name: "price_positive".to_string(),
+                expr: "price > 0".to_string(),
+            },
+        };
+
+        let tokens_str = remove_whitespace(&op.repr().to_string());
+
+        assert_eq!(
+            tokens_str,
+            remove_whitespace(
+                &"::cot::db::migrations::Operation::remove_constraint()\
+                .table_name(::cot::db::Identifier::new(\"test_table\"))\
+                .constraint(::cot::db::migrations::Constraint::check(\
+                    ::cot::db::Identifier::new(\"test_table_price_positive\"),\
+                    \"price > 0\",\
+                ))\
+                .build()"
+            )
+        );
+    }
+
+    #[test]
+    fn model_in_source_expands_composite_foreign_keys() {
+        let item: syn::ItemStruct = parse_quote! {
+            struct Grade {
+                #[model(primary_key)]
+                id: Auto<i32>,
+                #[model(foreign_key(columns(course_code, course_year)))]
+                course: ForeignKey<Course>,
+            }
+        };
+
+        let model_in_source = ModelInSource::from_item(
+            "app",
+            item,
+            &ModelArgs::default(),
+            &SymbolResolver::new(vec![]),
+        )
+        .unwrap();
+
+        let fields = &model_in_source.model.fields;
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[1].column_name, "course_code");
+        assert_eq!(fields[2].column_name, "course_year");
+        assert_eq!(fields[2].foreign_key, None);
+        assert_eq!(
+            remove_whitespace(&fields[2].ty.to_token_stream().to_string()),
+            remove_whitespace(
+                &"<<Course as ::cot::db::Model>::PrimaryKey \
+                as ::cot::db::CompositePrimaryKeyColumn<1usize>>::Type"
+            )
+        );
+        assert!(matches!(
+            &model_in_source.model.constraints[..],
+            [ConstraintSpec::ForeignKey { .. }]
+        ));
+    }
+
+    #[test]
+    fn repr_for_add_foreign_key_constraint_operation() {
+        let op = DynOperation::AddConstraint {
+            table_name: "app__grade".to_string(),
+            model_ty: parse_quote!(Grade),
+            constraint: ConstraintSpec::ForeignKey {
+                columns: vec!["course_code".to_string(), "course_year".to_string()],
+                to_model: Box::new(parse_quote!(Course)),
+                on_delete: ForeignKeyOnDeletePolicy::Restrict,
+                on_update: ForeignKeyOnUpdatePolicy::Cascade,
+            },
+        };
+
+        let tokens_str = remove_whitespace(&op.repr().to_string());
+
+        assert_eq!(
+            tokens_str,
+            remove_whitespace(
+                &"::cot::db::migrations::Operation::add_constraint()\
+                .table_name(::cot::db::Identifier::new(\"app__grade\"))\
+                .constraint(::cot::db::migrations::Constraint::foreign_key(\
+                    ::cot::db::Identifier::new(\"app__grade_course_code_course_year_fk\"),\
+                    &[\
+                        ::cot::db::Identifier::new(\"course_code\"),\
+                        ::cot::db::Identifier::new(\"course_year\"),\
+                    ],\
+                    <Course as ::cot::db::Model>::TABLE_NAME,\
+                    <Course as ::cot::db::Model>::PRIMARY_KEY_NAMES,\
+                    ::cot::db::ForeignKeyOnDeletePolicy::Restrict,\
+                    ::cot::db::ForeignKeyOnUpdatePolicy::Cascade,\
+                ))\
+                .build()"
+            )
+        );
+        assert_eq!(
+            GeneratedMigration::get_ops_adding_foreign_keys(&[op]),
+            vec![(0, parse_quote!(Grade)), (0, parse_quote!(Course))]
+        );
+    }
+
     #[test]
     fn repr_for_remove_field_operation() {
         let op = DynOperation::RemoveField {
@@ -2173,6 +4366,7 @@ mod tests {
                 auto_value: false,
                 primary_key: false,
                 unique: false,
+                default: None,
                 foreign_key: None,
             }),
         };
@@ -2198,6 +4392,130 @@ mod tests {
         );
     }
 
+    #[test]
+    fn repr_for_alter_field_operation() {
+        let field = Field {
+            name: format_ident!("test_field"),
+            column_name: "test_field".to_string(),
+            ty: parse_quote!(i32),
+            auto_value: false,
+            primary_key: false,
+            unique: false,
+            default: None,
+            foreign_key: None,
+        };
+        let op = DynOperation::AlterField {
+            table_name: "test_table".to_string(),
+            model_ty: parse_quote!(TestModel),
+            old_field: Box::new(field.clone()),
+            new_field: Box::new(Field {
+                ty: parse_quote!(i64),
+                ..field
+            }),
+        };
+
+        let tokens_str = remove_whitespace(&op.repr().to_string());
+
+        assert!(
+            tokens_str.contains("::cot::db::migrations::Operation::alter_field()"),
+            "Should call alter_field() but got: {tokens_str}"
+        );
+        assert!(
+            tokens_str.contains(".old_field(::cot::db::migrations::Field::new(::cot::db::Identifier::new(\"test_field\"),<i32as::cot::db::DatabaseField>::TYPE,)"),
+            "Should call old_field() with the old type but got: {tokens_str}"
+        );
+        assert!(
+            tokens_str.contains(".new_field(::cot::db::migrations::Field::new(::cot::db::Identifier::new(\"test_field\"),<i64as::cot::db::DatabaseField>::TYPE,)"),
+            "Should call new_field() with the new type but got: {tokens_str}"
+        );
+        assert!(
+            tokens_str.contains(".build()"),
+            "Should call build() but got: {tokens_str}"
+        );
+    }
+
+    #[test]
+    fn repr_for_field_with_default() {
+        let field = Field {
+            name: format_ident!("created_at"),
+            column_name: "created_at".to_string(),
+            ty: parse_quote!(Auto<NaiveDateTime>),
+            auto_value: false,
+            primary_key: false,
+            unique: false,
+            default: Some(DefaultSpec::Expr("CURRENT_TIMESTAMP".to_string())),
+            foreign_key: None,
+        };
+
+        let tokens_str = remove_whitespace(&field.repr().to_string());
+
+        assert!(
+            tokens_str.ends_with(
+                ".default(::cot::db::migrations::DefaultValue::Expr(\"CURRENT_TIMESTAMP\"))"
+            ),
+            "Should call default() but got: {tokens_str}"
+        );
+        assert!(
+            !tokens_str.contains(".auto()"),
+            "Shouldn't call auto() but got: {tokens_str}"
+        );
+
+        let default = DefaultSpec::Int(-5).repr().to_string();
+        assert_eq!(
+            remove_whitespace(&default),
+            "::cot::db::migrations::DefaultValue::Int(-5i64)"
+        );
+        let default = DefaultSpec::Float("0.5".to_string()).repr().to_string();
+        assert_eq!(
+            remove_whitespace(&default),
+            "::cot::db::migrations::DefaultValue::Float(0.5f64)"
+        );
+    }
+
+    #[test]
+    fn repr_for_rename_field_operation() {
+        let op = DynOperation::RenameField {
+            table_name: "test_table".to_string(),
+            model_ty: parse_quote!(TestModel),
+            old_name: "old_field".to_string(),
+            new_name: "new_field".to_string(),
+        };
+
+        let tokens_str = remove_whitespace(&op.repr().to_string());
+
+        assert_eq!(
+            tokens_str,
+            remove_whitespace(
+                &"::cot::db::migrations::Operation::rename_field()\
+                .table_name(::cot::db::Identifier::new(\"test_table\"))\
+                .old_name(::cot::db::Identifier::new(\"old_field\"))\
+                .new_name(::cot::db::Identifier::new(\"new_field\"))\
+                .build()"
+            )
+        );
+    }
+
+    #[test]
+    fn repr_for_rename_model_operation() {
+        let op = DynOperation::RenameModel {
+            model_ty: parse_quote!(NewModel),
+            old_table_name: "old_table".to_string(),
+            new_table_name: "new_table".to_string(),
+        };
+
+        let tokens_str = remove_whitespace(&op.repr().to_string());
+
+        assert_eq!(
+            tokens_str,
+            remove_whitespace(
+                &"::cot::db::migrations::Operation::rename_model()\
+                .old_table_name(::cot::db::Identifier::new(\"old_table\"))\
+                .new_table_name(::cot::db::Identifier::new(\"new_table\"))\
+                .build()"
+            )
+        );
+    }
+
     repr_for_foreign_key_operation_test!(
         repr_for_foreign_key_operation_cascade_cascade,
         ForeignKeyOnDeletePolicy::Cascade,
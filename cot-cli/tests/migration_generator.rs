@@ -7,6 +7,7 @@ use cot_cli::migration_generator::{
     MigrationGeneratorOptions, SourceFile,
 };
 use cot_cli::test_utils;
+use cot_codegen::model::ConstraintSpec;
 use syn::parse_quote;
 
 pub const EXAMPLE_DATABASE_MODEL: &str = include_str!("resources/example_database_model.rs");
@@ -160,6 +161,242 @@ fn create_models_foreign_key_two_migrations() {
     assert_eq!(table_name, "cot__child");
 }
 
+#[test]
+fn alter_field_two_migrations() {
+    let generator = test_generator();
+
+    let src = include_str!("migration_generator/alter_field_two_migrations/step_1.rs");
+    let source_files = vec![SourceFile::parse(PathBuf::from("main.rs"), src).unwrap()];
+    let migration_file = generator
+        .generate_migrations_as_source_from_files(source_files)
+        .unwrap()
+        .unwrap();
+
+    let src = include_str!("migration_generator/alter_field_two_migrations/step_2.rs");
+    let source_files = vec![
+        SourceFile::parse(PathBuf::from("main.rs"), src).unwrap(),
+        SourceFile::parse(PathBuf::from(&migration_file.name), &migration_file.content).unwrap(),
+    ];
+    let migration = generator
+        .generate_migrations_as_generated_from_files(source_files)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(migration.operations.len(), 2);
+
+    let (table_name, old_field, new_field) = unwrap_alter_field(&migration.operations[0]);
+    assert_eq!(table_name, "cot__item");
+    assert_eq!(old_field.name, "name");
+    assert_eq!(old_field.ty, parse_quote!(String));
+    assert!(!old_field.unique);
+    assert_eq!(new_field.ty, parse_quote!(Option<String>));
+    assert!(new_field.unique);
+
+    let (table_name, old_field, new_field) = unwrap_alter_field(&migration.operations[1]);
+    assert_eq!(table_name, "cot__item");
+    assert_eq!(old_field.name, "count");
+    assert_eq!(old_field.ty, parse_quote!(i32));
+    assert_eq!(new_field.ty, parse_quote!(i64));
+}
+
+#[test]
+fn rename_two_migrations() {
+    let generator = test_generator();
+
+    let src = include_str!("migration_generator/rename_two_migrations/step_1.rs");
+    let source_files = vec![SourceFile::parse(PathBuf::from("main.rs"), src).unwrap()];
+    let migration_file = generator
+        .generate_migrations_as_source_from_files(source_files)
+        .unwrap()
+        .unwrap();
+
+    let src = include_str!("migration_generator/rename_two_migrations/step_2.rs");
+    let source_files = vec![
+        SourceFile::parse(PathBuf::from("main.rs"), src).unwrap(),
+        SourceFile::parse(PathBuf::from(&migration_file.name), &migration_file.content).unwrap(),
+    ];
+    let migration = generator
+        .generate_migrations_as_generated_from_files(source_files)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(migration.operations.len(), 2);
+
+    if let DynOperation::RenameModel {
+        old_table_name,
+        new_table_name,
+        ..
+    } = &migration.operations[0]
+    {
+        assert_eq!(old_table_name, "cot__item");
+        assert_eq!(new_table_name, "cot__product");
+    } else {
+        panic!("expected rename model operation");
+    }
+
+    if let DynOperation::RenameField {
+        table_name,
+        old_name,
+        new_name,
+        ..
+    } = &migration.operations[1]
+    {
+        assert_eq!(table_name, "cot__product");
+        assert_eq!(old_name, "name");
+        assert_eq!(new_name, "title");
+    } else {
+        panic!("expected rename field operation");
+    }
+}
+
+#[test]
+fn index_two_migrations() {
+    let generator = test_generator();
+
+    let src = include_str!("migration_generator/index_two_migrations/step_1.rs");
+    let source_files = vec![SourceFile::parse(PathBuf::from("main.rs"), src).unwrap()];
+    let migration_file = generator
+        .generate_migrations_as_source_from_files(source_files)
+        .unwrap()
+        .unwrap();
+
+    let src = include_str!("migration_generator/index_two_migrations/step_2.rs");
+    let source_files = vec![
+        SourceFile::parse(PathBuf::from("main.rs"), src).unwrap(),
+        SourceFile::parse(PathBuf::from(&migration_file.name), &migration_file.content).unwrap(),
+    ];
+    let migration = generator
+        .generate_migrations_as_generated_from_files(source_files)
+        .unwrap()
+        .unwrap();
+
+    // the unchanged check constraint is kept in the migration model, so it's
+    // neither removed nor added again
+    assert_eq!(migration.operations.len(), 3);
+
+    if let DynOperation::RemoveIndex {
+        table_name, index, ..
+    } = &migration.operations[0]
+    {
+        assert_eq!(table_name, "cot__item");
+        assert_eq!(index.columns, ["name", "count"]);
+    } else {
+        panic!("expected remove index operation");
+    }
+
+    // removing the old index has to come first, the additions can be
+    // applied in any order
+    assert!(migration.operations[1..].iter().any(|op| matches!(
+        op,
+        DynOperation::AddIndex { table_name, index, .. }
+            if table_name == "cot__item" && index.columns == ["count"]
+    )));
+    assert!(migration.operations[1..].iter().any(|op| matches!(
+        op,
+        DynOperation::AddConstraint {
+            table_name,
+            constraint: ConstraintSpec::Unique { columns },
+            ..
+        } if table_name == "cot__item" && columns == &["name", "count"]
+    )));
+}
+
+#[test]
+fn db_enum_two_migrations() {
+    let generator = test_generator();
+
+    let src = include_str!("migration_generator/db_enum_two_migrations/step_1.rs");
+    let source_files = vec![SourceFile::parse(PathBuf::from("main.rs"), src).unwrap()];
+    let migration_file = generator
+        .generate_migrations_as_source_from_files(source_files)
+        .unwrap()
+        .unwrap();
+    assert!(migration_file.content.contains(
+        r#"check(name = "status_choices", expr = "\"status\" IN ('Draft', 'Published')")"#
+    ));
+    assert!(
+        migration_file
+            .content
+            .contains(r#"check(name = "priority_choices", expr = "\"priority\" IN (1, 10)")"#)
+    );
+
+    let src = include_str!("migration_generator/db_enum_two_migrations/step_2.rs");
+    let source_files = vec![
+        SourceFile::parse(PathBuf::from("main.rs"), src).unwrap(),
+        SourceFile::parse(PathBuf::from(&migration_file.name), &migration_file.content).unwrap(),
+    ];
+    let migration = generator
+        .generate_migrations_as_generated_from_files(source_files)
+        .unwrap()
+        .unwrap();
+
+    // only the constraint of the changed enum is replaced
+    assert_eq!(migration.operations.len(), 2);
+    assert!(matches!(
+        &migration.operations[0],
+        DynOperation::RemoveConstraint {
+            constraint: ConstraintSpec::Check { expr, .. },
+            ..
+        } if expr == r#""status" IN ('Draft', 'Published')"#
+    ));
+    assert!(matches!(
+        &migration.operations[1],
+        DynOperation::AddConstraint {
+            constraint: ConstraintSpec::Check { expr, .. },
+            ..
+        } if expr == r#""status" IN ('Draft', 'Published', 'archive')"#
+    ));
+}
+
+#[test]
+fn db_enum_in_another_file() {
+    let generator = test_generator();
+
+    let models = r"
+        use cot::db::{model, Auto};
+
+        use crate::status::Status;
+
+        #[model]
+        struct Article {
+            #[model(primary_key)]
+            id: Auto<i32>,
+            order: Status,
+        }
+
+        fn main() {}
+    ";
+    let status = r#"
+        #[derive(Debug, Clone, cot::db::DbEnum)]
+        pub enum Status {
+            #[select_choice(id = "draft")]
+            Draft,
+            #[select_choice(id = "published")]
+            Published,
+        }
+    "#;
+    let source_files = vec![
+        SourceFile::parse(PathBuf::from("main.rs"), models).unwrap(),
+        SourceFile::parse(PathBuf::from("status.rs"), status).unwrap(),
+    ];
+    let migration = generator
+        .generate_migrations_as_generated_from_files(source_files)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(migration.operations.len(), 2);
+    assert!(matches!(
+        &migration.operations[1],
+        DynOperation::AddConstraint {
+            table_name,
+            constraint: ConstraintSpec::Check { name, expr },
+            ..
+        } if table_name == "cot__article"
+            && name == "order_choices"
+            && expr == r#""order" IN ('draft', 'published')"#
+    ));
+}
+
 #[test]
 fn create_model_keywords() {
     let generator = test_generator();
@@ -247,6 +484,54 @@ fn create_model_compile_test() {
     compile_test(src, &migration_name, &migration_content);
 }
 
+/// Test that the migration generated for models with reverse relations
+/// compiles alongside the models, i.e. the relation accessors are not
+/// generated again for the migration models.
+#[test]
+#[cfg_attr(
+    miri,
+    ignore = "unsupported operation: extern static `pidfd_spawnp` is not supported by Miri"
+)]
+fn related_name_compile_test() {
+    let generator = test_generator();
+    let src = include_str!("migration_generator/related_name.rs");
+    let source_files = vec![SourceFile::parse(PathBuf::from("main.rs"), src).unwrap()];
+
+    let MigrationAsSource {
+        name: migration_name,
+        content: migration_content,
+    } = generator
+        .generate_migrations_as_source_from_files(source_files)
+        .unwrap()
+        .unwrap();
+
+    assert!(migration_content.contains("related_name"));
+    compile_test(src, &migration_name, &migration_content);
+}
+
+#[test]
+#[cfg_attr(
+    miri,
+    ignore = "unsupported operation: extern static `pidfd_spawnp` is not supported by Miri"
+)]
+fn many_to_many_compile_test() {
+    let generator = test_generator();
+    let src = include_str!("migration_generator/many_to_many.rs");
+    let source_files = vec![SourceFile::parse(PathBuf::from("main.rs"), src).unwrap()];
+
+    let MigrationAsSource {
+        name: migration_name,
+        content: migration_content,
+    } = generator
+        .generate_migrations_as_source_from_files(source_files)
+        .unwrap()
+        .unwrap();
+
+    assert!(migration_content.contains("post_tags"));
+    assert!(migration_content.contains("add_constraint"));
+    compile_test(src, &migration_name, &migration_content);
+}
+
 #[test]
 #[cfg_attr(
     miri,
@@ -288,6 +573,157 @@ mod migrations {{
     t.pass(&test_path);
 }
 
+#[test]
+fn squash_two_migrations() {
+    let source_files = foreign_key_two_migrations_files();
+    let generator = test_generator();
+
+    let migration = generator
+        .generate_squashed_migration_from_files("cot", "0001", "0002", source_files)
+        .unwrap();
+
+    assert_eq!(migration.name, "m_0003_squashed_0001_0002");
+    let content: String = migration.content.split_whitespace().collect();
+    assert!(
+        content.contains(r#"constREPLACES:&'static[&'staticstr]=&["m_0001_initial","m_0002_auto_"#)
+    );
+    // the `Parent` model is created by the squashed migration itself
+    assert!(!content.contains("MigrationDependency::migration"));
+    assert!(!content.contains("MigrationDependency::model"));
+    assert_eq!(content.matches("Operation::create_model()").count(), 2);
+    assert!(content.contains("struct_Parent"));
+    assert!(content.contains("struct_Child"));
+}
+
+#[test]
+fn squash_migrations_uses_next_free_number() {
+    let generator = test_generator();
+    let custom = generator
+        .generate_custom_migration_from_files("custom", foreign_key_two_migrations_files())
+        .unwrap();
+    let mut source_files = foreign_key_two_migrations_files();
+    source_files.push(SourceFile::parse(PathBuf::from(&custom.name), &custom.content).unwrap());
+
+    let migration = generator
+        .generate_squashed_migration_from_files("cot", "0001", "0002", source_files)
+        .unwrap();
+
+    assert_eq!(migration.name, "m_0004_squashed_0001_0002");
+    let content: String = migration.content.split_whitespace().collect();
+    assert!(content.contains(r#"constMIGRATION_NAME:&'staticstr="m_0004_squashed_0001_0002";"#));
+}
+
+#[test]
+fn squash_non_atomic_migration() {
+    let mut migrations = foreign_key_two_migrations();
+    migrations[1].content = migrations[1].content.replace(
+        "const APP_NAME",
+        "const ATOMIC: bool = false;\n    const APP_NAME",
+    );
+    let source_files = migrations
+        .iter()
+        .map(|migration| {
+            SourceFile::parse(PathBuf::from(&migration.name), &migration.content).unwrap()
+        })
+        .collect();
+    let generator = test_generator();
+
+    let migration = generator
+        .generate_squashed_migration_from_files("cot", "0001", "0002", source_files)
+        .unwrap();
+    let content: String = migration.content.split_whitespace().collect();
+    assert!(content.contains("constATOMIC:bool=false;"));
+
+    let migration = generator
+        .generate_squashed_migration_from_files(
+            "cot",
+            "0001",
+            "0002",
+            foreign_key_two_migrations_files(),
+        )
+        .unwrap();
+    assert!(!migration.content.contains("ATOMIC"));
+}
+
+#[test]
+fn squash_migrations_invalid_range() {
+    let source_files = foreign_key_two_migrations_files();
+    let generator = test_generator();
+
+    let result = generator.generate_squashed_migration_from_files(
+        "cot",
+        "0002",
+        "0001",
+        source_files.clone(),
+    );
+    assert!(result.is_err());
+
+    let result = generator.generate_squashed_migration_from_files(
+        "cot",
+        "0001",
+        "0003",
+        source_files.clone(),
+    );
+    assert!(result.is_err());
+
+    let result =
+        generator.generate_squashed_migration_from_files("other", "0001", "0002", source_files);
+    assert!(result.is_err());
+}
+
+#[test]
+fn squash_custom_migration() {
+    let generator = test_generator();
+    let custom = generator
+        .generate_custom_migration_from_files("custom", vec![])
+        .unwrap();
+    let mut source_files = foreign_key_two_migrations_files();
+    source_files.push(SourceFile::parse(PathBuf::from(&custom.name), &custom.content).unwrap());
+
+    let result =
+        generator.generate_squashed_migration_from_files("cot", "0001", "0003", source_files);
+
+    assert!(result.is_err());
+}
+
+/// Returns the migration files generated for the `foreign_key_two_migrations`
+/// test case.
+fn foreign_key_two_migrations_files() -> Vec<SourceFile> {
+    foreign_key_two_migrations()
+        .iter()
+        .map(|migration| {
+            SourceFile::parse(PathBuf::from(&migration.name), &migration.content).unwrap()
+        })
+        .collect()
+}
+
+/// Returns the migrations generated for the `foreign_key_two_migrations` test
+/// case.
+fn foreign_key_two_migrations() -> Vec<MigrationAsSource> {
+    let generator = test_generator();
+
+    let src = include_str!("migration_generator/foreign_key_two_migrations/step_1.rs");
+    let source_files = vec![SourceFile::parse(PathBuf::from("main.rs"), src).unwrap()];
+    let migration_1 = generator
+        .generate_migrations_as_source_from_files(source_files)
+        .unwrap()
+        .unwrap();
+
+    let src = include_str!("migration_generator/foreign_key_two_migrations/step_2.rs");
+    let migration_file_1 =
+        SourceFile::parse(PathBuf::from(&migration_1.name), &migration_1.content).unwrap();
+    let source_files = vec![
+        SourceFile::parse(PathBuf::from("main.rs"), src).unwrap(),
+        migration_file_1,
+    ];
+    let migration_2 = generator
+        .generate_migrations_as_source_from_files(source_files)
+        .unwrap()
+        .unwrap();
+
+    vec![migration_1, migration_2]
+}
+
 #[test]
 fn write_migrations_module() {
     let tempdir = tempfile::tempdir().unwrap();
@@ -427,3 +863,19 @@ fn unwrap_add_field(op: &DynOperation) -> (&str, cot_codegen::model::Field) {
         panic!("expected create model operation");
     }
 }
+
+fn unwrap_alter_field(
+    op: &DynOperation,
+) -> (&str, cot_codegen::model::Field, cot_codegen::model::Field) {
+    if let DynOperation::AlterField {
+        table_name,
+        old_field,
+        new_field,
+        ..
+    } = op
+    {
+        (table_name, *old_field.clone(), *new_field.clone())
+    } else {
+        panic!("expected alter field operation");
+    }
+}
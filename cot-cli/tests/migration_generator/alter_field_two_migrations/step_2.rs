@@ -0,0 +1,12 @@
+use cot::db::{model, Auto};
+
+#[model]
+struct Item {
+    #[model(primary_key)]
+    id: Auto<i32>,
+    #[model(unique)]
+    name: Option<String>,
+    count: i64,
+}
+
+fn main() {}
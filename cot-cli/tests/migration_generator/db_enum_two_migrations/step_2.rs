@@ -0,0 +1,26 @@
+use cot::db::{model, Auto, DbEnum};
+
+#[derive(Debug, Clone, DbEnum)]
+enum Status {
+    Draft,
+    Published,
+    #[db_enum(value = "archive")]
+    Archived,
+}
+
+#[derive(Debug, Clone, DbEnum)]
+#[db_enum(storage = "integer")]
+enum Priority {
+    Low = 1,
+    High = 10,
+}
+
+#[model]
+struct Article {
+    #[model(primary_key)]
+    id: Auto<i32>,
+    status: Status,
+    priority: Option<Priority>,
+}
+
+fn main() {}
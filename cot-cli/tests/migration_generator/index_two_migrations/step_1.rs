@@ -0,0 +1,11 @@
+use cot::db::{model, Auto};
+
+#[model(index(name, count), check(name = "count_positive", expr = "count > 0"))]
+struct Item {
+    #[model(primary_key)]
+    id: Auto<i32>,
+    name: String,
+    count: i32,
+}
+
+fn main() {}
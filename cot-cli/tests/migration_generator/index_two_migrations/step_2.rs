@@ -0,0 +1,12 @@
+use cot::db::{model, Auto};
+
+#[model(unique_together(name, count), check(name = "count_positive", expr = "count > 0"))]
+struct Item {
+    #[model(primary_key)]
+    id: Auto<i32>,
+    name: String,
+    #[model(index)]
+    count: i32,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use cot::db::{model, Auto, ForeignKey};
+
+#[derive(Debug)]
+#[model]
+struct User {
+    #[model(primary_key)]
+    id: Auto<i32>,
+}
+
+#[derive(Debug)]
+#[model]
+struct Post {
+    #[model(primary_key)]
+    id: Auto<i32>,
+    #[model(related_name = "posts")]
+    author: ForeignKey<User>,
+}
+
+fn main() {
+    let user = User { id: Auto::fixed(1) };
+    let _ = user.posts();
+}
@@ -0,0 +1,10 @@
+use cot::db::{model, Auto};
+
+#[model]
+struct Item {
+    #[model(primary_key)]
+    id: Auto<i32>,
+    name: String,
+}
+
+fn main() {}
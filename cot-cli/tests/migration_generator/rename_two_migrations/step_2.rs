@@ -0,0 +1,11 @@
+use cot::db::{model, Auto};
+
+#[model(renamed_from = "Item")]
+struct Product {
+    #[model(primary_key)]
+    id: Auto<i32>,
+    #[model(renamed_from = "name")]
+    title: String,
+}
+
+fn main() {}
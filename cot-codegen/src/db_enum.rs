@@ -0,0 +1,358 @@
+use std::collections::HashSet;
+
+use darling::{FromDeriveInput, FromMeta, FromVariant};
+use syn::spanned::Spanned;
+
+use crate::model::ConstraintSpec;
+
+#[expect(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, FromDeriveInput)]
+#[darling(attributes(db_enum), supports(enum_unit))]
+pub struct DbEnumOpts {
+    pub ident: syn::Ident,
+    pub data: darling::ast::Data<DbEnumVariantOpts, darling::util::Ignored>,
+    #[darling(default)]
+    pub storage: DbEnumStorage,
+}
+
+/// How the variants of an enum are stored in the database, declared with the
+/// `#[db_enum(storage = "...")]` attribute.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, FromMeta)]
+pub enum DbEnumStorage {
+    /// The variants are stored as text values (the default).
+    #[default]
+    Text,
+    /// The variants are stored as their integer discriminants.
+    Integer,
+}
+
+#[derive(Debug, Clone, FromVariant)]
+#[darling(attributes(db_enum), forward_attrs(select_choice))]
+pub struct DbEnumVariantOpts {
+    pub ident: syn::Ident,
+    pub discriminant: Option<syn::Expr>,
+    pub attrs: Vec<syn::Attribute>,
+    pub value: Option<String>,
+}
+
+/// The arguments of the `#[select_choice(...)]` attribute, which are used to
+/// keep the values stored in the database consistent with the IDs used in
+/// forms.
+#[derive(Debug, Default, FromMeta)]
+struct SelectChoiceArgs {
+    id: Option<String>,
+    #[expect(dead_code)]
+    name: Option<String>,
+}
+
+impl DbEnumOpts {
+    /// Convert the enum options into a database enum.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the enum has no variants, if a variant is assigned
+    /// a value that is not valid for the storage type, or if two variants are
+    /// stored as the same value.
+    pub fn as_db_enum(&self) -> Result<DbEnum, syn::Error> {
+        let variants = self
+            .data
+            .as_ref()
+            .take_enum()
+            .expect("Only enums are supported");
+        if variants.is_empty() {
+            return Err(syn::Error::new(
+                self.ident.span(),
+                "`DbEnum` cannot be derived for empty enums",
+            ));
+        }
+
+        let mut db_variants = Vec::with_capacity(variants.len());
+        let mut next_discriminant = 0_i64;
+        for variant in variants {
+            let value = match self.storage {
+                DbEnumStorage::Text => DbEnumValue::Text(variant.text_value()?),
+                DbEnumStorage::Integer => {
+                    if variant.value.is_some() {
+                        return Err(syn::Error::new(
+                            variant.ident.span(),
+                            "`value` can only be set for enums stored as text; enums stored \
+                            as integers use the discriminants of the variants",
+                        ));
+                    }
+                    let discriminant = match &variant.discriminant {
+                        Some(discriminant) => parse_discriminant(discriminant)?,
+                        None => next_discriminant,
+                    };
+                    next_discriminant = discriminant + 1;
+                    let discriminant = i32::try_from(discriminant).map_err(|_| {
+                        syn::Error::new(
+                            variant.ident.span(),
+                            "the discriminants of enums stored as integers must fit in `i32`",
+                        )
+                    })?;
+                    DbEnumValue::Integer(discriminant)
+                }
+            };
+            db_variants.push(DbEnumVariant {
+                ident: variant.ident.clone(),
+                value,
+            });
+        }
+
+        let mut seen_values = HashSet::new();
+        for variant in &db_variants {
+            if !seen_values.insert(&variant.value) {
+                return Err(syn::Error::new(
+                    variant.ident.span(),
+                    format!(
+                        "duplicate database value `{}`",
+                        variant.value.to_sql_literal()
+                    ),
+                ));
+            }
+        }
+
+        Ok(DbEnum {
+            name: self.ident.clone(),
+            storage: self.storage,
+            variants: db_variants,
+        })
+    }
+}
+
+impl DbEnumVariantOpts {
+    /// Returns the value the variant is stored as when the enum is stored as
+    /// text: the `value` set in `#[db_enum(...)]`, the `id` set in
+    /// `#[select_choice(...)]`, or the name of the variant, in that order.
+    fn text_value(&self) -> Result<String, syn::Error> {
+        if let Some(value) = &self.value {
+            return Ok(value.clone());
+        }
+
+        for attr in &self.attrs {
+            let args = SelectChoiceArgs::from_meta(&attr.meta)
+                .map_err(|error| syn::Error::new(attr.span(), error.to_string()))?;
+            if let Some(id) = args.id {
+                return Ok(id);
+            }
+        }
+
+        Ok(self.ident.to_string())
+    }
+}
+
+fn parse_discriminant(expr: &syn::Expr) -> Result<i64, syn::Error> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit.base10_parse(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr: inner,
+            ..
+        }) => parse_discriminant(inner).map(|value| -value),
+        _ => Err(syn::Error::new(
+            expr.span(),
+            "the discriminants of enums stored as integers must be integer literals",
+        )),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DbEnum {
+    pub name: syn::Ident,
+    pub storage: DbEnumStorage,
+    pub variants: Vec<DbEnumVariant>,
+}
+
+impl DbEnum {
+    /// Returns the `CHECK` constraint ensuring that the given column only
+    /// contains the values of the enum variants.
+    #[must_use]
+    pub fn check_constraint(&self, column_name: &str) -> ConstraintSpec {
+        let values = self
+            .variants
+            .iter()
+            .map(|variant| variant.value.to_sql_literal())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        ConstraintSpec::Check {
+            name: format!("{column_name}_choices"),
+            expr: format!("\"{}\" IN ({values})", column_name.replace('"', "\"\"")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DbEnumVariant {
+    pub ident: syn::Ident,
+    pub value: DbEnumValue,
+}
+
+/// The value an enum variant is stored as in the database.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DbEnumValue {
+    Text(String),
+    Integer(i32),
+}
+
+impl DbEnumValue {
+    /// Returns the value as an SQL literal, with quotes escaped.
+    #[must_use]
+    pub fn to_sql_literal(&self) -> String {
+        match self {
+            Self::Text(value) => format!("'{}'", value.replace('\'', "''")),
+            Self::Integer(value) => value.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn db_enum(input: &syn::DeriveInput) -> Result<DbEnum, syn::Error> {
+        DbEnumOpts::from_derive_input(input).unwrap().as_db_enum()
+    }
+
+    #[test]
+    fn db_enum_text_values() {
+        let db_enum = db_enum(&parse_quote! {
+            #[derive(DbEnum)]
+            enum Status {
+                Draft,
+                #[select_choice(id = "published", name = "Published")]
+                Published,
+                #[db_enum(value = "gone")]
+                #[select_choice(id = "archived")]
+                Archived,
+            }
+        })
+        .unwrap();
+
+        assert_eq!(db_enum.storage, DbEnumStorage::Text);
+        let values: Vec<_> = db_enum.variants.iter().map(|v| v.value.clone()).collect();
+        assert_eq!(
+            values,
+            [
+                DbEnumValue::Text("Draft".to_owned()),
+                DbEnumValue::Text("published".to_owned()),
+                DbEnumValue::Text("gone".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn db_enum_integer_values() {
+        let db_enum = db_enum(&parse_quote! {
+            #[derive(DbEnum)]
+            #[db_enum(storage = "integer")]
+            enum Priority {
+                Low,
+                Medium = 5,
+                High,
+                Negative = -1,
+            }
+        })
+        .unwrap();
+
+        assert_eq!(db_enum.storage, DbEnumStorage::Integer);
+        let values: Vec<_> = db_enum.variants.iter().map(|v| v.value.clone()).collect();
+        assert_eq!(
+            values,
+            [
+                DbEnumValue::Integer(0),
+                DbEnumValue::Integer(5),
+                DbEnumValue::Integer(6),
+                DbEnumValue::Integer(-1),
+            ]
+        );
+    }
+
+    #[test]
+    fn db_enum_check_constraint() {
+        let db_enum = db_enum(&parse_quote! {
+            #[derive(DbEnum)]
+            enum Status {
+                #[db_enum(value = "it's")]
+                Quoted,
+                Plain,
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            db_enum.check_constraint("status"),
+            ConstraintSpec::Check {
+                name: "status_choices".to_owned(),
+                expr: r#""status" IN ('it''s', 'Plain')"#.to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn db_enum_empty() {
+        let error = db_enum(&parse_quote! {
+            #[derive(DbEnum)]
+            enum Empty {}
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "`DbEnum` cannot be derived for empty enums"
+        );
+    }
+
+    #[test]
+    fn db_enum_duplicate_values() {
+        let error = db_enum(&parse_quote! {
+            #[derive(DbEnum)]
+            enum Status {
+                #[db_enum(value = "a")]
+                First,
+                #[db_enum(value = "a")]
+                Second,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(error.to_string(), "duplicate database value `'a'`");
+    }
+
+    #[test]
+    fn db_enum_integer_with_value() {
+        let error = db_enum(&parse_quote! {
+            #[derive(DbEnum)]
+            #[db_enum(storage = "integer")]
+            enum Status {
+                #[db_enum(value = "a")]
+                First,
+            }
+        })
+        .unwrap_err();
+
+        assert!(error.to_string().starts_with("`value` can only be set"));
+    }
+
+    #[test]
+    fn db_enum_integer_out_of_range() {
+        let error = db_enum(&parse_quote! {
+            #[derive(DbEnum)]
+            #[db_enum(storage = "integer")]
+            enum Status {
+                Big = 3_000_000_000,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "the discriminants of enums stored as integers must fit in `i32`"
+        );
+    }
+}
@@ -284,6 +284,11 @@ impl BindingPriority<u8, u8> {
 
 type InfixBindingPriority = BindingPriority<u8, u8>;
 
+/// The binding priority of the prefix `!` operator. It's higher than the
+/// priority of any infix operator, so that, just like in Rust, `!$a == $b` is
+/// parsed as `(!$a) == $b`.
+const NOT_BINDING_PRIORITY: u8 = 20;
+
 /// A parsed expression.
 ///
 /// This type represents a parsed expression that can be used to generate code.
@@ -325,6 +330,7 @@ pub enum Expr {
         function: Box<Expr>,
         args: Vec<syn::Expr>,
     },
+    Not(Box<Expr>),
     And(Box<Expr>, Box<Expr>),
     Or(Box<Expr>, Box<Expr>),
     Eq(Box<Expr>, Box<Expr>),
@@ -352,7 +358,10 @@ impl Expr {
     fn parse_impl(input: ParseStream<'_>, min_binding_priority: u8) -> syn::Result<Self> {
         // Implementation of Pratt parsing algorithm
 
-        let mut lhs = if input.peek(syn::token::Paren) {
+        let mut lhs = if input.peek(Token![!]) {
+            input.parse::<Token![!]>()?;
+            Expr::Not(Box::new(Self::parse_impl(input, NOT_BINDING_PRIORITY)?))
+        } else if input.peek(syn::token::Paren) {
             let content;
             let _ = syn::parenthesized!(content in input);
             Self::parse_impl(&content, 0)?
@@ -391,7 +400,9 @@ impl Expr {
         };
 
         loop {
-            if input.is_empty() {
+            // A comma or a semicolon ends the expression, so that it can be
+            // followed by other items (e.g. in a list of sort keys)
+            if input.is_empty() || input.peek(Token![,]) || input.peek(Token![;]) {
                 break;
             }
 
@@ -504,6 +515,10 @@ impl Expr {
                 let function_tokens = function.as_tokens_impl(mode)?;
                 Some(quote! {#function_tokens(#(#args),*)})
             }
+            Expr::Not(expr) => {
+                let expr_tokens = expr.as_tokens_impl(mode)?;
+                Some(quote! {!#expr_tokens})
+            }
             Expr::And(lhs, rhs) => {
                 let lhs_tokens = lhs.as_tokens_impl(mode)?;
                 let rhs_tokens = rhs.as_tokens_impl(mode)?;
@@ -757,6 +772,68 @@ mod tests {
         assert_eq!(expected, unwrap_syn(Expr::parse(input)));
     }
 
+    #[test]
+    fn stops_at_separator() {
+        let input = quote! { $a == 5, $b.desc(); rest };
+        let parser = |input: ParseStream<'_>| {
+            let first = input.parse::<Expr>()?;
+            input.parse::<Token![,]>()?;
+            let second = input.parse::<Expr>()?;
+            input.parse::<Token![;]>()?;
+            input.parse::<syn::Ident>()?;
+            Ok((first, second))
+        };
+        let (first, second) = unwrap_syn(syn::parse::Parser::parse2(parser, input));
+
+        assert_eq!(
+            first,
+            Expr::Eq(Box::new(field("a")), Box::new(Expr::Value(parse_quote!(5))))
+        );
+        assert_eq!(
+            second,
+            Expr::FunctionCall {
+                function: Box::new(member_access(field("b"), "desc")),
+                args: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn not() {
+        let input = quote! { !$a == 5 };
+        let expected = Expr::Eq(
+            Box::new(Expr::Not(Box::new(field("a")))),
+            Box::new(Expr::Value(parse_quote!(5))),
+        );
+
+        assert_eq!(expected, unwrap_syn(Expr::parse(input)));
+    }
+
+    #[test]
+    fn not_method_call() {
+        let input = quote! { !$a.is_null() && !($b == 5) };
+        let expected = Expr::And(
+            Box::new(Expr::Not(Box::new(Expr::FunctionCall {
+                function: Box::new(member_access(field("a"), "is_null")),
+                args: Vec::new(),
+            }))),
+            Box::new(Expr::Not(Box::new(Expr::Eq(
+                Box::new(field("b")),
+                Box::new(Expr::Value(parse_quote!(5))),
+            )))),
+        );
+
+        assert_eq!(expected, unwrap_syn(Expr::parse(input)));
+    }
+
+    #[test]
+    fn tokens_not() {
+        let input = quote! { !x };
+        let expr = unwrap_syn(Expr::parse(input.clone()));
+
+        assert_eq!(input.to_string(), expr.as_tokens().unwrap().to_string());
+    }
+
     #[test]
     fn tokens_field_ref() {
         let input = quote! { $migration.like("%this") };
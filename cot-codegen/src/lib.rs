@@ -4,6 +4,7 @@
 // at the item level, so it is allowed crate-wide.
 #![allow(unused_qualifications)]
 
+pub mod db_enum;
 pub mod expr;
 pub mod model;
 pub mod symbol_resolver;
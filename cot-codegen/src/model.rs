@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use darling::{FromDeriveInput, FromField, FromMeta};
 use heck::ToSnakeCase;
 use proc_macro2::TokenStream;
@@ -13,6 +15,25 @@ pub struct ModelArgs {
     #[darling(default)]
     pub model_type: ModelType,
     pub table_name: Option<String>,
+    /// The previous name of the model struct, used by the migration generator
+    /// to rename the table instead of recreating it.
+    pub renamed_from: Option<String>,
+    /// The fields of the composite indexes of the model.
+    #[darling(multiple)]
+    pub index: Vec<darling::util::PathList>,
+    /// The fields of the composite unique constraints of the model.
+    #[darling(multiple)]
+    pub unique_together: Vec<darling::util::PathList>,
+    #[darling(multiple)]
+    pub check: Vec<CheckArgs>,
+}
+
+/// A `CHECK` constraint declared with the
+/// `#[model(check(name = "...", expr = "..."))]` attribute.
+#[derive(Debug, Clone, FromMeta)]
+pub struct CheckArgs {
+    pub name: String,
+    pub expr: String,
 }
 
 #[expect(clippy::module_name_repetitions)]
@@ -72,13 +93,39 @@ impl ModelOpts {
         symbol_resolver: &SymbolResolver,
     ) -> Result<Model, syn::Error> {
         let self_reference = self.ident.to_string();
-        let as_field = |field: &&FieldOpts| field.as_field(symbol_resolver, Some(&self_reference));
 
-        let fields = self
-            .fields()
-            .iter()
-            .map(as_field)
-            .collect::<Result<Vec<_>, _>>()?;
+        let mut fields = Vec::new();
+        let mut many_to_many_fields = Vec::new();
+        let mut reverse_relations = Vec::new();
+        let mut renamed_fields = BTreeMap::new();
+        let mut indexes = Vec::new();
+        for field in self.fields() {
+            if let Some(many_to_many_field) =
+                field.as_many_to_many_field(symbol_resolver, Some(&self_reference))?
+            {
+                many_to_many_fields.push(many_to_many_field);
+            } else {
+                let model_field = field.as_field(symbol_resolver, Some(&self_reference))?;
+                let reverse_relation = field.as_reverse_relation(&model_field)?;
+                // the accessors are only generated for the application models;
+                // the migration models are copies of them, so the accessors
+                // would be defined twice
+                if let Some(reverse_relation) = reverse_relation
+                    && args.model_type != ModelType::Migration
+                {
+                    reverse_relations.push(reverse_relation);
+                }
+                if let Some(renamed_from) = &field.renamed_from {
+                    renamed_fields.insert(model_field.column_name.clone(), renamed_from.clone());
+                }
+                if field.index.is_present() {
+                    indexes.push(IndexSpec {
+                        columns: model_field.column_names(),
+                    });
+                }
+                fields.push(model_field);
+            }
+        }
 
         let mut original_name = self.ident.unraw().to_string();
         if args.model_type == ModelType::Migration {
@@ -98,7 +145,25 @@ impl ModelOpts {
             original_name.to_snake_case()
         };
 
-        let primary_key_field = self.get_primary_key_field(&fields)?;
+        let primary_key_fields = self.get_primary_key_fields(&fields, &many_to_many_fields)?;
+
+        for field_names in &args.index {
+            indexes.push(IndexSpec {
+                columns: self.column_names(&fields, field_names)?,
+            });
+        }
+        let mut constraints = composite_foreign_key_constraints(&mut fields);
+        for field_names in &args.unique_together {
+            constraints.push(ConstraintSpec::Unique {
+                columns: self.column_names(&fields, field_names)?,
+            });
+        }
+        for check in &args.check {
+            constraints.push(ConstraintSpec::Check {
+                name: check.name.clone(),
+                expr: check.expr.clone(),
+            });
+        }
 
         let ty = {
             let mut ty = syn::Type::Path(syn::TypePath {
@@ -117,12 +182,58 @@ impl ModelOpts {
             resolved_ty: ty,
             model_type: args.model_type,
             table_name,
-            pk_field: primary_key_field.clone(),
+            renamed_from: args.renamed_from.clone(),
+            pk_fields: primary_key_fields,
             fields,
+            many_to_many_fields,
+            reverse_relations,
+            renamed_fields,
+            indexes,
+            constraints,
         })
     }
 
-    fn get_primary_key_field<'a>(&self, fields: &'a [Field]) -> Result<&'a Field, syn::Error> {
+    /// Returns the column names of the fields listed in a model-level
+    /// attribute, such as `#[model(index(...))]`.
+    fn column_names(
+        &self,
+        fields: &[Field],
+        field_names: &darling::util::PathList,
+    ) -> Result<Vec<String>, syn::Error> {
+        if field_names.is_empty() {
+            return Err(syn::Error::new(
+                self.ident.span(),
+                "indexes and constraints must contain at least one field",
+            ));
+        }
+
+        field_names
+            .iter()
+            .map(|path| {
+                fields
+                    .iter()
+                    .find(|field| path.is_ident(&field.name))
+                    .map(Field::column_names)
+                    .ok_or_else(|| {
+                        syn::Error::new(
+                            path.span(),
+                            format!(
+                                "unknown field `{}` (note that `ManyToMany<T>` fields \
+                                can't be used in indexes or constraints)",
+                                path.to_token_stream()
+                            ),
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|columns| columns.into_iter().flatten().collect())
+    }
+
+    fn get_primary_key_fields(
+        &self,
+        fields: &[Field],
+        many_to_many_fields: &[ManyToManyField],
+    ) -> Result<Vec<Field>, syn::Error> {
         let pks: Vec<_> = fields.iter().filter(|field| field.primary_key).collect();
         if pks.is_empty() {
             return Err(syn::Error::new(
@@ -132,22 +243,63 @@ impl ModelOpts {
             ));
         }
         if pks.len() > 1 {
-            return Err(syn::Error::new(
-                pks[1].name.span(),
-                "composite primary keys are not supported; only one primary key field is allowed",
-            ));
+            if let Some(auto_pk) = pks.iter().find(|field| field.auto_value) {
+                return Err(syn::Error::new(
+                    auto_pk.name.span(),
+                    "`Auto<T>` fields cannot be a part of a composite primary key",
+                ));
+            }
+            if let Some(many_to_many_field) = many_to_many_fields.first() {
+                return Err(syn::Error::new(
+                    many_to_many_field.name.span(),
+                    "`ManyToMany<T>` fields are not supported in models with a composite \
+                    primary key",
+                ));
+            }
         }
 
-        Ok(pks[0])
+        Ok(pks.into_iter().cloned().collect())
+    }
+}
+
+/// Returns the constraints of the foreign keys referencing models with a
+/// composite primary key, which span all of the columns the foreign keys are
+/// stored in.
+///
+/// The `unique` flag of such fields is turned into a constraint as well, as a
+/// column-level `UNIQUE` constraint can't span multiple columns.
+fn composite_foreign_key_constraints(fields: &mut [Field]) -> Vec<ConstraintSpec> {
+    let mut constraints = Vec::new();
+    for field in fields {
+        let Some(foreign_key) = field.foreign_key.as_ref().filter(|fk| fk.is_composite()) else {
+            continue;
+        };
+        if field.unique {
+            field.unique = false;
+            constraints.push(ConstraintSpec::Unique {
+                columns: foreign_key.columns.clone(),
+            });
+        }
+        constraints.push(ConstraintSpec::ForeignKey {
+            columns: foreign_key.columns.clone(),
+            to_model: Box::new(foreign_key.to_model.clone()),
+            on_delete: foreign_key.on_delete.unwrap_or_default(),
+            on_update: foreign_key.on_update.unwrap_or_default(),
+        });
     }
+    constraints
 }
 
-#[derive(Debug, Clone, Copy, Default, FromMeta)]
+#[derive(Debug, Clone, Default, FromMeta)]
 pub struct ForeignKeyArgs {
     #[darling(default)]
     pub on_delete: ForeignKeyOnDeletePolicy,
     #[darling(default)]
     pub on_update: ForeignKeyOnUpdatePolicy,
+    /// The columns the foreign key is stored in, if the referenced model has a
+    /// composite primary key.
+    #[darling(default)]
+    pub columns: darling::util::PathList,
 }
 
 #[derive(Debug, Clone, Copy, Default, FromMeta, PartialEq, Eq, Hash)]
@@ -215,8 +367,12 @@ pub struct FieldOpts {
     pub ty: syn::Type,
     pub primary_key: darling::util::Flag,
     pub unique: darling::util::Flag,
+    pub index: darling::util::Flag,
     pub field_name: Option<String>,
     pub foreign_key: Option<ForeignKeyArgs>,
+    pub related_name: Option<String>,
+    pub renamed_from: Option<String>,
+    pub default: Option<DefaultSpec>,
 }
 
 impl FieldOpts {
@@ -296,6 +452,175 @@ impl FieldOpts {
             .or_else(|| self.find_type("ForeignKey", symbol_resolver))
     }
 
+    fn is_many_to_many_type(&self, symbol_resolver: &SymbolResolver) -> bool {
+        matches!(
+            self.resolved_type_name(symbol_resolver).as_deref(),
+            Some("cot::db::ManyToMany" | "ManyToMany")
+        )
+    }
+
+    fn column_name(&self) -> String {
+        if let Some(specified_field_name) = &self.field_name {
+            specified_field_name.clone()
+        } else {
+            self.ident
+                .as_ref()
+                .expect("Only structs are supported")
+                .unraw()
+                .to_string()
+        }
+    }
+
+    /// Convert the field options into a many-to-many field, or return
+    /// [`None`] if the field is not a `ManyToMany<T>` field.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the field is a `ManyToMany<T>` field, but it is
+    /// also marked as a primary key, unique, or a foreign key, or if the type
+    /// doesn't have exactly one generic argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field does not have an identifier (i.e. it is a tuple
+    /// struct).
+    pub fn as_many_to_many_field(
+        &self,
+        symbol_resolver: &SymbolResolver,
+        self_reference: Option<&String>,
+    ) -> Result<Option<ManyToManyField>, syn::Error> {
+        if !self.is_many_to_many_type(symbol_resolver) {
+            return Ok(None);
+        }
+
+        let name = self.ident.clone().expect("Only structs are supported");
+        if self.primary_key.is_present() || self.unique.is_present() || self.foreign_key.is_some() {
+            return Err(syn::Error::new(
+                name.span(),
+                "`ManyToMany<T>` fields cannot be primary keys, unique, or foreign keys",
+            ));
+        }
+        if self.related_name.is_some() {
+            return Err(Self::related_name_error(&name));
+        }
+        if self.renamed_from.is_some() {
+            return Err(syn::Error::new(
+                name.span(),
+                "`ManyToMany<T>` fields cannot be renamed with `renamed_from`",
+            ));
+        }
+        if self.index.is_present() {
+            return Err(syn::Error::new(
+                name.span(),
+                "`ManyToMany<T>` fields cannot be indexed",
+            ));
+        }
+        if self.default.is_some() {
+            return Err(syn::Error::new(
+                name.span(),
+                "`ManyToMany<T>` fields cannot have default values",
+            ));
+        }
+
+        let mut resolved_ty = self.ty.clone();
+        symbol_resolver.resolve(&mut resolved_ty, self_reference);
+        let to_model = single_generic_argument(&resolved_ty, "ManyToMany")?;
+
+        Ok(Some(ManyToManyField {
+            column_name: self.column_name(),
+            name,
+            ty: resolved_ty,
+            to_model,
+        }))
+    }
+
+    /// Returns the reverse accessor declared on the field with the
+    /// `#[model(related_name = "...")]` attribute, or [`None`] if there is no
+    /// such attribute.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the field is not a foreign key, or if the related
+    /// name is not a valid identifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field does not have an identifier (i.e. it is a tuple
+    /// struct).
+    pub fn as_reverse_relation(
+        &self,
+        field: &Field,
+    ) -> Result<Option<ReverseRelationSpec>, syn::Error> {
+        let Some(related_name) = &self.related_name else {
+            return Ok(None);
+        };
+
+        let name = self.ident.as_ref().expect("Only structs are supported");
+        let Some(foreign_key) = &field.foreign_key else {
+            return Err(Self::related_name_error(name));
+        };
+        let accessor_name = syn::parse_str::<syn::Ident>(related_name).map_err(|_| {
+            syn::Error::new(
+                name.span(),
+                format!("`related_name` must be a valid identifier, got `{related_name}`"),
+            )
+        })?;
+
+        Ok(Some(ReverseRelationSpec {
+            name: accessor_name,
+            field_name: field.name.clone(),
+            column_name: field.column_name.clone(),
+            source_model: foreign_key.to_model.clone(),
+        }))
+    }
+
+    fn related_name_error(name: &syn::Ident) -> syn::Error {
+        syn::Error::new(
+            name.span(),
+            "`#[model(related_name = \"...\")]` can only be used on `ForeignKey<T>` fields",
+        )
+    }
+
+    /// Checks that a foreign key stored in multiple columns doesn't use any of
+    /// the options that only make sense for a single column.
+    fn validate_composite_foreign_key(
+        &self,
+        foreign_key: &ForeignKeySpec,
+        symbol_resolver: &SymbolResolver,
+    ) -> Result<(), syn::Error> {
+        let error = |message: &str| Err(syn::Error::new(self.ident.span(), message));
+
+        if foreign_key.columns.len() < 2 {
+            return error(
+                "`#[model(foreign_key(columns(...)))]` must list at least two columns; \
+                use `#[model(field_name = \"...\")]` to rename a single column",
+            );
+        }
+        if self.is_option_type(symbol_resolver) {
+            return error("composite foreign keys cannot be optional");
+        }
+        if self.primary_key.is_present() {
+            return error("composite foreign keys cannot be a part of the primary key");
+        }
+        if self.default.is_some() {
+            return error("composite foreign keys cannot have a default value");
+        }
+        if self.field_name.is_some() {
+            return error(
+                "composite foreign keys cannot be renamed with `field_name`; \
+                the column names are set with `columns(...)`",
+            );
+        }
+        if self.renamed_from.is_some() {
+            return error("composite foreign keys cannot be renamed with `renamed_from`");
+        }
+        if self.related_name.is_some() {
+            return error("composite foreign keys cannot have a `related_name`");
+        }
+
+        Ok(())
+    }
+
     /// Convert the field options into a field.
     ///
     /// # Panics
@@ -308,14 +633,12 @@ impl FieldOpts {
         self_reference: Option<&String>,
     ) -> Result<Field, syn::Error> {
         let name = self.ident.clone().expect("Only structs are supported");
+        let column_name = self.column_name();
 
-        let column_name = if let Some(specified_field_name) = &self.field_name {
-            specified_field_name.clone()
-        } else {
-            name.unraw().to_string()
-        };
-
-        let auto_value = self.find_type("cot::db::Auto", symbol_resolver).is_some();
+        // `Auto<T>` fields with a default value are filled in by the database
+        // using the default instead of an auto-incrementing value
+        let auto_value =
+            self.default.is_none() && self.find_type("cot::db::Auto", symbol_resolver).is_some();
         let foreign_key_ty = self.find_foreign_key_type(symbol_resolver);
 
         if self.foreign_key.is_some() && foreign_key_ty.is_none() {
@@ -329,11 +652,21 @@ impl FieldOpts {
             .map(ForeignKeySpec::try_from)
             .transpose()?
             .map(|mut fk| {
-                let args = self.foreign_key.unwrap_or_default();
+                let args = self.foreign_key.clone().unwrap_or_default();
                 fk.set_on_delete(args.on_delete);
                 fk.set_on_update(args.on_update);
-                fk
-            });
+                fk.columns = args
+                    .columns
+                    .iter()
+                    .map(|path| {
+                        path.get_ident()
+                            .map(ToString::to_string)
+                            .ok_or_else(|| syn::Error::new(path.span(), "expected a column name"))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok::<_, syn::Error>(fk)
+            })
+            .transpose()?;
 
         // SetNone can only be used with Option types.
         if let Some(foreign_key) = &foreign_key {
@@ -351,6 +684,10 @@ impl FieldOpts {
                     "`set_none` foreign key policy can only be used on `Option<ForeignKey<T>>` fields",
                 ));
             }
+
+            if foreign_key.is_composite() {
+                self.validate_composite_foreign_key(foreign_key, symbol_resolver)?;
+            }
         }
 
         let is_primary_key = self.primary_key.is_present();
@@ -364,6 +701,7 @@ impl FieldOpts {
             primary_key: is_primary_key,
             foreign_key,
             unique: self.unique.is_present(),
+            default: self.default.clone(),
         })
     }
 }
@@ -378,8 +716,26 @@ pub struct Model {
     #[expect(clippy::struct_field_names)] // `type` is not an allowed identifier in Rust
     pub model_type: ModelType,
     pub table_name: String,
-    pub pk_field: Field,
+    /// The previous name of the model, as declared with the
+    /// `#[model(renamed_from = "...")]` attribute.
+    pub renamed_from: Option<String>,
+    /// The primary key fields of the model. There is more than one field
+    /// if the model has a composite primary key.
+    pub pk_fields: Vec<Field>,
     pub fields: Vec<Field>,
+    /// The `ManyToMany<T>` fields of the model. These are not stored in the
+    /// model's table, so they are not included in [`Self::fields`].
+    pub many_to_many_fields: Vec<ManyToManyField>,
+    /// The reverse accessors declared on the foreign keys of the model.
+    pub reverse_relations: Vec<ReverseRelationSpec>,
+    /// The previous names of the fields declared with the
+    /// `#[model(renamed_from = "...")]` attribute, keyed by the current
+    /// column names.
+    pub renamed_fields: BTreeMap<String, String>,
+    /// The indexes declared on the fields or on the model.
+    pub indexes: Vec<IndexSpec>,
+    /// The composite unique and the `CHECK` constraints of the model.
+    pub constraints: Vec<ConstraintSpec>,
 }
 
 impl Model {
@@ -389,6 +745,20 @@ impl Model {
     }
 }
 
+impl Field {
+    /// Returns the names of the columns the field is stored in.
+    ///
+    /// This is the field's column name, unless the field is a foreign key
+    /// referencing a model with a composite primary key.
+    #[must_use]
+    pub fn column_names(&self) -> Vec<String> {
+        match &self.foreign_key {
+            Some(foreign_key) if foreign_key.is_composite() => foreign_key.columns.clone(),
+            _ => vec![self.column_name.clone()],
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Field {
     pub name: syn::Ident,
@@ -401,6 +771,64 @@ pub struct Field {
     /// determined not to be a foreign key.
     pub foreign_key: Option<ForeignKeySpec>,
     pub unique: bool,
+    /// The database-level default value declared with the
+    /// `#[model(default = ...)]` attribute.
+    pub default: Option<DefaultSpec>,
+}
+
+/// A database-level default value of a field.
+///
+/// Literals are declared as `#[model(default = 0)]`, and SQL expressions as
+/// `#[model(default(expr = "CURRENT_TIMESTAMP"))]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DefaultSpec {
+    Bool(bool),
+    Int(i64),
+    /// A floating point literal, stored as its decimal representation.
+    Float(String),
+    String(String),
+    /// An SQL expression evaluated by the database.
+    Expr(String),
+}
+
+impl FromMeta for DefaultSpec {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        match expr {
+            syn::Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Neg(_),
+                expr: inner,
+                ..
+            }) => match Self::from_expr(inner)? {
+                Self::Int(value) => Ok(Self::Int(-value)),
+                Self::Float(value) => Ok(Self::Float(format!("-{value}"))),
+                _ => Err(darling::Error::unexpected_expr_type(expr)),
+            },
+            syn::Expr::Lit(lit) => Self::from_value(&lit.lit),
+            syn::Expr::Group(group) => Self::from_expr(&group.expr),
+            _ => Err(darling::Error::unexpected_expr_type(expr)),
+        }
+        .map_err(|e| e.with_span(expr))
+    }
+
+    fn from_value(value: &syn::Lit) -> darling::Result<Self> {
+        match value {
+            syn::Lit::Bool(value) => Ok(Self::Bool(value.value)),
+            syn::Lit::Int(value) => Ok(Self::Int(value.base10_parse()?)),
+            syn::Lit::Float(value) => Ok(Self::Float(value.base10_digits().to_owned())),
+            syn::Lit::Str(value) => Ok(Self::String(value.value())),
+            _ => Err(darling::Error::unexpected_lit_type(value)),
+        }
+    }
+
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        #[derive(FromMeta)]
+        struct ExprArgs {
+            expr: String,
+        }
+
+        let args = ExprArgs::from_list(items)?;
+        Ok(Self::Expr(args.expr))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -408,9 +836,21 @@ pub struct ForeignKeySpec {
     pub to_model: syn::Type,
     pub on_delete: Option<ForeignKeyOnDeletePolicy>,
     pub on_update: Option<ForeignKeyOnUpdatePolicy>,
+    /// The columns the foreign key is stored in if the referenced model has a
+    /// composite primary key, as declared with the
+    /// `#[model(foreign_key(columns(...)))]` attribute. Empty if the foreign key
+    /// is stored in a single column.
+    pub columns: Vec<String>,
 }
 
 impl ForeignKeySpec {
+    /// Returns whether the foreign key references a model with a composite
+    /// primary key, and so is stored in multiple columns.
+    #[must_use]
+    pub fn is_composite(&self) -> bool {
+        !self.columns.is_empty()
+    }
+
     pub fn set_on_delete(&mut self, on_delete: ForeignKeyOnDeletePolicy) -> &mut Self {
         self.on_delete = Some(on_delete);
         self
@@ -425,48 +865,269 @@ impl TryFrom<syn::Type> for ForeignKeySpec {
     type Error = syn::Error;
 
     fn try_from(ty: syn::Type) -> Result<Self, Self::Error> {
-        let syn::Type::Path(type_path) = &ty else {
-            panic!("Expected a path type for a foreign key");
-        };
+        Ok(Self {
+            to_model: single_generic_argument(&ty, "ForeignKey")?,
+            on_delete: None,
+            on_update: None,
+            columns: Vec::new(),
+        })
+    }
+}
 
-        let syn::PathArguments::AngleBracketed(args) = &type_path
-            .path
-            .segments
-            .last()
-            .expect("type path must have at least one segment")
-            .arguments
-        else {
-            return Err(syn::Error::new(
-                ty.span(),
-                "expected ForeignKey to have angle-bracketed generic arguments",
-            ));
+/// An index declared with the `#[model(index)]` field attribute, or the
+/// `#[model(index(...))]` model attribute.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IndexSpec {
+    pub columns: Vec<String>,
+}
+
+impl IndexSpec {
+    /// Returns the name of the index in the database.
+    ///
+    /// `table_name` is the full (i.e. possibly prefixed with the app name)
+    /// name of the table the index is created on.
+    #[must_use]
+    pub fn name(&self, table_name: &str) -> String {
+        truncate_identifier(format!("{table_name}_{}_idx", self.columns.join("_")))
+    }
+}
+
+/// A constraint declared with the `#[model(unique_together(...))]` or
+/// `#[model(check(...))]` model attribute, or implied by a composite foreign
+/// key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ConstraintSpec {
+    /// The combination of values of the columns must be unique.
+    Unique { columns: Vec<String> },
+    /// The SQL expression must hold for every row.
+    Check { name: String, expr: String },
+    /// The columns of a foreign key referencing a model with a composite
+    /// primary key.
+    ForeignKey {
+        columns: Vec<String>,
+        to_model: Box<syn::Type>,
+        on_delete: ForeignKeyOnDeletePolicy,
+        on_update: ForeignKeyOnUpdatePolicy,
+    },
+}
+
+impl ConstraintSpec {
+    /// Returns the name of the constraint in the database.
+    ///
+    /// `table_name` is the full (i.e. possibly prefixed with the app name)
+    /// name of the table the constraint is created on.
+    #[must_use]
+    pub fn name(&self, table_name: &str) -> String {
+        let name = match self {
+            Self::Unique { columns } => format!("{table_name}_{}_uniq", columns.join("_")),
+            Self::Check { name, .. } => format!("{table_name}_{name}"),
+            Self::ForeignKey { columns, .. } => format!("{table_name}_{}_fk", columns.join("_")),
         };
+        truncate_identifier(name)
+    }
+}
 
-        if args.args.len() != 1 {
-            return Err(syn::Error::new(
-                ty.span(),
-                "expected ForeignKey to have only one generic parameter",
-            ));
+/// The maximum length of the generated index and constraint names.
+///
+/// This is the limit of PostgreSQL, which is the strictest one among the
+/// supported databases (MySQL allows 64 characters), so that the names are
+/// the same everywhere.
+const MAX_IDENTIFIER_LENGTH: usize = 63;
+
+/// Shortens a generated index or constraint name that's longer than
+/// [`MAX_IDENTIFIER_LENGTH`] by replacing its end with a hash of the full
+/// name, so that the names sharing a long prefix stay distinct.
+fn truncate_identifier(name: String) -> String {
+    if name.len() <= MAX_IDENTIFIER_LENGTH {
+        return name;
+    }
+
+    let hash = format!("{:08x}", fnv1a_hash(&name));
+    let mut end = MAX_IDENTIFIER_LENGTH - hash.len() - 1;
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}_{hash}", &name[..end])
+}
+
+/// Computes the 32-bit FNV-1a hash of the string. Unlike the hashers from the
+/// standard library, it's guaranteed to stay the same across Rust versions,
+/// which is needed for the names stored in migrations.
+fn fnv1a_hash(value: &str) -> u32 {
+    value.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// A reverse accessor declared on a foreign key field with the
+/// `#[model(related_name = "...")]` attribute.
+///
+/// The accessor is generated on the model referenced by the foreign key (the
+/// source model) and returns the instances of the model containing the field.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReverseRelationSpec {
+    /// The name of the accessor method.
+    pub name: syn::Ident,
+    pub field_name: syn::Ident,
+    pub column_name: String,
+    /// The model referenced by the foreign key, on which the accessor is
+    /// generated.
+    pub source_model: syn::Type,
+}
+
+/// Returns the only generic type argument of given path type, such as `T` in
+/// `ForeignKey<T>`.
+fn single_generic_argument(ty: &syn::Type, type_name: &str) -> Result<syn::Type, syn::Error> {
+    let syn::Type::Path(type_path) = ty else {
+        panic!("Expected a path type for {type_name}");
+    };
+
+    let syn::PathArguments::AngleBracketed(args) = &type_path
+        .path
+        .segments
+        .last()
+        .expect("type path must have at least one segment")
+        .arguments
+    else {
+        return Err(syn::Error::new(
+            ty.span(),
+            format!("expected {type_name} to have angle-bracketed generic arguments"),
+        ));
+    };
+
+    if args.args.len() != 1 {
+        return Err(syn::Error::new(
+            ty.span(),
+            format!("expected {type_name} to have only one generic parameter"),
+        ));
+    }
+
+    if let syn::GenericArgument::Type(inner) = &args.args[0] {
+        Ok(inner.clone())
+    } else {
+        Err(syn::Error::new(
+            ty.span(),
+            format!("expected {type_name} to have a type generic argument"),
+        ))
+    }
+}
+
+/// A `ManyToMany<T>` field of a model.
+///
+/// Many-to-many fields are not stored in the model's table; instead, each
+/// of them is backed by a separate [`JoinTable`] that links the primary keys
+/// of both models.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ManyToManyField {
+    pub name: syn::Ident,
+    pub column_name: String,
+    pub ty: syn::Type,
+    /// The type of the model on the other side of the relation.
+    pub to_model: syn::Type,
+}
+
+impl ManyToManyField {
+    /// Returns the join table backing this field.
+    ///
+    /// `model_name` is the original name of the model that contains the
+    /// field, and `table_name` is the full (i.e. possibly prefixed with the
+    /// app name) name of its table.
+    #[must_use]
+    pub fn join_table(&self, model_name: &str, table_name: &str) -> JoinTable {
+        let source_name = model_name.to_snake_case();
+        let target_name = match &self.to_model {
+            syn::Type::Path(type_path) => type_path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.unraw().to_string().to_snake_case()),
+            _ => None,
         }
+        .unwrap_or_else(|| "target".to_owned());
 
-        let inner = &args.args[0];
-        if let syn::GenericArgument::Type(ty) = inner {
-            Ok(Self {
-                to_model: ty.clone(),
-                on_delete: None,
-                on_update: None,
-            })
+        let (source_column, target_column) = if source_name == target_name {
+            (format!("from_{source_name}"), format!("to_{target_name}"))
         } else {
-            Err(syn::Error::new(
-                ty.span(),
-                "expected ForeignKey to have a type generic argument",
-            ))
+            (source_name, target_name)
+        };
+
+        JoinTable {
+            table_name: format!("{table_name}_{}", self.column_name),
+            source_column,
+            target_column,
+        }
+    }
+}
+
+/// The table storing the links of a [`ManyToManyField`].
+///
+/// Each row of the table links a single instance of the model containing the
+/// field (the source) with a single instance of the related model (the
+/// target).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JoinTable {
+    pub table_name: String,
+    pub source_column: String,
+    pub target_column: String,
+}
+
+impl JoinTable {
+    /// Returns the columns of the join table: an auto-incremented primary
+    /// key, and foreign keys to the source and the target models.
+    ///
+    /// Both foreign keys cascade, so that the links are removed together with
+    /// any of the linked model instances. The pair of the foreign keys is
+    /// made unique by a separate constraint (see
+    /// [`JoinTable::unique_constraint`]).
+    #[must_use]
+    pub fn fields(&self, source_model: &syn::Type, target_model: &syn::Type) -> Vec<Field> {
+        let foreign_key = |name: &str, to_model: &syn::Type| Field {
+            // the column names are derived from model names, which might be keywords
+            name: syn::parse_str(name)
+                .unwrap_or_else(|_| syn::Ident::new_raw(name, proc_macro2::Span::call_site())),
+            column_name: name.to_owned(),
+            ty: syn::parse_quote!(::cot::db::ForeignKey<#to_model>),
+            auto_value: false,
+            primary_key: false,
+            foreign_key: Some(ForeignKeySpec {
+                to_model: to_model.clone(),
+                on_delete: Some(ForeignKeyOnDeletePolicy::Cascade),
+                on_update: Some(ForeignKeyOnUpdatePolicy::Cascade),
+                columns: Vec::new(),
+            }),
+            unique: false,
+            default: None,
+        };
+
+        vec![
+            Field {
+                name: syn::Ident::new("id", proc_macro2::Span::call_site()),
+                column_name: "id".to_owned(),
+                ty: syn::parse_quote!(::cot::db::Auto<i64>),
+                auto_value: true,
+                primary_key: true,
+                foreign_key: None,
+                unique: false,
+                default: None,
+            },
+            foreign_key(&self.source_column, source_model),
+            foreign_key(&self.target_column, target_model),
+        ]
+    }
+
+    /// Returns the unique constraint on the pair of the foreign keys, so that
+    /// the same instances can't be linked more than once.
+    #[must_use]
+    pub fn unique_constraint(&self) -> ConstraintSpec {
+        ConstraintSpec::Unique {
+            columns: vec![self.source_column.clone(), self.target_column.clone()],
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use quote::format_ident;
     use syn::parse_quote;
 
     use super::*;
@@ -546,6 +1207,103 @@ mod tests {
         assert_eq!(model.field_count(), 2);
     }
 
+    #[test]
+    fn model_opts_as_model_renamed_from() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct TestModel {
+                #[model(primary_key)]
+                id: i32,
+                #[model(renamed_from = "name", field_name = "title_col")]
+                title: String,
+            }
+        };
+        let opts = ModelOpts::new_from_derive_input(&input).unwrap();
+        let args = ModelArgs::from_meta(&parse_quote!(model(renamed_from = "OldModel"))).unwrap();
+        let model = opts.as_model(&args, &SymbolResolver::new(vec![])).unwrap();
+        assert_eq!(model.renamed_from.as_deref(), Some("OldModel"));
+        assert_eq!(
+            model.renamed_fields,
+            BTreeMap::from([("title_col".to_owned(), "name".to_owned())])
+        );
+    }
+
+    #[test]
+    fn model_opts_as_model_indexes_and_constraints() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct TestModel {
+                #[model(primary_key)]
+                id: i32,
+                #[model(index)]
+                name: String,
+                #[model(field_name = "last_name_col")]
+                last_name: String,
+                price: i32,
+            }
+        };
+        let opts = ModelOpts::new_from_derive_input(&input).unwrap();
+        let args = ModelArgs::from_meta(&parse_quote!(model(
+            index(last_name, name),
+            unique_together(name, last_name),
+            check(name = "price_positive", expr = "price > 0"),
+        )))
+        .unwrap();
+        let model = opts.as_model(&args, &SymbolResolver::new(vec![])).unwrap();
+
+        assert_eq!(
+            model.indexes,
+            vec![
+                IndexSpec {
+                    columns: vec!["name".to_owned()],
+                },
+                IndexSpec {
+                    columns: vec!["last_name_col".to_owned(), "name".to_owned()],
+                },
+            ]
+        );
+        assert_eq!(
+            model.constraints,
+            vec![
+                ConstraintSpec::Unique {
+                    columns: vec!["name".to_owned(), "last_name_col".to_owned()],
+                },
+                ConstraintSpec::Check {
+                    name: "price_positive".to_owned(),
+                    expr: "price > 0".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            model.indexes[1].name("app__test_model"),
+            "app__test_model_last_name_col_name_idx"
+        );
+        assert_eq!(
+            model.constraints[0].name("app__test_model"),
+            "app__test_model_name_last_name_col_uniq"
+        );
+        assert_eq!(
+            model.constraints[1].name("app__test_model"),
+            "app__test_model_price_positive"
+        );
+    }
+
+    #[test]
+    fn model_opts_as_model_index_unknown_field() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct TestModel {
+                #[model(primary_key)]
+                id: i32,
+                name: String,
+            }
+        };
+        let opts = ModelOpts::new_from_derive_input(&input).unwrap();
+        let args = ModelArgs::from_meta(&parse_quote!(model(index(name, title)))).unwrap();
+        let err = opts
+            .as_model(&args, &SymbolResolver::new(vec![]))
+            .unwrap_err();
+
+        assert!(err.to_string().starts_with("unknown field `title`"));
+    }
+
     #[test]
     fn model_opts_raw_name() {
         let input: syn::DeriveInput = parse_quote! {
@@ -633,12 +1391,57 @@ mod tests {
         };
         let opts = ModelOpts::new_from_derive_input(&input).unwrap();
         let args = ModelArgs::default();
+        let model = opts.as_model(&args, &SymbolResolver::new(vec![])).unwrap();
+        let pk_names: Vec<_> = model
+            .pk_fields
+            .iter()
+            .map(|field| field.name.to_string())
+            .collect();
+        assert_eq!(pk_names, ["id", "id_2"]);
+    }
+
+    #[test]
+    fn model_opts_as_model_composite_pk_auto() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[model]
+            struct TestModel {
+                #[model(primary_key)]
+                id: cot::db::Auto<i64>,
+                #[model(primary_key)]
+                id_2: i64,
+            }
+        };
+        let opts = ModelOpts::new_from_derive_input(&input).unwrap();
+        let args = ModelArgs::default();
         let err = opts
             .as_model(&args, &SymbolResolver::new(vec![]))
             .unwrap_err();
         assert_eq!(
             err.to_string(),
-            "composite primary keys are not supported; only one primary key field is allowed"
+            "`Auto<T>` fields cannot be a part of a composite primary key"
+        );
+    }
+
+    #[test]
+    fn model_opts_as_model_composite_pk_many_to_many() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[model]
+            struct TestModel {
+                #[model(primary_key)]
+                id: i64,
+                #[model(primary_key)]
+                id_2: i64,
+                tags: ManyToMany<Tag>,
+            }
+        };
+        let opts = ModelOpts::new_from_derive_input(&input).unwrap();
+        let args = ModelArgs::default();
+        let err = opts
+            .as_model(&args, &SymbolResolver::new(vec![]))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`ManyToMany<T>` fields are not supported in models with a composite primary key"
         );
     }
 
@@ -685,6 +1488,55 @@ mod tests {
         assert_eq!(field.column_name, "test_field");
     }
 
+    #[test]
+    fn field_opts_default() {
+        let cases: [(syn::Field, DefaultSpec); 6] = [
+            (
+                parse_quote!(#[model(default = true)] active: bool),
+                DefaultSpec::Bool(true),
+            ),
+            (
+                parse_quote!(#[model(default = -5)] count: i32),
+                DefaultSpec::Int(-5),
+            ),
+            (
+                parse_quote!(#[model(default = 1.5)] ratio: f64),
+                DefaultSpec::Float("1.5".to_string()),
+            ),
+            (
+                parse_quote!(#[model(default = -0.5)] ratio: f64),
+                DefaultSpec::Float("-0.5".to_string()),
+            ),
+            (
+                parse_quote!(#[model(default = "draft")] status: String),
+                DefaultSpec::String("draft".to_string()),
+            ),
+            (
+                parse_quote!(#[model(default(expr = "CURRENT_TIMESTAMP"))] created_at: Auto<NaiveDateTime>),
+                DefaultSpec::Expr("CURRENT_TIMESTAMP".to_string()),
+            ),
+        ];
+
+        for (input, expected) in cases {
+            let field_opts = FieldOpts::from_field(&input).unwrap();
+            let field = field_opts
+                .as_field(&SymbolResolver::new(vec![]), Some(&"TestModel".to_string()))
+                .unwrap();
+            assert_eq!(field.default, Some(expected));
+            // the database fills in the default instead of an auto-incrementing value
+            assert!(!field.auto_value);
+        }
+    }
+
+    #[test]
+    fn field_opts_default_invalid() {
+        let input: syn::Field = parse_quote! {
+            #[model(default = b'a')]
+            letter: u8
+        };
+        assert!(FieldOpts::from_field(&input).is_err());
+    }
+
     assert_foreign_key_policies!(
         field_opts_foreign_key_restrict_restrict,
         ForeignKey<Foo>,
@@ -805,6 +1657,352 @@ mod tests {
         );
     }
 
+    #[test]
+    fn model_opts_as_model_many_to_many() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Post {
+                #[model(primary_key)]
+                id: i32,
+                tags: ManyToMany<Tag>,
+            }
+        };
+        let opts = ModelOpts::new_from_derive_input(&input).unwrap();
+        let model = opts
+            .as_model(&ModelArgs::default(), &SymbolResolver::new(vec![]))
+            .unwrap();
+
+        assert_eq!(model.field_count(), 1);
+        assert_eq!(model.many_to_many_fields.len(), 1);
+        let field = &model.many_to_many_fields[0];
+        assert_eq!(field.name.to_string(), "tags");
+        assert_eq!(field.column_name, "tags");
+        assert_eq!(field.to_model, parse_quote!(Tag));
+    }
+
+    #[test]
+    fn field_opts_as_many_to_many_field_not_many_to_many() {
+        let input: syn::Field = parse_quote! {
+            tags: Vec<Tag>
+        };
+        let field_opts = FieldOpts::from_field(&input).unwrap();
+        let field = field_opts
+            .as_many_to_many_field(&SymbolResolver::new(vec![]), Some(&"Post".to_string()))
+            .unwrap();
+
+        assert!(field.is_none());
+    }
+
+    #[test]
+    fn field_opts_as_many_to_many_field_rejects_unique() {
+        let input: syn::Field = parse_quote! {
+            #[model(unique)]
+            tags: ManyToMany<Tag>
+        };
+        let field_opts = FieldOpts::from_field(&input).unwrap();
+        let err = field_opts
+            .as_many_to_many_field(&SymbolResolver::new(vec![]), Some(&"Post".to_string()))
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "`ManyToMany<T>` fields cannot be primary keys, unique, or foreign keys"
+        );
+    }
+
+    #[test]
+    fn model_opts_as_model_reverse_relation() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Post {
+                #[model(primary_key)]
+                id: i32,
+                #[model(related_name = "posts", field_name = "author_id")]
+                author: Option<ForeignKey<User>>,
+            }
+        };
+        let opts = ModelOpts::new_from_derive_input(&input).unwrap();
+        let model = opts
+            .as_model(&ModelArgs::default(), &SymbolResolver::new(vec![]))
+            .unwrap();
+
+        assert_eq!(model.field_count(), 2);
+        assert_eq!(
+            model.reverse_relations,
+            vec![ReverseRelationSpec {
+                name: format_ident!("posts"),
+                field_name: format_ident!("author"),
+                column_name: "author_id".to_string(),
+                source_model: parse_quote!(User),
+            }]
+        );
+    }
+
+    #[test]
+    fn model_opts_as_model_reverse_relation_migration() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct _Post {
+                #[model(primary_key)]
+                id: i32,
+                #[model(related_name = "posts")]
+                author: ForeignKey<User>,
+            }
+        };
+        let opts = ModelOpts::new_from_derive_input(&input).unwrap();
+        let args = ModelArgs {
+            model_type: ModelType::Migration,
+            ..Default::default()
+        };
+        let model = opts.as_model(&args, &SymbolResolver::new(vec![])).unwrap();
+
+        assert!(model.reverse_relations.is_empty());
+    }
+
+    #[test]
+    fn model_opts_as_model_composite_foreign_key() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Grade {
+                #[model(primary_key)]
+                id: i32,
+                #[model(
+                    foreign_key(columns(course_code, course_year), on_delete = "cascade"),
+                    unique,
+                    index
+                )]
+                course: ForeignKey<Course>,
+            }
+        };
+        let opts = ModelOpts::new_from_derive_input(&input).unwrap();
+        let model = opts
+            .as_model(&ModelArgs::default(), &SymbolResolver::new(vec![]))
+            .unwrap();
+
+        let course = &model.fields[1];
+        assert_eq!(course.column_name, "course");
+        assert!(!course.unique);
+        assert_eq!(
+            course.column_names(),
+            vec!["course_code".to_owned(), "course_year".to_owned()]
+        );
+        assert_eq!(
+            model.indexes,
+            vec![IndexSpec {
+                columns: vec!["course_code".to_owned(), "course_year".to_owned()],
+            }]
+        );
+        assert_eq!(
+            model.constraints,
+            vec![
+                ConstraintSpec::Unique {
+                    columns: vec!["course_code".to_owned(), "course_year".to_owned()],
+                },
+                ConstraintSpec::ForeignKey {
+                    columns: vec!["course_code".to_owned(), "course_year".to_owned()],
+                    to_model: Box::new(parse_quote!(Course)),
+                    on_delete: ForeignKeyOnDeletePolicy::Cascade,
+                    on_update: ForeignKeyOnUpdatePolicy::Cascade,
+                },
+            ]
+        );
+        assert_eq!(
+            model.constraints[1].name("app__grade"),
+            "app__grade_course_code_course_year_fk"
+        );
+    }
+
+    #[test]
+    fn long_index_and_constraint_names_are_truncated() {
+        let table_name = "my_application__very_long_model_name_for_testing";
+        let columns = vec![
+            "first_very_long_column_name".to_owned(),
+            "second_very_long_column_name".to_owned(),
+        ];
+        let index = IndexSpec {
+            columns: columns.clone(),
+        };
+        let unique = ConstraintSpec::Unique { columns };
+        let other_unique = ConstraintSpec::Unique {
+            columns: vec![
+                "first_very_long_column_name".to_owned(),
+                "third_very_long_column_name".to_owned(),
+            ],
+        };
+
+        let index_name = index.name(table_name);
+        let unique_name = unique.name(table_name);
+
+        assert_eq!(index_name.len(), 63);
+        assert_eq!(unique_name.len(), 63);
+        assert!(index_name.starts_with("my_application__very_long_model_name_for_testing_first"));
+        assert_ne!(index_name, unique_name);
+        assert_ne!(unique_name, other_unique.name(table_name));
+        // the names must be stable, as they are stored in the migrations
+        assert_eq!(index_name, index.name(table_name));
+        assert_eq!(
+            index_name,
+            format!(
+                "my_application__very_long_model_name_for_testing_first_{:08x}",
+                fnv1a_hash(
+                    "my_application__very_long_model_name_for_testing_\
+                     first_very_long_column_name_second_very_long_column_name_idx"
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn short_index_and_constraint_names_are_kept() {
+        let name = ConstraintSpec::Check {
+            name: "price_positive".to_owned(),
+            expr: "price > 0".to_owned(),
+        }
+        .name("app__item");
+
+        assert_eq!(name, "app__item_price_positive");
+    }
+
+    #[test]
+    fn fnv1a_hash_known_values() {
+        assert_eq!(fnv1a_hash(""), 0x811c_9dc5);
+        assert_eq!(fnv1a_hash("a"), 0xe40c_292c);
+    }
+
+    #[test]
+    fn field_opts_composite_foreign_key_single_column() {
+        let input: syn::Field = parse_quote! {
+            #[model(foreign_key(columns(course_code)))]
+            course: ForeignKey<Course>
+        };
+        let field_opts = FieldOpts::from_field(&input).unwrap();
+        let err = field_opts
+            .as_field(&SymbolResolver::new(vec![]), None)
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "`#[model(foreign_key(columns(...)))]` must list at least two columns; \
+            use `#[model(field_name = \"...\")]` to rename a single column"
+        );
+    }
+
+    #[test]
+    fn field_opts_composite_foreign_key_optional() {
+        let input: syn::Field = parse_quote! {
+            #[model(foreign_key(columns(course_code, course_year)))]
+            course: Option<ForeignKey<Course>>
+        };
+        let field_opts = FieldOpts::from_field(&input).unwrap();
+        let err = field_opts
+            .as_field(&SymbolResolver::new(vec![]), None)
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "composite foreign keys cannot be optional");
+    }
+
+    #[test]
+    fn field_opts_composite_foreign_key_related_name() {
+        let input: syn::Field = parse_quote! {
+            #[model(foreign_key(columns(course_code, course_year)), related_name = "grades")]
+            course: ForeignKey<Course>
+        };
+        let field_opts = FieldOpts::from_field(&input).unwrap();
+        let err = field_opts
+            .as_field(&SymbolResolver::new(vec![]), None)
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "composite foreign keys cannot have a `related_name`"
+        );
+    }
+
+    #[test]
+    fn field_opts_as_reverse_relation_not_foreign_key() {
+        let input: syn::Field = parse_quote! {
+            #[model(related_name = "posts")]
+            author: i32
+        };
+        let field_opts = FieldOpts::from_field(&input).unwrap();
+        let field = field_opts
+            .as_field(&SymbolResolver::new(vec![]), Some(&"Post".to_string()))
+            .unwrap();
+        let err = field_opts.as_reverse_relation(&field).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "`#[model(related_name = \"...\")]` can only be used on `ForeignKey<T>` fields"
+        );
+    }
+
+    #[test]
+    fn field_opts_as_reverse_relation_invalid_name() {
+        let input: syn::Field = parse_quote! {
+            #[model(related_name = "all posts")]
+            author: ForeignKey<User>
+        };
+        let field_opts = FieldOpts::from_field(&input).unwrap();
+        let field = field_opts
+            .as_field(&SymbolResolver::new(vec![]), Some(&"Post".to_string()))
+            .unwrap();
+        let err = field_opts.as_reverse_relation(&field).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "`related_name` must be a valid identifier, got `all posts`"
+        );
+    }
+
+    #[test]
+    fn many_to_many_field_join_table() {
+        let input: syn::Field = parse_quote! {
+            #[model(field_name = "labels")]
+            tags: cot::db::ManyToMany<crate::models::Tag>
+        };
+        let field_opts = FieldOpts::from_field(&input).unwrap();
+        let field = field_opts
+            .as_many_to_many_field(&SymbolResolver::new(vec![]), Some(&"Post".to_string()))
+            .unwrap()
+            .unwrap();
+
+        let join_table = field.join_table("BlogPost", "app__blog_post");
+
+        assert_eq!(join_table.table_name, "app__blog_post_labels");
+        assert_eq!(join_table.source_column, "blog_post");
+        assert_eq!(join_table.target_column, "tag");
+
+        let fields = join_table.fields(&parse_quote!(BlogPost), &parse_quote!(Tag));
+        assert_eq!(fields.len(), 3);
+        assert!(fields[0].primary_key);
+        assert_eq!(
+            fields[1].foreign_key.as_ref().unwrap().to_model,
+            parse_quote!(BlogPost)
+        );
+        assert_eq!(
+            fields[2].foreign_key.as_ref().unwrap().to_model,
+            parse_quote!(Tag)
+        );
+        assert_eq!(
+            join_table.unique_constraint(),
+            ConstraintSpec::Unique {
+                columns: vec!["blog_post".to_owned(), "tag".to_owned()],
+            }
+        );
+    }
+
+    #[test]
+    fn many_to_many_field_join_table_self_referencing() {
+        let field = ManyToManyField {
+            name: parse_quote!(friends),
+            column_name: "friends".to_owned(),
+            ty: parse_quote!(ManyToMany<Person>),
+            to_model: parse_quote!(Person),
+        };
+
+        let join_table = field.join_table("Person", "app__person");
+
+        assert_eq!(join_table.table_name, "app__person_friends");
+        assert_eq!(join_table.source_column, "from_person");
+        assert_eq!(join_table.target_column, "to_person");
+    }
+
     #[test]
     fn find_type_resolved() {
         let input: syn::Type =
@@ -829,8 +2027,12 @@ mod tests {
             ty: parse_quote! { MyContainer<std::string::String> },
             primary_key: darling::util::Flag::default(),
             unique: darling::util::Flag::default(),
+            index: darling::util::Flag::default(),
             field_name: None,
             foreign_key: None,
+            related_name: None,
+            renamed_from: None,
+            default: None,
         };
 
         assert!(opts.find_type("my_crate::MyContainer", &resolver).is_some());
@@ -0,0 +1,98 @@
+use cot_codegen::db_enum::{DbEnum, DbEnumOpts, DbEnumStorage, DbEnumValue};
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+
+use crate::model::orm_ident;
+
+pub(super) fn impl_db_enum_for_enum(ast: &syn::DeriveInput) -> TokenStream {
+    let opts = match DbEnumOpts::from_derive_input(ast) {
+        Ok(val) => val,
+        Err(err) => {
+            return err.write_errors();
+        }
+    };
+    let db_enum = match opts.as_db_enum() {
+        Ok(val) => val,
+        Err(err) => {
+            return err.to_compile_error();
+        }
+    };
+
+    DbEnumDeriveBuilder { db_enum }.to_token_stream()
+}
+
+#[derive(Debug)]
+struct DbEnumDeriveBuilder {
+    db_enum: DbEnum,
+}
+
+impl ToTokens for DbEnumDeriveBuilder {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let orm_ident = orm_ident();
+        let name = &self.db_enum.name;
+
+        let repr = match self.db_enum.storage {
+            DbEnumStorage::Text => quote!(::std::string::String),
+            DbEnumStorage::Integer => quote!(::core::primitive::i32),
+        };
+        let to_repr_arms = self.db_enum.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            match &variant.value {
+                DbEnumValue::Text(value) => {
+                    quote!(Self::#ident => ::std::string::String::from(#value),)
+                }
+                DbEnumValue::Integer(value) => quote!(Self::#ident => #value,),
+            }
+        });
+        let from_repr_arms = self.db_enum.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            match &variant.value {
+                DbEnumValue::Text(value) => {
+                    quote!(#value => ::core::option::Option::Some(Self::#ident),)
+                }
+                DbEnumValue::Integer(value) => {
+                    quote!(#value => ::core::option::Option::Some(Self::#ident),)
+                }
+            }
+        });
+        let repr_expr = match self.db_enum.storage {
+            DbEnumStorage::Text => quote!(::std::string::String::as_str(repr)),
+            DbEnumStorage::Integer => quote!(*repr),
+        };
+
+        let new_tokens = quote! {
+            #[automatically_derived]
+            impl #orm_ident::DbEnum for #name {
+                type Repr = #repr;
+
+                fn to_repr(&self) -> Self::Repr {
+                    match self {
+                        #(#to_repr_arms)*
+                    }
+                }
+
+                fn from_repr(repr: &Self::Repr) -> ::core::option::Option<Self> {
+                    match #repr_expr {
+                        #(#from_repr_arms)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl #orm_ident::DatabaseField for #name {
+                const TYPE: #orm_ident::ColumnType =
+                    <#repr as #orm_ident::DatabaseField>::TYPE;
+            }
+
+            #[automatically_derived]
+            impl #orm_ident::ToDbValue for #name {
+                fn to_db_value(&self) -> #orm_ident::DbValue {
+                    #orm_ident::ToDbValue::to_db_value(&#orm_ident::DbEnum::to_repr(self))
+                }
+            }
+        };
+        new_tokens.to_tokens(tokens);
+    }
+}
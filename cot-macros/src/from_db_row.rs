@@ -0,0 +1,43 @@
+use darling::Error;
+use quote::quote;
+use syn::{Data, Fields};
+
+use crate::cot_ident;
+
+pub(super) fn impl_from_db_row_for_struct(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    let struct_name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let cot = cot_ident();
+
+    let Data::Struct(data_struct) = &ast.data else {
+        return Error::custom("Only structs can derive `FromDbRow`").write_errors();
+    };
+
+    // the fields are read from the columns at the same positions in the row
+    let initializers = data_struct.fields.iter().enumerate().map(|(index, field)| {
+        let field_type = &field.ty;
+        let value = quote! { db_row.get::<#field_type>(#index)? };
+        if let Some(field_name) = &field.ident {
+            quote! { #field_name: #value, }
+        } else {
+            quote! { #value, }
+        }
+    });
+
+    let constructor = match &data_struct.fields {
+        Fields::Named(_) => quote! { Self { #(#initializers)* } },
+        Fields::Unnamed(_) => quote! { Self(#(#initializers)*) },
+        Fields::Unit => {
+            return Error::custom("Unit structs cannot derive `FromDbRow`").write_errors();
+        }
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #cot::db::FromDbRow for #struct_name #ty_generics #where_clause {
+            fn from_db_row(db_row: #cot::db::Row) -> #cot::db::Result<Self> {
+                Ok(#constructor)
+            }
+        }
+    }
+}
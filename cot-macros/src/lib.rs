@@ -6,8 +6,10 @@
 mod admin;
 mod api_response_enum;
 mod cache;
+mod db_enum;
 mod dbtest;
 mod form;
+mod from_db_row;
 mod from_request;
 mod main_fn;
 mod migration_op;
@@ -25,8 +27,10 @@ use syn::{DeriveInput, ItemFn, parse_macro_input};
 
 use crate::admin::impl_admin_model_for_struct;
 use crate::api_response_enum::{impl_api_operation_response_for_enum, impl_into_response_for_enum};
+use crate::db_enum::impl_db_enum_for_enum;
 use crate::dbtest::fn_to_dbtest;
 use crate::form::impl_form_for_struct;
+use crate::from_db_row::impl_from_db_row_for_struct;
 use crate::from_request::impl_from_request_head_for_struct;
 use crate::main_fn::{fn_to_cot_e2e_test, fn_to_cot_main, fn_to_cot_test};
 use crate::migration_op::fn_to_migration_op;
@@ -199,6 +203,20 @@ pub fn derive_from_request_head(input: TokenStream) -> TokenStream {
     token_stream.into()
 }
 
+#[proc_macro_derive(FromDbRow)]
+pub fn derive_from_db_row(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let token_stream = impl_from_db_row_for_struct(&ast);
+    token_stream.into()
+}
+
+#[proc_macro_derive(DbEnum, attributes(db_enum))]
+pub fn derive_db_enum(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let token_stream = impl_db_enum_for_enum(&ast);
+    token_stream.into()
+}
+
 #[proc_macro_derive(SelectChoice, attributes(select_choice))]
 pub fn derive_select_choice(input: TokenStream) -> TokenStream {
     let ast = syn::parse_macro_input!(input as DeriveInput);
@@ -1,4 +1,7 @@
-use cot_codegen::model::{Field, Model, ModelArgs, ModelOpts, ModelType};
+use cot_codegen::model::{
+    Field, ForeignKeySpec, ManyToManyField, Model, ModelArgs, ModelOpts, ModelType,
+    ReverseRelationSpec,
+};
 use cot_codegen::symbol_resolver::{SymbolResolver, VisibleSymbol, VisibleSymbolKind};
 use darling::FromMeta;
 use darling::ast::NestedMeta;
@@ -76,19 +79,26 @@ struct ModelBuilder {
     vis: syn::Visibility,
     table_name: String,
     model_type: ModelType,
-    pk_field: Field,
+    pk_fields: Vec<Field>,
     fields_struct_name: Ident,
     fields_as_columns: Vec<TokenStream>,
     fields_as_from_db: Vec<TokenStream>,
     fields_as_update_from_db: Vec<TokenStream>,
     fields_as_get_values: Vec<TokenStream>,
+    fields_as_fill_related: Vec<TokenStream>,
+    fields_as_many_to_many_new: Vec<TokenStream>,
+    fields_as_bind_many_to_many: Vec<TokenStream>,
     fields_as_field_refs: Vec<TokenStream>,
+    fields_as_assertions: Vec<TokenStream>,
+    reverse_relations: Vec<TokenStream>,
 }
 
 impl ToTokens for ModelBuilder {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         tokens.append_all(self.build_model_impl());
         tokens.append_all(self.build_fields_struct());
+        tokens.append_all(&self.fields_as_assertions);
+        tokens.append_all(&self.reverse_relations);
     }
 }
 
@@ -111,22 +121,42 @@ impl ModelBuilder {
             vis: model.vis,
             table_name,
             model_type: model.model_type,
-            pk_field: model.pk_field.clone(),
+            pk_fields: model.pk_fields.clone(),
             fields_struct_name: format_ident!("{}Fields", model.name),
             fields_as_columns: Vec::with_capacity(field_count),
             fields_as_from_db: Vec::with_capacity(field_count),
             fields_as_update_from_db: Vec::with_capacity(field_count),
             fields_as_get_values: Vec::with_capacity(field_count),
+            fields_as_fill_related: Vec::new(),
+            fields_as_many_to_many_new: Vec::with_capacity(model.many_to_many_fields.len()),
+            fields_as_bind_many_to_many: Vec::with_capacity(model.many_to_many_fields.len()),
             fields_as_field_refs: Vec::with_capacity(field_count),
+            fields_as_assertions: Vec::new(),
+            reverse_relations: Vec::with_capacity(model.reverse_relations.len()),
         };
         for field in &model.fields {
             model_builder.push_field(field);
         }
+        for field in &model.many_to_many_fields {
+            model_builder.push_many_to_many_field(&model.original_name, field);
+        }
+        for reverse_relation in &model.reverse_relations {
+            model_builder.push_reverse_relation(reverse_relation);
+        }
 
         model_builder
     }
 
     fn push_field(&mut self, field: &Field) {
+        if field
+            .foreign_key
+            .as_ref()
+            .is_some_and(ForeignKeySpec::is_composite)
+        {
+            self.push_composite_foreign_key_field(field);
+            return;
+        }
+
         let orm_ident = orm_ident();
 
         let name = &field.name;
@@ -142,7 +172,7 @@ impl ModelBuilder {
         }
 
         self.fields_as_from_db.push(quote!(
-            #name: db_row.get::<#ty>(#index)?
+            #name: db_row.get::<#ty>(offset + #index)?
         ));
 
         self.fields_as_update_from_db.push(quote!(
@@ -153,6 +183,12 @@ impl ModelBuilder {
             #index => &self.#name as &dyn #orm_ident::ToDbFieldValue
         ));
 
+        if field.foreign_key.is_some() {
+            self.fields_as_fill_related.push(quote!(
+                #column_name => #orm_ident::ForeignKeyField::fill_from_db(&mut self.#name, db_row, offset)
+            ));
+        }
+
         self.fields_as_field_refs.push(quote!(
             #[doc = concat!("Field reference to [`", stringify!(#name), "::", stringify!(#column_name), "`].")]
             pub const #name: #orm_ident::query::expr::FieldRef<#ty> =
@@ -160,6 +196,139 @@ impl ModelBuilder {
         ));
     }
 
+    /// Pushes a foreign key field referencing a model with a composite primary
+    /// key. Such a field is stored in one column for each of the primary key
+    /// fields of the referenced model.
+    fn push_composite_foreign_key_field(&mut self, field: &Field) {
+        let orm_ident = orm_ident();
+
+        let name = &field.name;
+        let ty = &field.ty;
+        let index = self.fields_as_columns.len();
+        let column_name = &field.column_name;
+        let columns = field.column_names();
+        let column_count = columns.len();
+
+        for column in &columns {
+            self.fields_as_columns.push(quote!(#orm_ident::Column::new(
+                #orm_ident::Identifier::new(#column)
+            )));
+        }
+
+        self.fields_as_from_db.push(quote!(
+            #name: <#ty as #orm_ident::CompositeForeignKeyField>::from_db_at(db_row, offset + #index)?
+        ));
+
+        // the columns of composite foreign keys are never filled in by the
+        // database, so they don't need to be handled in `update_from_db`
+        for column_index in 0..column_count {
+            let model_index = index + column_index;
+            self.fields_as_get_values.push(quote!(
+                #model_index => #orm_ident::CompositeForeignKeyField::column_value(&self.#name, #column_index)
+            ));
+        }
+
+        self.fields_as_fill_related.push(quote!(
+            #column_name => #orm_ident::ForeignKeyField::fill_from_db(&mut self.#name, db_row, offset)
+        ));
+
+        self.fields_as_field_refs.push(quote!(
+            #[doc = concat!("Field reference to [`", stringify!(#name), "::", stringify!(#column_name), "`].")]
+            pub const #name: #orm_ident::query::expr::FieldRef<#ty> =
+                #orm_ident::query::expr::FieldRef::<#ty>::composite(
+                    #orm_ident::Identifier::new(#column_name),
+                    &[#(#orm_ident::Identifier::new(#columns),)*],
+                );
+        ));
+
+        self.fields_as_assertions.push(quote!(
+            const _: () = assert!(
+                <#ty as #orm_ident::CompositeForeignKeyField>::COLUMN_COUNT == #column_count,
+                concat!(
+                    "`", stringify!(#name), "` must list as many columns as the referenced ",
+                    "model has primary key fields"
+                ),
+            );
+        ));
+    }
+
+    fn push_many_to_many_field(&mut self, model_name: &str, field: &ManyToManyField) {
+        let orm_ident = orm_ident();
+
+        let name = &field.name;
+        // `ManyToMany` fields are rejected in models with composite primary keys
+        let pk_field_name = &self.pk_fields[0].name;
+        let join_table = field.join_table(model_name, &self.table_name);
+        let join_table_name = &join_table.table_name;
+        let source_column = &join_table.source_column;
+        let target_column = &join_table.target_column;
+
+        self.fields_as_many_to_many_new.push(quote!(
+            #name: #orm_ident::ManyToMany::new()
+        ));
+
+        self.fields_as_bind_many_to_many.push(quote!(
+            self.#name.bind(
+                #orm_ident::ManyToManyTable::new(
+                    #orm_ident::Identifier::new(#join_table_name),
+                    #orm_ident::Identifier::new(#source_column),
+                    #orm_ident::Identifier::new(#target_column),
+                ),
+                &self.#pk_field_name,
+            );
+        ));
+    }
+
+    fn push_reverse_relation(&mut self, reverse_relation: &ReverseRelationSpec) {
+        let orm_ident = orm_ident();
+
+        let name = &self.name;
+        let vis = &self.vis;
+        let accessor_name = &reverse_relation.name;
+        let prefetch_name = format_ident!("prefetch_{}", accessor_name);
+        let field_name = &reverse_relation.field_name;
+        let column_name = &reverse_relation.column_name;
+        let source_model = &reverse_relation.source_model;
+
+        let relation = quote!(
+            #orm_ident::ReverseRelation::<#name>::new(
+                #orm_ident::Identifier::new(#column_name),
+                |model: &#name| #orm_ident::ToDbFieldValue::to_db_field_value(&model.#field_name),
+            )
+        );
+
+        self.reverse_relations.push(quote! {
+            impl #source_model {
+                #[doc = concat!(
+                    "Returns a query for the [`", stringify!(#name), "`] instances referencing ",
+                    "this instance through the `", stringify!(#field_name), "` field.\n\n",
+                    "Use [`Query::and_filter`](cot::db::query::Query::and_filter) to narrow ",
+                    "the results down."
+                )]
+                #[must_use]
+                #vis fn #accessor_name(&self) -> #orm_ident::query::Query<#name> {
+                    #relation.query(self)
+                }
+
+                #[doc = concat!(
+                    "Fetches the [`", stringify!(#name), "`] instances referencing each of the ",
+                    "given instances through the `", stringify!(#field_name), "` field, ",
+                    "using a single database query.\n\n",
+                    "See [`ReverseRelation::prefetch`](cot::db::ReverseRelation::prefetch) ",
+                    "for more details.\n\n",
+                    "# Errors\n\n",
+                    "Returns an error if there was a problem communicating with the database."
+                )]
+                #vis async fn #prefetch_name<DB: #orm_ident::DatabaseBackend>(
+                    db: DB,
+                    instances: &[Self],
+                ) -> #orm_ident::Result<::std::vec::Vec<::std::vec::Vec<#name>>> {
+                    #relation.prefetch(db, instances).await
+                }
+            }
+        });
+    }
+
     #[must_use]
     fn build_model_impl(&self) -> TokenStream {
         let crate_ident = cot_ident();
@@ -175,19 +344,31 @@ impl ModelBuilder {
         };
         let fields_struct_name = &self.fields_struct_name;
         let fields_as_columns = &self.fields_as_columns;
-        let pk_field_name = &self.pk_field.name;
-        let pk_column_name = &self.pk_field.column_name;
-        let pk_type = &self.pk_field.ty;
+        let pk_column_names: Vec<_> = self
+            .pk_fields
+            .iter()
+            .map(|field| &field.column_name)
+            .collect();
+        let pk_column_name = pk_column_names[0];
+        let primary_key_impl = self.build_primary_key_impl();
         let fields_as_from_db = &self.fields_as_from_db;
         let fields_as_update_from_db = &self.fields_as_update_from_db;
         let fields_as_get_values = &self.fields_as_get_values;
+        let fields_as_fill_related = &self.fields_as_fill_related;
+        let fields_as_many_to_many_new = &self.fields_as_many_to_many_new;
+        let fields_as_bind_many_to_many = &self.fields_as_bind_many_to_many;
+        // avoid unused variable warnings for models without foreign keys
+        let (fill_related_row, fill_related_offset) = if fields_as_fill_related.is_empty() {
+            (format_ident!("_db_row"), format_ident!("_offset"))
+        } else {
+            (format_ident!("db_row"), format_ident!("offset"))
+        };
 
         quote! {
             #[#crate_ident::__private::async_trait]
             #[automatically_derived]
             impl #orm_ident::Model for #name {
                 type Fields = #fields_struct_name;
-                type PrimaryKey = #pk_type;
 
                 const COLUMNS: &'static [#orm_ident::Column] = &[
                     #(#fields_as_columns,)*
@@ -196,19 +377,41 @@ impl ModelBuilder {
                 const APP_NAME: &'static str = #app_name;
                 const TABLE_NAME: #orm_ident::Identifier = #orm_ident::Identifier::new(#table_name);
                 const PRIMARY_KEY_NAME: #orm_ident::Identifier = #orm_ident::Identifier::new(#pk_column_name);
+                const PRIMARY_KEY_NAMES: &'static [#orm_ident::Identifier] = &[
+                    #(#orm_ident::Identifier::new(#pk_column_names),)*
+                ];
 
-                fn primary_key(&self) -> &Self::PrimaryKey {
-                    &self.#pk_field_name
-                }
+                #primary_key_impl
 
-                fn set_primary_key(&mut self, primary_key: Self::PrimaryKey) {
-                    self.#pk_field_name = primary_key;
+                fn from_db(db_row: #orm_ident::Row) -> #orm_ident::Result<Self> {
+                    Self::from_db_at(&db_row, 0)
                 }
 
-                fn from_db(db_row: #orm_ident::Row) -> #orm_ident::Result<Self> {
-                    Ok(Self {
+                fn from_db_at(db_row: &#orm_ident::Row, offset: usize) -> #orm_ident::Result<Self> {
+                    let mut model = Self {
                         #(#fields_as_from_db,)*
-                    })
+                        #(#fields_as_many_to_many_new,)*
+                    };
+                    #orm_ident::Model::bind_many_to_many(&mut model);
+                    Ok(model)
+                }
+
+                fn fill_related(
+                    &mut self,
+                    column: #orm_ident::Identifier,
+                    #fill_related_row: &#orm_ident::Row,
+                    #fill_related_offset: usize,
+                ) -> #orm_ident::Result<()> {
+                    match column.as_str() {
+                        #(#fields_as_fill_related,)*
+                        _ => Err(#orm_ident::query::QueryBuildingError::NotAForeignKey(
+                            column.as_str().to_owned(),
+                        ).into()),
+                    }
+                }
+
+                fn bind_many_to_many(&mut self) {
+                    #(#fields_as_bind_many_to_many)*
                 }
 
                 fn update_from_db(&mut self, db_row: #orm_ident::Row, columns: &[usize]) -> #orm_ident::Result<()> {
@@ -231,15 +434,64 @@ impl ModelBuilder {
                         })
                         .collect()
                 }
+            }
+        }
+    }
 
-                async fn get_by_primary_key<DB: #orm_ident::DatabaseBackend>(
-                    mut db: DB,
-                    pk: Self::PrimaryKey,
-                ) -> #orm_ident::Result<Option<Self>> {
-                    #orm_ident::query!(Self, $#pk_field_name == pk)
-                        .get(&mut db)
-                        .await
-                }
+    /// Builds the primary key type and the methods of the [`Model`] trait
+    /// that access the primary key. For models with a composite primary key,
+    /// the primary key is a tuple of all the primary key fields.
+    #[must_use]
+    fn build_primary_key_impl(&self) -> TokenStream {
+        let orm_ident = orm_ident();
+
+        let pk_field_names: Vec<_> = self.pk_fields.iter().map(|field| &field.name).collect();
+        let filter = pk_field_names
+            .iter()
+            .map(|name| quote!($#name == #name))
+            .reduce(|lhs, rhs| quote!(#lhs && #rhs))
+            .expect("models have at least one primary key field");
+
+        let (pk_type, pk_ref_type, pk_value, pk_pattern) =
+            if let [pk_field] = self.pk_fields.as_slice() {
+                let name = &pk_field.name;
+                let ty = &pk_field.ty;
+                (
+                    ty.to_token_stream(),
+                    quote!(&#ty),
+                    quote!(&self.#name),
+                    quote!(#name),
+                )
+            } else {
+                let pk_types: Vec<_> = self.pk_fields.iter().map(|field| &field.ty).collect();
+                (
+                    quote!((#(#pk_types,)*)),
+                    quote!((#(&#pk_types,)*)),
+                    quote!((#(&self.#pk_field_names,)*)),
+                    quote!((#(#pk_field_names,)*)),
+                )
+            };
+
+        quote! {
+            type PrimaryKey = #pk_type;
+
+            fn primary_key(&self) -> #pk_ref_type {
+                #pk_value
+            }
+
+            fn set_primary_key(&mut self, primary_key: Self::PrimaryKey) {
+                let #pk_pattern = primary_key;
+                #(self.#pk_field_names = #pk_field_names;)*
+            }
+
+            async fn get_by_primary_key<DB: #orm_ident::DatabaseBackend>(
+                mut db: DB,
+                pk: Self::PrimaryKey,
+            ) -> #orm_ident::Result<Option<Self>> {
+                let #pk_pattern = pk;
+                #orm_ident::query!(Self, #filter)
+                    .get(&mut db)
+                    .await
             }
         }
     }
@@ -265,7 +517,7 @@ impl ModelBuilder {
 }
 
 #[must_use]
-fn orm_ident() -> TokenStream {
+pub(super) fn orm_ident() -> TokenStream {
     let crate_ident = cot_ident();
     quote! { #crate_ident::db }
 }
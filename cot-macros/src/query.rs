@@ -3,22 +3,57 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use syn::Token;
 use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
 
 use crate::cot_ident;
 
 #[derive(Debug)]
 pub(crate) struct Query {
     model_name: syn::Type,
-    _comma: Token![,],
-    expr: Expr,
+    filter: Option<Expr>,
+    order_by: Vec<Expr>,
 }
 
 impl Parse for Query {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let model_name = input.parse()?;
+
+        let filter = if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        let order_by = if input.peek(Token![;]) {
+            input.parse::<Token![;]>()?;
+            let keyword = input.parse::<syn::Ident>()?;
+            if keyword != "order_by" {
+                return Err(syn::Error::new(keyword.span(), "expected `order_by`"));
+            }
+
+            let content;
+            let parens = syn::parenthesized!(content in input);
+            let keys = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+            if keys.is_empty() {
+                return Err(syn::Error::new(
+                    parens.span.join(),
+                    "expected at least one sort key",
+                ));
+            }
+            keys.into_iter().collect()
+        } else {
+            Vec::new()
+        };
+
+        if filter.is_none() && order_by.is_empty() {
+            return Err(input.error("expected a filter expression or an `order_by` clause"));
+        }
+
         Ok(Self {
-            model_name: input.parse()?,
-            _comma: input.parse()?,
-            expr: input.parse()?,
+            model_name,
+            filter,
+            order_by,
         })
     }
 }
@@ -26,41 +61,91 @@ impl Parse for Query {
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub(crate) struct FieldRefMethod {
     name: &'static str,
+    trait_name: &'static str,
     arity: u32,
+    /// Whether the only argument is a list of values rather than a single
+    /// value.
+    list_arg: bool,
 }
 
 const FIELD_REF_METHODS: &[FieldRefMethod] = &[
     FieldRefMethod {
         name: "contains",
+        trait_name: "ExprLike",
         arity: 1,
+        list_arg: false,
     },
     FieldRefMethod {
         name: "icontains",
+        trait_name: "ExprLike",
         arity: 1,
+        list_arg: false,
     },
     FieldRefMethod {
         name: "starts_with",
+        trait_name: "ExprLike",
         arity: 1,
+        list_arg: false,
     },
     FieldRefMethod {
         name: "istarts_with",
+        trait_name: "ExprLike",
         arity: 1,
+        list_arg: false,
     },
     FieldRefMethod {
         name: "ends_with",
+        trait_name: "ExprLike",
         arity: 1,
+        list_arg: false,
     },
     FieldRefMethod {
         name: "iends_with",
+        trait_name: "ExprLike",
         arity: 1,
+        list_arg: false,
     },
     FieldRefMethod {
         name: "raw_like",
+        trait_name: "ExprLike",
         arity: 1,
+        list_arg: false,
     },
     FieldRefMethod {
         name: "iraw_like",
+        trait_name: "ExprLike",
+        arity: 1,
+        list_arg: false,
+    },
+    FieldRefMethod {
+        name: "is_in",
+        trait_name: "ExprIn",
+        arity: 1,
+        list_arg: true,
+    },
+    FieldRefMethod {
+        name: "is_not_in",
+        trait_name: "ExprIn",
         arity: 1,
+        list_arg: true,
+    },
+    FieldRefMethod {
+        name: "is_null",
+        trait_name: "ExprNull",
+        arity: 0,
+        list_arg: false,
+    },
+    FieldRefMethod {
+        name: "is_not_null",
+        trait_name: "ExprNull",
+        arity: 0,
+        list_arg: false,
+    },
+    FieldRefMethod {
+        name: "between",
+        trait_name: "ExprOrd",
+        arity: 2,
+        list_arg: false,
     },
 ];
 
@@ -74,6 +159,10 @@ impl FieldRefMethod {
         format_ident!("{}", self.name)
     }
 
+    pub(crate) fn trait_ident(self) -> syn::Ident {
+        format_ident!("{}", self.trait_name)
+    }
+
     pub(crate) fn all_names() -> impl Iterator<Item = &'static str> {
         FIELD_REF_METHODS.iter().map(|m| m.name)
     }
@@ -82,11 +171,76 @@ impl FieldRefMethod {
 pub(super) fn query_to_tokens(query: Query) -> TokenStream {
     let crate_name = cot_ident();
     let model_name = query.model_name;
-    let expr = expr_to_tokens(&model_name, query.expr);
 
-    quote! {
-        <#model_name as #crate_name::db::Model>::objects().filter(#expr)
+    let mut tokens = quote! {
+        <#model_name as #crate_name::db::Model>::objects()
+    };
+    if let Some(filter) = query.filter {
+        let expr = expr_to_tokens(&model_name, filter);
+        tokens.extend(quote!(.filter(#expr)));
+    }
+    for key in query.order_by {
+        let order = order_by_to_tokens(&model_name, key);
+        tokens.extend(quote!(.order_by(#order)));
     }
+
+    tokens
+}
+
+/// Converts a single sort key, such as `$name`, `$name.desc()` or
+/// `$name.asc().nulls_last()`, into an `OrderBy` instance.
+fn order_by_to_tokens(model_name: &syn::Type, key: Expr) -> TokenStream {
+    let crate_name = cot_ident();
+
+    let (key, nulls) = match strip_order_method(key, &["nulls_first", "nulls_last"]) {
+        Ok(result) => result,
+        Err(error) => return error.to_compile_error(),
+    };
+    let (key, direction) = match strip_order_method(key, &["asc", "desc"]) {
+        Ok(result) => result,
+        Err(error) => return error.to_compile_error(),
+    };
+    let direction = direction.unwrap_or_else(|| format_ident!("asc"));
+
+    let expr = expr_to_tokens(model_name, key);
+    let order = quote!(#crate_name::db::query::order::OrderBy::#direction(#expr));
+    match nulls {
+        Some(nulls) => quote!(#order.#nulls()),
+        None => order,
+    }
+}
+
+/// If the expression is a call to one of the given argument-less methods,
+/// returns the receiver of the call along with the method name.
+fn strip_order_method(key: Expr, methods: &[&str]) -> syn::Result<(Expr, Option<syn::Ident>)> {
+    let Expr::FunctionCall { function, args } = key else {
+        return Ok((key, None));
+    };
+    let Expr::MemberAccess {
+        parent,
+        member_name,
+        member_access_token,
+    } = *function
+    else {
+        return Ok((Expr::FunctionCall { function, args }, None));
+    };
+
+    if !methods.iter().any(|method| member_name == method) {
+        let function = Box::new(Expr::MemberAccess {
+            parent,
+            member_name,
+            member_access_token,
+        });
+        return Ok((Expr::FunctionCall { function, args }, None));
+    }
+    if let Some(arg) = args.first() {
+        return Err(syn::Error::new_spanned(
+            arg,
+            format!("`{member_name}` expects no arguments"),
+        ));
+    }
+
+    Ok((*parent, Some(member_name)))
 }
 
 pub(super) fn expr_to_tokens(model_name: &syn::Type, expr: Expr) -> TokenStream {
@@ -139,6 +293,11 @@ pub(super) fn expr_to_tokens(model_name: &syn::Type, expr: Expr) -> TokenStream
                 return handle_field_ref_method(model_name, *parent, &args, *method);
             }
 
+            if is_exists_call(&function, &args) {
+                let subquery = &args[0];
+                return quote!(#crate_name::db::query::expr::Expr::exists(#subquery));
+            }
+
             if let Some(tokens) = non_field_tokens {
                 quote!(#crate_name::db::query::expr::Expr::value(#tokens(#(#args),*)))
             } else {
@@ -153,6 +312,10 @@ pub(super) fn expr_to_tokens(model_name: &syn::Type, expr: Expr) -> TokenStream
                 syn::Error::new_spanned(function.as_tokens_full(), msg).to_compile_error()
             }
         }
+        Expr::Not(expr) => {
+            let expr = expr_to_tokens(model_name, *expr);
+            quote!(#crate_name::db::query::expr::Expr::not(#expr))
+        }
         Expr::And(lhs, rhs) => {
             let lhs = expr_to_tokens(model_name, *lhs);
             let rhs = expr_to_tokens(model_name, *rhs);
@@ -176,6 +339,13 @@ pub(super) fn expr_to_tokens(model_name: &syn::Type, expr: Expr) -> TokenStream
     }
 }
 
+/// Checks if the function call is `exists(subquery)`, which is translated to
+/// an `EXISTS` expression rather than a call to a function named `exists`.
+fn is_exists_call(function: &Expr, args: &[syn::Expr]) -> bool {
+    matches!(function, Expr::Value(syn::Expr::Path(path)) if path.path.is_ident("exists"))
+        && args.len() == 1
+}
+
 fn handle_binary_comparison(
     model_name: &syn::Type,
     lhs: Expr,
@@ -223,8 +393,9 @@ fn handle_field_ref_method(
     }
 
     if let Expr::FieldRef { ref field_name, .. } = receiver {
+        let trait_ident = method.trait_ident();
         return quote! {
-            #crate_name::db::query::expr::ExprLike::#method_ident(
+            #crate_name::db::query::expr::#trait_ident::#method_ident(
                 <#model_name as #crate_name::db::Model>::Fields::#field_name,
                 #(#args),*
             )
@@ -232,9 +403,16 @@ fn handle_field_ref_method(
     }
 
     let receiver_tokens = expr_to_tokens(model_name, receiver);
-    let wrapped_args = args
-        .iter()
-        .map(|arg| quote!(#crate_name::db::query::expr::Expr::value(#arg)));
+    let wrapped_args = args.iter().map(|arg| {
+        if method.list_arg {
+            quote! {
+                ::core::iter::IntoIterator::into_iter(#arg)
+                    .map(#crate_name::db::query::expr::Expr::value)
+            }
+        } else {
+            quote!(#crate_name::db::query::expr::Expr::value(#arg))
+        }
+    });
 
     quote! {
         #crate_name::db::query::expr::Expr::#method_ident(
@@ -251,7 +429,7 @@ mod tests {
     #[test]
     fn test_field_ref_method_all_names() {
         let all_names = FieldRefMethod::all_names().collect::<Vec<_>>();
-        assert_eq!(all_names.len(), 8);
+        assert_eq!(all_names.len(), 13);
         assert_eq!(
             all_names,
             [
@@ -263,67 +441,116 @@ mod tests {
                 "iends_with",
                 "raw_like",
                 "iraw_like",
+                "is_in",
+                "is_not_in",
+                "is_null",
+                "is_not_null",
+                "between",
             ]
         );
     }
 
     #[test]
+    #[expect(clippy::too_many_lines)]
     fn test_field_ref_method_lookup() {
         let idents = [
             (
                 "contains",
                 Some(FieldRefMethod {
                     name: "contains",
+                    trait_name: "ExprLike",
                     arity: 1,
+                    list_arg: false,
                 }),
             ),
             (
                 "icontains",
                 Some(FieldRefMethod {
                     name: "icontains",
+                    trait_name: "ExprLike",
                     arity: 1,
+                    list_arg: false,
                 }),
             ),
             (
                 "starts_with",
                 Some(FieldRefMethod {
                     name: "starts_with",
+                    trait_name: "ExprLike",
                     arity: 1,
+                    list_arg: false,
                 }),
             ),
             (
                 "istarts_with",
                 Some(FieldRefMethod {
                     name: "istarts_with",
+                    trait_name: "ExprLike",
                     arity: 1,
+                    list_arg: false,
                 }),
             ),
             (
                 "ends_with",
                 Some(FieldRefMethod {
                     name: "ends_with",
+                    trait_name: "ExprLike",
                     arity: 1,
+                    list_arg: false,
                 }),
             ),
             (
                 "iends_with",
                 Some(FieldRefMethod {
                     name: "iends_with",
+                    trait_name: "ExprLike",
                     arity: 1,
+                    list_arg: false,
                 }),
             ),
             (
                 "raw_like",
                 Some(FieldRefMethod {
                     name: "raw_like",
+                    trait_name: "ExprLike",
                     arity: 1,
+                    list_arg: false,
                 }),
             ),
             (
                 "iraw_like",
                 Some(FieldRefMethod {
                     name: "iraw_like",
+                    trait_name: "ExprLike",
+                    arity: 1,
+                    list_arg: false,
+                }),
+            ),
+            (
+                "is_in",
+                Some(FieldRefMethod {
+                    name: "is_in",
+                    trait_name: "ExprIn",
                     arity: 1,
+                    list_arg: true,
+                }),
+            ),
+            (
+                "is_null",
+                Some(FieldRefMethod {
+                    name: "is_null",
+                    trait_name: "ExprNull",
+                    arity: 0,
+                    list_arg: false,
+                }),
+            ),
+            (
+                "between",
+                Some(FieldRefMethod {
+                    name: "between",
+                    trait_name: "ExprOrd",
+                    arity: 2,
+                    list_arg: false,
                 }),
             ),
             ("__non_existent__", None),
@@ -59,6 +59,7 @@ fn derive_admin_model() {
 fn func_query() {
     let t = trybuild::TestCases::new();
     t.pass("tests/ui/func_query.rs");
+    t.pass("tests/ui/func_query_order_by.rs");
     t.compile_fail("tests/ui/func_query_double_op.rs");
     t.compile_fail("tests/ui/func_query_starting_op.rs");
     t.compile_fail("tests/ui/func_query_double_field.rs");
@@ -98,6 +99,21 @@ fn derive_from_struct() {
     t.compile_fail("tests/ui/derive_from_request_head_enum.rs");
 }
 
+#[rustversion::attr(
+    not(nightly),
+    ignore = "only test on nightly for consistent error messages"
+)]
+#[test]
+#[cfg_attr(
+    miri,
+    ignore = "unsupported operation: extern static `pidfd_spawnp` is not supported by Miri"
+)]
+fn derive_from_db_row() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/derive_from_db_row.rs");
+    t.compile_fail("tests/ui/derive_from_db_row_enum.rs");
+}
+
 #[rustversion::attr(
     not(nightly),
     ignore = "only test on nightly for consistent error messages"
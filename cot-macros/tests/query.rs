@@ -1,5 +1,8 @@
 use cot::db::query::Query;
-use cot::db::query::expr::{Expr, ExprAdd, ExprDiv, ExprEq, ExprLike, ExprMul, ExprOrd, ExprSub};
+use cot::db::query::expr::{
+    Expr, ExprAdd, ExprDiv, ExprEq, ExprIn, ExprLike, ExprMul, ExprNull, ExprOrd, ExprSub,
+};
+use cot::db::query::order::OrderBy;
 use cot::db::{model, query};
 
 #[model]
@@ -12,6 +15,15 @@ struct MyModel {
     price: i64,
     quantity: i64,
     valid: bool,
+    description: Option<String>,
+}
+
+#[model]
+#[derive(Debug, PartialEq)]
+struct OtherModel {
+    #[model(primary_key)]
+    id: i32,
+    active: bool,
 }
 
 #[test]
@@ -424,3 +436,162 @@ fn test_query_string_method_non_field_receiver_call() {
         query!(MyModel, $valid == allowed_names.contains(&"foo"))
     );
 }
+
+#[test]
+fn test_query_order_by() {
+    assert_eq!(
+        Query::<MyModel>::new().order_by(<MyModel as cot::db::Model>::Fields::name.asc()),
+        query!(MyModel; order_by($name))
+    );
+
+    assert_eq!(
+        Query::<MyModel>::new()
+            .order_by(<MyModel as cot::db::Model>::Fields::price.desc())
+            .order_by(<MyModel as cot::db::Model>::Fields::id.asc()),
+        query!(MyModel; order_by($price.desc(), $id.asc()))
+    );
+
+    assert_eq!(
+        Query::<MyModel>::new()
+            .order_by(
+                <MyModel as cot::db::Model>::Fields::title
+                    .asc()
+                    .nulls_first()
+            )
+            .order_by(
+                <MyModel as cot::db::Model>::Fields::price
+                    .desc()
+                    .nulls_last()
+            ),
+        query!(MyModel; order_by($title.nulls_first(), $price.desc().nulls_last()))
+    );
+}
+
+#[test]
+fn test_query_filter_and_order_by() {
+    assert_eq!(
+        Query::<MyModel>::new()
+            .filter(ExprOrd::gt(<MyModel as cot::db::Model>::Fields::price, 10))
+            .order_by(<MyModel as cot::db::Model>::Fields::name.desc()),
+        query!(MyModel, $price > 10; order_by($name.desc()))
+    );
+
+    assert_eq!(
+        Query::<MyModel>::new()
+            .filter(ExprEq::eq(<MyModel as cot::db::Model>::Fields::valid, true))
+            .order_by(OrderBy::asc(Expr::mul(
+                <MyModel as cot::db::Model>::Fields::price.as_expr(),
+                <MyModel as cot::db::Model>::Fields::quantity.as_expr()
+            ))),
+        query!(MyModel, $valid == true; order_by($price * $quantity))
+    );
+}
+
+#[test]
+fn test_query_in() {
+    assert_eq!(
+        Query::<MyModel>::new().filter(ExprIn::is_in(
+            <MyModel as cot::db::Model>::Fields::id,
+            [1, 2, 3]
+        )),
+        query!(MyModel, $id.is_in([1, 2, 3]))
+    );
+
+    let names = vec!["foo", "bar"];
+    assert_eq!(
+        Query::<MyModel>::new().filter(ExprIn::is_not_in(
+            <MyModel as cot::db::Model>::Fields::name,
+            names.clone()
+        )),
+        query!(MyModel, $name.is_not_in(names))
+    );
+
+    assert_eq!(
+        Query::<MyModel>::new().filter(Expr::is_in(
+            Expr::add(
+                <MyModel as cot::db::Model>::Fields::price.as_expr(),
+                <MyModel as cot::db::Model>::Fields::quantity.as_expr()
+            ),
+            [Expr::value(10), Expr::value(20)]
+        )),
+        query!(MyModel, ($price + $quantity).is_in([10, 20]))
+    );
+}
+
+#[test]
+fn test_query_null() {
+    assert_eq!(
+        Query::<MyModel>::new().filter(ExprNull::is_null(
+            <MyModel as cot::db::Model>::Fields::description
+        )),
+        query!(MyModel, $description.is_null())
+    );
+
+    assert_eq!(
+        Query::<MyModel>::new().filter(ExprNull::is_not_null(
+            <MyModel as cot::db::Model>::Fields::description
+        )),
+        query!(MyModel, $description.is_not_null())
+    );
+}
+
+#[test]
+fn test_query_between() {
+    assert_eq!(
+        Query::<MyModel>::new().filter(ExprOrd::between(
+            <MyModel as cot::db::Model>::Fields::price,
+            10,
+            20
+        )),
+        query!(MyModel, $price.between(10, 20))
+    );
+
+    assert_eq!(
+        Query::<MyModel>::new().filter(Expr::between(
+            Expr::mul(
+                <MyModel as cot::db::Model>::Fields::price.as_expr(),
+                <MyModel as cot::db::Model>::Fields::quantity.as_expr()
+            ),
+            Expr::value(100),
+            Expr::value(200)
+        )),
+        query!(MyModel, ($price * $quantity).between(100, 200))
+    );
+}
+
+#[test]
+fn test_query_not() {
+    assert_eq!(
+        Query::<MyModel>::new().filter(Expr::not(ExprLike::contains(
+            <MyModel as cot::db::Model>::Fields::name,
+            "foo"
+        ))),
+        query!(MyModel, !$name.contains("foo"))
+    );
+
+    assert_eq!(
+        Query::<MyModel>::new().filter(Expr::and(
+            Expr::not(Expr::or(
+                ExprEq::eq(<MyModel as cot::db::Model>::Fields::id, 1),
+                ExprEq::eq(<MyModel as cot::db::Model>::Fields::id, 2)
+            )),
+            ExprEq::eq(<MyModel as cot::db::Model>::Fields::valid, true)
+        )),
+        query!(MyModel, !($id == 1 || $id == 2) && $valid == true)
+    );
+}
+
+#[test]
+fn test_query_exists() {
+    let active = query!(OtherModel, $active == true).clone();
+
+    assert_eq!(
+        Query::<MyModel>::new().filter(Expr::exists(active.clone())),
+        query!(MyModel, exists(active.clone()))
+    );
+
+    assert_eq!(
+        Query::<MyModel>::new().filter(Expr::not(Expr::exists(&active))),
+        query!(MyModel, !exists(&active))
+    );
+}
@@ -0,0 +1,13 @@
+use cot::db::FromDbRow;
+
+#[derive(FromDbRow)]
+struct MyStruct {
+    id: i32,
+    name: String,
+    description: Option<String>,
+}
+
+#[derive(FromDbRow)]
+struct MyTupleStruct(i32, String);
+
+fn main() {}
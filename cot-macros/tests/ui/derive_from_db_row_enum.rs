@@ -0,0 +1,9 @@
+use cot::db::FromDbRow;
+
+#[derive(FromDbRow)]
+enum MyEnum {
+    A,
+    B,
+}
+
+fn main() {}
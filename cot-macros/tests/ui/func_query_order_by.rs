@@ -0,0 +1,19 @@
+use cot::db::{model, query};
+
+#[derive(Debug)]
+#[model]
+struct MyModel {
+    #[model(primary_key)]
+    id: i32,
+    name: std::string::String,
+    visits: Option<i32>,
+}
+
+fn main() {
+    query!(MyModel; order_by($name));
+    query!(
+        MyModel,
+        $name == "hello";
+        order_by($visits.desc().nulls_last(), $name.asc(), $id)
+    );
+}
@@ -8,10 +8,14 @@ use async_trait::async_trait;
 pub use clap;
 use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
 #[cfg(feature = "db")]
-use cot::db::migrations::{MigrationEngine, SyncDynMigration};
+use cot::db::migrations::{MigrationEngine, MigrationTarget, SyncDynMigration};
+#[cfg(feature = "db")]
+use cot::db::{Database, DatabaseDialect};
 use cot::project::BootstrappedProject;
 use derive_more::Debug;
 
+#[cfg(feature = "db")]
+use crate::App;
 use crate::{Bootstrapper, Error, Result};
 
 const CONFIG_PARAM: &str = "config";
@@ -21,6 +25,9 @@ const LISTEN_PARAM: &str = "listen";
 const COLLECT_STATIC_DIR_PARAM: &str = "dir";
 const MIGRATION_GROUP_SUBCOMMAND: &str = "migration";
 const MIGRATION_ROLLBACK_SUBCOMMAND: &str = "rollback";
+const MIGRATE_SUBCOMMAND: &str = "migrate";
+const SHOW_MIGRATIONS_SUBCOMMAND: &str = "showmigrations";
+const SQL_MIGRATE_SUBCOMMAND: &str = "sqlmigrate";
 
 /// A central point for configuring the default Command Line Interface (CLI) for
 /// Cot-powered projects.
@@ -103,6 +110,9 @@ impl Cli {
             migration_group.add_task(MigrationRollback);
 
             cli.add_task(migration_group);
+            cli.add_task(Migrate);
+            cli.add_task(ShowMigrations);
+            cli.add_task(SqlMigrate);
         }
         cli
     }
@@ -604,6 +614,7 @@ impl CliTask for MigrationRollback {
                     .action(ArgAction::SetTrue)
                     .help("Print the Rollback Plan without changing the database"),
             )
+            .arg(database_arg())
     }
 
     async fn execute(
@@ -616,6 +627,216 @@ impl CliTask for MigrationRollback {
             .expect("required argument");
         let dry_run = matches.get_flag("dry-run");
 
+        let migration_context = MigrationContext::boot(bootstrapper, matches).await?;
+        let app_name = matches
+            .get_one::<String>("app")
+            .map_or(migration_context.crate_name, String::as_str);
+
+        let database = migration_context.database();
+        let migration_engine = &migration_context.migration_engine;
+        if dry_run {
+            migration_engine
+                .rollback_dry_run(database, file, app_name, &mut std::io::stderr())
+                .await?;
+        } else {
+            migration_engine
+                .rollback(database, file, app_name, &mut std::io::stderr())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "db")]
+struct Migrate;
+
+#[cfg(feature = "db")]
+#[async_trait(?Send)]
+impl CliTask for Migrate {
+    fn subcommand(&self) -> Command {
+        Command::new(MIGRATE_SUBCOMMAND)
+            .about("Apply or roll back migrations up to the specified migration")
+            .arg(
+                Arg::new("migration_name")
+                    .help(
+                        "The migration name to migrate to (e.g. m_0001_initial, 0001, or zero) \
+                         [default: all migrations of all apps]",
+                    )
+                    .value_name("MIGRATION_NAME")
+                    .required(false),
+            )
+            .arg(
+                Arg::new("app")
+                    .long("app")
+                    .help("The name of the app the migration belongs to [default: crate name]")
+                    .value_name("APP")
+                    .requires("migration_name"),
+            )
+            .arg(
+                Arg::new("fake")
+                    .long("fake")
+                    .action(ArgAction::SetTrue)
+                    .help("Mark the migrations as applied or rolled back without running them"),
+            )
+            .arg(
+                Arg::new("plan")
+                    .long("plan")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with_all(["fake", "check"])
+                    .help("Print the migration plan without changing the database"),
+            )
+            .arg(
+                Arg::new("check")
+                    .long("check")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with("fake")
+                    .help(
+                        "Print the migration plan and exit with an error if there are any \
+                         migrations to apply or roll back",
+                    ),
+            )
+            .arg(database_arg())
+    }
+
+    async fn execute(
+        &mut self,
+        matches: &ArgMatches,
+        bootstrapper: Bootstrapper<WithConfig>,
+    ) -> Result<()> {
+        let migration_context = MigrationContext::boot(bootstrapper, matches).await?;
+        let target = match matches.get_one::<String>("migration_name") {
+            Some(migration_name) => MigrationTarget::Migration {
+                app_name: matches
+                    .get_one::<String>("app")
+                    .map_or(migration_context.crate_name, String::as_str),
+                migration_name,
+            },
+            None => MigrationTarget::Latest,
+        };
+
+        let database = migration_context.database();
+        let migration_engine = &migration_context.migration_engine;
+        if matches.get_flag("plan") || matches.get_flag("check") {
+            let plan = migration_engine.plan(database, target).await?;
+            plan.write(&mut std::io::stderr()).map_err(Error::wrap)?;
+
+            if matches.get_flag("check") && !plan.is_empty() {
+                return Err(Error::wrap(
+                    "the database is not migrated to the target; see the plan above",
+                ));
+            }
+        } else {
+            migration_engine
+                .migrate(
+                    database,
+                    target,
+                    matches.get_flag("fake"),
+                    &mut std::io::stderr(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "db")]
+struct ShowMigrations;
+
+#[cfg(feature = "db")]
+#[async_trait(?Send)]
+impl CliTask for ShowMigrations {
+    fn subcommand(&self) -> Command {
+        Command::new(SHOW_MIGRATIONS_SUBCOMMAND)
+            .about("List all migrations and whether they are applied")
+            .arg(database_arg())
+    }
+
+    async fn execute(
+        &mut self,
+        matches: &ArgMatches,
+        bootstrapper: Bootstrapper<WithConfig>,
+    ) -> Result<()> {
+        let migration_context = MigrationContext::boot(bootstrapper, matches).await?;
+
+        migration_context
+            .migration_engine
+            .show_migrations(migration_context.database(), &mut std::io::stdout())
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "db")]
+struct SqlMigrate;
+
+#[cfg(feature = "db")]
+#[async_trait(?Send)]
+impl CliTask for SqlMigrate {
+    fn subcommand(&self) -> Command {
+        Command::new(SQL_MIGRATE_SUBCOMMAND)
+            .about("Print the SQL run when applying and rolling back a migration")
+            .arg(
+                Arg::new("app")
+                    .help("The name of the app the migration belongs to")
+                    .value_name("APP")
+                    .required(true),
+            )
+            .arg(
+                Arg::new("migration_name")
+                    .help("The migration name (e.g. m_0001_initial or 0001)")
+                    .value_name("MIGRATION_NAME")
+                    .required(true),
+            )
+            .arg(
+                Arg::new("backend")
+                    .long("backend")
+                    .help("The database backend to print the SQL for")
+                    .value_name("BACKEND")
+                    .value_parser(value_parser!(DatabaseDialect))
+                    .required(true),
+            )
+    }
+
+    async fn execute(
+        &mut self,
+        matches: &ArgMatches,
+        bootstrapper: Bootstrapper<WithConfig>,
+    ) -> Result<()> {
+        let app_name = matches.get_one::<String>("app").expect("required argument");
+        let migration_name = matches
+            .get_one::<String>("migration_name")
+            .expect("required argument");
+        let dialect = *matches
+            .get_one::<DatabaseDialect>("backend")
+            .expect("required argument");
+
+        // the SQL is rendered without connecting to the database, so only the
+        // apps are needed
+        let bootstrapper = bootstrapper.with_apps();
+        let migration_engine = migration_engine(bootstrapper.context().apps())?;
+
+        let sql = migration_engine
+            .migration_sql(app_name, migration_name, dialect)
+            .await?;
+        sql.write(&mut std::io::stdout()).map_err(Error::wrap)?;
+        Ok(())
+    }
+}
+
+/// The state needed by the migration-related CLI tasks.
+#[cfg(feature = "db")]
+struct MigrationContext {
+    crate_name: &'static str,
+    context: ProjectContext<Initialized>,
+    database_alias: Option<String>,
+    migration_engine: MigrationEngine,
+}
+
+#[cfg(feature = "db")]
+impl MigrationContext {
+    /// Boots the project and creates the migration engine with the migrations
+    /// of the database chosen with the `--database` argument.
+    async fn boot(bootstrapper: Bootstrapper<WithConfig>, matches: &ArgMatches) -> Result<Self> {
         let bootstrapper = bootstrapper
             .with_apps()
             .with_database()
@@ -624,35 +845,61 @@ impl CliTask for MigrationRollback {
             .await?;
 
         let crate_name = bootstrapper.project().cli_metadata().name;
-        let app_name = matches
-            .get_one::<String>("app")
-            .map_or(crate_name, String::as_str);
-
         let BootstrappedProject {
             context,
             handler: _,
             error_handler: _,
         } = bootstrapper.finish();
 
-        #[cfg(feature = "db")]
+        let database_alias = matches.get_one::<String>("database").cloned();
+        if let Some(alias) = &database_alias
+            && context.try_named_database(alias).is_none()
         {
-            let mut migrations: Vec<Box<SyncDynMigration>> = Vec::new();
-            for app in context.apps() {
-                migrations.extend(app.migrations());
-            }
-            let migration_engine = MigrationEngine::new(migrations)?;
-            if dry_run {
-                migration_engine
-                    .rollback_dry_run(context.database(), file, app_name, &mut std::io::stderr())
-                    .await?;
-            } else {
-                migration_engine
-                    .rollback(context.database(), file, app_name, &mut std::io::stderr())
-                    .await?;
-            }
+            return Err(Error::wrap(format!(
+                "database `{alias}` is not configured in the `databases` config"
+            )));
         }
-        Ok(())
+        let migrations =
+            database_migrations(context.apps(), context.config(), database_alias.as_deref());
+        let migration_engine = MigrationEngine::new(migrations)?;
+
+        Ok(Self {
+            crate_name,
+            context,
+            database_alias,
+            migration_engine,
+        })
     }
+
+    /// Returns the database chosen with the `--database` argument.
+    fn database(&self) -> &Database {
+        match &self.database_alias {
+            Some(alias) => self.context.named_database(alias),
+            None => self.context.database(),
+        }
+    }
+}
+
+/// Returns the argument choosing the database the migrations are run on.
+#[cfg(feature = "db")]
+fn database_arg() -> Arg {
+    Arg::new("database")
+        .long("database")
+        .help(
+            "The alias of the named database to run the migrations on \
+             [default: the default database]",
+        )
+        .value_name("DATABASE")
+}
+
+/// Creates a migration engine with the migrations of all the given apps.
+#[cfg(feature = "db")]
+fn migration_engine(apps: &[Box<dyn App>]) -> Result<MigrationEngine> {
+    let mut migrations: Vec<Box<SyncDynMigration>> = Vec::new();
+    for app in apps {
+        migrations.extend(app.migrations());
+    }
+    Ok(MigrationEngine::new(migrations)?)
 }
 
 /// A macro to generate a [`CliMetadata`] struct from the Cargo manifest.
@@ -670,6 +917,8 @@ macro_rules! metadata {
 
 pub use metadata;
 
+#[cfg(feature = "db")]
+use crate::project::{Initialized, ProjectContext, database_migrations};
 use crate::project::{StartServerError, WithConfig};
 use crate::static_files::StaticFiles;
 
@@ -761,6 +1010,79 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "db")]
+    fn cli_new_includes_migration_subcommands() {
+        let cli = Cli::new();
+
+        for name in [
+            MIGRATE_SUBCOMMAND,
+            SHOW_MIGRATIONS_SUBCOMMAND,
+            SQL_MIGRATE_SUBCOMMAND,
+        ] {
+            assert!(
+                cli.command
+                    .get_subcommands()
+                    .any(|command| command.get_name() == name),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "db")]
+    fn migrate_subcommand() {
+        let command = Migrate.subcommand();
+
+        let matches = command
+            .clone()
+            .try_get_matches_from(["migrate", "0001", "--app", "app", "--fake"])
+            .unwrap();
+        assert_eq!(matches.get_one::<String>("migration_name").unwrap(), "0001");
+        assert!(matches.get_flag("fake"));
+
+        assert!(
+            command
+                .clone()
+                .try_get_matches_from(["migrate", "--plan", "--fake"])
+                .is_err()
+        );
+        assert!(
+            command
+                .try_get_matches_from(["migrate", "--app", "app"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "db", feature = "sqlite"))]
+    fn sql_migrate_subcommand() {
+        let command = SqlMigrate.subcommand();
+
+        let matches = command
+            .clone()
+            .try_get_matches_from(["sqlmigrate", "app", "0001", "--backend", "sqlite"])
+            .unwrap();
+        assert_eq!(matches.get_one::<String>("app").unwrap(), "app");
+        assert_eq!(matches.get_one::<String>("migration_name").unwrap(), "0001");
+        assert_eq!(
+            matches.get_one::<DatabaseDialect>("backend").unwrap(),
+            &DatabaseDialect::Sqlite
+        );
+
+        assert!(
+            command
+                .clone()
+                .try_get_matches_from(["sqlmigrate", "app", "0001", "--backend", "oracle"])
+                .is_err()
+        );
+        assert!(
+            command
+                .try_get_matches_from(["sqlmigrate", "app", "0001"])
+                .is_err()
+        );
+    }
+
     #[cot::test]
     async fn cli_task_group_dispatches_nested_task() {
         struct NestedTask;
@@ -15,6 +15,8 @@
 // not implementing Copy for them
 #![allow(missing_copy_implementations)]
 
+#[cfg(feature = "db")]
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -172,6 +174,38 @@ pub struct ProjectConfig {
     /// ```
     #[cfg(feature = "db")]
     pub database: DatabaseConfig,
+    /// Additional databases, keyed by their aliases.
+    ///
+    /// These can be accessed with
+    /// [`ProjectContext::named_database`](crate::project::ProjectContext::named_database),
+    /// e.g. to store some of the apps' tables in a separate database. Use
+    /// [`DatabaseConfig::apps`] to choose the apps whose migrations are
+    /// applied to each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::config::{DatabaseUrl, ProjectConfig};
+    ///
+    /// let config = ProjectConfig::from_toml(
+    ///     r#"
+    /// [database]
+    /// url = "sqlite::memory:"
+    ///
+    /// [databases.analytics]
+    /// url = "sqlite://analytics.sqlite3"
+    /// apps = ["analytics"]
+    /// "#,
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     config.databases["analytics"].url,
+    ///     Some(DatabaseUrl::from("sqlite://analytics.sqlite3"))
+    /// );
+    /// # Ok::<(), cot::Error>(())
+    /// ```
+    #[cfg(feature = "db")]
+    pub databases: HashMap<String, DatabaseConfig>,
     /// Configuration related to the cache.
     ///
     /// # Examples
@@ -364,7 +398,8 @@ impl ProjectConfig {
     /// # Errors
     ///
     /// This function will return an error if the TOML fails to parse as a
-    /// [`ProjectConfig`].
+    /// [`ProjectConfig`], or if an app is listed in [`DatabaseConfig::apps`]
+    /// of more than one of the [named databases](ProjectConfig::databases).
     ///
     /// # Examples
     ///
@@ -379,8 +414,37 @@ impl ProjectConfig {
     /// ```
     pub fn from_toml(toml_content: &str) -> crate::Result<ProjectConfig> {
         let config: ProjectConfig = toml::from_str(toml_content).map_err(ParseConfig)?;
+        #[cfg(feature = "db")]
+        config.check_database_apps()?;
         Ok(config)
     }
+
+    /// Checks that each app is listed in [`DatabaseConfig::apps`] of at most
+    /// one of the named databases, as otherwise it would be ambiguous which
+    /// database its migrations are applied to.
+    #[cfg(feature = "db")]
+    pub(crate) fn check_database_apps(&self) -> Result<(), DuplicateDatabaseApp> {
+        // sorted, so that the error is the same every time
+        let mut aliases: Vec<&String> = self.databases.keys().collect();
+        aliases.sort();
+
+        let mut app_databases: HashMap<&str, &str> = HashMap::new();
+        for alias in aliases {
+            for app in &self.databases[alias].apps {
+                match app_databases.insert(app, alias) {
+                    Some(first_alias) if first_alias != alias => {
+                        return Err(DuplicateDatabaseApp {
+                            app: app.clone(),
+                            first_alias: first_alias.to_owned(),
+                            second_alias: alias.clone(),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Error)]
@@ -388,6 +452,19 @@ impl ProjectConfig {
 struct ParseConfig(#[from] toml::de::Error);
 impl_into_cot_error!(ParseConfig);
 
+#[cfg(feature = "db")]
+#[derive(Debug, Error)]
+#[error(
+    "app `{app}` is listed in the apps of more than one database: `{first_alias}` and `{second_alias}`"
+)]
+pub(crate) struct DuplicateDatabaseApp {
+    app: String,
+    first_alias: String,
+    second_alias: String,
+}
+#[cfg(feature = "db")]
+impl_into_cot_error!(DuplicateDatabaseApp);
+
 impl ProjectConfigBuilder {
     /// Builds the project configuration.
     ///
@@ -409,6 +486,8 @@ impl ProjectConfigBuilder {
             auth_backend: self.auth_backend.unwrap_or_default(),
             #[cfg(feature = "db")]
             database: self.database.clone().unwrap_or_default(),
+            #[cfg(feature = "db")]
+            databases: self.databases.clone().unwrap_or_default(),
             #[cfg(feature = "cache")]
             cache: self.cache.clone().unwrap_or_default(),
             static_files: self.static_files.clone().unwrap_or_default(),
@@ -460,7 +539,7 @@ pub enum AuthBackendConfig {
 /// let config = DatabaseConfig::builder().url("sqlite::memory:").build();
 /// ```
 #[cfg(feature = "db")]
-#[derive(Debug, Default, Clone, PartialEq, Eq, Builder, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Builder, Serialize, Deserialize)]
 #[builder(build_fn(skip, error = std::convert::Infallible))]
 #[serde(default)]
 #[non_exhaustive]
@@ -478,6 +557,102 @@ pub struct DatabaseConfig {
     /// ```
     #[builder(setter(into, strip_option), default)]
     pub url: Option<DatabaseUrl>,
+    /// Whether to apply the migrations automatically when the server starts.
+    ///
+    /// If disabled, the migrations have to be applied with the `migrate`
+    /// command of the project's CLI before the server starts, e.g. as a
+    /// separate deployment step.
+    ///
+    /// Default is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::config::DatabaseConfig;
+    ///
+    /// let config = DatabaseConfig::builder()
+    ///     .url("sqlite::memory:")
+    ///     .auto_migrate(false)
+    ///     .build();
+    /// assert!(!config.auto_migrate);
+    /// ```
+    #[builder(default = "true")]
+    pub auto_migrate: bool,
+    /// The URLs of the read replicas of the database.
+    ///
+    /// If any replicas are set, the queries reading application models (such
+    /// as [`Query::all`](crate::db::query::Query::all),
+    /// [`Query::get`](crate::db::query::Query::get), or
+    /// [`Query::count`](crate::db::query::Query::count)) are distributed
+    /// between the replicas, while all the writes, the transactions, and the
+    /// migrations are run on the primary database at [`Self::url`].
+    ///
+    /// Default is an empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::config::{DatabaseConfig, DatabaseUrl};
+    ///
+    /// let config = DatabaseConfig::builder()
+    ///     .url("postgresql://primary/db")
+    ///     .replicas(vec![DatabaseUrl::from("postgresql://replica/db")])
+    ///     .build();
+    /// assert_eq!(config.replicas.len(), 1);
+    /// ```
+    #[builder(default)]
+    pub replicas: Vec<DatabaseUrl>,
+    /// The names of the apps whose models are stored in this database.
+    ///
+    /// This is only used for the databases in [`ProjectConfig::databases`]:
+    /// the migrations of the listed apps are applied to that database instead
+    /// of the default one. An app can only be listed in one of the databases.
+    ///
+    /// Default is an empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::config::DatabaseConfig;
+    ///
+    /// let config = DatabaseConfig::builder()
+    ///     .url("sqlite://analytics.sqlite3")
+    ///     .apps(vec!["analytics".to_owned()])
+    ///     .build();
+    /// assert_eq!(config.apps, vec!["analytics".to_owned()]);
+    /// ```
+    #[builder(default)]
+    pub apps: Vec<String>,
+    /// The settings of the connection pool of the database.
+    ///
+    /// These are also applied to the pools of the [`Self::replicas`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::config::{DatabaseConfig, DatabasePoolConfig};
+    ///
+    /// let config = DatabaseConfig::builder()
+    ///     .url("postgresql://localhost/db")
+    ///     .pool(DatabasePoolConfig::builder().max_connections(50).build())
+    ///     .build();
+    /// assert_eq!(config.pool.max_connections, Some(50));
+    /// ```
+    #[builder(default)]
+    pub pool: DatabasePoolConfig,
+}
+
+#[cfg(feature = "db")]
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            auto_migrate: true,
+            replicas: Vec::new(),
+            apps: Vec::new(),
+            pool: DatabasePoolConfig::default(),
+        }
+    }
 }
 
 #[cfg(feature = "db")]
@@ -499,6 +674,10 @@ impl DatabaseConfigBuilder {
     pub fn build(&self) -> DatabaseConfig {
         DatabaseConfig {
             url: self.url.clone().expect("Database URL is required"),
+            auto_migrate: self.auto_migrate.unwrap_or(true),
+            replicas: self.replicas.clone().unwrap_or_default(),
+            apps: self.apps.clone().unwrap_or_default(),
+            pool: self.pool.clone().unwrap_or_default(),
         }
     }
 }
@@ -520,6 +699,214 @@ impl DatabaseConfig {
     }
 }
 
+/// The settings of a database connection pool.
+///
+/// It is used as part of the [`DatabaseConfig`] struct. The settings that are
+/// not set use the defaults of the underlying database driver.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use cot::config::DatabasePoolConfig;
+///
+/// let config = DatabasePoolConfig::builder()
+///     .max_connections(50)
+///     .min_connections(5)
+///     .acquire_timeout(Duration::from_secs(5))
+///     .init_sql(vec!["PRAGMA journal_mode = WAL".to_owned()])
+///     .build();
+/// ```
+#[cfg(feature = "db")]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Builder, Serialize, Deserialize)]
+#[builder(build_fn(skip, error = std::convert::Infallible))]
+#[serde(default)]
+#[non_exhaustive]
+pub struct DatabasePoolConfig {
+    /// The maximum number of connections in the pool.
+    ///
+    /// Default is `10`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::config::DatabasePoolConfig;
+    ///
+    /// let config = DatabasePoolConfig::builder().max_connections(50).build();
+    /// assert_eq!(config.max_connections, Some(50));
+    /// ```
+    #[builder(setter(strip_option), default)]
+    pub max_connections: Option<u32>,
+    /// The minimum number of idle connections the pool tries to maintain.
+    ///
+    /// Default is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::config::DatabasePoolConfig;
+    ///
+    /// let config = DatabasePoolConfig::builder().min_connections(5).build();
+    /// assert_eq!(config.min_connections, Some(5));
+    /// ```
+    #[builder(setter(strip_option), default)]
+    pub min_connections: Option<u32>,
+    /// The maximum time to wait for a connection from the pool before
+    /// returning an error.
+    ///
+    /// Default is 30 seconds.
+    ///
+    /// # TOML
+    ///
+    /// This field is serialized as a "human-readable" duration, like `5s`,
+    /// `1m`, etc. Please refer to the [`humantime::parse_duration`]
+    /// documentation for the supported formats for this field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use cot::config::ProjectConfig;
+    ///
+    /// let config = ProjectConfig::from_toml(
+    ///     r#"
+    /// [database.pool]
+    /// acquire_timeout = "5s"
+    /// "#,
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     config.database.pool.acquire_timeout,
+    ///     Some(Duration::from_secs(5))
+    /// );
+    /// # Ok::<(), cot::Error>(())
+    /// ```
+    #[serde(with = "crate::serializers::humantime")]
+    #[builder(setter(strip_option), default)]
+    pub acquire_timeout: Option<Duration>,
+    /// The time after which an idle connection is closed.
+    ///
+    /// Default is 10 minutes.
+    ///
+    /// # TOML
+    ///
+    /// This field is serialized as a "human-readable" duration, like `5s`,
+    /// `1m`, etc. Please refer to the [`humantime::parse_duration`]
+    /// documentation for the supported formats for this field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use cot::config::DatabasePoolConfig;
+    ///
+    /// let config = DatabasePoolConfig::builder()
+    ///     .idle_timeout(Duration::from_secs(60))
+    ///     .build();
+    /// assert_eq!(config.idle_timeout, Some(Duration::from_secs(60)));
+    /// ```
+    #[serde(with = "crate::serializers::humantime")]
+    #[builder(setter(strip_option), default)]
+    pub idle_timeout: Option<Duration>,
+    /// The time after which a connection is closed, regardless of whether it
+    /// is idle.
+    ///
+    /// Default is 30 minutes.
+    ///
+    /// # TOML
+    ///
+    /// This field is serialized as a "human-readable" duration, like `5s`,
+    /// `1m`, etc. Please refer to the [`humantime::parse_duration`]
+    /// documentation for the supported formats for this field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use cot::config::DatabasePoolConfig;
+    ///
+    /// let config = DatabasePoolConfig::builder()
+    ///     .max_lifetime(Duration::from_secs(3600))
+    ///     .build();
+    /// assert_eq!(config.max_lifetime, Some(Duration::from_secs(3600)));
+    /// ```
+    #[serde(with = "crate::serializers::humantime")]
+    #[builder(setter(strip_option), default)]
+    pub max_lifetime: Option<Duration>,
+    /// The SQL statements run on each new connection before it is used, e.g.
+    /// `PRAGMA journal_mode = WAL` on SQLite or `SET search_path = my_schema`
+    /// on PostgreSQL.
+    ///
+    /// Default is an empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::config::ProjectConfig;
+    ///
+    /// let config = ProjectConfig::from_toml(
+    ///     r#"
+    /// [database.pool]
+    /// init_sql = ["SET search_path = my_schema"]
+    /// "#,
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     config.database.pool.init_sql,
+    ///     vec!["SET search_path = my_schema".to_owned()]
+    /// );
+    /// # Ok::<(), cot::Error>(())
+    /// ```
+    #[builder(default)]
+    pub init_sql: Vec<String>,
+}
+
+#[cfg(feature = "db")]
+impl DatabasePoolConfigBuilder {
+    /// Builds the database pool configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::config::DatabasePoolConfig;
+    ///
+    /// let config = DatabasePoolConfig::builder().max_connections(50).build();
+    /// ```
+    #[must_use]
+    pub fn build(&self) -> DatabasePoolConfig {
+        DatabasePoolConfig {
+            max_connections: self.max_connections.unwrap_or_default(),
+            min_connections: self.min_connections.unwrap_or_default(),
+            acquire_timeout: self.acquire_timeout.unwrap_or_default(),
+            idle_timeout: self.idle_timeout.unwrap_or_default(),
+            max_lifetime: self.max_lifetime.unwrap_or_default(),
+            init_sql: self.init_sql.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(feature = "db")]
+impl DatabasePoolConfig {
+    /// Create a new [`DatabasePoolConfigBuilder`] to build a
+    /// [`DatabasePoolConfig`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::config::DatabasePoolConfig;
+    ///
+    /// let config = DatabasePoolConfig::builder().max_connections(50).build();
+    /// ```
+    #[must_use]
+    pub fn builder() -> DatabasePoolConfigBuilder {
+        DatabasePoolConfigBuilder::default()
+    }
+}
+
 /// Expiration policy for cached values.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -2766,6 +3153,124 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "db")]
+    fn database_auto_migrate_from_toml() {
+        let config = ProjectConfig::from_toml(
+            r#"
+            [database]
+            url = "sqlite::memory:"
+        "#,
+        )
+        .unwrap();
+        assert!(config.database.auto_migrate);
+
+        let config = ProjectConfig::from_toml(
+            r#"
+            [database]
+            url = "sqlite::memory:"
+            auto_migrate = false
+        "#,
+        )
+        .unwrap();
+        assert!(!config.database.auto_migrate);
+    }
+
+    #[test]
+    #[cfg(feature = "db")]
+    fn databases_from_toml() {
+        let config = ProjectConfig::from_toml(
+            r#"
+            [database]
+            url = "postgresql://primary/db"
+            replicas = ["postgresql://replica1/db", "postgresql://replica2/db"]
+
+            [databases.analytics]
+            url = "sqlite://analytics.sqlite3"
+            apps = ["analytics"]
+            auto_migrate = false
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.database.replicas,
+            vec![
+                DatabaseUrl::from("postgresql://replica1/db"),
+                DatabaseUrl::from("postgresql://replica2/db"),
+            ]
+        );
+        assert!(config.database.apps.is_empty());
+        assert_eq!(config.databases.len(), 1);
+        let analytics = &config.databases["analytics"];
+        assert_eq!(
+            analytics.url,
+            Some(DatabaseUrl::from("sqlite://analytics.sqlite3"))
+        );
+        assert_eq!(analytics.apps, vec!["analytics".to_owned()]);
+        assert!(analytics.replicas.is_empty());
+        assert!(!analytics.auto_migrate);
+    }
+
+    #[test]
+    #[cfg(feature = "db")]
+    fn databases_from_toml_duplicate_app() {
+        let result = ProjectConfig::from_toml(
+            r#"
+            [databases.reporting]
+            url = "sqlite://reporting.sqlite3"
+            apps = ["analytics"]
+
+            [databases.analytics]
+            url = "sqlite://analytics.sqlite3"
+            apps = ["analytics", "events"]
+        "#,
+        );
+
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains(
+            "app `analytics` is listed in the apps of more than one database: \
+             `analytics` and `reporting`"
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "db")]
+    fn database_pool_from_toml() {
+        let config = ProjectConfig::from_toml(
+            r#"
+            [database]
+            url = "sqlite::memory:"
+
+            [database.pool]
+            max_connections = 50
+            min_connections = 5
+            acquire_timeout = "5s"
+            idle_timeout = "1m"
+            max_lifetime = "1h"
+            init_sql = ["PRAGMA journal_mode = WAL"]
+        "#,
+        )
+        .unwrap();
+
+        let pool = &config.database.pool;
+        assert_eq!(pool.max_connections, Some(50));
+        assert_eq!(pool.min_connections, Some(5));
+        assert_eq!(pool.acquire_timeout, Some(Duration::from_secs(5)));
+        assert_eq!(pool.idle_timeout, Some(Duration::from_mins(1)));
+        assert_eq!(pool.max_lifetime, Some(Duration::from_hours(1)));
+        assert_eq!(pool.init_sql, vec!["PRAGMA journal_mode = WAL".to_owned()]);
+
+        let config = ProjectConfig::from_toml(
+            r#"
+            [database]
+            url = "sqlite::memory:"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.database.pool, DatabasePoolConfig::default());
+    }
+
     #[test]
     fn secret_key_serialize_json() {
         let key = SecretKey::from("abc123");
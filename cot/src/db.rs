@@ -15,13 +15,102 @@ pub mod query;
 mod relations;
 mod sea_query_db;
 
+use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::hash::Hash;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub use async_trait::async_trait;
 use cot_core::error::impl_into_cot_error;
+/// Derive the [`DbEnum`](trait@DbEnum) trait for an enum, allowing it to be
+/// used as a model field.
+///
+/// The enum must only have unit variants. By default, the variants are stored
+/// as text, with the name of the variant as the value. This can be changed for
+/// a single variant with the `#[db_enum(value = "...")]` attribute. If the
+/// enum also derives [`SelectChoice`](cot::form::fields::SelectChoice), the
+/// `id` set with `#[select_choice(id = "...")]` is used as the value, so that
+/// the same identifiers are used in the database and in forms (including the
+/// admin panel).
+///
+/// Alternatively, the variants can be stored as their integer discriminants
+/// using the `#[db_enum(storage = "integer")]` attribute on the enum.
+///
+/// The migration generator adds a `CHECK` constraint to the columns storing
+/// the enum, so that the database only accepts the values of the variants.
+///
+/// # Examples
+///
+/// ```
+/// use cot::db::{Auto, DbEnum, model};
+/// use cot::form::fields::{SelectAsFormField, SelectChoice};
+///
+/// #[derive(Debug, Clone, PartialEq, Eq, Hash, DbEnum, SelectChoice, SelectAsFormField)]
+/// enum Status {
+///     #[select_choice(id = "draft")]
+///     Draft,
+///     #[select_choice(id = "published")]
+///     Published,
+///     #[db_enum(value = "archive")]
+///     Archived,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum)]
+/// #[db_enum(storage = "integer")]
+/// enum Priority {
+///     Low = 1,
+///     Medium = 5,
+///     High = 10,
+/// }
+///
+/// #[model]
+/// struct Article {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     status: Status,
+///     priority: Option<Priority>,
+/// }
+/// ```
+pub use cot_macros::DbEnum;
+/// Derives the [`FromDbRow`] trait for a struct.
+///
+/// The struct's fields are read from the row's columns in the order they are
+/// declared, so the struct can be used to receive the results of queries
+/// that don't return whole model instances, such as [`Query::values`] or
+/// [`Database::raw_values`]. Each field type must implement
+/// [`FromDbValue`].
+///
+/// # Example
+///
+/// ```
+/// use cot::db::query::Expr;
+/// use cot::db::{Auto, Database, FromDbRow, model, query};
+///
+/// #[model]
+/// struct Article {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     title: String,
+///     body: String,
+/// }
+///
+/// #[derive(Debug, FromDbRow)]
+/// struct ArticleTitle {
+///     id: i32,
+///     title: String,
+/// }
+///
+/// # async fn example(db: &Database) -> cot::db::Result<()> {
+/// let titles: Vec<ArticleTitle> = query!(Article, $title.contains("Rust"))
+///     .values(db, &[Expr::field("id"), Expr::field("title")])
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub use cot_macros::FromDbRow;
 /// Implement the [`Model`] trait for a struct.
 ///
 /// This macro will generate an implementation of the [`Model`] trait for the
@@ -113,6 +202,28 @@ use cot_core::error::impl_into_cot_error;
 /// }
 /// ```
 ///
+/// ## `renamed_from`
+/// By default, the migration generator treats a renamed model as a removed
+/// model and a new one, which drops all the data stored in it. The
+/// `renamed_from` parameter tells the generator the previous name of the
+/// struct, so that the table is renamed instead:
+///
+/// ```
+/// use cot::db::{Auto, model};
+///
+/// // previously `struct Member`
+/// #[model(renamed_from = "Member")]
+/// struct User {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     username: String,
+/// }
+/// ```
+///
+/// The parameter is ignored if there is no model with given name in the
+/// latest migration, so it can be safely left in place after the migration
+/// has been generated.
+///
 /// # Field Attributes
 /// In addition to the struct-level attributes, you can also specify field-level
 /// attributes using the `#[model(...)]` attribute, which is used to specify
@@ -120,8 +231,8 @@ use cot_core::error::impl_into_cot_error;
 ///
 /// ## `primary_key`
 /// The `primary_key` attribute is used to specify that a field is the primary
-/// key of the model. This attribute is required and must be used on exactly one
-/// field of the struct.
+/// key of the model. This attribute is required and must be used on at least
+/// one field of the struct.
 ///
 /// ```
 /// use cot::db::{Auto, model};
@@ -134,6 +245,37 @@ use cot_core::error::impl_into_cot_error;
 /// }
 /// ```
 ///
+/// If the attribute is used on multiple fields, the model has a composite
+/// primary key, and [`Model::PrimaryKey`] is a tuple of the field types in the
+/// order of declaration. The fields of a composite primary key can't be
+/// [`Auto`], and such models can't have [`ManyToMany`] fields.
+///
+/// ```
+/// use cot::db::{Auto, ForeignKey, Model, model};
+///
+/// #[derive(Debug, Clone)]
+/// #[model]
+/// struct User {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     username: String,
+/// }
+///
+/// #[derive(Debug, Clone)]
+/// #[model]
+/// struct Membership {
+///     #[model(primary_key)]
+///     user: ForeignKey<User>,
+///     #[model(primary_key)]
+///     group: String,
+///     is_admin: bool,
+/// }
+///
+/// # fn primary_key(membership: &Membership) {
+/// let (user, group): (&ForeignKey<User>, &String) = membership.primary_key();
+/// # }
+/// ```
+///
 /// ## `unique`
 /// The `unique` attribute is used to specify that a field must be unique across
 /// all rows in the database. This will create a unique constraint on the
@@ -169,6 +311,49 @@ use cot_core::error::impl_into_cot_error;
 /// }
 /// ```
 ///
+/// ## `renamed_from`
+/// Similarly to the struct-level attribute, the `renamed_from` attribute tells
+/// the migration generator the previous name of the field, so that the column
+/// is renamed instead of being removed and added again:
+///
+/// ```
+/// use cot::db::{Auto, model};
+///
+/// #[model]
+/// struct User {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     // previously `name: String`
+///     #[model(renamed_from = "name")]
+///     username: String,
+/// }
+/// ```
+///
+/// ## `default`
+/// The `default` attribute sets the default value of the column in the
+/// database. It is used to fill in the existing rows when the field is added
+/// to a model, and when a row is inserted without a value for the column.
+/// The value can be a literal (`#[model(default = 0)]`), or an SQL
+/// expression (`#[model(default(expr = "CURRENT_TIMESTAMP"))]`).
+///
+/// To make the database fill in the default value when inserting a row, wrap
+/// the field in [`Auto`] and set it to [`Auto::auto()`]. After the row is
+/// saved, the field contains the value set by the database.
+///
+/// ```
+/// use cot::db::{Auto, model};
+///
+/// #[model]
+/// struct Post {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     #[model(default = 0)]
+///     views: i32,
+///     #[model(default(expr = "CURRENT_TIMESTAMP"))]
+///     created_at: Auto<chrono::NaiveDateTime>,
+/// }
+/// ```
+///
 /// ## `foreign_key`
 ///
 /// The `foreign_key` attribute configures the referential integrity behavior
@@ -263,6 +448,40 @@ use cot_core::error::impl_into_cot_error;
 ///     author_id: Option<ForeignKey<User>>,
 /// }
 /// ```
+///
+/// ### Composite foreign keys
+///
+/// A [`ForeignKey`] to a model with a composite primary key is stored in
+/// multiple columns, one for each of the primary key fields of the referenced
+/// model. The names of the columns are specified with the `columns` parameter,
+/// in the order of the primary key fields:
+///
+/// ```
+/// use cot::db::{Auto, ForeignKey, model};
+///
+/// #[derive(Debug, Clone)]
+/// #[model]
+/// struct Enrollment {
+///     #[model(primary_key)]
+///     student_id: i32,
+///     #[model(primary_key)]
+///     course: String,
+/// }
+///
+/// #[model]
+/// struct Grade {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     #[model(foreign_key(columns(enrollment_student_id, enrollment_course)))]
+///     enrollment: ForeignKey<Enrollment>,
+///     grade: i32,
+/// }
+/// ```
+///
+/// The columns are covered by a single foreign key constraint, and `unique`
+/// and `index` apply to all of them together. Composite foreign keys can't be
+/// optional, a part of the primary key, have a default value, or declare a
+/// `related_name`.
 pub use cot_macros::model;
 /// A convenient macro that allows you to write queries in a declarative
 /// fashion.
@@ -276,7 +495,7 @@ pub use cot_macros::model;
 /// The macro expands roughly to:
 ///
 /// ```ignore
-/// <Model as cot::db::Model>::objects().filter(...)
+/// <Model as cot::db::Model>::objects().filter(...).order_by(...)
 /// ```
 ///
 /// # Query syntax
@@ -488,14 +707,50 @@ pub use cot_macros::model;
 /// See [`Expr::contains`](cot::db::query::expr::Expr::contains) and
 /// [`Expr::raw_like`](cot::db::query::expr::Expr::raw_like) for the full
 /// semantics, escaping rules, and glob pattern syntax.
+///
+/// ## Ordering
+///
+/// The filter expression can be followed by a semicolon and an `order_by`
+/// clause containing a comma-separated list of sort keys. Each key is a field
+/// reference, optionally followed by `.asc()` (the default) or `.desc()`, and
+/// by `.nulls_first()` or `.nulls_last()` to control where `NULL` values are
+/// placed. The filter expression can be omitted if only the ordering is
+/// needed.
+///
+/// ```
+/// use cot::db::{model, query};
+///
+/// # #[model]
+/// # struct Customer {
+/// #     #[model(primary_key)]
+/// #     id: i32,
+/// #     full_name: String,
+/// #     price: Option<i32>,
+/// #     is_active: bool,
+/// # }
+/// let _ = query!(Customer, $is_active == true; order_by($full_name));
+/// let _ = query!(Customer; order_by($price.desc().nulls_last(), $id.asc()));
+/// ```
+///
+/// See [`OrderBy`](cot::db::query::order::OrderBy) for more details.
 pub use cot_macros::query;
 use derive_more::{Debug, Deref, Display};
+use futures_core::stream::BoxStream;
+use futures_util::StreamExt;
 #[cfg(test)]
 use mockall::automock;
-use query::Query;
 use query::expr::SqlQueryBuilder;
+use query::expr::array::{ArrayExprBuilder, ArrayOperator};
+use query::expr::cast::CastExprBuilder;
+use query::expr::json::JsonExprBuilder;
 use query::expr::like::{CaseSensitivity, LikeExprBuilder};
-pub use relations::{ForeignKey, ForeignKeyOnDeletePolicy, ForeignKeyOnUpdatePolicy};
+use query::expr::search::SearchExprBuilder;
+use query::update::Assignment;
+use query::{Expr, Query};
+pub use relations::{
+    CompositeForeignKeyField, ForeignKey, ForeignKeyField, ForeignKeyOnDeletePolicy,
+    ForeignKeyOnUpdatePolicy, ManyToMany, ManyToManyTable, ReverseRelation,
+};
 use sea_query::{
     ColumnRef, ExprTrait, Iden, IntoColumnRef, OnConflict, ReturningClause, SchemaStatementBuilder,
     SimpleExpr,
@@ -506,6 +761,7 @@ use sqlx::{Acquire, Type, TypeInfo};
 use thiserror::Error;
 use tracing::{Instrument, Level, span, trace};
 
+use crate::config::DatabasePoolConfig;
 #[cfg(feature = "mysql")]
 use crate::db::impl_mysql::{DatabaseMySql, MySqlRow, MySqlValueRef, TransactionMySql};
 #[cfg(feature = "postgres")]
@@ -514,8 +770,9 @@ use crate::db::impl_postgres::{
 };
 #[cfg(feature = "sqlite")]
 use crate::db::impl_sqlite::{DatabaseSqlite, SqliteRow, SqliteValueRef, TransactionSqlite};
-use crate::db::migrations::ColumnTypeMapper;
+use crate::db::migrations::{ColumnTypeMapper, DefaultValue};
 use crate::db::query::QueryBuildingError;
+use crate::db::sea_query_db::SqlRecorder;
 
 const ERROR_PREFIX: &str = "database error:";
 /// An error that can occur when interacting with the database.
@@ -551,10 +808,28 @@ pub enum DatabaseError {
         /// The primary key of the record that was not found.
         primary_key: DbValue,
     },
+    /// A record of a model with a composite primary key could not be found in
+    /// the database.
+    #[error(
+        "{ERROR_PREFIX} record with primary key `({})` not found in the database",
+        display_primary_key(primary_key)
+    )]
+    CompositeRecordNotFound {
+        /// The values of the primary key columns of the record that was not
+        /// found.
+        primary_key: Vec<DbValue>,
+    },
     /// Foreign Key could not be retrieved from the database because the record
     /// was not found.
     #[error("{ERROR_PREFIX} error retrieving a Foreign Key from the database: record not found")]
     ForeignKeyNotFound,
+    /// A model that hasn't been saved to the database yet was used in a
+    /// many-to-many relation.
+    #[error(
+        "{ERROR_PREFIX} model has to be saved to the database before it can be used in a \
+        many-to-many relation"
+    )]
+    UnsavedModel,
     /// Error when a unique constraint is violated in the database.
     #[error("{ERROR_PREFIX} unique constraint violation")]
     UniqueViolation,
@@ -575,6 +850,30 @@ pub enum DatabaseError {
         auto-generated fields should be created individually using insert()"
     )]
     BulkInsertNoValueColumns,
+    /// A model that doesn't implement [`Model::from_db_at`] was read from a
+    /// row containing the columns of other models, such as when it's fetched
+    /// with [`Query::select_related`].
+    #[error(
+        "{ERROR_PREFIX} model `{model}` can't be read from a row containing the columns of other \
+        models; implement `Model::from_db_at` to support it"
+    )]
+    RowOffsetUnsupported {
+        /// The name of the model type.
+        model: &'static str,
+    },
+    /// Attempted to update rows without assigning any column.
+    #[error("{ERROR_PREFIX} update requires at least one assignment")]
+    UpdateNoAssignments,
+    /// A feature that is specific to some database backends was used with a
+    /// backend that doesn't support it, such as array columns outside of
+    /// PostgreSQL.
+    #[error("{ERROR_PREFIX} {feature} are not supported by the {backend} backend")]
+    UnsupportedByBackend {
+        /// The feature that is not supported, such as `array columns`.
+        feature: &'static str,
+        /// The name of the database backend.
+        backend: &'static str,
+    },
     /// Data returned by the bulk insert does not match the expected number of
     /// rows.
     #[error(
@@ -597,6 +896,29 @@ impl DatabaseError {
     pub fn value_decode(error: impl std::error::Error + 'static + Send + Sync) -> Self {
         Self::ValueDecode(Box::new(error))
     }
+
+    /// Returns whether the error was caused by a conflict with a concurrent
+    /// transaction, meaning that the transaction can succeed if it's retried.
+    ///
+    /// This is the case for serialization failures and deadlocks (SQLSTATE
+    /// `40001` and `40P01`) reported by PostgreSQL and MySQL, and for the
+    /// database being busy (`SQLITE_BUSY`) on SQLite.
+    ///
+    /// See [`Database::transaction_with_retry`] for a helper that retries the
+    /// transactions failing with such errors.
+    #[must_use]
+    pub fn is_transaction_conflict(&self) -> bool {
+        // SQLSTATE codes for PostgreSQL and MySQL, and the (extended) result
+        // codes for `SQLITE_BUSY` and `SQLITE_BUSY_SNAPSHOT` for SQLite
+        const CONFLICT_CODES: [&str; 4] = ["40001", "40P01", "5", "517"];
+
+        match self {
+            Self::DatabaseEngineError(sqlx::Error::Database(error)) => error
+                .code()
+                .is_some_and(|code| CONFLICT_CODES.contains(&code.as_ref())),
+            _ => false,
+        }
+    }
 }
 
 /// An alias for [`Result`] that uses [`DatabaseError`] as the error type.
@@ -654,7 +976,10 @@ pub trait Model: Sized + Send + 'static {
     type Fields;
 
     /// The primary key type of the model.
-    type PrimaryKey: PrimaryKey;
+    ///
+    /// For models with a composite primary key, this is a tuple of the
+    /// primary key field types, in the order they are declared in the model.
+    type PrimaryKey: ModelPrimaryKey;
 
     /// The name of the app in which this model is defined.
     const APP_NAME: &'static str;
@@ -663,8 +988,18 @@ pub trait Model: Sized + Send + 'static {
     const TABLE_NAME: Identifier;
 
     /// The name of the primary key column in the database.
+    ///
+    /// For models with a composite primary key, this is the name of the
+    /// first primary key column; see [`Self::PRIMARY_KEY_NAMES`] for all of
+    /// them.
     const PRIMARY_KEY_NAME: Identifier;
 
+    /// The names of all the primary key columns in the database.
+    ///
+    /// This contains more than one column if the model has a composite
+    /// primary key.
+    const PRIMARY_KEY_NAMES: &'static [Identifier] = &[Self::PRIMARY_KEY_NAME];
+
     /// The columns of the model.
     const COLUMNS: &'static [Column];
 
@@ -679,6 +1014,58 @@ pub trait Model: Sized + Send + 'static {
     /// with the model.
     fn from_db(db_row: Row) -> Result<Self>;
 
+    /// Creates a model instance from a database row, reading the model's
+    /// columns starting at the given index.
+    ///
+    /// This is used by the ORM to read related models that were fetched
+    /// together with the main model (see [`Query::select_related`]).
+    ///
+    /// The default implementation returns
+    /// [`DatabaseError::RowOffsetUnsupported`]; the implementation generated
+    /// by [`#[model]`](macro@model) supports any offset.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the data in the row is not compatible
+    /// with the model.
+    fn from_db_at(db_row: &Row, offset: usize) -> Result<Self> {
+        let _ = (db_row, offset);
+        Err(DatabaseError::RowOffsetUnsupported {
+            model: std::any::type_name::<Self>(),
+        })
+    }
+
+    /// Fills the foreign key field stored in the given column with the related
+    /// model instance read from a database row, starting at the given index.
+    ///
+    /// This is used by the ORM to populate [`ForeignKey::Model`] when the
+    /// related model is fetched together with the main model (see
+    /// [`Query::select_related`]).
+    ///
+    /// The default implementation is meant for the models without foreign
+    /// keys, and always returns [`QueryBuildingError::NotAForeignKey`].
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the data in the row is not compatible
+    /// with the related model.
+    ///
+    /// This method returns [`QueryBuildingError::NotAForeignKey`] if the column
+    /// is not a foreign key of this model.
+    fn fill_related(&mut self, column: Identifier, db_row: &Row, offset: usize) -> Result<()> {
+        let _ = (db_row, offset);
+        Err(QueryBuildingError::NotAForeignKey(column.as_str().to_owned()).into())
+    }
+
+    /// Binds the [`ManyToMany`] fields of the model to the model's primary
+    /// key, so that they can be used to query and modify the related models.
+    ///
+    /// This is used by the ORM whenever the model instance is read from or
+    /// saved to the database. If the primary key is still [`Auto`], the fields
+    /// are left unbound. The default implementation does nothing, which is
+    /// correct for the models without many-to-many fields.
+    fn bind_many_to_many(&mut self) {}
+
     /// Updates the model instance from a database row.
     ///
     /// This is used by the ORM to update the model instance after saving with
@@ -691,7 +1078,11 @@ pub trait Model: Sized + Send + 'static {
     fn update_from_db(&mut self, db_row: Row, columns: &[usize]) -> Result<()>;
 
     /// Returns the primary key of the model.
-    fn primary_key(&self) -> &Self::PrimaryKey;
+    ///
+    /// This is a reference to the primary key for models with a single
+    /// primary key field, and a tuple of references to the primary key fields
+    /// for models with a composite primary key.
+    fn primary_key(&self) -> <Self::PrimaryKey as ModelPrimaryKey>::Borrowed<'_>;
 
     /// Used by the ORM to set the primary key of the model after it has been
     /// saved to the database.
@@ -905,6 +1296,149 @@ impl Column {
 /// database.
 pub trait PrimaryKey: DatabaseField + Clone {}
 
+/// The primary key type of a model.
+///
+/// This is implemented for all the [`PrimaryKey`] types, which are used by the
+/// models with a single primary key column, as well as for tuples of them,
+/// which are used as the primary key type of models with a composite primary
+/// key.
+///
+/// [`Model::primary_key`] borrows the primary key of a model instance as
+/// [`Self::Borrowed`]: a reference to the primary key for single-column
+/// primary keys, and a tuple of references to the primary key fields for
+/// composite primary keys.
+pub trait ModelPrimaryKey: Clone + 'static {
+    /// The borrowed form of the primary key, as returned by
+    /// [`Model::primary_key`].
+    type Borrowed<'a>: Copy;
+
+    /// Borrows the primary key.
+    fn borrow_key(&self) -> Self::Borrowed<'_>;
+
+    /// Creates an owned primary key from its borrowed form.
+    fn from_borrowed(key: Self::Borrowed<'_>) -> Self;
+}
+
+impl<T: PrimaryKey + 'static> ModelPrimaryKey for T {
+    type Borrowed<'a> = &'a T;
+
+    fn borrow_key(&self) -> &T {
+        self
+    }
+
+    fn from_borrowed(key: &T) -> Self {
+        key.clone()
+    }
+}
+
+/// A [`PrimaryKey`] of a model with a single primary key column.
+///
+/// This is implemented automatically for all the [`PrimaryKey`] types. Use it
+/// instead of [`PrimaryKey`] to bound the primary key type of a model in
+/// generic code, so that [`Model::primary_key`] is known to return a
+/// reference to the primary key.
+///
+/// # Examples
+///
+/// ```
+/// use cot::db::{DbFieldValue, Model, SinglePrimaryKey, ToDbFieldValue};
+///
+/// fn primary_key_value<T: Model>(model: &T) -> DbFieldValue
+/// where
+///     T::PrimaryKey: SinglePrimaryKey,
+/// {
+///     let primary_key: &T::PrimaryKey = model.primary_key();
+///     primary_key.to_db_field_value()
+/// }
+/// ```
+pub trait SinglePrimaryKey: PrimaryKey + for<'a> ModelPrimaryKey<Borrowed<'a> = &'a Self> {}
+
+impl<T: PrimaryKey + 'static> SinglePrimaryKey for T {}
+
+/// The primary key of a model with a composite primary key, i.e. a tuple of
+/// [`PrimaryKey`] types.
+///
+/// This is used by the ORM to store [`ForeignKey`] fields referencing models
+/// with a composite primary key in multiple columns.
+pub trait CompositePrimaryKey: ModelPrimaryKey {
+    /// The number of the primary key columns.
+    const COLUMN_COUNT: usize;
+
+    /// Reads the primary key from a database row, starting at the given
+    /// column index.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the data in the row is not compatible
+    /// with the primary key types.
+    fn from_db_at(db_row: &Row, offset: usize) -> Result<Self>;
+
+    /// Returns the value of the primary key column with the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not smaller than [`Self::COLUMN_COUNT`].
+    fn column_value(key: Self::Borrowed<'_>, index: usize) -> &dyn ToDbFieldValue;
+}
+
+/// The type of the primary key column with the index `INDEX` of a
+/// [`CompositePrimaryKey`].
+///
+/// This is used by the migrations to determine the column types of the
+/// [`ForeignKey`] fields referencing models with a composite primary key.
+pub trait CompositePrimaryKeyColumn<const INDEX: usize>: CompositePrimaryKey {
+    /// The type of the column.
+    type Type: PrimaryKey;
+}
+
+macro_rules! impl_composite_primary_key_for_tuple {
+    ($($ty:ident: $index:tt),+) => {
+        impl<$($ty: PrimaryKey + 'static),+> ModelPrimaryKey for ($($ty,)+) {
+            type Borrowed<'a> = ($(&'a $ty,)+);
+
+            fn borrow_key(&self) -> Self::Borrowed<'_> {
+                ($(&self.$index,)+)
+            }
+
+            fn from_borrowed(key: Self::Borrowed<'_>) -> Self {
+                ($(key.$index.clone(),)+)
+            }
+        }
+
+        impl<$($ty: PrimaryKey + 'static),+> CompositePrimaryKey for ($($ty,)+) {
+            const COLUMN_COUNT: usize = [$($index),+].len();
+
+            fn from_db_at(db_row: &Row, offset: usize) -> Result<Self> {
+                Ok(($(db_row.get::<$ty>(offset + $index)?,)+))
+            }
+
+            fn column_value(key: Self::Borrowed<'_>, index: usize) -> &dyn ToDbFieldValue {
+                match index {
+                    $($index => key.$index,)+
+                    _ => panic!("Unknown primary key column index: {index}"),
+                }
+            }
+        }
+
+        impl_composite_primary_key_column_for_tuple!([$($ty),+] $($ty: $index),+);
+    };
+}
+
+macro_rules! impl_composite_primary_key_column_for_tuple {
+    ($all:tt $($ty:ident: $index:tt),+) => {
+        $(impl_composite_primary_key_column_for_tuple!(@column $all $ty: $index);)+
+    };
+    (@column [$($all:ident),+] $ty:ident: $index:tt) => {
+        impl<$($all: PrimaryKey + 'static),+> CompositePrimaryKeyColumn<$index> for ($($all,)+) {
+            type Type = $ty;
+        }
+    };
+}
+
+impl_composite_primary_key_for_tuple!(T0: 0, T1: 1);
+impl_composite_primary_key_for_tuple!(T0: 0, T1: 1, T2: 2);
+impl_composite_primary_key_for_tuple!(T0: 0, T1: 1, T2: 2, T3: 3);
+
 /// A row structure that holds the data of a single row retrieved from the
 /// database.
 #[non_exhaustive]
@@ -952,6 +1486,57 @@ impl Row {
     }
 }
 
+/// A trait denoting that some type can be created from a database [`Row`].
+///
+/// This is used to convert the rows returned by queries that don't return
+/// whole model instances, such as [`Query::values`] and
+/// [`Query::aggregate`], into Rust values. The trait is implemented for
+/// tuples of up to 12 elements, where each element is read from the column at
+/// the same position in the row, and can be derived for structs using the
+/// [`FromDbRow`](macro@FromDbRow) derive macro.
+///
+/// # Example
+///
+/// ```
+/// use cot::db::{FromDbRow, Row};
+///
+/// fn read_pair(row: Row) -> cot::db::Result<(String, i64)> {
+///     <(String, i64)>::from_db_row(row)
+/// }
+/// ```
+pub trait FromDbRow: Sized {
+    /// Creates a new instance of the type from the given database row.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the row doesn't have enough columns
+    /// or if any of the values is not compatible with the Rust type.
+    fn from_db_row(row: Row) -> Result<Self>;
+}
+
+macro_rules! impl_from_db_row_for_tuple {
+    ($($ty:ident: $index:tt),+) => {
+        impl<$($ty: FromDbValue),+> FromDbRow for ($($ty,)+) {
+            fn from_db_row(row: Row) -> Result<Self> {
+                Ok(($(row.get::<$ty>($index)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_db_row_for_tuple!(T0: 0);
+impl_from_db_row_for_tuple!(T0: 0, T1: 1);
+impl_from_db_row_for_tuple!(T0: 0, T1: 1, T2: 2);
+impl_from_db_row_for_tuple!(T0: 0, T1: 1, T2: 2, T3: 3);
+impl_from_db_row_for_tuple!(T0: 0, T1: 1, T2: 2, T3: 3, T4: 4);
+impl_from_db_row_for_tuple!(T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5);
+impl_from_db_row_for_tuple!(T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5, T6: 6);
+impl_from_db_row_for_tuple!(T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5, T6: 6, T7: 7);
+impl_from_db_row_for_tuple!(T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5, T6: 6, T7: 7, T8: 8);
+impl_from_db_row_for_tuple!(T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5, T6: 6, T7: 7, T8: 8, T9: 9);
+impl_from_db_row_for_tuple!(T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5, T6: 6, T7: 7, T8: 8, T9: 9, T10: 10);
+impl_from_db_row_for_tuple!(T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5, T6: 6, T7: 7, T8: 8, T9: 9, T10: 10, T11: 11);
+
 /// A trait denoting that some type can be used as a field in a database.
 pub trait DatabaseField: FromDbValue + ToDbFieldValue {
     /// Whether the field can be `NULL` in the database.
@@ -980,6 +1565,25 @@ pub trait DatabaseField: FromDbValue + ToDbFieldValue {
     const TYPE: ColumnType;
 }
 
+/// A Rust enum that is stored in the database as one of a fixed set of
+/// values.
+///
+/// This trait should be derived using the [`DbEnum`](macro@DbEnum) derive
+/// macro, which also implements [`DatabaseField`] and [`ToDbValue`] for the
+/// enum. [`FromDbValue`] is implemented for all the types implementing this
+/// trait.
+pub trait DbEnum: Sized {
+    /// The type of the values the variants are stored as.
+    type Repr: DatabaseField + ToDbValue + std::fmt::Debug;
+
+    /// Returns the value the variant is stored as.
+    fn to_repr(&self) -> Self::Repr;
+
+    /// Returns the variant stored as the given value, or [`None`] if the value
+    /// doesn't correspond to any variant.
+    fn from_repr(repr: &Self::Repr) -> Option<Self>;
+}
+
 /// A trait for converting a database value to a Rust value.
 pub trait FromDbValue {
     /// Converts the given SQLite database value to a Rust value.
@@ -1320,25 +1924,75 @@ impl Transaction<'_> {
         }
     }
 
-    /// Starts a new database transaction (savepoint).
+    /// Starts a new nested transaction within this one.
+    ///
+    /// This is the same as [`Transaction::savepoint`]; see its documentation
+    /// for more details.
     ///
     /// # Errors
     ///
     /// Returns an error if the transaction could not be started.
     pub async fn begin(&mut self) -> Result<Transaction<'_>> {
-        let inner = match &mut self.inner {
-            #[cfg(feature = "sqlite")]
-            TransactionImpl::Sqlite(inner) => {
-                TransactionImpl::Sqlite(TransactionSqlite::new(inner.inner.begin().await?))
-            }
-            #[cfg(feature = "postgres")]
-            TransactionImpl::Postgres(inner) => {
-                TransactionImpl::Postgres(TransactionPostgres::new(inner.inner.begin().await?))
-            }
-            #[cfg(feature = "mysql")]
-            TransactionImpl::MySql(inner) => {
-                TransactionImpl::MySql(TransactionMySql::new(inner.inner.begin().await?))
-            }
+        self.savepoint().await
+    }
+
+    /// Creates a savepoint within the transaction, returned as a nested
+    /// transaction.
+    ///
+    /// The changes made using the nested transaction can be undone with
+    /// [`Transaction::rollback`] (`ROLLBACK TO SAVEPOINT`) without affecting
+    /// the enclosing transaction, which can still be committed. Committing
+    /// the nested transaction (`RELEASE SAVEPOINT`) hands its changes over to
+    /// the enclosing transaction, so they are only persisted once the
+    /// outermost transaction is committed. If the nested transaction is
+    /// dropped without being committed, it is rolled back.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the savepoint could not be created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Database;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// let db = Database::new("sqlite::memory:").await?;
+    /// db.raw("CREATE TABLE log (message TEXT)").await?;
+    ///
+    /// let mut transaction = db.begin().await?;
+    /// transaction
+    ///     .raw("INSERT INTO log (message) VALUES ('kept')")
+    ///     .await?;
+    ///
+    /// let mut savepoint = transaction.savepoint().await?;
+    /// savepoint
+    ///     .raw("INSERT INTO log (message) VALUES ('discarded')")
+    ///     .await?;
+    /// savepoint.rollback().await?;
+    ///
+    /// transaction.commit().await?;
+    ///
+    /// let messages = db.raw_values::<(String,)>("SELECT message FROM log").await?;
+    /// assert_eq!(messages, vec![("kept".to_owned(),)]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn savepoint(&mut self) -> Result<Transaction<'_>> {
+        let inner = match &mut self.inner {
+            #[cfg(feature = "sqlite")]
+            TransactionImpl::Sqlite(inner) => {
+                TransactionImpl::Sqlite(TransactionSqlite::new(inner.inner.begin().await?))
+            }
+            #[cfg(feature = "postgres")]
+            TransactionImpl::Postgres(inner) => {
+                TransactionImpl::Postgres(TransactionPostgres::new(inner.inner.begin().await?))
+            }
+            #[cfg(feature = "mysql")]
+            TransactionImpl::MySql(inner) => {
+                TransactionImpl::MySql(TransactionMySql::new(inner.inner.begin().await?))
+            }
         };
 
         Ok(Transaction {
@@ -1540,6 +2194,58 @@ impl Transaction<'_> {
         rows.into_iter().map(T::from_db).collect::<Result<_>>()
     }
 
+    /// Executes a raw SQL query within the transaction and converts the results
+    /// to a type implementing [`FromDbRow`].
+    ///
+    /// This is the transaction-scoped counterpart of [`Database::raw_values`].
+    ///
+    /// # Safety
+    ///
+    /// This method executes the raw SQL string without any sanitization.
+    /// Callers are responsible for ensuring the query is safe.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query is invalid, if the values returned by the
+    /// database are not compatible with the requested Rust types, or if the
+    /// database connection is lost.
+    pub async fn raw_values<R: FromDbRow>(&mut self, query: &str) -> Result<Vec<R>> {
+        self.raw_values_with(query, &[]).await
+    }
+
+    /// Executes a raw SQL query with parameters within the transaction and
+    /// converts the results to a type implementing [`FromDbRow`].
+    ///
+    /// This is the transaction-scoped counterpart of
+    /// [`Database::raw_values_with`].
+    ///
+    /// # Safety
+    ///
+    /// This method executes the raw SQL string without any sanitization.
+    /// Callers are responsible for ensuring the query is safe. The bound
+    /// `values`, however, are passed to the database driver separately from
+    /// the query text and are safe from SQL injection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query is invalid, if the values returned by the
+    /// database are not compatible with the requested Rust types, or if the
+    /// database connection is lost.
+    pub async fn raw_values_with<R: FromDbRow>(
+        &mut self,
+        query: &str,
+        values: &[&dyn ToDbValue],
+    ) -> Result<Vec<R>> {
+        let values = values
+            .iter()
+            .map(ToDbValue::to_db_value)
+            .collect::<Vec<_>>();
+        let values = SqlxValues(sea_query::Values(values));
+
+        let rows = self.fetch_all(&RawStatement { sql: query, values }).await?;
+        rows.into_iter().map(R::from_db_row).collect::<Result<_>>()
+    }
+
     async fn fetch_option<T>(&mut self, statement: &T) -> Result<Option<Row>>
     where
         T: SqlxBinder + Send + Sync,
@@ -1593,6 +2299,29 @@ impl Transaction<'_> {
         Ok(result)
     }
 
+    fn fetch_stream<T>(&mut self, statement: &T) -> BoxStream<'_, Result<Row>>
+    where
+        T: SqlxBinder + Send + Sync,
+    {
+        match &mut self.inner {
+            #[cfg(feature = "sqlite")]
+            TransactionImpl::Sqlite(inner) => inner
+                .fetch_stream::<T>(statement)
+                .map(|row| row.map(Row::Sqlite))
+                .boxed(),
+            #[cfg(feature = "postgres")]
+            TransactionImpl::Postgres(inner) => inner
+                .fetch_stream::<T>(statement)
+                .map(|row| row.map(Row::Postgres))
+                .boxed(),
+            #[cfg(feature = "mysql")]
+            TransactionImpl::MySql(inner) => inner
+                .fetch_stream::<T>(statement)
+                .map(|row| row.map(Row::MySql))
+                .boxed(),
+        }
+    }
+
     async fn execute_statement<T>(&mut self, statement: &T) -> Result<StatementResult>
     where
         T: SqlxBinder + Send + Sync,
@@ -1640,6 +2369,10 @@ impl DatabaseBackend for Transaction<'_> {
         Database::get_generic(self, query).await
     }
 
+    fn stream<T: Model + Send + 'static>(&mut self, query: Query<T>) -> BoxStream<'_, Result<T>> {
+        Database::stream_generic(self, query)
+    }
+
     async fn exists<T: Model>(&mut self, query: &Query<T>) -> Result<bool> {
         Database::exists_generic(self, query).await
     }
@@ -1648,9 +2381,62 @@ impl DatabaseBackend for Transaction<'_> {
         Database::count_generic(self, query).await
     }
 
+    async fn values<T: Model, R: FromDbRow + Send + 'static>(
+        &mut self,
+        query: &Query<T>,
+        columns: &[Expr],
+    ) -> Result<Vec<R>> {
+        Database::values_generic(self, query, columns).await
+    }
+
+    async fn update_many<T: Model>(
+        &mut self,
+        query: &Query<T>,
+        assignments: &[Assignment],
+    ) -> Result<StatementResult> {
+        Database::update_many_generic(self, query, assignments).await
+    }
+
     async fn delete<T: Model>(&mut self, query: &Query<T>) -> Result<StatementResult> {
         Database::delete_generic(self, query).await
     }
+
+    async fn query_many_to_many<T: Model>(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+    ) -> Result<Vec<T>>
+    where
+        T::PrimaryKey: SinglePrimaryKey,
+    {
+        Database::query_many_to_many_generic(self, table, source_pk).await
+    }
+
+    async fn add_many_to_many(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+        target_pk: &DbValue,
+    ) -> Result<()> {
+        Database::add_many_to_many_generic(self, table, source_pk, target_pk).await
+    }
+
+    async fn remove_many_to_many(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+        target_pk: &DbValue,
+    ) -> Result<StatementResult> {
+        Database::remove_many_to_many_generic(self, table, source_pk, Some(target_pk)).await
+    }
+
+    async fn clear_many_to_many(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+    ) -> Result<StatementResult> {
+        Database::remove_many_to_many_generic(self, table, source_pk, None).await
+    }
 }
 
 impl LikeExprBuilder for Transaction<'_> {
@@ -1677,6 +2463,96 @@ impl LikeExprBuilder for Transaction<'_> {
     }
 }
 
+impl CastExprBuilder for Transaction<'_> {
+    fn cast_expr(
+        &self,
+        expr: SimpleExpr,
+        column_type: ColumnType,
+    ) -> std::result::Result<SimpleExpr, QueryBuildingError> {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            TransactionImpl::Sqlite(_) => impl_sqlite::build_cast_expr(expr, column_type),
+            #[cfg(feature = "postgres")]
+            TransactionImpl::Postgres(_) => impl_postgres::build_cast_expr(expr, column_type),
+            #[cfg(feature = "mysql")]
+            TransactionImpl::MySql(_) => impl_mysql::build_cast_expr(expr, column_type),
+        }
+    }
+}
+
+impl ArrayExprBuilder for Transaction<'_> {
+    fn array_expr(
+        &self,
+        lhs: SimpleExpr,
+        rhs: SimpleExpr,
+        operator: ArrayOperator,
+    ) -> std::result::Result<SimpleExpr, QueryBuildingError> {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            TransactionImpl::Sqlite(_) => impl_sqlite::build_array_expr(lhs, rhs, operator),
+            #[cfg(feature = "postgres")]
+            TransactionImpl::Postgres(_) => impl_postgres::build_array_expr(lhs, rhs, operator),
+            #[cfg(feature = "mysql")]
+            TransactionImpl::MySql(_) => impl_mysql::build_array_expr(lhs, rhs, operator),
+        }
+    }
+}
+
+impl SearchExprBuilder for Transaction<'_> {
+    fn search_expr(
+        &self,
+        document: SimpleExpr,
+        query: SimpleExpr,
+        config: &str,
+    ) -> std::result::Result<SimpleExpr, QueryBuildingError> {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            TransactionImpl::Sqlite(_) => impl_sqlite::build_search_expr(document, query, config),
+            #[cfg(feature = "postgres")]
+            TransactionImpl::Postgres(_) => {
+                impl_postgres::build_search_expr(document, query, config)
+            }
+            #[cfg(feature = "mysql")]
+            TransactionImpl::MySql(_) => impl_mysql::build_search_expr(document, query, config),
+        }
+    }
+
+    fn search_rank_expr(
+        &self,
+        document: SimpleExpr,
+        query: SimpleExpr,
+        config: &str,
+    ) -> std::result::Result<SimpleExpr, QueryBuildingError> {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            TransactionImpl::Sqlite(_) => impl_sqlite::build_search_expr(document, query, config),
+            #[cfg(feature = "postgres")]
+            TransactionImpl::Postgres(_) => {
+                impl_postgres::build_search_rank_expr(document, query, config)
+            }
+            #[cfg(feature = "mysql")]
+            TransactionImpl::MySql(_) => impl_mysql::build_search_expr(document, query, config),
+        }
+    }
+}
+
+impl JsonExprBuilder for Transaction<'_> {
+    fn json_extract_expr(
+        &self,
+        expr: SimpleExpr,
+        path: &[String],
+    ) -> std::result::Result<SimpleExpr, QueryBuildingError> {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            TransactionImpl::Sqlite(_) => impl_sqlite::build_json_extract_expr(expr, path),
+            #[cfg(feature = "postgres")]
+            TransactionImpl::Postgres(_) => impl_postgres::build_json_extract_expr(expr, path),
+            #[cfg(feature = "mysql")]
+            TransactionImpl::MySql(_) => impl_mysql::build_json_extract_expr(expr, path),
+        }
+    }
+}
+
 #[async_trait]
 trait RawExecutor {
     async fn fetch_option<T>(&mut self, statement: &T) -> Result<Option<Row>>
@@ -1687,6 +2563,10 @@ trait RawExecutor {
     where
         T: SqlxBinder + Send + Sync;
 
+    fn fetch_stream<T>(&mut self, statement: &T) -> BoxStream<'_, Result<Row>>
+    where
+        T: SqlxBinder + Send + Sync;
+
     async fn execute_statement<T>(&mut self, statement: &T) -> Result<StatementResult>
     where
         T: SqlxBinder + Send + Sync;
@@ -1757,6 +2637,29 @@ impl RawExecutor for &Database {
         Ok(result)
     }
 
+    fn fetch_stream<T>(&mut self, statement: &T) -> BoxStream<'_, Result<Row>>
+    where
+        T: SqlxBinder + Send + Sync,
+    {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => inner
+                .fetch_stream(statement)
+                .map(|row| row.map(Row::Sqlite))
+                .boxed(),
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => inner
+                .fetch_stream(statement)
+                .map(|row| row.map(Row::Postgres))
+                .boxed(),
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(inner) => inner
+                .fetch_stream(statement)
+                .map(|row| row.map(Row::MySql))
+                .boxed(),
+        }
+    }
+
     async fn execute_statement<T>(&mut self, statement: &T) -> Result<StatementResult>
     where
         T: SqlxBinder + Send + Sync,
@@ -1823,6 +2726,13 @@ impl RawExecutor for Transaction<'_> {
         self.fetch_all::<T>(statement).await
     }
 
+    fn fetch_stream<T>(&mut self, statement: &T) -> BoxStream<'_, Result<Row>>
+    where
+        T: SqlxBinder + Send + Sync,
+    {
+        self.fetch_stream::<T>(statement)
+    }
+
     async fn execute_statement<T>(&mut self, statement: &T) -> Result<StatementResult>
     where
         T: SqlxBinder + Send + Sync,
@@ -1887,6 +2797,13 @@ impl<E: RawExecutor + Send> RawExecutor for &mut E {
         (**self).fetch_all::<T>(statement).await
     }
 
+    fn fetch_stream<T>(&mut self, statement: &T) -> BoxStream<'_, Result<Row>>
+    where
+        T: SqlxBinder + Send + Sync,
+    {
+        (**self).fetch_stream::<T>(statement)
+    }
+
     async fn execute_statement<T>(&mut self, statement: &T) -> Result<StatementResult>
     where
         T: SqlxBinder + Send + Sync,
@@ -1945,6 +2862,96 @@ fn check_model_allowed<T: Model>(context: DatabaseContext) -> Result<()> {
     }
 }
 
+/// Returns the values of the primary key columns of a model instance, in the
+/// order of [`Model::PRIMARY_KEY_NAMES`].
+fn primary_key_values<T: Model>(data: &T) -> Vec<DbFieldValue> {
+    let indices: Vec<_> = T::PRIMARY_KEY_NAMES
+        .iter()
+        .map(|name| {
+            T::COLUMNS
+                .iter()
+                .position(|column| column.name == *name)
+                .expect("primary key column should be one of the model columns")
+        })
+        .collect();
+    data.get_values(&indices)
+        .into_iter()
+        .map(ToDbFieldValue::to_db_field_value)
+        .collect()
+}
+
+/// Returns the values of the primary key columns of a model instance, or
+/// [`None`] if any of them is to be filled in by the database.
+fn explicit_primary_key_values<T: Model>(data: &T) -> Option<Vec<DbValue>> {
+    primary_key_values(data)
+        .into_iter()
+        .map(|value| match value {
+            DbFieldValue::Value(value) => Some(value),
+            DbFieldValue::Auto => None,
+        })
+        .collect()
+}
+
+/// Returns a condition matching the row with the given primary key values.
+fn primary_key_condition<T: Model>(primary_key: Vec<DbValue>) -> sea_query::Condition {
+    std::iter::zip(T::PRIMARY_KEY_NAMES, primary_key)
+        .fold(sea_query::Condition::all(), |condition, (name, value)| {
+            condition.add(sea_query::Expr::col(*name).eq(value))
+        })
+}
+
+/// Returns an expression matching the rows with any of the given primary keys.
+fn primary_keys_in<T: Model>(primary_keys: &[Vec<DbValue>]) -> sea_query::Expr {
+    if let [name] = T::PRIMARY_KEY_NAMES {
+        sea_query::Expr::col(*name).is_in(
+            primary_keys
+                .iter()
+                .map(|primary_key| primary_key[0].clone()),
+        )
+    } else {
+        sea_query::Expr::tuple(
+            T::PRIMARY_KEY_NAMES
+                .iter()
+                .map(|name| sea_query::Expr::col(*name)),
+        )
+        .is_in(primary_keys.iter().map(|primary_key| {
+            sea_query::Expr::tuple(primary_key.iter().cloned().map(sea_query::Expr::val))
+        }))
+    }
+}
+
+fn display_primary_key(primary_key: &[DbValue]) -> String {
+    primary_key
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Builds the statement adding a `CHECK` constraint to an existing table, with
+/// the identifiers quoted by the given query builder.
+///
+/// `sea-query` doesn't support adding constraints to existing tables, so the
+/// statement is built by hand. The expression is included as is.
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn add_check_constraint_sql<B: sea_query::backend::QuotedBuilder>(
+    builder: &B,
+    table_name: Identifier,
+    name: Identifier,
+    expr: &str,
+) -> String {
+    use sea_query::IntoIden;
+
+    let mut sql = String::from("ALTER TABLE ");
+    builder.prepare_iden(&table_name.into_iden(), &mut sql);
+    sql.push_str(" ADD CONSTRAINT ");
+    builder.prepare_iden(&name.into_iden(), &mut sql);
+    sql.push_str(" CHECK (");
+    sql.push_str(expr);
+    sql.push(')');
+    sql
+}
+
 /// A database connection structure that holds the connection to the database.
 ///
 /// It is used to execute queries and interact with the database. The connection
@@ -1953,9 +2960,18 @@ fn check_model_allowed<T: Model>(context: DatabaseContext) -> Result<()> {
 #[derive(Debug, Clone)]
 pub struct Database {
     inner: Arc<DatabaseImpl>,
+    replicas: Arc<DatabaseReplicas>,
     context: DatabaseContext,
 }
 
+/// The read replicas of a [`Database`].
+#[derive(Debug, Default)]
+struct DatabaseReplicas {
+    databases: Vec<Database>,
+    /// The index of the replica to run the next read query on.
+    next: AtomicUsize,
+}
+
 #[derive(Debug)]
 enum DatabaseImpl {
     #[cfg(feature = "sqlite")]
@@ -1966,37 +2982,129 @@ enum DatabaseImpl {
     MySql(DatabaseMySql),
 }
 
-/// A [`SqlxBinder`] wrapping a raw SQL string and its bound values, letting
-/// raw queries reuse the same [`Database::fetch_all`] path as
-/// `sea_query`-built statements.
-struct RawStatement<'a> {
-    sql: &'a str,
-    values: SqlxValues,
+/// An error returned when parsing a [`DatabaseDialect`] from a string.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseDatabaseDialectError {
+    /// The input did not match any enabled database dialect.
+    #[error("unsupported database dialect: `{0}`")]
+    Unsupported(String),
 }
 
-impl SqlxBinder for RawStatement<'_> {
-    fn build_sqlx<T>(&self, _query_builder: T) -> (String, SqlxValues)
-    where
-        T: sea_query::QueryBuilder,
-    {
-        (self.sql.to_owned(), self.values.clone())
-    }
+/// The SQL dialect of one of the database backends supported by Cot.
+///
+/// This is used to choose the SQL dialect when there is no database to
+/// connect to, for instance when rendering the SQL of a migration with
+/// [`MigrationEngine::migration_sql`](migrations::MigrationEngine::migration_sql).
+///
+/// # Examples
+///
+/// ```
+/// use cot::db::DatabaseDialect;
+///
+/// let dialect: DatabaseDialect = "sqlite".parse().unwrap();
+/// assert_eq!(dialect, DatabaseDialect::Sqlite);
+/// assert_eq!(dialect.as_str(), "sqlite");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DatabaseDialect {
+    /// The SQLite dialect.
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+    /// The PostgreSQL dialect.
+    #[cfg(feature = "postgres")]
+    Postgres,
+    /// The MySQL dialect.
+    #[cfg(feature = "mysql")]
+    MySql,
 }
 
-impl Database {
-    /// Creates a new database connection. The connection string should be in
-    /// the format of the database URL.
-    ///
-    /// # Errors
+impl DatabaseDialect {
+    /// Returns the name of the dialect, as accepted by
+    /// [`FromStr`](std::str::FromStr).
     ///
-    /// This method can return an error if the connection to the database could
-    /// not be established.
-    ///
-    /// This method can return an error if the database URL is invalid.
+    /// # Examples
     ///
-    /// # Panics
+    /// ```
+    /// use cot::db::DatabaseDialect;
     ///
-    /// This method will panic if the database URL is not supported.
+    /// assert_eq!(DatabaseDialect::Sqlite.as_str(), "sqlite");
+    /// ```
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite => "sqlite",
+            #[cfg(feature = "postgres")]
+            Self::Postgres => "postgres",
+            #[cfg(feature = "mysql")]
+            Self::MySql => "mysql",
+        }
+    }
+}
+
+impl Display for DatabaseDialect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<&str> for DatabaseDialect {
+    type Error = ParseDatabaseDialectError;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            #[cfg(feature = "sqlite")]
+            "sqlite" => Ok(Self::Sqlite),
+            #[cfg(feature = "postgres")]
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            #[cfg(feature = "mysql")]
+            "mysql" => Ok(Self::MySql),
+            other => Err(ParseDatabaseDialectError::Unsupported(other.to_owned())),
+        }
+    }
+}
+
+impl FromStr for DatabaseDialect {
+    type Err = ParseDatabaseDialectError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// A [`SqlxBinder`] wrapping a raw SQL string and its bound values, letting
+/// raw queries reuse the same [`Database::fetch_all`] path as
+/// `sea_query`-built statements.
+struct RawStatement<'a> {
+    sql: &'a str,
+    values: SqlxValues,
+}
+
+impl SqlxBinder for RawStatement<'_> {
+    fn build_sqlx<T>(&self, _query_builder: T) -> (String, SqlxValues)
+    where
+        T: sea_query::QueryBuilder,
+    {
+        (self.sql.to_owned(), self.values.clone())
+    }
+}
+
+impl Database {
+    /// Creates a new database connection. The connection string should be in
+    /// the format of the database URL.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the connection to the database could
+    /// not be established.
+    ///
+    /// This method can return an error if the database URL is invalid.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the database URL is not supported.
     ///
     /// # Examples
     ///
@@ -2009,31 +3117,74 @@ impl Database {
     /// }
     /// ```
     pub async fn new<T: Into<String>>(url: T) -> Result<Self> {
+        Self::new_with_pool(url, &DatabasePoolConfig::default()).await
+    }
+
+    /// Creates a new database connection, with the connection pool configured
+    /// with the given settings. The connection string should be in the format
+    /// of the database URL.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the connection to the database could
+    /// not be established, for instance because any of the
+    /// [`init_sql`](DatabasePoolConfig::init_sql) statements failed.
+    ///
+    /// This method can return an error if the database URL is invalid.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the database URL is not supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::config::DatabasePoolConfig;
+    /// use cot::db::Database;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let pool_config = DatabasePoolConfig::builder()
+    ///         .max_connections(1)
+    ///         .init_sql(vec!["PRAGMA journal_mode = WAL".to_owned()])
+    ///         .build();
+    ///     let db = Database::new_with_pool("sqlite::memory:", &pool_config)
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub async fn new_with_pool<T: Into<String>>(
+        url: T,
+        pool_config: &DatabasePoolConfig,
+    ) -> Result<Self> {
         let url = url.into();
 
         #[cfg(feature = "sqlite")]
         if url.starts_with("sqlite:") {
-            let inner = DatabaseSqlite::new(&url).await?;
+            let inner = DatabaseSqlite::new(&url, pool_config).await?;
             return Ok(Self {
                 inner: Arc::new(DatabaseImpl::Sqlite(inner)),
+                replicas: Arc::default(),
                 context: DatabaseContext::Default,
             });
         }
 
         #[cfg(feature = "postgres")]
         if url.starts_with("postgresql:") {
-            let inner = DatabasePostgres::new(&url).await?;
+            let inner = DatabasePostgres::new(&url, pool_config).await?;
             return Ok(Self {
                 inner: Arc::new(DatabaseImpl::Postgres(inner)),
+                replicas: Arc::default(),
                 context: DatabaseContext::Default,
             });
         }
 
         #[cfg(feature = "mysql")]
         if url.starts_with("mysql:") {
-            let inner = DatabaseMySql::new(&url).await?;
+            let inner = DatabaseMySql::new(&url, pool_config).await?;
             return Ok(Self {
                 inner: Arc::new(DatabaseImpl::MySql(inner)),
+                replicas: Arc::default(),
                 context: DatabaseContext::Default,
             });
         }
@@ -2044,10 +3195,224 @@ impl Database {
     fn for_migration(&self) -> Self {
         Self {
             inner: Arc::clone(&self.inner),
+            replicas: Arc::default(),
             context: DatabaseContext::InMigration,
         }
     }
 
+    /// Returns the database with the given read replicas.
+    ///
+    /// The queries reading application models, such as [`Query::all`],
+    /// [`Query::get`], or [`Query::count`], are distributed between the
+    /// replicas in a round-robin fashion. The writes, the transactions, the
+    /// raw queries, and the queries locking the rows with
+    /// [`Query::for_update`] are always run on the primary database (`self`),
+    /// as are the migrations.
+    ///
+    /// Since replicas are usually updated asynchronously, a write might not be
+    /// visible on them right away. Use [`Self::primary`] to read the data that
+    /// has to be up to date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Database;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// let replica = Database::new("sqlite::memory:").await?;
+    /// let db = Database::new("sqlite::memory:")
+    ///     .await?
+    ///     .with_replicas([replica]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_replicas<I: IntoIterator<Item = Database>>(self, replicas: I) -> Self {
+        Self {
+            inner: self.inner,
+            replicas: Arc::new(DatabaseReplicas {
+                databases: replicas
+                    .into_iter()
+                    .map(|replica| replica.primary())
+                    .collect(),
+                next: AtomicUsize::new(0),
+            }),
+            context: self.context,
+        }
+    }
+
+    /// Returns the database that runs all the queries on the primary
+    /// database, ignoring the read replicas set with
+    /// [`Self::with_replicas`].
+    ///
+    /// This is useful to read the data that has just been written, which
+    /// might not have reached the replicas yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Database;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// let replica = Database::new("sqlite::memory:").await?;
+    /// let db = Database::new("sqlite::memory:")
+    ///     .await?
+    ///     .with_replicas([replica]);
+    ///
+    /// let primary = db.primary();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn primary(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            replicas: Arc::default(),
+            context: self.context,
+        }
+    }
+
+    /// Returns the database to run a query reading the given model with: one
+    /// of the read replicas, or the primary database if there are none.
+    ///
+    /// Only the application models are read from the replicas; the internal
+    /// models, such as the records of the applied migrations, always have to
+    /// be up to date.
+    fn read_database<T: Model>(&self) -> &Self {
+        let replicas = &self.replicas.databases;
+        if replicas.is_empty() || T::MODEL_TYPE != ModelType::Application {
+            return self;
+        }
+
+        let index = self.replicas.next.fetch_add(1, Ordering::Relaxed) % replicas.len();
+        &replicas[index]
+    }
+
+    /// Returns the database to run the given query with; see
+    /// [`Self::read_database`]. The queries locking the rows are always run on
+    /// the primary database.
+    fn database_for_query<T: Model>(&self, query: &Query<T>) -> &Self {
+        if query.has_lock() {
+            self
+        } else {
+            self.read_database::<T>()
+        }
+    }
+
+    /// Returns a database that runs all the statements on a single connection
+    /// acquired from the pool, such as the one a migration is applied with.
+    ///
+    /// The connection is returned to the pool when the returned database is
+    /// dropped.
+    async fn pin_connection(&self) -> Result<Self> {
+        let inner = match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => DatabaseImpl::Sqlite(inner.pin_connection().await?),
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => DatabaseImpl::Postgres(inner.pin_connection().await?),
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(inner) => DatabaseImpl::MySql(inner.pin_connection().await?),
+        };
+
+        Ok(Self {
+            inner: Arc::new(inner),
+            replicas: Arc::default(),
+            context: self.context,
+        })
+    }
+
+    /// Starts the transaction a migration is applied in, if the backend
+    /// supports transactional schema changes, on a database returned by
+    /// [`Self::pin_connection`].
+    ///
+    /// Returns whether the transaction has been started. If it has, it has to
+    /// be ended with either [`Self::commit_migration_transaction`] or
+    /// [`Self::rollback_migration_transaction`].
+    async fn begin_migration_transaction(&self) -> Result<bool> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => inner.begin_migration_transaction().await?,
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => inner.begin_pinned_transaction().await?,
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(_) => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Commits the transaction started with
+    /// [`Self::begin_migration_transaction`].
+    async fn commit_migration_transaction(&self) -> Result<()> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => inner.commit_migration_transaction().await,
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => inner.commit_pinned_transaction().await,
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(_) => {
+                unreachable!("migration transactions are not started on MySQL")
+            }
+        }
+    }
+
+    /// Rolls back the transaction started with
+    /// [`Self::begin_migration_transaction`].
+    async fn rollback_migration_transaction(&self) -> Result<()> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => inner.rollback_migration_transaction().await,
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => inner.rollback_pinned_transaction().await,
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(_) => {
+                unreachable!("migration transactions are not started on MySQL")
+            }
+        }
+    }
+
+    /// Creates a database that can be used to render the SQL of migrations in
+    /// the given dialect without connecting to an actual database, along with
+    /// the recorder of the schema statements it runs.
+    ///
+    /// The SQLite statements are run on a fresh in-memory database, because
+    /// altering a table there requires reading its current schema. For the
+    /// other dialects, the statements are only recorded, so the returned
+    /// database never connects.
+    pub(crate) fn offline(dialect: DatabaseDialect) -> Result<(Self, SqlRecorder)> {
+        let (inner, sql_recorder) = match dialect {
+            #[cfg(feature = "sqlite")]
+            DatabaseDialect::Sqlite => {
+                let sql_recorder = SqlRecorder::new(true);
+                let inner = DatabaseSqlite::new_lazy("sqlite::memory:")?
+                    .with_sql_recorder(sql_recorder.clone());
+                (DatabaseImpl::Sqlite(inner), sql_recorder)
+            }
+            #[cfg(feature = "postgres")]
+            DatabaseDialect::Postgres => {
+                let sql_recorder = SqlRecorder::new(false);
+                let inner = DatabasePostgres::new_lazy("postgresql://localhost")?
+                    .with_sql_recorder(sql_recorder.clone());
+                (DatabaseImpl::Postgres(inner), sql_recorder)
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseDialect::MySql => {
+                let sql_recorder = SqlRecorder::new(false);
+                let inner = DatabaseMySql::new_lazy("mysql://localhost")?
+                    .with_sql_recorder(sql_recorder.clone());
+                (DatabaseImpl::MySql(inner), sql_recorder)
+            }
+        };
+
+        let database = Self {
+            inner: Arc::new(inner),
+            replicas: Arc::default(),
+            context: DatabaseContext::Default,
+        };
+        Ok((database, sql_recorder))
+    }
+
     fn ensure_model_allowed<T: Model>(&self) -> Result<()> {
         check_model_allowed::<T>(self.context)
     }
@@ -2075,6 +3440,16 @@ impl Database {
     /// }
     /// ```
     pub async fn close(&self) -> Result<()> {
+        for replica in &self.replicas.databases {
+            replica.close_primary().await?;
+        }
+
+        self.close_primary().await
+    }
+
+    /// Closes the connection to the primary database, leaving the replicas
+    /// intact.
+    async fn close_primary(&self) -> Result<()> {
         match &*self.inner {
             #[cfg(feature = "sqlite")]
             DatabaseImpl::Sqlite(inner) => inner.close().await,
@@ -2087,6 +3462,11 @@ impl Database {
 
     /// Starts a new database transaction.
     ///
+    /// The transaction has to be explicitly committed with
+    /// [`Transaction::commit`]; if it's dropped without being committed, it is
+    /// rolled back. See also [`Database::transaction`] for a helper that
+    /// commits or rolls back the transaction automatically.
+    ///
     /// # Errors
     ///
     /// Returns an error if the transaction could not be started.
@@ -2112,6 +3492,123 @@ impl Database {
         })
     }
 
+    /// Runs the given closure within a new transaction.
+    ///
+    /// The transaction is committed if the closure returns `Ok`, and rolled
+    /// back if it returns `Err`, in which case the error is returned as-is.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error returned by the closure.
+    ///
+    /// Returns an error if the transaction could not be started or committed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::{Database, DatabaseError};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// let db = Database::new("sqlite::memory:").await?;
+    /// db.raw("CREATE TABLE account (id INTEGER PRIMARY KEY, balance INTEGER)")
+    ///     .await?;
+    /// db.raw("INSERT INTO account (id, balance) VALUES (1, 100), (2, 0)")
+    ///     .await?;
+    ///
+    /// db.transaction(async |transaction| {
+    ///     transaction
+    ///         .raw("UPDATE account SET balance = balance - 30 WHERE id = 1")
+    ///         .await?;
+    ///     transaction
+    ///         .raw("UPDATE account SET balance = balance + 30 WHERE id = 2")
+    ///         .await?;
+    ///     Ok::<_, DatabaseError>(())
+    /// })
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn transaction<T, E, F>(&self, mut f: F) -> std::result::Result<T, E>
+    where
+        F: AsyncFnMut(&mut Transaction<'_>) -> std::result::Result<T, E>,
+        E: From<DatabaseError>,
+    {
+        let mut transaction = self.begin().await?;
+        match f(&mut transaction).await {
+            Ok(value) => {
+                transaction.commit().await?;
+                Ok(value)
+            }
+            Err(error) => {
+                // the original error is more relevant to the caller than the
+                // rollback failure, and the transaction is rolled back by the
+                // database anyway once the connection is released
+                if let Err(rollback_error) = transaction.rollback().await {
+                    tracing::warn!("failed to roll back the transaction: {rollback_error}");
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Runs the given closure within a new transaction, retrying it if it
+    /// fails because of a conflict with a concurrent transaction.
+    ///
+    /// This works like [`Database::transaction`], but if the closure or the
+    /// commit fails with an error for which
+    /// [`DatabaseError::is_transaction_conflict`] returns `true` (such as a
+    /// serialization failure or a deadlock), the transaction is rolled back
+    /// and the closure is called again in a new transaction, up to
+    /// `max_retries` times. Because of that, the closure shouldn't have side
+    /// effects outside the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error returned by the closure, or the last conflict error
+    /// if the transaction still fails after `max_retries` retries.
+    ///
+    /// Returns an error if the transaction could not be started or committed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Database;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// let db = Database::new("sqlite::memory:").await?;
+    /// db.raw("CREATE TABLE counter (value INTEGER)").await?;
+    /// db.raw("INSERT INTO counter (value) VALUES (0)").await?;
+    ///
+    /// db.transaction_with_retry(3, async |transaction| {
+    ///     transaction
+    ///         .raw("UPDATE counter SET value = value + 1")
+    ///         .await?;
+    ///     Ok(())
+    /// })
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn transaction_with_retry<T, F>(&self, max_retries: u32, mut f: F) -> Result<T>
+    where
+        F: AsyncFnMut(&mut Transaction<'_>) -> Result<T>,
+    {
+        let mut retries = 0;
+        loop {
+            match self.transaction(&mut f).await {
+                Err(error) if error.is_transaction_conflict() && retries < max_retries => {
+                    retries += 1;
+                    tracing::debug!(
+                        "retrying the transaction after a conflict ({retries}/{max_retries}): {error}"
+                    );
+                }
+                result => return result,
+            }
+        }
+    }
+
     /// Inserts a new row into the database.
     ///
     /// # Errors
@@ -2197,7 +3694,7 @@ impl Database {
             .to_owned();
         if update && !value_identifiers.is_empty() {
             insert_statement.on_conflict(
-                OnConflict::column(T::PRIMARY_KEY_NAME)
+                OnConflict::columns(T::PRIMARY_KEY_NAMES.iter().copied())
                     .update_columns(value_identifiers)
                     .to_owned(),
             );
@@ -2215,13 +3712,25 @@ impl Database {
                     .expect("query should return the primary key")
             } else {
                 let result = executor.execute_statement(&insert_statement).await?;
-                let row_id = result
-                    .last_inserted_row_id
-                    .expect("expected last inserted row ID if RETURNING clause is not supported");
+                // the primary key can be set explicitly when only the columns with
+                // database defaults are filled in by the database
+                let primary_key: Vec<_> = primary_key_values(data)
+                    .into_iter()
+                    .map(|value| match value {
+                        DbFieldValue::Value(value) => value,
+                        DbFieldValue::Auto => result
+                            .last_inserted_row_id
+                            .expect(
+                                "expected last inserted row ID if RETURNING clause is not \
+                                supported",
+                            )
+                            .into(),
+                    })
+                    .collect();
                 let query = sea_query::Query::select()
                     .from(T::TABLE_NAME)
                     .columns(auto_col_identifiers)
-                    .and_where(sea_query::Expr::col(T::PRIMARY_KEY_NAME).eq(row_id))
+                    .cond_where(primary_key_condition::<T>(primary_key))
                     .to_owned();
                 executor.fetch_option(&query).await?.expect(
                     "expected a row returned from a SELECT if RETURNING clause is not supported",
@@ -2229,11 +3738,12 @@ impl Database {
             };
             data.update_from_db(row, &auto_col_ids)?;
         }
+        data.bind_many_to_many();
 
         if update {
-            trace!(primary_key = ?data.primary_key().to_db_field_value(), "Inserted or updated row");
+            trace!(primary_key = ?primary_key_values(data), "Inserted or updated row");
         } else {
-            trace!(primary_key = ?data.primary_key().to_db_field_value(), "Inserted row");
+            trace!(primary_key = ?primary_key_values(data), "Inserted row");
         }
 
         Ok(())
@@ -2254,7 +3764,7 @@ impl Database {
             Level::TRACE,
             "update",
             table = %T::TABLE_NAME,
-            primary_key = ?data.primary_key().to_db_field_value(),
+            primary_key = ?primary_key_values(data),
         );
 
         Self::update_generic(self, data).instrument(span).await
@@ -2285,20 +3795,24 @@ impl Database {
             }
         });
 
-        let primary_key = data
-            .primary_key()
-            .to_db_field_value()
-            .expect_value("primary key cannot be auto when updating");
+        let primary_key: Vec<_> = primary_key_values(data)
+            .into_iter()
+            .map(|value| value.expect_value("primary key cannot be auto when updating"))
+            .collect();
         let update_statement = sea_query::Query::update()
             .table(T::TABLE_NAME)
             .values(statement_values)
-            .and_where(sea_query::Expr::col(T::PRIMARY_KEY_NAME).eq(primary_key.clone()))
+            .cond_where(primary_key_condition::<T>(primary_key.clone()))
             .to_owned();
 
         let result = executor.execute_statement(&update_statement).await?;
         if result.rows_affected == RowsNum(0) {
-            return Err(DatabaseError::RecordNotFound { primary_key });
+            return Err(match <[DbValue; 1]>::try_from(primary_key) {
+                Ok([primary_key]) => DatabaseError::RecordNotFound { primary_key },
+                Err(primary_key) => DatabaseError::CompositeRecordNotFound { primary_key },
+            });
         }
+        data.bind_many_to_many();
 
         trace!("Updated row");
 
@@ -2428,6 +3942,7 @@ impl Database {
             }
             transaction.commit().await?;
         }
+        data.iter_mut().for_each(Model::bind_many_to_many);
 
         Ok(())
     }
@@ -2472,11 +3987,11 @@ impl Database {
         if update {
             let update_cols: Vec<_> = value_identifiers
                 .iter()
-                .filter(|id| **id != T::PRIMARY_KEY_NAME)
+                .filter(|id| !T::PRIMARY_KEY_NAMES.contains(id))
                 .copied()
                 .collect();
             insert_statement.on_conflict(
-                OnConflict::column(T::PRIMARY_KEY_NAME)
+                OnConflict::columns(T::PRIMARY_KEY_NAMES.iter().copied())
                     .update_columns(update_cols)
                     .to_owned(),
             );
@@ -2499,7 +4014,24 @@ impl Database {
             for (instance, row) in chunk.iter_mut().zip(rows) {
                 instance.update_from_db(row, auto_col_ids)?;
             }
-        } else {
+        } else if let Some(primary_keys) = chunk
+            .iter()
+            .map(explicit_primary_key_values)
+            .collect::<Option<Vec<_>>>()
+        {
+            // MySQL: the primary keys are set explicitly when only the columns with
+            // database defaults are filled in by the database, so the rows are fetched
+            // by them
+            executor.execute_statement(&insert_statement).await?;
+            Self::update_inserted_by_primary_keys(
+                executor,
+                chunk,
+                &primary_keys,
+                auto_col_ids,
+                auto_col_identifiers,
+            )
+            .await?;
+        } else if let [primary_key_name] = T::PRIMARY_KEY_NAMES {
             // MySQL: Use LAST_INSERT_ID() and fetch rows
             let result = executor.execute_statement(&insert_statement).await?;
             let first_id = result.last_inserted_row_id.ok_or_else(|| {
@@ -2508,40 +4040,119 @@ impl Database {
                     actual: 0,
                 }
             })?;
+            Self::update_inserted_by_first_id(
+                executor,
+                chunk,
+                *primary_key_name,
+                first_id,
+                auto_col_ids,
+                auto_col_identifiers,
+            )
+            .await?;
+        } else {
+            // LAST_INSERT_ID() only identifies the rows by a single
+            // auto-incremented primary key column
+            return Err(DatabaseError::UnsupportedByBackend {
+                feature: "bulk inserts of models with auto-incremented composite primary keys",
+                backend: "MySQL",
+            });
+        }
 
-            // Fetch the inserted rows using a SELECT query
-            // Note: This assumes IDs are consecutive, which is generally safe for
-            // auto_increment but could fail with concurrent inserts
-            let query = sea_query::Query::select()
-                .from(T::TABLE_NAME)
-                .columns(auto_col_identifiers.iter().cloned())
-                .and_where(
-                    sea_query::Expr::col(T::PRIMARY_KEY_NAME).gte(first_id).and(
-                        sea_query::Expr::col(T::PRIMARY_KEY_NAME).lt(first_id
-                            + <u64 as TryFrom<usize>>::try_from(chunk.len())
-                                .expect("chunk length fits in u64")),
-                    ),
-                )
-                .order_by(T::PRIMARY_KEY_NAME, sea_query::Order::Asc)
-                .to_owned();
+        if update {
+            trace!(count = chunk.len(), "Inserted or updated rows");
+        } else {
+            trace!(count = chunk.len(), "Inserted rows");
+        }
 
-            let rows = executor.fetch_all(&query).await?;
-            if rows.len() != chunk.len() {
-                return Err(DatabaseError::BulkInsertReturnDataInvalid {
-                    expected: chunk.len(),
-                    actual: rows.len(),
-                });
-            }
+        Ok(())
+    }
 
-            for (instance, row) in chunk.iter_mut().zip(rows) {
-                instance.update_from_db(row, auto_col_ids)?;
-            }
+    /// Fills in the columns set by the database in the inserted instances by
+    /// fetching the rows with consecutive auto-incremented primary keys,
+    /// starting with given one.
+    async fn update_inserted_by_first_id<T: Model, E: RawExecutor>(
+        mut executor: E,
+        chunk: &mut [T],
+        primary_key_name: Identifier,
+        first_id: u64,
+        auto_col_ids: &[usize],
+        auto_col_identifiers: &[ColumnRef],
+    ) -> Result<()> {
+        // Fetch the inserted rows using a SELECT query
+        // Note: This assumes IDs are consecutive, which is generally safe for
+        // auto_increment but could fail with concurrent inserts
+        let query = sea_query::Query::select()
+            .from(T::TABLE_NAME)
+            .columns(auto_col_identifiers.iter().cloned())
+            .and_where(
+                sea_query::Expr::col(primary_key_name).gte(first_id).and(
+                    sea_query::Expr::col(primary_key_name).lt(first_id
+                        + <u64 as TryFrom<usize>>::try_from(chunk.len())
+                            .expect("chunk length fits in u64")),
+                ),
+            )
+            .order_by(primary_key_name, sea_query::Order::Asc)
+            .to_owned();
+
+        let rows = executor.fetch_all(&query).await?;
+        if rows.len() != chunk.len() {
+            return Err(DatabaseError::BulkInsertReturnDataInvalid {
+                expected: chunk.len(),
+                actual: rows.len(),
+            });
         }
 
-        if update {
-            trace!(count = chunk.len(), "Inserted or updated rows");
-        } else {
-            trace!(count = chunk.len(), "Inserted rows");
+        for (instance, row) in chunk.iter_mut().zip(rows) {
+            instance.update_from_db(row, auto_col_ids)?;
+        }
+
+        Ok(())
+    }
+
+    /// Fills in the columns set by the database in the inserted instances by
+    /// fetching the rows with given primary keys.
+    ///
+    /// All the columns are selected after the filled in ones to match the rows
+    /// with the instances.
+    async fn update_inserted_by_primary_keys<T: Model, E: RawExecutor>(
+        mut executor: E,
+        chunk: &mut [T],
+        primary_keys: &[Vec<DbValue>],
+        auto_col_ids: &[usize],
+        auto_col_identifiers: &[ColumnRef],
+    ) -> Result<()> {
+        let columns_to_get: Vec<_> = T::COLUMNS.iter().map(|column| column.name).collect();
+        let query = sea_query::Query::select()
+            .from(T::TABLE_NAME)
+            .columns(auto_col_identifiers.iter().cloned())
+            .columns(columns_to_get)
+            .and_where(primary_keys_in::<T>(primary_keys))
+            .to_owned();
+
+        let rows = executor.fetch_all(&query).await?;
+
+        // `DbValue` doesn't implement `Hash`, so the rows are matched using the
+        // debug representations of the primary keys
+        let instance_indices: HashMap<_, _> = primary_keys
+            .iter()
+            .enumerate()
+            .map(|(index, primary_key)| (format!("{primary_key:?}"), index))
+            .collect();
+        let mut matched_rows = 0;
+        for row in rows {
+            let inserted = T::from_db_at(&row, auto_col_ids.len())?;
+            let index = explicit_primary_key_values(&inserted)
+                .and_then(|primary_key| instance_indices.get(&format!("{primary_key:?}")));
+            if let Some(&index) = index {
+                chunk[index].update_from_db(row, auto_col_ids)?;
+                matched_rows += 1;
+            }
+        }
+        if matched_rows != chunk.len() {
+            return Err(DatabaseError::BulkInsertReturnDataInvalid {
+                expected: chunk.len(),
+                actual: matched_rows,
+            });
         }
 
         Ok(())
@@ -2560,25 +4171,82 @@ impl Database {
     ///
     /// Can return an error if the database connection is lost.
     pub async fn query<T: Model>(&self, query: &Query<T>) -> Result<Vec<T>> {
-        Self::query_generic(self, query).await
+        Self::query_generic(self.database_for_query(query), query).await
     }
 
     async fn query_generic<T: Model, E: RawExecutor>(
         mut executor: E,
         query: &Query<T>,
     ) -> Result<Vec<T>> {
+        let select = Self::query_statement(&executor, query)?;
+
+        let rows = executor.fetch_all(&select).await?;
+        let result = rows
+            .into_iter()
+            .map(|row| query.model_from_row(row))
+            .collect::<Result<_>>()?;
+
+        Ok(result)
+    }
+
+    /// Executes the given query and returns a stream of the results converted
+    /// to the model type.
+    ///
+    /// Unlike [`Database::query`], the rows are fetched from the database
+    /// incrementally, as the stream is polled, so the results don't need to
+    /// fit in memory all at once.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields an error if the query is invalid.
+    ///
+    /// The stream yields an error if the data in the database is not
+    /// compatible with the model (usually meaning the migrations haven't been
+    /// generated or applied).
+    ///
+    /// The stream yields an error if the database connection is lost.
+    #[must_use]
+    pub fn stream<'a, T: Model + Send + 'a>(
+        &'a self,
+        query: &'a Query<T>,
+    ) -> BoxStream<'a, Result<T>> {
+        Self::stream_generic(self.database_for_query(query), query)
+    }
+
+    fn stream_generic<'a, T, Q, E>(mut executor: E, query: Q) -> BoxStream<'a, Result<T>>
+    where
+        T: Model + Send + 'a,
+        Q: Borrow<Query<T>> + Send + 'a,
+        E: RawExecutor + Send + 'a,
+    {
+        Box::pin(async_stream::try_stream! {
+            let query = query.borrow();
+            let select = Self::query_statement(&executor, query)?;
+
+            let mut rows = executor.fetch_stream(&select);
+            while let Some(row) = rows.next().await {
+                yield query.model_from_row(row?)?;
+            }
+        })
+    }
+
+    fn query_statement<T: Model, E: RawExecutor>(
+        executor: &E,
+        query: &Query<T>,
+    ) -> Result<sea_query::SelectStatement> {
         executor.ensure_model_allowed::<T>()?;
         let columns_to_get: Vec<_> = T::COLUMNS.iter().map(|column| column.name).collect();
         let mut select = sea_query::Query::select();
         select.columns(columns_to_get).from(T::TABLE_NAME);
+        query.add_related_columns_to_statement(&mut select);
+        query.add_joins_to_statement(&mut select);
         query.add_filter_to_statement(&mut select, executor.as_sql_query_builder())?;
+        query.add_order_by_to_statement(&mut select, executor.as_sql_query_builder())?;
         query.add_limit_to_statement(&mut select);
         query.add_offset_to_statement(&mut select);
+        query.add_lock_to_statement(&mut select);
 
-        let rows = executor.fetch_all(&select).await?;
-        let result = rows.into_iter().map(T::from_db).collect::<Result<_>>()?;
-
-        Ok(result)
+        Ok(select)
     }
 
     /// Returns the first row that matches the given query. If no rows match the
@@ -2594,7 +4262,7 @@ impl Database {
     ///
     /// Can return an error if the database connection is lost.
     pub async fn get<T: Model>(&self, query: &Query<T>) -> Result<Option<T>> {
-        Self::get_generic(self, query).await
+        Self::get_generic(self.database_for_query(query), query).await
     }
 
     async fn get_generic<T: Model, E: RawExecutor>(
@@ -2605,13 +4273,17 @@ impl Database {
         let columns_to_get: Vec<_> = T::COLUMNS.iter().map(|column| column.name).collect();
         let mut select = sea_query::Query::select();
         select.columns(columns_to_get).from(T::TABLE_NAME);
+        query.add_related_columns_to_statement(&mut select);
+        query.add_joins_to_statement(&mut select);
         query.add_filter_to_statement(&mut select, executor.as_sql_query_builder())?;
+        query.add_order_by_to_statement(&mut select, executor.as_sql_query_builder())?;
         select.limit(1);
+        query.add_lock_to_statement(&mut select);
 
         let row = executor.fetch_option(&select).await?;
 
         let result = match row {
-            Some(row) => Some(T::from_db(row)?),
+            Some(row) => Some(query.model_from_row(row)?),
             None => None,
         };
         Ok(result)
@@ -2629,7 +4301,7 @@ impl Database {
     ///
     /// Can return an error if the database connection is lost.
     pub async fn exists<T: Model>(&self, query: &Query<T>) -> Result<bool> {
-        Self::exists_generic(self, query).await
+        Self::exists_generic(self.database_for_query(query), query).await
     }
 
     async fn exists_generic<T: Model, E: RawExecutor>(
@@ -2639,6 +4311,7 @@ impl Database {
         executor.ensure_model_allowed::<T>()?;
         let mut select = sea_query::Query::select();
         select.expr(sea_query::Expr::value(1)).from(T::TABLE_NAME);
+        query.add_joins_to_statement(&mut select);
         query.add_filter_to_statement(&mut select, executor.as_sql_query_builder())?;
         select.limit(1);
 
@@ -2659,7 +4332,7 @@ impl Database {
     ///
     /// Can return an error if the database connection is lost.
     pub async fn count<T: Model>(&self, query: &Query<T>) -> Result<u64> {
-        Self::count_generic(self, query).await
+        Self::count_generic(self.database_for_query(query), query).await
     }
 
     async fn count_generic<T: Model, E: RawExecutor>(
@@ -2670,6 +4343,7 @@ impl Database {
         select
             .from(T::TABLE_NAME)
             .expr(sea_query::Expr::col(sea_query::Asterisk).count());
+        query.add_joins_to_statement(&mut select);
         query.add_filter_to_statement(&mut select, executor.as_sql_query_builder())?;
 
         let row = executor.fetch_option(&select).await?;
@@ -2681,6 +4355,110 @@ impl Database {
         Ok(count)
     }
 
+    /// Returns the values of the given columns (or, more generally,
+    /// expressions) for each row that matches the given query, instead of
+    /// whole model instances.
+    ///
+    /// This makes it possible to skip fetching large columns that are not
+    /// needed, and to read the values of columns of related models (see
+    /// [`Expr::related_field`]).
+    ///
+    /// The expressions can also be aggregates; the rows are then grouped by
+    /// the expressions added with [`Query::group_by`], or form a single group
+    /// if there are none.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the query is invalid.
+    ///
+    /// This method can return an error if the model doesn't exist in the
+    /// database (usually meaning the migrations haven't been generated or
+    /// applied).
+    ///
+    /// This method can return an error if the values returned by the database
+    /// are not compatible with the requested Rust types.
+    ///
+    /// Can return an error if the database connection is lost.
+    pub async fn values<T: Model, R: FromDbRow>(
+        &self,
+        query: &Query<T>,
+        columns: &[Expr],
+    ) -> Result<Vec<R>> {
+        Self::values_generic(self.database_for_query(query), query, columns).await
+    }
+
+    async fn values_generic<T: Model, R: FromDbRow, E: RawExecutor>(
+        mut executor: E,
+        query: &Query<T>,
+        columns: &[Expr],
+    ) -> Result<Vec<R>> {
+        executor.ensure_model_allowed::<T>()?;
+        let mut select = sea_query::Query::select();
+        select.from(T::TABLE_NAME);
+        for expr in columns {
+            select.expr(expr.as_sea_query_expr(executor.as_sql_query_builder())?);
+        }
+        query.add_joins_for_columns_to_statement(&mut select, columns);
+        query.add_filter_to_statement(&mut select, executor.as_sql_query_builder())?;
+        query.add_group_by_to_statement(&mut select, executor.as_sql_query_builder())?;
+        query.add_having_to_statement(&mut select, executor.as_sql_query_builder())?;
+        query.add_order_by_to_statement(&mut select, executor.as_sql_query_builder())?;
+        query.add_limit_to_statement(&mut select);
+        query.add_offset_to_statement(&mut select);
+
+        let rows = executor.fetch_all(&select).await?;
+        let result = rows
+            .into_iter()
+            .map(R::from_db_row)
+            .collect::<Result<_>>()?;
+
+        Ok(result)
+    }
+
+    /// Updates all rows that match the given query with a single `UPDATE`
+    /// statement, applying the given assignments.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the query is invalid, if no
+    /// assignments are given, or if any of the assignments references a field
+    /// of a related model.
+    ///
+    /// This method can return an error if the model doesn't exist in the
+    /// database (usually meaning the migrations haven't been generated or
+    /// applied).
+    ///
+    /// Can return an error if the database connection is lost.
+    pub async fn update_many<T: Model>(
+        &self,
+        query: &Query<T>,
+        assignments: &[Assignment],
+    ) -> Result<StatementResult> {
+        Self::update_many_generic(self, query, assignments).await
+    }
+
+    async fn update_many_generic<T: Model, E: RawExecutor>(
+        mut executor: E,
+        query: &Query<T>,
+        assignments: &[Assignment],
+    ) -> Result<StatementResult> {
+        executor.ensure_model_allowed::<T>()?;
+        if assignments.is_empty() {
+            return Err(DatabaseError::UpdateNoAssignments);
+        }
+
+        let mut update = sea_query::Query::update();
+        update.table(T::TABLE_NAME);
+        for assignment in assignments {
+            let (column, value) = assignment.as_sea_query_value(executor.as_sql_query_builder())?;
+            update.value(column, value);
+        }
+        query
+            .add_filter_by_primary_key_to_statement(&mut update, executor.as_sql_query_builder())?;
+
+        executor.execute_statement(&update).await
+    }
+
     /// Deletes all rows that match the given query.
     ///
     /// # Errors
@@ -2703,7 +4481,82 @@ impl Database {
         executor.ensure_model_allowed::<T>()?;
         let mut delete = sea_query::Query::delete();
         delete.from_table(T::TABLE_NAME);
-        query.add_filter_to_statement(&mut delete, executor.as_sql_query_builder())?;
+        query
+            .add_filter_by_primary_key_to_statement(&mut delete, executor.as_sql_query_builder())?;
+
+        executor.execute_statement(&delete).await
+    }
+
+    /// The join tables link the models through a single target column, so the
+    /// linked models can't have a composite primary key.
+    async fn query_many_to_many_generic<T: Model, E: RawExecutor>(
+        mut executor: E,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+    ) -> Result<Vec<T>>
+    where
+        T::PrimaryKey: SinglePrimaryKey,
+    {
+        executor.ensure_model_allowed::<T>()?;
+        let mut linked = sea_query::Query::select();
+        linked
+            .column(table.target_column())
+            .from(table.name())
+            .and_where(sea_query::Expr::col(table.source_column()).eq(source_pk.clone()));
+
+        let columns_to_get: Vec<_> = T::COLUMNS.iter().map(|column| column.name).collect();
+        let mut select = sea_query::Query::select();
+        select
+            .columns(columns_to_get)
+            .from(T::TABLE_NAME)
+            .and_where(sea_query::Expr::col(T::PRIMARY_KEY_NAME).in_subquery(linked))
+            .order_by(T::PRIMARY_KEY_NAME, sea_query::Order::Asc);
+
+        let rows = executor.fetch_all(&select).await?;
+        rows.into_iter().map(T::from_db).collect()
+    }
+
+    async fn add_many_to_many_generic<E: RawExecutor>(
+        mut executor: E,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+        target_pk: &DbValue,
+    ) -> Result<()> {
+        // the join table has a unique constraint on the pair of the columns, so
+        // the link is not duplicated even if it's added concurrently; on MySQL,
+        // `do_nothing_on` is rendered as a no-op `ON DUPLICATE KEY UPDATE`
+        let insert_statement = sea_query::Query::insert()
+            .into_table(table.name())
+            .columns([table.source_column(), table.target_column()])
+            .values([
+                SimpleExpr::Value(source_pk.clone()),
+                SimpleExpr::Value(target_pk.clone()),
+            ])
+            .map_err(QueryBuildingError::SeaQuery)?
+            .on_conflict(
+                OnConflict::new()
+                    .do_nothing_on([table.source_column()])
+                    .to_owned(),
+            )
+            .to_owned();
+        executor.execute_statement(&insert_statement).await?;
+
+        Ok(())
+    }
+
+    async fn remove_many_to_many_generic<E: RawExecutor>(
+        mut executor: E,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+        target_pk: Option<&DbValue>,
+    ) -> Result<StatementResult> {
+        let mut delete = sea_query::Query::delete();
+        delete
+            .from_table(table.name())
+            .and_where(sea_query::Expr::col(table.source_column()).eq(source_pk.clone()));
+        if let Some(target_pk) = target_pk {
+            delete.and_where(sea_query::Expr::col(table.target_column()).eq(target_pk.clone()));
+        }
 
         executor.execute_statement(&delete).await
     }
@@ -2898,6 +4751,112 @@ impl Database {
         rows.into_iter().map(T::from_db).collect::<Result<_>>()
     }
 
+    /// Executes a raw SQL query and converts the results to a type
+    /// implementing [`FromDbRow`].
+    ///
+    /// Unlike [`Database::raw_as`], the rows don't need to correspond to a
+    /// model, so this can be used to read the results of queries such as joins
+    /// or aggregations into tuples or structs deriving
+    /// [`FromDbRow`](macro@FromDbRow).
+    ///
+    /// # Safety
+    ///
+    /// This method executes the raw SQL string without any sanitization.
+    /// Callers are responsible for ensuring the query is safe.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query is invalid, if the values returned by the
+    /// database are not compatible with the requested Rust types, or if the
+    /// database connection is lost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::{Database, FromDbRow};
+    ///
+    /// #[derive(FromDbRow)]
+    /// struct UsernameCount {
+    ///     username: String,
+    ///     count: i64,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// let db = Database::new("sqlite::memory:").await?;
+    /// db.raw("CREATE TABLE user (id INTEGER PRIMARY KEY AUTOINCREMENT, username TEXT)")
+    ///     .await?;
+    /// db.raw("INSERT INTO user (username) VALUES ('jondoe'), ('jondoe')")
+    ///     .await?;
+    ///
+    /// let counts = db
+    ///     .raw_values::<UsernameCount>("SELECT username, COUNT(*) FROM user GROUP BY username")
+    ///     .await?;
+    /// assert_eq!(counts[0].count, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn raw_values<R: FromDbRow>(&self, query: &str) -> Result<Vec<R>> {
+        self.raw_values_with(query, &[]).await
+    }
+
+    /// Executes a raw SQL query with parameters and converts the results to a
+    /// type implementing [`FromDbRow`].
+    ///
+    /// Unlike [`Database::raw_as_with`], the rows don't need to correspond to
+    /// a model.
+    ///
+    /// # Safety
+    ///
+    /// This method executes the raw SQL string without any sanitization.
+    /// Callers are responsible for ensuring the query is safe. The bound
+    /// `values`, however, are passed to the database driver separately from
+    /// the query text and are safe from SQL injection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query is invalid, if the values returned by the
+    /// database are not compatible with the requested Rust types, or if the
+    /// database connection is lost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Database;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// let db = Database::new("sqlite::memory:").await?;
+    /// db.raw("CREATE TABLE user (id INTEGER PRIMARY KEY AUTOINCREMENT, username TEXT)")
+    ///     .await?;
+    /// db.raw("INSERT INTO user (username) VALUES ('jondoe')")
+    ///     .await?;
+    ///
+    /// let ids = db
+    ///     .raw_values_with::<(i32,)>("SELECT id FROM user WHERE username = ?", &[&"jondoe"])
+    ///     .await?;
+    /// assert_eq!(ids, vec![(1,)]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn raw_values_with<R: FromDbRow>(
+        &self,
+        query: &str,
+        values: &[&dyn ToDbValue],
+    ) -> Result<Vec<R>> {
+        let values = values
+            .iter()
+            .map(ToDbValue::to_db_value)
+            .collect::<Vec<_>>();
+        let values = SqlxValues(sea_query::Values(values));
+
+        let mut executor: &Database = self;
+        let rows = executor
+            .fetch_all(&RawStatement { sql: query, values })
+            .await?;
+        rows.into_iter().map(R::from_db_row).collect::<Result<_>>()
+    }
+
     async fn execute_schema<T: SchemaStatementBuilder>(
         &self,
         statement: T,
@@ -2913,6 +4872,182 @@ impl Database {
 
         Ok(result)
     }
+
+    async fn alter_column(
+        &self,
+        table_name: Identifier,
+        old_field: &migrations::Field,
+        new_field: &migrations::Field,
+    ) -> Result<()> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => {
+                inner
+                    .alter_column(self, table_name, old_field, new_field)
+                    .await
+            }
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => {
+                inner
+                    .alter_column(self, table_name, old_field, new_field)
+                    .await
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(inner) => {
+                inner
+                    .alter_column(self, table_name, old_field, new_field)
+                    .await
+            }
+        }
+    }
+
+    /// Returns an error if columns of the given type can't be created with
+    /// the database backend.
+    fn check_column_type(&self, column_type: ColumnType) -> Result<()> {
+        let supports_arrays = match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(_) => false,
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(_) => true,
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(_) => false,
+        };
+
+        if matches!(column_type, ColumnType::Array(_)) && !supports_arrays {
+            return Err(DatabaseError::UnsupportedByBackend {
+                feature: "array columns",
+                backend: self.backend_name(),
+            });
+        }
+        Ok(())
+    }
+
+    fn backend_name(&self) -> &'static str {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(_) => "SQLite",
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(_) => "PostgreSQL",
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(_) => "MySQL",
+        }
+    }
+
+    async fn add_check_constraint(
+        &self,
+        table_name: Identifier,
+        name: Identifier,
+        expr: &str,
+    ) -> Result<()> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => inner.add_check_constraint(table_name, name, expr).await,
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(_) => {
+                let sql = add_check_constraint_sql(
+                    &sea_query::PostgresQueryBuilder,
+                    table_name,
+                    name,
+                    expr,
+                );
+                self.raw_schema(&sql).await
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(_) => {
+                let sql = add_check_constraint_sql(
+                    &sea_query::MysqlQueryBuilder,
+                    table_name,
+                    name,
+                    &impl_mysql::backtick_quoted_identifiers(expr),
+                );
+                self.raw_schema(&sql).await
+            }
+        }
+    }
+
+    async fn remove_check_constraint(
+        &self,
+        table_name: Identifier,
+        name: Identifier,
+    ) -> Result<()> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => inner.remove_check_constraint(table_name, name).await,
+            #[cfg(any(feature = "postgres", feature = "mysql"))]
+            _ => {
+                let statement = sea_query::Table::alter()
+                    .table(table_name)
+                    .drop_constraint(name)
+                    .to_owned();
+                self.execute_schema(statement).await?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Runs a schema modification statement built by hand, for the changes
+    /// `sea-query` has no statements for.
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
+    async fn raw_schema(&self, sql: &str) -> Result<()> {
+        tracing::debug!("Schema modification: {}", sql);
+        self.raw(sql).await?;
+        Ok(())
+    }
+
+    async fn add_foreign_key_constraint(
+        &self,
+        table_name: Identifier,
+        foreign_key: sea_query::ForeignKeyCreateStatement,
+    ) -> Result<()> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => {
+                inner
+                    .add_foreign_key_constraint(table_name, foreign_key)
+                    .await
+            }
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => {
+                inner
+                    .add_foreign_key_constraint(table_name, foreign_key)
+                    .await
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(inner) => {
+                inner
+                    .add_foreign_key_constraint(table_name, foreign_key)
+                    .await
+            }
+        }
+    }
+
+    async fn remove_foreign_key_constraint(
+        &self,
+        table_name: Identifier,
+        name: Identifier,
+        columns: &[Identifier],
+    ) -> Result<()> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => {
+                inner
+                    .remove_foreign_key_constraint(table_name, name, columns)
+                    .await
+            }
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => {
+                inner
+                    .remove_foreign_key_constraint(table_name, name, columns)
+                    .await
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(inner) => {
+                inner
+                    .remove_foreign_key_constraint(table_name, name, columns)
+                    .await
+            }
+        }
+    }
 }
 
 impl ColumnTypeMapper for Database {
@@ -2926,6 +5061,17 @@ impl ColumnTypeMapper for Database {
             DatabaseImpl::MySql(inner) => inner.sea_query_column_type_for(column_type),
         }
     }
+
+    fn sea_query_default_for(&self, default: DefaultValue) -> sea_query::Expr {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(_) => default.into(),
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(_) => default.into(),
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(_) => impl_mysql::build_default_expr(default),
+        }
+    }
 }
 
 impl LikeExprBuilder for Database {
@@ -2946,6 +5092,92 @@ impl LikeExprBuilder for Database {
     }
 }
 
+impl CastExprBuilder for Database {
+    fn cast_expr(
+        &self,
+        expr: SimpleExpr,
+        column_type: ColumnType,
+    ) -> std::result::Result<SimpleExpr, QueryBuildingError> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => inner.cast_expr(expr, column_type),
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => inner.cast_expr(expr, column_type),
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(inner) => inner.cast_expr(expr, column_type),
+        }
+    }
+}
+
+impl ArrayExprBuilder for Database {
+    fn array_expr(
+        &self,
+        lhs: SimpleExpr,
+        rhs: SimpleExpr,
+        operator: ArrayOperator,
+    ) -> std::result::Result<SimpleExpr, QueryBuildingError> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => inner.array_expr(lhs, rhs, operator),
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => inner.array_expr(lhs, rhs, operator),
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(inner) => inner.array_expr(lhs, rhs, operator),
+        }
+    }
+}
+
+impl SearchExprBuilder for Database {
+    fn search_expr(
+        &self,
+        document: SimpleExpr,
+        query: SimpleExpr,
+        config: &str,
+    ) -> std::result::Result<SimpleExpr, QueryBuildingError> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => inner.search_expr(document, query, config),
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => inner.search_expr(document, query, config),
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(inner) => inner.search_expr(document, query, config),
+        }
+    }
+
+    fn search_rank_expr(
+        &self,
+        document: SimpleExpr,
+        query: SimpleExpr,
+        config: &str,
+    ) -> std::result::Result<SimpleExpr, QueryBuildingError> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => inner.search_rank_expr(document, query, config),
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => inner.search_rank_expr(document, query, config),
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(inner) => inner.search_rank_expr(document, query, config),
+        }
+    }
+}
+
+impl JsonExprBuilder for Database {
+    fn json_extract_expr(
+        &self,
+        expr: SimpleExpr,
+        path: &[String],
+    ) -> std::result::Result<SimpleExpr, QueryBuildingError> {
+        match &*self.inner {
+            #[cfg(feature = "sqlite")]
+            DatabaseImpl::Sqlite(inner) => inner.json_extract_expr(expr, path),
+            #[cfg(feature = "postgres")]
+            DatabaseImpl::Postgres(inner) => inner.json_extract_expr(expr, path),
+            #[cfg(feature = "mysql")]
+            DatabaseImpl::MySql(inner) => inner.json_extract_expr(expr, path),
+        }
+    }
+}
+
 /// A trait that provides a backend for the database.
 ///
 /// This trait is used to provide a backend for the database.
@@ -3026,6 +5258,21 @@ pub trait DatabaseBackend: Send {
     /// Can return an error if the database connection is lost.
     async fn get<T: Model>(&mut self, query: &Query<T>) -> Result<Option<T>>;
 
+    /// Executes the given query and returns a stream of the results converted
+    /// to the model type, fetching the rows incrementally as the stream is
+    /// polled.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields an error if the query is invalid.
+    ///
+    /// The stream yields an error if the data in the database is not
+    /// compatible with the model (usually meaning the migrations haven't been
+    /// generated or applied).
+    ///
+    /// The stream yields an error if the database connection is lost.
+    fn stream<T: Model + Send + 'static>(&mut self, query: Query<T>) -> BoxStream<'_, Result<T>>;
+
     /// Returns whether a row exists that matches the given query.
     ///
     /// # Errors
@@ -3052,6 +5299,48 @@ pub trait DatabaseBackend: Send {
     /// Can return an error if the database connection is lost.
     async fn count<T: Model>(&mut self, query: &Query<T>) -> Result<u64>;
 
+    /// Returns the values of the given columns (or, more generally,
+    /// expressions) for each row that matches the given query, instead of
+    /// whole model instances.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the query is invalid.
+    ///
+    /// This method can return an error if the model doesn't exist in the
+    /// database (usually meaning the migrations haven't been generated or
+    /// applied).
+    ///
+    /// This method can return an error if the values returned by the database
+    /// are not compatible with the requested Rust types.
+    ///
+    /// Can return an error if the database connection is lost.
+    async fn values<T: Model, R: FromDbRow + Send + 'static>(
+        &mut self,
+        query: &Query<T>,
+        columns: &[Expr],
+    ) -> Result<Vec<R>>;
+
+    /// Updates all rows that match the given query with a single `UPDATE`
+    /// statement, applying the given assignments.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the query is invalid, if no
+    /// assignments are given, or if any of the assignments references a field
+    /// of a related model.
+    ///
+    /// This method can return an error if the model doesn't exist in the
+    /// database (usually meaning the migrations haven't been generated or
+    /// applied).
+    ///
+    /// Can return an error if the database connection is lost.
+    async fn update_many<T: Model>(
+        &mut self,
+        query: &Query<T>,
+        assignments: &[Assignment],
+    ) -> Result<StatementResult>;
+
     /// Deletes all rows that match the given query.
     ///
     /// # Errors
@@ -3064,6 +5353,77 @@ pub trait DatabaseBackend: Send {
     ///
     /// Can return an error if the database connection is lost.
     async fn delete<T: Model>(&mut self, query: &Query<T>) -> Result<StatementResult>;
+
+    /// Returns the model instances linked to the model instance with the
+    /// given primary key through the given many-to-many join table.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the model or the join table doesn't
+    /// exist in the database (usually meaning the migrations haven't been
+    /// generated or applied).
+    ///
+    /// This method can return an error if the data in the database is not
+    /// compatible with the model.
+    ///
+    /// Can return an error if the database connection is lost.
+    async fn query_many_to_many<T: Model>(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+    ) -> Result<Vec<T>>
+    where
+        T::PrimaryKey: SinglePrimaryKey;
+
+    /// Links two model instances through the given many-to-many join table.
+    /// If the instances are already linked, this does nothing.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the join table doesn't exist in the
+    /// database (usually meaning the migrations haven't been generated or
+    /// applied), or if any of the linked rows doesn't exist.
+    ///
+    /// Can return an error if the database connection is lost.
+    async fn add_many_to_many(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+        target_pk: &DbValue,
+    ) -> Result<()>;
+
+    /// Removes the link between two model instances from the given
+    /// many-to-many join table.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the join table doesn't exist in the
+    /// database (usually meaning the migrations haven't been generated or
+    /// applied).
+    ///
+    /// Can return an error if the database connection is lost.
+    async fn remove_many_to_many(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+        target_pk: &DbValue,
+    ) -> Result<StatementResult>;
+
+    /// Removes all the links of the model instance with the given primary key
+    /// from the given many-to-many join table.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the join table doesn't exist in the
+    /// database (usually meaning the migrations haven't been generated or
+    /// applied).
+    ///
+    /// Can return an error if the database connection is lost.
+    async fn clear_many_to_many(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+    ) -> Result<StatementResult>;
 }
 
 #[async_trait]
@@ -3096,6 +5456,10 @@ impl<DB: DatabaseBackend + ?Sized + Send> DatabaseBackend for &mut DB {
         (**self).get(query).await
     }
 
+    fn stream<T: Model + Send + 'static>(&mut self, query: Query<T>) -> BoxStream<'_, Result<T>> {
+        (**self).stream(query)
+    }
+
     async fn exists<T: Model>(&mut self, query: &Query<T>) -> Result<bool> {
         (**self).exists(query).await
     }
@@ -3104,9 +5468,64 @@ impl<DB: DatabaseBackend + ?Sized + Send> DatabaseBackend for &mut DB {
         (**self).count(query).await
     }
 
+    async fn values<T: Model, R: FromDbRow + Send + 'static>(
+        &mut self,
+        query: &Query<T>,
+        columns: &[Expr],
+    ) -> Result<Vec<R>> {
+        (**self).values(query, columns).await
+    }
+
+    async fn update_many<T: Model>(
+        &mut self,
+        query: &Query<T>,
+        assignments: &[Assignment],
+    ) -> Result<StatementResult> {
+        (**self).update_many(query, assignments).await
+    }
+
     async fn delete<T: Model>(&mut self, query: &Query<T>) -> Result<StatementResult> {
         (**self).delete(query).await
     }
+
+    async fn query_many_to_many<T: Model>(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+    ) -> Result<Vec<T>>
+    where
+        T::PrimaryKey: SinglePrimaryKey,
+    {
+        (**self).query_many_to_many(table, source_pk).await
+    }
+
+    async fn add_many_to_many(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+        target_pk: &DbValue,
+    ) -> Result<()> {
+        (**self).add_many_to_many(table, source_pk, target_pk).await
+    }
+
+    async fn remove_many_to_many(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+        target_pk: &DbValue,
+    ) -> Result<StatementResult> {
+        (**self)
+            .remove_many_to_many(table, source_pk, target_pk)
+            .await
+    }
+
+    async fn clear_many_to_many(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+    ) -> Result<StatementResult> {
+        (**self).clear_many_to_many(table, source_pk).await
+    }
 }
 
 #[async_trait]
@@ -3132,24 +5551,81 @@ impl DatabaseBackend for &Database {
     }
 
     async fn query<T: Model>(&mut self, query: &Query<T>) -> Result<Vec<T>> {
-        Database::query_generic(*self, query).await
+        Database::query_generic(self.database_for_query(query), query).await
     }
 
     async fn get<T: Model>(&mut self, query: &Query<T>) -> Result<Option<T>> {
-        Database::get_generic(*self, query).await
+        Database::get_generic(self.database_for_query(query), query).await
+    }
+
+    fn stream<T: Model + Send + 'static>(&mut self, query: Query<T>) -> BoxStream<'_, Result<T>> {
+        Database::stream_generic(self.database_for_query(&query), query)
     }
 
     async fn exists<T: Model>(&mut self, query: &Query<T>) -> Result<bool> {
-        Database::exists_generic(*self, query).await
+        Database::exists_generic(self.database_for_query(query), query).await
     }
 
     async fn count<T: Model>(&mut self, query: &Query<T>) -> Result<u64> {
-        Database::count_generic(*self, query).await
+        Database::count_generic(self.database_for_query(query), query).await
+    }
+
+    async fn values<T: Model, R: FromDbRow + Send + 'static>(
+        &mut self,
+        query: &Query<T>,
+        columns: &[Expr],
+    ) -> Result<Vec<R>> {
+        Database::values_generic(self.database_for_query(query), query, columns).await
+    }
+
+    async fn update_many<T: Model>(
+        &mut self,
+        query: &Query<T>,
+        assignments: &[Assignment],
+    ) -> Result<StatementResult> {
+        Database::update_many_generic(*self, query, assignments).await
     }
 
     async fn delete<T: Model>(&mut self, query: &Query<T>) -> Result<StatementResult> {
         Database::delete_generic(*self, query).await
     }
+
+    async fn query_many_to_many<T: Model>(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+    ) -> Result<Vec<T>>
+    where
+        T::PrimaryKey: SinglePrimaryKey,
+    {
+        Database::query_many_to_many_generic(self.read_database::<T>(), table, source_pk).await
+    }
+
+    async fn add_many_to_many(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+        target_pk: &DbValue,
+    ) -> Result<()> {
+        Database::add_many_to_many_generic(*self, table, source_pk, target_pk).await
+    }
+
+    async fn remove_many_to_many(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+        target_pk: &DbValue,
+    ) -> Result<StatementResult> {
+        Database::remove_many_to_many_generic(*self, table, source_pk, Some(target_pk)).await
+    }
+
+    async fn clear_many_to_many(
+        &mut self,
+        table: ManyToManyTable,
+        source_pk: &DbValue,
+    ) -> Result<StatementResult> {
+        Database::remove_many_to_many_generic(*self, table, source_pk, None).await
+    }
 }
 
 /// Result of a statement execution.
@@ -3561,12 +6037,208 @@ pub enum ColumnType {
     Blob,
     /// A string column type with a maximum length.
     String(u32),
+    /// A UUID column type.
+    ///
+    /// This is a native `UUID` column on PostgreSQL, and a text column
+    /// holding the hyphenated representation of the UUID elsewhere.
+    Uuid,
+    /// A fixed-point decimal number column type.
+    ///
+    /// This is a `NUMERIC` column on PostgreSQL, a `DECIMAL(65, 28)` column
+    /// on MySQL, and a text column on SQLite, which doesn't have a
+    /// fixed-point number type.
+    Decimal,
+    /// A JSON column type.
+    ///
+    /// This is a `JSONB` column on PostgreSQL, a `JSON` column on MySQL, and a
+    /// text column on SQLite.
+    Json,
+    /// An array column type, holding any number of values of the given
+    /// element type.
+    ///
+    /// This is only supported on PostgreSQL. Creating such a column on other
+    /// database backends results in a
+    /// [`DatabaseError::UnsupportedByBackend`] error.
+    Array(&'static ColumnType),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[derive(std::fmt::Debug)]
+    struct TestSqlxDatabaseError {
+        code: &'static str,
+    }
+
+    impl Display for TestSqlxDatabaseError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "test error {}", self.code)
+        }
+    }
+
+    impl std::error::Error for TestSqlxDatabaseError {}
+
+    impl sqlx::error::DatabaseError for TestSqlxDatabaseError {
+        fn message(&self) -> &str {
+            self.code
+        }
+
+        fn code(&self) -> Option<std::borrow::Cow<'_, str>> {
+            Some(self.code.into())
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> sqlx::error::ErrorKind {
+            sqlx::error::ErrorKind::Other
+        }
+    }
+
+    fn engine_error(code: &'static str) -> DatabaseError {
+        DatabaseError::DatabaseEngineError(sqlx::Error::Database(Box::new(TestSqlxDatabaseError {
+            code,
+        })))
+    }
+
+    #[test]
+    fn database_error_is_transaction_conflict() {
+        assert!(engine_error("40001").is_transaction_conflict());
+        assert!(engine_error("40P01").is_transaction_conflict());
+        assert!(engine_error("5").is_transaction_conflict());
+        assert!(engine_error("517").is_transaction_conflict());
+        assert!(!engine_error("23505").is_transaction_conflict());
+        assert!(!DatabaseError::UniqueViolation.is_transaction_conflict());
+        assert!(!DatabaseError::ForeignKeyNotFound.is_transaction_conflict());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[cot::test]
+    #[cfg_attr(
+        miri,
+        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
+    )]
+    async fn transaction_with_retry_retries_conflicts() {
+        let db = Database::new("sqlite::memory:").await.unwrap();
+        db.raw("CREATE TABLE counter (value INTEGER)")
+            .await
+            .unwrap();
+        db.raw("INSERT INTO counter (value) VALUES (0)")
+            .await
+            .unwrap();
+
+        let mut attempts = 0;
+        let result = db
+            .transaction_with_retry(2, async |transaction| {
+                attempts += 1;
+                transaction
+                    .raw("UPDATE counter SET value = value + 1")
+                    .await?;
+                if attempts < 3 {
+                    return Err(engine_error("40001"));
+                }
+                Ok(attempts)
+            })
+            .await;
+        assert_eq!(result.unwrap(), 3);
+
+        // the failed attempts were rolled back
+        let values = db
+            .raw_values::<(i32,)>("SELECT value FROM counter")
+            .await
+            .unwrap();
+        assert_eq!(values, vec![(1,)]);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[cot::test]
+    #[cfg_attr(
+        miri,
+        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
+    )]
+    async fn transaction_with_retry_gives_up() {
+        let db = Database::new("sqlite::memory:").await.unwrap();
+
+        let mut attempts = 0;
+        let result: Result<()> = db
+            .transaction_with_retry(2, async |_| {
+                attempts += 1;
+                Err(engine_error("40P01"))
+            })
+            .await;
+        assert!(result.unwrap_err().is_transaction_conflict());
+        assert_eq!(attempts, 3);
+
+        // other errors are not retried
+        let mut attempts = 0;
+        let result: Result<()> = db
+            .transaction_with_retry(2, async |_| {
+                attempts += 1;
+                Err(DatabaseError::UniqueViolation)
+            })
+            .await;
+        assert!(matches!(result, Err(DatabaseError::UniqueViolation)));
+        assert_eq!(attempts, 1);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[cot::test]
+    #[cfg_attr(
+        miri,
+        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
+    )]
+    async fn new_with_pool_runs_init_sql() {
+        let pool_config = DatabasePoolConfig::builder()
+            .max_connections(2)
+            .init_sql(vec![
+                "PRAGMA cache_size = -4000; PRAGMA busy_timeout = 1234".to_owned(),
+            ])
+            .build();
+        let db = Database::new_with_pool("sqlite::memory:", &pool_config)
+            .await
+            .unwrap();
+
+        // each connection of the pool is initialized
+        let transaction = db.begin().await.unwrap();
+        let cache_size = db.raw_values::<(i32,)>("PRAGMA cache_size").await.unwrap();
+        let busy_timeout = db
+            .raw_values::<(i32,)>("PRAGMA busy_timeout")
+            .await
+            .unwrap();
+        transaction.rollback().await.unwrap();
+
+        assert_eq!(cache_size, vec![(-4000,)]);
+        assert_eq!(busy_timeout, vec![(1234,)]);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[cot::test]
+    #[cfg_attr(
+        miri,
+        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
+    )]
+    async fn new_with_pool_invalid_init_sql() {
+        // the connection is retried until the acquire timeout passes
+        let pool_config = DatabasePoolConfig::builder()
+            .acquire_timeout(std::time::Duration::from_secs(1))
+            .init_sql(vec!["NOT A STATEMENT".to_owned()])
+            .build();
+
+        let result = Database::new_with_pool("sqlite::memory:", &pool_config).await;
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn identifier() {
         let id = Identifier::new("test");
@@ -3682,4 +6354,36 @@ mod tests {
             serde_json::from_str("null");
         assert!(deserialized.is_err());
     }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn add_check_constraint_sql_postgres() {
+        let sql = add_check_constraint_sql(
+            &sea_query::PostgresQueryBuilder,
+            Identifier::new("testapp__item"),
+            Identifier::new("testapp__item_price_positive"),
+            "price > 0",
+        );
+
+        assert_eq!(
+            sql,
+            "ALTER TABLE \"testapp__item\" ADD CONSTRAINT \"testapp__item_price_positive\" CHECK (price > 0)"
+        );
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn add_check_constraint_sql_mysql() {
+        let sql = add_check_constraint_sql(
+            &sea_query::MysqlQueryBuilder,
+            Identifier::new("testapp__item"),
+            Identifier::new("testapp__item_price_positive"),
+            "price > 0",
+        );
+
+        assert_eq!(
+            sql,
+            "ALTER TABLE `testapp__item` ADD CONSTRAINT `testapp__item_price_positive` CHECK (price > 0)"
+        );
+    }
 }
@@ -9,12 +9,26 @@ use crate::db::impl_postgres::PostgresValueRef;
 #[cfg(feature = "sqlite")]
 use crate::db::impl_sqlite::SqliteValueRef;
 use crate::db::{
-    Auto, ColumnType, DatabaseError, DatabaseField, DbFieldValue, DbValue, ForeignKey, FromDbValue,
-    LimitedString, Model, PrimaryKey, Result, SqlxValueRef, TextField, ToDbFieldValue, ToDbValue,
+    Auto, ColumnType, DatabaseError, DatabaseField, DbEnum, DbFieldValue, DbValue, ForeignKey,
+    FromDbValue, LimitedString, Model, PrimaryKey, Result, SinglePrimaryKey, SqlxValueRef,
+    TextField, ToDbFieldValue, ToDbValue,
 };
 
+#[cfg(feature = "postgres")]
+mod array_fields;
 mod chrono_fields;
 mod chrono_wrapper;
+#[cfg(feature = "decimal")]
+mod decimal_fields;
+#[cfg(feature = "json")]
+mod json_fields;
+#[cfg(feature = "uuid")]
+mod uuid_fields;
+
+#[cfg(all(feature = "postgres", any(feature = "sqlite", feature = "mysql")))]
+pub(crate) use array_fields::reject_array_value;
+#[cfg(feature = "uuid")]
+pub(crate) use uuid_fields::uuid_to_string;
 
 macro_rules! impl_from_sqlite_default {
     () => {
@@ -96,6 +110,8 @@ macro_rules! impl_from_mysql_default {
     };
 }
 
+use impl_from_mysql_default;
+
 macro_rules! impl_to_db_value_default {
     ($ty:ty) => {
         impl crate::db::ToDbValue for $ty {
@@ -337,12 +353,94 @@ impl<const LIMIT: u32> FromDbValue for Option<LimitedString<LIMIT>> {
 
 impl<const LIMIT: u32> TextField for LimitedString<LIMIT> {}
 
-impl<T: Model + Send + Sync> DatabaseField for ForeignKey<T> {
+impl<T: DbEnum> FromDbValue for T {
+    #[cfg(feature = "sqlite")]
+    fn from_sqlite(value: SqliteValueRef<'_>) -> Result<Self> {
+        db_enum_from_repr(&T::Repr::from_sqlite(value)?)
+    }
+
+    #[cfg(feature = "postgres")]
+    fn from_postgres(value: PostgresValueRef<'_>) -> Result<Self> {
+        db_enum_from_repr(&T::Repr::from_postgres(value)?)
+    }
+
+    #[cfg(feature = "mysql")]
+    fn from_mysql(value: MySqlValueRef<'_>) -> Result<Self> {
+        db_enum_from_repr(&T::Repr::from_mysql(value)?)
+    }
+}
+
+impl<T: DbEnum> FromDbValue for Option<T>
+where
+    Option<T::Repr>: FromDbValue,
+{
+    #[cfg(feature = "sqlite")]
+    fn from_sqlite(value: SqliteValueRef<'_>) -> Result<Self> {
+        <Option<T::Repr>>::from_sqlite(value)?
+            .map(|repr| db_enum_from_repr(&repr))
+            .transpose()
+    }
+
+    #[cfg(feature = "postgres")]
+    fn from_postgres(value: PostgresValueRef<'_>) -> Result<Self> {
+        <Option<T::Repr>>::from_postgres(value)?
+            .map(|repr| db_enum_from_repr(&repr))
+            .transpose()
+    }
+
+    #[cfg(feature = "mysql")]
+    fn from_mysql(value: MySqlValueRef<'_>) -> Result<Self> {
+        <Option<T::Repr>>::from_mysql(value)?
+            .map(|repr| db_enum_from_repr(&repr))
+            .transpose()
+    }
+}
+
+// `ToDbValue` for the enum itself is generated by the `DbEnum` derive macro,
+// as a blanket implementation would conflict with the one for `&T`.
+impl<T: DbEnum + Send + Sync> ToDbValue for Option<T>
+where
+    Option<T::Repr>: ToDbValue,
+{
+    fn to_db_value(&self) -> DbValue {
+        self.as_ref().map(DbEnum::to_repr).to_db_value()
+    }
+}
+
+#[cfg_attr(
+    not(any(feature = "sqlite", feature = "postgres", feature = "mysql")),
+    expect(dead_code)
+)]
+fn db_enum_from_repr<T: DbEnum>(repr: &T::Repr) -> Result<T> {
+    T::from_repr(repr).ok_or_else(|| {
+        DatabaseError::value_decode(UnknownDbEnumValue {
+            enum_name: std::any::type_name::<T>(),
+            value: format!("{repr:?}"),
+        })
+    })
+}
+
+/// An error returned when a value read from the database doesn't correspond to
+/// any variant of a [`DbEnum`].
+#[derive(Debug, thiserror::Error)]
+#[error("unknown value for `{enum_name}`: {value}")]
+struct UnknownDbEnumValue {
+    enum_name: &'static str,
+    value: String,
+}
+
+impl<T: Model + Send + Sync> DatabaseField for ForeignKey<T>
+where
+    T::PrimaryKey: SinglePrimaryKey,
+{
     const NULLABLE: bool = T::PrimaryKey::NULLABLE;
     const TYPE: ColumnType = T::PrimaryKey::TYPE;
 }
 
-impl<T: Model + Send + Sync> FromDbValue for ForeignKey<T> {
+impl<T: Model + Send + Sync> FromDbValue for ForeignKey<T>
+where
+    T::PrimaryKey: SinglePrimaryKey,
+{
     #[cfg(feature = "sqlite")]
     fn from_sqlite(value: SqliteValueRef<'_>) -> Result<Self> {
         T::PrimaryKey::from_sqlite(value).map(ForeignKey::PrimaryKey)
@@ -359,7 +457,10 @@ impl<T: Model + Send + Sync> FromDbValue for ForeignKey<T> {
     }
 }
 
-impl<T: Model + Send + Sync> ToDbFieldValue for ForeignKey<T> {
+impl<T: Model + Send + Sync> ToDbFieldValue for ForeignKey<T>
+where
+    T::PrimaryKey: SinglePrimaryKey,
+{
     fn to_db_field_value(&self) -> DbFieldValue {
         self.primary_key().to_db_field_value()
     }
@@ -387,6 +488,7 @@ where
 
 impl<T: Model + Send + Sync> ToDbFieldValue for Option<ForeignKey<T>>
 where
+    T::PrimaryKey: SinglePrimaryKey,
     Option<T::PrimaryKey>: ToDbFieldValue,
 {
     fn to_db_field_value(&self) -> DbFieldValue {
@@ -489,3 +591,10 @@ impl PrimaryKey for u32 {}
 impl PrimaryKey for u64 {}
 
 impl PrimaryKey for String {}
+
+/// Foreign keys can be a part of a composite primary key, for instance in
+/// tables that link two other models together.
+impl<T: Model + Clone + Send + Sync> PrimaryKey for ForeignKey<T> where
+    T::PrimaryKey: SinglePrimaryKey
+{
+}
@@ -0,0 +1,114 @@
+//! PostgreSQL array fields.
+//!
+//! `Vec<T>` is stored as a native array column on PostgreSQL. Other database
+//! backends don't have an array type, so creating such a column, binding an
+//! array value or reading one results in a
+//! [`DatabaseError::UnsupportedByBackend`] error.
+
+#[cfg(any(feature = "sqlite", feature = "mysql"))]
+use crate::db::DatabaseError;
+#[cfg(any(feature = "sqlite", feature = "mysql"))]
+use crate::db::Result;
+use crate::db::fields::{impl_from_postgres_default, impl_to_db_value_default};
+#[cfg(feature = "mysql")]
+use crate::db::impl_mysql::MySqlValueRef;
+#[cfg(feature = "sqlite")]
+use crate::db::impl_sqlite::SqliteValueRef;
+use crate::db::{ColumnType, DatabaseField, FromDbValue};
+
+macro_rules! impl_array_db_field {
+    ($ty:ty, $column_type:ident) => {
+        impl DatabaseField for Vec<$ty> {
+            const TYPE: ColumnType = ColumnType::Array(&ColumnType::$column_type);
+        }
+
+        impl FromDbValue for Vec<$ty> {
+            impl_from_unsupported!();
+
+            impl_from_postgres_default!();
+        }
+
+        impl FromDbValue for Option<Vec<$ty>> {
+            impl_from_unsupported!();
+
+            impl_from_postgres_default!();
+        }
+
+        impl_to_db_value_default!(Vec<$ty>);
+    };
+}
+
+macro_rules! impl_from_unsupported {
+    () => {
+        #[cfg(feature = "sqlite")]
+        fn from_sqlite(_value: SqliteValueRef<'_>) -> Result<Self> {
+            Err(unsupported("SQLite"))
+        }
+
+        #[cfg(feature = "mysql")]
+        fn from_mysql(_value: MySqlValueRef<'_>) -> Result<Self> {
+            Err(unsupported("MySQL"))
+        }
+    };
+}
+
+impl_array_db_field!(bool, Boolean);
+impl_array_db_field!(i16, SmallInteger);
+impl_array_db_field!(i32, Integer);
+impl_array_db_field!(i64, BigInteger);
+impl_array_db_field!(f32, Float);
+impl_array_db_field!(f64, Double);
+impl_array_db_field!(String, Text);
+
+#[cfg(any(feature = "sqlite", feature = "mysql"))]
+fn unsupported(backend: &'static str) -> DatabaseError {
+    DatabaseError::UnsupportedByBackend {
+        feature: "array values",
+        backend,
+    }
+}
+
+/// Returns an error if the value is an array, so that arrays are never bound
+/// to queries on database backends that don't support them.
+#[cfg(any(feature = "sqlite", feature = "mysql"))]
+pub(crate) fn reject_array_value(value: &sea_query::Value, backend: &'static str) -> Result<()> {
+    if matches!(value, sea_query::Value::Array(..)) {
+        return Err(unsupported(backend));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{DbValue, ToDbValue};
+
+    #[test]
+    fn test_array_column_type() {
+        assert_eq!(
+            <Vec<i32> as DatabaseField>::TYPE,
+            ColumnType::Array(&ColumnType::Integer)
+        );
+        assert_eq!(
+            <Option<Vec<String>> as DatabaseField>::TYPE,
+            ColumnType::Array(&ColumnType::Text)
+        );
+        const { assert!(<Option<Vec<String>> as DatabaseField>::NULLABLE) };
+    }
+
+    #[test]
+    fn test_array_to_db_value() {
+        assert_eq!(vec![1_i64, 2].to_db_value(), DbValue::from(vec![1_i64, 2]));
+        assert_eq!(
+            None::<Vec<bool>>.to_db_value(),
+            DbValue::Array(sea_query::ArrayType::Bool, None)
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_reject_array_value() {
+        assert!(reject_array_value(&DbValue::from(vec![1_i32]), "SQLite").is_err());
+        assert!(reject_array_value(&DbValue::Int(Some(1)), "SQLite").is_ok());
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(any(feature = "sqlite", feature = "mysql"))]
+use crate::db::Result;
+use crate::db::fields::{
+    impl_from_mysql_default, impl_from_postgres_default, impl_to_db_value_default,
+};
+#[cfg(feature = "sqlite")]
+use crate::db::impl_sqlite::SqliteValueRef;
+use crate::db::{ColumnType, DatabaseField, FromDbValue};
+#[cfg(feature = "sqlite")]
+use crate::db::{DatabaseError, SqlxValueRef};
+
+impl DatabaseField for rust_decimal::Decimal {
+    const TYPE: ColumnType = ColumnType::Decimal;
+}
+
+impl FromDbValue for rust_decimal::Decimal {
+    #[cfg(feature = "sqlite")]
+    fn from_sqlite(value: SqliteValueRef<'_>) -> Result<Self> {
+        parse_decimal(&value.get::<String>()?)
+    }
+
+    impl_from_postgres_default!();
+
+    impl_from_mysql_default!();
+}
+
+impl FromDbValue for Option<rust_decimal::Decimal> {
+    #[cfg(feature = "sqlite")]
+    fn from_sqlite(value: SqliteValueRef<'_>) -> Result<Self> {
+        value
+            .get::<Option<String>>()?
+            .map(|decimal| parse_decimal(&decimal))
+            .transpose()
+    }
+
+    impl_from_postgres_default!();
+
+    impl_from_mysql_default!();
+}
+
+impl_to_db_value_default!(rust_decimal::Decimal);
+
+/// Parses a decimal number stored as text, which is how decimals are stored in
+/// SQLite to avoid losing precision.
+#[cfg(feature = "sqlite")]
+fn parse_decimal(value: &str) -> Result<rust_decimal::Decimal> {
+    value.parse().map_err(DatabaseError::value_decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use crate::db::{ColumnType, DatabaseField, DbValue, ToDbValue};
+
+    #[test]
+    fn test_decimal_column_type() {
+        assert_eq!(<Decimal as DatabaseField>::TYPE, ColumnType::Decimal);
+        const {
+            assert!(!<Decimal as DatabaseField>::NULLABLE);
+        }
+
+        assert_eq!(
+            <Option<Decimal> as DatabaseField>::TYPE,
+            ColumnType::Decimal
+        );
+        const {
+            assert!(<Option<Decimal> as DatabaseField>::NULLABLE);
+        }
+    }
+
+    #[test]
+    fn test_decimal_to_db_value() {
+        let decimal = Decimal::new(12345, 2);
+
+        assert_eq!(decimal.to_db_value(), DbValue::Decimal(Some(decimal)));
+        assert_eq!(None::<Decimal>.to_db_value(), DbValue::Decimal(None));
+    }
+}
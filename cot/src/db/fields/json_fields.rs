@@ -0,0 +1,77 @@
+#[cfg(any(feature = "sqlite", feature = "mysql"))]
+use crate::db::Result;
+use crate::db::fields::{
+    impl_from_mysql_default, impl_from_postgres_default, impl_to_db_value_default,
+};
+#[cfg(feature = "sqlite")]
+use crate::db::impl_sqlite::SqliteValueRef;
+use crate::db::{ColumnType, DatabaseField, FromDbValue};
+#[cfg(feature = "sqlite")]
+use crate::db::{DatabaseError, SqlxValueRef};
+
+impl DatabaseField for serde_json::Value {
+    const TYPE: ColumnType = ColumnType::Json;
+}
+
+impl FromDbValue for serde_json::Value {
+    #[cfg(feature = "sqlite")]
+    fn from_sqlite(value: SqliteValueRef<'_>) -> Result<Self> {
+        parse_json(&value.get::<String>()?)
+    }
+
+    impl_from_postgres_default!();
+
+    impl_from_mysql_default!();
+}
+
+impl FromDbValue for Option<serde_json::Value> {
+    #[cfg(feature = "sqlite")]
+    fn from_sqlite(value: SqliteValueRef<'_>) -> Result<Self> {
+        value
+            .get::<Option<String>>()?
+            .map(|json| parse_json(&json))
+            .transpose()
+    }
+
+    impl_from_postgres_default!();
+
+    impl_from_mysql_default!();
+}
+
+impl_to_db_value_default!(serde_json::Value);
+
+/// Parses a JSON document stored as text, which is how JSON is stored in
+/// SQLite.
+#[cfg(feature = "sqlite")]
+fn parse_json(value: &str) -> Result<serde_json::Value> {
+    serde_json::from_str(value).map_err(DatabaseError::value_decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{ColumnType, DatabaseField, DbValue, ToDbValue};
+
+    #[test]
+    fn test_json_column_type() {
+        assert_eq!(<serde_json::Value as DatabaseField>::TYPE, ColumnType::Json);
+        const {
+            assert!(!<serde_json::Value as DatabaseField>::NULLABLE);
+        }
+
+        assert_eq!(
+            <Option<serde_json::Value> as DatabaseField>::TYPE,
+            ColumnType::Json
+        );
+        const {
+            assert!(<Option<serde_json::Value> as DatabaseField>::NULLABLE);
+        }
+    }
+
+    #[test]
+    fn test_json_to_db_value() {
+        let json = serde_json::json!({"name": "cot", "tags": ["web", "rust"]});
+
+        assert_eq!(json.to_db_value(), DbValue::Json(Some(Box::new(json))));
+        assert_eq!(None::<serde_json::Value>.to_db_value(), DbValue::Json(None));
+    }
+}
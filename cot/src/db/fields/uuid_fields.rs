@@ -0,0 +1,98 @@
+use crate::db::fields::{impl_from_postgres_default, impl_to_db_value_default};
+#[cfg(feature = "mysql")]
+use crate::db::impl_mysql::MySqlValueRef;
+#[cfg(feature = "sqlite")]
+use crate::db::impl_sqlite::SqliteValueRef;
+use crate::db::{ColumnType, DatabaseField, FromDbValue, PrimaryKey};
+#[cfg(any(feature = "sqlite", feature = "mysql"))]
+use crate::db::{DatabaseError, Result, SqlxValueRef};
+
+impl DatabaseField for uuid::Uuid {
+    const TYPE: ColumnType = ColumnType::Uuid;
+}
+
+impl FromDbValue for uuid::Uuid {
+    #[cfg(feature = "sqlite")]
+    fn from_sqlite(value: SqliteValueRef<'_>) -> Result<Self> {
+        parse_uuid(&value.get::<String>()?)
+    }
+
+    impl_from_postgres_default!();
+
+    #[cfg(feature = "mysql")]
+    fn from_mysql(value: MySqlValueRef<'_>) -> Result<Self> {
+        parse_uuid(&value.get::<String>()?)
+    }
+}
+
+impl FromDbValue for Option<uuid::Uuid> {
+    #[cfg(feature = "sqlite")]
+    fn from_sqlite(value: SqliteValueRef<'_>) -> Result<Self> {
+        value
+            .get::<Option<String>>()?
+            .map(|uuid| parse_uuid(&uuid))
+            .transpose()
+    }
+
+    impl_from_postgres_default!();
+
+    #[cfg(feature = "mysql")]
+    fn from_mysql(value: MySqlValueRef<'_>) -> Result<Self> {
+        value
+            .get::<Option<String>>()?
+            .map(|uuid| parse_uuid(&uuid))
+            .transpose()
+    }
+}
+
+impl_to_db_value_default!(uuid::Uuid);
+
+impl PrimaryKey for uuid::Uuid {}
+
+#[cfg(any(feature = "sqlite", feature = "mysql"))]
+fn parse_uuid(value: &str) -> Result<uuid::Uuid> {
+    uuid::Uuid::parse_str(value).map_err(DatabaseError::value_decode)
+}
+
+/// Converts a UUID value to its hyphenated text representation, which is how
+/// UUIDs are stored in the databases that don't have a native UUID type.
+pub(crate) fn uuid_to_string(value: &mut sea_query::Value) {
+    if let sea_query::Value::Uuid(uuid) = value {
+        *value = sea_query::Value::String(uuid.map(|uuid| uuid.hyphenated().to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{DbValue, ToDbValue};
+
+    #[test]
+    fn test_uuid_column_type() {
+        assert_eq!(<uuid::Uuid as DatabaseField>::TYPE, ColumnType::Uuid);
+        assert_eq!(
+            <Option<uuid::Uuid> as DatabaseField>::TYPE,
+            ColumnType::Uuid
+        );
+    }
+
+    #[test]
+    fn test_uuid_to_string() {
+        let uuid = uuid::Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+
+        let mut value = uuid.to_db_value();
+        uuid_to_string(&mut value);
+        assert_eq!(
+            value,
+            DbValue::String(Some("67e55044-10b1-426f-9247-bb680e5fe0c8".to_owned()))
+        );
+
+        let mut value = None::<uuid::Uuid>.to_db_value();
+        uuid_to_string(&mut value);
+        assert_eq!(value, DbValue::String(None));
+
+        let mut value = 5.to_db_value();
+        uuid_to_string(&mut value);
+        assert_eq!(value, DbValue::Int(Some(5)));
+    }
+}
@@ -1,24 +1,72 @@
 //! Database interface implementation – MySQL backend.
 
 use cot::db::query::expr::like::LIKE_ESCAPE_CHAR;
-use sea_query::{ExprTrait, LikeExpr, SimpleExpr};
+use sea_query::{
+    ColumnDef, ExprTrait, ForeignKeyCreateStatement, Index, LikeExpr, SimpleExpr, Table,
+    TableAlterStatement,
+};
 
-use crate::db::ColumnType;
+use crate::db::migrations::{ColumnTypeMapper, DefaultValue, Field};
 use crate::db::query::QueryBuildingError;
+use crate::db::query::expr::array::{ArrayExprBuilder, ArrayOperator};
+use crate::db::query::expr::cast::CastExprBuilder;
+use crate::db::query::expr::json::{JsonExprBuilder, to_json_path};
 use crate::db::query::expr::like::{CaseSensitivity, LikeExprBuilder, to_sql_like};
+use crate::db::query::expr::search::SearchExprBuilder;
 use crate::db::sea_query_db::{impl_sea_query_db_backend, impl_sea_query_transaction_backend};
+use crate::db::{ColumnType, Identifier};
 
 impl_sea_query_db_backend!(DatabaseMySql: sqlx::mysql::MySql, sqlx::mysql::MySqlPool, MySqlRow, MySqlValueRef, sea_query::MysqlQueryBuilder);
 impl_sea_query_transaction_backend!(DatabaseMySql, TransactionMySql: sqlx::mysql::MySql, MySqlRow, sea_query::MysqlQueryBuilder);
 
+/// The length of the hyphenated representation of a UUID, which is how UUIDs
+/// are stored in MySQL.
+const MYSQL_UUID_LENGTH: u32 = 36;
+
+/// Selects the names of the foreign keys on a single column of a table.
+///
+/// The names are cast to `CHAR`, as `information_schema` returns them as
+/// binary strings in some MySQL versions.
+const FOREIGN_KEY_NAMES_QUERY: &str = "SELECT CAST(CONSTRAINT_NAME AS CHAR) \
+     FROM information_schema.KEY_COLUMN_USAGE \
+     WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? AND COLUMN_NAME = ? \
+     AND REFERENCED_TABLE_NAME IS NOT NULL";
+
+/// Selects the names of the unique indexes covering only a single column of a
+/// table.
+const UNIQUE_INDEX_NAMES_QUERY: &str = "SELECT CAST(INDEX_NAME AS CHAR) \
+     FROM information_schema.STATISTICS s \
+     WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? AND COLUMN_NAME = ? \
+     AND NON_UNIQUE = 0 AND INDEX_NAME <> 'PRIMARY' \
+     AND NOT EXISTS (SELECT 1 FROM information_schema.STATISTICS o \
+     WHERE o.TABLE_SCHEMA = s.TABLE_SCHEMA AND o.TABLE_NAME = s.TABLE_NAME \
+     AND o.INDEX_NAME = s.INDEX_NAME AND o.COLUMN_NAME <> s.COLUMN_NAME)";
+
 impl DatabaseMySql {
     #[expect(clippy::unused_async)]
     async fn init(&self) -> crate::db::Result<()> {
         Ok(())
     }
 
-    fn prepare_values(_values: &mut sea_query_sqlx::SqlxValues) {
+    #[cfg(not(any(feature = "uuid", feature = "postgres")))]
+    #[expect(clippy::unnecessary_wraps)] // to have a unified interface between database impls
+    fn prepare_values(_values: &mut sea_query_sqlx::SqlxValues) -> crate::db::Result<()> {
         // No changes are needed for MySQL
+        Ok(())
+    }
+
+    #[cfg(any(feature = "uuid", feature = "postgres"))]
+    #[cfg_attr(not(feature = "postgres"), expect(clippy::unnecessary_wraps))]
+    fn prepare_values(values: &mut sea_query_sqlx::SqlxValues) -> crate::db::Result<()> {
+        for value in &mut values.0.0 {
+            // arrays can only be used with PostgreSQL
+            #[cfg(feature = "postgres")]
+            crate::db::fields::reject_array_value(value, "MySQL")?;
+            // UUIDs are stored as text, but they would be bound as binary values
+            #[cfg(feature = "uuid")]
+            crate::db::fields::uuid_to_string(value);
+        }
+        Ok(())
     }
 
     #[expect(clippy::unnecessary_wraps)] // to have a unified interface between database impls
@@ -35,11 +83,122 @@ impl DatabaseMySql {
             ColumnType::DateTime | ColumnType::DateTimeWithTimeZone => {
                 return sea_query::ColumnType::custom("DATETIME(6)");
             }
+            // UUIDs are stored as text, so that they are readable when
+            // inspecting the database
+            ColumnType::Uuid => {
+                return sea_query::ColumnType::Char(Some(MYSQL_UUID_LENGTH));
+            }
+            ColumnType::Decimal => {
+                return sea_query::ColumnType::Decimal(Some((65, 28)));
+            }
+            ColumnType::Json => return sea_query::ColumnType::Json,
             _ => {}
         }
 
         sea_query::ColumnType::from(column_type)
     }
+
+    /// Changes the definition of a column.
+    pub(super) async fn alter_column<T: ColumnTypeMapper + Sync>(
+        &self,
+        mapper: &T,
+        table_name: Identifier,
+        old_field: &Field,
+        new_field: &Field,
+    ) -> crate::db::Result<()> {
+        if old_field.primary_key != new_field.primary_key {
+            return Err(crate::db::DatabaseError::UnsupportedByBackend {
+                feature: "primary key changes of existing columns",
+                backend: "MySQL",
+            });
+        }
+
+        let foreign_key_changed = old_field.foreign_key_differs(new_field);
+        if foreign_key_changed && old_field.has_foreign_key() {
+            for name in self
+                .column_constraint_names(FOREIGN_KEY_NAMES_QUERY, table_name, old_field.name)
+                .await?
+            {
+                let statement = sea_query::ForeignKey::drop()
+                    .name(name)
+                    .table(table_name)
+                    .to_owned();
+                self.execute_schema(statement).await?;
+            }
+        }
+        if old_field.unique && !new_field.unique {
+            for name in self
+                .column_constraint_names(UNIQUE_INDEX_NAMES_QUERY, table_name, old_field.name)
+                .await?
+            {
+                let statement = Index::drop().name(name).table(table_name).to_owned();
+                self.execute_schema(statement).await?;
+            }
+        }
+        self.execute_schema(alter_column_statement(
+            mapper, table_name, old_field, new_field,
+        ))
+        .await?;
+        if foreign_key_changed && let Some(foreign_key) = new_field.as_foreign_key_def(table_name) {
+            self.execute_schema(foreign_key).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the names of the constraints on a single column of a table,
+    /// as returned by one of the `information_schema` queries.
+    ///
+    /// The names are chosen by MySQL when the constraints are created as a
+    /// part of the column or table definition, so they have to be looked up
+    /// in the database. This isn't possible when the statements are only
+    /// recorded, not run, in which case an error is returned.
+    async fn column_constraint_names(
+        &self,
+        query: &'static str,
+        table_name: Identifier,
+        column_name: Identifier,
+    ) -> crate::db::Result<Vec<String>> {
+        if !self.runs_statements() {
+            return Err(crate::db::DatabaseError::UnsupportedByBackend {
+                feature: "removals of constraints without running the migration",
+                backend: "MySQL",
+            });
+        }
+
+        let query = sqlx::query_as::<_, (String,)>(query)
+            .bind(table_name.as_str())
+            .bind(column_name.as_str());
+        let names = match &self.migration_connection {
+            Some(connection) => query.fetch_all(connection.lock().await.connection()).await,
+            None => query.fetch_all(&self.db_connection).await,
+        }?;
+        Ok(names.into_iter().map(|(name,)| name).collect())
+    }
+
+    /// Adds a foreign key constraint to an existing table.
+    pub(super) async fn add_foreign_key_constraint(
+        &self,
+        _table_name: Identifier,
+        foreign_key: ForeignKeyCreateStatement,
+    ) -> crate::db::Result<()> {
+        self.execute_schema(foreign_key).await?;
+        Ok(())
+    }
+
+    /// Removes a foreign key constraint from an existing table.
+    pub(super) async fn remove_foreign_key_constraint(
+        &self,
+        table_name: Identifier,
+        name: Identifier,
+        _columns: &[Identifier],
+    ) -> crate::db::Result<()> {
+        let statement = sea_query::ForeignKey::drop()
+            .name(name.as_str())
+            .table(table_name)
+            .to_owned();
+        self.execute_schema(statement).await?;
+        Ok(())
+    }
 }
 
 impl LikeExprBuilder for DatabaseMySql {
@@ -54,7 +213,7 @@ impl LikeExprBuilder for DatabaseMySql {
 }
 
 /// Builds the MySQL pattern-matching expression for the given case
-/// sensitivity. Shared between the connection and transaction backends.
+/// sensitivity.
 // Returns `Result` to match the fallible `LikeExprBuilder::like_expr` contract,
 // even though this backend can always express the pattern.
 #[expect(clippy::unnecessary_wraps)]
@@ -81,6 +240,232 @@ pub(crate) fn build_like_expr(
     }
 }
 
+impl CastExprBuilder for DatabaseMySql {
+    fn cast_expr(
+        &self,
+        expr: SimpleExpr,
+        column_type: ColumnType,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_cast_expr(expr, column_type)
+    }
+}
+
+/// Builds the MySQL type conversion expression for the given column type.
+///
+/// MySQL only allows a restricted set of types in `CAST`; in particular, all
+/// the integer types are mapped to either `SIGNED` or `UNSIGNED`, which are
+/// 64-bit wide.
+pub(crate) fn build_cast_expr(
+    expr: SimpleExpr,
+    column_type: ColumnType,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    let type_name = match column_type {
+        ColumnType::Boolean
+        | ColumnType::TinyInteger
+        | ColumnType::SmallInteger
+        | ColumnType::Integer
+        | ColumnType::BigInteger => "SIGNED".to_owned(),
+        ColumnType::TinyUnsignedInteger
+        | ColumnType::SmallUnsignedInteger
+        | ColumnType::UnsignedInteger
+        | ColumnType::BigUnsignedInteger => "UNSIGNED".to_owned(),
+        ColumnType::Float => "FLOAT".to_owned(),
+        ColumnType::Double => "DOUBLE".to_owned(),
+        ColumnType::Time => "TIME".to_owned(),
+        ColumnType::Date => "DATE".to_owned(),
+        ColumnType::DateTime | ColumnType::DateTimeWithTimeZone => "DATETIME(6)".to_owned(),
+        ColumnType::Text => "CHAR".to_owned(),
+        ColumnType::String(length) => format!("CHAR({length})"),
+        ColumnType::Blob => "BINARY".to_owned(),
+        ColumnType::Uuid => format!("CHAR({MYSQL_UUID_LENGTH})"),
+        ColumnType::Decimal => "DECIMAL(65, 28)".to_owned(),
+        ColumnType::Json => "JSON".to_owned(),
+        ColumnType::Array(_) => {
+            return Err(QueryBuildingError::UnsupportedExpr(
+                "CAST to an array on MySQL".to_owned(),
+            ));
+        }
+    };
+
+    Ok(sea_query::Func::cast_as(expr, sea_query::Alias::new(type_name)).into())
+}
+
+/// Builds the MySQL expression for the default value of a column.
+///
+/// MySQL only accepts expression defaults for `TEXT`, `BLOB` and `JSON`
+/// columns, so the string literals are emitted as expressions (e.g.
+/// `DEFAULT ('draft')`), which are accepted for all column types.
+pub(crate) fn build_default_expr(default: DefaultValue) -> sea_query::Expr {
+    match default {
+        DefaultValue::String(value) => sea_query::Expr::cust_with_values("?", [value]),
+        _ => default.into(),
+    }
+}
+
+impl ArrayExprBuilder for DatabaseMySql {
+    fn array_expr(
+        &self,
+        lhs: SimpleExpr,
+        rhs: SimpleExpr,
+        operator: ArrayOperator,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_array_expr(lhs, rhs, operator)
+    }
+}
+
+/// MySQL doesn't have an array type, so array expressions always result in
+/// an error.
+pub(crate) fn build_array_expr(
+    _lhs: SimpleExpr,
+    _rhs: SimpleExpr,
+    _operator: ArrayOperator,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    Err(QueryBuildingError::UnsupportedExpr(
+        "array operators on MySQL".to_owned(),
+    ))
+}
+
+impl SearchExprBuilder for DatabaseMySql {
+    fn search_expr(
+        &self,
+        document: SimpleExpr,
+        query: SimpleExpr,
+        config: &str,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_search_expr(document, query, config)
+    }
+
+    fn search_rank_expr(
+        &self,
+        document: SimpleExpr,
+        query: SimpleExpr,
+        config: &str,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_search_expr(document, query, config)
+    }
+}
+
+/// Full-text search is only supported on PostgreSQL, so search expressions
+/// always result in an error.
+pub(crate) fn build_search_expr(
+    _document: SimpleExpr,
+    _query: SimpleExpr,
+    _config: &str,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    Err(QueryBuildingError::UnsupportedExpr(
+        "full-text search on MySQL".to_owned(),
+    ))
+}
+
+impl JsonExprBuilder for DatabaseMySql {
+    fn json_extract_expr(
+        &self,
+        expr: SimpleExpr,
+        path: &[String],
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_json_extract_expr(expr, path)
+    }
+}
+
+/// Builds the MySQL JSON path lookup expression.
+// Returns `Result` to match the fallible `JsonExprBuilder::json_extract_expr`
+// contract, even though this backend can always express the lookup.
+#[expect(clippy::unnecessary_wraps)]
+pub(crate) fn build_json_extract_expr(
+    expr: SimpleExpr,
+    path: &[String],
+) -> Result<SimpleExpr, QueryBuildingError> {
+    let json_extract = sea_query::Func::cust("JSON_EXTRACT")
+        .arg(expr)
+        .arg(to_json_path(path));
+
+    Ok(sea_query::Func::cust("JSON_UNQUOTE")
+        .arg(json_extract)
+        .into())
+}
+
+/// Builds the statement replacing the definition of a column.
+///
+/// The primary key isn't a part of the new definition, as `MODIFY COLUMN`
+/// keeps the existing one and would fail when trying to add it again. For
+/// the same reason, the `UNIQUE` constraint is only included when it's not
+/// there yet.
+fn alter_column_statement<T: ColumnTypeMapper>(
+    mapper: &T,
+    table_name: Identifier,
+    old_field: &Field,
+    new_field: &Field,
+) -> TableAlterStatement {
+    let mut column = ColumnDef::new_with_type(
+        new_field.name,
+        mapper.sea_query_column_type_for(new_field.ty),
+    );
+    if new_field.null {
+        column.null();
+    } else {
+        column.not_null();
+    }
+    if new_field.auto_value {
+        column.auto_increment();
+    }
+    if let Some(default) = new_field.default {
+        column.default(build_default_expr(default));
+    }
+    if new_field.unique && !old_field.unique {
+        column.unique_key();
+    }
+
+    Table::alter()
+        .table(table_name)
+        .modify_column(column)
+        .to_owned()
+}
+
+/// Converts the standard SQL double-quoted identifiers in `expr` to the
+/// backtick-quoted ones MySQL expects by default (without the `ANSI_QUOTES`
+/// SQL mode, double quotes denote string literals in MySQL).
+///
+/// String literals in single quotes are left intact.
+pub(super) fn backtick_quoted_identifiers(expr: &str) -> String {
+    let mut result = String::with_capacity(expr.len());
+    let mut chars = expr.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                result.push(c);
+                // a doubled quote is an escaped quote, so it's fine to handle it as the end of
+                // one literal and the start of another
+                for c in chars.by_ref() {
+                    result.push(c);
+                    if c == '\'' {
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                result.push('`');
+                while let Some(c) = chars.next() {
+                    if c == '"' {
+                        if chars.peek() == Some(&'"') {
+                            chars.next();
+                            result.push('"');
+                            continue;
+                        }
+                        break;
+                    }
+                    if c == '`' {
+                        result.push('`');
+                    }
+                    result.push(c);
+                }
+                result.push('`');
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use sea_query::{Alias, Asterisk, MysqlQueryBuilder, Query};
@@ -95,7 +480,11 @@ mod tests {
         let db_connection = sqlx::mysql::MySqlPoolOptions::new()
             .connect_lazy(&format!("{db_url}/mysql"))
             .expect("lazy pool creation should not fail");
-        DatabaseMySql { db_connection }
+        DatabaseMySql {
+            db_connection,
+            migration_connection: None,
+            sql_recorder: None,
+        }
     }
 
     fn col_expr() -> SimpleExpr {
@@ -165,4 +554,130 @@ mod tests {
             "insensitive path relies on LOWER, not collation: {sql}"
         );
     }
+
+    fn render_cast(column_type: ColumnType) -> String {
+        let expr = build_cast_expr(col_expr(), column_type).unwrap();
+        Query::select().expr(expr).to_string(MysqlQueryBuilder)
+    }
+
+    #[test]
+    fn cast_expr_maps_column_types() {
+        assert_eq!(
+            render_cast(ColumnType::BigInteger),
+            "SELECT CAST(`name` AS SIGNED)"
+        );
+        assert_eq!(
+            render_cast(ColumnType::BigUnsignedInteger),
+            "SELECT CAST(`name` AS UNSIGNED)"
+        );
+        assert_eq!(
+            render_cast(ColumnType::Double),
+            "SELECT CAST(`name` AS DOUBLE)"
+        );
+        assert_eq!(
+            render_cast(ColumnType::String(32)),
+            "SELECT CAST(`name` AS CHAR(32))"
+        );
+        assert_eq!(
+            render_cast(ColumnType::Uuid),
+            "SELECT CAST(`name` AS CHAR(36))"
+        );
+    }
+
+    fn render_json_extract(path: &[&str]) -> String {
+        let path: Vec<String> = path.iter().map(ToString::to_string).collect();
+        let expr = build_json_extract_expr(col_expr(), &path).unwrap();
+        Query::select().expr(expr).to_string(MysqlQueryBuilder)
+    }
+
+    #[test]
+    fn json_extract_expr() {
+        assert_eq!(
+            render_json_extract(&["user", "name"]),
+            r#"SELECT JSON_UNQUOTE(JSON_EXTRACT(`name`, '$.\"user\".\"name\"'))"#
+        );
+    }
+
+    struct TestColumnTypeMapper;
+
+    impl ColumnTypeMapper for TestColumnTypeMapper {
+        fn sea_query_column_type_for(&self, column_type: ColumnType) -> sea_query::ColumnType {
+            sea_query::ColumnType::from(column_type)
+        }
+    }
+
+    #[test]
+    fn alter_column() {
+        let statement = alter_column_statement(
+            &TestColumnTypeMapper,
+            Identifier::new("app__model"),
+            &Field::new(Identifier::new("count"), ColumnType::Integer).null(),
+            &Field::new(Identifier::new("count"), ColumnType::BigInteger).unique(),
+        );
+
+        assert_eq!(
+            statement.to_string(MysqlQueryBuilder),
+            "ALTER TABLE `app__model` MODIFY COLUMN `count` bigint NOT NULL UNIQUE"
+        );
+    }
+
+    #[test]
+    fn alter_column_keeps_auto_increment() {
+        let statement = alter_column_statement(
+            &TestColumnTypeMapper,
+            Identifier::new("app__model"),
+            &Field::new(Identifier::new("id"), ColumnType::Integer)
+                .primary_key()
+                .auto(),
+            &Field::new(Identifier::new("id"), ColumnType::BigInteger)
+                .primary_key()
+                .auto(),
+        );
+
+        assert_eq!(
+            statement.to_string(MysqlQueryBuilder),
+            "ALTER TABLE `app__model` MODIFY COLUMN `id` bigint NOT NULL AUTO_INCREMENT"
+        );
+    }
+
+    #[test]
+    fn alter_column_default() {
+        let statement = alter_column_statement(
+            &TestColumnTypeMapper,
+            Identifier::new("app__model"),
+            &Field::new(Identifier::new("created_at"), ColumnType::DateTime),
+            &Field::new(Identifier::new("created_at"), ColumnType::DateTime)
+                .default(DefaultValue::Expr("CURRENT_TIMESTAMP")),
+        );
+
+        assert_eq!(
+            statement.to_string(MysqlQueryBuilder),
+            "ALTER TABLE `app__model` MODIFY COLUMN `created_at` datetime NOT NULL DEFAULT (CURRENT_TIMESTAMP)"
+        );
+    }
+
+    #[test]
+    fn alter_column_string_default() {
+        let statement = alter_column_statement(
+            &TestColumnTypeMapper,
+            Identifier::new("app__model"),
+            &Field::new(Identifier::new("status"), ColumnType::Text),
+            &Field::new(Identifier::new("status"), ColumnType::Text)
+                .default(DefaultValue::String("it's a draft")),
+        );
+
+        // MySQL only accepts expression defaults for `TEXT` columns
+        assert_eq!(
+            statement.to_string(MysqlQueryBuilder),
+            "ALTER TABLE `app__model` MODIFY COLUMN `status` text NOT NULL DEFAULT ('it\\'s a draft')"
+        );
+    }
+
+    #[test]
+    fn backtick_quoted_identifiers_in_expr() {
+        let expr =
+            backtick_quoted_identifiers(r#""order" IN ('a "b"', 'it''s') AND "we""ird" > 0"#);
+
+        assert_eq!(expr, "`order` IN ('a \"b\"', 'it''s') AND `we\"ird` > 0");
+    }
 }
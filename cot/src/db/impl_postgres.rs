@@ -1,11 +1,21 @@
 //! Database interface implementation – PostgreSQL backend.
 
 use cot::db::query::QueryBuildingError;
-use sea_query::extension::postgres::PgExpr;
-use sea_query::{ExprTrait, LikeExpr, SimpleExpr};
+use sea_query::backend::{QuotedBuilder, TableBuilder};
+use sea_query::extension::postgres::{PgBinOper, PgExpr};
+use sea_query::{
+    Alias, BinOper, ColumnDef, Expr, ExprTrait, ForeignKeyCreateStatement, Func, IntoIden,
+    LikeExpr, SimpleExpr, Table, TableAlterStatement,
+};
 
+use crate::db::migrations::{ColumnTypeMapper, Field};
+use crate::db::query::expr::array::{ArrayExprBuilder, ArrayOperator};
+use crate::db::query::expr::cast::CastExprBuilder;
+use crate::db::query::expr::json::JsonExprBuilder;
 use crate::db::query::expr::like::{CaseSensitivity, LikeExprBuilder, to_sql_like};
+use crate::db::query::expr::search::SearchExprBuilder;
 use crate::db::sea_query_db::{impl_sea_query_db_backend, impl_sea_query_transaction_backend};
+use crate::db::{ColumnType, Identifier};
 
 impl_sea_query_db_backend!(DatabasePostgres: sqlx::postgres::Postgres, sqlx::postgres::PgPool, PostgresRow, PostgresValueRef, sea_query::PostgresQueryBuilder);
 impl_sea_query_transaction_backend!(DatabasePostgres, TransactionPostgres: sqlx::postgres::Postgres, PostgresRow, sea_query::PostgresQueryBuilder);
@@ -16,10 +26,12 @@ impl DatabasePostgres {
         Ok(())
     }
 
-    fn prepare_values(values: &mut sea_query_sqlx::SqlxValues) {
+    #[expect(clippy::unnecessary_wraps)] // to have a unified interface between database impls
+    fn prepare_values(values: &mut sea_query_sqlx::SqlxValues) -> crate::db::Result<()> {
         for value in &mut values.0.0 {
             Self::tinyint_to_smallint(value);
         }
+        Ok(())
     }
 
     /// PostgreSQL does only support 2+ bytes integers, so we need to convert
@@ -43,10 +55,84 @@ impl DatabasePostgres {
     #[expect(clippy::unused_self)] // to have a unified interface between database impls
     pub(super) fn sea_query_column_type_for(
         &self,
-        column_type: crate::db::ColumnType,
+        column_type: ColumnType,
     ) -> sea_query::ColumnType {
         sea_query::ColumnType::from(column_type)
     }
+
+    /// Changes the definition of a column.
+    pub(super) async fn alter_column<T: ColumnTypeMapper + Sync>(
+        &self,
+        mapper: &T,
+        table_name: Identifier,
+        old_field: &Field,
+        new_field: &Field,
+    ) -> crate::db::Result<()> {
+        if old_field.primary_key != new_field.primary_key {
+            return Err(crate::db::DatabaseError::UnsupportedByBackend {
+                feature: "primary key changes of existing columns",
+                backend: "PostgreSQL",
+            });
+        }
+
+        let foreign_key_changed = old_field.foreign_key_differs(new_field);
+        if foreign_key_changed && old_field.has_foreign_key() {
+            self.drop_column_constraints(table_name, old_field.name, ConstraintType::ForeignKey)
+                .await?;
+        }
+        if old_field.unique && !new_field.unique {
+            self.drop_column_constraints(table_name, old_field.name, ConstraintType::Unique)
+                .await?;
+        }
+        if let Some(statement) = alter_column_statement(mapper, table_name, old_field, new_field) {
+            self.execute_schema(statement).await?;
+        }
+        if foreign_key_changed && let Some(foreign_key) = new_field.as_foreign_key_def(table_name) {
+            self.execute_schema(foreign_key).await?;
+        }
+        Ok(())
+    }
+
+    async fn drop_column_constraints(
+        &self,
+        table_name: Identifier,
+        column_name: Identifier,
+        constraint_type: ConstraintType,
+    ) -> crate::db::Result<()> {
+        let sql = drop_column_constraints_sql(table_name, column_name, constraint_type);
+        tracing::debug!("Schema modification: {}", sql);
+        self.raw_with(
+            &sql,
+            sea_query_sqlx::SqlxValues(sea_query::Values(Vec::new())),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Adds a foreign key constraint to an existing table.
+    pub(super) async fn add_foreign_key_constraint(
+        &self,
+        _table_name: Identifier,
+        foreign_key: ForeignKeyCreateStatement,
+    ) -> crate::db::Result<()> {
+        self.execute_schema(foreign_key).await?;
+        Ok(())
+    }
+
+    /// Removes a foreign key constraint from an existing table.
+    pub(super) async fn remove_foreign_key_constraint(
+        &self,
+        table_name: Identifier,
+        name: Identifier,
+        _columns: &[Identifier],
+    ) -> crate::db::Result<()> {
+        let statement = sea_query::ForeignKey::drop()
+            .name(name.as_str())
+            .table(table_name)
+            .to_owned();
+        self.execute_schema(statement).await?;
+        Ok(())
+    }
 }
 
 impl LikeExprBuilder for DatabasePostgres {
@@ -61,7 +147,7 @@ impl LikeExprBuilder for DatabasePostgres {
 }
 
 /// Builds the PostgreSQL pattern-matching expression for the given case
-/// sensitivity. Shared between the connection and transaction backends.
+/// sensitivity.
 // Returns `Result` to match the fallible `LikeExprBuilder::like_expr` contract,
 // even though this backend can always express the pattern.
 #[expect(clippy::unnecessary_wraps)]
@@ -78,11 +164,301 @@ pub(crate) fn build_like_expr(
     }
 }
 
+impl CastExprBuilder for DatabasePostgres {
+    fn cast_expr(
+        &self,
+        expr: SimpleExpr,
+        column_type: ColumnType,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_cast_expr(expr, column_type)
+    }
+}
+
+/// Builds the PostgreSQL type conversion expression for the given column
+/// type.
+///
+/// Unsigned integers are mapped to the next wider signed integer type, the
+/// same way as the column types generated for them.
+// Returns `Result` to match the fallible `CastExprBuilder::cast_expr` contract,
+// even though this backend can always express the conversion.
+#[expect(clippy::unnecessary_wraps)]
+pub(crate) fn build_cast_expr(
+    expr: SimpleExpr,
+    column_type: ColumnType,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    Ok(Func::cast_as(expr, Alias::new(cast_type_name(column_type))).into())
+}
+
+fn cast_type_name(column_type: ColumnType) -> String {
+    match column_type {
+        ColumnType::Boolean => "BOOLEAN".to_owned(),
+        ColumnType::TinyInteger | ColumnType::SmallInteger | ColumnType::TinyUnsignedInteger => {
+            "SMALLINT".to_owned()
+        }
+        ColumnType::Integer | ColumnType::SmallUnsignedInteger => "INTEGER".to_owned(),
+        ColumnType::BigInteger | ColumnType::UnsignedInteger | ColumnType::BigUnsignedInteger => {
+            "BIGINT".to_owned()
+        }
+        ColumnType::Float => "REAL".to_owned(),
+        ColumnType::Double => "DOUBLE PRECISION".to_owned(),
+        ColumnType::Time => "TIME".to_owned(),
+        ColumnType::Date => "DATE".to_owned(),
+        ColumnType::DateTime => "TIMESTAMP".to_owned(),
+        ColumnType::DateTimeWithTimeZone => "TIMESTAMPTZ".to_owned(),
+        ColumnType::Text => "TEXT".to_owned(),
+        ColumnType::String(length) => format!("VARCHAR({length})"),
+        ColumnType::Blob => "BYTEA".to_owned(),
+        ColumnType::Uuid => "UUID".to_owned(),
+        ColumnType::Decimal => "NUMERIC".to_owned(),
+        ColumnType::Json => "JSONB".to_owned(),
+        ColumnType::Array(element_type) => format!("{}[]", cast_type_name(*element_type)),
+    }
+}
+
+impl JsonExprBuilder for DatabasePostgres {
+    fn json_extract_expr(
+        &self,
+        expr: SimpleExpr,
+        path: &[String],
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_json_extract_expr(expr, path)
+    }
+}
+
+/// Builds the PostgreSQL JSON path lookup expression.
+///
+/// All the keys but the last are followed with `->`, which keeps the values as
+/// JSON, and the last one with `->>`, which converts the value to text. An
+/// empty path returns the whole document as text.
+// Returns `Result` to match the fallible `JsonExprBuilder::json_extract_expr`
+// contract, even though this backend can always express the lookup.
+#[expect(clippy::unnecessary_wraps)]
+pub(crate) fn build_json_extract_expr(
+    expr: SimpleExpr,
+    path: &[String],
+) -> Result<SimpleExpr, QueryBuildingError> {
+    let Some((last_key, keys)) = path.split_last() else {
+        return Ok(expr.binary(BinOper::Custom("#>>"), Expr::cust("'{}'")));
+    };
+
+    let object = keys
+        .iter()
+        .fold(expr, |object, key| object.get_json_field(key.as_str()));
+    Ok(object.cast_json_field(last_key.as_str()))
+}
+
+impl ArrayExprBuilder for DatabasePostgres {
+    fn array_expr(
+        &self,
+        lhs: SimpleExpr,
+        rhs: SimpleExpr,
+        operator: ArrayOperator,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_array_expr(lhs, rhs, operator)
+    }
+}
+
+/// Builds the PostgreSQL array comparison expression.
+// Returns `Result` to match the fallible `ArrayExprBuilder::array_expr`
+// contract, even though this backend can always express the comparison.
+#[expect(clippy::unnecessary_wraps)]
+pub(crate) fn build_array_expr(
+    lhs: SimpleExpr,
+    rhs: SimpleExpr,
+    operator: ArrayOperator,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    let operator = match operator {
+        ArrayOperator::Contains => PgBinOper::Contains,
+        ArrayOperator::ContainedBy => PgBinOper::Contained,
+        ArrayOperator::Overlaps => PgBinOper::Overlap,
+    };
+
+    Ok(lhs.binary(operator, rhs))
+}
+
+impl SearchExprBuilder for DatabasePostgres {
+    fn search_expr(
+        &self,
+        document: SimpleExpr,
+        query: SimpleExpr,
+        config: &str,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_search_expr(document, query, config)
+    }
+
+    fn search_rank_expr(
+        &self,
+        document: SimpleExpr,
+        query: SimpleExpr,
+        config: &str,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_search_rank_expr(document, query, config)
+    }
+}
+
+/// Builds the PostgreSQL full-text search match expression.
+// Returns `Result` to match the fallible `SearchExprBuilder::search_expr`
+// contract, even though this backend can always express the search.
+#[expect(clippy::unnecessary_wraps)]
+pub(crate) fn build_search_expr(
+    document: SimpleExpr,
+    query: SimpleExpr,
+    config: &str,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    Ok(ts_vector(document, config).binary(PgBinOper::Matches, ts_query(query, config)))
+}
+
+/// Builds the PostgreSQL full-text search ranking expression.
+// Returns `Result` to match the fallible `SearchExprBuilder::search_rank_expr`
+// contract, even though this backend can always express the ranking.
+#[expect(clippy::unnecessary_wraps)]
+pub(crate) fn build_search_rank_expr(
+    document: SimpleExpr,
+    query: SimpleExpr,
+    config: &str,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    Ok(Func::cust("ts_rank")
+        .arg(ts_vector(document, config))
+        .arg(ts_query(query, config))
+        .into())
+}
+
+fn ts_vector(document: SimpleExpr, config: &str) -> SimpleExpr {
+    Func::cust("to_tsvector")
+        .arg(ts_config(config))
+        .arg(document)
+        .into()
+}
+
+fn ts_query(query: SimpleExpr, config: &str) -> SimpleExpr {
+    Func::cust("websearch_to_tsquery")
+        .arg(ts_config(config))
+        .arg(query)
+        .into()
+}
+
+/// The text search configuration is inlined in the query rather than bound as
+/// a parameter, so that the search expressions can use expression indexes
+/// such as `CREATE INDEX ... USING GIN (to_tsvector('english', body))`.
+fn ts_config(config: &str) -> SimpleExpr {
+    SimpleExpr::Constant(config.into()).cast_as(Alias::new("regconfig"))
+}
+
+/// Builds the statement changing the type, nullability, default value and
+/// uniqueness of a column, or returns `None` if none of these differ between
+/// the fields.
+///
+/// Removing the `UNIQUE` constraint and changing the foreign key are handled
+/// separately, as the names of the existing constraints have to be looked up
+/// first.
+fn alter_column_statement<T: ColumnTypeMapper>(
+    mapper: &T,
+    table_name: Identifier,
+    old_field: &Field,
+    new_field: &Field,
+) -> Option<TableAlterStatement> {
+    let mut statement = Table::alter().table(table_name).to_owned();
+    let mut changed = false;
+
+    if old_field.ty != new_field.ty {
+        let column_type = mapper.sea_query_column_type_for(new_field.ty);
+        let mut type_name = String::new();
+        sea_query::PostgresQueryBuilder.prepare_column_type(&column_type, &mut type_name);
+        // some types (e.g. `text` and `integer`) can't be converted implicitly,
+        // so the existing values are always cast explicitly
+        statement.modify_column(ColumnDef::new_with_type(new_field.name, column_type).using(
+            Func::cast_as(Expr::col(new_field.name), Alias::new(type_name)),
+        ));
+        changed = true;
+    }
+    if old_field.null != new_field.null {
+        let mut column = ColumnDef::new(new_field.name);
+        if new_field.null {
+            column.null();
+        } else {
+            column.not_null();
+        }
+        statement.modify_column(column);
+        changed = true;
+    }
+    if old_field.default != new_field.default {
+        // setting the default to `NULL` is the same as dropping it
+        let default = new_field
+            .default
+            .map_or_else(|| Expr::cust("NULL"), Expr::from);
+        statement.modify_column(ColumnDef::new(new_field.name).default(default));
+        changed = true;
+    }
+    if !old_field.unique && new_field.unique {
+        statement.modify_column(ColumnDef::new(new_field.name).unique_key());
+        changed = true;
+    }
+
+    changed.then_some(statement)
+}
+
+/// The type of the constraints removed by [`drop_column_constraints_sql`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ConstraintType {
+    ForeignKey,
+    Unique,
+}
+
+impl ConstraintType {
+    /// The value of `pg_constraint.contype` for the constraint type.
+    fn pg_contype(self) -> char {
+        match self {
+            Self::ForeignKey => 'f',
+            Self::Unique => 'u',
+        }
+    }
+}
+
+/// Builds the statement dropping all single-column constraints of the given
+/// type on a column.
+///
+/// The constraints are usually created as a part of the column or table
+/// definition, so their names are chosen by PostgreSQL (and may have been
+/// truncated or deduplicated). Because of that, the statement looks them up in
+/// the `pg_constraint` catalog when it's run instead of guessing them.
+fn drop_column_constraints_sql(
+    table_name: Identifier,
+    column_name: Identifier,
+    constraint_type: ConstraintType,
+) -> String {
+    let mut table = String::new();
+    sea_query::PostgresQueryBuilder.prepare_iden(&table_name.into_iden(), &mut table);
+    let table = quote_literal(&table);
+    let column = quote_literal(column_name.as_str());
+    let contype = constraint_type.pg_contype();
+
+    format!(
+        "DO $cot$ \
+         DECLARE constraint_name name; \
+         BEGIN \
+         FOR constraint_name IN \
+         SELECT con.conname FROM pg_constraint con \
+         JOIN pg_attribute att ON att.attrelid = con.conrelid AND att.attnum = con.conkey[1] \
+         WHERE con.conrelid = {table}::regclass AND con.contype = '{contype}' \
+         AND cardinality(con.conkey) = 1 AND att.attname = {column} \
+         LOOP \
+         EXECUTE format('ALTER TABLE %s DROP CONSTRAINT %I', {table}::regclass, constraint_name); \
+         END LOOP; \
+         END $cot$"
+    )
+}
+
+/// Quotes `value` as an SQL string literal.
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
 #[cfg(test)]
 mod tests {
     use sea_query::{Alias, Asterisk, PostgresQueryBuilder, Query};
 
     use super::*;
+    use crate::db::migrations::DefaultValue;
     use crate::test::DEFAULT_POSTGRES_TEST_URL;
 
     #[expect(clippy::unused_async)]
@@ -92,11 +468,15 @@ mod tests {
         let db_connection = sqlx::postgres::PgPoolOptions::new()
             .connect_lazy(&format!("{db_url}/postgres"))
             .expect("lazy pool creation should not fail");
-        DatabasePostgres { db_connection }
+        DatabasePostgres {
+            db_connection,
+            migration_connection: None,
+            sql_recorder: None,
+        }
     }
 
     fn col_expr() -> SimpleExpr {
-        sea_query::Expr::col(Alias::new("name"))
+        Expr::col(Alias::new("name"))
     }
 
     fn render(expr: SimpleExpr) -> String {
@@ -152,4 +532,182 @@ mod tests {
             .unwrap();
         assert_where(expr, "\"name\" LIKE E'100\\\\%off\\\\_sale'");
     }
+
+    fn render_cast(column_type: ColumnType) -> String {
+        let expr = build_cast_expr(col_expr(), column_type).unwrap();
+        Query::select().expr(expr).to_string(PostgresQueryBuilder)
+    }
+
+    #[test]
+    fn cast_expr_maps_column_types() {
+        assert_eq!(
+            render_cast(ColumnType::BigInteger),
+            "SELECT CAST(\"name\" AS BIGINT)"
+        );
+        assert_eq!(
+            render_cast(ColumnType::UnsignedInteger),
+            "SELECT CAST(\"name\" AS BIGINT)"
+        );
+        assert_eq!(
+            render_cast(ColumnType::Double),
+            "SELECT CAST(\"name\" AS DOUBLE PRECISION)"
+        );
+        assert_eq!(
+            render_cast(ColumnType::String(32)),
+            "SELECT CAST(\"name\" AS VARCHAR(32))"
+        );
+        assert_eq!(
+            render_cast(ColumnType::Uuid),
+            "SELECT CAST(\"name\" AS UUID)"
+        );
+        assert_eq!(
+            render_cast(ColumnType::Json),
+            "SELECT CAST(\"name\" AS JSONB)"
+        );
+        assert_eq!(
+            render_cast(ColumnType::Array(&ColumnType::Integer)),
+            "SELECT CAST(\"name\" AS INTEGER[])"
+        );
+    }
+
+    #[test]
+    fn array_expr_uses_array_operators() {
+        let rhs = || SimpleExpr::from(vec![1_i32, 2]);
+
+        assert_where(
+            build_array_expr(col_expr(), rhs(), ArrayOperator::Contains).unwrap(),
+            "\"name\" @> ARRAY [1,2]",
+        );
+        assert_where(
+            build_array_expr(col_expr(), rhs(), ArrayOperator::ContainedBy).unwrap(),
+            "\"name\" <@ ARRAY [1,2]",
+        );
+        assert_where(
+            build_array_expr(col_expr(), rhs(), ArrayOperator::Overlaps).unwrap(),
+            "\"name\" && ARRAY [1,2]",
+        );
+    }
+
+    #[test]
+    fn search_expr_matches_tsvector_with_tsquery() {
+        let expr = build_search_expr(col_expr(), "rust".into(), "english").unwrap();
+
+        assert_where(
+            expr,
+            "to_tsvector(CAST('english' AS regconfig), \"name\") @@ websearch_to_tsquery(CAST('english' AS regconfig), 'rust')",
+        );
+    }
+
+    #[test]
+    fn search_rank_expr_uses_ts_rank() {
+        let expr = build_search_rank_expr(col_expr(), "rust".into(), "simple").unwrap();
+
+        assert_eq!(
+            Query::select().expr(expr).to_string(PostgresQueryBuilder),
+            "SELECT ts_rank(to_tsvector(CAST('simple' AS regconfig), \"name\"), websearch_to_tsquery(CAST('simple' AS regconfig), 'rust'))"
+        );
+    }
+
+    fn render_json_extract(path: &[&str]) -> String {
+        let path: Vec<String> = path.iter().map(ToString::to_string).collect();
+        let expr = build_json_extract_expr(col_expr(), &path).unwrap();
+        Query::select().expr(expr).to_string(PostgresQueryBuilder)
+    }
+
+    #[test]
+    fn json_extract_expr() {
+        assert_eq!(
+            render_json_extract(&["user", "name"]),
+            "SELECT (\"name\" -> 'user') ->> 'name'"
+        );
+        assert_eq!(render_json_extract(&[]), "SELECT \"name\" #>> ('{}')");
+    }
+
+    struct TestColumnTypeMapper;
+
+    impl ColumnTypeMapper for TestColumnTypeMapper {
+        fn sea_query_column_type_for(&self, column_type: ColumnType) -> sea_query::ColumnType {
+            sea_query::ColumnType::from(column_type)
+        }
+    }
+
+    fn render_alter_column(old_field: Field, new_field: Field) -> Option<String> {
+        alter_column_statement(
+            &TestColumnTypeMapper,
+            Identifier::new("app__model"),
+            &old_field,
+            &new_field,
+        )
+        .map(|statement| statement.to_string(PostgresQueryBuilder))
+    }
+
+    #[test]
+    fn alter_column_type() {
+        let sql = render_alter_column(
+            Field::new(Identifier::new("count"), ColumnType::Text),
+            Field::new(Identifier::new("count"), ColumnType::BigInteger),
+        );
+
+        assert_eq!(
+            sql.unwrap(),
+            "ALTER TABLE \"app__model\" ALTER COLUMN \"count\" TYPE bigint USING CAST(\"count\" AS bigint)"
+        );
+    }
+
+    #[test]
+    fn alter_column_null_and_unique() {
+        let sql = render_alter_column(
+            Field::new(Identifier::new("name"), ColumnType::Text).unique(),
+            Field::new(Identifier::new("name"), ColumnType::Text).null(),
+        );
+
+        // the unique constraint is dropped separately, by looking up its name
+        assert_eq!(
+            sql.unwrap(),
+            "ALTER TABLE \"app__model\" ALTER COLUMN \"name\" DROP NOT NULL"
+        );
+
+        let sql = render_alter_column(
+            Field::new(Identifier::new("name"), ColumnType::Text).null(),
+            Field::new(Identifier::new("name"), ColumnType::Text).unique(),
+        );
+
+        assert_eq!(
+            sql.unwrap(),
+            "ALTER TABLE \"app__model\" ALTER COLUMN \"name\" SET NOT NULL, ADD UNIQUE (\"name\")"
+        );
+    }
+
+    #[test]
+    fn alter_column_default() {
+        let sql = render_alter_column(
+            Field::new(Identifier::new("count"), ColumnType::Integer),
+            Field::new(Identifier::new("count"), ColumnType::Integer).default(DefaultValue::Int(1)),
+        );
+
+        assert_eq!(
+            sql.unwrap(),
+            "ALTER TABLE \"app__model\" ALTER COLUMN \"count\" SET DEFAULT 1"
+        );
+
+        let sql = render_alter_column(
+            Field::new(Identifier::new("count"), ColumnType::Integer).default(DefaultValue::Int(1)),
+            Field::new(Identifier::new("count"), ColumnType::Integer),
+        );
+
+        assert_eq!(
+            sql.unwrap(),
+            "ALTER TABLE \"app__model\" ALTER COLUMN \"count\" SET DEFAULT NULL"
+        );
+    }
+
+    #[test]
+    fn alter_column_unchanged() {
+        let sql = render_alter_column(
+            Field::new(Identifier::new("id"), ColumnType::Integer),
+            Field::new(Identifier::new("id"), ColumnType::Integer).primary_key(),
+        );
+
+        assert_eq!(sql, None);
+    }
 }
@@ -1,31 +1,111 @@
 //! Database interface implementation – SQLite backend.
 
 use sea_query::extension::sqlite::SqliteExpr;
-use sea_query::{ExprTrait, LikeExpr, SimpleExpr};
+use sea_query::{
+    Alias, ColumnDef, Expr, ExprTrait, ForeignKeyAction, ForeignKeyCreateStatement, Index,
+    LikeExpr, Query, SimpleExpr, Table, TableCreateStatement,
+};
 use sea_query_sqlx::SqlxValues;
 
+use crate::db::migrations::{ColumnTypeMapper, Field, MigrationEngineError};
 use crate::db::query::QueryBuildingError;
+use crate::db::query::expr::array::{ArrayExprBuilder, ArrayOperator};
+use crate::db::query::expr::cast::CastExprBuilder;
+use crate::db::query::expr::json::{JsonExprBuilder, to_json_path};
 use crate::db::query::expr::like::{
     CaseSensitivity, LIKE_ESCAPE_CHAR, LikeExprBuilder, to_sql_like,
 };
-use crate::db::sea_query_db::{impl_sea_query_db_backend, impl_sea_query_transaction_backend};
+use crate::db::query::expr::search::SearchExprBuilder;
+use crate::db::sea_query_db::{
+    MigrationConnection, SqlRecorder, impl_sea_query_db_backend, impl_sea_query_transaction_backend,
+};
+use crate::db::{ColumnType, DatabaseError, Identifier};
 
 impl_sea_query_db_backend!(DatabaseSqlite: sqlx::sqlite::Sqlite, sqlx::sqlite::SqlitePool, SqliteRow, SqliteValueRef, sea_query::SqliteQueryBuilder);
 impl_sea_query_transaction_backend!(DatabaseSqlite, TransactionSqlite: sqlx::sqlite::Sqlite, SqliteRow, sea_query::SqliteQueryBuilder);
 
+const FOREIGN_KEYS_OFF: &str = "PRAGMA foreign_keys = OFF";
+const FOREIGN_KEYS_ON: &str = "PRAGMA foreign_keys = ON";
+
 impl DatabaseSqlite {
     async fn init(&self) -> crate::db::Result<()> {
-        self.raw("PRAGMA foreign_keys = ON").await?;
+        self.raw(FOREIGN_KEYS_ON).await?;
         Ok(())
     }
 
+    /// Starts the transaction a migration is applied in on the pinned
+    /// connection.
+    ///
+    /// Foreign key checks are disabled for the duration of the transaction,
+    /// as they can't be disabled inside it, and the tables altered by the
+    /// migration might have to be rebuilt. The tables rebuilt in the
+    /// transaction are checked for foreign key violations before it's
+    /// committed instead.
+    pub(super) async fn begin_migration_transaction(&self) -> crate::db::Result<()> {
+        self.change_pinned_session(FOREIGN_KEYS_OFF).await?;
+        self.begin_pinned_transaction().await
+    }
+
+    /// Commits the transaction started with
+    /// [`Self::begin_migration_transaction`], or rolls it back if the
+    /// migration violates any foreign key constraints.
+    pub(super) async fn commit_migration_transaction(&self) -> crate::db::Result<()> {
+        if self.runs_statements() {
+            let mut connection = self.lock_migration_connection().await;
+            let mut violating_table = None;
+            for table_name in connection.rebuilt_tables().to_vec() {
+                if violates_foreign_keys(connection.connection(), &table_name).await? {
+                    violating_table = Some(table_name);
+                    break;
+                }
+            }
+            drop(connection);
+
+            if let Some(table_name) = violating_table {
+                self.rollback_migration_transaction().await?;
+                return Err(DatabaseError::MigrationError(MigrationEngineError::Custom(
+                    format!(
+                        "the migration would violate foreign key constraints of table `{table_name}`"
+                    ),
+                )));
+            }
+        }
+
+        self.commit_pinned_transaction().await?;
+        self.restore_pinned_session(FOREIGN_KEYS_ON).await
+    }
+
+    /// Rolls back the transaction started with
+    /// [`Self::begin_migration_transaction`].
+    pub(super) async fn rollback_migration_transaction(&self) -> crate::db::Result<()> {
+        self.rollback_pinned_transaction().await?;
+        self.restore_pinned_session(FOREIGN_KEYS_ON).await
+    }
+
     async fn raw(&self, sql: &str) -> crate::db::Result<crate::db::StatementResult> {
         self.raw_with(sql, SqlxValues(sea_query::Values(Vec::new())))
             .await
     }
 
-    fn prepare_values(_values: &mut SqlxValues) {
+    #[cfg(not(any(feature = "uuid", feature = "postgres")))]
+    #[expect(clippy::unnecessary_wraps)] // to have a unified interface between database impls
+    fn prepare_values(_values: &mut SqlxValues) -> crate::db::Result<()> {
         // No changes are needed for SQLite
+        Ok(())
+    }
+
+    #[cfg(any(feature = "uuid", feature = "postgres"))]
+    #[cfg_attr(not(feature = "postgres"), expect(clippy::unnecessary_wraps))]
+    fn prepare_values(values: &mut SqlxValues) -> crate::db::Result<()> {
+        for value in &mut values.0.0 {
+            // arrays can only be used with PostgreSQL
+            #[cfg(feature = "postgres")]
+            crate::db::fields::reject_array_value(value, "SQLite")?;
+            // UUIDs are stored as text, but they would be bound as binary values
+            #[cfg(feature = "uuid")]
+            crate::db::fields::uuid_to_string(value);
+        }
+        Ok(())
     }
 
     #[expect(clippy::unnecessary_wraps)] // to have a unified interface between database impls
@@ -37,10 +117,173 @@ impl DatabaseSqlite {
     #[expect(clippy::unused_self)] // to have a unified interface between database impls
     pub(super) fn sea_query_column_type_for(
         &self,
-        column_type: crate::db::ColumnType,
+        column_type: ColumnType,
     ) -> sea_query::ColumnType {
-        sea_query::ColumnType::from(column_type)
+        match column_type {
+            // SQLite doesn't have dedicated types for these, so they are
+            // stored as text
+            ColumnType::Uuid | ColumnType::Decimal | ColumnType::Json => {
+                sea_query::ColumnType::Text
+            }
+            _ => sea_query::ColumnType::from(column_type),
+        }
+    }
+
+    /// Changes the definition of a column.
+    pub(super) async fn alter_column<T: ColumnTypeMapper + Sync>(
+        &self,
+        mapper: &T,
+        table_name: Identifier,
+        old_field: &Field,
+        new_field: &Field,
+    ) -> crate::db::Result<()> {
+        let change = TableChange::AlterColumn(Box::new(AlteredColumn {
+            old_field,
+            new_column: new_field.as_column_def(mapper),
+            new_foreign_key: new_field.as_foreign_key_def(table_name),
+        }));
+        self.rebuild_table(table_name, change).await
     }
+
+    /// Adds a `CHECK` constraint to an existing table.
+    pub(super) async fn add_check_constraint(
+        &self,
+        table_name: Identifier,
+        name: Identifier,
+        expr: &str,
+    ) -> crate::db::Result<()> {
+        self.rebuild_table(table_name, TableChange::AddCheck { name, expr })
+            .await
+    }
+
+    /// Removes a `CHECK` constraint from an existing table.
+    pub(super) async fn remove_check_constraint(
+        &self,
+        table_name: Identifier,
+        name: Identifier,
+    ) -> crate::db::Result<()> {
+        self.rebuild_table(table_name, TableChange::RemoveCheck { name })
+            .await
+    }
+
+    /// Adds a foreign key constraint to an existing table.
+    pub(super) async fn add_foreign_key_constraint(
+        &self,
+        table_name: Identifier,
+        foreign_key: ForeignKeyCreateStatement,
+    ) -> crate::db::Result<()> {
+        self.rebuild_table(
+            table_name,
+            TableChange::AddForeignKey(Box::new(foreign_key)),
+        )
+        .await
+    }
+
+    /// Removes a foreign key constraint from an existing table.
+    ///
+    /// SQLite doesn't keep the names of the foreign key constraints, so the
+    /// constraint is identified by its columns instead.
+    pub(super) async fn remove_foreign_key_constraint(
+        &self,
+        table_name: Identifier,
+        _name: Identifier,
+        columns: &[Identifier],
+    ) -> crate::db::Result<()> {
+        self.rebuild_table(table_name, TableChange::RemoveForeignKey { columns })
+            .await
+    }
+
+    /// Applies a change SQLite can't make with `ALTER TABLE`.
+    ///
+    /// This follows the procedure described in
+    /// <https://www.sqlite.org/lang_altertable.html#otheralter>: a new table
+    /// is created with the change applied, the data is copied over, and the
+    /// new table replaces the old one. The definitions of the remaining
+    /// columns, constraints and indexes are read from the schema of the
+    /// existing table, so they are preserved.
+    async fn rebuild_table(
+        &self,
+        table_name: Identifier,
+        change: TableChange<'_>,
+    ) -> crate::db::Result<()> {
+        let sql_recorder = self.sql_recorder.as_ref();
+        if let Some(connection) = &self.migration_connection {
+            return rebuild_table_with(
+                &mut *connection.lock().await,
+                table_name,
+                change,
+                sql_recorder,
+            )
+            .await;
+        }
+
+        let mut connection = MigrationConnection::new(self.db_connection.acquire().await?);
+        rebuild_table_with(&mut connection, table_name, change, sql_recorder).await
+    }
+}
+
+/// Rebuilds the table on the given connection with foreign key checks
+/// disabled.
+///
+/// If the connection is in the transaction of a migration, the checks are
+/// already disabled, and the rebuild is just a part of that transaction; the
+/// table is checked for foreign key violations when the transaction is
+/// committed. Otherwise, the rebuild runs (and is checked) in its own
+/// transaction, and the checks are enabled back whether it succeeds or not;
+/// if that fails, the connection is closed instead of being returned to the
+/// pool. The statements run are passed to the SQL recorder, if there is one.
+async fn rebuild_table_with(
+    connection: &mut MigrationConnection<sqlx::sqlite::Sqlite>,
+    table_name: Identifier,
+    change: TableChange<'_>,
+    sql_recorder: Option<&SqlRecorder>,
+) -> crate::db::Result<()> {
+    if connection.is_in_transaction() {
+        rebuild_table_on(connection.connection(), table_name, change, sql_recorder).await?;
+        connection.mark_table_rebuilt(table_name.as_str());
+        return Ok(());
+    }
+
+    // Dropping the old table would otherwise run the `ON DELETE` actions of
+    // the tables referencing it. This can't be changed inside a transaction.
+    if let Some(sql_recorder) = sql_recorder {
+        sql_recorder.record(FOREIGN_KEYS_OFF);
+    }
+    connection.change_session(FOREIGN_KEYS_OFF).await?;
+    let result =
+        rebuild_table_in_transaction(connection.connection(), table_name, change, sql_recorder)
+            .await;
+    if let Some(sql_recorder) = sql_recorder {
+        sql_recorder.record(FOREIGN_KEYS_ON);
+    }
+    connection.restore_session(FOREIGN_KEYS_ON).await?;
+
+    result
+}
+
+async fn rebuild_table_in_transaction(
+    connection: &mut sqlx::SqliteConnection,
+    table_name: Identifier,
+    change: TableChange<'_>,
+    sql_recorder: Option<&SqlRecorder>,
+) -> crate::db::Result<()> {
+    if let Some(sql_recorder) = sql_recorder {
+        sql_recorder.record("BEGIN");
+    }
+    let mut transaction = sqlx::Connection::begin(connection).await?;
+
+    rebuild_table_on(&mut transaction, table_name, change, sql_recorder).await?;
+    if violates_foreign_keys(&mut transaction, table_name.as_str()).await? {
+        return Err(DatabaseError::MigrationError(MigrationEngineError::Custom(
+            format!("altering table `{table_name}` would violate foreign key constraints"),
+        )));
+    }
+
+    if let Some(sql_recorder) = sql_recorder {
+        sql_recorder.record("COMMIT");
+    }
+    transaction.commit().await?;
+    Ok(())
 }
 
 impl LikeExprBuilder for DatabaseSqlite {
@@ -55,7 +298,7 @@ impl LikeExprBuilder for DatabaseSqlite {
 }
 
 /// Builds the SQLite pattern-matching expression for the given case
-/// sensitivity. Shared between the connection and transaction backends.
+/// sensitivity.
 // Returns `Result` to match the fallible `LikeExprBuilder::like_expr` contract,
 // even though this backend can always express the pattern.
 #[expect(clippy::unnecessary_wraps)]
@@ -77,6 +320,604 @@ pub(crate) fn build_like_expr(
     }
 }
 
+impl CastExprBuilder for DatabaseSqlite {
+    fn cast_expr(
+        &self,
+        expr: SimpleExpr,
+        column_type: ColumnType,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_cast_expr(expr, column_type)
+    }
+}
+
+/// Builds the SQLite type conversion expression for the given column type.
+///
+/// SQLite only has a handful of storage classes, so the column type is mapped
+/// to the storage class used to store it.
+pub(crate) fn build_cast_expr(
+    expr: SimpleExpr,
+    column_type: ColumnType,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    let type_name = match column_type {
+        ColumnType::Boolean
+        | ColumnType::TinyInteger
+        | ColumnType::SmallInteger
+        | ColumnType::Integer
+        | ColumnType::BigInteger
+        | ColumnType::TinyUnsignedInteger
+        | ColumnType::SmallUnsignedInteger
+        | ColumnType::UnsignedInteger
+        | ColumnType::BigUnsignedInteger => "INTEGER",
+        ColumnType::Float | ColumnType::Double => "REAL",
+        ColumnType::Time
+        | ColumnType::Date
+        | ColumnType::DateTime
+        | ColumnType::DateTimeWithTimeZone
+        | ColumnType::Text
+        | ColumnType::String(_)
+        | ColumnType::Uuid
+        | ColumnType::Decimal
+        | ColumnType::Json => "TEXT",
+        ColumnType::Blob => "BLOB",
+        ColumnType::Array(_) => {
+            return Err(QueryBuildingError::UnsupportedExpr(
+                "CAST to an array on SQLite".to_owned(),
+            ));
+        }
+    };
+
+    Ok(sea_query::Func::cast_as(expr, Alias::new(type_name)).into())
+}
+
+impl JsonExprBuilder for DatabaseSqlite {
+    fn json_extract_expr(
+        &self,
+        expr: SimpleExpr,
+        path: &[String],
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_json_extract_expr(expr, path)
+    }
+}
+
+/// Builds the SQLite JSON path lookup expression.
+///
+/// `json_extract` returns values of the matching SQL type (e.g. `INTEGER` for
+/// JSON numbers), so the result is cast to `TEXT` to match the other
+/// backends.
+// Returns `Result` to match the fallible `JsonExprBuilder::json_extract_expr`
+// contract, even though this backend can always express the lookup.
+#[expect(clippy::unnecessary_wraps)]
+pub(crate) fn build_json_extract_expr(
+    expr: SimpleExpr,
+    path: &[String],
+) -> Result<SimpleExpr, QueryBuildingError> {
+    let json_extract = sea_query::Func::cust("json_extract")
+        .arg(expr)
+        .arg(to_json_path(path));
+
+    Ok(sea_query::Func::cast_as(json_extract, Alias::new("TEXT")).into())
+}
+
+impl ArrayExprBuilder for DatabaseSqlite {
+    fn array_expr(
+        &self,
+        lhs: SimpleExpr,
+        rhs: SimpleExpr,
+        operator: ArrayOperator,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_array_expr(lhs, rhs, operator)
+    }
+}
+
+/// SQLite doesn't have an array type, so array expressions always result in
+/// an error.
+pub(crate) fn build_array_expr(
+    _lhs: SimpleExpr,
+    _rhs: SimpleExpr,
+    _operator: ArrayOperator,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    Err(QueryBuildingError::UnsupportedExpr(
+        "array operators on SQLite".to_owned(),
+    ))
+}
+
+impl SearchExprBuilder for DatabaseSqlite {
+    fn search_expr(
+        &self,
+        document: SimpleExpr,
+        query: SimpleExpr,
+        config: &str,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_search_expr(document, query, config)
+    }
+
+    fn search_rank_expr(
+        &self,
+        document: SimpleExpr,
+        query: SimpleExpr,
+        config: &str,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        build_search_expr(document, query, config)
+    }
+}
+
+/// Full-text search is only supported on PostgreSQL, so search expressions
+/// always result in an error.
+pub(crate) fn build_search_expr(
+    _document: SimpleExpr,
+    _query: SimpleExpr,
+    _config: &str,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    Err(QueryBuildingError::UnsupportedExpr(
+        "full-text search on SQLite".to_owned(),
+    ))
+}
+
+/// A change applied to a table by rebuilding it.
+enum TableChange<'a> {
+    AlterColumn(Box<AlteredColumn<'a>>),
+    AddCheck { name: Identifier, expr: &'a str },
+    RemoveCheck { name: Identifier },
+    AddForeignKey(Box<ForeignKeyCreateStatement>),
+    RemoveForeignKey { columns: &'a [Identifier] },
+}
+
+/// The new definition of an altered column.
+struct AlteredColumn<'a> {
+    old_field: &'a Field,
+    new_column: ColumnDef,
+    new_foreign_key: Option<ForeignKeyCreateStatement>,
+}
+
+async fn rebuild_table_on(
+    connection: &mut sqlx::SqliteConnection,
+    table_name: Identifier,
+    change: TableChange<'_>,
+    sql_recorder: Option<&SqlRecorder>,
+) -> crate::db::Result<()> {
+    let mut schema = TableSchema::read(&mut *connection, table_name.as_str()).await?;
+    let (altered_column, new_foreign_key) = schema.apply_change(table_name, change)?;
+
+    let new_table_name = Alias::new(format!("{table_name}__new"));
+    let mut create = schema.create_statement(new_table_name.clone(), table_name, altered_column);
+    if let Some(mut foreign_key) = new_foreign_key {
+        create.foreign_key(&mut foreign_key);
+    }
+    let columns: Vec<_> = schema
+        .columns
+        .iter()
+        .map(|column| Alias::new(&column.name))
+        .collect();
+    let copy = Query::insert()
+        .into_table(new_table_name.clone())
+        .columns(columns.clone())
+        .select_from(Query::select().columns(columns).from(table_name).to_owned())
+        .expect("the number of columns is the same in both tables")
+        .to_owned();
+
+    let mut statements = vec![
+        create.to_string(sea_query::SqliteQueryBuilder),
+        copy.to_string(sea_query::SqliteQueryBuilder),
+        Table::drop()
+            .table(table_name)
+            .to_string(sea_query::SqliteQueryBuilder),
+        Table::rename()
+            .table(new_table_name, table_name)
+            .to_string(sea_query::SqliteQueryBuilder),
+    ];
+    statements.extend(schema.index_definitions);
+    for sql in statements {
+        tracing::debug!("Schema modification: {}", sql);
+        if let Some(sql_recorder) = sql_recorder {
+            sql_recorder.record(&sql);
+        }
+        sqlx::query(sqlx::AssertSqlSafe(sql))
+            .execute(&mut *connection)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Returns whether any row of the table violates its foreign key constraints.
+async fn violates_foreign_keys(
+    connection: &mut sqlx::SqliteConnection,
+    table_name: &str,
+) -> crate::db::Result<bool> {
+    let violations: Vec<(String,)> =
+        sqlx::query_as("SELECT \"table\" FROM pragma_foreign_key_check(?1)")
+            .bind(table_name)
+            .fetch_all(&mut *connection)
+            .await?;
+    Ok(!violations.is_empty())
+}
+
+/// The schema of an existing SQLite table, as reported by the `PRAGMA`
+/// functions.
+#[derive(Debug)]
+struct TableSchema {
+    columns: Vec<ColumnSchema>,
+    autoincrement: bool,
+    unique_constraints: Vec<Vec<String>>,
+    foreign_keys: Vec<ForeignKeySchema>,
+    check_constraints: Vec<CheckSchema>,
+    /// The `CREATE INDEX` statements of the indexes created explicitly on the
+    /// table.
+    index_definitions: Vec<String>,
+}
+
+#[derive(Debug)]
+struct ColumnSchema {
+    name: String,
+    ty: String,
+    not_null: bool,
+    default: Option<String>,
+    /// The 1-based position of the column in the primary key, or 0 if it's
+    /// not part of the primary key.
+    primary_key: i64,
+}
+
+#[derive(Debug)]
+struct CheckSchema {
+    name: String,
+    expr: String,
+}
+
+#[derive(Debug)]
+struct ForeignKeySchema {
+    from_columns: Vec<String>,
+    to_table: String,
+    to_columns: Vec<String>,
+    on_update: String,
+    on_delete: String,
+}
+
+impl TableSchema {
+    async fn read(
+        connection: &mut sqlx::SqliteConnection,
+        table_name: &str,
+    ) -> crate::db::Result<Self> {
+        let columns: Vec<(String, String, bool, Option<String>, i64)> = sqlx::query_as(
+            "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1) ORDER BY cid",
+        )
+        .bind(table_name)
+        .fetch_all(&mut *connection)
+        .await?;
+        let columns = columns
+            .into_iter()
+            .map(|(name, ty, not_null, default, primary_key)| ColumnSchema {
+                name,
+                ty,
+                not_null,
+                default,
+                primary_key,
+            })
+            .collect();
+
+        let table_definition: Option<(String,)> =
+            sqlx::query_as("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1")
+                .bind(table_name)
+                .fetch_optional(&mut *connection)
+                .await?;
+        let table_definition = table_definition.map(|(sql,)| sql).unwrap_or_default();
+        let autoincrement = table_definition
+            .to_ascii_uppercase()
+            .contains("AUTOINCREMENT");
+        let check_constraints = parse_check_constraints(&table_definition);
+
+        let unique_indexes: Vec<(String,)> = sqlx::query_as(
+            "SELECT name FROM pragma_index_list(?1) WHERE origin = 'u' ORDER BY seq",
+        )
+        .bind(table_name)
+        .fetch_all(&mut *connection)
+        .await?;
+        let mut unique_constraints = Vec::with_capacity(unique_indexes.len());
+        for (index_name,) in unique_indexes {
+            let index_columns: Vec<(String,)> =
+                sqlx::query_as("SELECT name FROM pragma_index_info(?1) ORDER BY seqno")
+                    .bind(index_name)
+                    .fetch_all(&mut *connection)
+                    .await?;
+            unique_constraints.push(index_columns.into_iter().map(|(name,)| name).collect());
+        }
+
+        let foreign_key_columns: Vec<(i64, String, String, Option<String>, String, String)> =
+            sqlx::query_as(
+                "SELECT id, \"table\", \"from\", \"to\", on_update, on_delete \
+                 FROM pragma_foreign_key_list(?1) ORDER BY id, seq",
+            )
+            .bind(table_name)
+            .fetch_all(&mut *connection)
+            .await?;
+        let mut foreign_keys: Vec<ForeignKeySchema> = Vec::new();
+        let mut last_id = None;
+        for (id, to_table, from, to, on_update, on_delete) in foreign_key_columns {
+            if last_id != Some(id) {
+                last_id = Some(id);
+                foreign_keys.push(ForeignKeySchema {
+                    from_columns: Vec::new(),
+                    to_table,
+                    to_columns: Vec::new(),
+                    on_update,
+                    on_delete,
+                });
+            }
+            let foreign_key = foreign_keys.last_mut().expect("pushed above");
+            foreign_key.from_columns.push(from);
+            foreign_key.to_columns.extend(to);
+        }
+
+        let index_definitions: Vec<(String,)> = sqlx::query_as(
+            "SELECT sql FROM sqlite_master WHERE type = 'index' AND tbl_name = ?1 AND sql IS NOT NULL",
+        )
+        .bind(table_name)
+        .fetch_all(&mut *connection)
+        .await?;
+
+        Ok(Self {
+            columns,
+            autoincrement,
+            unique_constraints,
+            foreign_keys,
+            check_constraints,
+            index_definitions: index_definitions.into_iter().map(|(sql,)| sql).collect(),
+        })
+    }
+
+    /// Applies the change to the schema. Returns the new definition of the
+    /// altered column and the added foreign key, if any, as these aren't a
+    /// part of the schema read from the existing table.
+    fn apply_change<'a>(
+        &mut self,
+        table_name: Identifier,
+        change: TableChange<'a>,
+    ) -> crate::db::Result<(Option<AlteredColumn<'a>>, Option<ForeignKeyCreateStatement>)> {
+        let altered_column = match change {
+            TableChange::AlterColumn(altered_column) => {
+                if !self.has_column(altered_column.old_field.name.as_str()) {
+                    return Err(DatabaseError::MigrationError(MigrationEngineError::Custom(
+                        format!(
+                            "column `{}` not found in table `{table_name}`",
+                            altered_column.old_field.name
+                        ),
+                    )));
+                }
+                Some(*altered_column)
+            }
+            TableChange::AddCheck { name, expr } => {
+                self.check_constraints.push(CheckSchema {
+                    name: name.as_str().to_owned(),
+                    expr: expr.to_owned(),
+                });
+                None
+            }
+            TableChange::RemoveCheck { name } => {
+                let count = self.check_constraints.len();
+                self.check_constraints
+                    .retain(|check| check.name != name.as_str());
+                if self.check_constraints.len() == count {
+                    return Err(DatabaseError::MigrationError(MigrationEngineError::Custom(
+                        format!("constraint `{name}` not found in table `{table_name}`"),
+                    )));
+                }
+                None
+            }
+            TableChange::AddForeignKey(foreign_key) => return Ok((None, Some(*foreign_key))),
+            TableChange::RemoveForeignKey { columns } => {
+                let count = self.foreign_keys.len();
+                self.foreign_keys.retain(|foreign_key| {
+                    !foreign_key
+                        .from_columns
+                        .iter()
+                        .map(String::as_str)
+                        .eq(columns.iter().map(Identifier::as_str))
+                });
+                if self.foreign_keys.len() == count {
+                    return Err(DatabaseError::MigrationError(MigrationEngineError::Custom(
+                        format!(
+                            "foreign key on columns {columns:?} not found in table `{table_name}`"
+                        ),
+                    )));
+                }
+                None
+            }
+        };
+
+        Ok((altered_column, None))
+    }
+
+    fn has_column(&self, name: &str) -> bool {
+        self.columns.iter().any(|column| column.name == name)
+    }
+
+    /// Builds a statement creating a copy of this table, with the definition
+    /// of the altered column, if any, replaced with its new definition.
+    fn create_statement(
+        &self,
+        new_table_name: Alias,
+        table_name: Identifier,
+        altered_column: Option<AlteredColumn<'_>>,
+    ) -> TableCreateStatement {
+        let (altered_column, new_column, new_foreign_key) = match altered_column {
+            Some(AlteredColumn {
+                old_field,
+                new_column,
+                new_foreign_key,
+            }) => (
+                Some(old_field.name.as_str()),
+                Some(new_column),
+                new_foreign_key,
+            ),
+            None => (None, None, None),
+        };
+        let mut create = Table::create().table(new_table_name).to_owned();
+
+        let mut primary_key: Vec<_> = self
+            .columns
+            .iter()
+            .filter(|column| column.primary_key > 0)
+            .collect();
+        primary_key.sort_by_key(|column| column.primary_key);
+
+        for column in &self.columns {
+            if let Some(new_column) = &new_column
+                && Some(column.name.as_str()) == altered_column
+            {
+                create.col(new_column.clone());
+                continue;
+            }
+
+            let mut def = ColumnDef::new_with_type(
+                Alias::new(&column.name),
+                sea_query::ColumnType::custom(Alias::new(&column.ty)),
+            );
+            if column.not_null {
+                def.not_null();
+            } else {
+                def.null();
+            }
+            if primary_key.len() == 1 && column.primary_key > 0 {
+                def.primary_key();
+                if self.autoincrement {
+                    def.auto_increment();
+                }
+            }
+            if let Some(default) = &column.default {
+                def.default(Expr::cust(default.clone()));
+            }
+            create.col(def);
+        }
+
+        if primary_key.len() > 1 {
+            let mut index = Index::create();
+            for column in primary_key {
+                index.col(Alias::new(&column.name));
+            }
+            create.primary_key(&mut index);
+        }
+
+        for columns in &self.unique_constraints {
+            // the column's own `UNIQUE` constraint is part of its new definition
+            if columns.len() == 1 && Some(columns[0].as_str()) == altered_column {
+                continue;
+            }
+            let mut index = Index::create();
+            index.unique();
+            for column in columns {
+                index.col(Alias::new(column));
+            }
+            create.index(&mut index);
+        }
+
+        for foreign_key in &self.foreign_keys {
+            if foreign_key
+                .from_columns
+                .iter()
+                .any(|column| Some(column.as_str()) == altered_column)
+            {
+                continue;
+            }
+            let mut def = ForeignKeyCreateStatement::new();
+            def.from_tbl(table_name)
+                .to_tbl(Alias::new(&foreign_key.to_table))
+                .on_update(foreign_key_action(&foreign_key.on_update))
+                .on_delete(foreign_key_action(&foreign_key.on_delete));
+            for column in &foreign_key.from_columns {
+                def.from_col(Alias::new(column));
+            }
+            for column in &foreign_key.to_columns {
+                def.to_col(Alias::new(column));
+            }
+            create.foreign_key(&mut def);
+        }
+        if let Some(mut foreign_key) = new_foreign_key {
+            create.foreign_key(&mut foreign_key);
+        }
+
+        for check in &self.check_constraints {
+            create.check((Alias::new(&check.name), Expr::cust(check.expr.clone())));
+        }
+
+        create
+    }
+}
+
+/// Returns the named `CHECK` constraints defined in given `CREATE TABLE`
+/// statement.
+///
+/// SQLite doesn't expose the `CHECK` constraints through its `PRAGMA`
+/// functions, so they are extracted from the table definition instead. Only
+/// the table constraints in the form generated by Cot, i.e.
+/// `CONSTRAINT "name" CHECK (expr)`, are recognized.
+fn parse_check_constraints(table_definition: &str) -> Vec<CheckSchema> {
+    const PREFIX: &str = "CONSTRAINT \"";
+
+    let mut constraints = Vec::new();
+    let mut rest = table_definition;
+    while let Some(start) = rest.find(PREFIX) {
+        rest = &rest[start + PREFIX.len()..];
+        let Some(name_end) = rest.find('"') else {
+            break;
+        };
+        let name = &rest[..name_end];
+        rest = &rest[name_end + 1..];
+
+        let Some(expr) = rest
+            .trim_start()
+            .strip_prefix("CHECK")
+            .and_then(|check| check.trim_start().strip_prefix('('))
+        else {
+            continue;
+        };
+        let Some(expr_end) = find_closing_paren(expr) else {
+            break;
+        };
+        constraints.push(CheckSchema {
+            name: name.to_owned(),
+            expr: expr[..expr_end].to_owned(),
+        });
+        rest = &expr[expr_end + 1..];
+    }
+
+    constraints
+}
+
+/// Returns the position of the parenthesis closing an expression, skipping
+/// over the parentheses inside nested expressions, string literals and quoted
+/// identifiers.
+fn find_closing_paren(sql: &str) -> Option<usize> {
+    let mut depth = 0_usize;
+    let mut quote = None;
+    for (i, ch) in sql.char_indices() {
+        if let Some(quote_char) = quote {
+            // escaped quotes (e.g. `''`) just close and reopen the literal
+            if ch == quote_char {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '\'' | '"' | '`' => quote = Some(ch),
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(i),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn foreign_key_action(action: &str) -> ForeignKeyAction {
+    match action {
+        "CASCADE" => ForeignKeyAction::Cascade,
+        "SET NULL" => ForeignKeyAction::SetNull,
+        "SET DEFAULT" => ForeignKeyAction::SetDefault,
+        "RESTRICT" => ForeignKeyAction::Restrict,
+        _ => ForeignKeyAction::NoAction,
+    }
+}
+
 fn to_sqlite_glob(glob: &str) -> String {
     let mut escaped = String::with_capacity(glob.len());
     let mut chars = glob.chars();
@@ -143,7 +984,7 @@ mod tests {
     }
 
     fn col_expr() -> SimpleExpr {
-        sea_query::Expr::col(Alias::new("name"))
+        Expr::col(Alias::new("name"))
     }
 
     fn render(expr: SimpleExpr) -> String {
@@ -231,4 +1072,70 @@ mod tests {
             .unwrap();
         assert_where(expr, "LOWER(\"name\") LIKE '100\\%off' ESCAPE '\\'");
     }
+
+    #[test]
+    fn parse_check_constraints_from_table_definition() {
+        let constraints = parse_check_constraints(
+            "CREATE TABLE \"t\" ( \"id\" integer NOT NULL PRIMARY KEY, \"price\" integer NOT NULL, \
+             \"name\" text NOT NULL, CONSTRAINT \"t_price_positive\" CHECK (price > 0), \
+             CONSTRAINT \"t_name\" CHECK (name IN ('(', 'a''b') AND length(name) > 1) )",
+        );
+
+        assert_eq!(constraints.len(), 2);
+        assert_eq!(constraints[0].name, "t_price_positive");
+        assert_eq!(constraints[0].expr, "price > 0");
+        assert_eq!(constraints[1].name, "t_name");
+        assert_eq!(
+            constraints[1].expr,
+            "name IN ('(', 'a''b') AND length(name) > 1"
+        );
+    }
+
+    #[test]
+    fn parse_check_constraints_ignores_other_constraints() {
+        let constraints = parse_check_constraints(
+            "CREATE TABLE \"t\" ( \"id\" integer NOT NULL, CONSTRAINT \"t_pk\" PRIMARY KEY (\"id\") )",
+        );
+
+        assert!(constraints.is_empty());
+    }
+
+    fn render_cast(column_type: ColumnType) -> String {
+        let expr = build_cast_expr(col_expr(), column_type).unwrap();
+        Query::select().expr(expr).to_string(SqliteQueryBuilder)
+    }
+
+    #[test]
+    fn cast_expr_maps_column_types() {
+        assert_eq!(
+            render_cast(ColumnType::BigInteger),
+            "SELECT CAST(\"name\" AS INTEGER)"
+        );
+        assert_eq!(
+            render_cast(ColumnType::Double),
+            "SELECT CAST(\"name\" AS REAL)"
+        );
+        assert_eq!(
+            render_cast(ColumnType::String(32)),
+            "SELECT CAST(\"name\" AS TEXT)"
+        );
+        assert_eq!(
+            render_cast(ColumnType::Json),
+            "SELECT CAST(\"name\" AS TEXT)"
+        );
+    }
+
+    fn render_json_extract(path: &[&str]) -> String {
+        let path: Vec<String> = path.iter().map(ToString::to_string).collect();
+        let expr = build_json_extract_expr(col_expr(), &path).unwrap();
+        Query::select().expr(expr).to_string(SqliteQueryBuilder)
+    }
+
+    #[test]
+    fn json_extract_expr() {
+        assert_eq!(
+            render_json_extract(&["user", "name"]),
+            r#"SELECT CAST(json_extract("name", '$."user"."name"') AS TEXT)"#
+        );
+    }
 }
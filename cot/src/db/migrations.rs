@@ -11,14 +11,17 @@ use std::{fmt, io};
 pub use cot_macros::migration_op;
 use sea_query::{ColumnDef, StringLen};
 use thiserror::Error;
-use tracing::{Level, info};
+use tracing::{Level, info, warn};
 
 use crate::db::migrations::sorter::{MigrationSorter, MigrationSorterError};
 use crate::db::relations::{ForeignKeyOnDeletePolicy, ForeignKeyOnUpdatePolicy};
-use crate::db::{Auto, ColumnType, Database, DatabaseField, Identifier, Result, model, query};
+use crate::db::{
+    Auto, ColumnType, Database, DatabaseDialect, DatabaseField, Identifier, Result, model, query,
+};
 use crate::utils::cli::{StatusType, write_status_msg};
 
 const MIGRATION_ZERO_NAME: &str = "zero";
+const CUSTOM_OPERATION_SQL: &str = "-- custom operation; its SQL cannot be rendered";
 
 /// An error that occurred while running migrations.
 #[derive(Debug, Error)]
@@ -72,6 +75,9 @@ pub enum MigrationEngineError {
 #[derive(Debug)]
 pub struct MigrationEngine {
     migrations: Vec<MigrationWrapper>,
+    /// The squashed migrations whose replaced migrations are all present as
+    /// well; the replaced migrations are applied instead of them.
+    squashed_migrations: Vec<MigrationWrapper>,
 }
 
 impl MigrationEngine {
@@ -121,9 +127,56 @@ impl MigrationEngine {
         Self::from_wrapper(migrations)
     }
 
-    fn from_wrapper(mut migrations: Vec<MigrationWrapper>) -> Result<Self> {
+    fn from_wrapper(migrations: Vec<MigrationWrapper>) -> Result<Self> {
+        let (mut migrations, squashed_migrations) = Self::split_squashed_migrations(migrations)?;
         Self::sort_migrations(&mut migrations)?;
-        Ok(Self { migrations })
+        Ok(Self {
+            migrations,
+            squashed_migrations,
+        })
+    }
+
+    /// Separates the squashed migrations whose replaced migrations are still
+    /// present from the rest of the migrations.
+    ///
+    /// A squashed migration is only applied when the migrations it replaces
+    /// have been removed; otherwise, they are applied instead, so that the
+    /// databases they have been partially applied to can be migrated.
+    fn split_squashed_migrations(
+        migrations: Vec<MigrationWrapper>,
+    ) -> Result<(Vec<MigrationWrapper>, Vec<MigrationWrapper>)> {
+        let names: HashSet<(String, String)> = migrations
+            .iter()
+            .map(|migration| (migration.app_name().to_owned(), migration.name().to_owned()))
+            .collect();
+
+        let mut remaining = Vec::with_capacity(migrations.len());
+        let mut squashed = Vec::new();
+        for migration in migrations {
+            let present_count = migration
+                .replaces()
+                .iter()
+                .filter(|&&name| {
+                    names.contains(&(migration.app_name().to_owned(), name.to_owned()))
+                })
+                .count();
+
+            if present_count == 0 {
+                remaining.push(migration);
+            } else if present_count == migration.replaces().len() {
+                squashed.push(migration);
+            } else {
+                return Err(MigrationEngineError::Custom(format!(
+                    "squashed migration {}::{} replaces migrations that are only partially \
+                    present; either keep all of them or remove all of them",
+                    migration.app_name(),
+                    migration.name()
+                ))
+                .into());
+            }
+        }
+
+        Ok((remaining, squashed))
     }
 
     /// Sorts the migrations by app name and migration name to ensure that the
@@ -190,15 +243,7 @@ impl MigrationEngine {
             .await?;
 
         for migration in &self.migrations {
-            let span = tracing::span!(
-                Level::TRACE,
-                "apply_migration",
-                app_name = migration.app_name(),
-                migration_name = migration.name()
-            );
-            let _enter = span.enter();
-
-            if Self::is_migration_applied(database, migration).await? {
+            if self.is_applied(database, migration).await? {
                 info!(
                     "Migration {} for app {} is already applied",
                     migration.name(),
@@ -207,17 +252,7 @@ impl MigrationEngine {
                 continue;
             }
 
-            info!(
-                "Applying migration {} for app {}",
-                migration.name(),
-                migration.app_name()
-            );
-
-            for operation in migration.operations() {
-                operation.forwards(database).await?;
-            }
-
-            Self::mark_migration_applied(database, migration).await?;
+            self.apply_migration(database, migration, false).await?;
         }
 
         Ok(())
@@ -239,28 +274,14 @@ impl MigrationEngine {
         output: &mut impl Write,
     ) -> Result<()> {
         info!("Rolling back migrations");
-        // TODO: use a DB transaction here
 
         let rollback_plan = self.rollback_plan(migration_name, app_name)?;
 
         for migration in rollback_plan {
-            if !Self::is_migration_applied(database, migration).await? {
+            if !self.is_applied(database, migration).await? {
                 continue;
             }
 
-            let span = tracing::span!(
-                Level::TRACE,
-                "rollback_migration",
-                app_name = migration.app_name(),
-                migration_name = migration.name()
-            );
-            let _enter = span.enter();
-
-            info!(
-                "Rolling back migration {} for app {}",
-                migration.name(),
-                migration.app_name()
-            );
             write_status_msg(
                 output,
                 StatusType::RollingBack,
@@ -268,13 +289,7 @@ impl MigrationEngine {
             )
             .map_err(MigrationEngineError::Io)?;
 
-            for operation in migration.operations().iter().rev() {
-                operation.backwards(database).await?;
-            }
-
-            if Self::is_migration_applied(database, migration).await? {
-                Self::mark_migration_unapplied(database, migration).await?;
-            }
+            self.unapply_migration(database, migration, false).await?;
             write_status_msg(
                 output,
                 StatusType::RolledBack,
@@ -309,7 +324,7 @@ impl MigrationEngine {
 
         let mut entries = Vec::new();
         for migration in rollback_plan {
-            let applied = Self::is_migration_applied(database, migration).await?;
+            let applied = self.is_applied(database, migration).await?;
             entries.push((migration, applied));
         }
 
@@ -319,6 +334,324 @@ impl MigrationEngine {
         Ok(())
     }
 
+    /// Returns the migrations that have to be applied or rolled back to
+    /// migrate the database to the target.
+    ///
+    /// If the target migration is not applied, the plan applies it together
+    /// with all the migrations it depends on. Otherwise, the plan rolls back
+    /// the migrations following it, the same way as
+    /// [`rollback`](Self::rollback) does.
+    ///
+    /// This method will also create the `cot__migrations` table if it does
+    /// not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target migration cannot be found, if the
+    /// migration graph cannot be generated, or if the applied migration
+    /// state cannot be read from the database.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Database;
+    /// use cot::db::migrations::{MigrationDirection, MigrationEngine, MigrationTarget};
+    /// use cot::test::TestMigration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// let engine = MigrationEngine::new([TestMigration::new("app", "m_0001_initial", [], [])])?;
+    /// let database = Database::new("sqlite::memory:").await?;
+    ///
+    /// let plan = engine.plan(&database, MigrationTarget::Latest).await?;
+    /// assert_eq!(plan.direction(), MigrationDirection::Forwards);
+    /// assert_eq!(plan.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn plan(
+        &self,
+        database: &Database,
+        target: MigrationTarget<'_>,
+    ) -> Result<MigrationPlan<'_>> {
+        CREATE_APPLIED_MIGRATIONS_MIGRATION
+            .forwards(database)
+            .await?;
+
+        let (direction, candidates) = match target {
+            MigrationTarget::Latest => (
+                MigrationDirection::Forwards,
+                self.migrations.iter().collect(),
+            ),
+            MigrationTarget::Migration {
+                app_name,
+                migration_name,
+            } => match self.target_index(migration_name, app_name)? {
+                Some(index) if !self.is_applied(database, &self.migrations[index]).await? => {
+                    (MigrationDirection::Forwards, self.forwards_plan(index)?)
+                }
+                _ => (
+                    MigrationDirection::Backwards,
+                    self.rollback_plan(migration_name, app_name)?,
+                ),
+            },
+        };
+
+        let mut migrations = Vec::new();
+        for migration in candidates {
+            let applied = self.is_applied(database, migration).await?;
+            if applied == (direction == MigrationDirection::Backwards) {
+                migrations.push(migration);
+            }
+        }
+
+        Ok(MigrationPlan {
+            direction,
+            migrations,
+        })
+    }
+
+    /// Migrates the database to the target, applying or rolling back the
+    /// migrations returned by [`plan`](Self::plan).
+    ///
+    /// If `fake` is `true`, the migrations are only marked as applied or not
+    /// applied, without running their operations. This is useful when the
+    /// database schema has been changed by other means.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plan cannot be created, if any of the
+    /// migrations fail to apply or roll back, or if the status messages
+    /// cannot be written to the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Database;
+    /// use cot::db::migrations::{MigrationEngine, MigrationTarget};
+    /// use cot::test::TestMigration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// let engine = MigrationEngine::new([
+    ///     TestMigration::new("app", "m_0001_initial", [], []),
+    ///     TestMigration::new("app", "m_0002_second", [], []),
+    /// ])?;
+    /// let database = Database::new("sqlite::memory:").await?;
+    ///
+    /// let target = MigrationTarget::Migration {
+    ///     app_name: "app",
+    ///     migration_name: "0001",
+    /// };
+    /// engine
+    ///     .migrate(&database, target, false, &mut std::io::sink())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn migrate(
+        &self,
+        database: &Database,
+        target: MigrationTarget<'_>,
+        fake: bool,
+        output: &mut impl Write,
+    ) -> Result<()> {
+        let plan = self.plan(database, target).await?;
+        let (in_progress_status, done_status) = match plan.direction {
+            MigrationDirection::Forwards => (StatusType::Applying, StatusType::Applied),
+            MigrationDirection::Backwards => (StatusType::RollingBack, StatusType::RolledBack),
+        };
+        let fake_suffix = if fake { " (fake)" } else { "" };
+
+        for migration in &plan.migrations {
+            let message = format!(
+                "Migration {}::{}{fake_suffix}",
+                migration.app_name(),
+                migration.name()
+            );
+            write_status_msg(output, in_progress_status, &message)
+                .map_err(MigrationEngineError::Io)?;
+
+            match plan.direction {
+                MigrationDirection::Forwards => {
+                    self.apply_migration(database, migration, fake).await?;
+                }
+                MigrationDirection::Backwards => {
+                    self.unapply_migration(database, migration, fake).await?;
+                }
+            }
+
+            write_status_msg(output, done_status, &message).map_err(MigrationEngineError::Io)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the list of the migrations, grouped by app, along with whether
+    /// they are applied.
+    ///
+    /// This method will also create the `cot__migrations` table if it does
+    /// not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the applied migration state cannot be read from the
+    /// database, or if the list cannot be written to the output.
+    pub async fn show_migrations(
+        &self,
+        database: &Database,
+        output: &mut impl Write,
+    ) -> Result<()> {
+        CREATE_APPLIED_MIGRATIONS_MIGRATION
+            .forwards(database)
+            .await?;
+
+        let mut app_names: Vec<&str> = Vec::new();
+        for migration in &self.migrations {
+            if !app_names.contains(&migration.app_name()) {
+                app_names.push(migration.app_name());
+            }
+        }
+
+        for app_name in app_names {
+            writeln!(output, "{app_name}").map_err(MigrationEngineError::Io)?;
+            for migration in self
+                .migrations
+                .iter()
+                .filter(|migration| migration.app_name() == app_name)
+            {
+                let mark = if self.is_applied(database, migration).await? {
+                    'X'
+                } else {
+                    ' '
+                };
+                writeln!(output, "  [{mark}] {}", migration.name())
+                    .map_err(MigrationEngineError::Io)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renders the SQL run when applying and rolling back the given migration
+    /// of the given app on the given database backend.
+    ///
+    /// This doesn't need a connection to a database; the SQL is rendered for
+    /// an empty database with only the dependencies of the migration applied.
+    /// Custom operations can't be rendered, so they are represented by SQL
+    /// comments instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the migration cannot be found, or if any of its
+    /// operations cannot be rendered for the backend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::DatabaseDialect;
+    /// use cot::db::migrations::{Field, MigrationEngine, Operation};
+    /// use cot::db::{DatabaseField, Identifier};
+    /// use cot::test::TestMigration;
+    ///
+    /// const OPERATION: Operation = Operation::create_model()
+    ///     .table_name(Identifier::new("app__item"))
+    ///     .fields(&[
+    ///         Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+    ///             .primary_key()
+    ///             .auto(),
+    ///     ])
+    ///     .build();
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// let engine =
+    ///     MigrationEngine::new([TestMigration::new("app", "m_0001_initial", [], [OPERATION])])?;
+    ///
+    /// let sql = engine
+    ///     .migration_sql("app", "0001", DatabaseDialect::Sqlite)
+    ///     .await?;
+    /// assert_eq!(
+    ///     sql.backwards(),
+    ///     [
+    ///         "PRAGMA foreign_keys = OFF",
+    ///         "BEGIN",
+    ///         "DROP TABLE \"app__item\"",
+    ///         "COMMIT",
+    ///         "PRAGMA foreign_keys = ON",
+    ///     ]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn migration_sql(
+        &self,
+        app_name: &str,
+        migration_name: &str,
+        dialect: DatabaseDialect,
+    ) -> Result<MigrationSql> {
+        let index = self
+            .target_index(migration_name, app_name)?
+            .ok_or_else(|| {
+                MigrationEngineError::Custom(format!(
+                    "Migration name `{MIGRATION_ZERO_NAME}` does not refer to any migration"
+                ))
+            })?;
+        let migration = &self.migrations[index];
+
+        let (database, sql_recorder) = Database::offline(dialect)?;
+
+        // The SQL of some of the operations depends on the existing schema (such
+        // as the table rebuilds in SQLite), so the dependencies are applied first
+        for dependency in self.forwards_plan(index)? {
+            if std::ptr::eq(dependency, migration) {
+                continue;
+            }
+            for operation in dependency.operations() {
+                if !operation.is_custom() {
+                    operation.forwards(&database).await?;
+                }
+            }
+        }
+        sql_recorder.take();
+
+        let pinned_database = database.pin_connection().await?;
+        let in_transaction =
+            migration.atomic() && pinned_database.begin_migration_transaction().await?;
+        for operation in migration.operations() {
+            if operation.is_custom() {
+                sql_recorder.record(CUSTOM_OPERATION_SQL);
+            } else {
+                operation.forwards(&pinned_database).await?;
+            }
+        }
+        if in_transaction {
+            pinned_database.commit_migration_transaction().await?;
+        }
+        let forwards = sql_recorder.take();
+
+        let in_transaction =
+            migration.atomic() && pinned_database.begin_migration_transaction().await?;
+        for operation in migration.operations().iter().rev() {
+            if operation.is_custom() {
+                sql_recorder.record(CUSTOM_OPERATION_SQL);
+            } else {
+                operation.backwards(&pinned_database).await?;
+            }
+        }
+        if in_transaction {
+            pinned_database.commit_migration_transaction().await?;
+        }
+        let backwards = sql_recorder.take();
+        drop(pinned_database);
+
+        database.close().await?;
+        Ok(MigrationSql {
+            forwards,
+            backwards,
+        })
+    }
+
     fn write_dry_run_output(
         output: &mut impl Write,
         migration_name: &str,
@@ -377,35 +710,7 @@ impl MigrationEngine {
         migration_name: &str,
         app_name: &str,
     ) -> Result<Vec<&'a MigrationWrapper>> {
-        let migration_name = migration_name.trim();
-        let target_index = if migration_name.eq_ignore_ascii_case(MIGRATION_ZERO_NAME) {
-            if !self
-                .migrations
-                .iter()
-                .any(|migration| migration.app_name() == app_name)
-            {
-                return Err(MigrationEngineError::Custom(format!(
-                    "No migrations found for app {app_name}"
-                ))
-                .into());
-            }
-            None
-        } else {
-            Some(
-                self.migrations
-                    .iter()
-                    .position(|migration| {
-                        migration.app_name() == app_name
-                            && expand_migration_file_name(migration.name())
-                                .contains(&migration_name)
-                    })
-                    .ok_or_else(|| {
-                        MigrationEngineError::Custom(format!(
-                            "Migration with file name {migration_name} not found for app {app_name}"
-                        ))
-                    })?,
-            )
-        };
+        let target_index = self.target_index(migration_name, app_name)?;
 
         let mut rollback_indices = HashSet::new();
         // Seed later migrations in the same app, then include migrations from
@@ -450,45 +755,464 @@ impl MigrationEngine {
             .collect())
     }
 
-    async fn is_migration_applied(
-        database: &Database,
-        migration: &MigrationWrapper,
-    ) -> Result<bool> {
-        query!(
-            AppliedMigration,
-            $app == migration.app_name() && $name == migration.name()
-        )
-        .exists(database)
-        .await
+    /// Returns the migrations that have to be applied, in order, so that the
+    /// migration at `target_index` and all the migrations it depends on are
+    /// applied.
+    fn forwards_plan(&self, target_index: usize) -> Result<Vec<&MigrationWrapper>> {
+        let app_name = self.migrations[target_index].app_name();
+        let mut forwards_indices = (0..=target_index)
+            .filter(|&index| self.migrations[index].app_name() == app_name)
+            .collect::<HashSet<_>>();
+
+        let graph = MigrationSorter::generate_graph(&self.migrations).map_err(|e| {
+            MigrationEngineError::Custom(format!("Failed to generate migration graph: {e}"))
+        })?;
+        let mut dependencies = vec![Vec::new(); graph.vertex_num()];
+        for index in 0..graph.vertex_num() {
+            for &dependent_index in graph.get_edges(index) {
+                dependencies[dependent_index].push(index);
+            }
+        }
+
+        let mut queue = forwards_indices.iter().copied().collect::<VecDeque<_>>();
+        while let Some(index) = queue.pop_front() {
+            for &dependency_index in &dependencies[index] {
+                if forwards_indices.insert(dependency_index) {
+                    queue.push_back(dependency_index);
+                }
+            }
+        }
+
+        Ok(self
+            .migrations
+            .iter()
+            .enumerate()
+            .filter_map(|(index, migration)| forwards_indices.contains(&index).then_some(migration))
+            .collect())
+    }
+
+    /// Returns the index of the migration with given name in given app, or
+    /// `None` if the name is `zero`.
+    fn target_index(&self, migration_name: &str, app_name: &str) -> Result<Option<usize>> {
+        let migration_name = migration_name.trim();
+        if migration_name.eq_ignore_ascii_case(MIGRATION_ZERO_NAME) {
+            if !self
+                .migrations
+                .iter()
+                .any(|migration| migration.app_name() == app_name)
+            {
+                return Err(MigrationEngineError::Custom(format!(
+                    "No migrations found for app {app_name}"
+                ))
+                .into());
+            }
+            return Ok(None);
+        }
+
+        let index = self
+            .migrations
+            .iter()
+            .position(|migration| {
+                migration.app_name() == app_name
+                    && expand_migration_file_name(migration.name()).contains(&migration_name)
+            })
+            .ok_or_else(|| {
+                MigrationEngineError::Custom(format!(
+                    "Migration with file name {migration_name} not found for app {app_name}"
+                ))
+            })?;
+        Ok(Some(index))
     }
 
-    async fn mark_migration_applied(
+    /// Runs the operations of the migration and marks it as applied. If `fake`
+    /// is `true`, the migration is only marked as applied.
+    async fn apply_migration(
+        &self,
         database: &Database,
         migration: &MigrationWrapper,
+        fake: bool,
     ) -> Result<()> {
-        let mut applied_migration = AppliedMigration {
-            id: Auto::auto(),
-            app: migration.app_name().to_string(),
-            name: migration.name().to_string(),
-            applied: chrono::Utc::now().into(),
-        };
+        let span = tracing::span!(
+            Level::TRACE,
+            "apply_migration",
+            app_name = migration.app_name(),
+            migration_name = migration.name()
+        );
+        let _enter = span.enter();
+
+        if Self::any_replaced_migration_applied(database, migration).await? {
+            return Err(MigrationEngineError::Custom(format!(
+                "squashed migration {}::{} cannot be applied, because only some of the \
+                migrations it replaces are applied; apply the rest of them first",
+                migration.app_name(),
+                migration.name()
+            ))
+            .into());
+        }
 
-        database.insert(&mut applied_migration).await?;
-        Ok(())
+        info!(
+            "Applying migration {} for app {}",
+            migration.name(),
+            migration.app_name()
+        );
+
+        let database = database.pin_connection().await?;
+        let in_transaction = migration.atomic() && database.begin_migration_transaction().await?;
+        let result = async {
+            if !fake {
+                for operation in migration.operations() {
+                    operation.forwards(&database).await?;
+                }
+            }
+
+            self.mark_applied(&database, migration).await
+        }
+        .await;
+
+        Self::end_migration_transaction(&database, in_transaction, result).await
     }
 
-    async fn mark_migration_unapplied(
+    /// Runs the backwards operations of the migration and marks it as not
+    /// applied. If `fake` is `true`, the migration is only marked as not
+    /// applied.
+    async fn unapply_migration(
+        &self,
         database: &Database,
         migration: &MigrationWrapper,
+        fake: bool,
     ) -> Result<()> {
-        query!(AppliedMigration, $app == migration.app_name() && $name == migration.name())
-            .delete(database)
-            .await?;
-        Ok(())
-    }
-}
+        let span = tracing::span!(
+            Level::TRACE,
+            "rollback_migration",
+            app_name = migration.app_name(),
+            migration_name = migration.name()
+        );
+        let _enter = span.enter();
 
-/// Resolves the possible migration names that can be used to refer to a
+        info!(
+            "Rolling back migration {} for app {}",
+            migration.name(),
+            migration.app_name()
+        );
+
+        let database = database.pin_connection().await?;
+        let in_transaction = migration.atomic() && database.begin_migration_transaction().await?;
+        let result = async {
+            if !fake {
+                for operation in migration.operations().iter().rev() {
+                    operation.backwards(&database).await?;
+                }
+            }
+
+            self.mark_unapplied(&database, migration).await
+        }
+        .await;
+
+        Self::end_migration_transaction(&database, in_transaction, result).await
+    }
+
+    /// Commits the transaction of the migration if it has been applied or
+    /// rolled back successfully, and rolls it back otherwise.
+    async fn end_migration_transaction(
+        database: &Database,
+        in_transaction: bool,
+        result: Result<()>,
+    ) -> Result<()> {
+        if !in_transaction {
+            return result;
+        }
+
+        match result {
+            Ok(()) => database.commit_migration_transaction().await,
+            Err(error) => {
+                if let Err(rollback_error) = database.rollback_migration_transaction().await {
+                    warn!("Failed to roll back the migration transaction: {rollback_error}");
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Returns whether the migration is applied.
+    ///
+    /// A squashed migration is also applied when all the migrations it
+    /// replaces are, and a replaced migration is also applied when the
+    /// squashed migration replacing it is.
+    async fn is_applied(&self, database: &Database, migration: &MigrationWrapper) -> Result<bool> {
+        let app_name = migration.app_name();
+        if Self::is_migration_applied(database, app_name, migration.name()).await? {
+            return Ok(true);
+        }
+
+        if !migration.replaces().is_empty()
+            && Self::are_migrations_applied(database, app_name, migration.replaces()).await?
+        {
+            return Ok(true);
+        }
+
+        for squashed in self.squashed_migrations_replacing(migration) {
+            if Self::is_migration_applied(database, app_name, squashed.name()).await? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    async fn any_replaced_migration_applied(
+        database: &Database,
+        migration: &MigrationWrapper,
+    ) -> Result<bool> {
+        for &name in migration.replaces() {
+            if Self::is_migration_applied(database, migration.app_name(), name).await? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Records the migration as applied, together with the migrations it
+    /// replaces, and the squashed migrations whose replaced migrations have
+    /// all been applied with it.
+    async fn mark_applied(&self, database: &Database, migration: &MigrationWrapper) -> Result<()> {
+        let app_name = migration.app_name();
+        Self::mark_migration_applied(database, app_name, migration.name()).await?;
+        for &name in migration.replaces() {
+            Self::mark_migration_applied(database, app_name, name).await?;
+        }
+
+        for squashed in self.squashed_migrations_replacing(migration) {
+            if Self::are_migrations_applied(database, app_name, squashed.replaces()).await?
+                && !Self::is_migration_applied(database, app_name, squashed.name()).await?
+            {
+                Self::mark_migration_applied(database, app_name, squashed.name()).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the records of the migration being applied, together with the
+    /// migrations it replaces, and the squashed migrations replacing it.
+    async fn mark_unapplied(
+        &self,
+        database: &Database,
+        migration: &MigrationWrapper,
+    ) -> Result<()> {
+        let app_name = migration.app_name();
+        Self::mark_migration_unapplied(database, app_name, migration.name()).await?;
+        for &name in migration.replaces() {
+            Self::mark_migration_unapplied(database, app_name, name).await?;
+        }
+        for squashed in self.squashed_migrations_replacing(migration) {
+            Self::mark_migration_unapplied(database, app_name, squashed.name()).await?;
+        }
+
+        Ok(())
+    }
+
+    fn squashed_migrations_replacing<'a>(
+        &'a self,
+        migration: &'a MigrationWrapper,
+    ) -> impl Iterator<Item = &'a MigrationWrapper> {
+        self.squashed_migrations.iter().filter(|squashed| {
+            squashed.app_name() == migration.app_name()
+                && squashed.replaces().contains(&migration.name())
+        })
+    }
+
+    async fn is_migration_applied(database: &Database, app_name: &str, name: &str) -> Result<bool> {
+        query!(AppliedMigration, $app == app_name && $name == name)
+            .exists(database)
+            .await
+    }
+
+    async fn are_migrations_applied(
+        database: &Database,
+        app_name: &str,
+        names: &[&str],
+    ) -> Result<bool> {
+        for name in names {
+            if !Self::is_migration_applied(database, app_name, name).await? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    async fn mark_migration_applied(database: &Database, app_name: &str, name: &str) -> Result<()> {
+        let mut applied_migration = AppliedMigration {
+            id: Auto::auto(),
+            app: app_name.to_string(),
+            name: name.to_string(),
+            applied: chrono::Utc::now().into(),
+        };
+
+        database.insert(&mut applied_migration).await?;
+        Ok(())
+    }
+
+    async fn mark_migration_unapplied(
+        database: &Database,
+        app_name: &str,
+        name: &str,
+    ) -> Result<()> {
+        query!(AppliedMigration, $app == app_name && $name == name)
+            .delete(database)
+            .await?;
+        Ok(())
+    }
+}
+
+/// The state to migrate the database to with [`MigrationEngine::migrate`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MigrationTarget<'a> {
+    /// All the migrations applied.
+    Latest,
+    /// The given migration of the given app, and all the migrations it depends
+    /// on, applied, and the migrations of the app following it not applied.
+    Migration {
+        /// The name of the app the migration belongs to.
+        app_name: &'a str,
+        /// The name of the migration, either the full one (e.g.
+        /// `m_0001_initial`), or just its number (e.g. `0001`). `zero` means
+        /// none of the migrations of the app applied.
+        migration_name: &'a str,
+    },
+}
+
+/// Whether a [`MigrationPlan`] applies or rolls back migrations.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MigrationDirection {
+    /// The migrations are applied.
+    Forwards,
+    /// The migrations are rolled back.
+    Backwards,
+}
+
+/// A list of migrations to apply or roll back to migrate the database to a
+/// [`MigrationTarget`], as returned by [`MigrationEngine::plan`].
+#[derive(Debug)]
+pub struct MigrationPlan<'a> {
+    direction: MigrationDirection,
+    migrations: Vec<&'a MigrationWrapper>,
+}
+
+impl MigrationPlan<'_> {
+    /// Returns whether the migrations are applied or rolled back.
+    #[must_use]
+    pub fn direction(&self) -> MigrationDirection {
+        self.direction
+    }
+
+    /// Returns the number of migrations in the plan.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.migrations.len()
+    }
+
+    /// Returns `true` if there are no migrations to apply or roll back, i.e.
+    /// the database is already migrated to the target.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.migrations.is_empty()
+    }
+
+    /// Returns the migrations in the order they are applied or rolled back.
+    pub fn migrations(&self) -> impl Iterator<Item = &dyn DynMigration> {
+        self.migrations
+            .iter()
+            .copied()
+            .map(|migration| -> &dyn DynMigration { migration })
+    }
+
+    /// Writes the plan in a human-readable form.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plan cannot be written to the output.
+    pub fn write(&self, output: &mut impl Write) -> io::Result<()> {
+        let (direction, action) = match self.direction {
+            MigrationDirection::Forwards => ("forwards", "to apply:"),
+            MigrationDirection::Backwards => ("backwards", "to roll back:"),
+        };
+
+        writeln!(output, "Migration plan\n")?;
+        writeln!(output, "Direction: {direction}\n")?;
+        writeln!(output, "Plan:")?;
+        if self.migrations.is_empty() {
+            writeln!(output, "  (no migrations)")?;
+        }
+        for (index, migration) in self.migrations.iter().enumerate() {
+            writeln!(
+                output,
+                "  {}. {}::{}",
+                index + 1,
+                migration.app_name(),
+                migration.name()
+            )?;
+        }
+
+        writeln!(output, "\nSummary:")?;
+        writeln!(output, "  {action:<17} {}", self.migrations.len())?;
+        writeln!(output, "  database changes: none\n")?;
+
+        Ok(())
+    }
+}
+
+/// The SQL run when applying and rolling back a migration, as returned by
+/// [`MigrationEngine::migration_sql`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationSql {
+    forwards: Vec<String>,
+    backwards: Vec<String>,
+}
+
+impl MigrationSql {
+    /// Returns the statements run when applying the migration.
+    #[must_use]
+    pub fn forwards(&self) -> &[String] {
+        &self.forwards
+    }
+
+    /// Returns the statements run when rolling back the migration.
+    #[must_use]
+    pub fn backwards(&self) -> &[String] {
+        &self.backwards
+    }
+
+    /// Writes the statements as an SQL script, with the forwards and the
+    /// backwards statements in separate sections.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the statements cannot be written to the output.
+    pub fn write(&self, output: &mut impl Write) -> io::Result<()> {
+        writeln!(output, "-- Forwards")?;
+        for statement in &self.forwards {
+            writeln!(output, "{}", Self::terminated(statement))?;
+        }
+        writeln!(output, "\n-- Backwards")?;
+        for statement in &self.backwards {
+            writeln!(output, "{}", Self::terminated(statement))?;
+        }
+
+        Ok(())
+    }
+
+    fn terminated(statement: &str) -> String {
+        if statement.starts_with("--") {
+            statement.to_owned()
+        } else {
+            format!("{statement};")
+        }
+    }
+}
+
+/// Resolves the possible migration names that can be used to refer to a
 /// migration file. For example, for a migration file named `m_0001_initial`,
 /// this function will return both `m_0001_initial` and `0001`. This allows
 /// users to refer to migrations using either the full file name or just the
@@ -646,7 +1370,11 @@ impl Operation {
         RemoveFieldBuilder::new()
     }
 
-    /// Returns a builder for an operation that removes a model.
+    /// Returns a builder for an operation that changes the definition of a
+    /// field in a model, such as its type, nullability or uniqueness.
+    ///
+    /// On SQLite, which can't modify existing columns, this rebuilds the
+    /// whole table with the new column definition and copies the data over.
     ///
     /// Typically, you shouldn't need to use this directly. Instead, in most
     /// cases, this can be automatically generated by the Cot CLI.
@@ -659,114 +1387,362 @@ impl Operation {
     ///
     /// # #[tokio::main]
     /// # async fn main() -> cot::Result<()> {
-    /// const FIELDS: &[Field] = &[
-    ///     Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
-    ///         .primary_key()
-    ///         .auto(),
-    ///     Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
-    /// ];
-    ///
-    /// // First create the table
-    /// const CREATE_OPERATION: Operation = Operation::create_model()
-    ///     .table_name(Identifier::new("todoapp__my_model"))
-    ///     .fields(FIELDS)
-    ///     .build();
-    /// let database = cot::db::Database::new("sqlite::memory:").await?;
-    /// CREATE_OPERATION.forwards(&database).await?;
-    ///
-    /// // Then remove it
-    /// const OPERATION: Operation = Operation::remove_model()
+    /// # const CREATE_MODEL_OPERATION: Operation = Operation::create_model()
+    /// #     .table_name(Identifier::new("todoapp__my_model"))
+    /// #     .fields(&[
+    /// #         Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+    /// #             .primary_key()
+    /// #             .auto(),
+    /// #         Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+    /// #     ])
+    /// #     .build();
+    /// const OPERATION: Operation = Operation::alter_field()
     ///     .table_name(Identifier::new("todoapp__my_model"))
-    ///     .fields(FIELDS)
+    ///     .old_field(Field::new(
+    ///         Identifier::new("name"),
+    ///         <String as DatabaseField>::TYPE,
+    ///     ))
+    ///     .new_field(
+    ///         Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE).null(),
+    ///     )
     ///     .build();
     ///
-    /// OPERATION.forwards(&database).await?;
+    /// # let database = cot::db::Database::new("sqlite::memory:").await?;
+    /// # CREATE_MODEL_OPERATION.forwards(&database).await?;
+    /// # OPERATION.forwards(&database).await?;
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub const fn remove_model() -> RemoveModelBuilder {
-        RemoveModelBuilder::new()
+    pub const fn alter_field() -> AlterFieldBuilder {
+        AlterFieldBuilder::new()
     }
 
-    /// Returns a builder for a custom operation.
+    /// Returns a builder for an operation that renames a field in a model,
+    /// keeping the data stored in it.
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
     ///
     /// # Examples
     ///
     /// ```
-    /// use cot::db::Result;
-    /// use cot::db::migrations::{MigrationContext, Operation, migration_op};
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
     ///
-    /// #[migration_op]
-    /// async fn forwards(ctx: MigrationContext<'_>) -> Result<()> {
-    ///     // do something
-    ///     Ok(())
-    /// }
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// # use cot::db::DatabaseField;
+    /// # use cot::db::migrations::Field;
+    /// # const CREATE_MODEL_OPERATION: Operation = Operation::create_model()
+    /// #     .table_name(Identifier::new("todoapp__my_model"))
+    /// #     .fields(&[
+    /// #         Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+    /// #             .primary_key()
+    /// #             .auto(),
+    /// #         Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+    /// #     ])
+    /// #     .build();
+    /// const OPERATION: Operation = Operation::rename_field()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .old_name(Identifier::new("name"))
+    ///     .new_name(Identifier::new("title"))
+    ///     .build();
     ///
-    /// const OPERATION: Operation = Operation::custom(forwards).build();
+    /// # let database = cot::db::Database::new("sqlite::memory:").await?;
+    /// # CREATE_MODEL_OPERATION.forwards(&database).await?;
+    /// # OPERATION.forwards(&database).await?;
+    /// # Ok(())
+    /// # }
     /// ```
     #[must_use]
-    pub const fn custom(forwards: CustomOperationFn) -> CustomBuilder {
-        CustomBuilder::new(forwards)
+    pub const fn rename_field() -> RenameFieldBuilder {
+        RenameFieldBuilder::new()
     }
 
-    /// Runs the operation forwards.
-    ///
-    /// # Errors
+    /// Returns a builder for an operation that renames a model's table,
+    /// keeping the data stored in it.
     ///
-    /// Returns an error if the operation fails to apply.
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
     ///
     /// # Examples
     ///
     /// ```
-    /// use cot::db::migrations::{Field, Migration, Operation};
-    /// use cot::db::{Database, DatabaseField, Identifier};
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
     ///
     /// # #[tokio::main]
     /// # async fn main() -> cot::Result<()> {
-    /// const OPERATION: Operation = Operation::create_model()
-    ///     .table_name(Identifier::new("todoapp__my_model"))
-    ///     .fields(&[
-    ///         Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
-    ///             .primary_key()
-    ///             .auto(),
-    ///         Field::new(Identifier::new("app"), <String as DatabaseField>::TYPE),
-    ///     ])
+    /// # use cot::db::DatabaseField;
+    /// # use cot::db::migrations::Field;
+    /// # const CREATE_MODEL_OPERATION: Operation = Operation::create_model()
+    /// #     .table_name(Identifier::new("todoapp__my_model"))
+    /// #     .fields(&[
+    /// #         Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+    /// #             .primary_key()
+    /// #             .auto(),
+    /// #     ])
+    /// #     .build();
+    /// const OPERATION: Operation = Operation::rename_model()
+    ///     .old_table_name(Identifier::new("todoapp__my_model"))
+    ///     .new_table_name(Identifier::new("todoapp__todo_item"))
     ///     .build();
     ///
-    /// let database = Database::new("sqlite::memory:").await?;
-    /// OPERATION.forwards(&database).await?;
+    /// # let database = cot::db::Database::new("sqlite::memory:").await?;
+    /// # CREATE_MODEL_OPERATION.forwards(&database).await?;
+    /// # OPERATION.forwards(&database).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn forwards(&self, database: &Database) -> Result<()> {
-        match &self.inner {
-            OperationInner::CreateModel {
-                table_name,
-                fields,
+    #[must_use]
+    pub const fn rename_model() -> RenameModelBuilder {
+        RenameModelBuilder::new()
+    }
+
+    /// Returns a builder for an operation that creates an index on a model.
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::{Index, Operation};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// # use cot::db::DatabaseField;
+    /// # use cot::db::migrations::Field;
+    /// # const CREATE_MODEL_OPERATION: Operation = Operation::create_model()
+    /// #     .table_name(Identifier::new("todoapp__my_model"))
+    /// #     .fields(&[
+    /// #         Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+    /// #             .primary_key()
+    /// #             .auto(),
+    /// #         Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+    /// #     ])
+    /// #     .build();
+    /// const OPERATION: Operation = Operation::add_index()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .index(Index::new(
+    ///         Identifier::new("todoapp__my_model_name_idx"),
+    ///         &[Identifier::new("name")],
+    ///     ))
+    ///     .build();
+    ///
+    /// # let database = cot::db::Database::new("sqlite::memory:").await?;
+    /// # CREATE_MODEL_OPERATION.forwards(&database).await?;
+    /// # OPERATION.forwards(&database).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub const fn add_index() -> AddIndexBuilder {
+        AddIndexBuilder::new()
+    }
+
+    /// Returns a builder for an operation that removes an index from a model.
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::{Index, Operation};
+    ///
+    /// const OPERATION: Operation = Operation::remove_index()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .index(Index::new(
+    ///         Identifier::new("todoapp__my_model_name_idx"),
+    ///         &[Identifier::new("name")],
+    ///     ))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub const fn remove_index() -> RemoveIndexBuilder {
+        RemoveIndexBuilder::new()
+    }
+
+    /// Returns a builder for an operation that adds a constraint to a model.
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::{Constraint, Operation};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// # use cot::db::DatabaseField;
+    /// # use cot::db::migrations::Field;
+    /// # const CREATE_MODEL_OPERATION: Operation = Operation::create_model()
+    /// #     .table_name(Identifier::new("todoapp__my_model"))
+    /// #     .fields(&[
+    /// #         Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+    /// #             .primary_key()
+    /// #             .auto(),
+    /// #         Field::new(Identifier::new("price"), <i32 as DatabaseField>::TYPE),
+    /// #     ])
+    /// #     .build();
+    /// const OPERATION: Operation = Operation::add_constraint()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .constraint(Constraint::check(
+    ///         Identifier::new("todoapp__my_model_price_positive"),
+    ///         "price > 0",
+    ///     ))
+    ///     .build();
+    ///
+    /// # let database = cot::db::Database::new("sqlite::memory:").await?;
+    /// # CREATE_MODEL_OPERATION.forwards(&database).await?;
+    /// # OPERATION.forwards(&database).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub const fn add_constraint() -> AddConstraintBuilder {
+        AddConstraintBuilder::new()
+    }
+
+    /// Returns a builder for an operation that removes a constraint from a
+    /// model.
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::{Constraint, Operation};
+    ///
+    /// const OPERATION: Operation = Operation::remove_constraint()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .constraint(Constraint::check(
+    ///         Identifier::new("todoapp__my_model_price_positive"),
+    ///         "price > 0",
+    ///     ))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub const fn remove_constraint() -> RemoveConstraintBuilder {
+        RemoveConstraintBuilder::new()
+    }
+
+    /// Returns a builder for an operation that removes a model.
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::migrations::{Field, Operation};
+    /// use cot::db::{DatabaseField, Identifier};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// const FIELDS: &[Field] = &[
+    ///     Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+    ///         .primary_key()
+    ///         .auto(),
+    ///     Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+    /// ];
+    ///
+    /// // First create the table
+    /// const CREATE_OPERATION: Operation = Operation::create_model()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .fields(FIELDS)
+    ///     .build();
+    /// let database = cot::db::Database::new("sqlite::memory:").await?;
+    /// CREATE_OPERATION.forwards(&database).await?;
+    ///
+    /// // Then remove it
+    /// const OPERATION: Operation = Operation::remove_model()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .fields(FIELDS)
+    ///     .build();
+    ///
+    /// OPERATION.forwards(&database).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub const fn remove_model() -> RemoveModelBuilder {
+        RemoveModelBuilder::new()
+    }
+
+    /// Returns a builder for a custom operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Result;
+    /// use cot::db::migrations::{MigrationContext, Operation, migration_op};
+    ///
+    /// #[migration_op]
+    /// async fn forwards(ctx: MigrationContext<'_>) -> Result<()> {
+    ///     // do something
+    ///     Ok(())
+    /// }
+    ///
+    /// const OPERATION: Operation = Operation::custom(forwards).build();
+    /// ```
+    #[must_use]
+    pub const fn custom(forwards: CustomOperationFn) -> CustomBuilder {
+        CustomBuilder::new(forwards)
+    }
+
+    /// Runs the operation forwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation fails to apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::migrations::{Field, Migration, Operation};
+    /// use cot::db::{Database, DatabaseField, Identifier};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// const OPERATION: Operation = Operation::create_model()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .fields(&[
+    ///         Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+    ///             .primary_key()
+    ///             .auto(),
+    ///         Field::new(Identifier::new("app"), <String as DatabaseField>::TYPE),
+    ///     ])
+    ///     .build();
+    ///
+    /// let database = Database::new("sqlite::memory:").await?;
+    /// OPERATION.forwards(&database).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn forwards(&self, database: &Database) -> Result<()> {
+        match &self.inner {
+            OperationInner::CreateModel {
+                table_name,
+                fields,
                 if_not_exists,
             } => {
-                let mut query = sea_query::Table::create().table(*table_name).to_owned();
-                for field in *fields {
-                    query.col(field.as_column_def(database));
-                    if let Some(foreign_key) = field.foreign_key {
-                        query.foreign_key(
-                            sea_query::ForeignKeyCreateStatement::new()
-                                .from_tbl(*table_name)
-                                .from_col(field.name)
-                                .to_tbl(foreign_key.model)
-                                .to_col(foreign_key.field)
-                                .on_delete(foreign_key.on_delete.into())
-                                .on_update(foreign_key.on_update.into()),
-                        );
-                    }
-                }
+                let mut query = create_table_statement(database, *table_name, fields)?;
                 if *if_not_exists {
                     query.if_not_exists();
                 }
                 database.execute_schema(query).await?;
             }
             OperationInner::AddField { table_name, field } => {
+                database.check_column_type(field.ty)?;
                 let query = sea_query::Table::alter()
                     .table(*table_name)
                     .add_column(field.as_column_def(database))
@@ -780,6 +1756,54 @@ impl Operation {
                     .to_owned();
                 database.execute_schema(query).await?;
             }
+            OperationInner::AlterField {
+                table_name,
+                old_field,
+                new_field,
+            } => {
+                database.check_column_type(new_field.ty)?;
+                database
+                    .alter_column(*table_name, old_field, new_field)
+                    .await?;
+            }
+            OperationInner::RenameField {
+                table_name,
+                old_name,
+                new_name,
+            } => {
+                let query = sea_query::Table::alter()
+                    .table(*table_name)
+                    .rename_column(*old_name, *new_name)
+                    .to_owned();
+                database.execute_schema(query).await?;
+            }
+            OperationInner::RenameModel {
+                old_table_name,
+                new_table_name,
+            } => {
+                let query = sea_query::Table::rename()
+                    .table(*old_table_name, *new_table_name)
+                    .to_owned();
+                database.execute_schema(query).await?;
+            }
+            OperationInner::AddIndex { table_name, index } => {
+                index.add(database, *table_name).await?;
+            }
+            OperationInner::RemoveIndex { table_name, index } => {
+                index.remove(database, *table_name).await?;
+            }
+            OperationInner::AddConstraint {
+                table_name,
+                constraint,
+            } => {
+                constraint.add(database, *table_name).await?;
+            }
+            OperationInner::RemoveConstraint {
+                table_name,
+                constraint,
+            } => {
+                constraint.remove(database, *table_name).await?;
+            }
             OperationInner::RemoveModel {
                 table_name,
                 fields: _,
@@ -848,28 +1872,63 @@ impl Operation {
                 database.execute_schema(query).await?;
             }
             OperationInner::RemoveField { table_name, field } => {
+                database.check_column_type(field.ty)?;
                 let query = sea_query::Table::alter()
                     .table(*table_name)
                     .add_column(field.as_column_def(database))
                     .to_owned();
                 database.execute_schema(query).await?;
             }
+            OperationInner::AlterField {
+                table_name,
+                old_field,
+                new_field,
+            } => {
+                database.check_column_type(old_field.ty)?;
+                database
+                    .alter_column(*table_name, new_field, old_field)
+                    .await?;
+            }
+            OperationInner::RenameField {
+                table_name,
+                old_name,
+                new_name,
+            } => {
+                let query = sea_query::Table::alter()
+                    .table(*table_name)
+                    .rename_column(*new_name, *old_name)
+                    .to_owned();
+                database.execute_schema(query).await?;
+            }
+            OperationInner::RenameModel {
+                old_table_name,
+                new_table_name,
+            } => {
+                let query = sea_query::Table::rename()
+                    .table(*new_table_name, *old_table_name)
+                    .to_owned();
+                database.execute_schema(query).await?;
+            }
+            OperationInner::AddIndex { table_name, index } => {
+                index.remove(database, *table_name).await?;
+            }
+            OperationInner::RemoveIndex { table_name, index } => {
+                index.add(database, *table_name).await?;
+            }
+            OperationInner::AddConstraint {
+                table_name,
+                constraint,
+            } => {
+                constraint.remove(database, *table_name).await?;
+            }
+            OperationInner::RemoveConstraint {
+                table_name,
+                constraint,
+            } => {
+                constraint.add(database, *table_name).await?;
+            }
             OperationInner::RemoveModel { table_name, fields } => {
-                let mut query = sea_query::Table::create().table(*table_name).to_owned();
-                for field in *fields {
-                    query.col(field.as_column_def(database));
-                    if let Some(foreign_key) = field.foreign_key {
-                        query.foreign_key(
-                            sea_query::ForeignKeyCreateStatement::new()
-                                .from_tbl(*table_name)
-                                .from_col(field.name)
-                                .to_tbl(foreign_key.model)
-                                .to_col(foreign_key.field)
-                                .on_delete(foreign_key.on_delete.into())
-                                .on_update(foreign_key.on_update.into()),
-                        );
-                    }
-                }
+                let query = create_table_statement(database, *table_name, fields)?;
                 database.execute_schema(query).await?;
             }
             OperationInner::Custom {
@@ -889,6 +1948,10 @@ impl Operation {
         }
         Ok(())
     }
+
+    fn is_custom(&self) -> bool {
+        matches!(self.inner, OperationInner::Custom { .. })
+    }
 }
 
 /// A context for a custom migration operation.
@@ -935,20 +1998,89 @@ enum OperationInner {
         table_name: Identifier,
         field: Field,
     },
-    /// Remove a model with the given fields
-    RemoveModel {
+    /// Change the definition of an existing field.
+    AlterField {
         table_name: Identifier,
-        fields: &'static [Field],
+        old_field: Field,
+        new_field: Field,
     },
-    Custom {
-        forwards: CustomOperationFn,
-        backwards: Option<CustomOperationFn>,
+    /// Rename a field in an existing model.
+    RenameField {
+        table_name: Identifier,
+        old_name: Identifier,
+        new_name: Identifier,
     },
-}
-
-/// A field in a model.
-#[expect(clippy::struct_excessive_bools)]
-#[derive(Debug, Copy, Clone)]
+    /// Rename an existing model.
+    RenameModel {
+        old_table_name: Identifier,
+        new_table_name: Identifier,
+    },
+    /// Create an index on an existing model.
+    AddIndex {
+        table_name: Identifier,
+        index: Index,
+    },
+    /// Remove an index from an existing model.
+    RemoveIndex {
+        table_name: Identifier,
+        index: Index,
+    },
+    /// Add a constraint to an existing model.
+    AddConstraint {
+        table_name: Identifier,
+        constraint: Constraint,
+    },
+    /// Remove a constraint from an existing model.
+    RemoveConstraint {
+        table_name: Identifier,
+        constraint: Constraint,
+    },
+    /// Remove a model with the given fields
+    RemoveModel {
+        table_name: Identifier,
+        fields: &'static [Field],
+    },
+    Custom {
+        forwards: CustomOperationFn,
+        backwards: Option<CustomOperationFn>,
+    },
+}
+
+fn create_table_statement(
+    database: &Database,
+    table_name: Identifier,
+    fields: &[Field],
+) -> Result<sea_query::TableCreateStatement> {
+    let mut query = sea_query::Table::create().table(table_name).to_owned();
+    // composite primary keys are declared as a table constraint instead of
+    // marking each of the columns as the primary key
+    let composite_primary_key = fields.iter().filter(|field| field.primary_key).count() > 1;
+    let mut primary_key = sea_query::Index::create();
+    for field in fields {
+        database.check_column_type(field.ty)?;
+        if composite_primary_key && field.primary_key {
+            primary_key.col(field.name);
+            let column = Field {
+                primary_key: false,
+                ..*field
+            };
+            query.col(column.as_column_def(database));
+        } else {
+            query.col(field.as_column_def(database));
+        }
+        if let Some(mut foreign_key) = field.as_foreign_key_def(table_name) {
+            query.foreign_key(&mut foreign_key);
+        }
+    }
+    if composite_primary_key {
+        query.primary_key(&mut primary_key);
+    }
+    Ok(query)
+}
+
+/// A field in a model.
+#[expect(clippy::struct_excessive_bools)]
+#[derive(Debug, Copy, Clone)]
 pub struct Field {
     /// The name of the field
     pub name: Identifier,
@@ -963,6 +2095,8 @@ pub struct Field {
     pub null: bool,
     /// Whether the column has a unique constraint
     pub unique: bool,
+    /// The value the database uses when no value is given for the column
+    pub default: Option<DefaultValue>,
     foreign_key: Option<ForeignKeyReference>,
 }
 
@@ -991,6 +2125,7 @@ impl Field {
             auto_value: false,
             null: false,
             unique: false,
+            default: None,
             foreign_key: None,
         }
     }
@@ -1155,7 +2290,34 @@ impl Field {
         self
     }
 
-    fn as_column_def<T: ColumnTypeMapper>(&self, mapper: &T) -> ColumnDef {
+    /// Sets the database-level default value of the field.
+    ///
+    /// The default is used when a row is inserted without a value for the
+    /// column, and to fill in the existing rows when the column is added to a
+    /// table.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI when you mark
+    /// your field with a `#[model(default = ...)]` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::migrations::{DefaultValue, Field};
+    /// use cot::db::{DatabaseField, Identifier};
+    ///
+    /// let field = Field::new(Identifier::new("count"), <i32 as DatabaseField>::TYPE)
+    ///     .default(DefaultValue::Int(0));
+    /// ```
+    #[must_use]
+    pub const fn default(mut self, value: DefaultValue) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    pub(super) fn as_column_def<T: ColumnTypeMapper>(&self, mapper: &T) -> ColumnDef {
         let mut def =
             ColumnDef::new_with_type(self.name, mapper.sea_query_column_type_for(self.ty));
         if self.primary_key {
@@ -1172,8 +2334,69 @@ impl Field {
         if self.unique {
             def.unique_key();
         }
+        if let Some(default) = self.default {
+            def.default(mapper.sea_query_default_for(default));
+        }
         def
     }
+
+    pub(super) fn as_foreign_key_def(
+        &self,
+        table_name: Identifier,
+    ) -> Option<sea_query::ForeignKeyCreateStatement> {
+        self.foreign_key.map(|foreign_key| {
+            sea_query::ForeignKeyCreateStatement::new()
+                .from_tbl(table_name)
+                .from_col(self.name)
+                .to_tbl(foreign_key.model)
+                .to_col(foreign_key.field)
+                .on_delete(foreign_key.on_delete.into())
+                .on_update(foreign_key.on_update.into())
+                .to_owned()
+        })
+    }
+
+    pub(super) fn has_foreign_key(&self) -> bool {
+        self.foreign_key.is_some()
+    }
+
+    pub(super) fn foreign_key_differs(&self, other: &Self) -> bool {
+        self.foreign_key != other.foreign_key
+    }
+}
+
+/// A database-level default value of a field.
+///
+/// # Cot CLI Usage
+///
+/// Typically, you shouldn't need to use this directly. Instead, in most
+/// cases, this can be automatically generated by the Cot CLI when you mark
+/// your field with a `#[model(default = ...)]` attribute.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DefaultValue {
+    /// A boolean literal.
+    Bool(bool),
+    /// An integer literal.
+    Int(i64),
+    /// A floating point literal.
+    Float(f64),
+    /// A string literal.
+    String(&'static str),
+    /// An SQL expression evaluated by the database, such as
+    /// `CURRENT_TIMESTAMP`.
+    Expr(&'static str),
+}
+
+impl From<DefaultValue> for sea_query::Expr {
+    fn from(value: DefaultValue) -> Self {
+        match value {
+            DefaultValue::Bool(value) => sea_query::Expr::val(value),
+            DefaultValue::Int(value) => sea_query::Expr::val(value),
+            DefaultValue::Float(value) => sea_query::Expr::val(value),
+            DefaultValue::String(value) => sea_query::Expr::val(value),
+            DefaultValue::Expr(expr) => sea_query::Expr::cust(expr),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -1184,9 +2407,284 @@ struct ForeignKeyReference {
     on_update: ForeignKeyOnUpdatePolicy,
 }
 
+/// An index on one or more columns of a model.
+#[derive(Debug, Copy, Clone)]
+pub struct Index {
+    /// The name of the index
+    pub name: Identifier,
+    /// The columns the index is created on
+    pub columns: &'static [Identifier],
+}
+
+impl Index {
+    /// Creates a new index for use in a migration operation.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI when you mark
+    /// your field with a `#[model(index)]` attribute, or your model with a
+    /// `#[model(index(...))]` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Index;
+    ///
+    /// const INDEX: Index = Index::new(
+    ///     Identifier::new("todoapp__my_model_name_idx"),
+    ///     &[Identifier::new("name")],
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn new(name: Identifier, columns: &'static [Identifier]) -> Self {
+        Self { name, columns }
+    }
+
+    fn as_create_statement(&self, table_name: Identifier) -> sea_query::IndexCreateStatement {
+        let mut statement = sea_query::Index::create()
+            .name(self.name.as_str())
+            .table(table_name)
+            .to_owned();
+        for column in self.columns {
+            statement.col(*column);
+        }
+        statement
+    }
+
+    fn as_drop_statement(&self, table_name: Identifier) -> sea_query::IndexDropStatement {
+        sea_query::Index::drop()
+            .name(self.name.as_str())
+            .table(table_name)
+            .to_owned()
+    }
+
+    async fn add(&self, database: &Database, table_name: Identifier) -> Result<()> {
+        database
+            .execute_schema(self.as_create_statement(table_name))
+            .await?;
+        Ok(())
+    }
+
+    async fn remove(&self, database: &Database, table_name: Identifier) -> Result<()> {
+        database
+            .execute_schema(self.as_drop_statement(table_name))
+            .await?;
+        Ok(())
+    }
+}
+
+/// A table-level constraint of a model.
+#[derive(Debug, Copy, Clone)]
+pub struct Constraint {
+    inner: ConstraintInner,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum ConstraintInner {
+    Unique {
+        name: Identifier,
+        columns: &'static [Identifier],
+    },
+    Check {
+        name: Identifier,
+        expr: &'static str,
+    },
+    ForeignKey {
+        name: Identifier,
+        columns: &'static [Identifier],
+        to_table: Identifier,
+        to_columns: &'static [Identifier],
+        on_delete: ForeignKeyOnDeletePolicy,
+        on_update: ForeignKeyOnUpdatePolicy,
+    },
+}
+
+impl Constraint {
+    /// Creates a constraint ensuring that the combination of values of given
+    /// columns is unique across all rows.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI when you mark
+    /// your model with a `#[model(unique_together(...))]` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Constraint;
+    ///
+    /// const CONSTRAINT: Constraint = Constraint::unique(
+    ///     Identifier::new("todoapp__my_model_first_name_last_name_uniq"),
+    ///     &[Identifier::new("first_name"), Identifier::new("last_name")],
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn unique(name: Identifier, columns: &'static [Identifier]) -> Self {
+        Self {
+            inner: ConstraintInner::Unique { name, columns },
+        }
+    }
+
+    /// Creates a `CHECK` constraint ensuring that given SQL expression holds
+    /// for every row.
+    ///
+    /// Column names that are reserved words or contain uppercase letters must
+    /// be quoted with double quotes, as in standard SQL (e.g. `"order" > 0`).
+    /// On MySQL, the double-quoted identifiers are converted to backtick-quoted
+    /// ones.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI when you mark
+    /// your model with a `#[model(check(...))]` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Constraint;
+    ///
+    /// const CONSTRAINT: Constraint = Constraint::check(
+    ///     Identifier::new("todoapp__my_model_price_positive"),
+    ///     "price > 0",
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn check(name: Identifier, expr: &'static str) -> Self {
+        Self {
+            inner: ConstraintInner::Check { name, expr },
+        }
+    }
+
+    /// Creates a foreign key constraint spanning multiple columns, ensuring
+    /// that the combination of values of `columns` references an existing
+    /// row of `to_table`.
+    ///
+    /// This is used for foreign keys referencing models with a composite
+    /// primary key; foreign keys stored in a single column are declared on
+    /// the field instead (see [`Field::foreign_key`]).
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI when you mark
+    /// your field with a `#[model(foreign_key(columns(...)))]` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::migrations::Constraint;
+    /// use cot::db::{ForeignKeyOnDeletePolicy, ForeignKeyOnUpdatePolicy, Identifier};
+    ///
+    /// const CONSTRAINT: Constraint = Constraint::foreign_key(
+    ///     Identifier::new("todoapp__grade_enrollment_student_enrollment_course_fk"),
+    ///     &[
+    ///         Identifier::new("enrollment_student"),
+    ///         Identifier::new("enrollment_course"),
+    ///     ],
+    ///     Identifier::new("todoapp__enrollment"),
+    ///     &[Identifier::new("student"), Identifier::new("course")],
+    ///     ForeignKeyOnDeletePolicy::Restrict,
+    ///     ForeignKeyOnUpdatePolicy::Cascade,
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn foreign_key(
+        name: Identifier,
+        columns: &'static [Identifier],
+        to_table: Identifier,
+        to_columns: &'static [Identifier],
+        on_delete: ForeignKeyOnDeletePolicy,
+        on_update: ForeignKeyOnUpdatePolicy,
+    ) -> Self {
+        Self {
+            inner: ConstraintInner::ForeignKey {
+                name,
+                columns,
+                to_table,
+                to_columns,
+                on_delete,
+                on_update,
+            },
+        }
+    }
+
+    async fn add(&self, database: &Database, table_name: Identifier) -> Result<()> {
+        match self.inner {
+            // unique indexes can be created on existing tables in all the supported
+            // databases, unlike unique constraints
+            ConstraintInner::Unique { name, columns } => {
+                let index = Index::new(name, columns);
+                let mut statement = index.as_create_statement(table_name);
+                statement.unique();
+                database.execute_schema(statement).await?;
+            }
+            ConstraintInner::Check { name, expr } => {
+                database
+                    .add_check_constraint(table_name, name, expr)
+                    .await?;
+            }
+            ConstraintInner::ForeignKey {
+                name,
+                columns,
+                to_table,
+                to_columns,
+                on_delete,
+                on_update,
+            } => {
+                let mut foreign_key = sea_query::ForeignKey::create();
+                foreign_key
+                    .name(name.as_str())
+                    .from_tbl(table_name)
+                    .to_tbl(to_table)
+                    .on_delete(on_delete.into())
+                    .on_update(on_update.into());
+                for &column in columns {
+                    foreign_key.from_col(column);
+                }
+                for &column in to_columns {
+                    foreign_key.to_col(column);
+                }
+                database
+                    .add_foreign_key_constraint(table_name, foreign_key)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, database: &Database, table_name: Identifier) -> Result<()> {
+        match self.inner {
+            ConstraintInner::Unique { name, columns } => {
+                Index::new(name, columns)
+                    .remove(database, table_name)
+                    .await?;
+            }
+            ConstraintInner::Check { name, .. } => {
+                database.remove_check_constraint(table_name, name).await?;
+            }
+            ConstraintInner::ForeignKey { name, columns, .. } => {
+                database
+                    .remove_foreign_key_constraint(table_name, name, columns)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg_attr(test, mockall::automock)]
 pub(super) trait ColumnTypeMapper {
     fn sea_query_column_type_for(&self, column_type: ColumnType) -> sea_query::ColumnType;
+
+    fn sea_query_default_for(&self, default: DefaultValue) -> sea_query::Expr {
+        default.into()
+    }
 }
 
 macro_rules! unwrap_builder_option {
@@ -1743,7 +3241,9 @@ impl RemoveFieldBuilder {
     }
 }
 
-/// A builder for removing a model.
+/// A builder for changing the definition of a field in a model.
+///
+/// # Cot CLI Usage
 ///
 /// Typically, you shouldn't need to use this directly. Instead, in most
 /// cases, this can be automatically generated by the Cot CLI.
@@ -1756,52 +3256,58 @@ impl RemoveFieldBuilder {
 ///
 /// # #[tokio::main]
 /// # async fn main() -> cot::Result<()> {
-/// const FIELDS: &[Field] = &[
-///     Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
-///         .primary_key()
-///         .auto(),
-///     Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
-/// ];
-///
-/// // First create the table
-/// const CREATE_OPERATION: Operation = Operation::create_model()
-///     .table_name(Identifier::new("todoapp__my_model"))
-///     .fields(FIELDS)
-///     .build();
-/// let database = cot::db::Database::new("sqlite::memory:").await?;
-/// CREATE_OPERATION.forwards(&database).await?;
-///
-/// // Then remove it
-/// const OPERATION: Operation = Operation::remove_model()
+/// # const CREATE_MODEL_OPERATION: Operation = Operation::create_model()
+/// #     .table_name(Identifier::new("todoapp__my_model"))
+/// #     .fields(&[
+/// #         Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+/// #             .primary_key()
+/// #             .auto(),
+/// #         Field::new(Identifier::new("count"), <i32 as DatabaseField>::TYPE),
+/// #     ])
+/// #     .build();
+/// const OPERATION: Operation = Operation::alter_field()
 ///     .table_name(Identifier::new("todoapp__my_model"))
-///     .fields(FIELDS)
+///     .old_field(Field::new(
+///         Identifier::new("count"),
+///         <i32 as DatabaseField>::TYPE,
+///     ))
+///     .new_field(Field::new(
+///         Identifier::new("count"),
+///         <i64 as DatabaseField>::TYPE,
+///     ))
 ///     .build();
 ///
-/// OPERATION.forwards(&database).await?;
+/// # let database = cot::db::Database::new("sqlite::memory:").await?;
+/// # CREATE_MODEL_OPERATION.forwards(&database).await?;
+/// # OPERATION.forwards(&database).await?;
+/// # OPERATION.backwards(&database).await?;
 /// # Ok(())
 /// # }
 /// ```
 #[derive(Debug, Copy, Clone)]
-pub struct RemoveModelBuilder {
+pub struct AlterFieldBuilder {
     table_name: Option<Identifier>,
-    fields: Option<&'static [Field]>,
+    old_field: Option<Field>,
+    new_field: Option<Field>,
 }
 
-impl Default for RemoveModelBuilder {
+impl Default for AlterFieldBuilder {
     fn default() -> Self {
         Self::new()
     }
 }
-impl RemoveModelBuilder {
+
+impl AlterFieldBuilder {
     #[must_use]
     const fn new() -> Self {
         Self {
             table_name: None,
-            fields: None,
+            old_field: None,
+            new_field: None,
         }
     }
 
-    /// Sets the name of the table to remove.
+    /// Sets the name of the table containing the field.
     ///
     /// # Cot CLI Usage
     ///
@@ -1811,51 +3317,63 @@ impl RemoveModelBuilder {
     /// # Examples
     ///
     /// ```
-    /// use cot::db::migrations::{Field, Operation};
-    /// use cot::db::{DatabaseField, Identifier};
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
     ///
-    /// # #[tokio::main]
-    /// # async fn main() -> cot::Result<()> {
-    /// const FIELDS: &[Field] = &[
-    ///     Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
-    ///         .primary_key()
-    ///         .auto(),
-    ///     Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
-    /// ];
+    /// let builder = Operation::alter_field().table_name(Identifier::new("todoapp__my_model"));
+    /// ```
+    #[must_use]
+    pub const fn table_name(mut self, table_name: Identifier) -> Self {
+        self.table_name = Some(table_name);
+        self
+    }
+
+    /// Sets the definition of the field before the change. This is the
+    /// definition that is restored when the operation is run backwards.
     ///
-    /// // First create the table
-    /// const CREATE_OPERATION: Operation = Operation::create_model()
-    ///     .table_name(Identifier::new("todoapp__my_model"))
-    ///     .fields(FIELDS)
-    ///     .build();
-    /// let database = cot::db::Database::new("sqlite::memory:").await?;
-    /// CREATE_OPERATION.forwards(&database).await?;
+    /// # Cot CLI Usage
     ///
-    /// // Then remove it
-    /// const OPERATION: Operation = Operation::remove_model()
-    ///     .table_name(Identifier::new("todoapp__my_model"))
-    ///     .fields(FIELDS)
-    ///     .build();
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
     ///
-    /// OPERATION.forwards(&database).await?;
-    /// # Ok(())
-    /// # }
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::migrations::{Field, Operation};
+    /// use cot::db::{DatabaseField, Identifier};
+    ///
+    /// let builder = Operation::alter_field().old_field(Field::new(
+    ///     Identifier::new("count"),
+    ///     <i32 as DatabaseField>::TYPE,
+    /// ));
     /// ```
     #[must_use]
-    pub const fn table_name(mut self, table_name: Identifier) -> Self {
-        self.table_name = Some(table_name);
+    pub const fn old_field(mut self, field: Field) -> Self {
+        self.old_field = Some(field);
         self
     }
 
-    /// Sets the fields to store for potential backwards migration.
+    /// Sets the definition of the field after the change.
     ///
     /// # Cot CLI Usage
     ///
     /// Typically, you shouldn't need to use this directly. Instead, in most
     /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::migrations::{Field, Operation};
+    /// use cot::db::{DatabaseField, Identifier};
+    ///
+    /// let builder = Operation::alter_field().new_field(Field::new(
+    ///     Identifier::new("count"),
+    ///     <i64 as DatabaseField>::TYPE,
+    /// ));
+    /// ```
     #[must_use]
-    pub const fn fields(mut self, fields: &'static [Field]) -> Self {
-        self.fields = Some(fields);
+    pub const fn new_field(mut self, field: Field) -> Self {
+        self.new_field = Some(field);
         self
     }
 
@@ -1865,72 +3383,186 @@ impl RemoveModelBuilder {
     ///
     /// Typically, you shouldn't need to use this directly. Instead, in most
     /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::migrations::{Field, Operation};
+    /// use cot::db::{DatabaseField, Identifier};
+    ///
+    /// const OPERATION: Operation = Operation::alter_field()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .old_field(Field::new(
+    ///         Identifier::new("name"),
+    ///         <String as DatabaseField>::TYPE,
+    ///     ))
+    ///     .new_field(Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE).unique())
+    ///     .build();
+    /// ```
     #[must_use]
     pub const fn build(self) -> Operation {
-        Operation::new(OperationInner::RemoveModel {
+        Operation::new(OperationInner::AlterField {
             table_name: unwrap_builder_option!(self, table_name),
-            fields: unwrap_builder_option!(self, fields),
+            old_field: unwrap_builder_option!(self, old_field),
+            new_field: unwrap_builder_option!(self, new_field),
         })
     }
 }
 
-/// A builder for a custom operation.
+/// A builder for renaming a field in a model.
+///
+/// Typically, you shouldn't need to use this directly. Instead, in most
+/// cases, this can be automatically generated by the Cot CLI.
 ///
 /// # Examples
 ///
 /// ```
-/// use cot::db::Result;
-/// use cot::db::migrations::{MigrationContext, Operation, migration_op};
-///
-/// #[migration_op]
-/// async fn forwards(ctx: MigrationContext<'_>) -> Result<()> {
-///     // do something
-///     Ok(())
-/// }
+/// use cot::db::Identifier;
+/// use cot::db::migrations::Operation;
 ///
-/// #[migration_op]
-/// async fn backwards(ctx: MigrationContext<'_>) -> Result<()> {
-///     // undo something
-///     Ok(())
-/// }
+/// # #[tokio::main]
+/// # async fn main() -> cot::Result<()> {
+/// # use cot::db::DatabaseField;
+/// # use cot::db::migrations::Field;
+/// # const CREATE_MODEL_OPERATION: Operation = Operation::create_model()
+/// #     .table_name(Identifier::new("todoapp__my_model"))
+/// #     .fields(&[
+/// #         Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+/// #             .primary_key()
+/// #             .auto(),
+/// #         Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+/// #     ])
+/// #     .build();
+/// const OPERATION: Operation = Operation::rename_field()
+///     .table_name(Identifier::new("todoapp__my_model"))
+///     .old_name(Identifier::new("name"))
+///     .new_name(Identifier::new("title"))
+///     .build();
 ///
-/// const OPERATION: Operation = Operation::custom(forwards).backwards(backwards).build();
+/// # let database = cot::db::Database::new("sqlite::memory:").await?;
+/// # CREATE_MODEL_OPERATION.forwards(&database).await?;
+/// # OPERATION.forwards(&database).await?;
+/// # OPERATION.backwards(&database).await?;
+/// # Ok(())
+/// # }
 /// ```
+#[expect(clippy::struct_field_names)]
 #[derive(Debug, Copy, Clone)]
-pub struct CustomBuilder {
-    forwards: CustomOperationFn,
-    backwards: Option<CustomOperationFn>,
+pub struct RenameFieldBuilder {
+    table_name: Option<Identifier>,
+    old_name: Option<Identifier>,
+    new_name: Option<Identifier>,
 }
 
-impl CustomBuilder {
+impl Default for RenameFieldBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenameFieldBuilder {
     #[must_use]
-    const fn new(forwards: CustomOperationFn) -> Self {
+    const fn new() -> Self {
         Self {
-            forwards,
-            backwards: None,
+            table_name: None,
+            old_name: None,
+            new_name: None,
         }
     }
 
-    /// Sets the backwards operation.
+    /// Sets the name of the table containing the field.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
+    ///
+    /// let builder = Operation::rename_field().table_name(Identifier::new("todoapp__my_model"));
+    /// ```
     #[must_use]
-    pub const fn backwards(mut self, backwards: CustomOperationFn) -> Self {
-        self.backwards = Some(backwards);
+    pub const fn table_name(mut self, table_name: Identifier) -> Self {
+        self.table_name = Some(table_name);
+        self
+    }
+
+    /// Sets the current name of the field.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
+    ///
+    /// let builder = Operation::rename_field().old_name(Identifier::new("name"));
+    /// ```
+    #[must_use]
+    pub const fn old_name(mut self, old_name: Identifier) -> Self {
+        self.old_name = Some(old_name);
+        self
+    }
+
+    /// Sets the name the field is renamed to.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
+    ///
+    /// let builder = Operation::rename_field().new_name(Identifier::new("title"));
+    /// ```
+    #[must_use]
+    pub const fn new_name(mut self, new_name: Identifier) -> Self {
+        self.new_name = Some(new_name);
         self
     }
 
     /// Builds the operation.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
+    ///
+    /// const OPERATION: Operation = Operation::rename_field()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .old_name(Identifier::new("name"))
+    ///     .new_name(Identifier::new("title"))
+    ///     .build();
+    /// ```
     #[must_use]
     pub const fn build(self) -> Operation {
-        Operation::new(OperationInner::Custom {
-            forwards: self.forwards,
-            backwards: self.backwards,
+        Operation::new(OperationInner::RenameField {
+            table_name: unwrap_builder_option!(self, table_name),
+            old_name: unwrap_builder_option!(self, old_name),
+            new_name: unwrap_builder_option!(self, new_name),
         })
     }
 }
 
-/// A trait for defining a migration.
-///
-/// # Cot CLI Usage
+/// A builder for renaming a model.
 ///
 /// Typically, you shouldn't need to use this directly. Instead, in most
 /// cases, this can be automatically generated by the Cot CLI.
@@ -1938,833 +3570,2537 @@ impl CustomBuilder {
 /// # Examples
 ///
 /// ```
-/// use cot::db::migrations::{Field, Migration, MigrationDependency, Operation};
-/// use cot::db::{DatabaseField, Identifier};
+/// use cot::db::Identifier;
+/// use cot::db::migrations::Operation;
 ///
-/// struct MyMigration;
+/// # #[tokio::main]
+/// # async fn main() -> cot::Result<()> {
+/// # use cot::db::DatabaseField;
+/// # use cot::db::migrations::Field;
+/// # const CREATE_MODEL_OPERATION: Operation = Operation::create_model()
+/// #     .table_name(Identifier::new("todoapp__my_model"))
+/// #     .fields(&[
+/// #         Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+/// #             .primary_key()
+/// #             .auto(),
+/// #     ])
+/// #     .build();
+/// const OPERATION: Operation = Operation::rename_model()
+///     .old_table_name(Identifier::new("todoapp__my_model"))
+///     .new_table_name(Identifier::new("todoapp__todo_item"))
+///     .build();
 ///
-/// impl Migration for MyMigration {
-///     const APP_NAME: &'static str = "myapp";
-///     const MIGRATION_NAME: &'static str = "m_0001_initial";
-///     const DEPENDENCIES: &'static [MigrationDependency] = &[];
-///     const OPERATIONS: &'static [Operation] = &[Operation::create_model()
-///         .table_name(Identifier::new("todoapp__my_model"))
-///         .fields(&[
-///             Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE).primary_key(),
-///         ])
-///         .build()];
-/// }
+/// # let database = cot::db::Database::new("sqlite::memory:").await?;
+/// # CREATE_MODEL_OPERATION.forwards(&database).await?;
+/// # OPERATION.forwards(&database).await?;
+/// # OPERATION.backwards(&database).await?;
+/// # Ok(())
+/// # }
 /// ```
-pub trait Migration {
-    /// The name of the app that this migration belongs to.
-    const APP_NAME: &'static str;
-
-    /// The name of the migration.
-    const MIGRATION_NAME: &'static str;
-
-    /// The list of dependencies of the migration.
-    const DEPENDENCIES: &'static [MigrationDependency];
-
-    /// The list of operations to apply in the migration.
-    const OPERATIONS: &'static [Operation];
+#[derive(Debug, Copy, Clone)]
+pub struct RenameModelBuilder {
+    old_table_name: Option<Identifier>,
+    new_table_name: Option<Identifier>,
 }
 
-/// A trait for defining a migration that can be dynamically applied.
-///
-/// This is mostly useful for use in the [`MigrationEngine`] to allow
-/// migrations to be dynamically loaded from multiple apps. This can also be
-/// used to implement custom migration loading logic, or to implement
-/// migrations that are not statically defined.
-///
-/// This trait has a blanket implementation for types that implement
-/// [`Migration`].
-pub trait DynMigration {
-    /// The name of the app that this migration belongs to.
-    fn app_name(&self) -> &str;
-
-    /// The name of the migration.
-    fn name(&self) -> &str;
-
-    /// The list of dependencies of the migration.
-    fn dependencies(&self) -> &[MigrationDependency];
-
-    /// The list of operations to apply in the migration.
-    fn operations(&self) -> &[Operation];
+impl Default for RenameModelBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-/// A type alias for a dynamic migration that is both [`Send`] and [`Sync`].
-pub type SyncDynMigration = dyn DynMigration + Send + Sync;
-
-impl<T: Migration + Send + Sync + 'static> DynMigration for T {
-    fn app_name(&self) -> &str {
-        Self::APP_NAME
+impl RenameModelBuilder {
+    #[must_use]
+    const fn new() -> Self {
+        Self {
+            old_table_name: None,
+            new_table_name: None,
+        }
     }
 
-    fn name(&self) -> &str {
-        Self::MIGRATION_NAME
+    /// Sets the current name of the model's table.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
+    ///
+    /// let builder = Operation::rename_model().old_table_name(Identifier::new("todoapp__my_model"));
+    /// ```
+    #[must_use]
+    pub const fn old_table_name(mut self, old_table_name: Identifier) -> Self {
+        self.old_table_name = Some(old_table_name);
+        self
     }
 
-    fn dependencies(&self) -> &[MigrationDependency] {
-        Self::DEPENDENCIES
-    }
-
-    fn operations(&self) -> &[Operation] {
-        Self::OPERATIONS
-    }
-}
-
-impl DynMigration for &dyn DynMigration {
-    fn app_name(&self) -> &str {
-        DynMigration::app_name(*self)
+    /// Sets the name the model's table is renamed to.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
+    ///
+    /// let builder = Operation::rename_model().new_table_name(Identifier::new("todoapp__todo_item"));
+    /// ```
+    #[must_use]
+    pub const fn new_table_name(mut self, new_table_name: Identifier) -> Self {
+        self.new_table_name = Some(new_table_name);
+        self
     }
 
-    fn name(&self) -> &str {
-        DynMigration::name(*self)
+    /// Builds the operation.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
+    ///
+    /// const OPERATION: Operation = Operation::rename_model()
+    ///     .old_table_name(Identifier::new("todoapp__my_model"))
+    ///     .new_table_name(Identifier::new("todoapp__todo_item"))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub const fn build(self) -> Operation {
+        Operation::new(OperationInner::RenameModel {
+            old_table_name: unwrap_builder_option!(self, old_table_name),
+            new_table_name: unwrap_builder_option!(self, new_table_name),
+        })
     }
+}
 
-    fn dependencies(&self) -> &[MigrationDependency] {
-        DynMigration::dependencies(*self)
-    }
+/// A builder for creating an index on a model.
+///
+/// Typically, you shouldn't need to use this directly. Instead, in most
+/// cases, this can be automatically generated by the Cot CLI.
+///
+/// # Examples
+///
+/// ```
+/// use cot::db::Identifier;
+/// use cot::db::migrations::{Index, Operation};
+///
+/// const OPERATION: Operation = Operation::add_index()
+///     .table_name(Identifier::new("todoapp__my_model"))
+///     .index(Index::new(
+///         Identifier::new("todoapp__my_model_name_idx"),
+///         &[Identifier::new("name")],
+///     ))
+///     .build();
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct AddIndexBuilder {
+    table_name: Option<Identifier>,
+    index: Option<Index>,
+}
 
-    fn operations(&self) -> &[Operation] {
-        DynMigration::operations(*self)
+impl Default for AddIndexBuilder {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl DynMigration for &SyncDynMigration {
-    fn app_name(&self) -> &str {
-        DynMigration::app_name(*self)
+impl AddIndexBuilder {
+    #[must_use]
+    const fn new() -> Self {
+        Self {
+            table_name: None,
+            index: None,
+        }
     }
 
-    fn name(&self) -> &str {
-        DynMigration::name(*self)
+    /// Sets the name of the table to create the index on.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
+    ///
+    /// let builder = Operation::add_index().table_name(Identifier::new("todoapp__my_model"));
+    /// ```
+    #[must_use]
+    pub const fn table_name(mut self, table_name: Identifier) -> Self {
+        self.table_name = Some(table_name);
+        self
     }
 
-    fn dependencies(&self) -> &[MigrationDependency] {
-        DynMigration::dependencies(*self)
+    /// Sets the index to create.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::{AddIndexBuilder, Index, Operation};
+    ///
+    /// const BUILDER: AddIndexBuilder = Operation::add_index().index(Index::new(
+    ///     Identifier::new("todoapp__my_model_name_idx"),
+    ///     &[Identifier::new("name")],
+    /// ));
+    /// ```
+    #[must_use]
+    pub const fn index(mut self, index: Index) -> Self {
+        self.index = Some(index);
+        self
     }
 
-    fn operations(&self) -> &[Operation] {
-        DynMigration::operations(*self)
+    /// Builds the operation.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::{Index, Operation};
+    ///
+    /// const OPERATION: Operation = Operation::add_index()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .index(Index::new(
+    ///         Identifier::new("todoapp__my_model_name_idx"),
+    ///         &[Identifier::new("name")],
+    ///     ))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub const fn build(self) -> Operation {
+        Operation::new(OperationInner::AddIndex {
+            table_name: unwrap_builder_option!(self, table_name),
+            index: unwrap_builder_option!(self, index),
+        })
     }
 }
 
-impl DynMigration for Box<dyn DynMigration> {
-    fn app_name(&self) -> &str {
-        DynMigration::app_name(&**self)
-    }
-
-    fn name(&self) -> &str {
-        DynMigration::name(&**self)
-    }
-
-    fn dependencies(&self) -> &[MigrationDependency] {
-        DynMigration::dependencies(&**self)
-    }
-
-    fn operations(&self) -> &[Operation] {
-        DynMigration::operations(&**self)
-    }
+/// A builder for removing an index from a model.
+///
+/// Typically, you shouldn't need to use this directly. Instead, in most
+/// cases, this can be automatically generated by the Cot CLI.
+///
+/// # Examples
+///
+/// ```
+/// use cot::db::Identifier;
+/// use cot::db::migrations::{Index, Operation};
+///
+/// const OPERATION: Operation = Operation::remove_index()
+///     .table_name(Identifier::new("todoapp__my_model"))
+///     .index(Index::new(
+///         Identifier::new("todoapp__my_model_name_idx"),
+///         &[Identifier::new("name")],
+///     ))
+///     .build();
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct RemoveIndexBuilder {
+    table_name: Option<Identifier>,
+    index: Option<Index>,
 }
 
-impl DynMigration for Box<SyncDynMigration> {
-    fn app_name(&self) -> &str {
-        DynMigration::app_name(&**self)
+impl Default for RemoveIndexBuilder {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    fn name(&self) -> &str {
-        DynMigration::name(&**self)
+impl RemoveIndexBuilder {
+    #[must_use]
+    const fn new() -> Self {
+        Self {
+            table_name: None,
+            index: None,
+        }
     }
 
-    fn dependencies(&self) -> &[MigrationDependency] {
-        DynMigration::dependencies(&**self)
+    /// Sets the name of the table to remove the index from.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
+    ///
+    /// let builder = Operation::remove_index().table_name(Identifier::new("todoapp__my_model"));
+    /// ```
+    #[must_use]
+    pub const fn table_name(mut self, table_name: Identifier) -> Self {
+        self.table_name = Some(table_name);
+        self
     }
 
-    fn operations(&self) -> &[Operation] {
-        DynMigration::operations(&**self)
+    /// Sets the index to remove.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::{RemoveIndexBuilder, Index, Operation};
+    ///
+    /// const BUILDER: RemoveIndexBuilder = Operation::remove_index().index(Index::new(
+    ///     Identifier::new("todoapp__my_model_name_idx"),
+    ///     &[Identifier::new("name")],
+    /// ));
+    /// ```
+    #[must_use]
+    pub const fn index(mut self, index: Index) -> Self {
+        self.index = Some(index);
+        self
     }
-}
 
-pub(crate) struct MigrationWrapper(Box<SyncDynMigration>);
-
-impl MigrationWrapper {
+    /// Builds the operation.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::{Index, Operation};
+    ///
+    /// const OPERATION: Operation = Operation::remove_index()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .index(Index::new(
+    ///         Identifier::new("todoapp__my_model_name_idx"),
+    ///         &[Identifier::new("name")],
+    ///     ))
+    ///     .build();
+    /// ```
     #[must_use]
-    pub(crate) fn new<T: DynMigration + Send + Sync + 'static>(migration: T) -> Self {
-        Self(Box::new(migration))
+    pub const fn build(self) -> Operation {
+        Operation::new(OperationInner::RemoveIndex {
+            table_name: unwrap_builder_option!(self, table_name),
+            index: unwrap_builder_option!(self, index),
+        })
     }
 }
 
-impl DynMigration for MigrationWrapper {
-    fn app_name(&self) -> &str {
-        self.0.app_name()
-    }
+/// A builder for adding a constraint to a model.
+///
+/// Typically, you shouldn't need to use this directly. Instead, in most
+/// cases, this can be automatically generated by the Cot CLI.
+///
+/// # Examples
+///
+/// ```
+/// use cot::db::Identifier;
+/// use cot::db::migrations::{Constraint, Operation};
+///
+/// const OPERATION: Operation = Operation::add_constraint()
+///     .table_name(Identifier::new("todoapp__my_model"))
+///     .constraint(Constraint::check(
+///         Identifier::new("todoapp__my_model_price_positive"),
+///         "price > 0",
+///     ))
+///     .build();
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct AddConstraintBuilder {
+    table_name: Option<Identifier>,
+    constraint: Option<Constraint>,
+}
 
-    fn name(&self) -> &str {
-        self.0.name()
+impl Default for AddConstraintBuilder {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    fn dependencies(&self) -> &[MigrationDependency] {
-        self.0.dependencies()
+impl AddConstraintBuilder {
+    #[must_use]
+    const fn new() -> Self {
+        Self {
+            table_name: None,
+            constraint: None,
+        }
     }
 
-    fn operations(&self) -> &[Operation] {
-        self.0.operations()
+    /// Sets the name of the table to add the constraint to.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
+    ///
+    /// let builder = Operation::add_constraint().table_name(Identifier::new("todoapp__my_model"));
+    /// ```
+    #[must_use]
+    pub const fn table_name(mut self, table_name: Identifier) -> Self {
+        self.table_name = Some(table_name);
+        self
     }
-}
 
-impl Debug for MigrationWrapper {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.debug_struct("DynMigrationWrapper")
-            .field("app_name", &self.app_name())
-            .field("migration_name", &self.name())
-            .field("operations", &self.operations())
-            .finish()
+    /// Sets the constraint to add.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::{AddConstraintBuilder, Constraint, Operation};
+    ///
+    /// const BUILDER: AddConstraintBuilder = Operation::add_constraint().constraint(Constraint::check(
+    ///     Identifier::new("todoapp__my_model_price_positive"),
+    ///     "price > 0",
+    /// ));
+    /// ```
+    #[must_use]
+    pub const fn constraint(mut self, constraint: Constraint) -> Self {
+        self.constraint = Some(constraint);
+        self
     }
-}
 
-impl From<ColumnType> for sea_query::ColumnType {
-    fn from(value: ColumnType) -> Self {
-        match value {
-            ColumnType::Boolean => Self::Boolean,
-            ColumnType::TinyInteger => Self::TinyInteger,
-            ColumnType::SmallInteger => Self::SmallInteger,
-            ColumnType::Integer => Self::Integer,
-            ColumnType::BigInteger => Self::BigInteger,
-            ColumnType::TinyUnsignedInteger => Self::TinyUnsigned,
-            ColumnType::SmallUnsignedInteger => Self::SmallUnsigned,
-            ColumnType::UnsignedInteger => Self::Unsigned,
-            ColumnType::BigUnsignedInteger => Self::BigUnsigned,
-            ColumnType::Float => Self::Float,
-            ColumnType::Double => Self::Double,
-            ColumnType::Time => Self::Time,
-            ColumnType::Date => Self::Date,
-            ColumnType::DateTime => Self::DateTime,
-            ColumnType::DateTimeWithTimeZone => Self::TimestampWithTimeZone,
-            ColumnType::Text => Self::Text,
-            ColumnType::Blob => Self::Blob,
-            ColumnType::String(len) => Self::String(StringLen::N(len)),
-        }
+    /// Builds the operation.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::{Constraint, Operation};
+    ///
+    /// const OPERATION: Operation = Operation::add_constraint()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .constraint(Constraint::check(
+    ///         Identifier::new("todoapp__my_model_price_positive"),
+    ///         "price > 0",
+    ///     ))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub const fn build(self) -> Operation {
+        Operation::new(OperationInner::AddConstraint {
+            table_name: unwrap_builder_option!(self, table_name),
+            constraint: unwrap_builder_option!(self, constraint),
+        })
     }
 }
 
-/// A migration dependency: a relationship between two migrations that tells the
-/// migration engine which migrations need to be applied before
-/// others.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub struct MigrationDependency {
-    inner: MigrationDependencyInner,
+/// A builder for removing a constraint from a model.
+///
+/// Typically, you shouldn't need to use this directly. Instead, in most
+/// cases, this can be automatically generated by the Cot CLI.
+///
+/// # Examples
+///
+/// ```
+/// use cot::db::Identifier;
+/// use cot::db::migrations::{Constraint, Operation};
+///
+/// const OPERATION: Operation = Operation::remove_constraint()
+///     .table_name(Identifier::new("todoapp__my_model"))
+///     .constraint(Constraint::check(
+///         Identifier::new("todoapp__my_model_price_positive"),
+///         "price > 0",
+///     ))
+///     .build();
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct RemoveConstraintBuilder {
+    table_name: Option<Identifier>,
+    constraint: Option<Constraint>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum MigrationDependencyInner {
-    Migration {
-        app: &'static str,
-        migration: &'static str,
-    },
-    Model {
-        app: &'static str,
-        table_name: Identifier,
-    },
+impl Default for RemoveConstraintBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl MigrationDependency {
+impl RemoveConstraintBuilder {
     #[must_use]
-    const fn new(inner: MigrationDependencyInner) -> Self {
-        Self { inner }
+    const fn new() -> Self {
+        Self {
+            table_name: None,
+            constraint: None,
+        }
     }
 
-    /// Creates a dependency on another migration.
+    /// Sets the name of the table to remove the constraint from.
     ///
-    /// This ensures that the migration engine will apply the migration with
-    /// given app and migration name before the current migration.
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::Operation;
+    ///
+    /// let builder = Operation::remove_constraint().table_name(Identifier::new("todoapp__my_model"));
+    /// ```
     #[must_use]
-    pub const fn migration(app: &'static str, migration: &'static str) -> Self {
-        Self::new(MigrationDependencyInner::Migration { app, migration })
+    pub const fn table_name(mut self, table_name: Identifier) -> Self {
+        self.table_name = Some(table_name);
+        self
     }
 
-    /// Creates a dependency on a model.
+    /// Sets the constraint to remove.
     ///
-    /// This ensures that the migration engine will apply the migration that
-    /// creates the model with the given app and table name before the current
-    /// migration.
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::{RemoveConstraintBuilder, Constraint, Operation};
+    ///
+    /// const BUILDER: RemoveConstraintBuilder = Operation::remove_constraint().constraint(Constraint::check(
+    ///     Identifier::new("todoapp__my_model_price_positive"),
+    ///     "price > 0",
+    /// ));
+    /// ```
     #[must_use]
-    pub const fn model(app: &'static str, table_name: Identifier) -> Self {
-        Self::new(MigrationDependencyInner::Model { app, table_name })
+    pub const fn constraint(mut self, constraint: Constraint) -> Self {
+        self.constraint = Some(constraint);
+        self
+    }
+
+    /// Builds the operation.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::migrations::{Constraint, Operation};
+    ///
+    /// const OPERATION: Operation = Operation::remove_constraint()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .constraint(Constraint::check(
+    ///         Identifier::new("todoapp__my_model_price_positive"),
+    ///         "price > 0",
+    ///     ))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub const fn build(self) -> Operation {
+        Operation::new(OperationInner::RemoveConstraint {
+            table_name: unwrap_builder_option!(self, table_name),
+            constraint: unwrap_builder_option!(self, constraint),
+        })
     }
 }
 
-/// Wrap a list of statically defined migrations into a dynamic [`Vec`].
+/// A builder for removing a model.
 ///
-/// This is mostly useful for use in [`crate::project::App::migrations`] when
-/// all of your migrations are statically defined (which is the most common
-/// case).
+/// Typically, you shouldn't need to use this directly. Instead, in most
+/// cases, this can be automatically generated by the Cot CLI.
 ///
 /// # Examples
 ///
 /// ```
-/// // m_0001_initial.rs
-/// # mod migrations {
-/// # mod m_0001_initial {
-/// pub struct Migration;
-///
-/// impl ::cot::db::migrations::Migration for Migration {
-///     const APP_NAME: &'static str = "todoapp";
-///     const MIGRATION_NAME: &'static str = "m_0001_initial";
-///     const DEPENDENCIES: &'static [::cot::db::migrations::MigrationDependency] = &[];
-///     const OPERATIONS: &'static [::cot::db::migrations::Operation] = &[
-///         // ...
-///     ];
-/// }
-/// # }
-///
-/// // migrations.rs
-/// pub const MIGRATIONS: &[&::cot::db::migrations::SyncDynMigration] =
-///     &[&m_0001_initial::Migration];
-/// # }
-///
-/// // main.rs
-/// use cot::db::migrations::SyncDynMigration;
-/// use cot::project::App;
+/// use cot::db::migrations::{Field, Operation};
+/// use cot::db::{DatabaseField, Identifier};
 ///
-/// struct MyApp;
+/// # #[tokio::main]
+/// # async fn main() -> cot::Result<()> {
+/// const FIELDS: &[Field] = &[
+///     Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+///         .primary_key()
+///         .auto(),
+///     Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+/// ];
 ///
-/// impl App for MyApp {
-///     fn name(&self) -> &str {
-///         env!("CARGO_PKG_NAME")
-///     }
+/// // First create the table
+/// const CREATE_OPERATION: Operation = Operation::create_model()
+///     .table_name(Identifier::new("todoapp__my_model"))
+///     .fields(FIELDS)
+///     .build();
+/// let database = cot::db::Database::new("sqlite::memory:").await?;
+/// CREATE_OPERATION.forwards(&database).await?;
 ///
-///     fn migrations(&self) -> Vec<Box<SyncDynMigration>> {
-///         cot::db::migrations::wrap_migrations(&migrations::MIGRATIONS)
-///     }
-/// }
+/// // Then remove it
+/// const OPERATION: Operation = Operation::remove_model()
+///     .table_name(Identifier::new("todoapp__my_model"))
+///     .fields(FIELDS)
+///     .build();
 ///
-/// # #[tokio::main]
-/// # async fn main() -> cot::Result<()> {
-/// # let engine = cot::db::migrations::MigrationEngine::new(MyApp.migrations())?;
-/// # let database = cot::db::Database::new("sqlite::memory:").await?;
-/// # engine.run(&database).await?;
+/// OPERATION.forwards(&database).await?;
 /// # Ok(())
 /// # }
 /// ```
-pub fn wrap_migrations(migrations: &[&'static SyncDynMigration]) -> Vec<Box<SyncDynMigration>> {
-    #[expect(trivial_casts)] // cast to the correct trait object type
-    migrations
-        .iter()
-        .copied()
-        .map(|x| Box::new(x) as Box<SyncDynMigration>)
-        .collect()
+#[derive(Debug, Copy, Clone)]
+pub struct RemoveModelBuilder {
+    table_name: Option<Identifier>,
+    fields: Option<&'static [Field]>,
 }
 
-#[derive(Debug)]
-#[model(table_name = "cot__migrations", model_type = "internal")]
-struct AppliedMigration {
-    #[model(primary_key)]
+impl Default for RemoveModelBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl RemoveModelBuilder {
+    #[must_use]
+    const fn new() -> Self {
+        Self {
+            table_name: None,
+            fields: None,
+        }
+    }
+
+    /// Sets the name of the table to remove.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::migrations::{Field, Operation};
+    /// use cot::db::{DatabaseField, Identifier};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// const FIELDS: &[Field] = &[
+    ///     Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+    ///         .primary_key()
+    ///         .auto(),
+    ///     Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+    /// ];
+    ///
+    /// // First create the table
+    /// const CREATE_OPERATION: Operation = Operation::create_model()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .fields(FIELDS)
+    ///     .build();
+    /// let database = cot::db::Database::new("sqlite::memory:").await?;
+    /// CREATE_OPERATION.forwards(&database).await?;
+    ///
+    /// // Then remove it
+    /// const OPERATION: Operation = Operation::remove_model()
+    ///     .table_name(Identifier::new("todoapp__my_model"))
+    ///     .fields(FIELDS)
+    ///     .build();
+    ///
+    /// OPERATION.forwards(&database).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub const fn table_name(mut self, table_name: Identifier) -> Self {
+        self.table_name = Some(table_name);
+        self
+    }
+
+    /// Sets the fields to store for potential backwards migration.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    #[must_use]
+    pub const fn fields(mut self, fields: &'static [Field]) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Builds the operation.
+    ///
+    /// # Cot CLI Usage
+    ///
+    /// Typically, you shouldn't need to use this directly. Instead, in most
+    /// cases, this can be automatically generated by the Cot CLI.
+    #[must_use]
+    pub const fn build(self) -> Operation {
+        Operation::new(OperationInner::RemoveModel {
+            table_name: unwrap_builder_option!(self, table_name),
+            fields: unwrap_builder_option!(self, fields),
+        })
+    }
+}
+
+/// A builder for a custom operation.
+///
+/// # Examples
+///
+/// ```
+/// use cot::db::Result;
+/// use cot::db::migrations::{MigrationContext, Operation, migration_op};
+///
+/// #[migration_op]
+/// async fn forwards(ctx: MigrationContext<'_>) -> Result<()> {
+///     // do something
+///     Ok(())
+/// }
+///
+/// #[migration_op]
+/// async fn backwards(ctx: MigrationContext<'_>) -> Result<()> {
+///     // undo something
+///     Ok(())
+/// }
+///
+/// const OPERATION: Operation = Operation::custom(forwards).backwards(backwards).build();
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct CustomBuilder {
+    forwards: CustomOperationFn,
+    backwards: Option<CustomOperationFn>,
+}
+
+impl CustomBuilder {
+    #[must_use]
+    const fn new(forwards: CustomOperationFn) -> Self {
+        Self {
+            forwards,
+            backwards: None,
+        }
+    }
+
+    /// Sets the backwards operation.
+    #[must_use]
+    pub const fn backwards(mut self, backwards: CustomOperationFn) -> Self {
+        self.backwards = Some(backwards);
+        self
+    }
+
+    /// Builds the operation.
+    #[must_use]
+    pub const fn build(self) -> Operation {
+        Operation::new(OperationInner::Custom {
+            forwards: self.forwards,
+            backwards: self.backwards,
+        })
+    }
+}
+
+/// A trait for defining a migration.
+///
+/// # Cot CLI Usage
+///
+/// Typically, you shouldn't need to use this directly. Instead, in most
+/// cases, this can be automatically generated by the Cot CLI.
+///
+/// # Examples
+///
+/// ```
+/// use cot::db::migrations::{Field, Migration, MigrationDependency, Operation};
+/// use cot::db::{DatabaseField, Identifier};
+///
+/// struct MyMigration;
+///
+/// impl Migration for MyMigration {
+///     const APP_NAME: &'static str = "myapp";
+///     const MIGRATION_NAME: &'static str = "m_0001_initial";
+///     const DEPENDENCIES: &'static [MigrationDependency] = &[];
+///     const OPERATIONS: &'static [Operation] = &[Operation::create_model()
+///         .table_name(Identifier::new("todoapp__my_model"))
+///         .fields(&[
+///             Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE).primary_key(),
+///         ])
+///         .build()];
+/// }
+/// ```
+pub trait Migration {
+    /// The name of the app that this migration belongs to.
+    const APP_NAME: &'static str;
+
+    /// The name of the migration.
+    const MIGRATION_NAME: &'static str;
+
+    /// The list of dependencies of the migration.
+    const DEPENDENCIES: &'static [MigrationDependency];
+
+    /// The list of operations to apply in the migration.
+    const OPERATIONS: &'static [Operation];
+
+    /// The names of the migrations of the same app that this migration
+    /// replaces.
+    ///
+    /// This is set for migrations squashed from a range of other migrations
+    /// (see `cot migration squash`). The squashed migration is treated as
+    /// applied when all the migrations it replaces are, and the other way
+    /// around; this means it can be deployed both to databases that already
+    /// have the original migrations applied, and to the new ones.
+    const REPLACES: &'static [&'static str] = &[];
+
+    /// Whether the migration is applied and rolled back atomically.
+    ///
+    /// If `true` (the default), the operations of the migration and the record
+    /// of it being applied are run in a single transaction, so that a failing
+    /// migration doesn't leave the database half-migrated. This is only the
+    /// case for the database backends supporting transactional schema
+    /// changes, i.e. SQLite and PostgreSQL; on MySQL, each schema change is
+    /// committed immediately.
+    ///
+    /// Set this to `false` for the migrations that can't run in a
+    /// transaction, such as the ones creating PostgreSQL indexes
+    /// concurrently, or the ones with custom operations starting their own
+    /// transactions.
+    const ATOMIC: bool = true;
+}
+
+/// A trait for defining a migration that can be dynamically applied.
+///
+/// This is mostly useful for use in the [`MigrationEngine`] to allow
+/// migrations to be dynamically loaded from multiple apps. This can also be
+/// used to implement custom migration loading logic, or to implement
+/// migrations that are not statically defined.
+///
+/// This trait has a blanket implementation for types that implement
+/// [`Migration`].
+pub trait DynMigration {
+    /// The name of the app that this migration belongs to.
+    fn app_name(&self) -> &str;
+
+    /// The name of the migration.
+    fn name(&self) -> &str;
+
+    /// The list of dependencies of the migration.
+    fn dependencies(&self) -> &[MigrationDependency];
+
+    /// The list of operations to apply in the migration.
+    fn operations(&self) -> &[Operation];
+
+    /// The names of the migrations of the same app that this migration
+    /// replaces.
+    ///
+    /// See [`Migration::REPLACES`] for more details.
+    fn replaces(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Whether the migration is applied and rolled back atomically.
+    ///
+    /// See [`Migration::ATOMIC`] for more details.
+    fn atomic(&self) -> bool {
+        true
+    }
+}
+
+/// A type alias for a dynamic migration that is both [`Send`] and [`Sync`].
+pub type SyncDynMigration = dyn DynMigration + Send + Sync;
+
+impl<T: Migration + Send + Sync + 'static> DynMigration for T {
+    fn app_name(&self) -> &str {
+        Self::APP_NAME
+    }
+
+    fn name(&self) -> &str {
+        Self::MIGRATION_NAME
+    }
+
+    fn dependencies(&self) -> &[MigrationDependency] {
+        Self::DEPENDENCIES
+    }
+
+    fn operations(&self) -> &[Operation] {
+        Self::OPERATIONS
+    }
+
+    fn replaces(&self) -> &[&str] {
+        Self::REPLACES
+    }
+
+    fn atomic(&self) -> bool {
+        Self::ATOMIC
+    }
+}
+
+impl DynMigration for &dyn DynMigration {
+    fn app_name(&self) -> &str {
+        DynMigration::app_name(*self)
+    }
+
+    fn name(&self) -> &str {
+        DynMigration::name(*self)
+    }
+
+    fn dependencies(&self) -> &[MigrationDependency] {
+        DynMigration::dependencies(*self)
+    }
+
+    fn operations(&self) -> &[Operation] {
+        DynMigration::operations(*self)
+    }
+
+    fn replaces(&self) -> &[&str] {
+        DynMigration::replaces(*self)
+    }
+
+    fn atomic(&self) -> bool {
+        DynMigration::atomic(*self)
+    }
+}
+
+impl DynMigration for &SyncDynMigration {
+    fn app_name(&self) -> &str {
+        DynMigration::app_name(*self)
+    }
+
+    fn name(&self) -> &str {
+        DynMigration::name(*self)
+    }
+
+    fn dependencies(&self) -> &[MigrationDependency] {
+        DynMigration::dependencies(*self)
+    }
+
+    fn operations(&self) -> &[Operation] {
+        DynMigration::operations(*self)
+    }
+
+    fn replaces(&self) -> &[&str] {
+        DynMigration::replaces(*self)
+    }
+
+    fn atomic(&self) -> bool {
+        DynMigration::atomic(*self)
+    }
+}
+
+impl DynMigration for Box<dyn DynMigration> {
+    fn app_name(&self) -> &str {
+        DynMigration::app_name(&**self)
+    }
+
+    fn name(&self) -> &str {
+        DynMigration::name(&**self)
+    }
+
+    fn dependencies(&self) -> &[MigrationDependency] {
+        DynMigration::dependencies(&**self)
+    }
+
+    fn operations(&self) -> &[Operation] {
+        DynMigration::operations(&**self)
+    }
+
+    fn replaces(&self) -> &[&str] {
+        DynMigration::replaces(&**self)
+    }
+
+    fn atomic(&self) -> bool {
+        DynMigration::atomic(&**self)
+    }
+}
+
+impl DynMigration for Box<SyncDynMigration> {
+    fn app_name(&self) -> &str {
+        DynMigration::app_name(&**self)
+    }
+
+    fn name(&self) -> &str {
+        DynMigration::name(&**self)
+    }
+
+    fn dependencies(&self) -> &[MigrationDependency] {
+        DynMigration::dependencies(&**self)
+    }
+
+    fn operations(&self) -> &[Operation] {
+        DynMigration::operations(&**self)
+    }
+
+    fn replaces(&self) -> &[&str] {
+        DynMigration::replaces(&**self)
+    }
+
+    fn atomic(&self) -> bool {
+        DynMigration::atomic(&**self)
+    }
+}
+
+pub(crate) struct MigrationWrapper(Box<SyncDynMigration>);
+
+impl MigrationWrapper {
+    #[must_use]
+    pub(crate) fn new<T: DynMigration + Send + Sync + 'static>(migration: T) -> Self {
+        Self(Box::new(migration))
+    }
+}
+
+impl DynMigration for MigrationWrapper {
+    fn app_name(&self) -> &str {
+        self.0.app_name()
+    }
+
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    fn dependencies(&self) -> &[MigrationDependency] {
+        self.0.dependencies()
+    }
+
+    fn operations(&self) -> &[Operation] {
+        self.0.operations()
+    }
+
+    fn replaces(&self) -> &[&str] {
+        self.0.replaces()
+    }
+
+    fn atomic(&self) -> bool {
+        self.0.atomic()
+    }
+}
+
+impl Debug for MigrationWrapper {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynMigrationWrapper")
+            .field("app_name", &self.app_name())
+            .field("migration_name", &self.name())
+            .field("operations", &self.operations())
+            .field("replaces", &self.replaces())
+            .field("atomic", &self.atomic())
+            .finish()
+    }
+}
+
+impl From<ColumnType> for sea_query::ColumnType {
+    fn from(value: ColumnType) -> Self {
+        match value {
+            ColumnType::Boolean => Self::Boolean,
+            ColumnType::TinyInteger => Self::TinyInteger,
+            ColumnType::SmallInteger => Self::SmallInteger,
+            ColumnType::Integer => Self::Integer,
+            ColumnType::BigInteger => Self::BigInteger,
+            ColumnType::TinyUnsignedInteger => Self::TinyUnsigned,
+            ColumnType::SmallUnsignedInteger => Self::SmallUnsigned,
+            ColumnType::UnsignedInteger => Self::Unsigned,
+            ColumnType::BigUnsignedInteger => Self::BigUnsigned,
+            ColumnType::Float => Self::Float,
+            ColumnType::Double => Self::Double,
+            ColumnType::Time => Self::Time,
+            ColumnType::Date => Self::Date,
+            ColumnType::DateTime => Self::DateTime,
+            ColumnType::DateTimeWithTimeZone => Self::TimestampWithTimeZone,
+            ColumnType::Text => Self::Text,
+            ColumnType::Blob => Self::Blob,
+            ColumnType::String(len) => Self::String(StringLen::N(len)),
+            ColumnType::Uuid => Self::Uuid,
+            ColumnType::Decimal => Self::Decimal(None),
+            ColumnType::Json => Self::JsonBinary,
+            ColumnType::Array(element_type) => {
+                Self::Array(sea_query::RcOrArc::new((*element_type).into()))
+            }
+        }
+    }
+}
+
+/// A migration dependency: a relationship between two migrations that tells the
+/// migration engine which migrations need to be applied before
+/// others.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MigrationDependency {
+    inner: MigrationDependencyInner,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum MigrationDependencyInner {
+    Migration {
+        app: &'static str,
+        migration: &'static str,
+    },
+    Model {
+        app: &'static str,
+        table_name: Identifier,
+    },
+}
+
+impl MigrationDependency {
+    #[must_use]
+    const fn new(inner: MigrationDependencyInner) -> Self {
+        Self { inner }
+    }
+
+    /// Creates a dependency on another migration.
+    ///
+    /// This ensures that the migration engine will apply the migration with
+    /// given app and migration name before the current migration.
+    #[must_use]
+    pub const fn migration(app: &'static str, migration: &'static str) -> Self {
+        Self::new(MigrationDependencyInner::Migration { app, migration })
+    }
+
+    /// Creates a dependency on a model.
+    ///
+    /// This ensures that the migration engine will apply the migration that
+    /// creates the model with the given app and table name before the current
+    /// migration.
+    #[must_use]
+    pub const fn model(app: &'static str, table_name: Identifier) -> Self {
+        Self::new(MigrationDependencyInner::Model { app, table_name })
+    }
+}
+
+/// Wrap a list of statically defined migrations into a dynamic [`Vec`].
+///
+/// This is mostly useful for use in [`crate::project::App::migrations`] when
+/// all of your migrations are statically defined (which is the most common
+/// case).
+///
+/// # Examples
+///
+/// ```
+/// // m_0001_initial.rs
+/// # mod migrations {
+/// # mod m_0001_initial {
+/// pub struct Migration;
+///
+/// impl ::cot::db::migrations::Migration for Migration {
+///     const APP_NAME: &'static str = "todoapp";
+///     const MIGRATION_NAME: &'static str = "m_0001_initial";
+///     const DEPENDENCIES: &'static [::cot::db::migrations::MigrationDependency] = &[];
+///     const OPERATIONS: &'static [::cot::db::migrations::Operation] = &[
+///         // ...
+///     ];
+/// }
+/// # }
+///
+/// // migrations.rs
+/// pub const MIGRATIONS: &[&::cot::db::migrations::SyncDynMigration] =
+///     &[&m_0001_initial::Migration];
+/// # }
+///
+/// // main.rs
+/// use cot::db::migrations::SyncDynMigration;
+/// use cot::project::App;
+///
+/// struct MyApp;
+///
+/// impl App for MyApp {
+///     fn name(&self) -> &str {
+///         env!("CARGO_PKG_NAME")
+///     }
+///
+///     fn migrations(&self) -> Vec<Box<SyncDynMigration>> {
+///         cot::db::migrations::wrap_migrations(&migrations::MIGRATIONS)
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> cot::Result<()> {
+/// # let engine = cot::db::migrations::MigrationEngine::new(MyApp.migrations())?;
+/// # let database = cot::db::Database::new("sqlite::memory:").await?;
+/// # engine.run(&database).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn wrap_migrations(migrations: &[&'static SyncDynMigration]) -> Vec<Box<SyncDynMigration>> {
+    #[expect(trivial_casts)] // cast to the correct trait object type
+    migrations
+        .iter()
+        .copied()
+        .map(|x| Box::new(x) as Box<SyncDynMigration>)
+        .collect()
+}
+
+#[derive(Debug)]
+#[model(table_name = "cot__migrations", model_type = "internal")]
+struct AppliedMigration {
+    #[model(primary_key)]
     id: Auto<i32>,
     app: String,
     name: String,
     applied: chrono::DateTime<chrono::FixedOffset>,
 }
 
-const CREATE_APPLIED_MIGRATIONS_MIGRATION: Operation = Operation::create_model()
-    .table_name(Identifier::new("cot__migrations"))
-    .fields(&[
-        Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+const CREATE_APPLIED_MIGRATIONS_MIGRATION: Operation = Operation::create_model()
+    .table_name(Identifier::new("cot__migrations"))
+    .fields(&[
+        Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+            .primary_key()
+            .auto(),
+        Field::new(Identifier::new("app"), <String as DatabaseField>::TYPE),
+        Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+        Field::new(
+            Identifier::new("applied"),
+            <chrono::DateTime<chrono::FixedOffset> as DatabaseField>::TYPE,
+        ),
+    ])
+    .if_not_exists()
+    .build();
+
+#[cfg(test)]
+mod tests {
+    use cot::test::TestDatabase;
+
+    use super::*;
+    use crate::db::{ColumnType, DatabaseError, DatabaseField, Identifier, Model};
+
+    #[model]
+    struct ApplicationModel {
+        #[model(primary_key)]
+        id: i32,
+    }
+
+    #[model(model_type = "migration")]
+    struct _MigrationModel {
+        #[model(primary_key)]
+        id: i32,
+    }
+
+    struct TestMigration;
+
+    impl Migration for TestMigration {
+        const APP_NAME: &'static str = "testapp";
+        const MIGRATION_NAME: &'static str = "m_0001_initial";
+        const DEPENDENCIES: &'static [MigrationDependency] = &[];
+        const OPERATIONS: &'static [Operation] = &[Operation::create_model()
+            .table_name(Identifier::new("testapp__test_model"))
+            .fields(&[
+                Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+                    .primary_key()
+                    .auto(),
+                Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+            ])
+            .build()];
+    }
+
+    struct DummyMigration;
+
+    impl Migration for DummyMigration {
+        const APP_NAME: &'static str = "testapp";
+        const MIGRATION_NAME: &'static str = "m_0002_custom";
+        const DEPENDENCIES: &'static [MigrationDependency] = &[];
+        const OPERATIONS: &'static [Operation] = &[];
+    }
+
+    #[cot_macros::dbtest]
+    async fn test_migration_engine_run(test_db: &mut TestDatabase) {
+        let engine = MigrationEngine::new([TestMigration]).unwrap();
+
+        let result = engine.run(&test_db.database()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[cot_macros::dbtest]
+    async fn test_migration_engine_multiple_migrations_run(test_db: &mut TestDatabase) {
+        #[expect(trivial_casts)] // cast to the correct trait object type
+        let engine = MigrationEngine::new([
+            &TestMigration as &SyncDynMigration,
+            &DummyMigration as &SyncDynMigration,
+        ])
+        .unwrap();
+
+        let result = engine.run(&test_db.database()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_operation_create_model() {
+        const OPERATION_CREATE_MODEL_FIELDS: &[Field; 2] = &[
+            Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+        ];
+
+        let operation = Operation::create_model()
+            .table_name(Identifier::new("testapp__test_model"))
+            .fields(OPERATION_CREATE_MODEL_FIELDS)
+            .build();
+
+        if let OperationInner::CreateModel {
+            table_name,
+            fields,
+            if_not_exists,
+        } = operation.inner
+        {
+            assert_eq!(table_name.to_string(), "testapp__test_model");
+            assert_eq!(fields.len(), 2);
+            assert!(!if_not_exists);
+        } else {
+            panic!("Expected OperationInner::CreateModel");
+        }
+    }
+
+    #[test]
+    fn test_operation_remove_model() {
+        const MODEL_FIELDS: &[Field; 2] = &[
+            Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+        ];
+
+        let operation = Operation::remove_model()
+            .table_name(Identifier::new("testapp__test_model"))
+            .fields(MODEL_FIELDS)
+            .build();
+
+        if let OperationInner::RemoveModel { table_name, fields } = operation.inner {
+            assert_eq!(table_name.to_string(), "testapp__test_model");
+            assert_eq!(fields.len(), 2);
+        } else {
+            panic!("Expected OperationInner::RemoveModel");
+        }
+    }
+
+    #[test]
+    fn test_operation_add_field() {
+        let operation = Operation::add_field()
+            .table_name(Identifier::new("testapp__test_model"))
+            .field(Field::new(
+                Identifier::new("age"),
+                <i32 as DatabaseField>::TYPE,
+            ))
+            .build();
+
+        if let OperationInner::AddField { table_name, field } = operation.inner {
+            assert_eq!(table_name.to_string(), "testapp__test_model");
+            assert_eq!(field.name.to_string(), "age");
+        } else {
+            panic!("Expected OperationInner::AddField");
+        }
+    }
+
+    #[cot::test]
+    #[cfg_attr(
+        miri,
+        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
+    )]
+    async fn operation_custom() {
+        // test only on SQLite because we are using raw SQL
+        let test_db = TestDatabase::new_sqlite().await.unwrap();
+
+        #[migration_op]
+        async fn forwards(ctx: MigrationContext<'_>) -> Result<()> {
+            ctx.db
+                .raw("CREATE TABLE custom_test (id INTEGER PRIMARY KEY)")
+                .await?;
+            Ok(())
+        }
+
+        let operation = Operation::custom(forwards).build();
+        operation.forwards(&test_db.database()).await.unwrap();
+
+        let result = test_db.database().raw("SELECT * FROM custom_test").await;
+        assert!(result.is_ok());
+    }
+
+    #[cot::test]
+    #[cfg_attr(
+        miri,
+        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
+    )]
+    async fn operation_custom_model_access() {
+        let test_db = TestDatabase::new_sqlite().await.unwrap();
+
+        let result = _MigrationModel::objects().all(&test_db.database()).await;
+        assert!(matches!(
+            result,
+            Err(DatabaseError::MigrationError(MigrationEngineError::Custom(
+                _
+            )))
+        ));
+
+        #[migration_op]
+        async fn application_model(ctx: MigrationContext<'_>) -> Result<()> {
+            ApplicationModel::objects().all(ctx.db).await?;
+            Ok(())
+        }
+
+        let result = Operation::custom(application_model)
+            .build()
+            .forwards(&test_db.database())
+            .await;
+        assert!(matches!(
+            result,
+            Err(DatabaseError::MigrationError(MigrationEngineError::Custom(
+                _
+            )))
+        ));
+
+        test_db
+            .database()
+            .raw("CREATE TABLE cot__migration_model (id INTEGER PRIMARY KEY)")
+            .await
+            .unwrap();
+
+        #[migration_op]
+        async fn migration_model(ctx: MigrationContext<'_>) -> Result<()> {
+            _MigrationModel::objects().all(ctx.db).await?;
+            Ok(())
+        }
+
+        Operation::custom(migration_model)
+            .build()
+            .forwards(&test_db.database())
+            .await
+            .unwrap();
+    }
+
+    #[cot::test]
+    #[cfg_attr(
+        miri,
+        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
+    )]
+    async fn operation_custom_backwards() {
+        // test only on SQLite because we are using raw SQL
+        let test_db = TestDatabase::new_sqlite().await.unwrap();
+
+        #[migration_op]
+        async fn forwards(_ctx: MigrationContext<'_>) -> Result<()> {
+            panic!("this should not be called");
+        }
+
+        #[migration_op]
+        async fn backwards(ctx: MigrationContext<'_>) -> Result<()> {
+            ctx.db.raw("DROP TABLE custom_test_back").await?;
+            Ok(())
+        }
+
+        test_db
+            .database()
+            .raw("CREATE TABLE custom_test_back (id INTEGER PRIMARY KEY)")
+            .await
+            .unwrap();
+
+        let operation = Operation::custom(forwards).backwards(backwards).build();
+        operation.backwards(&test_db.database()).await.unwrap();
+
+        let result = test_db
+            .database()
+            .raw("SELECT * FROM custom_test_back")
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[cot::test]
+    #[cfg_attr(
+        miri,
+        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
+    )]
+    async fn operation_custom_backwards_not_implemented() {
+        // test only on SQLite because we are using raw SQL
+        let test_db = TestDatabase::new_sqlite().await.unwrap();
+
+        #[migration_op]
+        async fn forwards(_ctx: MigrationContext<'_>) -> Result<()> {
+            Ok(())
+        }
+
+        let operation = Operation::custom(forwards).build();
+        let result = operation.backwards(&test_db.database()).await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn field_new() {
+        let field = Field::new(Identifier::new("id"), ColumnType::Integer)
             .primary_key()
-            .auto(),
-        Field::new(Identifier::new("app"), <String as DatabaseField>::TYPE),
-        Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
-        Field::new(
-            Identifier::new("applied"),
-            <chrono::DateTime<chrono::FixedOffset> as DatabaseField>::TYPE,
-        ),
-    ])
-    .if_not_exists()
-    .build();
+            .auto()
+            .null();
+
+        assert_eq!(field.name.to_string(), "id");
+        assert_eq!(field.ty, ColumnType::Integer);
+        assert!(field.primary_key);
+        assert!(field.auto_value);
+        assert!(field.null);
+    }
+
+    #[test]
+    fn field_foreign_key() {
+        let field = Field::new(Identifier::new("parent"), ColumnType::Integer).foreign_key(
+            Identifier::new("testapp__parent"),
+            Identifier::new("id"),
+            ForeignKeyOnDeletePolicy::Restrict,
+            ForeignKeyOnUpdatePolicy::Restrict,
+        );
 
-#[cfg(test)]
-mod tests {
-    use cot::test::TestDatabase;
+        assert_eq!(
+            field.foreign_key,
+            Some(ForeignKeyReference {
+                model: Identifier::new("testapp__parent"),
+                field: Identifier::new("id"),
+                on_delete: ForeignKeyOnDeletePolicy::Restrict,
+                on_update: ForeignKeyOnUpdatePolicy::Restrict,
+            })
+        );
+    }
 
-    use super::*;
-    use crate::db::{ColumnType, DatabaseError, DatabaseField, Identifier, Model};
+    #[test]
+    fn test_migration_wrapper() {
+        let migration = MigrationWrapper::new(TestMigration);
 
-    #[model]
-    struct ApplicationModel {
-        #[model(primary_key)]
-        id: i32,
+        assert_eq!(migration.app_name(), "testapp");
+        assert_eq!(migration.name(), "m_0001_initial");
+        assert_eq!(migration.operations().len(), 1);
     }
 
-    #[model(model_type = "migration")]
-    struct _MigrationModel {
-        #[model(primary_key)]
-        id: i32,
+    #[test]
+    fn test_field_to_column_def() {
+        let field = Field::new(Identifier::new("id"), ColumnType::Integer)
+            .primary_key()
+            .auto()
+            .null()
+            .unique();
+
+        let mut mapper = MockColumnTypeMapper::new();
+        mapper
+            .expect_sea_query_column_type_for()
+            .return_const(sea_query::ColumnType::Integer);
+        let column_def = field.as_column_def(&mapper);
+
+        assert_eq!(column_def.get_column_name(), "id");
+        assert_eq!(
+            column_def.get_column_type(),
+            Some(&sea_query::ColumnType::Integer)
+        );
+        let spec = column_def.get_column_spec();
+        assert!(spec.primary_key);
+        assert!(spec.auto_increment);
+        assert_eq!(spec.nullable, Some(true));
+        assert!(spec.unique);
     }
 
-    struct TestMigration;
+    #[test]
+    fn test_field_to_column_def_without_options() {
+        let field = Field::new(Identifier::new("name"), ColumnType::Text);
 
-    impl Migration for TestMigration {
-        const APP_NAME: &'static str = "testapp";
-        const MIGRATION_NAME: &'static str = "m_0001_initial";
-        const DEPENDENCIES: &'static [MigrationDependency] = &[];
-        const OPERATIONS: &'static [Operation] = &[Operation::create_model()
-            .table_name(Identifier::new("testapp__test_model"))
-            .fields(&[
-                Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
-                    .primary_key()
-                    .auto(),
-                Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
-            ])
-            .build()];
+        let mut mapper = MockColumnTypeMapper::new();
+        mapper
+            .expect_sea_query_column_type_for()
+            .return_const(sea_query::ColumnType::Text);
+        let column_def = field.as_column_def(&mapper);
+
+        assert_eq!(column_def.get_column_name(), "name");
+        assert_eq!(
+            column_def.get_column_type(),
+            Some(&sea_query::ColumnType::Text)
+        );
+        let spec = column_def.get_column_spec();
+        assert!(!spec.primary_key);
+        assert!(!spec.auto_increment);
+        assert_ne!(spec.nullable, Some(true));
+        assert!(!spec.unique);
+        assert_eq!(spec.default, None);
     }
 
-    struct DummyMigration;
+    #[test]
+    fn test_field_to_column_def_with_default() {
+        let field = Field::new(Identifier::new("created_at"), ColumnType::DateTime)
+            .default(DefaultValue::Expr("CURRENT_TIMESTAMP"));
 
-    impl Migration for DummyMigration {
-        const APP_NAME: &'static str = "testapp";
-        const MIGRATION_NAME: &'static str = "m_0002_custom";
-        const DEPENDENCIES: &'static [MigrationDependency] = &[];
-        const OPERATIONS: &'static [Operation] = &[];
+        let mut mapper = MockColumnTypeMapper::new();
+        mapper
+            .expect_sea_query_column_type_for()
+            .return_const(sea_query::ColumnType::DateTime);
+        mapper
+            .expect_sea_query_default_for()
+            .returning(sea_query::Expr::from);
+        let column_def = field.as_column_def(&mapper);
+
+        assert_eq!(
+            column_def.get_column_spec().default,
+            Some(sea_query::Expr::cust("CURRENT_TIMESTAMP"))
+        );
     }
 
-    #[cot_macros::dbtest]
-    async fn test_migration_engine_run(test_db: &mut TestDatabase) {
-        let engine = MigrationEngine::new([TestMigration]).unwrap();
+    #[test]
+    fn test_default_value_to_expr() {
+        assert_eq!(
+            sea_query::Expr::from(DefaultValue::Bool(true)),
+            sea_query::Expr::val(true)
+        );
+        assert_eq!(
+            sea_query::Expr::from(DefaultValue::Int(-1)),
+            sea_query::Expr::val(-1_i64)
+        );
+        assert_eq!(
+            sea_query::Expr::from(DefaultValue::Float(0.5)),
+            sea_query::Expr::val(0.5_f64)
+        );
+        assert_eq!(
+            sea_query::Expr::from(DefaultValue::String("draft")),
+            sea_query::Expr::val("draft")
+        );
+    }
 
-        let result = engine.run(&test_db.database()).await;
+    #[test]
+    fn test_operation_remove_field() {
+        let operation = Operation::remove_field()
+            .table_name(Identifier::new("testapp__test_model"))
+            .field(Field::new(
+                Identifier::new("name"),
+                <String as DatabaseField>::TYPE,
+            ))
+            .build();
 
-        assert!(result.is_ok());
+        if let OperationInner::RemoveField { table_name, field } = operation.inner {
+            assert_eq!(table_name.to_string(), "testapp__test_model");
+            assert_eq!(field.name.to_string(), "name");
+            assert_eq!(field.ty, ColumnType::Text);
+        } else {
+            panic!("Expected OperationInner::RemoveField");
+        }
     }
 
     #[cot_macros::dbtest]
-    async fn test_migration_engine_multiple_migrations_run(test_db: &mut TestDatabase) {
-        #[expect(trivial_casts)] // cast to the correct trait object type
-        let engine = MigrationEngine::new([
-            &TestMigration as &SyncDynMigration,
-            &DummyMigration as &SyncDynMigration,
-        ])
-        .unwrap();
+    async fn test_remove_field_operation_forwards(test_db: &mut TestDatabase) {
+        const FIELDS: &[Field] = &[
+            Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+        ];
 
-        let result = engine.run(&test_db.database()).await;
+        let create_operation = Operation::create_model()
+            .table_name(Identifier::new("testapp__test_model"))
+            .fields(FIELDS)
+            .build();
+
+        create_operation
+            .forwards(&test_db.database())
+            .await
+            .unwrap();
+
+        let remove_operation = Operation::remove_field()
+            .table_name(Identifier::new("testapp__test_model"))
+            .field(Field::new(
+                Identifier::new("name"),
+                <String as DatabaseField>::TYPE,
+            ))
+            .build();
 
+        let result = remove_operation.forwards(&test_db.database()).await;
         assert!(result.is_ok());
     }
 
-    #[test]
-    fn test_operation_create_model() {
-        const OPERATION_CREATE_MODEL_FIELDS: &[Field; 2] = &[
+    #[cot_macros::dbtest]
+    async fn test_remove_field_operation_backwards(test_db: &mut TestDatabase) {
+        const FIELDS: &[Field] = &[
             Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
                 .primary_key()
                 .auto(),
-            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
         ];
 
-        let operation = Operation::create_model()
+        let create_operation = Operation::create_model()
             .table_name(Identifier::new("testapp__test_model"))
-            .fields(OPERATION_CREATE_MODEL_FIELDS)
+            .fields(FIELDS)
             .build();
 
-        if let OperationInner::CreateModel {
-            table_name,
-            fields,
-            if_not_exists,
-        } = operation.inner
-        {
-            assert_eq!(table_name.to_string(), "testapp__test_model");
-            assert_eq!(fields.len(), 2);
-            assert!(!if_not_exists);
-        } else {
-            panic!("Expected OperationInner::CreateModel");
-        }
+        create_operation
+            .forwards(&test_db.database())
+            .await
+            .unwrap();
+
+        let remove_operation = Operation::remove_field()
+            .table_name(Identifier::new("testapp__test_model"))
+            .field(Field::new(
+                Identifier::new("name"),
+                <String as DatabaseField>::TYPE,
+            ))
+            .build();
+
+        let result = remove_operation.backwards(&test_db.database()).await;
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_remove_field_builder_new() {
+        let builder = RemoveFieldBuilder::new();
+        assert!(builder.table_name.is_none());
+        assert!(builder.field.is_none());
+    }
+
+    #[test]
+    fn test_remove_field_builder_table_name() {
+        let builder = RemoveFieldBuilder::new().table_name(Identifier::new("testapp__test_model"));
+
+        assert_eq!(
+            builder.table_name.unwrap().to_string(),
+            "testapp__test_model"
+        );
+    }
+
+    #[test]
+    fn test_remove_field_builder_field() {
+        let builder = RemoveFieldBuilder::new().field(Field::new(
+            Identifier::new("name"),
+            <String as DatabaseField>::TYPE,
+        ));
+
+        let field = builder.field.unwrap();
+        assert_eq!(field.name.to_string(), "name");
+        assert_eq!(field.ty, ColumnType::Text);
+    }
+
+    #[test]
+    #[should_panic(expected = "`table_name` is required")]
+    fn test_remove_field_builder_missing_table_name() {
+        let _ = RemoveFieldBuilder::new()
+            .field(Field::new(
+                Identifier::new("name"),
+                <String as DatabaseField>::TYPE,
+            ))
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "`field` is required")]
+    fn test_remove_field_builder_missing_field() {
+        let _ = RemoveFieldBuilder::new()
+            .table_name(Identifier::new("testapp__test_model"))
+            .build();
     }
 
     #[test]
-    fn test_operation_remove_model() {
-        const MODEL_FIELDS: &[Field; 2] = &[
-            Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
-                .primary_key()
-                .auto(),
-            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
-        ];
-
-        let operation = Operation::remove_model()
+    fn test_operation_alter_field() {
+        let operation = Operation::alter_field()
             .table_name(Identifier::new("testapp__test_model"))
-            .fields(MODEL_FIELDS)
+            .old_field(Field::new(Identifier::new("name"), ColumnType::Text))
+            .new_field(Field::new(Identifier::new("name"), ColumnType::Text).null())
             .build();
 
-        if let OperationInner::RemoveModel { table_name, fields } = operation.inner {
+        if let OperationInner::AlterField {
+            table_name,
+            old_field,
+            new_field,
+        } = operation.inner
+        {
             assert_eq!(table_name.to_string(), "testapp__test_model");
-            assert_eq!(fields.len(), 2);
+            assert_eq!(old_field.name.to_string(), "name");
+            assert!(!old_field.null);
+            assert_eq!(new_field.name.to_string(), "name");
+            assert!(new_field.null);
         } else {
-            panic!("Expected OperationInner::RemoveModel");
+            panic!("Expected OperationInner::AlterField");
         }
     }
 
     #[test]
-    fn test_operation_add_field() {
-        let operation = Operation::add_field()
+    #[should_panic(expected = "`new_field` is required")]
+    fn test_alter_field_builder_missing_new_field() {
+        let _ = AlterFieldBuilder::new()
             .table_name(Identifier::new("testapp__test_model"))
-            .field(Field::new(
-                Identifier::new("age"),
-                <i32 as DatabaseField>::TYPE,
-            ))
+            .old_field(Field::new(Identifier::new("name"), ColumnType::Text))
             .build();
-
-        if let OperationInner::AddField { table_name, field } = operation.inner {
-            assert_eq!(table_name.to_string(), "testapp__test_model");
-            assert_eq!(field.name.to_string(), "age");
-        } else {
-            panic!("Expected OperationInner::AddField");
-        }
     }
 
-    #[cot::test]
-    #[cfg_attr(
-        miri,
-        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
-    )]
-    async fn operation_custom() {
-        // test only on SQLite because we are using raw SQL
-        let test_db = TestDatabase::new_sqlite().await.unwrap();
+    const ALTER_FIELD_CREATE_OPERATION: Operation = Operation::create_model()
+        .table_name(Identifier::new("testapp__alter_model"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+            Field::new(Identifier::new("count"), <i32 as DatabaseField>::TYPE),
+        ])
+        .build();
 
-        #[migration_op]
-        async fn forwards(ctx: MigrationContext<'_>) -> Result<()> {
-            ctx.db
-                .raw("CREATE TABLE custom_test (id INTEGER PRIMARY KEY)")
-                .await?;
-            Ok(())
-        }
+    #[cot_macros::dbtest]
+    async fn test_add_field_operation_with_default(test_db: &mut TestDatabase) {
+        const OPERATION: Operation = Operation::add_field()
+            .table_name(Identifier::new("testapp__alter_model"))
+            .field(
+                Field::new(Identifier::new("score"), <i32 as DatabaseField>::TYPE)
+                    .default(DefaultValue::Int(10)),
+            )
+            .build();
 
-        let operation = Operation::custom(forwards).build();
-        operation.forwards(&test_db.database()).await.unwrap();
+        let database = test_db.database();
+        ALTER_FIELD_CREATE_OPERATION
+            .forwards(&database)
+            .await
+            .unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('foo', 1)")
+            .await
+            .unwrap();
 
-        let result = test_db.database().raw("SELECT * FROM custom_test").await;
-        assert!(result.is_ok());
+        // the existing rows are filled in with the default value
+        OPERATION.forwards(&database).await.unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('bar', 2)")
+            .await
+            .unwrap();
+        let scores = database
+            .raw_values::<(i32,)>("SELECT score FROM testapp__alter_model ORDER BY id")
+            .await
+            .unwrap();
+        assert_eq!(scores, vec![(10,), (10,)]);
+
+        // changing the default doesn't modify the existing rows
+        Operation::alter_field()
+            .table_name(Identifier::new("testapp__alter_model"))
+            .old_field(
+                Field::new(Identifier::new("score"), <i32 as DatabaseField>::TYPE)
+                    .default(DefaultValue::Int(10)),
+            )
+            .new_field(
+                Field::new(Identifier::new("score"), <i32 as DatabaseField>::TYPE)
+                    .default(DefaultValue::Int(20)),
+            )
+            .build()
+            .forwards(&database)
+            .await
+            .unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('baz', 3)")
+            .await
+            .unwrap();
+        let scores = database
+            .raw_values::<(i32,)>("SELECT score FROM testapp__alter_model ORDER BY id")
+            .await
+            .unwrap();
+        assert_eq!(scores, vec![(10,), (10,), (20,)]);
     }
 
-    #[cot::test]
-    #[cfg_attr(
-        miri,
-        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
-    )]
-    async fn operation_custom_model_access() {
-        let test_db = TestDatabase::new_sqlite().await.unwrap();
+    #[cot_macros::dbtest]
+    async fn test_alter_field_operation_null(test_db: &mut TestDatabase) {
+        let database = test_db.database();
+        ALTER_FIELD_CREATE_OPERATION
+            .forwards(&database)
+            .await
+            .unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('foo', 1)")
+            .await
+            .unwrap();
 
-        let result = _MigrationModel::objects().all(&test_db.database()).await;
-        assert!(matches!(
-            result,
-            Err(DatabaseError::MigrationError(MigrationEngineError::Custom(
-                _
-            )))
-        ));
+        let operation = Operation::alter_field()
+            .table_name(Identifier::new("testapp__alter_model"))
+            .old_field(Field::new(
+                Identifier::new("name"),
+                <String as DatabaseField>::TYPE,
+            ))
+            .new_field(Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE).null())
+            .build();
 
-        #[migration_op]
-        async fn application_model(ctx: MigrationContext<'_>) -> Result<()> {
-            ApplicationModel::objects().all(ctx.db).await?;
-            Ok(())
-        }
+        operation.forwards(&database).await.unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES (NULL, 2)")
+            .await
+            .unwrap();
+        let names = database
+            .raw_values::<(Option<String>,)>("SELECT name FROM testapp__alter_model ORDER BY id")
+            .await
+            .unwrap();
+        assert_eq!(names, vec![(Some("foo".to_owned()),), (None,)]);
 
-        let result = Operation::custom(application_model)
-            .build()
-            .forwards(&test_db.database())
+        database
+            .raw("DELETE FROM testapp__alter_model WHERE name IS NULL")
+            .await
+            .unwrap();
+        operation.backwards(&database).await.unwrap();
+        let result = database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES (NULL, 3)")
             .await;
-        assert!(matches!(
-            result,
-            Err(DatabaseError::MigrationError(MigrationEngineError::Custom(
-                _
-            )))
-        ));
+        assert!(result.is_err());
+    }
 
-        test_db
-            .database()
-            .raw("CREATE TABLE cot__migration_model (id INTEGER PRIMARY KEY)")
+    #[cot_macros::dbtest]
+    async fn test_alter_field_operation_type_and_unique(test_db: &mut TestDatabase) {
+        let database = test_db.database();
+        ALTER_FIELD_CREATE_OPERATION
+            .forwards(&database)
+            .await
+            .unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('foo', 1)")
             .await
             .unwrap();
 
-        #[migration_op]
-        async fn migration_model(ctx: MigrationContext<'_>) -> Result<()> {
-            _MigrationModel::objects().all(ctx.db).await?;
-            Ok(())
-        }
+        let operation = Operation::alter_field()
+            .table_name(Identifier::new("testapp__alter_model"))
+            .old_field(Field::new(
+                Identifier::new("count"),
+                <i32 as DatabaseField>::TYPE,
+            ))
+            .new_field(Field::new(Identifier::new("count"), <i64 as DatabaseField>::TYPE).unique())
+            .build();
 
-        Operation::custom(migration_model)
-            .build()
-            .forwards(&test_db.database())
+        operation.forwards(&database).await.unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('bar', 5000000000)")
             .await
             .unwrap();
-    }
-
-    #[cot::test]
-    #[cfg_attr(
-        miri,
-        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
-    )]
-    async fn operation_custom_backwards() {
-        // test only on SQLite because we are using raw SQL
-        let test_db = TestDatabase::new_sqlite().await.unwrap();
+        let result = database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('baz', 1)")
+            .await;
+        assert!(result.is_err());
+        let counts = database
+            .raw_values::<(i64,)>("SELECT count FROM testapp__alter_model ORDER BY id")
+            .await
+            .unwrap();
+        assert_eq!(counts, vec![(1,), (5_000_000_000,)]);
 
-        #[migration_op]
-        async fn forwards(_ctx: MigrationContext<'_>) -> Result<()> {
-            panic!("this should not be called");
-        }
+        database
+            .raw("DELETE FROM testapp__alter_model WHERE name = 'bar'")
+            .await
+            .unwrap();
+        operation.backwards(&database).await.unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('baz', 1)")
+            .await
+            .unwrap();
+    }
 
-        #[migration_op]
-        async fn backwards(ctx: MigrationContext<'_>) -> Result<()> {
-            ctx.db.raw("DROP TABLE custom_test_back").await?;
-            Ok(())
-        }
+    #[cot_macros::dbtest]
+    async fn test_alter_field_operation_foreign_key(test_db: &mut TestDatabase) {
+        const CREATE_OLD_PARENT: Operation = Operation::create_model()
+            .table_name(Identifier::new("testapp__old_parent"))
+            .fields(&[
+                Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE).primary_key(),
+            ])
+            .build();
+        const CREATE_NEW_PARENT: Operation = Operation::create_model()
+            .table_name(Identifier::new("testapp__new_parent"))
+            .fields(&[
+                Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE).primary_key(),
+            ])
+            .build();
+        const CREATE_CHILD: Operation = Operation::create_model()
+            .table_name(Identifier::new("testapp__child"))
+            .fields(&[
+                Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE).primary_key(),
+                Field::new(Identifier::new("parent"), <i32 as DatabaseField>::TYPE).foreign_key(
+                    Identifier::new("testapp__old_parent"),
+                    Identifier::new("id"),
+                    ForeignKeyOnDeletePolicy::Restrict,
+                    ForeignKeyOnUpdatePolicy::Restrict,
+                ),
+            ])
+            .build();
+        const OPERATION: Operation = Operation::alter_field()
+            .table_name(Identifier::new("testapp__child"))
+            .old_field(
+                Field::new(Identifier::new("parent"), <i32 as DatabaseField>::TYPE).foreign_key(
+                    Identifier::new("testapp__old_parent"),
+                    Identifier::new("id"),
+                    ForeignKeyOnDeletePolicy::Restrict,
+                    ForeignKeyOnUpdatePolicy::Restrict,
+                ),
+            )
+            .new_field(
+                Field::new(Identifier::new("parent"), <i32 as DatabaseField>::TYPE).foreign_key(
+                    Identifier::new("testapp__new_parent"),
+                    Identifier::new("id"),
+                    ForeignKeyOnDeletePolicy::Restrict,
+                    ForeignKeyOnUpdatePolicy::Restrict,
+                ),
+            )
+            .build();
 
-        test_db
-            .database()
-            .raw("CREATE TABLE custom_test_back (id INTEGER PRIMARY KEY)")
+        let database = test_db.database();
+        CREATE_OLD_PARENT.forwards(&database).await.unwrap();
+        CREATE_NEW_PARENT.forwards(&database).await.unwrap();
+        CREATE_CHILD.forwards(&database).await.unwrap();
+        database
+            .raw("INSERT INTO testapp__old_parent (id) VALUES (1)")
+            .await
+            .unwrap();
+        database
+            .raw("INSERT INTO testapp__new_parent (id) VALUES (2)")
+            .await
+            .unwrap();
+        database
+            .raw("INSERT INTO testapp__child (id, parent) VALUES (1, 1)")
             .await
             .unwrap();
+        database.raw("DELETE FROM testapp__child").await.unwrap();
 
-        let operation = Operation::custom(forwards).backwards(backwards).build();
-        operation.backwards(&test_db.database()).await.unwrap();
+        OPERATION.forwards(&database).await.unwrap();
+        database
+            .raw("INSERT INTO testapp__child (id, parent) VALUES (1, 2)")
+            .await
+            .unwrap();
+        let result = database
+            .raw("INSERT INTO testapp__child (id, parent) VALUES (2, 1)")
+            .await;
+        assert!(result.is_err());
 
-        let result = test_db
-            .database()
-            .raw("SELECT * FROM custom_test_back")
+        database.raw("DELETE FROM testapp__child").await.unwrap();
+        OPERATION.backwards(&database).await.unwrap();
+        database
+            .raw("INSERT INTO testapp__child (id, parent) VALUES (1, 1)")
+            .await
+            .unwrap();
+        let result = database
+            .raw("INSERT INTO testapp__child (id, parent) VALUES (2, 2)")
             .await;
         assert!(result.is_err());
     }
 
-    #[cot::test]
-    #[cfg_attr(
-        miri,
-        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
-    )]
-    async fn operation_custom_backwards_not_implemented() {
-        // test only on SQLite because we are using raw SQL
-        let test_db = TestDatabase::new_sqlite().await.unwrap();
+    const ALTER_PRIMARY_KEY_OPERATION: Operation = Operation::alter_field()
+        .table_name(Identifier::new("testapp__alter_model"))
+        .old_field(Field::new(
+            Identifier::new("count"),
+            <i32 as DatabaseField>::TYPE,
+        ))
+        .new_field(Field::new(Identifier::new("count"), <i32 as DatabaseField>::TYPE).primary_key())
+        .build();
 
-        #[migration_op]
-        async fn forwards(_ctx: MigrationContext<'_>) -> Result<()> {
-            Ok(())
-        }
+    #[cfg(feature = "postgres")]
+    #[cot::test]
+    async fn test_alter_field_operation_primary_key_postgres() {
+        let (database, _) = Database::offline(DatabaseDialect::Postgres).unwrap();
 
-        let operation = Operation::custom(forwards).build();
-        let result = operation.backwards(&test_db.database()).await;
+        let result = ALTER_PRIMARY_KEY_OPERATION.forwards(&database).await;
 
-        assert!(result.is_err());
+        assert!(matches!(
+            result,
+            Err(DatabaseError::UnsupportedByBackend {
+                backend: "PostgreSQL",
+                ..
+            })
+        ));
     }
 
-    #[test]
-    fn field_new() {
-        let field = Field::new(Identifier::new("id"), ColumnType::Integer)
-            .primary_key()
-            .auto()
-            .null();
+    #[cfg(feature = "mysql")]
+    #[cot::test]
+    async fn test_alter_field_operation_primary_key_mysql() {
+        let (database, _) = Database::offline(DatabaseDialect::MySql).unwrap();
 
-        assert_eq!(field.name.to_string(), "id");
-        assert_eq!(field.ty, ColumnType::Integer);
-        assert!(field.primary_key);
-        assert!(field.auto_value);
-        assert!(field.null);
+        let result = ALTER_PRIMARY_KEY_OPERATION.forwards(&database).await;
+
+        assert!(matches!(
+            result,
+            Err(DatabaseError::UnsupportedByBackend {
+                backend: "MySQL",
+                ..
+            })
+        ));
     }
 
     #[test]
-    fn field_foreign_key() {
-        let field = Field::new(Identifier::new("parent"), ColumnType::Integer).foreign_key(
-            Identifier::new("testapp__parent"),
-            Identifier::new("id"),
-            ForeignKeyOnDeletePolicy::Restrict,
-            ForeignKeyOnUpdatePolicy::Restrict,
-        );
+    fn test_operation_rename_field() {
+        let operation = Operation::rename_field()
+            .table_name(Identifier::new("testapp__test_model"))
+            .old_name(Identifier::new("name"))
+            .new_name(Identifier::new("title"))
+            .build();
 
-        assert_eq!(
-            field.foreign_key,
-            Some(ForeignKeyReference {
-                model: Identifier::new("testapp__parent"),
-                field: Identifier::new("id"),
-                on_delete: ForeignKeyOnDeletePolicy::Restrict,
-                on_update: ForeignKeyOnUpdatePolicy::Restrict,
-            })
-        );
+        if let OperationInner::RenameField {
+            table_name,
+            old_name,
+            new_name,
+        } = operation.inner
+        {
+            assert_eq!(table_name.to_string(), "testapp__test_model");
+            assert_eq!(old_name.to_string(), "name");
+            assert_eq!(new_name.to_string(), "title");
+        } else {
+            panic!("Expected OperationInner::RenameField");
+        }
     }
 
     #[test]
-    fn test_migration_wrapper() {
-        let migration = MigrationWrapper::new(TestMigration);
+    #[should_panic(expected = "`new_name` is required")]
+    fn test_rename_field_builder_missing_new_name() {
+        let _ = RenameFieldBuilder::new()
+            .table_name(Identifier::new("testapp__test_model"))
+            .old_name(Identifier::new("name"))
+            .build();
+    }
 
-        assert_eq!(migration.app_name(), "testapp");
-        assert_eq!(migration.name(), "m_0001_initial");
-        assert_eq!(migration.operations().len(), 1);
+    #[test]
+    fn test_operation_rename_model() {
+        let operation = Operation::rename_model()
+            .old_table_name(Identifier::new("testapp__test_model"))
+            .new_table_name(Identifier::new("testapp__renamed_model"))
+            .build();
+
+        if let OperationInner::RenameModel {
+            old_table_name,
+            new_table_name,
+        } = operation.inner
+        {
+            assert_eq!(old_table_name.to_string(), "testapp__test_model");
+            assert_eq!(new_table_name.to_string(), "testapp__renamed_model");
+        } else {
+            panic!("Expected OperationInner::RenameModel");
+        }
     }
 
     #[test]
-    fn test_field_to_column_def() {
-        let field = Field::new(Identifier::new("id"), ColumnType::Integer)
-            .primary_key()
-            .auto()
-            .null()
-            .unique();
+    #[should_panic(expected = "`old_table_name` is required")]
+    fn test_rename_model_builder_missing_old_table_name() {
+        let _ = RenameModelBuilder::new()
+            .new_table_name(Identifier::new("testapp__renamed_model"))
+            .build();
+    }
 
-        let mut mapper = MockColumnTypeMapper::new();
-        mapper
-            .expect_sea_query_column_type_for()
-            .return_const(sea_query::ColumnType::Integer);
-        let column_def = field.as_column_def(&mapper);
+    #[cot_macros::dbtest]
+    async fn test_rename_field_operation(test_db: &mut TestDatabase) {
+        let database = test_db.database();
+        ALTER_FIELD_CREATE_OPERATION
+            .forwards(&database)
+            .await
+            .unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('foo', 1)")
+            .await
+            .unwrap();
 
-        assert_eq!(column_def.get_column_name(), "id");
-        assert_eq!(
-            column_def.get_column_type(),
-            Some(&sea_query::ColumnType::Integer)
-        );
-        let spec = column_def.get_column_spec();
-        assert!(spec.primary_key);
-        assert!(spec.auto_increment);
-        assert_eq!(spec.nullable, Some(true));
-        assert!(spec.unique);
+        let operation = Operation::rename_field()
+            .table_name(Identifier::new("testapp__alter_model"))
+            .old_name(Identifier::new("name"))
+            .new_name(Identifier::new("title"))
+            .build();
+
+        operation.forwards(&database).await.unwrap();
+        let titles = database
+            .raw_values::<(String,)>("SELECT title FROM testapp__alter_model")
+            .await
+            .unwrap();
+        assert_eq!(titles, vec![("foo".to_owned(),)]);
+
+        operation.backwards(&database).await.unwrap();
+        let names = database
+            .raw_values::<(String,)>("SELECT name FROM testapp__alter_model")
+            .await
+            .unwrap();
+        assert_eq!(names, vec![("foo".to_owned(),)]);
     }
 
-    #[test]
-    fn test_field_to_column_def_without_options() {
-        let field = Field::new(Identifier::new("name"), ColumnType::Text);
+    #[cot_macros::dbtest]
+    async fn test_rename_model_operation(test_db: &mut TestDatabase) {
+        let database = test_db.database();
+        ALTER_FIELD_CREATE_OPERATION
+            .forwards(&database)
+            .await
+            .unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('foo', 1)")
+            .await
+            .unwrap();
 
-        let mut mapper = MockColumnTypeMapper::new();
-        mapper
-            .expect_sea_query_column_type_for()
-            .return_const(sea_query::ColumnType::Text);
-        let column_def = field.as_column_def(&mapper);
+        let operation = Operation::rename_model()
+            .old_table_name(Identifier::new("testapp__alter_model"))
+            .new_table_name(Identifier::new("testapp__renamed_model"))
+            .build();
 
-        assert_eq!(column_def.get_column_name(), "name");
-        assert_eq!(
-            column_def.get_column_type(),
-            Some(&sea_query::ColumnType::Text)
+        operation.forwards(&database).await.unwrap();
+        let names = database
+            .raw_values::<(String,)>("SELECT name FROM testapp__renamed_model")
+            .await
+            .unwrap();
+        assert_eq!(names, vec![("foo".to_owned(),)]);
+        assert!(
+            database
+                .raw("SELECT name FROM testapp__alter_model")
+                .await
+                .is_err()
         );
-        let spec = column_def.get_column_spec();
-        assert!(!spec.primary_key);
-        assert!(!spec.auto_increment);
-        assert_ne!(spec.nullable, Some(true));
-        assert!(!spec.unique);
+
+        operation.backwards(&database).await.unwrap();
+        let names = database
+            .raw_values::<(String,)>("SELECT name FROM testapp__alter_model")
+            .await
+            .unwrap();
+        assert_eq!(names, vec![("foo".to_owned(),)]);
     }
 
     #[test]
-    fn test_operation_remove_field() {
-        let operation = Operation::remove_field()
+    fn test_operation_add_index() {
+        const COLUMNS: &[Identifier] = &[Identifier::new("name")];
+        let operation = Operation::add_index()
             .table_name(Identifier::new("testapp__test_model"))
-            .field(Field::new(
-                Identifier::new("name"),
-                <String as DatabaseField>::TYPE,
+            .index(Index::new(
+                Identifier::new("testapp__test_model_name_idx"),
+                COLUMNS,
             ))
             .build();
 
-        if let OperationInner::RemoveField { table_name, field } = operation.inner {
+        if let OperationInner::AddIndex { table_name, index } = operation.inner {
             assert_eq!(table_name.to_string(), "testapp__test_model");
-            assert_eq!(field.name.to_string(), "name");
-            assert_eq!(field.ty, ColumnType::Text);
+            assert_eq!(index.name.to_string(), "testapp__test_model_name_idx");
+            assert_eq!(index.columns, COLUMNS);
         } else {
-            panic!("Expected OperationInner::RemoveField");
+            panic!("Expected OperationInner::AddIndex");
         }
     }
 
-    #[cot_macros::dbtest]
-    async fn test_remove_field_operation_forwards(test_db: &mut TestDatabase) {
-        const FIELDS: &[Field] = &[
-            Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
-                .primary_key()
-                .auto(),
-            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
-        ];
+    #[test]
+    #[should_panic(expected = "`index` is required")]
+    fn test_remove_index_builder_missing_index() {
+        let _ = RemoveIndexBuilder::new()
+            .table_name(Identifier::new("testapp__test_model"))
+            .build();
+    }
 
-        let create_operation = Operation::create_model()
+    #[test]
+    fn test_operation_add_constraint() {
+        let operation = Operation::add_constraint()
             .table_name(Identifier::new("testapp__test_model"))
-            .fields(FIELDS)
+            .constraint(Constraint::check(
+                Identifier::new("testapp__test_model_count_positive"),
+                "count > 0",
+            ))
             .build();
 
-        create_operation
-            .forwards(&test_db.database())
+        if let OperationInner::AddConstraint {
+            table_name,
+            constraint,
+        } = operation.inner
+        {
+            assert_eq!(table_name.to_string(), "testapp__test_model");
+            assert!(matches!(
+                constraint.inner,
+                ConstraintInner::Check { name, expr: "count > 0" }
+                    if name.as_str() == "testapp__test_model_count_positive"
+            ));
+        } else {
+            panic!("Expected OperationInner::AddConstraint");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "`table_name` is required")]
+    fn test_remove_constraint_builder_missing_table_name() {
+        let _ = RemoveConstraintBuilder::new()
+            .constraint(Constraint::check(
+                Identifier::new("testapp__test_model_count_positive"),
+                "count > 0",
+            ))
+            .build();
+    }
+
+    #[cot_macros::dbtest]
+    async fn test_add_index_operation(test_db: &mut TestDatabase) {
+        const OPERATION: Operation = Operation::add_index()
+            .table_name(Identifier::new("testapp__alter_model"))
+            .index(Index::new(
+                Identifier::new("testapp__alter_model_count_idx"),
+                &[Identifier::new("count")],
+            ))
+            .build();
+
+        let database = test_db.database();
+        ALTER_FIELD_CREATE_OPERATION
+            .forwards(&database)
             .await
             .unwrap();
 
-        let remove_operation = Operation::remove_field()
-            .table_name(Identifier::new("testapp__test_model"))
-            .field(Field::new(
-                Identifier::new("name"),
-                <String as DatabaseField>::TYPE,
+        OPERATION.forwards(&database).await.unwrap();
+        // the index already exists
+        assert!(OPERATION.forwards(&database).await.is_err());
+
+        OPERATION.backwards(&database).await.unwrap();
+        OPERATION.forwards(&database).await.unwrap();
+    }
+
+    #[cot_macros::dbtest]
+    async fn test_add_unique_constraint_operation(test_db: &mut TestDatabase) {
+        const OPERATION: Operation = Operation::add_constraint()
+            .table_name(Identifier::new("testapp__alter_model"))
+            .constraint(Constraint::unique(
+                Identifier::new("testapp__alter_model_id_count_uniq"),
+                &[Identifier::new("id"), Identifier::new("count")],
             ))
             .build();
 
-        let result = remove_operation.forwards(&test_db.database()).await;
-        assert!(result.is_ok());
+        let database = test_db.database();
+        ALTER_FIELD_CREATE_OPERATION
+            .forwards(&database)
+            .await
+            .unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('foo', 1)")
+            .await
+            .unwrap();
+
+        OPERATION.forwards(&database).await.unwrap();
+        let result = database
+            .raw("INSERT INTO testapp__alter_model (id, name, count) VALUES (1, 'bar', 1)")
+            .await;
+        assert!(result.is_err());
+
+        OPERATION.backwards(&database).await.unwrap();
+        OPERATION.forwards(&database).await.unwrap();
     }
 
     #[cot_macros::dbtest]
-    async fn test_remove_field_operation_backwards(test_db: &mut TestDatabase) {
-        const FIELDS: &[Field] = &[
-            Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
-                .primary_key()
-                .auto(),
-        ];
+    async fn test_add_check_constraint_operation(test_db: &mut TestDatabase) {
+        let database = test_db.database();
+        ALTER_FIELD_CREATE_OPERATION
+            .forwards(&database)
+            .await
+            .unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('foo', 1)")
+            .await
+            .unwrap();
 
-        let create_operation = Operation::create_model()
-            .table_name(Identifier::new("testapp__test_model"))
-            .fields(FIELDS)
+        let operation = Operation::add_constraint()
+            .table_name(Identifier::new("testapp__alter_model"))
+            .constraint(Constraint::check(
+                Identifier::new("testapp__alter_model_count_positive"),
+                "count > 0",
+            ))
             .build();
 
-        create_operation
-            .forwards(&test_db.database())
+        operation.forwards(&database).await.unwrap();
+        let result = database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('bar', 0)")
+            .await;
+        assert!(result.is_err());
+        let names = database
+            .raw_values::<(String,)>("SELECT name FROM testapp__alter_model")
             .await
             .unwrap();
+        assert_eq!(names, vec![("foo".to_owned(),)]);
 
-        let remove_operation = Operation::remove_field()
-            .table_name(Identifier::new("testapp__test_model"))
-            .field(Field::new(
+        // the constraint is kept when altering other fields
+        Operation::alter_field()
+            .table_name(Identifier::new("testapp__alter_model"))
+            .old_field(Field::new(
                 Identifier::new("name"),
                 <String as DatabaseField>::TYPE,
             ))
-            .build();
-
-        let result = remove_operation.backwards(&test_db.database()).await;
-        assert!(result.is_ok());
-    }
+            .new_field(Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE).null())
+            .build()
+            .forwards(&database)
+            .await
+            .unwrap();
+        let result = database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('bar', 0)")
+            .await;
+        assert!(result.is_err());
 
-    #[test]
-    fn test_remove_field_builder_new() {
-        let builder = RemoveFieldBuilder::new();
-        assert!(builder.table_name.is_none());
-        assert!(builder.field.is_none());
+        operation.backwards(&database).await.unwrap();
+        database
+            .raw("INSERT INTO testapp__alter_model (name, count) VALUES ('bar', 0)")
+            .await
+            .unwrap();
+        // the existing rows violate the constraint
+        assert!(operation.forwards(&database).await.is_err());
     }
 
-    #[test]
-    fn test_remove_field_builder_table_name() {
-        let builder = RemoveFieldBuilder::new().table_name(Identifier::new("testapp__test_model"));
+    #[cot_macros::dbtest]
+    async fn test_add_foreign_key_constraint_operation(test_db: &mut TestDatabase) {
+        const CREATE_PARENT: Operation = Operation::create_model()
+            .table_name(Identifier::new("testapp__parent"))
+            .fields(&[
+                Field::new(Identifier::new("code"), <String as DatabaseField>::TYPE).primary_key(),
+                Field::new(Identifier::new("year"), <i32 as DatabaseField>::TYPE).primary_key(),
+            ])
+            .build();
+        const CREATE_CHILD: Operation = Operation::create_model()
+            .table_name(Identifier::new("testapp__child"))
+            .fields(&[
+                Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE).primary_key(),
+                Field::new(
+                    Identifier::new("parent_code"),
+                    <String as DatabaseField>::TYPE,
+                ),
+                Field::new(Identifier::new("parent_year"), <i32 as DatabaseField>::TYPE),
+            ])
+            .build();
+        const OPERATION: Operation = Operation::add_constraint()
+            .table_name(Identifier::new("testapp__child"))
+            .constraint(Constraint::foreign_key(
+                Identifier::new("testapp__child_parent_code_parent_year_fk"),
+                &[
+                    Identifier::new("parent_code"),
+                    Identifier::new("parent_year"),
+                ],
+                Identifier::new("testapp__parent"),
+                &[Identifier::new("code"), Identifier::new("year")],
+                ForeignKeyOnDeletePolicy::Restrict,
+                ForeignKeyOnUpdatePolicy::Cascade,
+            ))
+            .build();
 
-        assert_eq!(
-            builder.table_name.unwrap().to_string(),
-            "testapp__test_model"
-        );
-    }
+        let database = test_db.database();
+        CREATE_PARENT.forwards(&database).await.unwrap();
+        CREATE_CHILD.forwards(&database).await.unwrap();
+        database
+            .raw("INSERT INTO testapp__parent (code, year) VALUES ('a', 1)")
+            .await
+            .unwrap();
 
-    #[test]
-    fn test_remove_field_builder_field() {
-        let builder = RemoveFieldBuilder::new().field(Field::new(
-            Identifier::new("name"),
-            <String as DatabaseField>::TYPE,
-        ));
+        OPERATION.forwards(&database).await.unwrap();
+        database
+            .raw("INSERT INTO testapp__child (id, parent_code, parent_year) VALUES (1, 'a', 1)")
+            .await
+            .unwrap();
+        let result = database
+            .raw("INSERT INTO testapp__child (id, parent_code, parent_year) VALUES (2, 'a', 2)")
+            .await;
+        assert!(result.is_err());
 
-        let field = builder.field.unwrap();
-        assert_eq!(field.name.to_string(), "name");
-        assert_eq!(field.ty, ColumnType::Text);
+        OPERATION.backwards(&database).await.unwrap();
+        database
+            .raw("INSERT INTO testapp__child (id, parent_code, parent_year) VALUES (2, 'a', 2)")
+            .await
+            .unwrap();
+        // the existing rows violate the constraint
+        assert!(OPERATION.forwards(&database).await.is_err());
     }
 
-    #[test]
-    #[should_panic(expected = "`table_name` is required")]
-    fn test_remove_field_builder_missing_table_name() {
-        let _ = RemoveFieldBuilder::new()
-            .field(Field::new(
-                Identifier::new("name"),
-                <String as DatabaseField>::TYPE,
+    #[cfg(feature = "sqlite")]
+    #[cot::test]
+    async fn test_failed_sqlite_table_rebuild_restores_foreign_keys() {
+        const OPERATION: Operation = Operation::add_constraint()
+            .table_name(Identifier::new("testapp__child"))
+            .constraint(Constraint::foreign_key(
+                Identifier::new("testapp__child_parent_fk"),
+                &[Identifier::new("parent")],
+                Identifier::new("testapp__parent"),
+                &[Identifier::new("id")],
+                ForeignKeyOnDeletePolicy::Restrict,
+                ForeignKeyOnUpdatePolicy::Restrict,
             ))
             .build();
+
+        let database = Database::new("sqlite::memory:")
+            .await
+            .unwrap()
+            .pin_connection()
+            .await
+            .unwrap();
+        database
+            .raw("CREATE TABLE testapp__parent (id INTEGER PRIMARY KEY)")
+            .await
+            .unwrap();
+        database
+            .raw("CREATE TABLE testapp__child (id INTEGER PRIMARY KEY, parent INTEGER)")
+            .await
+            .unwrap();
+        database
+            .raw("INSERT INTO testapp__child (id, parent) VALUES (1, 1)")
+            .await
+            .unwrap();
+
+        // the existing rows violate the constraint
+        assert!(OPERATION.forwards(&database).await.is_err());
+        let foreign_keys = database
+            .raw_values::<(i32,)>("PRAGMA foreign_keys")
+            .await
+            .unwrap();
+        assert_eq!(foreign_keys, vec![(1,)]);
     }
 
-    #[test]
-    #[should_panic(expected = "`field` is required")]
-    fn test_remove_field_builder_missing_field() {
-        let _ = RemoveFieldBuilder::new()
-            .table_name(Identifier::new("testapp__test_model"))
+    #[cfg(feature = "sqlite")]
+    #[cot::test]
+    async fn test_sqlite_migration_checks_foreign_keys_of_rebuilt_tables() {
+        const ALTER_FIELD: Operation = Operation::alter_field()
+            .table_name(Identifier::new("testapp__child"))
+            .old_field(Field::new(
+                Identifier::new("parent"),
+                <i32 as DatabaseField>::TYPE,
+            ))
+            .new_field(Field::new(Identifier::new("parent"), <i32 as DatabaseField>::TYPE).null())
             .build();
+        const ADD_FOREIGN_KEY: Operation = Operation::add_constraint()
+            .table_name(Identifier::new("testapp__child"))
+            .constraint(Constraint::foreign_key(
+                Identifier::new("testapp__child_parent_fk"),
+                &[Identifier::new("parent")],
+                Identifier::new("testapp__parent"),
+                &[Identifier::new("id")],
+                ForeignKeyOnDeletePolicy::Restrict,
+                ForeignKeyOnUpdatePolicy::Restrict,
+            ))
+            .build();
+
+        let database = Database::new("sqlite::memory:").await.unwrap();
+        // a violation in a table the migrations don't touch
+        let connection = database.pin_connection().await.unwrap();
+        for sql in [
+            "PRAGMA foreign_keys = OFF",
+            "CREATE TABLE testapp__parent (id INTEGER PRIMARY KEY)",
+            "CREATE TABLE testapp__legacy (id INTEGER PRIMARY KEY, \
+             parent INTEGER REFERENCES testapp__parent (id))",
+            "INSERT INTO testapp__legacy (id, parent) VALUES (1, 1)",
+            "PRAGMA foreign_keys = ON",
+            "CREATE TABLE testapp__child (id INTEGER PRIMARY KEY, parent INTEGER NOT NULL)",
+            "INSERT INTO testapp__child (id, parent) VALUES (1, 1)",
+        ] {
+            connection.raw(sql).await.unwrap();
+        }
+        drop(connection);
+
+        let alter_field =
+            crate::test::TestMigration::new("testapp", "m_0001_alter", [], [ALTER_FIELD]);
+        let add_foreign_key = crate::test::TestMigration::new(
+            "testapp",
+            "m_0002_foreign_key",
+            [MigrationDependency::migration("testapp", "m_0001_alter")],
+            [ADD_FOREIGN_KEY],
+        );
+
+        MigrationEngine::new([alter_field.clone()])
+            .unwrap()
+            .run(&database)
+            .await
+            .unwrap();
+        // the rows of the rebuilt table violate the new constraint
+        let result = MigrationEngine::new([alter_field, add_foreign_key])
+            .unwrap()
+            .run(&database)
+            .await;
+        assert!(result.is_err());
+        let result = database
+            .raw("INSERT INTO testapp__child (id, parent) VALUES (2, 2)")
+            .await;
+        assert!(result.is_ok());
     }
 }
@@ -102,21 +102,45 @@ impl<'a, T: DynMigration> MigrationSorter<'a, T> {
             }
 
             for operation in migration.operations() {
-                if let OperationInner::CreateModel { table_name, .. } = operation.inner {
-                    let app_and_model = MigrationLookup::ByAppAndModel {
-                        app: migration.app_name(),
-                        table_name,
-                    };
-                    if map.insert(app_and_model, index).is_some() {
-                        return Err(MigrationSorterError::DuplicateModel {
-                            app_name: migration.app_name().to_owned(),
-                            table_name: table_name.0.to_owned(),
-                        });
+                match operation.inner {
+                    OperationInner::CreateModel { table_name, .. } => {
+                        let app_and_model = MigrationLookup::ByAppAndModel {
+                            app: migration.app_name(),
+                            table_name,
+                        };
+                        if map.insert(app_and_model, index).is_some() {
+                            return Err(MigrationSorterError::DuplicateModel {
+                                app_name: migration.app_name().to_owned(),
+                                table_name: table_name.0.to_owned(),
+                            });
+                        }
                     }
+                    OperationInner::RenameModel { new_table_name, .. } => {
+                        // a renamed model can be renamed back later, so this is not
+                        // treated as a duplicate
+                        let app_and_model = MigrationLookup::ByAppAndModel {
+                            app: migration.app_name(),
+                            table_name: new_table_name,
+                        };
+                        map.insert(app_and_model, index);
+                    }
+                    _ => {}
                 }
             }
         }
 
+        // dependencies on the migrations replaced by a squashed migration are
+        // satisfied by the squashed migration once the replaced ones are removed
+        for (index, migration) in migrations.iter().enumerate() {
+            for &replaced in migration.replaces() {
+                map.entry(MigrationLookup::ByAppAndName {
+                    app: migration.app_name(),
+                    name: replaced,
+                })
+                .or_insert(index);
+            }
+        }
+
         Ok(map)
     }
 }
@@ -200,6 +224,40 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn create_lookup_table_rename_model() {
+        let migrations = vec![
+            TestMigration::new(
+                "app1",
+                "migration1",
+                [],
+                [Operation::create_model()
+                    .table_name(Identifier::new("model1"))
+                    .fields(&[])
+                    .build()],
+            ),
+            TestMigration::new(
+                "app1",
+                "migration2",
+                [],
+                [Operation::rename_model()
+                    .old_table_name(Identifier::new("model1"))
+                    .new_table_name(Identifier::new("model2"))
+                    .build()],
+            ),
+        ];
+
+        let lookup = MigrationSorter::create_lookup_table(&migrations).unwrap();
+
+        assert_eq!(
+            lookup.get(&MigrationLookup::ByAppAndModel {
+                app: "app1",
+                table_name: Identifier::new("model2")
+            }),
+            Some(&1)
+        );
+    }
+
     #[test]
     fn sort() {
         let mut migrations = vec![
@@ -296,6 +354,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn create_lookup_table_replaced_migrations() {
+        let migrations = vec![
+            TestMigration::new("app1", "migration2_squashed", [], [])
+                .replaces(["migration1", "migration2"]),
+        ];
+
+        let lookup = MigrationSorter::create_lookup_table(&migrations).unwrap();
+
+        assert_eq!(
+            lookup.get(&MigrationLookup::ByAppAndName {
+                app: "app1",
+                name: "migration1"
+            }),
+            Some(&0)
+        );
+        assert_eq!(
+            lookup.get(&MigrationLookup::ByAppAndName {
+                app: "app1",
+                name: "migration2"
+            }),
+            Some(&0)
+        );
+    }
+
+    #[test]
+    fn toposort_self_dependency() {
+        let mut migrations = vec![TestMigration::new(
+            "app1",
+            "migration1",
+            [MigrationDependency::model(
+                "app1",
+                Identifier::new("model1"),
+            )],
+            [Operation::create_model()
+                .table_name(Identifier::new("model1"))
+                .fields(&[])
+                .build()],
+        )];
+
+        let mut sorter = MigrationSorter::new(&mut migrations);
+        assert!(matches!(
+            sorter.toposort(),
+            Err(MigrationSorterError::CycleDetected(_))
+        ));
+    }
+
     #[test]
     fn cycle_detection() {
         let mut migrations = vec![
@@ -1,16 +1,32 @@
 //! Database query builder.
 
 pub mod expr;
+pub mod order;
+pub mod relation;
+#[cfg(test)]
+pub(crate) mod test_utils;
+pub mod update;
 
 use std::marker::PhantomData;
 
 use derive_more::with_trait::Debug;
+use futures_core::stream::BoxStream;
+use futures_util::StreamExt;
+use sea_query::ExprTrait;
 use thiserror::Error;
 
 use crate::db;
-use crate::db::query::expr::SqlQueryBuilder;
-pub use crate::db::query::expr::{Expr, ExprAdd, ExprDiv, ExprMul, ExprOrd, ExprSub};
-use crate::db::{Auto, DatabaseBackend, ForeignKey, Model, StatementResult, ToDbFieldValue};
+pub use crate::db::query::expr::{
+    Expr, ExprAdd, ExprAggregate, ExprDiv, ExprIn, ExprMul, ExprNull, ExprOrd, ExprSub, Subquery,
+};
+use crate::db::query::expr::{FieldRef, SqlQueryBuilder};
+pub use crate::db::query::order::{NullsOrder, OrderBy, OrderDirection};
+use crate::db::query::relation::Relation;
+use crate::db::query::update::Assignment;
+use crate::db::{
+    Auto, DatabaseBackend, ForeignKey, ForeignKeyField, FromDbRow, Model, Row, StatementResult,
+    ToDbFieldValue,
+};
 const ERROR_PREFIX: &str = "expression error:";
 
 /// An error that can occur when building a query.
@@ -23,10 +39,14 @@ pub enum QueryBuildingError {
     /// Error when building a query.
     #[error(transparent)]
     SeaQuery(#[from] sea_query::error::Error),
+    /// The field passed to [`Query::select_related`] is not a foreign key of
+    /// the model.
+    #[error("{ERROR_PREFIX} `{0}` is not a foreign key of the model")]
+    NotAForeignKey(String),
 }
 
-/// A query that can be executed on a database. Can be used to filter, update,
-/// or delete rows.
+/// A query that can be executed on a database. Can be used to filter, order,
+/// aggregate, update, or delete rows.
 ///
 /// # Example
 ///
@@ -46,18 +66,40 @@ pub enum QueryBuildingError {
 /// ```
 pub struct Query<T> {
     filter: Option<Expr>,
+    order_by: Vec<OrderBy>,
+    group_by: Vec<Expr>,
+    having: Option<Expr>,
+    select_related: Vec<Relation>,
     limit: Option<u64>,
     offset: Option<u64>,
+    lock: Option<RowLock>,
     phantom_data: PhantomData<fn() -> T>,
 }
 
+/// A row lock taken by a query with [`Query::for_update`] or one of its
+/// variants.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum RowLock {
+    /// Wait for the rows locked by other transactions to be released.
+    Wait,
+    /// Fail immediately if any of the rows is locked by another transaction.
+    NoWait,
+    /// Skip the rows that are locked by other transactions.
+    SkipLocked,
+}
+
 // manual implementation to avoid `T: Debug` in the trait bounds
 impl<T> Debug for Query<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Query")
             .field("filter", &self.filter)
+            .field("order_by", &self.order_by)
+            .field("group_by", &self.group_by)
+            .field("having", &self.having)
+            .field("select_related", &self.select_related)
             .field("limit", &self.limit)
             .field("offset", &self.offset)
+            .field("lock", &self.lock)
             .field("phantom_data", &self.phantom_data)
             .finish()
     }
@@ -68,8 +110,13 @@ impl<T> Clone for Query<T> {
     fn clone(&self) -> Self {
         Self {
             filter: self.filter.clone(),
+            order_by: self.order_by.clone(),
+            group_by: self.group_by.clone(),
+            having: self.having.clone(),
+            select_related: self.select_related.clone(),
             limit: self.limit,
             offset: self.offset,
+            lock: self.lock,
             phantom_data: PhantomData,
         }
     }
@@ -79,6 +126,11 @@ impl<T> Clone for Query<T> {
 impl<T> PartialEq for Query<T> {
     fn eq(&self, other: &Self) -> bool {
         self.filter == other.filter
+            && self.order_by == other.order_by
+            && self.group_by == other.group_by
+            && self.having == other.having
+            && self.select_related == other.select_related
+            && self.lock == other.lock
     }
 }
 
@@ -111,14 +163,22 @@ impl<T: Model> Query<T> {
     pub fn new() -> Self {
         Self {
             filter: None,
+            order_by: Vec::new(),
+            group_by: Vec::new(),
+            having: None,
+            select_related: Vec::new(),
             limit: None,
             offset: None,
+            lock: None,
             phantom_data: PhantomData,
         }
     }
 
     /// Set the filter expression for the query.
     ///
+    /// This replaces the existing filter of the query, if any. Use
+    /// [`Self::and_filter`] to narrow it down instead.
+    ///
     /// # Example
     ///
     /// ```
@@ -140,6 +200,172 @@ impl<T: Model> Query<T> {
         self
     }
 
+    /// Add a filter expression to the query, combining it with the existing
+    /// filter (if any) using `AND`.
+    ///
+    /// This is useful for narrowing down the queries that already have a
+    /// filter, such as the ones returned by reverse relation accessors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::{Expr, Query};
+    ///
+    /// #[model]
+    /// struct User {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: String,
+    ///     age: i32,
+    /// }
+    ///
+    /// let query = Query::<User>::new()
+    ///     .filter(Expr::eq(Expr::field("name"), Expr::value("John")))
+    ///     .and_filter(Expr::gte(Expr::field("age"), Expr::value(18)));
+    /// ```
+    pub fn and_filter(&mut self, filter: Expr) -> &mut Self {
+        self.filter = Some(match self.filter.take() {
+            Some(existing) => Expr::and(existing, filter),
+            None => filter,
+        });
+        self
+    }
+
+    /// Add a sort key to the query.
+    ///
+    /// Calling this method multiple times adds multiple sort keys; the results
+    /// are ordered by the first key, then by the second key for rows that
+    /// compare equal on the first one, and so on. Passing a field directly
+    /// orders the results by that field in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::Query;
+    ///
+    /// #[model]
+    /// struct User {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: String,
+    ///     age: Option<i32>,
+    /// }
+    ///
+    /// let query = Query::<User>::new()
+    ///     .order_by(<User as cot::db::Model>::Fields::age.desc().nulls_last())
+    ///     .order_by(<User as cot::db::Model>::Fields::name);
+    /// ```
+    pub fn order_by<O: Into<OrderBy>>(&mut self, order: O) -> &mut Self {
+        self.order_by.push(order.into());
+        self
+    }
+
+    /// Add an expression to group the results of the query by.
+    ///
+    /// Grouping only makes sense when the query is executed with
+    /// [`Query::aggregate`]; the aggregates are then computed separately for
+    /// each group of rows that share the same values of the grouping
+    /// expressions. Calling this method multiple times adds multiple grouping
+    /// expressions. Passing a field directly groups the results by that field.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::Query;
+    ///
+    /// #[model]
+    /// struct User {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: String,
+    ///     age: i32,
+    /// }
+    ///
+    /// let query = Query::<User>::new().group_by(<User as cot::db::Model>::Fields::age);
+    /// ```
+    pub fn group_by<E: Into<Expr>>(&mut self, expr: E) -> &mut Self {
+        self.group_by.push(expr.into());
+        self
+    }
+
+    /// Set the filter expression applied to the groups of the query.
+    ///
+    /// Unlike [`Query::filter`], which filters the rows before they are
+    /// grouped, this filters the groups themselves, so it can refer to
+    /// aggregate expressions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::{Expr, Query};
+    ///
+    /// #[model]
+    /// struct User {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: String,
+    ///     age: i32,
+    /// }
+    ///
+    /// let query = Query::<User>::new()
+    ///     .group_by(<User as cot::db::Model>::Fields::age)
+    ///     .having(Expr::gt(Expr::count_all(), Expr::value(1)));
+    /// ```
+    pub fn having(&mut self, having: Expr) -> &mut Self {
+        self.having = Some(having);
+        self
+    }
+
+    /// Fetch the model referenced by the given foreign key field together with
+    /// the queried model.
+    ///
+    /// The referenced model's table is joined to the query, and the foreign
+    /// key field of each returned model instance is set to
+    /// [`ForeignKey::Model`], so that accessing the referenced model doesn't
+    /// require another database query. Calling this method multiple times
+    /// fetches multiple referenced models.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::{Auto, Database, ForeignKey, Model, model};
+    ///
+    /// #[model]
+    /// struct User {
+    ///     #[model(primary_key)]
+    ///     id: Auto<i32>,
+    ///     username: String,
+    /// }
+    ///
+    /// #[model]
+    /// struct Post {
+    ///     #[model(primary_key)]
+    ///     id: Auto<i32>,
+    ///     author: ForeignKey<User>,
+    /// }
+    ///
+    /// # async fn example(db: &Database) -> cot::db::Result<()> {
+    /// let posts = Post::objects()
+    ///     .select_related(<Post as Model>::Fields::author)
+    ///     .all(db)
+    ///     .await?;
+    /// for post in posts {
+    ///     // no additional database query is needed here
+    ///     let author = post.author.model().unwrap();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn select_related<F: ForeignKeyField>(&mut self, field: FieldRef<F>) -> &mut Self {
+        relation::push_unique(&mut self.select_related, field.relation());
+        self
+    }
+
     /// Set the limit for the query.
     ///
     /// # Example
@@ -186,6 +412,110 @@ impl<T: Model> Query<T> {
         self
     }
 
+    /// Lock the rows returned by the query for update (`SELECT ... FOR
+    /// UPDATE`), so that other transactions can't modify or lock them until
+    /// the current transaction ends.
+    ///
+    /// If any of the rows is already locked by another transaction, the query
+    /// waits for the lock to be released. See [`Query::for_update_nowait`]
+    /// and [`Query::for_update_skip_locked`] for the alternatives.
+    ///
+    /// The lock is only useful when the query is executed on a
+    /// [`Transaction`](db::Transaction); otherwise, it is released as soon as
+    /// the query finishes. Only the rows of the queried model are locked, even
+    /// if the query joins other models, e.g. with [`Query::select_related`].
+    /// The lock applies to the queries returning model instances:
+    /// [`Query::all`], [`Query::get`] and [`Query::stream`].
+    ///
+    /// SQLite doesn't support row locks, as it locks the whole database when
+    /// a transaction writes to it, so on SQLite the lock clause is omitted and
+    /// the query is executed as usual.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{Database, Model, model, query};
+    ///
+    /// #[model]
+    /// struct Product {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     stock: i32,
+    /// }
+    ///
+    /// # async fn example(db: &Database) -> cot::db::Result<()> {
+    /// let mut transaction = db.begin().await?;
+    /// let product = query!(Product, $id == 5)
+    ///     .for_update()
+    ///     .get(&mut transaction)
+    ///     .await?;
+    /// if let Some(mut product) = product {
+    ///     product.stock -= 1;
+    ///     product.save(&mut transaction).await?;
+    /// }
+    /// transaction.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn for_update(&mut self) -> &mut Self {
+        self.lock = Some(RowLock::Wait);
+        self
+    }
+
+    /// Lock the rows returned by the query for update, failing immediately
+    /// if any of them is already locked by another transaction (`SELECT ...
+    /// FOR UPDATE NOWAIT`).
+    ///
+    /// See [`Query::for_update`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::Query;
+    ///
+    /// #[model]
+    /// struct Product {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     stock: i32,
+    /// }
+    ///
+    /// let query = Query::<Product>::new().for_update_nowait();
+    /// ```
+    pub fn for_update_nowait(&mut self) -> &mut Self {
+        self.lock = Some(RowLock::NoWait);
+        self
+    }
+
+    /// Lock the rows returned by the query for update, skipping the rows that
+    /// are already locked by another transaction (`SELECT ... FOR UPDATE SKIP
+    /// LOCKED`).
+    ///
+    /// This is useful for implementing work queues, where each worker should
+    /// pick rows that are not being processed by other workers. See
+    /// [`Query::for_update`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::Query;
+    ///
+    /// #[model]
+    /// struct Job {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     done: bool,
+    /// }
+    ///
+    /// let query = Query::<Job>::new().limit(10).for_update_skip_locked();
+    /// ```
+    pub fn for_update_skip_locked(&mut self) -> &mut Self {
+        self.lock = Some(RowLock::SkipLocked);
+        self
+    }
+
     /// Execute the query and return all results.
     ///
     /// # Errors
@@ -205,6 +535,54 @@ impl<T: Model> Query<T> {
         db.get(self).await
     }
 
+    /// Execute the query and return a stream of the results.
+    ///
+    /// The rows are fetched from the database incrementally as the stream is
+    /// polled, which makes it possible to process large result sets without
+    /// loading them into memory all at once. The database connection (or the
+    /// transaction) is held for as long as the stream is alive.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields an error if the query fails, or if a row can't be
+    /// converted to the model.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{Database, model, query};
+    /// use futures_util::TryStreamExt;
+    ///
+    /// #[model]
+    /// struct User {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     active: bool,
+    /// }
+    ///
+    /// # async fn example(db: &Database) -> cot::db::Result<()> {
+    /// let mut users = query!(User, $active == true).stream(db);
+    /// while let Some(user) = users.try_next().await? {
+    ///     println!("{}", user.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn stream<'a, DB: DatabaseBackend + 'a>(&self, db: DB) -> BoxStream<'a, db::Result<T>>
+    where
+        T: Send,
+    {
+        let query = self.clone();
+        Box::pin(async_stream::try_stream! {
+            let mut db = db;
+            let mut rows = db.stream(query);
+            while let Some(row) = rows.next().await {
+                yield row?;
+            }
+        })
+    }
+
     /// Execute the query and return the number of results.
     ///
     /// # Errors
@@ -223,6 +601,156 @@ impl<T: Model> Query<T> {
         db.exists(self).await
     }
 
+    /// Execute the query and return only the given columns of the matching
+    /// rows, instead of whole model instances.
+    ///
+    /// Each row is converted into `R`, which can be a tuple with one element
+    /// per column, or a struct deriving [`FromDbRow`](macro@db::FromDbRow),
+    /// whose fields are read in the order they are declared. This makes it
+    /// possible to skip large columns that are not needed, or to read columns
+    /// of related models using [`Expr::related_field`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails, or if the values returned by the
+    /// database cannot be converted into `R`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::Expr;
+    /// use cot::db::{Database, FromDbRow, model, query};
+    ///
+    /// #[model]
+    /// struct Document {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     title: String,
+    ///     content: String,
+    /// }
+    ///
+    /// #[derive(FromDbRow)]
+    /// struct DocumentTitle {
+    ///     id: i32,
+    ///     title: String,
+    /// }
+    ///
+    /// # async fn example(db: &Database) -> cot::db::Result<()> {
+    /// let columns = [Expr::field("id"), Expr::field("title")];
+    /// let titles: Vec<DocumentTitle> = query!(Document, $id > 10).values(db, &columns).await?;
+    /// let pairs: Vec<(i32, String)> = query!(Document, $id > 10).values(db, &columns).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn values<R: FromDbRow + Send + 'static, DB: DatabaseBackend>(
+        &self,
+        mut db: DB,
+        columns: &[Expr],
+    ) -> db::Result<Vec<R>> {
+        db.values(self, columns).await
+    }
+
+    /// Execute the query and return the values of the given expressions,
+    /// typically aggregates, for each group of rows.
+    ///
+    /// If no grouping expressions were added with [`Query::group_by`], the
+    /// whole set of rows matching the filter forms a single group, so exactly
+    /// one row is returned. Each row is converted into `R`, which is usually a
+    /// tuple with one element per expression.
+    ///
+    /// This is the same as [`Query::values`]; it only exists to make the
+    /// intent of the query clearer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails, or if the values returned by the
+    /// database cannot be converted into `R`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::{Expr, ExprAggregate, Query};
+    /// use cot::db::{Database, Model, model};
+    ///
+    /// #[model]
+    /// struct Order {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     customer: String,
+    ///     quantity: i32,
+    /// }
+    ///
+    /// # async fn example(db: &Database) -> cot::db::Result<()> {
+    /// let totals: Vec<(String, i64, Option<i64>)> = Query::<Order>::new()
+    ///     .group_by(<Order as Model>::Fields::customer)
+    ///     .aggregate(
+    ///         db,
+    ///         &[
+    ///             Expr::field("customer"),
+    ///             Expr::count_all(),
+    ///             <Order as Model>::Fields::quantity.sum(),
+    ///         ],
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn aggregate<R: FromDbRow + Send + 'static, DB: DatabaseBackend>(
+        &self,
+        db: DB,
+        exprs: &[Expr],
+    ) -> db::Result<Vec<R>> {
+        self.values(db, exprs).await
+    }
+
+    /// Update all rows that match the query with a single `UPDATE` statement,
+    /// without loading them into memory.
+    ///
+    /// The assigned values can be expressions referencing the current values
+    /// of the row's columns, so this can be used to, e.g., atomically increment
+    /// a counter. Only the filter of the query is taken into account; its
+    /// ordering and limits are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails, if `assignments` is empty, or if
+    /// any of the assignments references a field of a related model.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::ExprAdd;
+    /// use cot::db::{Database, Model, model, query};
+    ///
+    /// #[model]
+    /// struct Post {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     status: String,
+    ///     views: i64,
+    /// }
+    ///
+    /// # async fn example(db: &Database) -> cot::db::Result<()> {
+    /// let views = <Post as Model>::Fields::views;
+    /// query!(Post, $id == 5)
+    ///     .update(db, &[views.set_expr(views.add(1))])
+    ///     .await?;
+    ///
+    /// let result = query!(Post, $status == "draft")
+    ///     .update(db, &[<Post as Model>::Fields::status.set("archived")])
+    ///     .await?;
+    /// println!("archived {} posts", result.rows_affected());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update<DB: DatabaseBackend>(
+        &self,
+        mut db: DB,
+        assignments: &[Assignment],
+    ) -> db::Result<StatementResult> {
+        db.update_many(self, assignments).await
+    }
+
     /// Delete all rows that match the query.
     ///
     /// # Errors
@@ -243,6 +771,150 @@ impl<T: Model> Query<T> {
         Ok(())
     }
 
+    pub(super) fn add_order_by_to_statement(
+        &self,
+        statement: &mut sea_query::SelectStatement,
+        sql_builder: &dyn SqlQueryBuilder,
+    ) -> Result<(), QueryBuildingError> {
+        for order in &self.order_by {
+            order.add_to_statement(statement, sql_builder)?;
+        }
+        Ok(())
+    }
+
+    /// Returns all the relations that need to be joined to execute the query:
+    /// the ones requested with [`Query::select_related`], followed by the
+    /// ones referenced by any of the query's expressions.
+    fn relations(&self) -> Vec<Relation> {
+        let mut relations = self.select_related.clone();
+        let exprs = self
+            .filter
+            .iter()
+            .chain(self.order_by.iter().map(OrderBy::expr))
+            .chain(&self.group_by)
+            .chain(&self.having);
+        for expr in exprs {
+            expr.collect_relations(&mut relations);
+        }
+        relations
+    }
+
+    /// Adds the filter expression to a statement that can't contain joins,
+    /// such as `DELETE`.
+    ///
+    /// If the filter references any related models, the rows are matched by
+    /// their primary keys against a subquery that does the joins.
+    pub(super) fn add_filter_by_primary_key_to_statement<S: sea_query::ConditionalStatement>(
+        &self,
+        statement: &mut S,
+        sql_builder: &dyn SqlQueryBuilder,
+    ) -> Result<(), QueryBuildingError> {
+        if !self.has_relations() {
+            return self.add_filter_to_statement(statement, sql_builder);
+        }
+
+        let mut matching = sea_query::Query::select();
+        matching
+            .columns(T::PRIMARY_KEY_NAMES.iter().copied())
+            .from(T::TABLE_NAME);
+        self.add_joins_to_statement(&mut matching);
+        self.add_filter_to_statement(&mut matching, sql_builder)?;
+
+        // MySQL doesn't allow the table that is being modified to be used
+        // directly in a subquery, so the subquery is wrapped in a derived table
+        let mut subquery = sea_query::Query::select();
+        subquery
+            .columns(T::PRIMARY_KEY_NAMES.iter().copied())
+            .from_subquery(matching, sea_query::Alias::new("matching"));
+        let primary_key = if let [name] = T::PRIMARY_KEY_NAMES {
+            sea_query::Expr::col(*name)
+        } else {
+            sea_query::Expr::tuple(
+                T::PRIMARY_KEY_NAMES
+                    .iter()
+                    .map(|name| sea_query::Expr::col(*name)),
+            )
+        };
+        statement.and_where(primary_key.in_subquery(subquery));
+        Ok(())
+    }
+
+    pub(super) fn has_relations(&self) -> bool {
+        !self.relations().is_empty()
+    }
+
+    pub(super) fn add_joins_to_statement(&self, statement: &mut sea_query::SelectStatement) {
+        for relation in self.relations() {
+            relation.add_join_to_statement(statement, T::TABLE_NAME);
+        }
+    }
+
+    /// Adds the joins needed by the query, as well as by the given selected
+    /// columns, to a statement.
+    pub(super) fn add_joins_for_columns_to_statement(
+        &self,
+        statement: &mut sea_query::SelectStatement,
+        columns: &[Expr],
+    ) {
+        let mut relations = self.relations();
+        for column in columns {
+            column.collect_relations(&mut relations);
+        }
+        for relation in relations {
+            relation.add_join_to_statement(statement, T::TABLE_NAME);
+        }
+    }
+
+    pub(super) fn add_related_columns_to_statement(
+        &self,
+        statement: &mut sea_query::SelectStatement,
+    ) {
+        for relation in &self.select_related {
+            statement.columns(relation.column_refs());
+        }
+    }
+
+    /// Reads a model instance from a row returned by this query, filling in
+    /// the related models added with [`Query::select_related`].
+    pub(super) fn model_from_row(&self, row: Row) -> db::Result<T> {
+        // reading the model at an offset is only needed when the row contains
+        // the columns of the related models, so the models that don't support
+        // it can still be fetched without `select_related`
+        if self.select_related.is_empty() {
+            return T::from_db(row);
+        }
+
+        let mut model = T::from_db_at(&row, 0)?;
+        let mut offset = T::COLUMNS.len();
+        for relation in &self.select_related {
+            model.fill_related(relation.column(), &row, offset)?;
+            offset += relation.column_count();
+        }
+        Ok(model)
+    }
+
+    pub(super) fn add_group_by_to_statement(
+        &self,
+        statement: &mut sea_query::SelectStatement,
+        sql_builder: &dyn SqlQueryBuilder,
+    ) -> Result<(), QueryBuildingError> {
+        for expr in &self.group_by {
+            statement.add_group_by([expr.as_sea_query_expr(sql_builder)?]);
+        }
+        Ok(())
+    }
+
+    pub(super) fn add_having_to_statement(
+        &self,
+        statement: &mut sea_query::SelectStatement,
+        sql_builder: &dyn SqlQueryBuilder,
+    ) -> Result<(), QueryBuildingError> {
+        if let Some(having) = &self.having {
+            statement.and_having(having.as_sea_query_expr(sql_builder)?);
+        }
+        Ok(())
+    }
+
     pub(super) fn add_limit_to_statement(&self, statement: &mut sea_query::SelectStatement) {
         if let Some(limit) = self.limit {
             statement.limit(limit);
@@ -254,6 +926,39 @@ impl<T: Model> Query<T> {
             statement.offset(offset);
         }
     }
+
+    /// Returns whether the query locks the rows it returns.
+    pub(super) fn has_lock(&self) -> bool {
+        self.lock.is_some()
+    }
+
+    /// Adds the row lock to a statement. Only the rows of the queried model are
+    /// locked, as PostgreSQL doesn't allow locking the nullable side of an
+    /// outer join.
+    pub(super) fn add_lock_to_statement(&self, statement: &mut sea_query::SelectStatement) {
+        let tables = [T::TABLE_NAME];
+        let lock_type = sea_query::LockType::Update;
+        match self.lock {
+            None => {}
+            Some(RowLock::Wait) => {
+                statement.lock_with_tables(lock_type, tables);
+            }
+            Some(RowLock::NoWait) => {
+                statement.lock_with_tables_behavior(
+                    lock_type,
+                    tables,
+                    sea_query::LockBehavior::Nowait,
+                );
+            }
+            Some(RowLock::SkipLocked) => {
+                statement.lock_with_tables_behavior(
+                    lock_type,
+                    tables,
+                    sea_query::LockBehavior::SkipLocked,
+                );
+            }
+        }
+    }
 }
 
 /// A trait for database types that can be converted to the field type.
@@ -318,6 +1023,7 @@ mod tests {
     use cot_macros::model;
 
     use super::*;
+    use crate::db::query::expr::ExprEq;
     use crate::db::{MockDatabaseBackend, RowsNum};
 
     #[model]
@@ -327,11 +1033,22 @@ mod tests {
         id: i32,
     }
 
+    #[model]
+    struct MockRelatedModel {
+        #[model(primary_key)]
+        id: i32,
+        parent: ForeignKey<MockModel>,
+    }
+
     #[test]
     fn query_new() {
         let query: Query<MockModel> = Query::new();
 
         assert!(query.filter.is_none());
+        assert!(query.order_by.is_empty());
+        assert!(query.group_by.is_empty());
+        assert!(query.having.is_none());
+        assert!(query.select_related.is_empty());
         assert!(query.limit.is_none());
         assert!(query.offset.is_none());
     }
@@ -341,6 +1058,10 @@ mod tests {
         let query: Query<MockModel> = Query::default();
 
         assert!(query.filter.is_none());
+        assert!(query.order_by.is_empty());
+        assert!(query.group_by.is_empty());
+        assert!(query.having.is_none());
+        assert!(query.select_related.is_empty());
         assert!(query.limit.is_none());
         assert!(query.offset.is_none());
     }
@@ -354,6 +1075,128 @@ mod tests {
         assert!(query.filter.is_some());
     }
 
+    #[test]
+    fn query_filter_replaces() {
+        let mut query: Query<MockModel> = Query::new();
+
+        query
+            .filter(Expr::eq(Expr::field("name"), Expr::value("John")))
+            .filter(Expr::eq(Expr::field("id"), Expr::value(1)));
+
+        assert_eq!(
+            query.filter,
+            Some(Expr::eq(Expr::field("id"), Expr::value(1)))
+        );
+    }
+
+    #[test]
+    fn query_and_filter() {
+        let mut query: Query<MockModel> = Query::new();
+
+        query
+            .filter(Expr::eq(Expr::field("name"), Expr::value("John")))
+            .and_filter(Expr::eq(Expr::field("id"), Expr::value(1)));
+
+        assert_eq!(
+            query.filter,
+            Some(Expr::and(
+                Expr::eq(Expr::field("name"), Expr::value("John")),
+                Expr::eq(Expr::field("id"), Expr::value(1)),
+            ))
+        );
+    }
+
+    #[test]
+    fn query_order_by() {
+        let mut query: Query<MockModel> = Query::new();
+
+        query
+            .order_by(OrderBy::desc(Expr::field("name")).nulls_last())
+            .order_by(<MockModel as Model>::Fields::id);
+
+        assert_eq!(
+            query.order_by,
+            vec![
+                OrderBy::desc(Expr::field("name")).nulls_last(),
+                OrderBy::asc(Expr::field("id")),
+            ]
+        );
+    }
+
+    #[test]
+    fn query_order_by_eq() {
+        let mut query_1: Query<MockModel> = Query::new();
+        query_1.order_by(OrderBy::asc(Expr::field("id")));
+        let mut query_2: Query<MockModel> = Query::new();
+        query_2.order_by(OrderBy::desc(Expr::field("id")));
+
+        assert_ne!(query_1, query_2);
+        assert_ne!(query_1, Query::new());
+    }
+
+    #[test]
+    fn query_group_by() {
+        let mut query: Query<MockModel> = Query::new();
+
+        query
+            .group_by(Expr::field("name"))
+            .group_by(<MockModel as Model>::Fields::id);
+
+        assert_eq!(query.group_by, vec![Expr::field("name"), Expr::field("id")]);
+    }
+
+    #[test]
+    fn query_having() {
+        let mut query: Query<MockModel> = Query::new();
+
+        query.having(Expr::gt(Expr::count_all(), Expr::value(1)));
+
+        assert_eq!(
+            query.having,
+            Some(Expr::gt(Expr::count_all(), Expr::value(1)))
+        );
+    }
+
+    #[test]
+    fn query_group_by_eq() {
+        let mut query_1: Query<MockModel> = Query::new();
+        query_1.group_by(Expr::field("id"));
+        let mut query_2: Query<MockModel> = Query::new();
+        query_2
+            .group_by(Expr::field("id"))
+            .having(Expr::gt(Expr::count_all(), Expr::value(1)));
+
+        assert_ne!(query_1, query_2);
+        assert_ne!(query_1, Query::new());
+    }
+
+    #[test]
+    fn query_select_related() {
+        let mut query: Query<MockRelatedModel> = Query::new();
+
+        query
+            .select_related(<MockRelatedModel as Model>::Fields::parent)
+            .select_related(<MockRelatedModel as Model>::Fields::parent);
+
+        assert_eq!(
+            query.select_related,
+            vec![<MockRelatedModel as Model>::Fields::parent.relation()]
+        );
+        assert_ne!(query, Query::new());
+    }
+
+    #[test]
+    fn query_relations() {
+        let parent = <MockRelatedModel as Model>::Fields::parent;
+        let mut query: Query<MockRelatedModel> = Query::new();
+        assert!(!query.has_relations());
+
+        query.filter(parent.related(<MockModel as Model>::Fields::id).eq(5));
+
+        assert!(query.has_relations());
+        assert_eq!(query.relations(), vec![parent.relation()]);
+    }
+
     #[test]
     fn query_limit() {
         let mut query: Query<MockModel> = Query::new();
@@ -370,6 +1213,86 @@ mod tests {
         assert_eq!(query.offset.unwrap(), 10);
     }
 
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    fn render_lock<B: sea_query::QueryBuilder>(
+        query: &Query<MockModel>,
+        query_builder: B,
+    ) -> String {
+        let mut select = sea_query::Query::select();
+        select
+            .column(sea_query::Asterisk)
+            .from(<MockModel as Model>::TABLE_NAME);
+        query.add_lock_to_statement(&mut select);
+        select.to_string(query_builder)
+    }
+
+    #[test]
+    fn query_for_update() {
+        let mut query: Query<MockModel> = Query::new();
+        assert_eq!(query.lock, None);
+
+        query.for_update();
+        assert_eq!(query.lock, Some(RowLock::Wait));
+
+        query.for_update_nowait();
+        assert_eq!(query.lock, Some(RowLock::NoWait));
+
+        query.for_update_skip_locked();
+        assert_eq!(query.lock, Some(RowLock::SkipLocked));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn query_for_update_sqlite() {
+        let mut query: Query<MockModel> = Query::new();
+        query.for_update();
+
+        // SQLite doesn't support row locks
+        assert_eq!(
+            render_lock(&query, sea_query::SqliteQueryBuilder).trim_end(),
+            r#"SELECT * FROM "cot__mock_model""#
+        );
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn query_for_update_postgres() {
+        let mut query: Query<MockModel> = Query::new();
+        assert_eq!(
+            render_lock(&query, sea_query::PostgresQueryBuilder),
+            r#"SELECT * FROM "cot__mock_model""#
+        );
+
+        query.for_update();
+        assert_eq!(
+            render_lock(&query, sea_query::PostgresQueryBuilder),
+            r#"SELECT * FROM "cot__mock_model" FOR UPDATE OF "cot__mock_model""#
+        );
+
+        query.for_update_nowait();
+        assert_eq!(
+            render_lock(&query, sea_query::PostgresQueryBuilder),
+            r#"SELECT * FROM "cot__mock_model" FOR UPDATE OF "cot__mock_model" NOWAIT"#
+        );
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn query_for_update_mysql() {
+        let mut query: Query<MockModel> = Query::new();
+        query.for_update();
+        assert_eq!(
+            render_lock(&query, sea_query::MysqlQueryBuilder),
+            "SELECT * FROM `cot__mock_model` FOR UPDATE OF `cot__mock_model`"
+        );
+
+        query.for_update_skip_locked();
+        assert_eq!(
+            render_lock(&query, sea_query::MysqlQueryBuilder),
+            "SELECT * FROM `cot__mock_model` FOR UPDATE OF `cot__mock_model` SKIP LOCKED"
+        );
+    }
+
     #[cot::test]
     async fn query_all() {
         let mut db = MockDatabaseBackend::new();
@@ -381,6 +1304,25 @@ mod tests {
         assert_eq!(result.unwrap(), Vec::<MockModel>::new());
     }
 
+    #[cot::test]
+    async fn query_stream() {
+        let mut db = MockDatabaseBackend::new();
+        db.expect_stream().returning(|_: Query<MockModel>| {
+            futures_util::stream::iter([Ok(MockModel { id: 1 }), Ok(MockModel { id: 2 })]).boxed()
+        });
+        let query: Query<MockModel> = Query::new();
+
+        let result: Vec<_> = query.stream(&mut db).collect().await;
+
+        assert_eq!(
+            result
+                .into_iter()
+                .map(|row| row.unwrap().id)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+    }
+
     #[cot::test]
     async fn query_get() {
         let mut db = MockDatabaseBackend::new();
@@ -404,6 +1346,35 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[cot::test]
+    async fn query_values() {
+        let mut db = MockDatabaseBackend::new();
+        db.expect_values()
+            .withf(|_: &Query<MockModel>, columns: &[Expr]| columns == [Expr::field("id")])
+            .returning(|_, _| Ok(vec![(1_i32,), (2_i32,)]));
+        let query: Query<MockModel> = Query::new();
+
+        let result: Vec<(i32,)> = query.values(&mut db, &[Expr::field("id")]).await.unwrap();
+
+        assert_eq!(result, vec![(1,), (2,)]);
+    }
+
+    #[cot::test]
+    async fn query_aggregate() {
+        let mut db = MockDatabaseBackend::new();
+        db.expect_values()
+            .withf(|_: &Query<MockModel>, exprs: &[Expr]| exprs == [Expr::count_all()])
+            .returning(|_, _| Ok(vec![(5_i64,)]));
+        let query: Query<MockModel> = Query::new();
+
+        let result: Vec<(i64,)> = query
+            .aggregate(&mut db, &[Expr::count_all()])
+            .await
+            .unwrap();
+
+        assert_eq!(result, vec![(5,)]);
+    }
+
     #[cot::test]
     async fn query_delete() {
         let mut db = MockDatabaseBackend::new();
@@ -415,4 +1386,21 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[cot::test]
+    async fn query_update() {
+        let mut db = MockDatabaseBackend::new();
+        db.expect_update_many()
+            .withf(|_: &Query<MockModel>, assignments: &[Assignment]| {
+                assignments == [Assignment::new("id", Expr::value(1))]
+            })
+            .returning(|_: &Query<MockModel>, _| Ok(StatementResult::new(RowsNum(3))));
+        let query: Query<MockModel> = Query::new();
+
+        let result = query
+            .update(&mut db, &[Assignment::new("id", Expr::value(1))])
+            .await;
+
+        assert_eq!(result.unwrap().rows_affected(), RowsNum(3));
+    }
 }
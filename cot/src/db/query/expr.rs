@@ -1,13 +1,33 @@
 //! Database expressions.
+pub mod array;
+pub mod cast;
+pub mod json;
 pub mod like;
+pub mod search;
 
 use std::marker::PhantomData;
 
-use cot::db::query::{IntoField, QueryBuildingError};
-use cot::db::{DbFieldValue, DbValue, FromDbValue, Identifier, ToDbFieldValue};
+#[cfg(feature = "postgres")]
+pub use array::ExprArray;
+use array::{ArrayExprBuilder, ArrayOperator};
+use cast::CastExprBuilder;
+use cot::db::query::order::OrderBy;
+use cot::db::query::relation::{self, Relation};
+use cot::db::query::update::Assignment;
+use cot::db::query::{IntoField, Query, QueryBuildingError};
+use cot::db::{
+    ColumnType, CompositeForeignKeyField, DbFieldValue, DbValue, ForeignKeyField, FromDbValue,
+    Identifier, Model, ToDbFieldValue,
+};
+#[cfg(feature = "json")]
+pub use json::ExprJson;
+use json::JsonExprBuilder;
 pub use like::ExprLike;
 use like::{CaseSensitivity, LikeExprBuilder, LikeMode};
-use sea_query::{ExprTrait, IntoColumnRef, SimpleExpr};
+use sea_query::{Asterisk, ExprTrait, IntoColumnRef, SimpleExpr};
+#[cfg(feature = "postgres")]
+pub use search::ExprSearch;
+use search::SearchExprBuilder;
 
 /// An expression that can be used to filter, update, or delete rows.
 ///
@@ -61,6 +81,49 @@ pub enum Expr {
     /// );
     /// ```
     Field(Identifier),
+    /// An expression containing a reference to a column of a model referenced
+    /// through a foreign key.
+    ///
+    /// The referenced model's table is joined automatically when the query is
+    /// executed. See [`FieldRef::related`] for the typed way to create this
+    /// expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::{Expr, ExprEq};
+    /// use cot::db::{Auto, ForeignKey, Identifier, Model, model};
+    ///
+    /// #[model]
+    /// struct User {
+    ///     #[model(primary_key)]
+    ///     id: Auto<i32>,
+    ///     username: String,
+    /// }
+    ///
+    /// #[model]
+    /// struct Post {
+    ///     #[model(primary_key)]
+    ///     id: Auto<i32>,
+    ///     author: ForeignKey<User>,
+    /// }
+    ///
+    /// let expr = Expr::eq(
+    ///     Expr::related_field(
+    ///         <Post as Model>::Fields::author.relation(),
+    ///         Identifier::new("username"),
+    ///     ),
+    ///     Expr::value("alice"),
+    /// );
+    ///
+    /// assert_eq!(
+    ///     expr,
+    ///     <Post as Model>::Fields::author
+    ///         .related(<User as Model>::Fields::username)
+    ///         .eq("alice")
+    /// );
+    /// ```
+    RelatedField(Relation, Identifier),
     /// An expression containing a literal value.
     ///
     /// # Example
@@ -469,74 +532,158 @@ pub enum Expr {
     /// );
     /// ```
     RawLike(Box<Expr>, Box<Expr>, CaseSensitivity),
-}
-
-impl Expr {
-    /// Create a new field expression. This represents a reference to a column
-    /// in the database.
+    /// A `COUNT` aggregate, counting the rows in which the expression is not
+    /// `NULL`.
     ///
     /// # Example
     ///
     /// ```
-    /// use cot::db::{model, query};
-    /// use cot::db::query::Query;
     /// use cot::db::query::expr::Expr;
     ///
-    /// #[model]
-    /// struct MyModel {
-    ///     #[model(primary_key)]
-    ///     id: i32,
-    /// };
+    /// let expr = Expr::count(Expr::field("name"));
+    /// ```
+    Count(Box<Expr>),
+    /// A `COUNT(*)` aggregate, counting all the rows.
     ///
-    /// let expr = Expr::eq(Expr::field("id"), Expr::value(5));
+    /// # Example
     ///
-    /// assert_eq!(
-    ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id == 5)
-    /// );
     /// ```
-    #[must_use]
-    pub fn field<T: Into<Identifier>>(identifier: T) -> Self {
-        Self::Field(identifier.into())
-    }
-
-    /// Create a new value expression. This represents a literal value that gets
-    /// passed into the SQL query.
+    /// use cot::db::query::expr::Expr;
     ///
-    /// # Panics
+    /// let expr = Expr::count_all();
+    /// ```
+    CountAll,
+    /// A `SUM` aggregate.
     ///
-    /// If the value provided is a [`DbFieldValue::Auto`].
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::sum(Expr::field("price"));
+    /// ```
+    Sum(Box<Expr>),
+    /// An `AVG` aggregate.
     ///
     /// # Example
     ///
     /// ```
-    /// use cot::db::{model, query};
-    /// use cot::db::query::Query;
     /// use cot::db::query::expr::Expr;
     ///
-    /// #[model]
-    /// struct MyModel {
-    ///     #[model(primary_key)]
-    ///     id: i32,
-    /// };
+    /// let expr = Expr::avg(Expr::field("price"));
+    /// ```
+    Avg(Box<Expr>),
+    /// A `MIN` aggregate.
     ///
-    /// let expr = Expr::ne(Expr::field("id"), Expr::value(5));
+    /// # Example
     ///
-    /// assert_eq!(
-    ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id != 5)
-    /// );
     /// ```
-    #[must_use]
-    #[expect(clippy::needless_pass_by_value)]
-    pub fn value<T: ToDbFieldValue>(value: T) -> Self {
-        match value.to_db_field_value() {
-            DbFieldValue::Value(value) => Self::Value(value),
-            DbFieldValue::Auto => panic!("Cannot create query with a non-value field"),
-        }
-    }
-
-    /// Create a new `AND` expression.
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::min(Expr::field("price"));
+    /// ```
+    Min(Box<Expr>),
+    /// A `MAX` aggregate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::max(Expr::field("price"));
+    /// ```
+    Max(Box<Expr>),
+    /// A `CAST` expression, converting the expression to the database type
+    /// corresponding to the given [`ColumnType`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::ColumnType;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::cast(Expr::sum(Expr::field("price")), ColumnType::BigInteger);
+    /// ```
+    Cast(Box<Expr>, ColumnType),
+    /// A JSON path lookup, extracting the value found under the given
+    /// sequence of object keys inside a JSON document, as text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::json_get(Expr::field("data"), ["user", "name"]);
+    /// ```
+    JsonGet(Box<Expr>, Vec<String>),
+    /// An array containment expression, checking if the first array contains
+    /// all the elements of the second one.
+    ///
+    /// This is only supported on PostgreSQL.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::array_contains(Expr::field("tags"), Expr::field("required_tags"));
+    /// ```
+    ArrayContains(Box<Expr>, Box<Expr>),
+    /// An array containment expression, checking if all the elements of the
+    /// first array are contained in the second one.
+    ///
+    /// This is only supported on PostgreSQL.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::array_contained_by(Expr::field("tags"), Expr::field("allowed_tags"));
+    /// ```
+    ArrayContainedBy(Box<Expr>, Box<Expr>),
+    /// An array overlap expression, checking if the arrays have at least one
+    /// element in common.
+    ///
+    /// This is only supported on PostgreSQL.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::array_overlaps(Expr::field("tags"), Expr::field("followed_tags"));
+    /// ```
+    ArrayOverlaps(Box<Expr>, Box<Expr>),
+    /// A full-text search expression, checking if the document matches the
+    /// search query, using the text search configuration with the given
+    /// name.
+    ///
+    /// This is only supported on PostgreSQL.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::search(Expr::field("body"), Expr::value("rust orm"), "english");
+    /// ```
+    Search(Box<Expr>, Box<Expr>, String),
+    /// A full-text search ranking expression, computing how well the document
+    /// matches the search query, using the text search configuration with
+    /// the given name.
+    ///
+    /// This is only supported on PostgreSQL.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::search_rank(Expr::field("body"), Expr::value("rust orm"), "english");
+    /// ```
+    SearchRank(Box<Expr>, Box<Expr>, String),
+    /// A `NOT` expression.
     ///
     /// # Example
     ///
@@ -551,21 +698,16 @@ impl Expr {
     ///     id: i32,
     /// };
     ///
-    /// let expr = Expr::and(
-    ///     Expr::gt(Expr::field("id"), Expr::value(10)),
-    ///     Expr::lt(Expr::field("id"), Expr::value(20))
-    /// );
+    /// let expr = Expr::not(Expr::eq(Expr::field("id"), Expr::value(5)));
+    ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id > 10 && $id < 20)
+    ///     query!(MyModel, !($id == 5))
     /// );
     /// ```
-    #[must_use]
-    pub fn and(lhs: Self, rhs: Self) -> Self {
-        Self::And(Box::new(lhs), Box::new(rhs))
-    }
-
-    /// Create a new `OR` expression.
+    Not(Box<Expr>),
+    /// A `BETWEEN` expression, checking if the first expression lies between
+    /// the other two, inclusive.
     ///
     /// # Example
     ///
@@ -580,21 +722,16 @@ impl Expr {
     ///     id: i32,
     /// };
     ///
-    /// let expr = Expr::or(
-    ///     Expr::gt(Expr::field("id"), Expr::value(10)),
-    ///     Expr::lt(Expr::field("id"), Expr::value(20))
-    /// );
+    /// let expr = Expr::between(Expr::field("id"), Expr::value(10), Expr::value(20));
+    ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id > 10 || $id < 20)
+    ///     query!(MyModel, $id.between(10, 20))
     /// );
     /// ```
-    #[must_use]
-    pub fn or(lhs: Self, rhs: Self) -> Self {
-        Self::Or(Box::new(lhs), Box::new(rhs))
-    }
-
-    /// Create a new `=` expression.
+    Between(Box<Expr>, Box<Expr>, Box<Expr>),
+    /// An `IN` expression, checking if the expression is equal to any of the
+    /// expressions in the list.
     ///
     /// # Example
     ///
@@ -609,19 +746,16 @@ impl Expr {
     ///     id: i32,
     /// };
     ///
-    /// let expr = Expr::eq(Expr::field("id"), Expr::value(5));
+    /// let expr = Expr::is_in(Expr::field("id"), [Expr::value(1), Expr::value(2)]);
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id == 5)
+    ///     query!(MyModel, $id.is_in([1, 2]))
     /// );
     /// ```
-    #[must_use]
-    pub fn eq(lhs: Self, rhs: Self) -> Self {
-        Self::Eq(Box::new(lhs), Box::new(rhs))
-    }
-
-    /// Create a new `!=` expression.
+    In(Box<Expr>, Vec<Expr>),
+    /// A `NOT IN` expression, checking if the expression is not equal to any
+    /// of the expressions in the list.
     ///
     /// # Example
     ///
@@ -636,19 +770,15 @@ impl Expr {
     ///     id: i32,
     /// };
     ///
-    /// let expr = Expr::ne(Expr::field("id"), Expr::value(5));
+    /// let expr = Expr::is_not_in(Expr::field("id"), [Expr::value(1), Expr::value(2)]);
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id != 5)
+    ///     query!(MyModel, $id.is_not_in([1, 2]))
     /// );
     /// ```
-    #[must_use]
-    pub fn ne(lhs: Self, rhs: Self) -> Self {
-        Self::Ne(Box::new(lhs), Box::new(rhs))
-    }
-
-    /// Create a new `<` expression.
+    NotIn(Box<Expr>, Vec<Expr>),
+    /// An `IS NULL` expression.
     ///
     /// # Example
     ///
@@ -661,21 +791,18 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
+    ///     name: Option<String>,
     /// };
     ///
-    /// let expr = Expr::lt(Expr::field("id"), Expr::value(5));
+    /// let expr = Expr::is_null(Expr::field("name"));
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id < 5)
+    ///     query!(MyModel, $name.is_null())
     /// );
     /// ```
-    #[must_use]
-    pub fn lt(lhs: Self, rhs: Self) -> Self {
-        Self::Lt(Box::new(lhs), Box::new(rhs))
-    }
-
-    /// Create a new `<=` expression.
+    IsNull(Box<Expr>),
+    /// An `IS NOT NULL` expression.
     ///
     /// # Example
     ///
@@ -688,21 +815,18 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
+    ///     name: Option<String>,
     /// };
     ///
-    /// let expr = Expr::lte(Expr::field("id"), Expr::value(5));
+    /// let expr = Expr::is_not_null(Expr::field("name"));
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id <= 5)
+    ///     query!(MyModel, $name.is_not_null())
     /// );
     /// ```
-    #[must_use]
-    pub fn lte(lhs: Self, rhs: Self) -> Self {
-        Self::Lte(Box::new(lhs), Box::new(rhs))
-    }
-
-    /// Create a new `>` expression.
+    IsNotNull(Box<Expr>),
+    /// An `EXISTS` expression, checking if the subquery returns any rows.
     ///
     /// # Example
     ///
@@ -712,24 +836,27 @@ impl Expr {
     /// use cot::db::query::expr::Expr;
     ///
     /// #[model]
-    /// struct MyModel {
+    /// struct Post {
     ///     #[model(primary_key)]
     ///     id: i32,
+    ///     published: bool,
     /// };
     ///
-    /// let expr = Expr::gt(Expr::field("id"), Expr::value(5));
-    ///
-    /// assert_eq!(
-    ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id > 5)
-    /// );
+    /// let expr = Expr::exists(query!(Post, $published == true));
     /// ```
-    #[must_use]
-    pub fn gt(lhs: Self, rhs: Self) -> Self {
-        Self::Gt(Box::new(lhs), Box::new(rhs))
-    }
+    Exists(Box<Subquery>),
+    /// An expression containing a reference to a column of the table
+    /// queried by the outer query. This is only useful inside a [`Subquery`],
+    /// to make it depend on the currently processed row of the outer query
+    /// (a correlated subquery).
+    ///
+    /// See [`Expr::outer_field`] for more details.
+    OuterField(Identifier, Identifier),
+}
 
-    /// Create a new `>=` expression.
+impl Expr {
+    /// Create a new field expression. This represents a reference to a column
+    /// in the database.
     ///
     /// # Example
     ///
@@ -744,48 +871,59 @@ impl Expr {
     ///     id: i32,
     /// };
     ///
-    /// let expr = Expr::gte(Expr::field("id"), Expr::value(5));
+    /// let expr = Expr::eq(Expr::field("id"), Expr::value(5));
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id >= 5)
+    ///     query!(MyModel, $id == 5)
     /// );
     /// ```
     #[must_use]
-    pub fn gte(lhs: Self, rhs: Self) -> Self {
-        Self::Gte(Box::new(lhs), Box::new(rhs))
+    pub fn field<T: Into<Identifier>>(identifier: T) -> Self {
+        Self::Field(identifier.into())
     }
 
-    /// Create a new `+` expression.
+    /// Create a new related field expression. This represents a reference to a
+    /// column of a model referenced through a foreign key.
+    ///
+    /// See [`FieldRef::related`] for the typed way to create this expression.
     ///
     /// # Example
     ///
     /// ```
-    /// use cot::db::{model, query};
-    /// use cot::db::query::Query;
     /// use cot::db::query::expr::Expr;
+    /// use cot::db::{Auto, ForeignKey, Identifier, Model, model};
     ///
     /// #[model]
-    /// struct MyModel {
+    /// struct User {
     ///     #[model(primary_key)]
-    ///     id: i32,
-    ///     id_2: i32,
-    /// };
+    ///     id: Auto<i32>,
+    ///     username: String,
+    /// }
     ///
-    /// let expr = Expr::eq(Expr::field("id"), Expr::add(Expr::field("id_2"), Expr::value(5)));
+    /// #[model]
+    /// struct Post {
+    ///     #[model(primary_key)]
+    ///     id: Auto<i32>,
+    ///     author: ForeignKey<User>,
+    /// }
     ///
-    /// assert_eq!(
-    ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id == $id_2 + 5)
+    /// let expr = Expr::related_field(
+    ///     <Post as Model>::Fields::author.relation(),
+    ///     Identifier::new("username"),
     /// );
     /// ```
-    #[expect(clippy::should_implement_trait)]
     #[must_use]
-    pub fn add(lhs: Self, rhs: Self) -> Self {
-        Self::Add(Box::new(lhs), Box::new(rhs))
+    pub fn related_field(relation: Relation, column: Identifier) -> Self {
+        Self::RelatedField(relation, column)
     }
 
-    /// Create a new `-` expression.
+    /// Create a new value expression. This represents a literal value that gets
+    /// passed into the SQL query.
+    ///
+    /// # Panics
+    ///
+    /// If the value provided is a [`DbFieldValue::Auto`].
     ///
     /// # Example
     ///
@@ -798,23 +936,25 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
-    ///     id_2: i32,
     /// };
     ///
-    /// let expr = Expr::eq(Expr::field("id"), Expr::sub(Expr::field("id_2"), Expr::value(5)));
+    /// let expr = Expr::ne(Expr::field("id"), Expr::value(5));
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id == $id_2 - 5)
+    ///     query!(MyModel, $id != 5)
     /// );
     /// ```
-    #[expect(clippy::should_implement_trait)]
     #[must_use]
-    pub fn sub(lhs: Self, rhs: Self) -> Self {
-        Self::Sub(Box::new(lhs), Box::new(rhs))
+    #[expect(clippy::needless_pass_by_value)]
+    pub fn value<T: ToDbFieldValue>(value: T) -> Self {
+        match value.to_db_field_value() {
+            DbFieldValue::Value(value) => Self::Value(value),
+            DbFieldValue::Auto => panic!("Cannot create query with a non-value field"),
+        }
     }
 
-    /// Create a new `*` expression.
+    /// Create a new `AND` expression.
     ///
     /// # Example
     ///
@@ -827,23 +967,23 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
-    ///     id_2: i32,
     /// };
     ///
-    /// let expr = Expr::eq(Expr::field("id"), Expr::mul(Expr::field("id_2"), Expr::value(2)));
-    ///
+    /// let expr = Expr::and(
+    ///     Expr::gt(Expr::field("id"), Expr::value(10)),
+    ///     Expr::lt(Expr::field("id"), Expr::value(20))
+    /// );
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id == $id_2 * 2)
+    ///     query!(MyModel, $id > 10 && $id < 20)
     /// );
     /// ```
-    #[expect(clippy::should_implement_trait)]
     #[must_use]
-    pub fn mul(lhs: Self, rhs: Self) -> Self {
-        Self::Mul(Box::new(lhs), Box::new(rhs))
+    pub fn and(lhs: Self, rhs: Self) -> Self {
+        Self::And(Box::new(lhs), Box::new(rhs))
     }
 
-    /// Create a new `/` expression.
+    /// Create a new `OR` expression.
     ///
     /// # Example
     ///
@@ -856,29 +996,23 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
-    ///     id_2: i32,
     /// };
     ///
-    /// let expr = Expr::eq(Expr::field("id"), Expr::div(Expr::field("id_2"), Expr::value(2)));
-    ///
+    /// let expr = Expr::or(
+    ///     Expr::gt(Expr::field("id"), Expr::value(10)),
+    ///     Expr::lt(Expr::field("id"), Expr::value(20))
+    /// );
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $id == $id_2 / 2)
+    ///     query!(MyModel, $id > 10 || $id < 20)
     /// );
     /// ```
-    #[expect(clippy::should_implement_trait)]
     #[must_use]
-    pub fn div(lhs: Self, rhs: Self) -> Self {
-        Self::Div(Box::new(lhs), Box::new(rhs))
+    pub fn or(lhs: Self, rhs: Self) -> Self {
+        Self::Or(Box::new(lhs), Box::new(rhs))
     }
 
-    /// Creates an expression that checks whether `lhs` contains `rhs` as a
-    /// substring, comparing case-sensitively.
-    ///
-    /// The search string in `rhs` is treated as a literal: any characters in
-    /// it that would otherwise be treated as wildcards by [`raw_like`] are
-    /// escaped automatically, so `"50% off"` matches the literal text
-    /// `50% off`, not a wildcard pattern.
+    /// Create a new `=` expression.
     ///
     /// # Example
     ///
@@ -891,27 +1025,21 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
-    ///     name: String,
-    /// }
+    /// };
     ///
-    /// let expr = Expr::contains(Expr::field("name"), Expr::value("test"));
+    /// let expr = Expr::eq(Expr::field("id"), Expr::value(5));
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $name.contains("test"))
+    ///     query!(MyModel, $id == 5)
     /// );
     /// ```
     #[must_use]
-    pub fn contains(lhs: Self, rhs: Self) -> Self {
-        Self::Contains(Box::new(lhs), Box::new(rhs), CaseSensitivity::Sensitive)
+    pub fn eq(lhs: Self, rhs: Self) -> Self {
+        Self::Eq(Box::new(lhs), Box::new(rhs))
     }
 
-    /// Creates an expression that checks whether `lhs` contains `rhs` as a
-    /// substring, comparing case-insensitively.
-    ///
-    /// The case-insensitive counterpart to [`contains`](Self::contains). As
-    /// with `contains`, the search string in `rhs` is treated as a literal
-    /// and any wildcard-like characters in it are escaped automatically.
+    /// Create a new `!=` expression.
     ///
     /// # Example
     ///
@@ -924,27 +1052,21 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
-    ///     name: String,
-    /// }
+    /// };
     ///
-    /// let expr = Expr::icontains(Expr::field("name"), Expr::value("TEST"));
+    /// let expr = Expr::ne(Expr::field("id"), Expr::value(5));
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $name.icontains("TEST"))
+    ///     query!(MyModel, $id != 5)
     /// );
     /// ```
     #[must_use]
-    pub fn icontains(lhs: Self, rhs: Self) -> Self {
-        Self::Contains(Box::new(lhs), Box::new(rhs), CaseSensitivity::Insensitive)
+    pub fn ne(lhs: Self, rhs: Self) -> Self {
+        Self::Ne(Box::new(lhs), Box::new(rhs))
     }
 
-    /// Creates an expression that checks whether `lhs` starts with `rhs`,
-    /// comparing case-sensitively.
-    ///
-    /// Behaves like [`contains`](Self::contains) but anchored to the start of
-    /// the value: `rhs` is treated as a literal prefix, with any
-    /// wildcard-like characters in it escaped automatically.
+    /// Create a new `<` expression.
     ///
     /// # Example
     ///
@@ -957,25 +1079,21 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
-    ///     name: String,
-    /// }
+    /// };
     ///
-    /// let expr = Expr::starts_with(Expr::field("name"), Expr::value("Mr."));
+    /// let expr = Expr::lt(Expr::field("id"), Expr::value(5));
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $name.starts_with("Mr."))
+    ///     query!(MyModel, $id < 5)
     /// );
     /// ```
     #[must_use]
-    pub fn starts_with(lhs: Self, rhs: Self) -> Self {
-        Self::StartsWith(Box::new(lhs), Box::new(rhs), CaseSensitivity::Sensitive)
+    pub fn lt(lhs: Self, rhs: Self) -> Self {
+        Self::Lt(Box::new(lhs), Box::new(rhs))
     }
 
-    /// Creates an expression that checks whether `lhs` starts with `rhs`,
-    /// comparing case-insensitively.
-    ///
-    /// The case-insensitive counterpart to [`starts_with`](Self::starts_with).
+    /// Create a new `<=` expression.
     ///
     /// # Example
     ///
@@ -988,27 +1106,21 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
-    ///     name: String,
-    /// }
+    /// };
     ///
-    /// let expr = Expr::istarts_with(Expr::field("name"), Expr::value("mr."));
+    /// let expr = Expr::lte(Expr::field("id"), Expr::value(5));
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $name.istarts_with("mr."))
+    ///     query!(MyModel, $id <= 5)
     /// );
     /// ```
     #[must_use]
-    pub fn istarts_with(lhs: Self, rhs: Self) -> Self {
-        Self::StartsWith(Box::new(lhs), Box::new(rhs), CaseSensitivity::Insensitive)
+    pub fn lte(lhs: Self, rhs: Self) -> Self {
+        Self::Lte(Box::new(lhs), Box::new(rhs))
     }
 
-    /// Creates an expression that checks whether `lhs` ends with `rhs`,
-    /// comparing case-sensitively.
-    ///
-    /// Behaves like [`contains`](Self::contains) but anchored to the end of
-    /// the value: `rhs` is treated as a literal suffix, with any
-    /// wildcard-like characters in it escaped automatically.
+    /// Create a new `>` expression.
     ///
     /// # Example
     ///
@@ -1021,25 +1133,21 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
-    ///     name: String,
-    /// }
+    /// };
     ///
-    /// let expr = Expr::ends_with(Expr::field("name"), Expr::value("foo"));
+    /// let expr = Expr::gt(Expr::field("id"), Expr::value(5));
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $name.ends_with("foo"))
+    ///     query!(MyModel, $id > 5)
     /// );
     /// ```
     #[must_use]
-    pub fn ends_with(lhs: Self, rhs: Self) -> Self {
-        Self::EndsWith(Box::new(lhs), Box::new(rhs), CaseSensitivity::Sensitive)
+    pub fn gt(lhs: Self, rhs: Self) -> Self {
+        Self::Gt(Box::new(lhs), Box::new(rhs))
     }
 
-    /// Creates an expression that checks whether `lhs` ends with `rhs`,
-    /// comparing case-insensitively.
-    ///
-    /// The case-insensitive counterpart to [`ends_with`](Self::ends_with).
+    /// Create a new `>=` expression.
     ///
     /// # Example
     ///
@@ -1052,81 +1160,23 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
-    ///     name: String,
-    /// }
+    /// };
     ///
-    /// let expr = Expr::iends_with(Expr::field("name"), Expr::value("FOO"));
+    /// let expr = Expr::gte(Expr::field("id"), Expr::value(5));
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $name.iends_with("FOO"))
+    ///     query!(MyModel, $id >= 5)
     /// );
     /// ```
     #[must_use]
-    pub fn iends_with(lhs: Self, rhs: Self) -> Self {
-        Self::EndsWith(Box::new(lhs), Box::new(rhs), CaseSensitivity::Insensitive)
+    pub fn gte(lhs: Self, rhs: Self) -> Self {
+        Self::Gte(Box::new(lhs), Box::new(rhs))
     }
 
-    /// Creates an expression that matches `lhs` against a raw pattern in
-    /// `rhs`, comparing case-sensitively.
-    ///
-    /// Unlike [`contains`](Self::contains)/[`starts_with`](Self::starts_with)/
-    /// [`ends_with`](Self::ends_with), which escape the search string and
-    /// wrap it for you, `raw_like` passes `rhs` through as a **pattern you
-    /// write yourself** — use it when you need a shape those three methods
-    /// can't express, such as a match with wildcards in the middle, or a
-    /// fixed-width positional match.
-    ///
-    /// # Pattern syntax
-    ///
-    /// The pattern in `rhs` uses glob syntax:
-    ///
-    /// | Character | Meaning |
-    /// |-----------|---------|
-    /// | `*` | matches zero or more of any character |
-    /// | `?` | matches exactly one character |
-    /// | `\` | escapes the following character, making it literal |
-    ///
-    /// Any other character matches itself literally.
-    ///
-    /// # Escaping literal `*`, `?`, or `\` in the pattern
-    ///
-    /// If the text you're matching against can itself contain a literal `*`
-    /// or `?`, escape it with a backslash so it isn't treated as a wildcard.
-    /// For example, to match values that literally contain the substring
-    /// `"a * here"`, write the pattern as `"*a \\* here*"`. `\\*` in the Rust
-    /// string literal produces the two glob characters `\*`, i.e. an escaped,
-    /// literal asterisk, while the surrounding bare `*` characters remain
-    /// wildcards.
-    ///
-    /// If you don't need this level of control, prefer
-    /// [`contains`](Self::contains)/[`starts_with`](Self::starts_with)/
-    /// [`ends_with`](Self::ends_with), which handle escaping for you
-    /// automatically based on a plain search string.
-    ///
-    /// # Examples
-    ///
-    /// Substring match (equivalent to `contains("foo")`, spelled out
-    /// explicitly):
-    ///
-    /// ```
-    /// use cot::db::query::expr::Expr;
-    ///
-    /// let expr = Expr::raw_like(Expr::field("name"), Expr::value("*foo*"));
-    /// ```
-    ///
-    /// Prefix match (equivalent to `starts_with("foo")`):
-    ///
-    /// ```
-    /// use cot::db::query::expr::Expr;
-    ///
-    /// let expr = Expr::raw_like(Expr::field("name"), Expr::value("foo*"));
-    /// ```
+    /// Create a new `+` expression.
     ///
-    /// **Positional match**: something `contains`/`starts_with`/`ends_with`
-    /// cannot express at all: match values that are exactly `f`, then any two
-    /// characters, then `o` (e.g. matches `faXo`, `fooo`, but not `fo` or
-    /// `faXYo`):
+    /// # Example
     ///
     /// ```
     /// use cot::db::{model, query};
@@ -1137,42 +1187,26 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
-    ///     code: String,
-    /// }
+    ///     id_2: i32,
+    /// };
     ///
-    /// let expr = Expr::raw_like(Expr::field("code"), Expr::value("f??o"));
+    /// let expr = Expr::eq(Expr::field("id"), Expr::add(Expr::field("id_2"), Expr::value(5)));
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $code.raw_like("f??o"))
+    ///     query!(MyModel, $id == $id_2 + 5)
     /// );
     /// ```
-    ///
-    /// Wildcards in the middle of a pattern; matching values that contain
-    /// `foo`, later followed by `bar`, later followed by `baz`, in that
-    /// order, with anything in between:
-    ///
-    /// ```
-    /// use cot::db::query::expr::Expr;
-    ///
-    /// let expr = Expr::raw_like(Expr::field("path"), Expr::value("*foo*bar*baz*"));
-    /// ```
+    #[expect(clippy::should_implement_trait)]
     #[must_use]
-    pub fn raw_like(lhs: Self, rhs: Self) -> Self {
-        Self::RawLike(Box::new(lhs), Box::new(rhs), CaseSensitivity::Sensitive)
+    pub fn add(lhs: Self, rhs: Self) -> Self {
+        Self::Add(Box::new(lhs), Box::new(rhs))
     }
 
-    /// Creates an expression that matches `lhs` against a raw pattern in
-    /// `rhs`, comparing case-insensitively.
-    ///
-    /// The case-insensitive counterpart to [`raw_like`](Self::raw_like). See
-    /// its documentation for the pattern syntax (`*`/`?`/`\`).
+    /// Create a new `-` expression.
     ///
     /// # Example
     ///
-    /// Case-insensitive positional match — matches `README`, `ReadMe`,
-    /// `readme`, etc., but not `READMEE` or `REDME`:
-    ///
     /// ```
     /// use cot::db::{model, query};
     /// use cot::db::query::Query;
@@ -1182,27 +1216,1005 @@ impl Expr {
     /// struct MyModel {
     ///     #[model(primary_key)]
     ///     id: i32,
-    ///     filename: String,
-    /// }
+    ///     id_2: i32,
+    /// };
     ///
-    /// let expr = Expr::iraw_like(Expr::field("filename"), Expr::value("re?dme"));
+    /// let expr = Expr::eq(Expr::field("id"), Expr::sub(Expr::field("id_2"), Expr::value(5)));
     ///
     /// assert_eq!(
     ///     <Query<MyModel>>::new().filter(expr),
-    ///     query!(MyModel, $filename.iraw_like("re?dme"))
+    ///     query!(MyModel, $id == $id_2 - 5)
     /// );
     /// ```
+    #[expect(clippy::should_implement_trait)]
     #[must_use]
-    pub fn iraw_like(lhs: Self, rhs: Self) -> Self {
-        Self::RawLike(Box::new(lhs), Box::new(rhs), CaseSensitivity::Insensitive)
+    pub fn sub(lhs: Self, rhs: Self) -> Self {
+        Self::Sub(Box::new(lhs), Box::new(rhs))
     }
 
-    /// Returns the expression as a [`sea_query::SimpleExpr`].
+    /// Create a new `*` expression.
     ///
     /// # Example
     ///
     /// ```
-    /// use cot::db::query::expr::Expr;
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     id_2: i32,
+    /// };
+    ///
+    /// let expr = Expr::eq(Expr::field("id"), Expr::mul(Expr::field("id_2"), Expr::value(2)));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $id == $id_2 * 2)
+    /// );
+    /// ```
+    #[expect(clippy::should_implement_trait)]
+    #[must_use]
+    pub fn mul(lhs: Self, rhs: Self) -> Self {
+        Self::Mul(Box::new(lhs), Box::new(rhs))
+    }
+
+    /// Create a new `/` expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     id_2: i32,
+    /// };
+    ///
+    /// let expr = Expr::eq(Expr::field("id"), Expr::div(Expr::field("id_2"), Expr::value(2)));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $id == $id_2 / 2)
+    /// );
+    /// ```
+    #[expect(clippy::should_implement_trait)]
+    #[must_use]
+    pub fn div(lhs: Self, rhs: Self) -> Self {
+        Self::Div(Box::new(lhs), Box::new(rhs))
+    }
+
+    /// Creates an expression that checks whether `lhs` contains `rhs` as a
+    /// substring, comparing case-sensitively.
+    ///
+    /// The search string in `rhs` is treated as a literal: any characters in
+    /// it that would otherwise be treated as wildcards by [`raw_like`] are
+    /// escaped automatically, so `"50% off"` matches the literal text
+    /// `50% off`, not a wildcard pattern.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: String,
+    /// }
+    ///
+    /// let expr = Expr::contains(Expr::field("name"), Expr::value("test"));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $name.contains("test"))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn contains(lhs: Self, rhs: Self) -> Self {
+        Self::Contains(Box::new(lhs), Box::new(rhs), CaseSensitivity::Sensitive)
+    }
+
+    /// Creates an expression that checks whether `lhs` contains `rhs` as a
+    /// substring, comparing case-insensitively.
+    ///
+    /// The case-insensitive counterpart to [`contains`](Self::contains). As
+    /// with `contains`, the search string in `rhs` is treated as a literal
+    /// and any wildcard-like characters in it are escaped automatically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: String,
+    /// }
+    ///
+    /// let expr = Expr::icontains(Expr::field("name"), Expr::value("TEST"));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $name.icontains("TEST"))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn icontains(lhs: Self, rhs: Self) -> Self {
+        Self::Contains(Box::new(lhs), Box::new(rhs), CaseSensitivity::Insensitive)
+    }
+
+    /// Creates an expression that checks whether `lhs` starts with `rhs`,
+    /// comparing case-sensitively.
+    ///
+    /// Behaves like [`contains`](Self::contains) but anchored to the start of
+    /// the value: `rhs` is treated as a literal prefix, with any
+    /// wildcard-like characters in it escaped automatically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: String,
+    /// }
+    ///
+    /// let expr = Expr::starts_with(Expr::field("name"), Expr::value("Mr."));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $name.starts_with("Mr."))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn starts_with(lhs: Self, rhs: Self) -> Self {
+        Self::StartsWith(Box::new(lhs), Box::new(rhs), CaseSensitivity::Sensitive)
+    }
+
+    /// Creates an expression that checks whether `lhs` starts with `rhs`,
+    /// comparing case-insensitively.
+    ///
+    /// The case-insensitive counterpart to [`starts_with`](Self::starts_with).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: String,
+    /// }
+    ///
+    /// let expr = Expr::istarts_with(Expr::field("name"), Expr::value("mr."));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $name.istarts_with("mr."))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn istarts_with(lhs: Self, rhs: Self) -> Self {
+        Self::StartsWith(Box::new(lhs), Box::new(rhs), CaseSensitivity::Insensitive)
+    }
+
+    /// Creates an expression that checks whether `lhs` ends with `rhs`,
+    /// comparing case-sensitively.
+    ///
+    /// Behaves like [`contains`](Self::contains) but anchored to the end of
+    /// the value: `rhs` is treated as a literal suffix, with any
+    /// wildcard-like characters in it escaped automatically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: String,
+    /// }
+    ///
+    /// let expr = Expr::ends_with(Expr::field("name"), Expr::value("foo"));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $name.ends_with("foo"))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn ends_with(lhs: Self, rhs: Self) -> Self {
+        Self::EndsWith(Box::new(lhs), Box::new(rhs), CaseSensitivity::Sensitive)
+    }
+
+    /// Creates an expression that checks whether `lhs` ends with `rhs`,
+    /// comparing case-insensitively.
+    ///
+    /// The case-insensitive counterpart to [`ends_with`](Self::ends_with).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: String,
+    /// }
+    ///
+    /// let expr = Expr::iends_with(Expr::field("name"), Expr::value("FOO"));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $name.iends_with("FOO"))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn iends_with(lhs: Self, rhs: Self) -> Self {
+        Self::EndsWith(Box::new(lhs), Box::new(rhs), CaseSensitivity::Insensitive)
+    }
+
+    /// Creates an expression that matches `lhs` against a raw pattern in
+    /// `rhs`, comparing case-sensitively.
+    ///
+    /// Unlike [`contains`](Self::contains)/[`starts_with`](Self::starts_with)/
+    /// [`ends_with`](Self::ends_with), which escape the search string and
+    /// wrap it for you, `raw_like` passes `rhs` through as a **pattern you
+    /// write yourself** — use it when you need a shape those three methods
+    /// can't express, such as a match with wildcards in the middle, or a
+    /// fixed-width positional match.
+    ///
+    /// # Pattern syntax
+    ///
+    /// The pattern in `rhs` uses glob syntax:
+    ///
+    /// | Character | Meaning |
+    /// |-----------|---------|
+    /// | `*` | matches zero or more of any character |
+    /// | `?` | matches exactly one character |
+    /// | `\` | escapes the following character, making it literal |
+    ///
+    /// Any other character matches itself literally.
+    ///
+    /// # Escaping literal `*`, `?`, or `\` in the pattern
+    ///
+    /// If the text you're matching against can itself contain a literal `*`
+    /// or `?`, escape it with a backslash so it isn't treated as a wildcard.
+    /// For example, to match values that literally contain the substring
+    /// `"a * here"`, write the pattern as `"*a \\* here*"`. `\\*` in the Rust
+    /// string literal produces the two glob characters `\*`, i.e. an escaped,
+    /// literal asterisk, while the surrounding bare `*` characters remain
+    /// wildcards.
+    ///
+    /// If you don't need this level of control, prefer
+    /// [`contains`](Self::contains)/[`starts_with`](Self::starts_with)/
+    /// [`ends_with`](Self::ends_with), which handle escaping for you
+    /// automatically based on a plain search string.
+    ///
+    /// # Examples
+    ///
+    /// Substring match (equivalent to `contains("foo")`, spelled out
+    /// explicitly):
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::raw_like(Expr::field("name"), Expr::value("*foo*"));
+    /// ```
+    ///
+    /// Prefix match (equivalent to `starts_with("foo")`):
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::raw_like(Expr::field("name"), Expr::value("foo*"));
+    /// ```
+    ///
+    /// **Positional match**: something `contains`/`starts_with`/`ends_with`
+    /// cannot express at all: match values that are exactly `f`, then any two
+    /// characters, then `o` (e.g. matches `faXo`, `fooo`, but not `fo` or
+    /// `faXYo`):
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     code: String,
+    /// }
+    ///
+    /// let expr = Expr::raw_like(Expr::field("code"), Expr::value("f??o"));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $code.raw_like("f??o"))
+    /// );
+    /// ```
+    ///
+    /// Wildcards in the middle of a pattern; matching values that contain
+    /// `foo`, later followed by `bar`, later followed by `baz`, in that
+    /// order, with anything in between:
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::raw_like(Expr::field("path"), Expr::value("*foo*bar*baz*"));
+    /// ```
+    #[must_use]
+    pub fn raw_like(lhs: Self, rhs: Self) -> Self {
+        Self::RawLike(Box::new(lhs), Box::new(rhs), CaseSensitivity::Sensitive)
+    }
+
+    /// Creates an expression that matches `lhs` against a raw pattern in
+    /// `rhs`, comparing case-insensitively.
+    ///
+    /// The case-insensitive counterpart to [`raw_like`](Self::raw_like). See
+    /// its documentation for the pattern syntax (`*`/`?`/`\`).
+    ///
+    /// # Example
+    ///
+    /// Case-insensitive positional match — matches `README`, `ReadMe`,
+    /// `readme`, etc., but not `READMEE` or `REDME`:
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     filename: String,
+    /// }
+    ///
+    /// let expr = Expr::iraw_like(Expr::field("filename"), Expr::value("re?dme"));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $filename.iraw_like("re?dme"))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn iraw_like(lhs: Self, rhs: Self) -> Self {
+        Self::RawLike(Box::new(lhs), Box::new(rhs), CaseSensitivity::Insensitive)
+    }
+
+    /// Creates a new `COUNT` aggregate expression, counting the rows in which
+    /// `expr` is not `NULL`.
+    ///
+    /// Aggregate expressions are meant to be passed to
+    /// [`Query::aggregate`](cot::db::query::Query::aggregate) or used in
+    /// [`Query::having`](cot::db::query::Query::having).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     category: String,
+    /// };
+    ///
+    /// let mut query = <Query<MyModel>>::new();
+    /// query
+    ///     .group_by(Expr::field("category"))
+    ///     .having(Expr::gt(Expr::count(Expr::field("id")), Expr::value(1)));
+    /// ```
+    #[must_use]
+    pub fn count(expr: Self) -> Self {
+        Self::Count(Box::new(expr))
+    }
+
+    /// Creates a new `COUNT(*)` aggregate expression, counting all the rows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     category: String,
+    /// };
+    ///
+    /// let mut query = <Query<MyModel>>::new();
+    /// query
+    ///     .group_by(Expr::field("category"))
+    ///     .having(Expr::gte(Expr::count_all(), Expr::value(10)));
+    /// ```
+    #[must_use]
+    pub fn count_all() -> Self {
+        Self::CountAll
+    }
+
+    /// Creates a new `SUM` aggregate expression.
+    ///
+    /// Note that the type of the result depends on the database: PostgreSQL
+    /// and MySQL return a decimal when summing integers. Wrap the expression
+    /// in [`Expr::cast`] (or use [`ExprAggregate::sum`], which does this for
+    /// you) to get a consistent result type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::ColumnType;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::cast(Expr::sum(Expr::field("quantity")), ColumnType::BigInteger);
+    /// ```
+    #[must_use]
+    pub fn sum(expr: Self) -> Self {
+        Self::Sum(Box::new(expr))
+    }
+
+    /// Creates a new `AVG` aggregate expression.
+    ///
+    /// Note that the type of the result depends on the database: PostgreSQL
+    /// and MySQL return a decimal. Wrap the expression in [`Expr::cast`] (or
+    /// use [`ExprAggregate::avg`], which does this for you) to get a
+    /// consistent result type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::ColumnType;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::cast(Expr::avg(Expr::field("quantity")), ColumnType::Double);
+    /// ```
+    #[must_use]
+    pub fn avg(expr: Self) -> Self {
+        Self::Avg(Box::new(expr))
+    }
+
+    /// Creates a new `MIN` aggregate expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::min(Expr::field("price"));
+    /// ```
+    #[must_use]
+    pub fn min(expr: Self) -> Self {
+        Self::Min(Box::new(expr))
+    }
+
+    /// Creates a new `MAX` aggregate expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::max(Expr::field("price"));
+    /// ```
+    #[must_use]
+    pub fn max(expr: Self) -> Self {
+        Self::Max(Box::new(expr))
+    }
+
+    /// Creates a new expression converting `expr` to the database type
+    /// corresponding to `column_type`.
+    ///
+    /// The exact SQL type used is chosen by the database backend; for
+    /// instance, MySQL only allows casting integers to `SIGNED` or
+    /// `UNSIGNED`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::ColumnType;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::cast(Expr::field("score"), ColumnType::Double);
+    /// ```
+    #[must_use]
+    pub fn cast(expr: Self, column_type: ColumnType) -> Self {
+        Self::Cast(Box::new(expr), column_type)
+    }
+
+    /// Creates a new JSON path lookup expression, extracting the value found
+    /// under the given sequence of object keys inside the JSON document
+    /// `expr` evaluates to.
+    ///
+    /// The extracted value is returned as text: strings are unquoted, while
+    /// numbers, booleans, objects and arrays are returned in their textual
+    /// representation. If there's no value at the given path, the result is
+    /// `NULL`. Use [`Expr::cast`] to compare the extracted value with
+    /// non-textual values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::ColumnType;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let name = Expr::eq(
+    ///     Expr::json_get(Expr::field("data"), ["user", "name"]),
+    ///     Expr::value("alice"),
+    /// );
+    /// let age = Expr::gte(
+    ///     Expr::cast(
+    ///         Expr::json_get(Expr::field("data"), ["user", "age"]),
+    ///         ColumnType::Integer,
+    ///     ),
+    ///     Expr::value(18),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn json_get<I, S>(expr: Self, path: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::JsonGet(Box::new(expr), path.into_iter().map(Into::into).collect())
+    }
+
+    /// Creates a new expression checking if the array `lhs` contains all the
+    /// elements of the array `rhs` (the `@>` operator).
+    ///
+    /// This is only supported on PostgreSQL; building the expression for
+    /// other database backends results in an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::array_contains(Expr::field("tags"), Expr::value(vec!["rust".to_owned()]));
+    /// ```
+    #[must_use]
+    pub fn array_contains(lhs: Self, rhs: Self) -> Self {
+        Self::ArrayContains(Box::new(lhs), Box::new(rhs))
+    }
+
+    /// Creates a new expression checking if all the elements of the array
+    /// `lhs` are contained in the array `rhs` (the `<@` operator).
+    ///
+    /// This is only supported on PostgreSQL; building the expression for
+    /// other database backends results in an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::array_contained_by(Expr::field("scores"), Expr::value(vec![1, 2, 3]));
+    /// ```
+    #[must_use]
+    pub fn array_contained_by(lhs: Self, rhs: Self) -> Self {
+        Self::ArrayContainedBy(Box::new(lhs), Box::new(rhs))
+    }
+
+    /// Creates a new expression checking if the arrays `lhs` and `rhs` have
+    /// at least one element in common (the `&&` operator).
+    ///
+    /// This is only supported on PostgreSQL; building the expression for
+    /// other database backends results in an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::array_overlaps(Expr::field("tags"), Expr::value(vec!["rust".to_owned()]));
+    /// ```
+    #[must_use]
+    pub fn array_overlaps(lhs: Self, rhs: Self) -> Self {
+        Self::ArrayOverlaps(Box::new(lhs), Box::new(rhs))
+    }
+
+    /// Creates a new full-text search expression, checking if the text
+    /// `document` matches the search `query`.
+    ///
+    /// Both the document and the query are processed using the text search
+    /// configuration named `config` (such as `english` or `simple`), which
+    /// determines how the text is split into words and normalized. The query
+    /// uses the web search syntax: unquoted words must all be present, quoted
+    /// text must appear as a phrase, `or` separates alternatives, and `-`
+    /// excludes a word.
+    ///
+    /// This is only supported on PostgreSQL, where it is translated into
+    /// `to_tsvector(config, document) @@ websearch_to_tsquery(config, query)`;
+    /// building the expression for other database backends results in an
+    /// error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::search(Expr::field("body"), Expr::value("rust -python"), "english");
+    /// ```
+    #[must_use]
+    pub fn search<C: Into<String>>(document: Self, query: Self, config: C) -> Self {
+        Self::Search(Box::new(document), Box::new(query), config.into())
+    }
+
+    /// Creates a new expression computing how well the text `document`
+    /// matches the search `query`, to be used for ordering the results of a
+    /// [`Expr::search`]. Higher values mean more relevant matches.
+    ///
+    /// This is only supported on PostgreSQL, where it is translated into a
+    /// call to the `ts_rank` function; building the expression for other
+    /// database backends results in an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// let expr = Expr::search_rank(Expr::field("body"), Expr::value("rust"), "english");
+    /// ```
+    #[must_use]
+    pub fn search_rank<C: Into<String>>(document: Self, query: Self, config: C) -> Self {
+        Self::SearchRank(Box::new(document), Box::new(query), config.into())
+    }
+
+    /// Creates a new field expression referencing a column of the table of
+    /// model `T`, qualified with the table name.
+    ///
+    /// This is useful in the filter of a [`Subquery`] to reference the row
+    /// currently processed by the outer query, which queries model `T`. Since
+    /// the column is qualified only with the table name, the subquery must
+    /// query a different model than the outer query.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    /// use cot::db::{Auto, ForeignKey, Model, model};
+    ///
+    /// #[model]
+    /// struct User {
+    ///     #[model(primary_key)]
+    ///     id: Auto<i32>,
+    /// }
+    ///
+    /// #[model]
+    /// struct Post {
+    ///     #[model(primary_key)]
+    ///     id: Auto<i32>,
+    ///     author: ForeignKey<User>,
+    /// }
+    ///
+    /// // users that have written at least one post
+    /// let mut posts = Query::<Post>::new();
+    /// posts.filter(Expr::eq(
+    ///     Expr::field("author"),
+    ///     Expr::outer_field::<User, _>("id"),
+    /// ));
+    /// let mut users = Query::<User>::new();
+    /// users.filter(Expr::exists(&posts));
+    /// ```
+    #[must_use]
+    pub fn outer_field<T: Model, I: Into<Identifier>>(column: I) -> Self {
+        Self::OuterField(T::TABLE_NAME, column.into())
+    }
+
+    /// Creates a new `NOT` expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: String,
+    /// };
+    ///
+    /// let expr = Expr::not(Expr::contains(Expr::field("name"), Expr::value("foo")));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, !$name.contains("foo"))
+    /// );
+    /// ```
+    #[must_use]
+    #[expect(clippy::should_implement_trait)]
+    pub fn not(expr: Self) -> Self {
+        Self::Not(Box::new(expr))
+    }
+
+    /// Creates a new `BETWEEN` expression, checking if `expr` lies between
+    /// `low` and `high`, inclusive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let expr = Expr::between(Expr::field("id"), Expr::value(1), Expr::value(10));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $id.between(1, 10))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn between(expr: Self, low: Self, high: Self) -> Self {
+        Self::Between(Box::new(expr), Box::new(low), Box::new(high))
+    }
+
+    /// Creates a new `IN` expression, checking if `lhs` is equal to any of the
+    /// given values. If there are no values, the expression is always false.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let expr = Expr::is_in(Expr::field("id"), [1, 2, 3].map(Expr::value));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $id.is_in([1, 2, 3]))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn is_in<I: IntoIterator<Item = Self>>(lhs: Self, values: I) -> Self {
+        Self::In(Box::new(lhs), values.into_iter().collect())
+    }
+
+    /// Creates a new `NOT IN` expression, checking if `lhs` is not equal to
+    /// any of the given values. If there are no values, the expression is
+    /// always true.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let expr = Expr::is_not_in(Expr::field("id"), [1, 2, 3].map(Expr::value));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $id.is_not_in([1, 2, 3]))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn is_not_in<I: IntoIterator<Item = Self>>(lhs: Self, values: I) -> Self {
+        Self::NotIn(Box::new(lhs), values.into_iter().collect())
+    }
+
+    /// Creates a new `IS NULL` expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: Option<String>,
+    /// };
+    ///
+    /// let expr = Expr::is_null(Expr::field("name"));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $name.is_null())
+    /// );
+    /// ```
+    #[must_use]
+    pub fn is_null(expr: Self) -> Self {
+        Self::IsNull(Box::new(expr))
+    }
+
+    /// Creates a new `IS NOT NULL` expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: Option<String>,
+    /// };
+    ///
+    /// let expr = Expr::is_not_null(Expr::field("name"));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $name.is_not_null())
+    /// );
+    /// ```
+    #[must_use]
+    pub fn is_not_null(expr: Self) -> Self {
+        Self::IsNotNull(Box::new(expr))
+    }
+
+    /// Creates a new `EXISTS` expression, checking if the subquery returns any
+    /// rows.
+    ///
+    /// Only the filter of the query (along with any joins it requires) is used
+    /// in the subquery; the ordering, grouping, and limits are ignored. Use
+    /// [`Expr::outer_field`] in the filter to reference the row processed by
+    /// the outer query.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::{model, query};
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct Post {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     published: bool,
+    /// };
+    ///
+    /// #[model]
+    /// struct Settings {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let published = query!(Post, $published == true).clone();
+    ///
+    /// assert_eq!(
+    ///     <Query<Settings>>::new().filter(Expr::exists(published.clone())),
+    ///     query!(Settings, exists(published))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn exists<S: Into<Subquery>>(subquery: S) -> Self {
+        Self::Exists(Box::new(subquery.into()))
+    }
+
+    /// Adds the relations referenced anywhere in this expression to the list,
+    /// skipping the ones that are already there.
+    pub(crate) fn collect_relations(&self, relations: &mut Vec<Relation>) {
+        match self {
+            Self::RelatedField(relation, _) => relation::push_unique(relations, *relation),
+            // subqueries do their own joins
+            Self::Field(_)
+            | Self::OuterField(..)
+            | Self::Value(_)
+            | Self::CountAll
+            | Self::Exists(_) => {}
+            Self::And(lhs, rhs)
+            | Self::Or(lhs, rhs)
+            | Self::Eq(lhs, rhs)
+            | Self::Ne(lhs, rhs)
+            | Self::Lt(lhs, rhs)
+            | Self::Lte(lhs, rhs)
+            | Self::Gt(lhs, rhs)
+            | Self::Gte(lhs, rhs)
+            | Self::Add(lhs, rhs)
+            | Self::Sub(lhs, rhs)
+            | Self::Mul(lhs, rhs)
+            | Self::Div(lhs, rhs)
+            | Self::Contains(lhs, rhs, _)
+            | Self::StartsWith(lhs, rhs, _)
+            | Self::EndsWith(lhs, rhs, _)
+            | Self::RawLike(lhs, rhs, _)
+            | Self::ArrayContains(lhs, rhs)
+            | Self::ArrayContainedBy(lhs, rhs)
+            | Self::ArrayOverlaps(lhs, rhs)
+            | Self::Search(lhs, rhs, _)
+            | Self::SearchRank(lhs, rhs, _) => {
+                lhs.collect_relations(relations);
+                rhs.collect_relations(relations);
+            }
+            Self::Between(expr, low, high) => {
+                expr.collect_relations(relations);
+                low.collect_relations(relations);
+                high.collect_relations(relations);
+            }
+            Self::In(lhs, values) | Self::NotIn(lhs, values) => {
+                lhs.collect_relations(relations);
+                for value in values {
+                    value.collect_relations(relations);
+                }
+            }
+            Self::Count(expr)
+            | Self::Sum(expr)
+            | Self::Avg(expr)
+            | Self::Min(expr)
+            | Self::Max(expr)
+            | Self::Cast(expr, _)
+            | Self::JsonGet(expr, _)
+            | Self::Not(expr)
+            | Self::IsNull(expr)
+            | Self::IsNotNull(expr) => expr.collect_relations(relations),
+        }
+    }
+
+    /// Returns the expression as a [`sea_query::SimpleExpr`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::expr::Expr;
     /// use cot::db::{Database, Identifier};
     /// use sea_query::{ExprTrait, IntoColumnRef};
     ///
@@ -1225,12 +2237,14 @@ impl Expr {
     ///
     /// Returns [`QueryBuildingError`] if the backend cannot express a given
     /// expression.
+    #[expect(clippy::too_many_lines)] // it's one arm per expression
     pub fn as_sea_query_expr(
         &self,
         sql_builder: &dyn SqlQueryBuilder,
     ) -> Result<SimpleExpr, QueryBuildingError> {
         match self {
             Self::Field(identifier) => Ok((*identifier).into_column_ref().into()),
+            Self::RelatedField(relation, identifier) => Ok(relation.column_ref(*identifier).into()),
             Self::Value(value) => Ok((*value).clone().into()),
             Self::And(lhs, rhs) => Ok(lhs
                 .as_sea_query_expr(sql_builder)?
@@ -1284,10 +2298,158 @@ impl Expr {
             Self::RawLike(lhs, rhs, case_sensitivity) => {
                 like::like_expr(sql_builder, lhs, rhs, LikeMode::Raw, *case_sensitivity)
             }
+            Self::Count(expr) => {
+                Ok(sea_query::Func::count(expr.as_sea_query_expr(sql_builder)?).into())
+            }
+            Self::CountAll => Ok(sea_query::Func::count(sea_query::Expr::col(Asterisk)).into()),
+            Self::Sum(expr) => {
+                Ok(sea_query::Func::sum(expr.as_sea_query_expr(sql_builder)?).into())
+            }
+            Self::Avg(expr) => {
+                Ok(sea_query::Func::avg(expr.as_sea_query_expr(sql_builder)?).into())
+            }
+            Self::Min(expr) => {
+                Ok(sea_query::Func::min(expr.as_sea_query_expr(sql_builder)?).into())
+            }
+            Self::Max(expr) => {
+                Ok(sea_query::Func::max(expr.as_sea_query_expr(sql_builder)?).into())
+            }
+            Self::Cast(expr, column_type) => {
+                sql_builder.cast_expr(expr.as_sea_query_expr(sql_builder)?, *column_type)
+            }
+            Self::JsonGet(expr, path) => {
+                sql_builder.json_extract_expr(expr.as_sea_query_expr(sql_builder)?, path)
+            }
+            Self::ArrayContains(lhs, rhs) => {
+                array::array_expr(sql_builder, lhs, rhs, ArrayOperator::Contains)
+            }
+            Self::ArrayContainedBy(lhs, rhs) => {
+                array::array_expr(sql_builder, lhs, rhs, ArrayOperator::ContainedBy)
+            }
+            Self::ArrayOverlaps(lhs, rhs) => {
+                array::array_expr(sql_builder, lhs, rhs, ArrayOperator::Overlaps)
+            }
+            Self::Search(document, query, config) => {
+                search::search_expr(sql_builder, document, query, config)
+            }
+            Self::SearchRank(document, query, config) => {
+                search::search_rank_expr(sql_builder, document, query, config)
+            }
+            Self::Not(expr) => Ok(expr.as_sea_query_expr(sql_builder)?.not()),
+            Self::Between(expr, low, high) => Ok(expr.as_sea_query_expr(sql_builder)?.between(
+                low.as_sea_query_expr(sql_builder)?,
+                high.as_sea_query_expr(sql_builder)?,
+            )),
+            Self::In(lhs, values) => Ok(lhs
+                .as_sea_query_expr(sql_builder)?
+                .is_in(Self::as_sea_query_exprs(values, sql_builder)?)),
+            Self::NotIn(lhs, values) => Ok(lhs
+                .as_sea_query_expr(sql_builder)?
+                .is_not_in(Self::as_sea_query_exprs(values, sql_builder)?)),
+            Self::IsNull(expr) => Ok(expr.as_sea_query_expr(sql_builder)?.is_null()),
+            Self::IsNotNull(expr) => Ok(expr.as_sea_query_expr(sql_builder)?.is_not_null()),
+            Self::Exists(subquery) => Ok(SimpleExpr::exists(
+                subquery.as_sea_query_statement(sql_builder)?,
+            )),
+            Self::OuterField(table, column) => Ok((*table, *column).into_column_ref().into()),
+        }
+    }
+
+    fn as_sea_query_exprs(
+        exprs: &[Self],
+        sql_builder: &dyn SqlQueryBuilder,
+    ) -> Result<Vec<SimpleExpr>, QueryBuildingError> {
+        exprs
+            .iter()
+            .map(|expr| expr.as_sea_query_expr(sql_builder))
+            .collect()
+    }
+}
+
+/// A query used inside an expression, such as [`Expr::exists`].
+///
+/// A subquery is created from a [`Query`] and only keeps the model's table and
+/// the query's filter, along with the joins the filter requires.
+///
+/// # Example
+///
+/// ```
+/// use cot::db::query::Query;
+/// use cot::db::query::expr::{Expr, Subquery};
+/// use cot::db::{model, query};
+///
+/// #[model]
+/// struct MyModel {
+///     #[model(primary_key)]
+///     id: i32,
+/// };
+///
+/// let subquery = Subquery::from(query!(MyModel, $id == 5));
+/// let expr = Expr::exists(subquery);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subquery {
+    table: Identifier,
+    filter: Option<Expr>,
+    relations: Vec<Relation>,
+}
+
+impl Subquery {
+    /// Returns the name of the queried table.
+    #[must_use]
+    pub fn table(&self) -> Identifier {
+        self.table
+    }
+
+    /// Returns the filter of the subquery, if any.
+    #[must_use]
+    pub fn filter(&self) -> Option<&Expr> {
+        self.filter.as_ref()
+    }
+
+    fn as_sea_query_statement(
+        &self,
+        sql_builder: &dyn SqlQueryBuilder,
+    ) -> Result<sea_query::SelectStatement, QueryBuildingError> {
+        let mut statement = sea_query::Query::select();
+        statement.expr(SimpleExpr::value(1)).from(self.table);
+        for relation in &self.relations {
+            relation.add_join_to_statement(&mut statement, self.table);
+        }
+        if let Some(filter) = &self.filter {
+            statement.and_where(filter.as_sea_query_expr(sql_builder)?);
+        }
+        Ok(statement)
+    }
+}
+
+impl<T: Model> From<&Query<T>> for Subquery {
+    fn from(query: &Query<T>) -> Self {
+        let mut relations = Vec::new();
+        if let Some(filter) = &query.filter {
+            filter.collect_relations(&mut relations);
+        }
+
+        Self {
+            table: T::TABLE_NAME,
+            filter: query.filter.clone(),
+            relations,
         }
     }
 }
 
+impl<T: Model> From<&mut Query<T>> for Subquery {
+    fn from(query: &mut Query<T>) -> Self {
+        Self::from(&*query)
+    }
+}
+
+impl<T: Model> From<Query<T>> for Subquery {
+    fn from(query: Query<T>) -> Self {
+        Self::from(&query)
+    }
+}
+
 /// A reference to a field in a database table.
 ///
 /// This is used to create expressions that reference a specific column in a
@@ -1296,25 +2458,341 @@ impl Expr {
 #[derive(Debug)]
 pub struct FieldRef<T> {
     identifier: Identifier,
+    /// The columns the field is stored in, if there is more than one.
+    columns: &'static [Identifier],
+    relation: Option<Relation>,
     phantom_data: PhantomData<T>,
 }
 
-impl<T: FromDbValue + ToDbFieldValue> FieldRef<T> {
-    /// Create a new field reference.
+// manual impls to avoid the `T: Clone` and `T: Copy` bounds added by derives
+impl<T> Clone for FieldRef<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FieldRef<T> {}
+
+impl<T: FromDbValue + ToDbFieldValue> FieldRef<T> {
+    /// Create a new field reference.
+    #[must_use]
+    pub const fn new(identifier: Identifier) -> Self {
+        Self {
+            identifier,
+            columns: &[],
+            relation: None,
+            phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<T: CompositeForeignKeyField> FieldRef<T> {
+    /// Create a new reference to a foreign key field referencing a model with
+    /// a composite primary key.
+    ///
+    /// `identifier` is the name of the field, and `columns` are the columns
+    /// the field is stored in, in the order of the primary key fields of the
+    /// referenced model.
+    #[must_use]
+    pub const fn composite(identifier: Identifier, columns: &'static [Identifier]) -> Self {
+        Self {
+            identifier,
+            columns,
+            relation: None,
+            phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<T> FieldRef<T> {
+    /// Returns the field reference as an [`Expr`].
+    #[must_use]
+    pub fn as_expr(&self) -> Expr {
+        match self.relation {
+            Some(relation) => Expr::RelatedField(relation, self.identifier),
+            None => Expr::Field(self.identifier),
+        }
+    }
+
+    /// Returns a sort key that orders the results by this field in ascending
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::{model, query};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let order = <MyModel as cot::db::Model>::Fields::id.asc();
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().order_by(order),
+    ///     query!(MyModel; order_by($id.asc()))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn asc(&self) -> OrderBy {
+        OrderBy::asc(self.as_expr())
+    }
+
+    /// Returns a sort key that orders the results by this field in descending
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::{model, query};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let order = <MyModel as cot::db::Model>::Fields::id.desc();
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().order_by(order),
+    ///     query!(MyModel; order_by($id.desc()))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn desc(&self) -> OrderBy {
+        OrderBy::desc(self.as_expr())
+    }
+
+    /// Returns a `COUNT` aggregate over this field, counting the rows in which
+    /// the field is not `NULL`.
+    ///
+    /// The result of this aggregate can be read as an `i64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let expr = <MyModel as cot::db::Model>::Fields::id.count();
+    ///
+    /// assert_eq!(expr, Expr::count(Expr::field("id")));
+    /// ```
+    #[must_use]
+    pub fn count(&self) -> Expr {
+        Expr::count(self.as_expr())
+    }
+
+    /// Returns a `MIN` aggregate over this field.
+    ///
+    /// The result of this aggregate can be read as an `Option<T>`; it is
+    /// `None` if there are no rows to aggregate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let expr = <MyModel as cot::db::Model>::Fields::id.min();
+    ///
+    /// assert_eq!(expr, Expr::min(Expr::field("id")));
+    /// ```
+    #[must_use]
+    pub fn min(&self) -> Expr {
+        Expr::min(self.as_expr())
+    }
+
+    /// Returns a `MAX` aggregate over this field.
+    ///
+    /// The result of this aggregate can be read as an `Option<T>`; it is
+    /// `None` if there are no rows to aggregate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::expr::Expr;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let expr = <MyModel as cot::db::Model>::Fields::id.max();
+    ///
+    /// assert_eq!(expr, Expr::max(Expr::field("id")));
+    /// ```
+    #[must_use]
+    pub fn max(&self) -> Expr {
+        Expr::max(self.as_expr())
+    }
+
+    /// Returns an assignment that sets this field to the value of the given
+    /// expression when used in [`Query::update`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::expr::{Expr, ExprAdd};
+    /// use cot::db::query::update::Assignment;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     counter: i32,
+    /// };
+    ///
+    /// let counter = <MyModel as cot::db::Model>::Fields::counter;
+    /// let assignment = counter.set_expr(counter.add(1));
+    ///
+    /// assert_eq!(
+    ///     assignment,
+    ///     Assignment::new(
+    ///         "counter",
+    ///         Expr::add(Expr::field("counter"), Expr::value(1))
+    ///     )
+    /// );
+    /// ```
+    #[must_use]
+    pub fn set_expr<E: Into<Expr>>(&self, value: E) -> Assignment {
+        Assignment::with_relation(self.identifier, self.relation, value.into())
+    }
+}
+
+impl<T: ToDbFieldValue + 'static> FieldRef<T> {
+    /// Returns an assignment that sets this field to the given value when
+    /// used in [`Query::update`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::model;
+    /// use cot::db::query::expr::Expr;
+    /// use cot::db::query::update::Assignment;
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: String,
+    /// };
+    ///
+    /// let assignment = <MyModel as cot::db::Model>::Fields::name.set("John");
+    ///
+    /// assert_eq!(
+    ///     assignment,
+    ///     Assignment::new("name", Expr::value("John"))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn set<V: IntoField<T>>(&self, value: V) -> Assignment {
+        self.set_expr(Expr::value(value.into_field()))
+    }
+}
+
+impl<T: ForeignKeyField> FieldRef<T> {
+    /// Returns the relation following this foreign key field to the referenced
+    /// model.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::{Auto, ForeignKey, Model, model};
+    ///
+    /// #[model]
+    /// struct User {
+    ///     #[model(primary_key)]
+    ///     id: Auto<i32>,
+    /// }
+    ///
+    /// #[model]
+    /// struct Post {
+    ///     #[model(primary_key)]
+    ///     id: Auto<i32>,
+    ///     author: ForeignKey<User>,
+    /// }
+    ///
+    /// let relation = <Post as Model>::Fields::author.relation();
+    ///
+    /// assert_eq!(relation.column().as_str(), "author");
+    /// ```
     #[must_use]
-    pub const fn new(identifier: Identifier) -> Self {
-        Self {
-            identifier,
+    pub fn relation(&self) -> Relation {
+        Relation::with_foreign_key_columns::<T>(self.identifier, self.columns)
+    }
+
+    /// Returns a reference to a field of the model referenced by this foreign
+    /// key field, which can be used to filter or order the results by the
+    /// values of the referenced model.
+    ///
+    /// `field` must be one of the fields of the referenced model. The
+    /// referenced model's table is joined automatically when the query is
+    /// executed. Only a single level of relations is supported, i.e. the
+    /// returned field reference can't be used to follow another relation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::ExprEq;
+    /// use cot::db::{Auto, ForeignKey, Model, model};
+    ///
+    /// #[model]
+    /// struct User {
+    ///     #[model(primary_key)]
+    ///     id: Auto<i32>,
+    ///     username: String,
+    /// }
+    ///
+    /// #[model]
+    /// struct Post {
+    ///     #[model(primary_key)]
+    ///     id: Auto<i32>,
+    ///     author: ForeignKey<User>,
+    /// }
+    ///
+    /// let query = Post::objects().filter(
+    ///     <Post as Model>::Fields::author
+    ///         .related(<User as Model>::Fields::username)
+    ///         .eq("alice"),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn related<U>(&self, field: FieldRef<U>) -> FieldRef<U> {
+        FieldRef {
+            identifier: field.identifier,
+            columns: field.columns,
+            relation: Some(self.relation()),
             phantom_data: PhantomData,
         }
     }
 }
 
-impl<T> FieldRef<T> {
-    /// Returns the field reference as an [`Expr`].
-    #[must_use]
-    pub fn as_expr(&self) -> Expr {
-        Expr::Field(self.identifier)
+impl<T> From<FieldRef<T>> for Expr {
+    fn from(field: FieldRef<T>) -> Self {
+        field.as_expr()
     }
 }
 
@@ -1589,6 +3067,31 @@ pub trait ExprOrd<T> {
     /// );
     /// ```
     fn gte<V: IntoField<T>>(self, other: V) -> Expr;
+
+    /// Creates an expression that checks if the field lies between the given
+    /// values, inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::query::{Expr, ExprOrd};
+    /// use cot::db::{model, query};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let expr = <MyModel as cot::db::Model>::Fields::id.between(5, 10);
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $id.between(5, 10))
+    /// );
+    /// ```
+    fn between<L: IntoField<T>, H: IntoField<T>>(self, low: L, high: H) -> Expr;
 }
 
 impl<T: ToDbFieldValue + Ord + 'static> ExprOrd<T> for FieldRef<T> {
@@ -1607,13 +3110,232 @@ impl<T: ToDbFieldValue + Ord + 'static> ExprOrd<T> for FieldRef<T> {
     fn gte<V: IntoField<T>>(self, other: V) -> Expr {
         Expr::gte(self.as_expr(), Expr::value(other.into_field()))
     }
+
+    fn between<L: IntoField<T>, H: IntoField<T>>(self, low: L, high: H) -> Expr {
+        Expr::between(
+            self.as_expr(),
+            Expr::value(low.into_field()),
+            Expr::value(high.into_field()),
+        )
+    }
+}
+
+/// A trait for types that can be checked for membership in a list of values in
+/// database expressions.
+pub trait ExprIn<T> {
+    /// Creates an expression that checks if the field is equal to any of the
+    /// given values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::query::{Expr, ExprIn};
+    /// use cot::db::{model, query};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let expr = <MyModel as cot::db::Model>::Fields::id.is_in([1, 2, 3]);
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $id.is_in([1, 2, 3]))
+    /// );
+    /// ```
+    #[expect(clippy::wrong_self_convention)] // mirrors the SQL operator name
+    fn is_in<V: IntoField<T>, I: IntoIterator<Item = V>>(self, values: I) -> Expr;
+
+    /// Creates an expression that checks if the field is not equal to any of
+    /// the given values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::query::{Expr, ExprIn};
+    /// use cot::db::{model, query};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let expr = <MyModel as cot::db::Model>::Fields::id.is_not_in([1, 2, 3]);
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $id.is_not_in([1, 2, 3]))
+    /// );
+    /// ```
+    #[expect(clippy::wrong_self_convention)] // mirrors the SQL operator name
+    fn is_not_in<V: IntoField<T>, I: IntoIterator<Item = V>>(self, values: I) -> Expr;
+}
+
+impl<T: ToDbFieldValue + 'static> ExprIn<T> for FieldRef<T> {
+    fn is_in<V: IntoField<T>, I: IntoIterator<Item = V>>(self, values: I) -> Expr {
+        Expr::is_in(
+            self.as_expr(),
+            values
+                .into_iter()
+                .map(|value| Expr::value(value.into_field())),
+        )
+    }
+
+    fn is_not_in<V: IntoField<T>, I: IntoIterator<Item = V>>(self, values: I) -> Expr {
+        Expr::is_not_in(
+            self.as_expr(),
+            values
+                .into_iter()
+                .map(|value| Expr::value(value.into_field())),
+        )
+    }
+}
+
+/// A trait for nullable database types that can be checked for `NULL` in
+/// database expressions.
+pub trait ExprNull {
+    /// Creates an expression that checks if the field is `NULL`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::query::{Expr, ExprNull};
+    /// use cot::db::{model, query};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: Option<String>,
+    /// };
+    ///
+    /// let expr = <MyModel as cot::db::Model>::Fields::name.is_null();
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $name.is_null())
+    /// );
+    /// ```
+    #[expect(clippy::wrong_self_convention)] // mirrors the SQL operator name
+    fn is_null(self) -> Expr;
+
+    /// Creates an expression that checks if the field is not `NULL`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::query::{Expr, ExprNull};
+    /// use cot::db::{model, query};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: Option<String>,
+    /// };
+    ///
+    /// let expr = <MyModel as cot::db::Model>::Fields::name.is_not_null();
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().filter(expr),
+    ///     query!(MyModel, $name.is_not_null())
+    /// );
+    /// ```
+    #[expect(clippy::wrong_self_convention)] // mirrors the SQL operator name
+    fn is_not_null(self) -> Expr;
+}
+
+impl<T> ExprNull for FieldRef<Option<T>> {
+    fn is_null(self) -> Expr {
+        Expr::is_null(self.as_expr())
+    }
+
+    fn is_not_null(self) -> Expr {
+        Expr::is_not_null(self.as_expr())
+    }
+}
+
+/// A trait for numeric database types that can be aggregated.
+///
+/// Unlike [`Expr::sum`] and [`Expr::avg`], the expressions returned by this
+/// trait's methods are cast to a database type that has the same
+/// representation on every backend, so that the results can always be read
+/// back as the documented Rust types.
+pub trait ExprAggregate<T> {
+    /// Returns a `SUM` aggregate over this field.
+    ///
+    /// The result of this aggregate can be read as an `Option<i64>` for
+    /// integer fields and as an `Option<f64>` for floating-point fields; it is
+    /// `None` if there are no rows to aggregate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::query::expr::{Expr, ExprAggregate};
+    /// use cot::db::{ColumnType, model};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     quantity: i32,
+    /// };
+    ///
+    /// let expr = <MyModel as cot::db::Model>::Fields::quantity.sum();
+    ///
+    /// assert_eq!(
+    ///     expr,
+    ///     Expr::cast(Expr::sum(Expr::field("quantity")), ColumnType::BigInteger)
+    /// );
+    /// ```
+    fn sum(self) -> Expr;
+
+    /// Returns an `AVG` aggregate over this field.
+    ///
+    /// The result of this aggregate can be read as an `Option<f64>`; it is
+    /// `None` if there are no rows to aggregate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::db::query::expr::{Expr, ExprAggregate};
+    /// use cot::db::{ColumnType, model};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     quantity: i32,
+    /// };
+    ///
+    /// let expr = <MyModel as cot::db::Model>::Fields::quantity.avg();
+    ///
+    /// assert_eq!(
+    ///     expr,
+    ///     Expr::cast(Expr::avg(Expr::field("quantity")), ColumnType::Double)
+    /// );
+    /// ```
+    fn avg(self) -> Expr;
 }
 
 /// A marker trait that represents the full set of query-translation
 /// capabilities a database backend may support.
-pub trait SqlQueryBuilder: LikeExprBuilder {}
+pub trait SqlQueryBuilder:
+    LikeExprBuilder + CastExprBuilder + JsonExprBuilder + ArrayExprBuilder + SearchExprBuilder
+{
+}
 
-impl<T> SqlQueryBuilder for T where T: LikeExprBuilder {}
+impl<T> SqlQueryBuilder for T where
+    T: LikeExprBuilder + CastExprBuilder + JsonExprBuilder + ArrayExprBuilder + SearchExprBuilder
+{
+}
 
 macro_rules! impl_expr {
     ($ty:ty, $trait:ident, $method:ident) => {
@@ -1625,29 +3347,46 @@ macro_rules! impl_expr {
     };
 }
 
+macro_rules! impl_aggregate_expr {
+    ($ty:ty, $sum_type:ident) => {
+        impl ExprAggregate<$ty> for FieldRef<$ty> {
+            fn sum(self) -> Expr {
+                Expr::cast(Expr::sum(self.as_expr()), ColumnType::$sum_type)
+            }
+
+            fn avg(self) -> Expr {
+                Expr::cast(Expr::avg(self.as_expr()), ColumnType::Double)
+            }
+        }
+    };
+}
+
 macro_rules! impl_num_expr {
-    ($ty:ty) => {
+    ($ty:ty, $sum_type:ident) => {
         impl_expr!($ty, ExprAdd, add);
         impl_expr!($ty, ExprSub, sub);
         impl_expr!($ty, ExprMul, mul);
         impl_expr!($ty, ExprDiv, div);
+        impl_aggregate_expr!($ty, $sum_type);
     };
 }
 
-impl_num_expr!(i8);
-impl_num_expr!(i16);
-impl_num_expr!(i32);
-impl_num_expr!(i64);
-impl_num_expr!(u8);
-impl_num_expr!(u16);
-impl_num_expr!(u32);
-impl_num_expr!(u64);
-impl_num_expr!(f32);
-impl_num_expr!(f64);
+impl_num_expr!(i8, BigInteger);
+impl_num_expr!(i16, BigInteger);
+impl_num_expr!(i32, BigInteger);
+impl_num_expr!(i64, BigInteger);
+impl_num_expr!(u8, BigInteger);
+impl_num_expr!(u16, BigInteger);
+impl_num_expr!(u32, BigInteger);
+impl_num_expr!(u64, BigInteger);
+impl_num_expr!(f32, Double);
+impl_num_expr!(f64, Double);
 
 #[cfg(test)]
 mod test {
     use super::*;
+    #[cfg(feature = "sqlite")]
+    use crate::db::query::test_utils::TestSqlBuilder;
 
     #[test]
     fn expr_field() {
@@ -1696,4 +3435,188 @@ mod test {
     test_expr_constructor!(expr_sub, Sub, sub);
     test_expr_constructor!(expr_mul, Mul, mul);
     test_expr_constructor!(expr_div, Div, div);
+
+    macro_rules! test_aggregate_constructor {
+        ($test_name:ident, $match:ident, $constructor:ident) => {
+            #[test]
+            fn $test_name() {
+                let expr = Expr::$constructor(Expr::field("price"));
+                if let Expr::$match(inner) = expr {
+                    assert_eq!(*inner, Expr::field("price"));
+                } else {
+                    panic!(concat!("Expected Expr::", stringify!($match)));
+                }
+            }
+        };
+    }
+
+    test_aggregate_constructor!(expr_count, Count, count);
+    test_aggregate_constructor!(expr_sum, Sum, sum);
+    test_aggregate_constructor!(expr_avg, Avg, avg);
+    test_aggregate_constructor!(expr_min, Min, min);
+    test_aggregate_constructor!(expr_max, Max, max);
+
+    #[test]
+    fn expr_cast() {
+        let expr = Expr::cast(Expr::field("price"), ColumnType::Double);
+        if let Expr::Cast(inner, column_type) = expr {
+            assert_eq!(*inner, Expr::field("price"));
+            assert_eq!(column_type, ColumnType::Double);
+        } else {
+            panic!("Expected Expr::Cast");
+        }
+    }
+
+    #[test]
+    fn expr_json_get() {
+        let expr = Expr::json_get(Expr::field("data"), ["user", "name"]);
+        if let Expr::JsonGet(inner, path) = expr {
+            assert_eq!(*inner, Expr::field("data"));
+            assert_eq!(path, ["user", "name"]);
+        } else {
+            panic!("Expected Expr::JsonGet");
+        }
+    }
+
+    #[test]
+    fn field_ref_related() {
+        #[crate::db::model]
+        struct Author {
+            #[model(primary_key)]
+            id: crate::db::Auto<i32>,
+            username: String,
+        }
+
+        let author = FieldRef::<crate::db::ForeignKey<Author>>::new(Identifier::new("author"));
+        let relation = author.relation();
+        let username = author.related(FieldRef::<String>::new(Identifier::new("username")));
+
+        assert_eq!(
+            username.as_expr(),
+            Expr::related_field(relation, Identifier::new("username"))
+        );
+
+        let mut relations = Vec::new();
+        Expr::and(
+            Expr::eq(username.as_expr(), Expr::value("alice")),
+            Expr::eq(Expr::field("id"), Expr::value(1)),
+        )
+        .collect_relations(&mut relations);
+        assert_eq!(relations, vec![relation]);
+    }
+
+    #[test]
+    fn field_ref_aggregates() {
+        let field = || FieldRef::<i32>::new(Identifier::new("quantity"));
+
+        assert_eq!(field().count(), Expr::count(Expr::field("quantity")));
+        assert_eq!(field().min(), Expr::min(Expr::field("quantity")));
+        assert_eq!(field().max(), Expr::max(Expr::field("quantity")));
+        assert_eq!(
+            field().sum(),
+            Expr::cast(Expr::sum(Expr::field("quantity")), ColumnType::BigInteger)
+        );
+        assert_eq!(
+            field().avg(),
+            Expr::cast(Expr::avg(Expr::field("quantity")), ColumnType::Double)
+        );
+        assert_eq!(
+            FieldRef::<f64>::new(Identifier::new("price")).sum(),
+            Expr::cast(Expr::sum(Expr::field("price")), ColumnType::Double)
+        );
+    }
+
+    test_aggregate_constructor!(expr_not, Not, not);
+    test_aggregate_constructor!(expr_is_null, IsNull, is_null);
+    test_aggregate_constructor!(expr_is_not_null, IsNotNull, is_not_null);
+
+    #[test]
+    fn expr_between() {
+        let expr = Expr::between(Expr::field("price"), Expr::value(1), Expr::value(5));
+        if let Expr::Between(inner, low, high) = expr {
+            assert_eq!(*inner, Expr::field("price"));
+            assert_eq!(*low, Expr::value(1));
+            assert_eq!(*high, Expr::value(5));
+        } else {
+            panic!("Expected Expr::Between");
+        }
+    }
+
+    #[test]
+    fn expr_is_in() {
+        let expr = Expr::is_in(Expr::field("id"), [Expr::value(1), Expr::value(2)]);
+        assert_eq!(
+            expr,
+            Expr::In(
+                Box::new(Expr::field("id")),
+                vec![Expr::value(1), Expr::value(2)]
+            )
+        );
+
+        let expr = Expr::is_not_in(Expr::field("id"), []);
+        assert_eq!(expr, Expr::NotIn(Box::new(Expr::field("id")), Vec::new()));
+    }
+
+    #[test]
+    fn field_ref_in_null_between() {
+        let id = FieldRef::<i32>::new(Identifier::new("id"));
+        let name = FieldRef::<Option<String>>::new(Identifier::new("name"));
+
+        assert_eq!(
+            id.is_in([1, 2]),
+            Expr::is_in(Expr::field("id"), [Expr::value(1), Expr::value(2)])
+        );
+        assert_eq!(
+            id.is_not_in(Vec::<i32>::new()),
+            Expr::is_not_in(Expr::field("id"), [])
+        );
+        assert_eq!(
+            id.between(1, 5),
+            Expr::between(Expr::field("id"), Expr::value(1), Expr::value(5))
+        );
+        assert_eq!(name.is_null(), Expr::is_null(Expr::field("name")));
+        assert_eq!(name.is_not_null(), Expr::is_not_null(Expr::field("name")));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn exists_subquery() {
+        #[crate::db::model]
+        struct Author {
+            #[model(primary_key)]
+            id: crate::db::Auto<i32>,
+            username: String,
+        }
+
+        #[crate::db::model]
+        struct Book {
+            #[model(primary_key)]
+            id: crate::db::Auto<i32>,
+            author: crate::db::ForeignKey<Author>,
+        }
+
+        let author = FieldRef::<crate::db::ForeignKey<Author>>::new(Identifier::new("author"));
+        let username = author.related(FieldRef::<String>::new(Identifier::new("username")));
+        let mut books = Query::<Book>::new();
+        books.filter(Expr::and(
+            Expr::eq(Expr::field("id"), Expr::outer_field::<Author, _>("id")),
+            Expr::is_in(username.as_expr(), [Expr::value("alice")]),
+        ));
+        let expr = Expr::not(Expr::exists(&books));
+
+        // the relation is joined inside the subquery, not in the outer query
+        let mut relations = Vec::new();
+        expr.collect_relations(&mut relations);
+        assert!(relations.is_empty());
+
+        let mut select = sea_query::Query::select();
+        select
+            .column(Identifier::new("id"))
+            .from(Author::TABLE_NAME)
+            .and_where(expr.as_sea_query_expr(&TestSqlBuilder).unwrap());
+        assert_eq!(
+            select.to_string(sea_query::SqliteQueryBuilder),
+            r#"SELECT "id" FROM "cot__author" WHERE NOT EXISTS(SELECT 1 FROM "cot__book" LEFT JOIN (SELECT "id" AS "author__id", "username" AS "author__username" FROM "cot__author") AS "author" ON "cot__book"."author" = "author"."author__id" WHERE "id" = "cot__author"."id" AND "author"."author__username" IN ('alice'))"#
+        );
+    }
 }
@@ -0,0 +1,150 @@
+//! Database expressions for array columns.
+
+use sea_query::SimpleExpr;
+
+#[cfg(feature = "postgres")]
+use crate::db::ToDbValue;
+use crate::db::query::QueryBuildingError;
+#[cfg(feature = "postgres")]
+use crate::db::query::expr::FieldRef;
+use crate::db::query::expr::{Expr, SqlQueryBuilder};
+
+/// The array comparison performed by an array expression.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ArrayOperator {
+    /// The left-hand side array contains all the elements of the right-hand
+    /// side one (`@>`).
+    Contains,
+    /// All the elements of the left-hand side array are contained in the
+    /// right-hand side one (`<@`).
+    ContainedBy,
+    /// The arrays have at least one element in common (`&&`).
+    Overlaps,
+}
+
+/// Translates Cot's array query expressions (see [`Expr::array_contains`],
+/// [`Expr::array_contained_by`] and [`Expr::array_overlaps`]) into a
+/// backend-specific `sea_query` expression.
+///
+/// Only PostgreSQL has native array columns; the other backends return an
+/// error.
+///
+/// [`Expr::array_contains`]: crate::db::query::expr::Expr::array_contains
+/// [`Expr::array_contained_by`]: crate::db::query::expr::Expr::array_contained_by
+/// [`Expr::array_overlaps`]: crate::db::query::expr::Expr::array_overlaps
+pub trait ArrayExprBuilder {
+    /// Builds the `sea_query` expression comparing the arrays `lhs` and `rhs`
+    /// using `operator`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QueryBuildingError`] if the backend doesn't support arrays.
+    fn array_expr(
+        &self,
+        lhs: SimpleExpr,
+        rhs: SimpleExpr,
+        operator: ArrayOperator,
+    ) -> Result<SimpleExpr, QueryBuildingError>;
+}
+
+pub(crate) fn array_expr(
+    sql_builder: &dyn SqlQueryBuilder,
+    lhs: &Expr,
+    rhs: &Expr,
+    operator: ArrayOperator,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    sql_builder.array_expr(
+        lhs.as_sea_query_expr(sql_builder)?,
+        rhs.as_sea_query_expr(sql_builder)?,
+        operator,
+    )
+}
+
+/// A trait for database types that store arrays.
+#[cfg(feature = "postgres")]
+pub trait ExprArray<T> {
+    /// Checks if the field contains all of the given `values`.
+    ///
+    /// See [`Expr::array_contains`] for the underlying semantics.
+    fn array_contains<V: IntoIterator<Item = T>>(self, values: V) -> Expr;
+
+    /// Checks if all the elements of the field are among the given `values`.
+    ///
+    /// See [`Expr::array_contained_by`] for the underlying semantics.
+    fn array_contained_by<V: IntoIterator<Item = T>>(self, values: V) -> Expr;
+
+    /// Checks if the field contains any of the given `values`.
+    ///
+    /// See [`Expr::array_overlaps`] for the underlying semantics.
+    fn array_overlaps<V: IntoIterator<Item = T>>(self, values: V) -> Expr;
+}
+
+#[cfg(feature = "postgres")]
+impl<T> ExprArray<T> for FieldRef<Vec<T>>
+where
+    Vec<T>: ToDbValue,
+{
+    fn array_contains<V: IntoIterator<Item = T>>(self, values: V) -> Expr {
+        Expr::array_contains(self.as_expr(), array_value(values))
+    }
+
+    fn array_contained_by<V: IntoIterator<Item = T>>(self, values: V) -> Expr {
+        Expr::array_contained_by(self.as_expr(), array_value(values))
+    }
+
+    fn array_overlaps<V: IntoIterator<Item = T>>(self, values: V) -> Expr {
+        Expr::array_overlaps(self.as_expr(), array_value(values))
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T> ExprArray<T> for FieldRef<Option<Vec<T>>>
+where
+    Vec<T>: ToDbValue,
+{
+    fn array_contains<V: IntoIterator<Item = T>>(self, values: V) -> Expr {
+        Expr::array_contains(self.as_expr(), array_value(values))
+    }
+
+    fn array_contained_by<V: IntoIterator<Item = T>>(self, values: V) -> Expr {
+        Expr::array_contained_by(self.as_expr(), array_value(values))
+    }
+
+    fn array_overlaps<V: IntoIterator<Item = T>>(self, values: V) -> Expr {
+        Expr::array_overlaps(self.as_expr(), array_value(values))
+    }
+}
+
+#[cfg(feature = "postgres")]
+fn array_value<T, V: IntoIterator<Item = T>>(values: V) -> Expr
+where
+    Vec<T>: ToDbValue,
+{
+    Expr::value(values.into_iter().collect::<Vec<T>>())
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use super::*;
+    use crate::db::Identifier;
+
+    #[test]
+    fn field_ref_array_contains() {
+        let field = FieldRef::<Vec<String>>::new(Identifier::new("tags"));
+
+        assert_eq!(
+            field.array_contains(["rust".to_owned()]),
+            Expr::array_contains(Expr::field("tags"), Expr::value(vec!["rust".to_owned()]))
+        );
+    }
+
+    #[test]
+    fn field_ref_array_overlaps_nullable() {
+        let field = FieldRef::<Option<Vec<i32>>>::new(Identifier::new("scores"));
+
+        assert_eq!(
+            field.array_overlaps([1, 2]),
+            Expr::array_overlaps(Expr::field("scores"), Expr::value(vec![1, 2]))
+        );
+    }
+}
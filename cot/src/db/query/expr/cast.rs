@@ -0,0 +1,31 @@
+//! Database expressions for type conversion.
+
+use sea_query::SimpleExpr;
+
+use crate::db::ColumnType;
+use crate::db::query::QueryBuildingError;
+
+/// Translates Cot's type conversion query expressions (see [`Expr::cast`])
+/// into a backend-specific `sea_query` expression.
+///
+/// Each database backend names its types differently (and some, such as
+/// MySQL, only accept a restricted set of types in a `CAST`), so the
+/// translation of a [`ColumnType`] into the target type name is left to the
+/// backend. An implementor only ever sees a single, already-built expression
+/// and the requested [`ColumnType`].
+///
+/// [`Expr::cast`]: crate::db::query::expr::Expr::cast
+pub trait CastExprBuilder {
+    /// Builds the `sea_query` expression that converts `expr` to the
+    /// database type corresponding to `column_type`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QueryBuildingError`] if the backend cannot convert an
+    /// expression to the requested [`ColumnType`].
+    fn cast_expr(
+        &self,
+        expr: SimpleExpr,
+        column_type: ColumnType,
+    ) -> Result<SimpleExpr, QueryBuildingError>;
+}
@@ -0,0 +1,120 @@
+//! Database expressions for JSON documents.
+
+use sea_query::SimpleExpr;
+
+use crate::db::query::QueryBuildingError;
+#[cfg(feature = "json")]
+use crate::db::query::expr::{Expr, FieldRef};
+
+/// Translates Cot's JSON path lookups (see [`Expr::json_get`]) into a
+/// backend-specific `sea_query` expression.
+///
+/// Each database backend exposes a different set of functions for reading
+/// from JSON documents, so the translation is left to the backend. An
+/// implementor only ever sees a single, already-built expression evaluating
+/// to a JSON document, and the list of object keys to follow.
+///
+/// [`Expr::json_get`]: crate::db::query::expr::Expr::json_get
+pub trait JsonExprBuilder {
+    /// Builds the `sea_query` expression that extracts the value found at
+    /// `path` inside the JSON document `expr`, as text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QueryBuildingError`] if the backend cannot extract values
+    /// from JSON documents.
+    fn json_extract_expr(
+        &self,
+        expr: SimpleExpr,
+        path: &[String],
+    ) -> Result<SimpleExpr, QueryBuildingError>;
+}
+
+/// A trait for database types that store JSON documents.
+#[cfg(feature = "json")]
+pub trait ExprJson {
+    /// Extracts the value found at `path` inside the JSON document stored in
+    /// the field.
+    ///
+    /// See [`Expr::json_get`] for the underlying semantics.
+    fn json_get<I, S>(self, path: I) -> Expr
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>;
+}
+
+#[cfg(feature = "json")]
+impl ExprJson for FieldRef<serde_json::Value> {
+    fn json_get<I, S>(self, path: I) -> Expr
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Expr::json_get(self.as_expr(), path)
+    }
+}
+
+#[cfg(feature = "json")]
+impl ExprJson for FieldRef<Option<serde_json::Value>> {
+    fn json_get<I, S>(self, path: I) -> Expr
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Expr::json_get(self.as_expr(), path)
+    }
+}
+
+/// Converts a list of object keys into a JSON path expression, as accepted by
+/// the `JSON_EXTRACT` functions of SQLite and MySQL (e.g. `$."a"."b"`).
+///
+/// Every key is quoted, so that keys containing dots or other special
+/// characters are matched literally.
+pub(crate) fn to_json_path(path: &[String]) -> String {
+    let mut json_path = String::from("$");
+    for key in path {
+        json_path.push_str(".\"");
+        for c in key.chars() {
+            if matches!(c, '"' | '\\') {
+                json_path.push('\\');
+            }
+            json_path.push(c);
+        }
+        json_path.push('"');
+    }
+    json_path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_path_empty() {
+        assert_eq!(to_json_path(&[]), "$");
+    }
+
+    #[test]
+    fn json_path_keys() {
+        assert_eq!(
+            to_json_path(&["user".to_owned(), "name".to_owned()]),
+            r#"$."user"."name""#
+        );
+    }
+
+    #[test]
+    fn json_path_escapes_special_characters() {
+        assert_eq!(to_json_path(&[r#"a."b\c"#.to_owned()]), r#"$."a.\"b\\c""#);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn field_ref_json_get() {
+        let field = FieldRef::<serde_json::Value>::new(crate::db::Identifier::new("data"));
+
+        assert_eq!(
+            field.json_get(["user", "name"]),
+            Expr::json_get(Expr::field("data"), ["user", "name"])
+        );
+    }
+}
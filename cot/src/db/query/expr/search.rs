@@ -0,0 +1,130 @@
+//! Database expressions for full-text search.
+
+use sea_query::SimpleExpr;
+
+#[cfg(feature = "postgres")]
+use crate::db::TextField;
+use crate::db::query::QueryBuildingError;
+#[cfg(feature = "postgres")]
+use crate::db::query::expr::FieldRef;
+use crate::db::query::expr::{Expr, SqlQueryBuilder};
+
+/// Translates Cot's full-text search expressions (see [`Expr::search`] and
+/// [`Expr::search_rank`]) into a backend-specific `sea_query` expression.
+///
+/// Full-text search is only supported on PostgreSQL, where the document is
+/// converted to a `tsvector` and the query to a `tsquery`; the other backends
+/// return an error.
+///
+/// [`Expr::search`]: crate::db::query::expr::Expr::search
+/// [`Expr::search_rank`]: crate::db::query::expr::Expr::search_rank
+pub trait SearchExprBuilder {
+    /// Builds the `sea_query` expression checking whether the text `document`
+    /// matches the search `query`, using the text search configuration named
+    /// `config` (e.g. `english`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QueryBuildingError`] if the backend doesn't support
+    /// full-text search.
+    fn search_expr(
+        &self,
+        document: SimpleExpr,
+        query: SimpleExpr,
+        config: &str,
+    ) -> Result<SimpleExpr, QueryBuildingError>;
+
+    /// Builds the `sea_query` expression computing how well the text
+    /// `document` matches the search `query`, using the text search
+    /// configuration named `config` (e.g. `english`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QueryBuildingError`] if the backend doesn't support
+    /// full-text search.
+    fn search_rank_expr(
+        &self,
+        document: SimpleExpr,
+        query: SimpleExpr,
+        config: &str,
+    ) -> Result<SimpleExpr, QueryBuildingError>;
+}
+
+pub(crate) fn search_expr(
+    sql_builder: &dyn SqlQueryBuilder,
+    document: &Expr,
+    query: &Expr,
+    config: &str,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    sql_builder.search_expr(
+        document.as_sea_query_expr(sql_builder)?,
+        query.as_sea_query_expr(sql_builder)?,
+        config,
+    )
+}
+
+pub(crate) fn search_rank_expr(
+    sql_builder: &dyn SqlQueryBuilder,
+    document: &Expr,
+    query: &Expr,
+    config: &str,
+) -> Result<SimpleExpr, QueryBuildingError> {
+    sql_builder.search_rank_expr(
+        document.as_sea_query_expr(sql_builder)?,
+        query.as_sea_query_expr(sql_builder)?,
+        config,
+    )
+}
+
+/// A trait for database types that can be searched with full-text search.
+#[cfg(feature = "postgres")]
+pub trait ExprSearch {
+    /// Checks if the field matches the search `query`, using the text search
+    /// configuration named `config`.
+    ///
+    /// See [`Expr::search`] for the underlying semantics.
+    fn search<V: Into<String>>(self, query: V, config: &str) -> Expr;
+
+    /// Computes how well the field matches the search `query`, using the
+    /// text search configuration named `config`.
+    ///
+    /// See [`Expr::search_rank`] for the underlying semantics.
+    fn search_rank<V: Into<String>>(self, query: V, config: &str) -> Expr;
+}
+
+#[cfg(feature = "postgres")]
+impl<T: TextField + 'static> ExprSearch for FieldRef<T> {
+    fn search<V: Into<String>>(self, query: V, config: &str) -> Expr {
+        Expr::search(self.as_expr(), Expr::value(query.into()), config)
+    }
+
+    fn search_rank<V: Into<String>>(self, query: V, config: &str) -> Expr {
+        Expr::search_rank(self.as_expr(), Expr::value(query.into()), config)
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use super::*;
+    use crate::db::Identifier;
+
+    #[test]
+    fn field_ref_search() {
+        let field = FieldRef::<String>::new(Identifier::new("body"));
+
+        assert_eq!(
+            field.search("rust", "english"),
+            Expr::search(Expr::field("body"), Expr::value("rust"), "english")
+        );
+    }
+
+    #[test]
+    fn field_ref_search_rank() {
+        let field = FieldRef::<String>::new(Identifier::new("body"));
+
+        assert_eq!(
+            field.search_rank("rust", "simple"),
+            Expr::search_rank(Expr::field("body"), Expr::value("rust"), "simple")
+        );
+    }
+}
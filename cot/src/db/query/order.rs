@@ -0,0 +1,347 @@
+//! Ordering of query results.
+
+use crate::db::query::QueryBuildingError;
+use crate::db::query::expr::{Expr, FieldRef, SqlQueryBuilder};
+
+/// A single sort key used to order the results of a query.
+///
+/// Sort keys are typically created by calling [`FieldRef::asc`] or
+/// [`FieldRef::desc`] on one of the model's fields, or by using the `order_by`
+/// clause of the [`cot::db::query!`] macro.
+///
+/// # Example
+///
+/// ```
+/// use cot::db::query::Query;
+/// use cot::db::query::order::OrderBy;
+/// use cot::db::{model, query};
+///
+/// #[model]
+/// struct MyModel {
+///     #[model(primary_key)]
+///     id: i32,
+///     name: String,
+/// };
+///
+/// let order = <MyModel as cot::db::Model>::Fields::name.desc().nulls_last();
+///
+/// assert_eq!(
+///     <Query<MyModel>>::new().order_by(order),
+///     query!(MyModel; order_by($name.desc().nulls_last()))
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderBy {
+    expr: Expr,
+    direction: OrderDirection,
+    nulls: Option<NullsOrder>,
+}
+
+impl OrderBy {
+    /// Creates a sort key that orders the results by the given expression in
+    /// ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    /// use cot::db::query::order::OrderBy;
+    /// use cot::db::{model, query};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let order = OrderBy::asc(Expr::field("id"));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().order_by(order),
+    ///     query!(MyModel; order_by($id.asc()))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn asc(expr: Expr) -> Self {
+        Self {
+            expr,
+            direction: OrderDirection::Asc,
+            nulls: None,
+        }
+    }
+
+    /// Creates a sort key that orders the results by the given expression in
+    /// descending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::query::expr::Expr;
+    /// use cot::db::query::order::OrderBy;
+    /// use cot::db::{model, query};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    /// };
+    ///
+    /// let order = OrderBy::desc(Expr::field("id"));
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().order_by(order),
+    ///     query!(MyModel; order_by($id.desc()))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn desc(expr: Expr) -> Self {
+        Self {
+            expr,
+            direction: OrderDirection::Desc,
+            nulls: None,
+        }
+    }
+
+    /// Places `NULL` values before all non-`NULL` values, regardless of the
+    /// sort direction.
+    ///
+    /// MySQL doesn't support `NULLS FIRST` natively, so on this backend the
+    /// ordering is emulated by sorting by `expr IS NULL` first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::{model, query};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: Option<String>,
+    /// };
+    ///
+    /// let order = <MyModel as cot::db::Model>::Fields::name.asc().nulls_first();
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().order_by(order),
+    ///     query!(MyModel; order_by($name.nulls_first()))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn nulls_first(mut self) -> Self {
+        self.nulls = Some(NullsOrder::First);
+        self
+    }
+
+    /// Places `NULL` values after all non-`NULL` values, regardless of the
+    /// sort direction.
+    ///
+    /// MySQL doesn't support `NULLS LAST` natively, so on this backend the
+    /// ordering is emulated by sorting by `expr IS NULL` first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::query::Query;
+    /// use cot::db::{model, query};
+    ///
+    /// #[model]
+    /// struct MyModel {
+    ///     #[model(primary_key)]
+    ///     id: i32,
+    ///     name: Option<String>,
+    /// };
+    ///
+    /// let order = <MyModel as cot::db::Model>::Fields::name.desc().nulls_last();
+    ///
+    /// assert_eq!(
+    ///     <Query<MyModel>>::new().order_by(order),
+    ///     query!(MyModel; order_by($name.desc().nulls_last()))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn nulls_last(mut self) -> Self {
+        self.nulls = Some(NullsOrder::Last);
+        self
+    }
+
+    /// Returns the expression the results are ordered by.
+    #[must_use]
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    /// Returns the direction of the ordering.
+    #[must_use]
+    pub fn direction(&self) -> OrderDirection {
+        self.direction
+    }
+
+    /// Returns the placement of `NULL` values, or `None` if the database's
+    /// default should be used.
+    #[must_use]
+    pub fn nulls(&self) -> Option<NullsOrder> {
+        self.nulls
+    }
+
+    pub(super) fn add_to_statement(
+        &self,
+        statement: &mut sea_query::SelectStatement,
+        sql_builder: &dyn SqlQueryBuilder,
+    ) -> Result<(), QueryBuildingError> {
+        let expr = self.expr.as_sea_query_expr(sql_builder)?;
+        let order = self.direction.into();
+        match self.nulls {
+            Some(nulls) => statement.order_by_expr_with_nulls(expr, order, nulls.into()),
+            None => statement.order_by_expr(expr, order),
+        };
+        Ok(())
+    }
+}
+
+impl<T> From<FieldRef<T>> for OrderBy {
+    fn from(field: FieldRef<T>) -> Self {
+        field.asc()
+    }
+}
+
+/// The direction in which query results are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrderDirection {
+    /// Ascending order (`ASC`), i.e. smallest values first.
+    Asc,
+    /// Descending order (`DESC`), i.e. largest values first.
+    Desc,
+}
+
+impl From<OrderDirection> for sea_query::Order {
+    fn from(value: OrderDirection) -> Self {
+        match value {
+            OrderDirection::Asc => Self::Asc,
+            OrderDirection::Desc => Self::Desc,
+        }
+    }
+}
+
+/// The placement of `NULL` values in ordered query results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NullsOrder {
+    /// `NULL` values are placed before all other values (`NULLS FIRST`).
+    First,
+    /// `NULL` values are placed after all other values (`NULLS LAST`).
+    Last,
+}
+
+impl From<NullsOrder> for sea_query::NullOrdering {
+    fn from(value: NullsOrder) -> Self {
+        match value {
+            NullsOrder::First => Self::First,
+            NullsOrder::Last => Self::Last,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_query::{Alias, Asterisk};
+
+    use super::*;
+    use crate::db::query::test_utils::TestSqlBuilder;
+
+    fn render<B: sea_query::QueryBuilder>(orders: &[OrderBy], query_builder: B) -> String {
+        let mut select = sea_query::Query::select();
+        select.column(Asterisk).from(Alias::new("t"));
+        for order in orders {
+            order
+                .add_to_statement(&mut select, &TestSqlBuilder)
+                .unwrap();
+        }
+        select.to_string(query_builder)
+    }
+
+    fn test_orders() -> Vec<OrderBy> {
+        vec![
+            OrderBy::desc(Expr::field("age")).nulls_last(),
+            OrderBy::asc(Expr::field("name")).nulls_first(),
+            OrderBy::asc(Expr::field("id")),
+        ]
+    }
+
+    #[test]
+    fn order_by_asc() {
+        let order = OrderBy::asc(Expr::field("name"));
+
+        assert_eq!(order.expr(), &Expr::field("name"));
+        assert_eq!(order.direction(), OrderDirection::Asc);
+        assert_eq!(order.nulls(), None);
+    }
+
+    #[test]
+    fn order_by_desc() {
+        let order = OrderBy::desc(Expr::field("name"));
+
+        assert_eq!(order.direction(), OrderDirection::Desc);
+        assert_eq!(order.nulls(), None);
+    }
+
+    #[test]
+    fn order_by_nulls() {
+        let order = OrderBy::desc(Expr::field("name")).nulls_first();
+        assert_eq!(order.nulls(), Some(NullsOrder::First));
+
+        let order = order.nulls_last();
+        assert_eq!(order.direction(), OrderDirection::Desc);
+        assert_eq!(order.nulls(), Some(NullsOrder::Last));
+    }
+
+    #[test]
+    fn order_by_from_field_ref() {
+        let field = FieldRef::<i32>::new(crate::db::Identifier::new("id"));
+
+        assert_eq!(OrderBy::from(field), OrderBy::asc(Expr::field("id")));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn render_sqlite() {
+        assert_eq!(
+            render(&test_orders(), sea_query::SqliteQueryBuilder),
+            r#"SELECT * FROM "t" ORDER BY "age" DESC NULLS LAST, "name" ASC NULLS FIRST, "id" ASC"#
+        );
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn render_postgres() {
+        assert_eq!(
+            render(&test_orders(), sea_query::PostgresQueryBuilder),
+            r#"SELECT * FROM "t" ORDER BY "age" DESC NULLS LAST, "name" ASC NULLS FIRST, "id" ASC"#
+        );
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn render_mysql() {
+        assert_eq!(
+            render(&test_orders(), sea_query::MysqlQueryBuilder),
+            "SELECT * FROM `t` ORDER BY `age` IS NULL ASC, `age` DESC, \
+             `name` IS NULL DESC, `name` ASC, `id` ASC"
+        );
+    }
+
+    #[test]
+    fn add_to_statement_propagates_errors() {
+        let order = OrderBy::asc(Expr::contains(Expr::field("name"), Expr::value("foo")));
+        let mut select = sea_query::Query::select();
+
+        let result = order.add_to_statement(&mut select, &TestSqlBuilder);
+
+        assert!(matches!(
+            result,
+            Err(QueryBuildingError::UnsupportedExpr(_))
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+//! Relations between models that can be followed in queries.
+
+use sea_query::{Alias, ExprTrait, IntoColumnRef, IntoIden};
+
+use crate::db::{Column, CompositeForeignKeyField, ForeignKeyField, Identifier, Model};
+
+/// A foreign key relation that is followed when querying a model.
+///
+/// A relation is created from one of the model's foreign key fields, and
+/// causes the referenced model's table to be joined to the query. This makes it
+/// possible to fetch the referenced model instances together with the queried
+/// model (see [`Query::select_related`]) and to filter the queried model by the
+/// fields of the referenced model (see [`FieldRef::related`]).
+///
+/// The columns of the joined table are exposed under the name of the foreign
+/// key column followed by `__` and the name of the referenced column (e.g.
+/// `author__username`), so that they never clash with the columns of the
+/// queried model.
+///
+/// Foreign keys referencing a model with a composite primary key are stored in
+/// multiple columns; such relations are named after the foreign key field
+/// instead, and join the referenced table on all of the columns.
+///
+/// [`Query::select_related`]: crate::db::query::Query::select_related
+/// [`FieldRef::related`]: crate::db::query::expr::FieldRef::related
+///
+/// # Example
+///
+/// ```
+/// use cot::db::query::relation::Relation;
+/// use cot::db::{Auto, ForeignKey, Model, model};
+///
+/// #[model]
+/// struct User {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     username: String,
+/// }
+///
+/// #[model]
+/// struct Post {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     author: ForeignKey<User>,
+/// }
+///
+/// let relation = <Post as Model>::Fields::author.relation();
+///
+/// assert_eq!(relation.column().as_str(), "author");
+/// assert_eq!(relation.table(), <User as Model>::TABLE_NAME);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Relation {
+    column: Identifier,
+    /// The columns the foreign key is stored in, if there is more than one.
+    foreign_key_columns: &'static [Identifier],
+    table: Identifier,
+    primary_key: Identifier,
+    primary_key_names: &'static [Identifier],
+    columns: &'static [Column],
+}
+
+impl Relation {
+    /// Creates a new relation following the foreign key stored in `column` to
+    /// the model referenced by the field type `F`.
+    #[must_use]
+    pub fn new<F: ForeignKeyField>(column: Identifier) -> Self {
+        Self::with_foreign_key_columns::<F>(column, &[])
+    }
+
+    /// Creates a new relation following the foreign key field `name` stored in
+    /// `foreign_key_columns` to the model with a composite primary key
+    /// referenced by the field type `F`.
+    ///
+    /// The columns must be listed in the order of the primary key fields of
+    /// the referenced model.
+    #[must_use]
+    pub fn new_composite<F: CompositeForeignKeyField>(
+        name: Identifier,
+        foreign_key_columns: &'static [Identifier],
+    ) -> Self {
+        Self::with_foreign_key_columns::<F>(name, foreign_key_columns)
+    }
+
+    pub(crate) fn with_foreign_key_columns<F: ForeignKeyField>(
+        column: Identifier,
+        foreign_key_columns: &'static [Identifier],
+    ) -> Self {
+        Self {
+            column,
+            foreign_key_columns,
+            table: <F::Model as Model>::TABLE_NAME,
+            primary_key: <F::Model as Model>::PRIMARY_KEY_NAME,
+            primary_key_names: <F::Model as Model>::PRIMARY_KEY_NAMES,
+            columns: <F::Model as Model>::COLUMNS,
+        }
+    }
+
+    /// Returns the name of the foreign key column in the queried model.
+    ///
+    /// For foreign keys stored in multiple columns, this is the name of the
+    /// foreign key field.
+    #[must_use]
+    pub fn column(&self) -> Identifier {
+        self.column
+    }
+
+    /// Returns the name of the table of the referenced model.
+    #[must_use]
+    pub fn table(&self) -> Identifier {
+        self.table
+    }
+
+    /// Returns the name of the primary key column of the referenced model.
+    #[must_use]
+    pub fn primary_key(&self) -> Identifier {
+        self.primary_key
+    }
+
+    /// Returns the number of columns of the referenced model.
+    pub(crate) fn column_count(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the reference to a column of the referenced model, as exposed
+    /// by the join.
+    pub(crate) fn column_ref(&self, column: Identifier) -> sea_query::ColumnRef {
+        (self.alias(), self.column_alias(column)).into_column_ref()
+    }
+
+    /// Returns the references to all the columns of the referenced model, in
+    /// the order in which [`Model::from_db_at`] expects them.
+    pub(crate) fn column_refs(&self) -> impl Iterator<Item = sea_query::ColumnRef> + '_ {
+        self.columns
+            .iter()
+            .map(|column| self.column_ref(column.name))
+    }
+
+    /// Adds a `LEFT JOIN` of the referenced model's table to the statement.
+    ///
+    /// The table is joined as a derived table whose columns are prefixed with
+    /// the foreign key column name, so that unqualified column references in
+    /// the rest of the statement still refer to the queried model.
+    pub(crate) fn add_join_to_statement(
+        &self,
+        statement: &mut sea_query::SelectStatement,
+        base_table: Identifier,
+    ) {
+        let mut subquery = sea_query::Query::select();
+        subquery.from(self.table);
+        for column in self.columns {
+            subquery.expr_as(
+                sea_query::Expr::col(column.name),
+                self.column_alias(column.name),
+            );
+        }
+
+        let foreign_key_columns = if self.foreign_key_columns.is_empty() {
+            std::slice::from_ref(&self.column)
+        } else {
+            self.foreign_key_columns
+        };
+        let condition = foreign_key_columns
+            .iter()
+            .zip(self.primary_key_names)
+            .map(|(&column, &primary_key)| {
+                sea_query::Expr::col((base_table, column)).equals(self.column_ref(primary_key))
+            })
+            .reduce(ExprTrait::and)
+            .expect("models have at least one primary key column");
+
+        statement.join_subquery(
+            sea_query::JoinType::LeftJoin,
+            subquery,
+            self.alias(),
+            condition,
+        );
+    }
+
+    fn alias(&self) -> sea_query::DynIden {
+        Alias::new(self.column.as_str()).into_iden()
+    }
+
+    fn column_alias(&self, column: Identifier) -> sea_query::DynIden {
+        Alias::new(format!("{}__{}", self.column, column)).into_iden()
+    }
+}
+
+/// Adds the given relation to the list, unless a relation through the same
+/// foreign key column is already there.
+pub(crate) fn push_unique(relations: &mut Vec<Relation>, relation: Relation) {
+    if !relations.iter().any(|r| r.column == relation.column) {
+        relations.push(relation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{Auto, ForeignKey, model};
+
+    #[model]
+    struct Author {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        username: String,
+    }
+
+    fn relation() -> Relation {
+        Relation::new::<ForeignKey<Author>>(Identifier::new("author"))
+    }
+
+    #[test]
+    fn relation_new() {
+        let relation = relation();
+
+        assert_eq!(relation.column(), Identifier::new("author"));
+        assert_eq!(relation.table(), <Author as Model>::TABLE_NAME);
+        assert_eq!(relation.primary_key(), Identifier::new("id"));
+        assert_eq!(relation.column_count(), 2);
+    }
+
+    #[test]
+    fn push_unique_skips_duplicates() {
+        let mut relations = Vec::new();
+
+        push_unique(&mut relations, relation());
+        push_unique(&mut relations, relation());
+
+        assert_eq!(relations, vec![relation()]);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn add_join_to_statement() {
+        let relation = relation();
+        let mut select = sea_query::Query::select();
+        select
+            .column(Identifier::new("id"))
+            .from(Identifier::new("post"));
+        select.columns(relation.column_refs());
+
+        relation.add_join_to_statement(&mut select, Identifier::new("post"));
+
+        assert_eq!(
+            select.to_string(sea_query::SqliteQueryBuilder),
+            r#"SELECT "id", "author"."author__id", "author"."author__username" FROM "post" LEFT JOIN (SELECT "id" AS "author__id", "username" AS "author__username" FROM "cot__author") AS "author" ON "post"."author" = "author"."author__id""#
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn add_composite_join_to_statement() {
+        #[model]
+        struct Edition {
+            #[model(primary_key)]
+            isbn: String,
+            #[model(primary_key)]
+            number: i32,
+        }
+
+        const COLUMNS: &[Identifier] = &[
+            Identifier::new("edition_isbn"),
+            Identifier::new("edition_number"),
+        ];
+
+        let relation =
+            Relation::new_composite::<ForeignKey<Edition>>(Identifier::new("edition"), COLUMNS);
+        let mut select = sea_query::Query::select();
+        select
+            .column(Identifier::new("id"))
+            .from(Identifier::new("copy"));
+
+        relation.add_join_to_statement(&mut select, Identifier::new("copy"));
+
+        assert_eq!(
+            select.to_string(sea_query::SqliteQueryBuilder),
+            r#"SELECT "id" FROM "copy" LEFT JOIN (SELECT "isbn" AS "edition__isbn", "number" AS "edition__number" FROM "cot__edition") AS "edition" ON "copy"."edition_isbn" = "edition"."edition__isbn" AND "copy"."edition_number" = "edition"."edition__number""#
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! Helpers for the unit tests of the query building code.
+
+use sea_query::SimpleExpr;
+
+use crate::db::ColumnType;
+use crate::db::query::QueryBuildingError;
+use crate::db::query::expr::array::{ArrayExprBuilder, ArrayOperator};
+use crate::db::query::expr::cast::CastExprBuilder;
+use crate::db::query::expr::json::JsonExprBuilder;
+use crate::db::query::expr::like::{CaseSensitivity, LikeExprBuilder};
+use crate::db::query::expr::search::SearchExprBuilder;
+
+/// A SQL query builder for the unit tests that doesn't support any of the
+/// database-specific expressions.
+pub(crate) struct TestSqlBuilder;
+
+impl LikeExprBuilder for TestSqlBuilder {
+    fn like_expr(
+        &self,
+        _lhs: SimpleExpr,
+        _glob_pattern: &str,
+        _case_sensitivity: CaseSensitivity,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        Err(QueryBuildingError::UnsupportedExpr("LIKE".to_owned()))
+    }
+}
+
+impl CastExprBuilder for TestSqlBuilder {
+    fn cast_expr(
+        &self,
+        _expr: SimpleExpr,
+        _column_type: ColumnType,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        Err(QueryBuildingError::UnsupportedExpr("CAST".to_owned()))
+    }
+}
+
+impl JsonExprBuilder for TestSqlBuilder {
+    fn json_extract_expr(
+        &self,
+        _expr: SimpleExpr,
+        _path: &[String],
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        Err(QueryBuildingError::UnsupportedExpr("JSON".to_owned()))
+    }
+}
+
+impl ArrayExprBuilder for TestSqlBuilder {
+    fn array_expr(
+        &self,
+        _lhs: SimpleExpr,
+        _rhs: SimpleExpr,
+        _operator: ArrayOperator,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        Err(QueryBuildingError::UnsupportedExpr("ARRAY".to_owned()))
+    }
+}
+
+impl SearchExprBuilder for TestSqlBuilder {
+    fn search_expr(
+        &self,
+        _document: SimpleExpr,
+        _query: SimpleExpr,
+        _config: &str,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        Err(QueryBuildingError::UnsupportedExpr("SEARCH".to_owned()))
+    }
+
+    fn search_rank_expr(
+        &self,
+        _document: SimpleExpr,
+        _query: SimpleExpr,
+        _config: &str,
+    ) -> Result<SimpleExpr, QueryBuildingError> {
+        Err(QueryBuildingError::UnsupportedExpr("SEARCH".to_owned()))
+    }
+}
@@ -0,0 +1,178 @@
+//! Assignments used to update rows in bulk.
+
+use crate::db::Identifier;
+use crate::db::query::QueryBuildingError;
+use crate::db::query::expr::{Expr, SqlQueryBuilder};
+use crate::db::query::relation::Relation;
+
+/// A single `column = value` assignment used by [`Query::update`].
+///
+/// Assignments are typically created by calling [`FieldRef::set`] or
+/// [`FieldRef::set_expr`] on one of the model's fields. The assigned value can
+/// be any expression, including one that references the current values of the
+/// row's columns, which makes it possible to, e.g., atomically increment a
+/// counter.
+///
+/// [`Query::update`]: crate::db::query::Query::update
+/// [`FieldRef::set`]: crate::db::query::expr::FieldRef::set
+/// [`FieldRef::set_expr`]: crate::db::query::expr::FieldRef::set_expr
+///
+/// # Example
+///
+/// ```
+/// use cot::db::query::expr::{Expr, ExprAdd};
+/// use cot::db::query::update::Assignment;
+/// use cot::db::{Model, model};
+///
+/// #[model]
+/// struct Post {
+///     #[model(primary_key)]
+///     id: i32,
+///     views: i64,
+/// }
+///
+/// let assignment = <Post as Model>::Fields::views.set_expr(<Post as Model>::Fields::views.add(1));
+///
+/// assert_eq!(
+///     assignment,
+///     Assignment::new(
+///         "views",
+///         Expr::add(Expr::field("views"), Expr::value(1_i64))
+///     )
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    column: Identifier,
+    relation: Option<Relation>,
+    value: Expr,
+}
+
+impl Assignment {
+    /// Creates an assignment that sets the given column of the updated model
+    /// to the value of the expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cot::db::Identifier;
+    /// use cot::db::query::expr::Expr;
+    /// use cot::db::query::update::Assignment;
+    ///
+    /// let assignment = Assignment::new("status", Expr::value("archived"));
+    ///
+    /// assert_eq!(assignment.column(), Identifier::new("status"));
+    /// assert_eq!(assignment.value(), &Expr::value("archived"));
+    /// ```
+    #[must_use]
+    pub fn new<I: Into<Identifier>>(column: I, value: Expr) -> Self {
+        Self {
+            column: column.into(),
+            relation: None,
+            value,
+        }
+    }
+
+    pub(crate) fn with_relation(
+        column: Identifier,
+        relation: Option<Relation>,
+        value: Expr,
+    ) -> Self {
+        Self {
+            column,
+            relation,
+            value,
+        }
+    }
+
+    /// Returns the name of the assigned column.
+    #[must_use]
+    pub fn column(&self) -> Identifier {
+        self.column
+    }
+
+    /// Returns the expression whose value is assigned to the column.
+    #[must_use]
+    pub fn value(&self) -> &Expr {
+        &self.value
+    }
+
+    /// Returns the column and the `sea_query` expression of its new value.
+    ///
+    /// Columns of related models can't be updated, and the new value can't
+    /// reference them, as `UPDATE` statements can't join other tables in a
+    /// portable way.
+    pub(crate) fn as_sea_query_value(
+        &self,
+        sql_builder: &dyn SqlQueryBuilder,
+    ) -> Result<(Identifier, sea_query::SimpleExpr), QueryBuildingError> {
+        let mut relations = Vec::new();
+        self.value.collect_relations(&mut relations);
+        if self.relation.is_some() || !relations.is_empty() {
+            return Err(QueryBuildingError::UnsupportedExpr(format!(
+                "assignment to `{}` references a related model's field, which is not \
+                supported in updates",
+                self.column
+            )));
+        }
+
+        Ok((self.column, self.value.as_sea_query_expr(sql_builder)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::query::expr::FieldRef;
+    use crate::db::query::test_utils::TestSqlBuilder;
+    use crate::db::{Auto, ForeignKey, model};
+
+    #[model]
+    struct Author {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        username: String,
+    }
+
+    #[test]
+    fn assignment_from_field_ref() {
+        let field = FieldRef::<i32>::new(Identifier::new("views"));
+
+        assert_eq!(field.set(5), Assignment::new("views", Expr::value(5)));
+        assert_eq!(
+            field.set_expr(Expr::add(field.as_expr(), Expr::value(1))),
+            Assignment::new("views", Expr::add(Expr::field("views"), Expr::value(1)))
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn as_sea_query_value() {
+        let assignment = Assignment::new("views", Expr::add(Expr::field("views"), Expr::value(1)));
+
+        let (column, value) = assignment.as_sea_query_value(&TestSqlBuilder).unwrap();
+        let mut update = sea_query::Query::update();
+        update.table(Identifier::new("post")).value(column, value);
+
+        assert_eq!(
+            update.to_string(sea_query::SqliteQueryBuilder),
+            r#"UPDATE "post" SET "views" = "views" + 1"#
+        );
+    }
+
+    #[test]
+    fn as_sea_query_value_related_field() {
+        let author = FieldRef::<ForeignKey<Author>>::new(Identifier::new("author"));
+        let username = author.related(FieldRef::<String>::new(Identifier::new("username")));
+
+        let set_related = username.set("alice".to_owned());
+        let from_related = Assignment::new("title", username.as_expr());
+
+        for assignment in [set_related, from_related] {
+            assert!(matches!(
+                assignment.as_sea_query_value(&TestSqlBuilder),
+                Err(QueryBuildingError::UnsupportedExpr(_))
+            ));
+        }
+    }
+}
@@ -1,4 +1,13 @@
-use crate::db::{DatabaseBackend, DatabaseError, Model, Result};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+use crate::db::query::order::OrderBy;
+use crate::db::query::{Expr, Query};
+use crate::db::{
+    CompositePrimaryKey, DatabaseBackend, DatabaseError, DbFieldValue, DbValue, Identifier, Model,
+    ModelPrimaryKey, Result, Row, SinglePrimaryKey, ToDbFieldValue,
+};
 
 /// A foreign key to another model.
 ///
@@ -50,9 +59,13 @@ pub enum ForeignKey<T: Model> {
 
 impl<T: Model> ForeignKey<T> {
     /// Returns the primary key of the referenced model.
-    pub fn primary_key(&self) -> &T::PrimaryKey {
+    ///
+    /// Just like [`Model::primary_key`], this is a tuple of references to the
+    /// primary key fields if the referenced model has a composite primary
+    /// key.
+    pub fn primary_key(&self) -> <T::PrimaryKey as ModelPrimaryKey>::Borrowed<'_> {
         match self {
-            Self::PrimaryKey(pk) => pk,
+            Self::PrimaryKey(pk) => pk.borrow_key(),
             Self::Model(model) => model.primary_key(),
         }
     }
@@ -114,7 +127,8 @@ where
     T::PrimaryKey: PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.primary_key() == other.primary_key()
+        T::PrimaryKey::from_borrowed(self.primary_key())
+            == T::PrimaryKey::from_borrowed(other.primary_key())
     }
 }
 
@@ -128,7 +142,590 @@ impl<T: Model> From<T> for ForeignKey<T> {
 
 impl<T: Model> From<&T> for ForeignKey<T> {
     fn from(model: &T) -> Self {
-        Self::PrimaryKey(model.primary_key().clone())
+        Self::PrimaryKey(T::PrimaryKey::from_borrowed(model.primary_key()))
+    }
+}
+
+/// A trait for the types of model fields that reference another model.
+///
+/// This is implemented for [`ForeignKey`] and [`Option<ForeignKey>`], and is
+/// used to join the referenced model's table when querying (see
+/// [`Query::select_related`](crate::db::query::Query::select_related) and
+/// [`FieldRef::related`](crate::db::query::expr::FieldRef::related)).
+pub trait ForeignKeyField {
+    /// The referenced model.
+    type Model: Model;
+
+    /// Replaces the stored primary key with the referenced model instance, read
+    /// from a database row starting at the given index.
+    ///
+    /// If the field doesn't reference any model (i.e. it is [`None`]), the
+    /// row is not read.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the data in the row is not compatible
+    /// with the referenced model.
+    fn fill_from_db(&mut self, db_row: &Row, offset: usize) -> Result<()>;
+}
+
+impl<T: Model + Send + Sync> ForeignKeyField for ForeignKey<T> {
+    type Model = T;
+
+    fn fill_from_db(&mut self, db_row: &Row, offset: usize) -> Result<()> {
+        *self = Self::Model(Box::new(T::from_db_at(db_row, offset)?));
+        Ok(())
+    }
+}
+
+impl<T: Model + Send + Sync> ForeignKeyField for Option<ForeignKey<T>>
+where
+    T::PrimaryKey: SinglePrimaryKey,
+{
+    type Model = T;
+
+    fn fill_from_db(&mut self, db_row: &Row, offset: usize) -> Result<()> {
+        match self {
+            Some(foreign_key) => foreign_key.fill_from_db(db_row, offset),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A trait for the [`ForeignKey`] fields referencing models with a composite
+/// primary key.
+///
+/// Such fields are stored in multiple columns, one for each of the primary
+/// key fields of the referenced model. This is used by the code generated by
+/// the [`model`](crate::db::model) macro to read and write these columns.
+pub trait CompositeForeignKeyField: ForeignKeyField + Sized {
+    /// The number of columns the field is stored in.
+    const COLUMN_COUNT: usize;
+
+    /// Reads the field from a database row, starting at the given column
+    /// index.
+    ///
+    /// # Errors
+    ///
+    /// This method can return an error if the data in the row is not compatible
+    /// with the primary key of the referenced model.
+    fn from_db_at(db_row: &Row, offset: usize) -> Result<Self>;
+
+    /// Returns the value of the column with the given index, counting from the
+    /// first column of the field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not smaller than [`Self::COLUMN_COUNT`].
+    fn column_value(&self, index: usize) -> &dyn ToDbFieldValue;
+}
+
+impl<T: Model + Send + Sync> CompositeForeignKeyField for ForeignKey<T>
+where
+    T::PrimaryKey: CompositePrimaryKey,
+{
+    const COLUMN_COUNT: usize = T::PrimaryKey::COLUMN_COUNT;
+
+    fn from_db_at(db_row: &Row, offset: usize) -> Result<Self> {
+        T::PrimaryKey::from_db_at(db_row, offset).map(Self::PrimaryKey)
+    }
+
+    fn column_value(&self, index: usize) -> &dyn ToDbFieldValue {
+        T::PrimaryKey::column_value(self.primary_key(), index)
+    }
+}
+
+/// The reverse side of a foreign key: the instances of model `T` that
+/// reference a given model instance.
+///
+/// Reverse relations are typically not created manually. Instead, the
+/// [`model`](crate::db::model) macro generates reverse accessors for every
+/// foreign key field annotated with `#[model(related_name = "...")]`: a method
+/// returning a [`Query`] for the instances referencing a model instance, and
+/// an associated function that fetches them for multiple instances at once.
+///
+/// # Examples
+///
+/// ```
+/// use cot::db::{Auto, Database, ForeignKey, Model, model};
+///
+/// #[model]
+/// struct User {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     name: String,
+/// }
+///
+/// #[model]
+/// struct Post {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     #[model(related_name = "posts")]
+///     author: ForeignKey<User>,
+///     title: String,
+/// }
+///
+/// async fn posts(db: &Database) -> cot::db::Result<()> {
+///     let users = User::objects().all(db).await?;
+///
+///     // one query per user
+///     for user in &users {
+///         let posts = user.posts().all(db).await?;
+///         println!("{}: {} posts", user.name, posts.len());
+///     }
+///
+///     // a single query for all the users
+///     let posts = User::prefetch_posts(db, &users).await?;
+///     for (user, posts) in users.iter().zip(posts) {
+///         println!("{}: {} posts", user.name, posts.len());
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub struct ReverseRelation<T: Model> {
+    column: Identifier,
+    key: fn(&T) -> DbFieldValue,
+}
+
+impl<T: Model> ReverseRelation<T> {
+    /// Creates a new reverse relation through the foreign key stored in
+    /// `column` of model `T`. The `key` function returns the value of that
+    /// foreign key for a given instance of `T`.
+    #[must_use]
+    pub const fn new(column: Identifier, key: fn(&T) -> DbFieldValue) -> Self {
+        Self { column, key }
+    }
+
+    /// Returns the name of the foreign key column in model `T`.
+    #[must_use]
+    pub fn column(&self) -> Identifier {
+        self.column
+    }
+
+    /// Returns a query for the instances of `T` that reference the given
+    /// model instance.
+    ///
+    /// If the instance hasn't been saved to the database yet, nothing can
+    /// reference it, so the query doesn't return any results. The query can
+    /// be narrowed down further with [`Query::and_filter`]; note that
+    /// [`Query::filter`] would replace the filter on the relation.
+    #[must_use]
+    pub fn query<S>(&self, instance: &S) -> Query<T>
+    where
+        S: Model,
+        S::PrimaryKey: SinglePrimaryKey,
+    {
+        let filter = match instance.primary_key().to_db_field_value() {
+            DbFieldValue::Value(primary_key) => {
+                Expr::eq(Expr::field(self.column), Expr::Value(primary_key))
+            }
+            DbFieldValue::Auto => Expr::value(false),
+        };
+
+        let mut query = Query::new();
+        query.filter(filter);
+        query
+    }
+
+    /// Fetches the instances of `T` that reference any of the given model
+    /// instances using a single database query.
+    ///
+    /// The returned vector has the same length as `instances`; its element at
+    /// index `i` contains the instances of `T` referencing `instances[i]`,
+    /// ordered by the primary key. If the same model instance is passed
+    /// multiple times, the referencing instances are only returned for its
+    /// first occurrence. Instances that haven't been saved to the database yet
+    /// are never referenced by anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there was a problem communicating with the database.
+    pub async fn prefetch<S, DB>(&self, mut db: DB, instances: &[S]) -> Result<Vec<Vec<T>>>
+    where
+        S: Model + Sync,
+        S::PrimaryKey: SinglePrimaryKey,
+        DB: DatabaseBackend,
+    {
+        let primary_keys: Vec<_> = instances
+            .iter()
+            .map(
+                |instance| match instance.primary_key().to_db_field_value() {
+                    DbFieldValue::Value(primary_key) => Some(primary_key),
+                    DbFieldValue::Auto => None,
+                },
+            )
+            .collect();
+
+        let mut related: Vec<Vec<T>> = Vec::new();
+        related.resize_with(instances.len(), Vec::new);
+
+        let values: Vec<_> = primary_keys
+            .iter()
+            .flatten()
+            .map(|primary_key| Expr::Value(primary_key.clone()))
+            .collect();
+        if values.is_empty() {
+            return Ok(related);
+        }
+
+        let mut instance_indices = HashMap::with_capacity(values.len());
+        for (index, primary_key) in primary_keys.iter().enumerate() {
+            if let Some(key) = primary_key.as_ref().and_then(KeyValue::new) {
+                instance_indices.entry(key).or_insert(index);
+            }
+        }
+
+        let mut query = Query::new();
+        query.filter(Expr::is_in(Expr::field(self.column), values));
+        for primary_key_name in T::PRIMARY_KEY_NAMES {
+            query.order_by(OrderBy::asc(Expr::field(*primary_key_name)));
+        }
+        for model in db.query(&query).await? {
+            let DbFieldValue::Value(key) = (self.key)(&model) else {
+                continue;
+            };
+            if let Some(&index) = KeyValue::new(&key).and_then(|key| instance_indices.get(&key)) {
+                related[index].push(model);
+            }
+        }
+
+        Ok(related)
+    }
+}
+
+/// A value of a key column that can be used to match the rows of related
+/// models.
+///
+/// [`DbValue`] doesn't implement [`Hash`], and the same key can be represented
+/// by different variants of it (for instance, when a foreign key is read as
+/// `i64` and the primary key it references as `i32`), so the values are
+/// normalized first: all the integers are widened to `i128`, and the
+/// date-times with a time zone are converted to UTC.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum KeyValue {
+    Bool(bool),
+    Integer(i128),
+    String(String),
+    Bytes(Vec<u8>),
+    Date(chrono::NaiveDate),
+    Time(chrono::NaiveTime),
+    DateTime(chrono::NaiveDateTime),
+    DateTimeUtc(chrono::DateTime<chrono::Utc>),
+    #[cfg(feature = "uuid")]
+    Uuid(uuid::Uuid),
+    #[cfg(feature = "decimal")]
+    Decimal(rust_decimal::Decimal),
+}
+
+impl KeyValue {
+    /// Normalizes the given value. Returns [`None`] for `NULL`, which doesn't
+    /// match anything, and for the types that can't be used as keys, such as
+    /// floating-point numbers.
+    fn new(value: &DbValue) -> Option<Self> {
+        match value {
+            DbValue::Bool(value) => value.map(Self::Bool),
+            DbValue::TinyInt(value) => value.map(|value| Self::Integer(value.into())),
+            DbValue::SmallInt(value) => value.map(|value| Self::Integer(value.into())),
+            DbValue::Int(value) => value.map(|value| Self::Integer(value.into())),
+            DbValue::BigInt(value) => value.map(|value| Self::Integer(value.into())),
+            DbValue::TinyUnsigned(value) => value.map(|value| Self::Integer(value.into())),
+            DbValue::SmallUnsigned(value) => value.map(|value| Self::Integer(value.into())),
+            DbValue::Unsigned(value) => value.map(|value| Self::Integer(value.into())),
+            DbValue::BigUnsigned(value) => value.map(|value| Self::Integer(value.into())),
+            DbValue::String(value) => value.clone().map(Self::String),
+            DbValue::Char(value) => value.map(|value| Self::String(value.to_string())),
+            DbValue::Bytes(value) => value.clone().map(Self::Bytes),
+            DbValue::ChronoDate(value) => value.map(Self::Date),
+            DbValue::ChronoTime(value) => value.map(Self::Time),
+            DbValue::ChronoDateTime(value) => value.map(Self::DateTime),
+            DbValue::ChronoDateTimeUtc(value) => value.map(Self::DateTimeUtc),
+            DbValue::ChronoDateTimeLocal(value) => {
+                value.map(|value| Self::DateTimeUtc(value.to_utc()))
+            }
+            DbValue::ChronoDateTimeWithTimeZone(value) => {
+                value.map(|value| Self::DateTimeUtc(value.to_utc()))
+            }
+            #[cfg(feature = "uuid")]
+            DbValue::Uuid(value) => value.map(Self::Uuid),
+            #[cfg(feature = "decimal")]
+            DbValue::Decimal(value) => value.map(Self::Decimal),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Model> Debug for ReverseRelation<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReverseRelation")
+            .field("column", &self.column)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Model> Clone for ReverseRelation<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Model> Copy for ReverseRelation<T> {}
+
+/// A many-to-many relation with another model.
+///
+/// Unlike [`ForeignKey`], a many-to-many field doesn't store anything in the
+/// model's own table. Instead, the links between the model instances are
+/// stored in a separate join table, which is created by the migration
+/// generator for every `ManyToMany<T>` field. The join table is named after
+/// the model's table and the field (e.g. `myapp__post_tags`) and contains
+/// foreign keys to both linked models, with a unique constraint on the pair of
+/// them, so that the same instances can't be linked more than once.
+///
+/// The field is bound to the model instance that contains it when the
+/// instance is read from or saved to the database. Until then, all the
+/// methods that access the database return [`DatabaseError::UnsavedModel`].
+///
+/// # Examples
+///
+/// ```
+/// use cot::db::{Auto, Database, ManyToMany, Model, model};
+///
+/// #[model]
+/// struct Tag {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     name: String,
+/// }
+///
+/// #[model]
+/// struct Post {
+///     #[model(primary_key)]
+///     id: Auto<i32>,
+///     title: String,
+///     tags: ManyToMany<Tag>,
+/// }
+///
+/// async fn tag_post(db: &Database) -> cot::db::Result<()> {
+///     let mut tag = Tag {
+///         id: Auto::auto(),
+///         name: "rust".to_owned(),
+///     };
+///     tag.save(db).await?;
+///
+///     let mut post = Post {
+///         id: Auto::auto(),
+///         title: "Hello, world!".to_owned(),
+///         tags: ManyToMany::new(),
+///     };
+///     post.save(db).await?;
+///
+///     post.tags.add(db, &tag).await?;
+///     let tags = post.tags.all(db).await?;
+///     assert_eq!(tags[0].name, "rust");
+///
+///     Ok(())
+/// }
+/// ```
+pub struct ManyToMany<T: Model> {
+    source: Option<(ManyToManyTable, DbValue)>,
+    phantom_data: PhantomData<fn() -> T>,
+}
+
+impl<T: Model> ManyToMany<T> {
+    /// Creates a new many-to-many field that isn't bound to any model
+    /// instance yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            source: None,
+            phantom_data: PhantomData,
+        }
+    }
+
+    /// Binds the field to the model instance with the given primary key.
+    ///
+    /// This is called by the code generated by the [`model`](crate::db::model)
+    /// macro, so there is typically no need to call it manually. If the
+    /// primary key is [`Auto`](crate::db::Auto), the field is unbound.
+    pub fn bind(&mut self, table: ManyToManyTable, source_pk: &dyn ToDbFieldValue) {
+        self.source = match source_pk.to_db_field_value() {
+            DbFieldValue::Value(value) => Some((table, value)),
+            DbFieldValue::Auto => None,
+        };
+    }
+
+    /// Returns the join table this field is bound to, or [`None`] if the field
+    /// hasn't been bound to a model instance yet.
+    #[must_use]
+    pub fn table(&self) -> Option<ManyToManyTable> {
+        self.source.as_ref().map(|(table, _)| *table)
+    }
+
+    /// Returns all the model instances linked to the model containing this
+    /// field, ordered by their primary keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::UnsavedModel`] if the model containing this
+    /// field hasn't been saved to the database.
+    ///
+    /// Returns an error if there was a problem communicating with the database.
+    pub async fn all<DB: DatabaseBackend>(&self, mut db: DB) -> Result<Vec<T>>
+    where
+        T::PrimaryKey: SinglePrimaryKey,
+    {
+        let (table, source_pk) = self.source()?;
+        db.query_many_to_many(table, source_pk).await
+    }
+
+    /// Links the given model instance to the model containing this field. If
+    /// the instances are already linked, this does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::UnsavedModel`] if any of the model instances
+    /// hasn't been saved to the database.
+    ///
+    /// Returns an error if there was a problem communicating with the database.
+    pub async fn add<DB: DatabaseBackend>(&self, mut db: DB, model: &T) -> Result<()>
+    where
+        T: Sync,
+        T::PrimaryKey: SinglePrimaryKey,
+    {
+        let (table, source_pk) = self.source()?;
+        let target_pk = Self::target_pk(model)?;
+        db.add_many_to_many(table, source_pk, &target_pk).await
+    }
+
+    /// Removes the link between the given model instance and the model
+    /// containing this field. The model instance itself is not deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::UnsavedModel`] if any of the model instances
+    /// hasn't been saved to the database.
+    ///
+    /// Returns an error if there was a problem communicating with the database.
+    pub async fn remove<DB: DatabaseBackend>(&self, mut db: DB, model: &T) -> Result<()>
+    where
+        T: Sync,
+        T::PrimaryKey: SinglePrimaryKey,
+    {
+        let (table, source_pk) = self.source()?;
+        let target_pk = Self::target_pk(model)?;
+        db.remove_many_to_many(table, source_pk, &target_pk).await?;
+        Ok(())
+    }
+
+    /// Removes all the links of the model containing this field. The linked
+    /// model instances themselves are not deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DatabaseError::UnsavedModel`] if the model containing this
+    /// field hasn't been saved to the database.
+    ///
+    /// Returns an error if there was a problem communicating with the database.
+    pub async fn clear<DB: DatabaseBackend>(&self, mut db: DB) -> Result<()> {
+        let (table, source_pk) = self.source()?;
+        db.clear_many_to_many(table, source_pk).await?;
+        Ok(())
+    }
+
+    fn source(&self) -> Result<(ManyToManyTable, &DbValue)> {
+        self.source
+            .as_ref()
+            .map(|(table, source_pk)| (*table, source_pk))
+            .ok_or(DatabaseError::UnsavedModel)
+    }
+
+    fn target_pk(model: &T) -> Result<DbValue>
+    where
+        T::PrimaryKey: SinglePrimaryKey,
+    {
+        match model.primary_key().to_db_field_value() {
+            DbFieldValue::Value(value) => Ok(value),
+            DbFieldValue::Auto => Err(DatabaseError::UnsavedModel),
+        }
+    }
+}
+
+impl<T: Model> Default for ManyToMany<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Model> Debug for ManyToMany<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManyToMany")
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl<T: Model> Clone for ManyToMany<T> {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source.clone(),
+            phantom_data: PhantomData,
+        }
+    }
+}
+
+/// Many-to-many fields are equal if they are bound to the same join table and
+/// model instance, or if neither of them is bound. The linked instances are
+/// only stored in the database, so they are not compared.
+impl<T: Model> PartialEq for ManyToMany<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+/// The join table backing a [`ManyToMany`] field.
+///
+/// Each row of the table links an instance of the model containing the field
+/// (stored in the source column) with an instance of the related model
+/// (stored in the target column).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ManyToManyTable {
+    name: Identifier,
+    source_column: Identifier,
+    target_column: Identifier,
+}
+
+impl ManyToManyTable {
+    /// Creates a new join table definition.
+    #[must_use]
+    pub const fn new(
+        name: Identifier,
+        source_column: Identifier,
+        target_column: Identifier,
+    ) -> Self {
+        Self {
+            name,
+            source_column,
+            target_column,
+        }
+    }
+
+    /// Returns the name of the join table.
+    #[must_use]
+    pub fn name(&self) -> Identifier {
+        self.name
+    }
+
+    /// Returns the name of the column referencing the model containing the
+    /// [`ManyToMany`] field.
+    #[must_use]
+    pub fn source_column(&self) -> Identifier {
+        self.source_column
+    }
+
+    /// Returns the name of the column referencing the related model.
+    #[must_use]
+    pub fn target_column(&self) -> Identifier {
+        self.target_column
     }
 }
 
@@ -200,7 +797,7 @@ impl From<ForeignKeyOnUpdatePolicy> for sea_query::ForeignKeyAction {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::{Auto, model};
+    use crate::db::{Auto, MockDatabaseBackend, model};
 
     #[derive(Debug, Clone, PartialEq)]
     #[model]
@@ -263,4 +860,183 @@ mod tests {
 
         assert_eq!(fk.primary_key(), &Auto::fixed(1));
     }
+
+    const TEST_TABLE: ManyToManyTable = ManyToManyTable::new(
+        Identifier::new("cot__owner_tests"),
+        Identifier::new("owner"),
+        Identifier::new("test_model"),
+    );
+
+    #[test]
+    fn many_to_many_bind() {
+        let mut m2m = ManyToMany::<TestModel>::new();
+        assert_eq!(m2m.table(), None);
+
+        m2m.bind(TEST_TABLE, &Auto::<i32>::auto());
+        assert_eq!(m2m.table(), None);
+
+        m2m.bind(TEST_TABLE, &Auto::fixed(1));
+        assert_eq!(m2m.table(), Some(TEST_TABLE));
+    }
+
+    #[test]
+    fn many_to_many_eq() {
+        let unbound = ManyToMany::<TestModel>::new();
+        let mut bound_1 = ManyToMany::<TestModel>::new();
+        bound_1.bind(TEST_TABLE, &Auto::fixed(1));
+        let mut bound_2 = ManyToMany::<TestModel>::new();
+        bound_2.bind(TEST_TABLE, &Auto::fixed(2));
+
+        assert_eq!(unbound, ManyToMany::new());
+        assert_eq!(bound_1, bound_1.clone());
+        assert_ne!(unbound, bound_1);
+        assert_ne!(bound_1, bound_2);
+    }
+
+    #[cot::test]
+    async fn many_to_many_unbound() {
+        let m2m = ManyToMany::<TestModel>::new();
+        let mut db = MockDatabaseBackend::new();
+
+        assert!(matches!(
+            m2m.all(&mut db).await,
+            Err(DatabaseError::UnsavedModel)
+        ));
+        assert!(matches!(
+            m2m.clear(&mut db).await,
+            Err(DatabaseError::UnsavedModel)
+        ));
+    }
+
+    #[cot::test]
+    async fn many_to_many_add_unsaved_model() {
+        let mut m2m = ManyToMany::<TestModel>::new();
+        m2m.bind(TEST_TABLE, &Auto::fixed(1));
+        let mut db = MockDatabaseBackend::new();
+
+        let result = m2m.add(&mut db, &TestModel { id: Auto::auto() }).await;
+
+        assert!(matches!(result, Err(DatabaseError::UnsavedModel)));
+    }
+
+    #[cot::test]
+    async fn many_to_many_add() {
+        let mut m2m = ManyToMany::<TestModel>::new();
+        m2m.bind(TEST_TABLE, &Auto::fixed(1));
+        let mut db = MockDatabaseBackend::new();
+        db.expect_add_many_to_many()
+            .withf(|table, source_pk, target_pk| {
+                *table == TEST_TABLE
+                    && *source_pk == DbValue::from(1)
+                    && *target_pk == DbValue::from(2)
+            })
+            .returning(|_, _, _| Ok(()));
+
+        m2m.add(&mut db, &TestModel { id: Auto::fixed(2) })
+            .await
+            .unwrap();
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct TestChildModel {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        #[model(related_name = "children")]
+        parent: ForeignKey<TestModel>,
+    }
+
+    #[test]
+    fn reverse_relation_query() {
+        let parent = TestModel { id: Auto::fixed(1) };
+
+        let mut expected = Query::<TestChildModel>::new();
+        expected.filter(Expr::eq(Expr::field("parent"), Expr::value(1)));
+        assert_eq!(parent.children(), expected);
+    }
+
+    #[test]
+    fn reverse_relation_query_unsaved() {
+        let parent = TestModel { id: Auto::auto() };
+
+        let mut expected = Query::<TestChildModel>::new();
+        expected.filter(Expr::value(false));
+        assert_eq!(parent.children(), expected);
+    }
+
+    #[cot::test]
+    async fn reverse_relation_prefetch() {
+        let parents = [
+            TestModel { id: Auto::fixed(1) },
+            TestModel { id: Auto::auto() },
+            TestModel { id: Auto::fixed(2) },
+            TestModel { id: Auto::fixed(1) },
+        ];
+        let child = |id, parent| TestChildModel {
+            id: Auto::fixed(id),
+            parent: ForeignKey::PrimaryKey(Auto::fixed(parent)),
+        };
+        let mut db = MockDatabaseBackend::new();
+        db.expect_query()
+            .returning(move |_| Ok(vec![child(1, 2), child(2, 1), child(3, 2)]));
+
+        let children = TestModel::prefetch_children(&mut db, &parents)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            children,
+            vec![
+                vec![child(2, 1)],
+                vec![],
+                vec![child(1, 2), child(3, 2)],
+                // only the first occurrence of a duplicated instance is filled
+                vec![],
+            ]
+        );
+    }
+
+    #[cot::test]
+    async fn reverse_relation_prefetch_different_key_types() {
+        #[derive(Debug, Clone, PartialEq)]
+        #[model]
+        struct TestWideChildModel {
+            #[model(primary_key)]
+            id: Auto<i32>,
+            // an `i64` column referencing the `i32` primary key
+            parent: i64,
+        }
+
+        let relation =
+            ReverseRelation::new(Identifier::new("parent"), |child: &TestWideChildModel| {
+                child.parent.to_db_field_value()
+            });
+        let parents = [
+            TestModel { id: Auto::fixed(1) },
+            TestModel { id: Auto::fixed(2) },
+        ];
+        let child = |id, parent| TestWideChildModel {
+            id: Auto::fixed(id),
+            parent,
+        };
+        let mut db = MockDatabaseBackend::new();
+        db.expect_query()
+            .returning(move |_| Ok(vec![child(1, 2), child(2, 1)]));
+
+        let children = relation.prefetch(&mut db, &parents).await.unwrap();
+
+        assert_eq!(children, vec![vec![child(2, 1)], vec![child(1, 2)]]);
+    }
+
+    #[cot::test]
+    async fn reverse_relation_prefetch_empty() {
+        let mut db = MockDatabaseBackend::new();
+        db.expect_query::<TestChildModel>().never();
+
+        let children = TestModel::prefetch_children(&mut db, &[TestModel { id: Auto::auto() }])
+            .await
+            .unwrap();
+
+        assert_eq!(children, vec![vec![]]);
+    }
 }
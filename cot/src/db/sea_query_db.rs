@@ -11,17 +11,81 @@ macro_rules! impl_sea_query_db_backend {
         #[derive(Debug)]
         pub(super) struct $db_name {
             db_connection: $pool_ty,
+            /// The connection all the statements are run on, if the database is
+            /// pinned to a single connection (see [`Self::pin_connection`]).
+            migration_connection: Option<
+                tokio::sync::Mutex<crate::db::sea_query_db::MigrationConnection<$sqlx_db_ty>>,
+            >,
+            /// The recorder of the schema statements, if the database is used to
+            /// render the SQL of migrations (see [`Self::with_sql_recorder`]).
+            sql_recorder: Option<crate::db::sea_query_db::SqlRecorder>,
         }
 
         impl $db_name {
-            pub(super) async fn new(url: &str) -> crate::db::Result<Self> {
-                let db_connection = <$pool_ty>::connect(url).await?;
-
-                let db = Self { db_connection };
+            pub(super) async fn new(
+                url: &str,
+                pool_config: &crate::config::DatabasePoolConfig,
+            ) -> crate::db::Result<Self> {
+                let db_connection =
+                    crate::db::sea_query_db::pool_options::<$sqlx_db_ty>(pool_config)
+                        .connect(url)
+                        .await?;
+
+                let db = Self {
+                    db_connection,
+                    migration_connection: None,
+                    sql_recorder: None,
+                };
                 db.init().await?;
                 Ok(db)
             }
 
+            /// Creates a database that doesn't connect until a statement is run
+            /// on it.
+            pub(super) fn new_lazy(url: &str) -> crate::db::Result<Self> {
+                let db_connection = <$pool_ty>::connect_lazy(url)?;
+
+                Ok(Self {
+                    db_connection,
+                    migration_connection: None,
+                    sql_recorder: None,
+                })
+            }
+
+            /// Makes the database pass the schema statements to the recorder,
+            /// and only run them if the recorder allows it.
+            pub(super) fn with_sql_recorder(
+                mut self,
+                sql_recorder: crate::db::sea_query_db::SqlRecorder,
+            ) -> Self {
+                self.sql_recorder = Some(sql_recorder);
+                self
+            }
+
+            /// Returns a database that runs all the statements on a single
+            /// connection acquired from the pool, so that they can share the
+            /// session state (such as an open transaction).
+            pub(super) async fn pin_connection(&self) -> crate::db::Result<Self> {
+                if !self.runs_statements() {
+                    // nothing is going to be run, so there's no need to connect
+                    return Ok(Self {
+                        db_connection: self.db_connection.clone(),
+                        migration_connection: None,
+                        sql_recorder: self.sql_recorder.clone(),
+                    });
+                }
+
+                let connection = self.db_connection.acquire().await?;
+
+                Ok(Self {
+                    db_connection: self.db_connection.clone(),
+                    migration_connection: Some(tokio::sync::Mutex::new(
+                        crate::db::sea_query_db::MigrationConnection::new(connection),
+                    )),
+                    sql_recorder: self.sql_recorder.clone(),
+                })
+            }
+
             pub(super) async fn close(&self) -> crate::db::Result<()> {
                 self.db_connection.close().await;
                 Ok(())
@@ -42,10 +106,16 @@ macro_rules! impl_sea_query_db_backend {
             ) -> crate::db::Result<Option<$row_name>> {
                 let (sql, values) = Self::build_sql(statement);
 
-                let row = Self::sqlx_query_with(&sql, values)
-                    .fetch_optional(&self.db_connection)
-                    .await
-                    .map_err(crate::db::sea_query_db::map_sqlx_error)?;
+                let query = Self::sqlx_query_with(&sql, values)?;
+                let row = match &self.migration_connection {
+                    Some(connection) => {
+                        query
+                            .fetch_optional(connection.lock().await.connection())
+                            .await
+                    }
+                    None => query.fetch_optional(&self.db_connection).await,
+                }
+                .map_err(crate::db::sea_query_db::map_sqlx_error)?;
                 Ok(row.map($row_name::new))
             }
 
@@ -55,23 +125,64 @@ macro_rules! impl_sea_query_db_backend {
             ) -> crate::db::Result<Vec<$row_name>> {
                 let (sql, values) = Self::build_sql(statement);
 
-                let result = Self::sqlx_query_with(&sql, values)
-                    .fetch_all(&self.db_connection)
-                    .await?
-                    .into_iter()
-                    .map($row_name::new)
-                    .collect();
+                self.fetch_all_sqlx(&sql, values).await
+            }
+
+            async fn fetch_all_sqlx(
+                &self,
+                sql: &str,
+                values: sea_query_sqlx::SqlxValues,
+            ) -> crate::db::Result<Vec<$row_name>> {
+                let query = Self::sqlx_query_with(sql, values)?;
+                let result = match &self.migration_connection {
+                    Some(connection) => query.fetch_all(connection.lock().await.connection()).await,
+                    None => query.fetch_all(&self.db_connection).await,
+                }?
+                .into_iter()
+                .map($row_name::new)
+                .collect();
                 Ok(result)
             }
 
+            pub(super) fn fetch_stream<T: sea_query_sqlx::SqlxBinder + Send + Sync>(
+                &self,
+                statement: &T,
+            ) -> futures_core::stream::BoxStream<'_, crate::db::Result<$row_name>> {
+                let (sql, values) = Self::build_sql(statement);
+
+                if self.migration_connection.is_some() {
+                    // The rows can't be streamed while the connection is held
+                    // by the stream, so they are fetched all at once instead
+                    return Box::pin(futures_util::TryStreamExt::try_flatten(
+                        futures_util::stream::once(async move {
+                            let rows = self.fetch_all_sqlx(&sql, values).await?;
+                            Ok::<_, crate::db::DatabaseError>(futures_util::stream::iter(
+                                rows.into_iter().map(Ok),
+                            ))
+                        }),
+                    ));
+                }
+
+                let rows = match Self::sqlx_query_with(&sql, values) {
+                    Ok(query) => query.fetch(&self.db_connection),
+                    Err(error) => {
+                        return Box::pin(futures_util::stream::once(async { Err(error) }));
+                    }
+                };
+                Box::pin(futures_util::StreamExt::map(rows, |row| {
+                    row.map($row_name::new)
+                        .map_err(crate::db::sea_query_db::map_sqlx_error)
+                }))
+            }
+
             pub(super) async fn execute_statement<T: sea_query_sqlx::SqlxBinder + Send + Sync>(
                 &self,
                 statement: &T,
             ) -> crate::db::Result<crate::db::StatementResult> {
-                let (sql, mut values) = Self::build_sql(statement);
-                Self::prepare_values(&mut values);
+                let (sql, values) = Self::build_sql(statement);
 
-                self.execute_sqlx(Self::sqlx_query_with(&sql, values)).await
+                self.execute_sqlx(Self::sqlx_query_with(&sql, values)?)
+                    .await
             }
 
             pub(super) async fn execute_schema<T: sea_query::SchemaStatementBuilder>(
@@ -80,6 +191,12 @@ macro_rules! impl_sea_query_db_backend {
             ) -> crate::db::Result<crate::db::StatementResult> {
                 let sql = statement.build($query_builder);
                 tracing::debug!("Schema modification: {}", sql);
+                if !self.record_sql(&sql) {
+                    return Ok(crate::db::StatementResult {
+                        rows_affected: crate::db::RowsNum(0),
+                        last_inserted_row_id: None,
+                    });
+                }
 
                 self.execute_sqlx(sqlx::query(sqlx::AssertSqlSafe(sql)))
                     .await
@@ -90,7 +207,28 @@ macro_rules! impl_sea_query_db_backend {
                 sql: &str,
                 values: sea_query_sqlx::SqlxValues,
             ) -> crate::db::Result<crate::db::StatementResult> {
-                self.execute_sqlx(Self::sqlx_query_with(sql, values)).await
+                if !self.record_sql(sql) {
+                    return Ok(crate::db::StatementResult {
+                        rows_affected: crate::db::RowsNum(0),
+                        last_inserted_row_id: None,
+                    });
+                }
+
+                self.execute_sqlx(Self::sqlx_query_with(sql, values)?).await
+            }
+
+            /// Passes the statement to the SQL recorder, if there is one, and
+            /// returns whether the statement should be run.
+            fn record_sql(&self, sql: &str) -> bool {
+                self.sql_recorder
+                    .as_ref()
+                    .is_none_or(|sql_recorder| sql_recorder.record(sql))
+            }
+
+            fn runs_statements(&self) -> bool {
+                self.sql_recorder
+                    .as_ref()
+                    .is_none_or(crate::db::sea_query_db::SqlRecorder::runs_statements)
             }
 
             async fn execute_sqlx<'a, A>(
@@ -100,10 +238,15 @@ macro_rules! impl_sea_query_db_backend {
             where
                 A: 'a + sqlx::IntoArguments<$sqlx_db_ty>,
             {
-                let result = sqlx_statement
-                    .execute(&self.db_connection)
-                    .await
-                    .map_err(|err| crate::db::sea_query_db::map_sqlx_error(err))?;
+                let result = match &self.migration_connection {
+                    Some(connection) => {
+                        sqlx_statement
+                            .execute(connection.lock().await.connection())
+                            .await
+                    }
+                    None => sqlx_statement.execute(&self.db_connection).await,
+                }
+                .map_err(crate::db::sea_query_db::map_sqlx_error)?;
                 let result = crate::db::StatementResult {
                     rows_affected: crate::db::RowsNum(result.rows_affected()),
                     last_inserted_row_id: Self::last_inserted_row_id_for(&result),
@@ -125,11 +268,102 @@ macro_rules! impl_sea_query_db_backend {
             fn sqlx_query_with(
                 sql: &str,
                 mut values: sea_query_sqlx::SqlxValues,
-            ) -> sqlx::query::Query<'_, $sqlx_db_ty, sea_query_sqlx::SqlxValues> {
-                Self::prepare_values(&mut values);
+            ) -> crate::db::Result<
+                sqlx::query::Query<'static, $sqlx_db_ty, sea_query_sqlx::SqlxValues>,
+            > {
+                Self::prepare_values(&mut values)?;
                 tracing::debug!("Query: `{}` (values: {:?})", sql, values);
 
-                sqlx::query_with(sqlx::AssertSqlSafe(sql), values)
+                Ok(sqlx::query_with(sqlx::AssertSqlSafe(sql), values))
+            }
+        }
+
+        #[allow(
+            clippy::allow_attributes,
+            dead_code,
+            reason = "not used by MySQL, as it doesn't support transactional schema changes"
+        )]
+        impl $db_name {
+            /// Returns the connection pinned with [`Self::pin_connection`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the connection is not pinned.
+            async fn lock_migration_connection(
+                &self,
+            ) -> tokio::sync::MutexGuard<
+                '_,
+                crate::db::sea_query_db::MigrationConnection<$sqlx_db_ty>,
+            > {
+                self.migration_connection
+                    .as_ref()
+                    .expect("the connection should be pinned")
+                    .lock()
+                    .await
+            }
+
+            /// Runs a statement changing the session of the pinned connection.
+            pub(super) async fn change_pinned_session(
+                &self,
+                sql: &'static str,
+            ) -> crate::db::Result<()> {
+                if !self.record_sql(sql) {
+                    return Ok(());
+                }
+                self.lock_migration_connection()
+                    .await
+                    .change_session(sql)
+                    .await
+            }
+
+            /// Runs a statement undoing a change made with
+            /// [`Self::change_pinned_session`].
+            pub(super) async fn restore_pinned_session(
+                &self,
+                sql: &'static str,
+            ) -> crate::db::Result<()> {
+                if !self.record_sql(sql) {
+                    return Ok(());
+                }
+                self.lock_migration_connection()
+                    .await
+                    .restore_session(sql)
+                    .await
+            }
+
+            /// Starts a transaction on the pinned connection.
+            pub(super) async fn begin_pinned_transaction(&self) -> crate::db::Result<()> {
+                if !self.record_sql("BEGIN") {
+                    return Ok(());
+                }
+                self.lock_migration_connection()
+                    .await
+                    .begin_transaction()
+                    .await
+            }
+
+            /// Commits the transaction started with
+            /// [`Self::begin_pinned_transaction`].
+            pub(super) async fn commit_pinned_transaction(&self) -> crate::db::Result<()> {
+                if !self.record_sql("COMMIT") {
+                    return Ok(());
+                }
+                self.lock_migration_connection()
+                    .await
+                    .commit_transaction()
+                    .await
+            }
+
+            /// Rolls back the transaction started with
+            /// [`Self::begin_pinned_transaction`].
+            pub(super) async fn rollback_pinned_transaction(&self) -> crate::db::Result<()> {
+                if !self.runs_statements() {
+                    return Ok(());
+                }
+                self.lock_migration_connection()
+                    .await
+                    .rollback_transaction()
+                    .await
             }
         }
 
@@ -195,8 +429,240 @@ pub(crate) fn map_sqlx_error(err: sqlx::Error) -> crate::db::DatabaseError {
     crate::db::DatabaseError::from(err)
 }
 
+/// Returns the options of a connection pool with the given settings applied.
+///
+/// The settings that are not set are left at the defaults of `sqlx`. The
+/// initialization SQL is run with [`sqlx::raw_sql`], so each entry may
+/// contain multiple statements.
+pub(crate) fn pool_options<DB: sqlx::Database>(
+    config: &crate::config::DatabasePoolConfig,
+) -> sqlx::pool::PoolOptions<DB>
+where
+    for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+{
+    let mut options = sqlx::pool::PoolOptions::<DB>::new();
+    if let Some(max_connections) = config.max_connections {
+        options = options.max_connections(max_connections);
+    }
+    if let Some(min_connections) = config.min_connections {
+        options = options.min_connections(min_connections);
+    }
+    if let Some(acquire_timeout) = config.acquire_timeout {
+        options = options.acquire_timeout(acquire_timeout);
+    }
+    if let Some(idle_timeout) = config.idle_timeout {
+        options = options.idle_timeout(idle_timeout);
+    }
+    if let Some(max_lifetime) = config.max_lifetime {
+        options = options.max_lifetime(max_lifetime);
+    }
+
+    if !config.init_sql.is_empty() {
+        let init_sql = std::sync::Arc::new(config.init_sql.clone());
+        options = options.after_connect(move |connection, _metadata| {
+            let init_sql = std::sync::Arc::clone(&init_sql);
+            Box::pin(async move {
+                for sql in init_sql.iter() {
+                    tracing::debug!("Connection initialization: {}", sql);
+                    sqlx::raw_sql(sqlx::AssertSqlSafe(sql.clone()))
+                        .execute(&mut *connection)
+                        .await?;
+                }
+                Ok(())
+            })
+        });
+    }
+
+    options
+}
+
 pub(crate) use impl_sea_query_db_backend;
 
+/// A pool connection used exclusively by a database pinned to a single
+/// connection, such as the one a migration is applied with.
+///
+/// Statements run on the connection can change the state of its session (for
+/// instance, disable foreign key checks on SQLite). Each such change has to be
+/// undone with [`Self::restore_session`] after it's made with
+/// [`Self::change_session`]; if the connection is dropped while any of the
+/// changes is still in effect (because undoing it failed, or the future
+/// undoing it was cancelled), it is closed instead of being returned to the
+/// pool, so that the changes don't leak to other users of the pool.
+#[derive(derive_more::Debug)]
+pub(crate) struct MigrationConnection<DB: sqlx::Database> {
+    #[debug("...")]
+    connection: sqlx::pool::PoolConnection<DB>,
+    session_changes: usize,
+    in_transaction: bool,
+    rebuilt_tables: Vec<String>,
+}
+
+impl<DB: sqlx::Database> MigrationConnection<DB> {
+    pub(crate) fn new(connection: sqlx::pool::PoolConnection<DB>) -> Self {
+        Self {
+            connection,
+            session_changes: 0,
+            in_transaction: false,
+            rebuilt_tables: Vec::new(),
+        }
+    }
+
+    pub(crate) fn connection(&mut self) -> &mut DB::Connection {
+        &mut self.connection
+    }
+
+    /// Returns whether any change made with [`Self::change_session`] is still
+    /// in effect.
+    pub(crate) fn is_session_changed(&self) -> bool {
+        self.session_changes > 0
+    }
+
+    /// Runs a statement changing the state of the connection session.
+    pub(crate) async fn change_session(&mut self, sql: &'static str) -> crate::db::Result<()>
+    where
+        for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+    {
+        // count the change before running the statement, as it might take effect
+        // even if the future is cancelled
+        self.session_changes += 1;
+        tracing::debug!("Session change: {}", sql);
+        sqlx::raw_sql(sql)
+            .execute(&mut *self.connection)
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok(())
+    }
+
+    /// Runs a statement undoing a change made with [`Self::change_session`].
+    pub(crate) async fn restore_session(&mut self, sql: &'static str) -> crate::db::Result<()>
+    where
+        for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+    {
+        tracing::debug!("Session restore: {}", sql);
+        sqlx::raw_sql(sql)
+            .execute(&mut *self.connection)
+            .await
+            .map_err(map_sqlx_error)?;
+        self.session_changes -= 1;
+        Ok(())
+    }
+
+    /// Returns whether a transaction started with
+    /// [`Self::begin_transaction`] is in progress.
+    pub(crate) fn is_in_transaction(&self) -> bool {
+        self.in_transaction
+    }
+
+    /// Starts a transaction on the connection.
+    ///
+    /// The transaction is a session change, so the connection is closed (and
+    /// the transaction rolled back) if it's dropped before the transaction is
+    /// committed or rolled back.
+    pub(crate) async fn begin_transaction(&mut self) -> crate::db::Result<()>
+    where
+        for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+    {
+        self.change_session("BEGIN").await?;
+        self.in_transaction = true;
+        Ok(())
+    }
+
+    /// Commits the transaction started with [`Self::begin_transaction`].
+    pub(crate) async fn commit_transaction(&mut self) -> crate::db::Result<()>
+    where
+        for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+    {
+        self.restore_session("COMMIT").await?;
+        self.in_transaction = false;
+        self.rebuilt_tables.clear();
+        Ok(())
+    }
+
+    /// Rolls back the transaction started with [`Self::begin_transaction`].
+    pub(crate) async fn rollback_transaction(&mut self) -> crate::db::Result<()>
+    where
+        for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+    {
+        self.restore_session("ROLLBACK").await?;
+        self.in_transaction = false;
+        self.rebuilt_tables.clear();
+        Ok(())
+    }
+
+    /// Remembers that a table has been rebuilt in the transaction in
+    /// progress, so that its foreign keys can be checked before the
+    /// transaction is committed.
+    #[cfg_attr(not(feature = "sqlite"), expect(dead_code))]
+    pub(crate) fn mark_table_rebuilt(&mut self, table_name: &str) {
+        if !self.rebuilt_tables.iter().any(|name| name == table_name) {
+            self.rebuilt_tables.push(table_name.to_owned());
+        }
+    }
+
+    /// Returns the tables rebuilt in the transaction in progress.
+    #[cfg_attr(not(feature = "sqlite"), expect(dead_code))]
+    pub(crate) fn rebuilt_tables(&self) -> &[String] {
+        &self.rebuilt_tables
+    }
+}
+
+impl<DB: sqlx::Database> Drop for MigrationConnection<DB> {
+    fn drop(&mut self) {
+        if self.is_session_changed() {
+            tracing::warn!(
+                "Closing a database connection whose session state couldn't be restored"
+            );
+            self.connection.close_on_drop();
+        }
+    }
+}
+
+/// Collects the schema statements run by a database, so that the SQL of a
+/// migration can be rendered without applying it.
+///
+/// The clones of a recorder share the collected statements.
+#[derive(Debug, Clone)]
+pub(crate) struct SqlRecorder {
+    statements: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    run_statements: bool,
+}
+
+impl SqlRecorder {
+    /// Creates a new recorder. If `run_statements` is `false`, the recorded
+    /// statements are not run, so the database doesn't even have to be
+    /// connected to.
+    pub(crate) fn new(run_statements: bool) -> Self {
+        Self {
+            statements: std::sync::Arc::default(),
+            run_statements,
+        }
+    }
+
+    /// Returns whether the recorded statements should be run.
+    pub(crate) fn runs_statements(&self) -> bool {
+        self.run_statements
+    }
+
+    /// Records the statement and returns whether it should be run.
+    pub(crate) fn record(&self, sql: &str) -> bool {
+        self.statements
+            .lock()
+            .expect("the lock should not be poisoned")
+            .push(sql.to_owned());
+        self.run_statements
+    }
+
+    /// Returns the statements recorded so far, clearing the recorder.
+    pub(crate) fn take(&self) -> Vec<String> {
+        std::mem::take(
+            &mut *self
+                .statements
+                .lock()
+                .expect("the lock should not be poisoned"),
+        )
+    }
+}
+
 /// Implements the transaction backend for a specific engine using `SeaQuery`.
 macro_rules! impl_sea_query_transaction_backend {
     ($db_name:ident, $transaction_name:ident : $sqlx_db_ty:ty, $row_name:ident, $query_builder:expr) => {
@@ -231,7 +697,7 @@ macro_rules! impl_sea_query_transaction_backend {
             ) -> crate::db::Result<Option<$row_name>> {
                 let (sql, values) = $db_name::build_sql(statement);
 
-                let row = $db_name::sqlx_query_with(&sql, values)
+                let row = $db_name::sqlx_query_with(&sql, values)?
                     .fetch_optional(&mut *self.inner)
                     .await
                     .map_err(crate::db::sea_query_db::map_sqlx_error)?;
@@ -244,7 +710,7 @@ macro_rules! impl_sea_query_transaction_backend {
             ) -> crate::db::Result<Vec<$row_name>> {
                 let (sql, values) = $db_name::build_sql(statement);
 
-                let result = $db_name::sqlx_query_with(&sql, values)
+                let result = $db_name::sqlx_query_with(&sql, values)?
                     .fetch_all(&mut *self.inner)
                     .await
                     .map_err(crate::db::sea_query_db::map_sqlx_error)?
@@ -254,14 +720,31 @@ macro_rules! impl_sea_query_transaction_backend {
                 Ok(result)
             }
 
+            pub(super) fn fetch_stream<T: sea_query_sqlx::SqlxBinder + Send + Sync>(
+                &mut self,
+                statement: &T,
+            ) -> futures_core::stream::BoxStream<'_, crate::db::Result<$row_name>> {
+                let (sql, values) = $db_name::build_sql(statement);
+
+                let rows = match $db_name::sqlx_query_with(&sql, values) {
+                    Ok(query) => query.fetch(&mut *self.inner),
+                    Err(error) => {
+                        return Box::pin(futures_util::stream::once(async { Err(error) }));
+                    }
+                };
+                Box::pin(futures_util::StreamExt::map(rows, |row| {
+                    row.map($row_name::new)
+                        .map_err(crate::db::sea_query_db::map_sqlx_error)
+                }))
+            }
+
             pub(super) async fn execute_statement<T: sea_query_sqlx::SqlxBinder + Send + Sync>(
                 &mut self,
                 statement: &T,
             ) -> crate::db::Result<crate::db::StatementResult> {
-                let (sql, mut values) = $db_name::build_sql(statement);
-                $db_name::prepare_values(&mut values);
+                let (sql, values) = $db_name::build_sql(statement);
 
-                self.execute_sqlx($db_name::sqlx_query_with(&sql, values))
+                self.execute_sqlx($db_name::sqlx_query_with(&sql, values)?)
                     .await
             }
 
@@ -270,7 +753,7 @@ macro_rules! impl_sea_query_transaction_backend {
                 sql: &str,
                 values: sea_query_sqlx::SqlxValues,
             ) -> crate::db::Result<crate::db::StatementResult> {
-                self.execute_sqlx($db_name::sqlx_query_with(sql, values))
+                self.execute_sqlx($db_name::sqlx_query_with(sql, values)?)
                     .await
             }
 
@@ -27,7 +27,7 @@ pub use select::{
 use crate::auth::PasswordHash;
 use crate::common_types::{Email, Password, Url};
 #[cfg(feature = "db")]
-use crate::db::{Auto, ForeignKey, LimitedString, Model};
+use crate::db::{Auto, ForeignKey, LimitedString, Model, ModelPrimaryKey};
 use crate::form::{AsFormField, FormField, FormFieldOptions, FormFieldValidationError};
 use crate::html::HtmlTag;
 
@@ -926,7 +926,9 @@ where
     fn to_field_value(&self) -> String {
         match self {
             ForeignKey::PrimaryKey(primary_key) => primary_key.to_field_value(),
-            ForeignKey::Model(model) => model.primary_key().to_field_value(),
+            ForeignKey::Model(model) => {
+                <T as Model>::PrimaryKey::from_borrowed(model.primary_key()).to_field_value()
+            }
         }
     }
 }
@@ -20,6 +20,8 @@
 //!     MyProject
 //! }
 //! ```
+#[cfg(feature = "db")]
+use std::collections::HashMap;
 use std::future::poll_fn;
 use std::panic::AssertUnwindSafe;
 use std::path::PathBuf;
@@ -1208,9 +1210,15 @@ impl Bootstrapper<WithApps> {
     pub async fn with_database(self) -> cot::Result<Bootstrapper<WithDatabase>> {
         #[cfg(feature = "db")]
         let database = Self::init_database(&self.context.config.database).await?;
+        #[cfg(feature = "db")]
+        self.context.config.check_database_apps()?;
+        #[cfg(feature = "db")]
+        let named_databases = Self::init_named_databases(&self.context.config.databases).await?;
         let context = self.context.with_database(
             #[cfg(feature = "db")]
             database,
+            #[cfg(feature = "db")]
+            named_databases,
         );
 
         Ok(Bootstrapper {
@@ -1225,14 +1233,44 @@ impl Bootstrapper<WithApps> {
     async fn init_database(config: &DatabaseConfig) -> cot::Result<Option<Database>> {
         match &config.url {
             Some(url) => {
-                let database = Database::new(url.as_str()).await?;
+                let mut replicas = Vec::with_capacity(config.replicas.len());
+                for replica_url in &config.replicas {
+                    replicas
+                        .push(Database::new_with_pool(replica_url.as_str(), &config.pool).await?);
+                }
+
+                let database = Database::new_with_pool(url.as_str(), &config.pool)
+                    .await?
+                    .with_replicas(replicas);
                 Ok(Some(database))
             }
             None => Ok(None),
         }
     }
+
+    #[cfg(feature = "db")]
+    async fn init_named_databases(
+        configs: &HashMap<String, DatabaseConfig>,
+    ) -> cot::Result<HashMap<String, Database>> {
+        let mut databases = HashMap::with_capacity(configs.len());
+        for (alias, config) in configs {
+            let database = Self::init_database(config)
+                .await?
+                .ok_or_else(|| MissingDatabaseUrl(alias.clone()))?;
+            databases.insert(alias.clone(), database);
+        }
+
+        Ok(databases)
+    }
 }
 
+#[cfg(feature = "db")]
+#[derive(Debug, Error)]
+#[error("the URL of the database `{0}` is not set")]
+struct MissingDatabaseUrl(String);
+#[cfg(feature = "db")]
+impl_into_cot_error!(MissingDatabaseUrl);
+
 impl Bootstrapper<WithDatabase> {
     /// Builds the Cot project instance.
     ///
@@ -1535,6 +1573,9 @@ pub trait BootstrapPhase: sealed::Sealed {
     /// The type of the database.
     #[cfg(feature = "db")]
     type Database: Debug;
+    /// The type of the named databases.
+    #[cfg(feature = "db")]
+    type NamedDatabases: Debug;
     /// The type of the auth backend.
     type AuthBackend;
     /// The type of the cache.
@@ -1562,6 +1603,8 @@ impl BootstrapPhase for Uninitialized {
     type Router = ();
     #[cfg(feature = "db")]
     type Database = ();
+    #[cfg(feature = "db")]
+    type NamedDatabases = ();
     type AuthBackend = ();
     #[cfg(feature = "cache")]
     type Cache = ();
@@ -1587,6 +1630,8 @@ impl BootstrapPhase for WithConfig {
     type Router = ();
     #[cfg(feature = "db")]
     type Database = ();
+    #[cfg(feature = "db")]
+    type NamedDatabases = ();
     type AuthBackend = ();
     #[cfg(feature = "cache")]
     type Cache = ();
@@ -1612,6 +1657,8 @@ impl BootstrapPhase for WithApps {
     type Router = Arc<Router>;
     #[cfg(feature = "db")]
     type Database = ();
+    #[cfg(feature = "db")]
+    type NamedDatabases = ();
     type AuthBackend = ();
     #[cfg(feature = "cache")]
     type Cache = ();
@@ -1637,6 +1684,8 @@ impl BootstrapPhase for WithDatabase {
     type Router = <WithApps as BootstrapPhase>::Router;
     #[cfg(feature = "db")]
     type Database = Option<Database>;
+    #[cfg(feature = "db")]
+    type NamedDatabases = HashMap<String, Database>;
     type AuthBackend = <WithApps as BootstrapPhase>::AuthBackend;
     #[cfg(feature = "cache")]
     type Cache = ();
@@ -1662,6 +1711,8 @@ impl BootstrapPhase for WithCache {
     type Router = <WithApps as BootstrapPhase>::Router;
     #[cfg(feature = "db")]
     type Database = <WithDatabase as BootstrapPhase>::Database;
+    #[cfg(feature = "db")]
+    type NamedDatabases = <WithDatabase as BootstrapPhase>::NamedDatabases;
     type AuthBackend = <WithApps as BootstrapPhase>::AuthBackend;
     #[cfg(feature = "cache")]
     type Cache = Cache;
@@ -1687,6 +1738,8 @@ impl BootstrapPhase for Initialized {
     type Router = <WithDatabase as BootstrapPhase>::Router;
     #[cfg(feature = "db")]
     type Database = <WithDatabase as BootstrapPhase>::Database;
+    #[cfg(feature = "db")]
+    type NamedDatabases = <WithDatabase as BootstrapPhase>::NamedDatabases;
     type AuthBackend = Arc<dyn AuthBackend>;
     #[cfg(feature = "cache")]
     type Cache = <WithCache as BootstrapPhase>::Cache;
@@ -1702,6 +1755,8 @@ pub struct ProjectContext<S: BootstrapPhase = Initialized> {
     router: S::Router,
     #[cfg(feature = "db")]
     database: S::Database,
+    #[cfg(feature = "db")]
+    named_databases: S::NamedDatabases,
     #[debug("..")]
     auth_backend: S::AuthBackend,
     #[cfg(feature = "cache")]
@@ -1719,6 +1774,8 @@ impl ProjectContext<Uninitialized> {
             router: (),
             #[cfg(feature = "db")]
             database: (),
+            #[cfg(feature = "db")]
+            named_databases: (),
             auth_backend: (),
             #[cfg(feature = "cache")]
             cache: (),
@@ -1741,6 +1798,8 @@ impl ProjectContext<Uninitialized> {
             router: self.router,
             #[cfg(feature = "db")]
             database: self.database,
+            #[cfg(feature = "db")]
+            named_databases: self.named_databases,
             auth_backend: self.auth_backend,
             #[cfg(feature = "cache")]
             cache: self.cache,
@@ -1785,6 +1844,8 @@ impl ProjectContext<WithConfig> {
             router,
             #[cfg(feature = "db")]
             database: self.database,
+            #[cfg(feature = "db")]
+            named_databases: self.named_databases,
             auth_backend: self.auth_backend,
             #[cfg(feature = "cache")]
             cache: self.cache,
@@ -1821,6 +1882,7 @@ impl ProjectContext<WithApps> {
     fn with_database(
         self,
         #[cfg(feature = "db")] database: Option<Database>,
+        #[cfg(feature = "db")] named_databases: HashMap<String, Database>,
     ) -> ProjectContext<WithDatabase> {
         ProjectContext {
             config: self.config,
@@ -1828,6 +1890,8 @@ impl ProjectContext<WithApps> {
             router: self.router,
             #[cfg(feature = "db")]
             database,
+            #[cfg(feature = "db")]
+            named_databases,
             auth_backend: self.auth_backend,
             #[cfg(feature = "cache")]
             cache: self.cache,
@@ -1847,6 +1911,8 @@ impl ProjectContext<WithDatabase> {
             auth_backend: self.auth_backend,
             #[cfg(feature = "db")]
             database: self.database,
+            #[cfg(feature = "db")]
+            named_databases: self.named_databases,
             #[cfg(feature = "cache")]
             cache,
             #[cfg(feature = "email")]
@@ -1865,6 +1931,8 @@ impl ProjectContext<WithCache> {
             auth_backend,
             #[cfg(feature = "db")]
             database: self.database,
+            #[cfg(feature = "db")]
+            named_databases: self.named_databases,
             #[cfg(feature = "cache")]
             cache: self.cache,
             #[cfg(feature = "email")]
@@ -1874,12 +1942,18 @@ impl ProjectContext<WithCache> {
 }
 impl ProjectContext<Initialized> {
     #[cfg(feature = "test")]
+    #[allow(
+        clippy::allow_attributes,
+        clippy::too_many_arguments,
+        reason = "the number of arguments depends on the enabled features"
+    )]
     pub(crate) fn initialized(
         config: <Initialized as BootstrapPhase>::Config,
         apps: <Initialized as BootstrapPhase>::Apps,
         router: <Initialized as BootstrapPhase>::Router,
         auth_backend: <Initialized as BootstrapPhase>::AuthBackend,
         #[cfg(feature = "db")] database: <Initialized as BootstrapPhase>::Database,
+        #[cfg(feature = "db")] named_databases: <Initialized as BootstrapPhase>::NamedDatabases,
         #[cfg(feature = "cache")] cache: <Initialized as BootstrapPhase>::Cache,
         #[cfg(feature = "email")] email: <Initialized as BootstrapPhase>::Email,
     ) -> Self {
@@ -1889,6 +1963,8 @@ impl ProjectContext<Initialized> {
             router,
             #[cfg(feature = "db")]
             database,
+            #[cfg(feature = "db")]
+            named_databases,
             auth_backend,
             #[cfg(feature = "cache")]
             cache,
@@ -2043,6 +2119,110 @@ impl<S: BootstrapPhase<Database = Option<Database>>> ProjectContext<S> {
     }
 }
 
+#[cfg(feature = "db")]
+impl<S: BootstrapPhase<NamedDatabases = HashMap<String, Database>>> ProjectContext<S> {
+    /// Returns the database with the given alias, as configured in
+    /// [`ProjectConfig::databases`], if it exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::request::{Request, RequestExt};
+    /// use cot::response::Response;
+    ///
+    /// async fn index(request: Request) -> cot::Result<Response> {
+    ///     let database = request.context().try_named_database("analytics");
+    ///     if let Some(database) = database {
+    ///         // do something with the database
+    ///     } else {
+    ///         // the database is not configured
+    ///     }
+    /// #    unimplemented!()
+    /// }
+    /// ```
+    #[must_use]
+    pub fn try_named_database(&self, alias: &str) -> Option<&Database> {
+        self.named_databases.get(alias)
+    }
+
+    /// Returns the database with the given alias, as configured in
+    /// [`ProjectConfig::databases`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics if there is no database with the given alias.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::request::{Request, RequestExt};
+    /// use cot::response::Response;
+    ///
+    /// async fn index(request: Request) -> cot::Result<Response> {
+    ///     let database = request.context().named_database("analytics");
+    ///     // use the database
+    /// #    unimplemented!()
+    /// }
+    /// ```
+    #[must_use]
+    #[track_caller]
+    pub fn named_database(&self, alias: &str) -> &Database {
+        self.try_named_database(alias).unwrap_or_else(|| {
+            panic!(
+                "Database `{alias}` missing. Did you forget to add it to the `databases` config?"
+            )
+        })
+    }
+}
+
+/// Returns the migrations of the given apps that are applied to the database
+/// with the given alias, or to the default database if `alias` is `None`.
+///
+/// The migrations of the apps listed in [`DatabaseConfig::apps`] of any of the
+/// [named databases](ProjectConfig::databases) are applied to that database;
+/// the migrations of all the other apps are applied to the default one.
+#[cfg(feature = "db")]
+pub(crate) fn database_migrations(
+    apps: &[Box<dyn App>],
+    config: &ProjectConfig,
+    alias: Option<&str>,
+) -> Vec<Box<SyncDynMigration>> {
+    let app_database = |app_name: &str| {
+        config
+            .databases
+            .iter()
+            .find(|(_, database)| database.apps.iter().any(|app| app == app_name))
+            .map(|(alias, _)| alias.as_str())
+    };
+
+    apps.iter()
+        .filter(|app| app_database(app.name()) == alias)
+        .flat_map(|app| app.migrations())
+        .collect()
+}
+
+/// Applies the migrations to the databases that have
+/// [`DatabaseConfig::auto_migrate`] enabled.
+#[cfg(feature = "db")]
+async fn auto_migrate(context: &ProjectContext<Initialized>) -> cot::Result<()> {
+    let config = context.config();
+    if let Some(database) = &context.database
+        && config.database.auto_migrate
+    {
+        let migrations = database_migrations(&context.apps, config, None);
+        MigrationEngine::new(migrations)?.run(database).await?;
+    }
+
+    for (alias, database) in &context.named_databases {
+        if config.databases[alias].auto_migrate {
+            let migrations = database_migrations(&context.apps, config, Some(alias));
+            MigrationEngine::new(migrations)?.run(database).await?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Runs the Cot project on the given address.
 ///
 /// This function takes a Cot project and an address string and runs the
@@ -2104,14 +2284,7 @@ pub async fn run_at_with_shutdown(
     } = bootstrapper.finish();
 
     #[cfg(feature = "db")]
-    if let Some(database) = &context.database {
-        let mut migrations: Vec<Box<SyncDynMigration>> = Vec::new();
-        for app in &context.apps {
-            migrations.extend(app.migrations());
-        }
-        let migration_engine = MigrationEngine::new(migrations)?;
-        migration_engine.run(database).await?;
-    }
+    auto_migrate(&context).await?;
 
     let mut apps = std::mem::take(&mut context.apps);
     for app in &mut apps {
@@ -2195,6 +2368,10 @@ pub async fn run_at_with_shutdown(
     if let Some(database) = &context_cleanup.database {
         database.close().await?;
     }
+    #[cfg(feature = "db")]
+    for database in context_cleanup.named_databases.values() {
+        database.close().await?;
+    }
 
     Ok(())
 }
@@ -2578,7 +2755,7 @@ mod tests {
                     .build(),
             )
             .with_apps(vec![], Arc::new(Router::empty()))
-            .with_database(None)
+            .with_database(None, HashMap::new())
             .with_cache(cache_memory);
 
         let auth_backend = TestProject.auth_backend(&context);
@@ -2614,6 +2791,64 @@ mod tests {
         assert_eq!(bootstrapper.context().router.routes().len(), 1);
     }
 
+    #[cfg(feature = "sqlite")]
+    #[cot::test]
+    #[cfg_attr(
+        miri,
+        ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
+    )]
+    async fn bootstrapper_named_databases() {
+        struct TestProject;
+        impl Project for TestProject {}
+
+        let config = ProjectConfig::from_toml(
+            r#"
+            [databases.analytics]
+            url = "sqlite::memory:"
+        "#,
+        )
+        .unwrap();
+        let bootstrapper = Bootstrapper::new(TestProject)
+            .with_config(config)
+            .boot()
+            .await
+            .unwrap();
+
+        assert!(
+            bootstrapper
+                .context()
+                .try_named_database("analytics")
+                .is_some()
+        );
+        assert!(bootstrapper.context().try_named_database("other").is_none());
+        let _ = bootstrapper.context().named_database("analytics");
+    }
+
+    #[cfg(feature = "db")]
+    #[cot::test]
+    async fn bootstrapper_named_database_missing_url() {
+        struct TestProject;
+        impl Project for TestProject {}
+
+        let config = ProjectConfig::from_toml(
+            r"
+            [databases.analytics]
+            auto_migrate = false
+        ",
+        )
+        .unwrap();
+        let error = Bootstrapper::new(TestProject)
+            .with_config(config)
+            .boot()
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "the URL of the database `analytics` is not set"
+        );
+    }
+
     #[cot::test]
     async fn build_custom_error_page_poll_ready_failure() {
         #[derive(Clone)]
@@ -1,6 +1,8 @@
 //! Test utilities for Cot projects.
 
 use std::any::Any;
+#[cfg(feature = "db")]
+use std::collections::HashMap;
 use std::future::poll_fn;
 use std::marker::PhantomData;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -233,6 +235,8 @@ pub struct TestRequestBuilder {
     auth: Option<Auth>,
     #[cfg(feature = "db")]
     database: Option<Database>,
+    #[cfg(feature = "db")]
+    named_databases: HashMap<String, Database>,
     form_data: Option<Vec<(String, String)>>,
     #[cfg(feature = "json")]
     json_data: Option<String>,
@@ -290,6 +294,8 @@ impl Default for TestRequestBuilder {
             auth: None,
             #[cfg(feature = "db")]
             database: None,
+            #[cfg(feature = "db")]
+            named_databases: HashMap::new(),
             form_data: None,
             #[cfg(feature = "json")]
             json_data: None,
@@ -604,6 +610,54 @@ impl TestRequestBuilder {
         self
     }
 
+    /// Add a database with the given alias to the request builder. It can be
+    /// accessed with
+    /// [`ProjectContext::named_database`](crate::project::ProjectContext::named_database).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::RequestHandler;
+    /// use cot::db::Database;
+    /// use cot::html::Html;
+    /// use cot::request::{Request, RequestExt};
+    /// use cot::test::TestRequestBuilder;
+    ///
+    /// async fn index(request: Request) -> Html {
+    ///     let analytics = request.context().named_database("analytics");
+    ///     // ... do something with analytics
+    ///
+    ///     Html::new("Hello world!")
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> cot::Result<()> {
+    /// let request = TestRequestBuilder::get("/")
+    ///     .named_database("analytics", Database::new("sqlite::memory:").await?)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     index
+    ///         .handle(request)
+    ///         .await?
+    ///         .into_body()
+    ///         .into_bytes()
+    ///         .await?,
+    ///     "Hello world!"
+    /// );
+    /// # Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "db")]
+    pub fn named_database<DB: Into<Database>>(
+        &mut self,
+        alias: impl Into<String>,
+        database: DB,
+    ) -> &mut Self {
+        self.named_databases.insert(alias.into(), database.into());
+        self
+    }
+
     /// Use database authentication in the test request.
     ///
     /// Note that this calls [`Self::auth_backend`], [`Self::with_session`],
@@ -818,6 +872,8 @@ impl TestRequestBuilder {
             auth_backend,
             #[cfg(feature = "db")]
             self.database.clone(),
+            #[cfg(feature = "db")]
+            self.named_databases.clone(),
             #[cfg(feature = "cache")]
             self.cache
                 .clone()
@@ -1294,6 +1350,8 @@ pub struct TestMigration {
     name: &'static str,
     dependencies: Vec<MigrationDependency>,
     operations: Vec<Operation>,
+    replaces: Vec<&'static str>,
+    atomic: bool,
 }
 
 #[cfg(feature = "db")]
@@ -1328,8 +1386,43 @@ impl TestMigration {
             name,
             dependencies: dependencies.into(),
             operations: operations.into(),
+            replaces: Vec::new(),
+            atomic: true,
         }
     }
+
+    /// Sets the names of the migrations this migration replaces, making it a
+    /// squashed migration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::test::TestMigration;
+    ///
+    /// let migration = TestMigration::new("auth", "m_0003_squashed_0001_0002", vec![], vec![])
+    ///     .replaces(["m_0001_initial", "m_0002_auto"]);
+    /// ```
+    #[must_use]
+    pub fn replaces<R: Into<Vec<&'static str>>>(mut self, replaces: R) -> Self {
+        self.replaces = replaces.into();
+        self
+    }
+
+    /// Sets whether the migration is applied and rolled back atomically. This
+    /// is `true` by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cot::test::TestMigration;
+    ///
+    /// let migration = TestMigration::new("auth", "m_0001_initial", vec![], vec![]).atomic(false);
+    /// ```
+    #[must_use]
+    pub fn atomic(mut self, atomic: bool) -> Self {
+        self.atomic = atomic;
+        self
+    }
 }
 
 #[cfg(feature = "db")]
@@ -1349,6 +1442,14 @@ impl DynMigration for TestMigration {
     fn operations(&self) -> &[Operation] {
         &self.operations
     }
+
+    fn replaces(&self) -> &[&str] {
+        &self.replaces
+    }
+
+    fn atomic(&self) -> bool {
+        self.atomic
+    }
 }
 
 /// A utility for running entire projects in end-to-end tests.
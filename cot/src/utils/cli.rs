@@ -30,14 +30,18 @@ pub enum StatusType {
     Creating,
     Adding,
     Modifying,
+    Renaming,
     Removing,
     RollingBack,
+    Applying,
     // Completed Ops
     Created,
     Added,
     Modified,
+    Renamed,
     Removed,
     RolledBack,
+    Applied,
 
     // Status types
     Error,   // Should be used in Error handling inside remove operations
@@ -51,19 +55,27 @@ impl StatusType {
 
         match self {
             // In-Progress => Brighter colors
-            StatusType::Creating => base_style.fg_color(Some(Color::Ansi(AnsiColor::BrightGreen))),
+            StatusType::Creating | StatusType::Applying => {
+                base_style.fg_color(Some(Color::Ansi(AnsiColor::BrightGreen)))
+            }
             StatusType::Adding => base_style.fg_color(Some(Color::Ansi(AnsiColor::BrightCyan))),
             StatusType::Removing | StatusType::RollingBack => {
                 base_style.fg_color(Some(Color::Ansi(AnsiColor::BrightMagenta)))
             }
-            StatusType::Modifying => base_style.fg_color(Some(Color::Ansi(AnsiColor::BrightBlue))),
+            StatusType::Modifying | StatusType::Renaming => {
+                base_style.fg_color(Some(Color::Ansi(AnsiColor::BrightBlue)))
+            }
             // Completed => Dimmed colors
-            StatusType::Created => base_style.fg_color(Some(Color::Ansi(AnsiColor::Green))),
+            StatusType::Created | StatusType::Applied => {
+                base_style.fg_color(Some(Color::Ansi(AnsiColor::Green)))
+            }
             StatusType::Added => base_style.fg_color(Some(Color::Ansi(AnsiColor::Cyan))),
             StatusType::Removed | StatusType::RolledBack => {
                 base_style.fg_color(Some(Color::Ansi(AnsiColor::Magenta)))
             }
-            StatusType::Modified => base_style.fg_color(Some(Color::Ansi(AnsiColor::Blue))),
+            StatusType::Modified | StatusType::Renamed => {
+                base_style.fg_color(Some(Color::Ansi(AnsiColor::Blue)))
+            }
             // Status types
             StatusType::Warning => base_style.fg_color(Some(Color::Ansi(AnsiColor::Yellow))),
             StatusType::Error => base_style.fg_color(Some(Color::Ansi(AnsiColor::Red))),
@@ -75,16 +87,20 @@ impl StatusType {
             StatusType::Creating => "Creating",
             StatusType::Adding => "Adding",
             StatusType::Modifying => "Modifying",
+            StatusType::Renaming => "Renaming",
             StatusType::Removing => "Removing",
             StatusType::Created => "Created",
             StatusType::Added => "Added",
             StatusType::Modified => "Modified",
+            StatusType::Renamed => "Renamed",
             StatusType::Removed => "Removed",
             StatusType::Warning => "Warning",
             StatusType::Error => "Error",
             StatusType::Notice => "Notice",
             StatusType::RollingBack => "Rolling Back",
             StatusType::RolledBack => "Rolled Back",
+            StatusType::Applying => "Applying",
+            StatusType::Applied => "Applied",
         }
     }
 }
@@ -1,8 +1,8 @@
 use bytes::Bytes;
 use cot::auth::PasswordHash;
 use cot::common_types::{Email, Password, Url};
-use cot::db::migrations::{Field, Operation};
-use cot::db::{Auto, Database, DatabaseField, Identifier, LimitedString, Model};
+use cot::db::migrations::{DefaultValue, Field, Operation};
+use cot::db::{Auto, Database, DatabaseField, DbEnum, Identifier, LimitedString, Model};
 use cot::test::TestDatabase;
 use cot_macros::{model, query};
 use fake::rand::rngs::StdRng;
@@ -293,6 +293,130 @@ async fn password_hash_option(db: &TestDatabase) {
     assert!(models[1].password.is_none());
 }
 
+#[cot_macros::dbtest]
+async fn database_default_field(db: &TestDatabase) {
+    #[derive(Debug, Clone)]
+    #[model]
+    struct DefaultModel {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        #[model(default = 5)]
+        priority: Auto<i32>,
+        #[model(default = 0)]
+        views: i32,
+    }
+
+    const CREATE_DEFAULT_MODEL: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__default_model"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(
+                Identifier::new("priority"),
+                <Auto<i32> as DatabaseField>::TYPE,
+            )
+            .default(DefaultValue::Int(5)),
+            Field::new(Identifier::new("views"), <i32 as DatabaseField>::TYPE)
+                .default(DefaultValue::Int(0)),
+        ])
+        .build();
+
+    run_migrations!(db, CREATE_DEFAULT_MODEL);
+
+    let mut with_default = DefaultModel {
+        id: Auto::auto(),
+        priority: Auto::auto(),
+        views: 3,
+    };
+    with_default.save(&**db).await.unwrap();
+    assert_eq!(with_default.priority, Auto::fixed(5));
+
+    let mut with_value = DefaultModel {
+        id: Auto::auto(),
+        priority: Auto::fixed(1),
+        views: 7,
+    };
+    with_value.save(&**db).await.unwrap();
+
+    let models = DefaultModel::objects().all(&**db).await.unwrap();
+
+    assert_eq!(models.len(), 2);
+    assert_eq!(models[0].priority, Auto::fixed(5));
+    assert_eq!(models[0].views, 3);
+    assert_eq!(models[1].priority, Auto::fixed(1));
+    assert_eq!(models[1].views, 7);
+}
+
+#[cot_macros::dbtest]
+async fn database_default_field_bulk_insert_explicit_primary_key(db: &TestDatabase) {
+    #[derive(Debug, Clone)]
+    #[model]
+    struct DefaultExplicitPkModel {
+        #[model(primary_key)]
+        id: i32,
+        #[model(default = "draft")]
+        status: Auto<String>,
+        #[model(default = 5)]
+        priority: Auto<i32>,
+    }
+
+    const CREATE_DEFAULT_EXPLICIT_PK_MODEL: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__default_explicit_pk_model"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE).primary_key(),
+            Field::new(
+                Identifier::new("status"),
+                <Auto<String> as DatabaseField>::TYPE,
+            )
+            .default(DefaultValue::String("draft")),
+            Field::new(
+                Identifier::new("priority"),
+                <Auto<i32> as DatabaseField>::TYPE,
+            )
+            .default(DefaultValue::Int(5)),
+        ])
+        .build();
+
+    run_migrations!(db, CREATE_DEFAULT_EXPLICIT_PK_MODEL);
+
+    // the rows filled in by the database are matched with the instances by their
+    // primary keys, not by the insertion order
+    let mut models = vec![
+        DefaultExplicitPkModel {
+            id: 3,
+            status: Auto::auto(),
+            priority: Auto::auto(),
+        },
+        DefaultExplicitPkModel {
+            id: 1,
+            status: Auto::auto(),
+            priority: Auto::auto(),
+        },
+        DefaultExplicitPkModel {
+            id: 2,
+            status: Auto::auto(),
+            priority: Auto::auto(),
+        },
+    ];
+    DefaultExplicitPkModel::bulk_insert(&**db, &mut models)
+        .await
+        .unwrap();
+
+    for model in &models {
+        assert_eq!(model.status, Auto::fixed("draft".to_owned()));
+        assert_eq!(model.priority, Auto::fixed(5));
+    }
+
+    let stored = DefaultExplicitPkModel::objects().all(&**db).await.unwrap();
+    assert_eq!(stored.len(), 3);
+    assert!(
+        stored
+            .iter()
+            .all(|model| model.status == Auto::fixed("draft".to_owned()))
+    );
+}
+
 #[cot_macros::dbtest]
 #[expect(clippy::too_many_lines)]
 async fn weekday_set_field_functionality(db: &mut TestDatabase) {
@@ -429,3 +553,329 @@ async fn weekday_set_field_functionality(db: &mut TestDatabase) {
     assert_eq!(updated_model.schedule, weekdays_only);
     assert_eq!(updated_model.optional_schedule, Some(weekend_only));
 }
+
+#[cfg(all(feature = "uuid", feature = "decimal", feature = "json"))]
+#[cot_macros::dbtest]
+async fn uuid_decimal_json_fields(db: &TestDatabase) {
+    use cot::db::query::expr::Expr;
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct DocumentModel {
+        #[model(primary_key)]
+        id: uuid::Uuid,
+        price: rust_decimal::Decimal,
+        data: serde_json::Value,
+        parent: Option<uuid::Uuid>,
+        discount: Option<rust_decimal::Decimal>,
+        metadata: Option<serde_json::Value>,
+    }
+
+    const CREATE_DOCUMENT_MODEL: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__document_model"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <uuid::Uuid as DatabaseField>::TYPE).primary_key(),
+            Field::new(
+                Identifier::new("price"),
+                <rust_decimal::Decimal as DatabaseField>::TYPE,
+            ),
+            Field::new(
+                Identifier::new("data"),
+                <serde_json::Value as DatabaseField>::TYPE,
+            ),
+            Field::new(
+                Identifier::new("parent"),
+                <Option<uuid::Uuid> as DatabaseField>::TYPE,
+            )
+            .set_null(true),
+            Field::new(
+                Identifier::new("discount"),
+                <Option<rust_decimal::Decimal> as DatabaseField>::TYPE,
+            )
+            .set_null(true),
+            Field::new(
+                Identifier::new("metadata"),
+                <Option<serde_json::Value> as DatabaseField>::TYPE,
+            )
+            .set_null(true),
+        ])
+        .build();
+
+    run_migrations!(db, CREATE_DOCUMENT_MODEL);
+
+    let first_id = uuid::Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+    let second_id = uuid::Uuid::from_u128(0x936d_a01f_9abd_4d9d_80c7_02af_85c8_22a8);
+    let mut first = DocumentModel {
+        id: first_id,
+        price: rust_decimal::Decimal::new(1999, 2),
+        data: serde_json::json!({"author": {"name": "alice"}, "pages": 12}),
+        parent: None,
+        discount: None,
+        metadata: None,
+    };
+    first.insert(&**db).await.unwrap();
+    let mut second = DocumentModel {
+        id: second_id,
+        price: rust_decimal::Decimal::new(123_456_789, 4),
+        data: serde_json::json!({"author": {"name": "bob"}, "pages": 3}),
+        parent: Some(first_id),
+        discount: Some(rust_decimal::Decimal::new(5, 1)),
+        metadata: Some(serde_json::json!(["draft"])),
+    };
+    second.insert(&**db).await.unwrap();
+
+    let models = DocumentModel::objects().all(&**db).await.unwrap();
+    assert_eq!(models.len(), 2);
+    assert!(models.contains(&first));
+    assert!(models.contains(&second));
+
+    let by_id = query!(DocumentModel, $id == second_id)
+        .get(&**db)
+        .await
+        .unwrap();
+    assert_eq!(by_id, Some(second.clone()));
+
+    let by_author = DocumentModel::objects()
+        .filter(Expr::eq(
+            Expr::json_get(Expr::field("data"), ["author", "name"]),
+            Expr::value("alice"),
+        ))
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(by_author, vec![first]);
+
+    let by_pages = DocumentModel::objects()
+        .filter(Expr::lt(
+            Expr::cast(
+                Expr::json_get(Expr::field("data"), ["pages"]),
+                cot::db::ColumnType::Integer,
+            ),
+            Expr::value(10),
+        ))
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(by_pages, vec![second]);
+}
+
+#[cot_macros::dbtest]
+async fn db_enum_fields(db: &TestDatabase) {
+    #[derive(Debug, Clone, Copy, PartialEq, DbEnum)]
+    enum Status {
+        Draft,
+        #[db_enum(value = "pub")]
+        Published,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, DbEnum)]
+    #[db_enum(storage = "integer")]
+    enum Priority {
+        Low = 1,
+        High = 10,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct ArticleModel {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        status: Status,
+        priority: Option<Priority>,
+    }
+
+    const CREATE_ARTICLE_MODEL: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__article_model"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("status"), <Status as DatabaseField>::TYPE),
+            Field::new(
+                Identifier::new("priority"),
+                <Option<Priority> as DatabaseField>::TYPE,
+            )
+            .set_null(true),
+        ])
+        .build();
+
+    run_migrations!(db, CREATE_ARTICLE_MODEL);
+
+    let mut draft = ArticleModel {
+        id: Auto::auto(),
+        status: Status::Draft,
+        priority: None,
+    };
+    draft.insert(&**db).await.unwrap();
+    let mut published = ArticleModel {
+        id: Auto::auto(),
+        status: Status::Published,
+        priority: Some(Priority::High),
+    };
+    published.insert(&**db).await.unwrap();
+
+    let models = ArticleModel::objects().all(&**db).await.unwrap();
+    assert_eq!(models, vec![draft.clone(), published.clone()]);
+
+    let by_status = query!(ArticleModel, $status == Status::Published)
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(by_status, vec![published.clone()]);
+    let by_priority = query!(ArticleModel, $priority == Some(Priority::High))
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(by_priority, vec![published]);
+
+    db.raw("UPDATE cot__article_model SET status = 'unknown'")
+        .await
+        .unwrap();
+    assert!(ArticleModel::objects().all(&**db).await.is_err());
+}
+
+#[cfg(feature = "postgres")]
+#[ignore = "Tests that use PostgreSQL are ignored by default"]
+#[cot::test]
+async fn postgres_array_and_search_fields() {
+    use cot::db::query::expr::{ExprArray, ExprSearch};
+    use cot::db::query::order::OrderBy;
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct PostModel {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        body: String,
+        tags: Vec<String>,
+        scores: Option<Vec<i32>>,
+    }
+
+    const CREATE_POST_MODEL: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__post_model"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("body"), <String as DatabaseField>::TYPE),
+            Field::new(
+                Identifier::new("tags"),
+                <Vec<String> as DatabaseField>::TYPE,
+            ),
+            Field::new(
+                Identifier::new("scores"),
+                <Option<Vec<i32>> as DatabaseField>::TYPE,
+            )
+            .set_null(true),
+        ])
+        .build();
+
+    let db = &TestDatabase::new_postgres("postgres_array_and_search_fields")
+        .await
+        .unwrap();
+    run_migrations!(db, CREATE_POST_MODEL);
+
+    let mut rust = PostModel {
+        id: Auto::auto(),
+        body: "Writing fast and reliable web applications in Rust".to_owned(),
+        tags: vec!["rust".to_owned(), "web".to_owned()],
+        scores: Some(vec![1, 2, 3]),
+    };
+    rust.insert(&**db).await.unwrap();
+    let mut cooking = PostModel {
+        id: Auto::auto(),
+        body: "Baking bread at home".to_owned(),
+        tags: vec!["cooking".to_owned()],
+        scores: None,
+    };
+    cooking.insert(&**db).await.unwrap();
+
+    let all = PostModel::objects().all(&**db).await.unwrap();
+    assert_eq!(all, vec![rust.clone(), cooking.clone()]);
+
+    let tagged = PostModel::objects()
+        .filter(<PostModel as Model>::Fields::tags.array_contains(["rust".to_owned()]))
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(tagged, vec![rust.clone()]);
+    let overlapping = PostModel::objects()
+        .filter(<PostModel as Model>::Fields::scores.array_overlaps([3, 4]))
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(overlapping, vec![rust.clone()]);
+    let contained = PostModel::objects()
+        .filter(
+            <PostModel as Model>::Fields::tags
+                .array_contained_by(["cooking".to_owned(), "travel".to_owned()]),
+        )
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(contained, vec![cooking]);
+
+    let found = PostModel::objects()
+        .filter(<PostModel as Model>::Fields::body.search("application", "english"))
+        .order_by(OrderBy::desc(
+            <PostModel as Model>::Fields::body.search_rank("application", "english"),
+        ))
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(found, vec![rust]);
+
+    db.cleanup().await.unwrap();
+}
+
+#[cfg(all(feature = "postgres", feature = "sqlite"))]
+#[cot::test]
+#[cfg_attr(
+    miri,
+    ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
+)]
+async fn array_fields_unsupported_on_sqlite() {
+    use cot::db::DatabaseError;
+    use cot::db::query::expr::ExprArray;
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct TaggedModel {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        tags: Vec<String>,
+    }
+
+    const CREATE_TAGGED_MODEL: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__tagged_model"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(
+                Identifier::new("tags"),
+                <Vec<String> as DatabaseField>::TYPE,
+            ),
+        ])
+        .build();
+
+    let db = &TestDatabase::new_sqlite().await.unwrap();
+
+    let error = CREATE_TAGGED_MODEL.forwards(db).await.unwrap_err();
+    assert!(matches!(
+        error,
+        DatabaseError::UnsupportedByBackend {
+            feature: "array columns",
+            backend: "SQLite",
+        }
+    ));
+
+    let error = TaggedModel::objects()
+        .filter(<TaggedModel as Model>::Fields::tags.array_contains(["rust".to_owned()]))
+        .all(&**db)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, DatabaseError::QueryBuildingError(_)));
+
+    db.cleanup().await.unwrap();
+}
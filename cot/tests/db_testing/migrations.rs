@@ -1,12 +1,16 @@
 use cot::App;
 use cot::auth::db::DatabaseUserApp;
 use cot::db::migrations::{
-    Field, Migration, MigrationDependency, MigrationEngine, Operation, SyncDynMigration,
-    wrap_migrations,
+    DynMigration, Field, Migration, MigrationContext, MigrationDependency, MigrationDirection,
+    MigrationEngine, MigrationEngineError, MigrationTarget, Operation, SyncDynMigration,
+    migration_op, wrap_migrations,
+};
+use cot::db::{
+    Auto, Database, DatabaseDialect, DatabaseField, ForeignKeyOnDeletePolicy,
+    ForeignKeyOnUpdatePolicy, Identifier,
 };
-use cot::db::{Auto, Database, DatabaseField, Identifier};
 use cot::session::db::SessionApp;
-use cot::test::TestDatabase;
+use cot::test::{TestDatabase, TestMigration};
 use cot_macros::{model, query};
 
 const SNAPSHOT_RELATIVE_PATH: &str = "snapshots/migrations";
@@ -437,3 +441,671 @@ async fn test_migration_engine_rollback_zero(test_db: &mut TestDatabase) {
     )
     .await;
 }
+
+const SQUASH_FIRST_FIELDS: &[Field] =
+    &[
+        Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+            .primary_key()
+            .auto(),
+    ];
+
+fn squash_first() -> Operation {
+    Operation::create_model()
+        .table_name(Identifier::new("squash_app__first"))
+        .fields(SQUASH_FIRST_FIELDS)
+        .build()
+}
+
+fn squash_second() -> Operation {
+    Operation::create_model()
+        .table_name(Identifier::new("squash_app__second"))
+        .fields(SQUASH_FIRST_FIELDS)
+        .build()
+}
+
+fn squash_originals() -> [TestMigration; 2] {
+    [
+        TestMigration::new("squash_app", "m_0001_initial", [], [squash_first()]),
+        TestMigration::new(
+            "squash_app",
+            "m_0002_second",
+            [MigrationDependency::migration(
+                "squash_app",
+                "m_0001_initial",
+            )],
+            [squash_second()],
+        ),
+    ]
+}
+
+fn squash_squashed() -> TestMigration {
+    TestMigration::new(
+        "squash_app",
+        "m_0004_squashed_0001_0002",
+        [],
+        [squash_first(), squash_second()],
+    )
+    .replaces(["m_0001_initial", "m_0002_second"])
+}
+
+fn squash_third() -> TestMigration {
+    TestMigration::new(
+        "squash_app",
+        "m_0003_third",
+        [MigrationDependency::migration(
+            "squash_app",
+            "m_0002_second",
+        )],
+        [Operation::create_model()
+            .table_name(Identifier::new("squash_app__third"))
+            .fields(SQUASH_FIRST_FIELDS)
+            .build()],
+    )
+}
+
+#[cot_macros::dbtest]
+async fn test_squashed_migration_applied_originals(test_db: &mut TestDatabase) {
+    let database = test_db.database();
+    MigrationEngine::new(squash_originals())
+        .unwrap()
+        .run(&database)
+        .await
+        .unwrap();
+
+    // the originals are removed; the dependency on them is satisfied by the
+    // squashed migration, which is not applied again
+    let engine = MigrationEngine::new([squash_squashed(), squash_third()]).unwrap();
+    engine.run(&database).await.unwrap();
+
+    assert_migrations_applied(
+        &database,
+        &[
+            ("squash_app", "m_0001_initial", true),
+            ("squash_app", "m_0002_second", true),
+            ("squash_app", "m_0004_squashed_0001_0002", false),
+            ("squash_app", "m_0003_third", true),
+        ],
+    )
+    .await;
+}
+
+#[cot_macros::dbtest]
+async fn test_squashed_migration_applied_to_new_database(test_db: &mut TestDatabase) {
+    let database = test_db.database();
+    MigrationEngine::new([squash_squashed()])
+        .unwrap()
+        .run(&database)
+        .await
+        .unwrap();
+
+    assert_migrations_applied(
+        &database,
+        &[
+            ("squash_app", "m_0001_initial", true),
+            ("squash_app", "m_0002_second", true),
+            ("squash_app", "m_0004_squashed_0001_0002", true),
+        ],
+    )
+    .await;
+
+    // the originals are considered applied, so nothing is applied again
+    let [first, second] = squash_originals();
+    let engine = MigrationEngine::new([first, second, squash_squashed()]).unwrap();
+    engine.run(&database).await.unwrap();
+
+    let mut output = Vec::new();
+    migration_rollback(&engine, &database, &mut output, "zero", "squash_app").await;
+    assert_migrations_applied(
+        &database,
+        &[
+            ("squash_app", "m_0001_initial", false),
+            ("squash_app", "m_0002_second", false),
+            ("squash_app", "m_0004_squashed_0001_0002", false),
+        ],
+    )
+    .await;
+}
+
+#[cot_macros::dbtest]
+async fn test_squashed_migration_with_originals_present(test_db: &mut TestDatabase) {
+    let database = test_db.database();
+    let [first, second] = squash_originals();
+    MigrationEngine::new([first])
+        .unwrap()
+        .run(&database)
+        .await
+        .unwrap();
+
+    // the originals are applied instead of the squashed migration, since one
+    // of them is already applied
+    let [first, _] = squash_originals();
+    let engine = MigrationEngine::new([first, second, squash_squashed()]).unwrap();
+    engine.run(&database).await.unwrap();
+
+    assert_migrations_applied(
+        &database,
+        &[
+            ("squash_app", "m_0001_initial", true),
+            ("squash_app", "m_0002_second", true),
+            ("squash_app", "m_0004_squashed_0001_0002", true),
+        ],
+    )
+    .await;
+}
+
+#[cot_macros::dbtest]
+async fn test_squashed_migration_partially_applied_originals(test_db: &mut TestDatabase) {
+    let database = test_db.database();
+    let [first, _] = squash_originals();
+    MigrationEngine::new([first])
+        .unwrap()
+        .run(&database)
+        .await
+        .unwrap();
+
+    let result = MigrationEngine::new([squash_squashed()])
+        .unwrap()
+        .run(&database)
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn squashed_migration_partially_present_originals() {
+    let [first, _] = squash_originals();
+
+    let result = MigrationEngine::new([first, squash_squashed()]);
+
+    assert!(result.is_err());
+}
+
+fn rollback_app1_engine() -> MigrationEngine {
+    #[expect(trivial_casts)]
+    MigrationEngine::new([
+        &RollbackApp1Initial as &SyncDynMigration,
+        &RollbackApp10002 as &SyncDynMigration,
+        &RollbackApp1003 as &SyncDynMigration,
+    ])
+    .unwrap()
+}
+
+fn rollback_app1_target(migration_name: &str) -> MigrationTarget<'_> {
+    MigrationTarget::Migration {
+        app_name: "rollback_app1",
+        migration_name,
+    }
+}
+
+#[cot_macros::dbtest]
+async fn test_migrate_to_target(test_db: &mut TestDatabase) {
+    let database = test_db.database();
+    let engine = rollback_app1_engine();
+    let mut output = Vec::new();
+
+    engine
+        .migrate(&database, rollback_app1_target("0002"), false, &mut output)
+        .await
+        .unwrap();
+    assert_migrations_applied(
+        &database,
+        &[
+            ("rollback_app1", "m_0001_initial", true),
+            ("rollback_app1", "m_0002_second", true),
+            ("rollback_app1", "m_0003_third", false),
+        ],
+    )
+    .await;
+
+    engine
+        .migrate(&database, rollback_app1_target("0001"), false, &mut output)
+        .await
+        .unwrap();
+    assert_migrations_applied(
+        &database,
+        &[
+            ("rollback_app1", "m_0001_initial", true),
+            ("rollback_app1", "m_0002_second", false),
+            ("rollback_app1", "m_0003_third", false),
+        ],
+    )
+    .await;
+
+    engine
+        .migrate(&database, MigrationTarget::Latest, false, &mut output)
+        .await
+        .unwrap();
+    assert_migrations_applied(
+        &database,
+        &[
+            ("rollback_app1", "m_0001_initial", true),
+            ("rollback_app1", "m_0002_second", true),
+            ("rollback_app1", "m_0003_third", true),
+        ],
+    )
+    .await;
+}
+
+#[cot_macros::dbtest]
+async fn test_migrate_fake(test_db: &mut TestDatabase) {
+    let database = test_db.database();
+    let engine = rollback_app1_engine();
+    let mut output = Vec::new();
+
+    engine
+        .migrate(&database, MigrationTarget::Latest, true, &mut output)
+        .await
+        .unwrap();
+    assert_migrations_applied(
+        &database,
+        &[
+            ("rollback_app1", "m_0001_initial", true),
+            ("rollback_app1", "m_0003_third", true),
+        ],
+    )
+    .await;
+
+    engine
+        .migrate(&database, rollback_app1_target("zero"), true, &mut output)
+        .await
+        .unwrap();
+    assert_migrations_applied(
+        &database,
+        &[
+            ("rollback_app1", "m_0001_initial", false),
+            ("rollback_app1", "m_0003_third", false),
+        ],
+    )
+    .await;
+
+    // the tables have not been created by the fake migration, so the real one
+    // succeeds
+    engine.run(&database).await.unwrap();
+}
+
+#[cot_macros::dbtest]
+async fn test_migrate_plan(test_db: &mut TestDatabase) {
+    let database = test_db.database();
+    let engine = rollback_app1_engine();
+
+    let plan = engine
+        .plan(&database, rollback_app1_target("0002"))
+        .await
+        .unwrap();
+    assert_eq!(plan.direction(), MigrationDirection::Forwards);
+    let mut output = Vec::new();
+    plan.write(&mut output).unwrap();
+    insta::with_settings!({snapshot_path => SNAPSHOT_RELATIVE_PATH}, {
+        insta::assert_snapshot!("migrate_plan_forwards", std::str::from_utf8(&output).unwrap());
+    });
+
+    engine.run(&database).await.unwrap();
+    let plan = engine
+        .plan(&database, MigrationTarget::Latest)
+        .await
+        .unwrap();
+    assert!(plan.is_empty());
+
+    let plan = engine
+        .plan(&database, rollback_app1_target("0001"))
+        .await
+        .unwrap();
+    assert_eq!(plan.direction(), MigrationDirection::Backwards);
+    let names: Vec<_> = plan.migrations().map(DynMigration::name).collect();
+    assert_eq!(names, ["m_0003_third", "m_0002_second"]);
+}
+
+#[cot_macros::dbtest]
+async fn test_show_migrations(test_db: &mut TestDatabase) {
+    let database = test_db.database();
+    let engine = rollback_app1_engine();
+    engine
+        .migrate(
+            &database,
+            rollback_app1_target("0001"),
+            false,
+            &mut Vec::new(),
+        )
+        .await
+        .unwrap();
+
+    let mut output = Vec::new();
+    engine
+        .show_migrations(&database, &mut output)
+        .await
+        .unwrap();
+
+    insta::with_settings!({snapshot_path => SNAPSHOT_RELATIVE_PATH}, {
+        insta::assert_snapshot!("show_migrations", std::str::from_utf8(&output).unwrap());
+    });
+}
+
+fn migration_sql_engine() -> MigrationEngine {
+    MigrationEngine::new([
+        TestMigration::new(
+            "sql_app",
+            "m_0001_initial",
+            [],
+            [Operation::create_model()
+                .table_name(Identifier::new("alter_field__parent"))
+                .fields(ALTER_PARENT_FIELDS)
+                .build()],
+        ),
+        TestMigration::new(
+            "sql_app",
+            "m_0002_alter",
+            [MigrationDependency::migration("sql_app", "m_0001_initial")],
+            [
+                Operation::alter_field()
+                    .table_name(Identifier::new("alter_field__parent"))
+                    .old_field(
+                        Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE)
+                            .unique(),
+                    )
+                    .new_field(
+                        Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE)
+                            .unique()
+                            .null(),
+                    )
+                    .build(),
+                Operation::add_field()
+                    .table_name(Identifier::new("alter_field__parent"))
+                    .field(Field::new(
+                        Identifier::new("age"),
+                        <i32 as DatabaseField>::TYPE,
+                    ))
+                    .build(),
+            ],
+        ),
+    ])
+    .unwrap()
+}
+
+#[cot::test]
+async fn test_migration_sql() {
+    let engine = migration_sql_engine();
+
+    for dialect in ["sqlite", "postgres", "mysql"] {
+        let dialect: DatabaseDialect = dialect.parse().unwrap();
+        let sql = engine
+            .migration_sql("sql_app", "0002", dialect)
+            .await
+            .unwrap();
+
+        let mut output = Vec::new();
+        sql.write(&mut output).unwrap();
+        insta::with_settings!({snapshot_path => SNAPSHOT_RELATIVE_PATH}, {
+            insta::assert_snapshot!(
+                format!("migration_sql_{dialect}"),
+                std::str::from_utf8(&output).unwrap()
+            );
+        });
+    }
+}
+
+#[cot::test]
+async fn test_migration_sql_not_found() {
+    let engine = migration_sql_engine();
+
+    let dialect: DatabaseDialect = "sqlite".parse().unwrap();
+    assert!(
+        engine
+            .migration_sql("sql_app", "0003", dialect)
+            .await
+            .is_err()
+    );
+    assert!(
+        engine
+            .migration_sql("sql_app", "zero", dialect)
+            .await
+            .is_err()
+    );
+}
+
+#[migration_op]
+async fn failing_operation(_ctx: MigrationContext<'_>) -> cot::db::Result<()> {
+    Err(cot::db::DatabaseError::MigrationError(
+        MigrationEngineError::Custom("failing operation".to_owned()),
+    ))
+}
+
+#[migration_op]
+async fn noop_operation(_ctx: MigrationContext<'_>) -> cot::db::Result<()> {
+    Ok(())
+}
+
+const ATOMIC_FIELDS: &[Field] = &[
+    Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+        .primary_key()
+        .auto(),
+];
+
+fn atomic_create_model() -> Operation {
+    Operation::create_model()
+        .table_name(Identifier::new("atomic__item"))
+        .fields(ATOMIC_FIELDS)
+        .build()
+}
+
+async fn table_exists(database: &Database, table_name: &str) -> bool {
+    database
+        .raw(&format!("SELECT * FROM {table_name}"))
+        .await
+        .is_ok()
+}
+
+/// Checks that the migrations are applied and rolled back atomically; only
+/// run on the backends supporting transactional schema changes.
+async fn assert_migrations_atomic(test_db: &mut TestDatabase) {
+    let database = test_db.database();
+
+    let engine = MigrationEngine::new([TestMigration::new(
+        "atomic_app",
+        "m_0001_initial",
+        [],
+        [
+            atomic_create_model(),
+            Operation::custom(failing_operation).build(),
+        ],
+    )])
+    .unwrap();
+    assert!(engine.run(&database).await.is_err());
+    assert!(!table_exists(&database, "atomic__item").await);
+    assert_migration_applied(&database, "atomic_app", "m_0001_initial", false).await;
+
+    // the table rebuilds are a part of the transaction too
+    let engine = MigrationEngine::new([
+        TestMigration::new(
+            "atomic_app",
+            "m_0001_initial",
+            [],
+            [Operation::create_model()
+                .table_name(Identifier::new("alter_field__parent"))
+                .fields(ALTER_PARENT_FIELDS)
+                .build()],
+        ),
+        TestMigration::new(
+            "atomic_app",
+            "m_0002_alter",
+            [MigrationDependency::migration(
+                "atomic_app",
+                "m_0001_initial",
+            )],
+            [
+                Operation::alter_field()
+                    .table_name(Identifier::new("alter_field__parent"))
+                    .old_field(
+                        Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE)
+                            .unique(),
+                    )
+                    .new_field(
+                        Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE)
+                            .unique()
+                            .null(),
+                    )
+                    .build(),
+                Operation::custom(failing_operation).build(),
+            ],
+        ),
+    ])
+    .unwrap();
+    assert!(engine.run(&database).await.is_err());
+    assert_migration_applied(&database, "atomic_app", "m_0001_initial", true).await;
+    assert_migration_applied(&database, "atomic_app", "m_0002_alter", false).await;
+    let result = database
+        .raw("INSERT INTO alter_field__parent (name) VALUES (NULL)")
+        .await;
+    assert!(result.is_err());
+
+    // so are the rollbacks
+    let engine = MigrationEngine::new([TestMigration::new(
+        "atomic_app",
+        "m_0001_initial",
+        [],
+        [
+            Operation::custom(noop_operation)
+                .backwards(failing_operation)
+                .build(),
+            atomic_create_model(),
+        ],
+    )])
+    .unwrap();
+    database
+        .raw("DROP TABLE alter_field__parent")
+        .await
+        .unwrap();
+    database
+        .raw("DELETE FROM cot__migrations WHERE app = 'atomic_app'")
+        .await
+        .unwrap();
+    engine.run(&database).await.unwrap();
+    let result = engine
+        .rollback(&database, "zero", "atomic_app", &mut std::io::sink())
+        .await;
+    assert!(result.is_err());
+    assert!(table_exists(&database, "atomic__item").await);
+    assert_migration_applied(&database, "atomic_app", "m_0001_initial", true).await;
+}
+
+#[cot::test]
+#[cfg_attr(
+    miri,
+    ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
+)]
+async fn test_migrations_atomic_sqlite() {
+    let mut test_db = TestDatabase::new_sqlite().await.unwrap();
+
+    assert_migrations_atomic(&mut test_db).await;
+
+    test_db.cleanup().await.unwrap();
+}
+
+#[ignore = "Tests that use PostgreSQL are ignored by default"]
+#[cot::test]
+async fn test_migrations_atomic_postgres() {
+    let mut test_db = TestDatabase::new_postgres("test_migrations_atomic")
+        .await
+        .unwrap();
+
+    assert_migrations_atomic(&mut test_db).await;
+
+    test_db.cleanup().await.unwrap();
+}
+
+#[cot_macros::dbtest]
+async fn test_non_atomic_migration(test_db: &mut TestDatabase) {
+    let database = test_db.database();
+    let engine = MigrationEngine::new([TestMigration::new(
+        "atomic_app",
+        "m_0001_initial",
+        [],
+        [
+            atomic_create_model(),
+            Operation::custom(failing_operation).build(),
+        ],
+    )
+    .atomic(false)])
+    .unwrap();
+
+    assert!(engine.run(&database).await.is_err());
+
+    assert!(table_exists(&database, "atomic__item").await);
+    assert_migration_applied(&database, "atomic_app", "m_0001_initial", false).await;
+}
+
+const ALTER_PARENT_FIELDS: &[Field] = &[
+    Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+        .primary_key()
+        .auto(),
+    Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE).unique(),
+];
+const ALTER_CHILD_FIELDS: &[Field] = &[
+    Field::new(Identifier::new("id"), <i32 as DatabaseField>::TYPE)
+        .primary_key()
+        .auto(),
+    Field::new(Identifier::new("parent"), <i32 as DatabaseField>::TYPE).foreign_key(
+        Identifier::new("alter_field__parent"),
+        Identifier::new("id"),
+        ForeignKeyOnDeletePolicy::Cascade,
+        ForeignKeyOnUpdatePolicy::Cascade,
+    ),
+];
+
+#[cot_macros::dbtest]
+async fn test_alter_field_keeps_data_and_constraints(test_db: &mut TestDatabase) {
+    let database = test_db.database();
+    for operation in [
+        Operation::create_model()
+            .table_name(Identifier::new("alter_field__parent"))
+            .fields(ALTER_PARENT_FIELDS)
+            .build(),
+        Operation::create_model()
+            .table_name(Identifier::new("alter_field__child"))
+            .fields(ALTER_CHILD_FIELDS)
+            .build(),
+    ] {
+        operation.forwards(&database).await.unwrap();
+    }
+    database
+        .raw("INSERT INTO alter_field__parent (name) VALUES ('foo')")
+        .await
+        .unwrap();
+    database
+        .raw("INSERT INTO alter_field__child (parent) VALUES (1)")
+        .await
+        .unwrap();
+
+    let operation = Operation::alter_field()
+        .table_name(Identifier::new("alter_field__parent"))
+        .old_field(Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE).unique())
+        .new_field(
+            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE)
+                .unique()
+                .null(),
+        )
+        .build();
+    operation.forwards(&database).await.unwrap();
+
+    // the rows referencing the altered table are not affected
+    let children = database
+        .raw_values::<(i32,)>("SELECT parent FROM alter_field__child")
+        .await
+        .unwrap();
+    assert_eq!(children, vec![(1,)]);
+    // the constraints of the other columns are still in place
+    let result = database
+        .raw("INSERT INTO alter_field__parent (name) VALUES ('foo')")
+        .await;
+    assert!(result.is_err());
+    let result = database
+        .raw("INSERT INTO alter_field__child (parent) VALUES (2)")
+        .await;
+    assert!(result.is_err());
+
+    operation.backwards(&database).await.unwrap();
+    let names = database
+        .raw_values::<(String,)>("SELECT name FROM alter_field__parent")
+        .await
+        .unwrap();
+    assert_eq!(names, vec![("foo".to_owned(),)]);
+}
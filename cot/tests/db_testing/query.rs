@@ -1,8 +1,11 @@
 use cot::db::migrations::{Field, Operation};
-use cot::db::query::expr::ExprEq;
-use cot::db::{Auto, Database, DatabaseError, DatabaseField, Identifier, Model};
+use cot::db::query::expr::{Expr, ExprAdd, ExprAggregate, ExprEq};
+use cot::db::{
+    Auto, Database, DatabaseError, DatabaseField, FromDbRow, Identifier, Model, RowsNum,
+};
 use cot::test::TestDatabase;
 use cot_macros::{model, query};
+use futures_util::{StreamExt, TryStreamExt};
 
 #[derive(Debug, PartialEq)]
 #[model]
@@ -805,6 +808,505 @@ async fn model_query_contains_combined_with_boolean_ops(test_db: &mut TestDataba
     assert_eq!(got, vec!["apple pie", "apple tart", "banana split"]);
 }
 
+#[derive(Debug, PartialEq)]
+#[model]
+struct OrderedModel {
+    #[model(primary_key)]
+    id: Auto<i32>,
+    name: String,
+    score: Option<i32>,
+}
+
+const CREATE_ORDERED_MODEL: Operation = Operation::create_model()
+    .table_name(Identifier::new("cot__ordered_model"))
+    .fields(&[
+        Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+            .primary_key()
+            .auto(),
+        Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+        Field::new(
+            Identifier::new("score"),
+            <Option<i32> as DatabaseField>::TYPE,
+        )
+        .set_null(<Option<i32> as DatabaseField>::NULLABLE),
+    ])
+    .build();
+
+async fn seed_ordered(test_db: &TestDatabase) {
+    CREATE_ORDERED_MODEL.forwards(test_db).await.unwrap();
+
+    let mut models: Vec<OrderedModel> =
+        [("b", Some(2)), ("a", None), ("c", Some(2)), ("d", Some(1))]
+            .into_iter()
+            .map(|(name, score)| OrderedModel {
+                id: Auto::auto(),
+                name: name.to_owned(),
+                score,
+            })
+            .collect();
+    OrderedModel::bulk_insert(&**test_db, &mut models)
+        .await
+        .unwrap();
+}
+
+fn ordered_names(objects: &[OrderedModel]) -> Vec<&str> {
+    objects.iter().map(|o| o.name.as_str()).collect()
+}
+
+#[cot_macros::dbtest]
+async fn model_query_order_by(test_db: &mut TestDatabase) {
+    seed_ordered(test_db).await;
+
+    let objects = query!(OrderedModel; order_by($name))
+        .all(&**test_db)
+        .await
+        .unwrap();
+    assert_eq!(ordered_names(&objects), vec!["a", "b", "c", "d"]);
+
+    let objects = query!(OrderedModel; order_by($name.desc()))
+        .all(&**test_db)
+        .await
+        .unwrap();
+    assert_eq!(ordered_names(&objects), vec!["d", "c", "b", "a"]);
+
+    let objects = query!(OrderedModel, $name != "d"; order_by($name.desc()))
+        .all(&**test_db)
+        .await
+        .unwrap();
+    assert_eq!(ordered_names(&objects), vec!["c", "b", "a"]);
+}
+
+#[cot_macros::dbtest]
+async fn model_query_order_by_multiple_keys(test_db: &mut TestDatabase) {
+    seed_ordered(test_db).await;
+
+    let objects = query!(OrderedModel; order_by($score.desc().nulls_last(), $name.desc()))
+        .all(&**test_db)
+        .await
+        .unwrap();
+    assert_eq!(ordered_names(&objects), vec!["c", "b", "d", "a"]);
+
+    let objects = OrderedModel::objects()
+        .order_by(<OrderedModel as Model>::Fields::score.asc().nulls_first())
+        .order_by(<OrderedModel as Model>::Fields::name)
+        .all(&**test_db)
+        .await
+        .unwrap();
+    assert_eq!(ordered_names(&objects), vec!["a", "d", "b", "c"]);
+}
+
+#[cot_macros::dbtest]
+async fn model_query_order_by_with_limit_and_get(test_db: &mut TestDatabase) {
+    seed_ordered(test_db).await;
+
+    let objects = query!(OrderedModel; order_by($name.desc()))
+        .offset(1)
+        .limit(2)
+        .all(&**test_db)
+        .await
+        .unwrap();
+    assert_eq!(ordered_names(&objects), vec!["c", "b"]);
+
+    let object = query!(OrderedModel; order_by($score.nulls_last(), $name))
+        .get(&**test_db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(object.name, "d");
+}
+
+#[derive(Debug, PartialEq)]
+#[model]
+struct SaleModel {
+    #[model(primary_key)]
+    id: Auto<i32>,
+    region: String,
+    quantity: i32,
+    price: f64,
+}
+
+const CREATE_SALE_MODEL: Operation = Operation::create_model()
+    .table_name(Identifier::new("cot__sale_model"))
+    .fields(&[
+        Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+            .primary_key()
+            .auto(),
+        Field::new(Identifier::new("region"), <String as DatabaseField>::TYPE),
+        Field::new(Identifier::new("quantity"), <i32 as DatabaseField>::TYPE),
+        Field::new(Identifier::new("price"), <f64 as DatabaseField>::TYPE),
+    ])
+    .build();
+
+async fn seed_sales(test_db: &TestDatabase) {
+    CREATE_SALE_MODEL.forwards(test_db).await.unwrap();
+
+    let mut models: Vec<SaleModel> = [
+        ("north", 1, 1.5),
+        ("north", 3, 2.5),
+        ("south", 4, 10.0),
+        ("east", 2, 0.5),
+        ("east", 5, 1.0),
+        ("east", 6, 1.5),
+    ]
+    .into_iter()
+    .map(|(region, quantity, price)| SaleModel {
+        id: Auto::auto(),
+        region: region.to_owned(),
+        quantity,
+        price,
+    })
+    .collect();
+    SaleModel::bulk_insert(&**test_db, &mut models)
+        .await
+        .unwrap();
+}
+
+#[cot_macros::dbtest]
+async fn model_query_aggregate(test_db: &mut TestDatabase) {
+    seed_sales(test_db).await;
+
+    let fields = || <SaleModel as Model>::Fields::quantity;
+    let (count, sum, avg, min, max): (i64, Option<i64>, Option<f64>, Option<i32>, Option<i32>) =
+        SaleModel::objects()
+            .aggregate(
+                &**test_db,
+                &[
+                    Expr::count_all(),
+                    fields().sum(),
+                    fields().avg(),
+                    fields().min(),
+                    fields().max(),
+                ],
+            )
+            .await
+            .unwrap()
+            .remove(0);
+    assert_eq!(count, 6);
+    assert_eq!(sum, Some(21));
+    assert_eq!(avg, Some(3.5));
+    assert_eq!(min, Some(1));
+    assert_eq!(max, Some(6));
+
+    let result: Vec<(i64, Option<f64>)> = query!(SaleModel, $region == "west")
+        .aggregate(
+            &**test_db,
+            &[
+                <SaleModel as Model>::Fields::id.count(),
+                <SaleModel as Model>::Fields::price.sum(),
+            ],
+        )
+        .await
+        .unwrap();
+    assert_eq!(result, vec![(0, None)]);
+}
+
+#[cot_macros::dbtest]
+async fn model_query_aggregate_group_by(test_db: &mut TestDatabase) {
+    seed_sales(test_db).await;
+
+    let result: Vec<(String, i64, Option<i64>, Option<f64>)> = query!(SaleModel; order_by($region))
+        .group_by(<SaleModel as Model>::Fields::region)
+        .aggregate(
+            &**test_db,
+            &[
+                Expr::field("region"),
+                Expr::count_all(),
+                <SaleModel as Model>::Fields::quantity.sum(),
+                <SaleModel as Model>::Fields::price.sum(),
+            ],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        result,
+        vec![
+            ("east".to_owned(), 3, Some(13), Some(3.0)),
+            ("north".to_owned(), 2, Some(4), Some(4.0)),
+            ("south".to_owned(), 1, Some(4), Some(10.0)),
+        ]
+    );
+
+    let result: Vec<(String, Option<i32>)> =
+        query!(SaleModel, $quantity > 1; order_by($region.desc()))
+            .group_by(<SaleModel as Model>::Fields::region)
+            .having(Expr::gte(Expr::count_all(), Expr::value(1_i64)))
+            .aggregate(
+                &**test_db,
+                &[
+                    Expr::field("region"),
+                    <SaleModel as Model>::Fields::quantity.max(),
+                ],
+            )
+            .await
+            .unwrap();
+    assert_eq!(
+        result,
+        vec![
+            ("south".to_owned(), Some(4)),
+            ("north".to_owned(), Some(3)),
+            ("east".to_owned(), Some(6)),
+        ]
+    );
+
+    let result: Vec<(String,)> = query!(SaleModel; order_by($region))
+        .group_by(<SaleModel as Model>::Fields::region)
+        .having(Expr::gt(Expr::count_all(), Expr::value(1_i64)))
+        .aggregate(&**test_db, &[Expr::field("region")])
+        .await
+        .unwrap();
+    assert_eq!(result, vec![("east".to_owned(),), ("north".to_owned(),)]);
+}
+
+#[cot_macros::dbtest]
+async fn model_query_values(test_db: &mut TestDatabase) {
+    #[derive(Debug, PartialEq, FromDbRow)]
+    struct RegionQuantity {
+        region: String,
+        quantity: i32,
+    }
+
+    #[derive(Debug, PartialEq, FromDbRow)]
+    struct Total(Option<i64>);
+
+    seed_sales(test_db).await;
+
+    let columns = [Expr::field("region"), Expr::field("quantity")];
+    let result: Vec<RegionQuantity> = query!(SaleModel, $region == "east"; order_by($quantity))
+        .values(&**test_db, &columns)
+        .await
+        .unwrap();
+    assert_eq!(
+        result,
+        [2, 5, 6]
+            .into_iter()
+            .map(|quantity| RegionQuantity {
+                region: "east".to_owned(),
+                quantity,
+            })
+            .collect::<Vec<_>>()
+    );
+
+    let result: Vec<(String, i32)> = query!(SaleModel, $quantity > 4; order_by($quantity))
+        .values(&**test_db, &columns)
+        .await
+        .unwrap();
+    assert_eq!(result, vec![("east".to_owned(), 5), ("east".to_owned(), 6)]);
+
+    let result: Vec<Total> = query!(SaleModel, $region == "north")
+        .values(&**test_db, &[<SaleModel as Model>::Fields::quantity.sum()])
+        .await
+        .unwrap();
+    assert_eq!(result, vec![Total(Some(4))]);
+
+    // the column count doesn't match the struct
+    let result: cot::db::Result<Vec<RegionQuantity>> = SaleModel::objects()
+        .values(&**test_db, &[Expr::field("region")])
+        .await;
+    assert!(result.is_err());
+
+    let result: Vec<RegionQuantity> = test_db
+        .raw_values("SELECT region, quantity FROM cot__sale_model WHERE quantity = 3")
+        .await
+        .unwrap();
+    assert_eq!(
+        result,
+        vec![RegionQuantity {
+            region: "north".to_owned(),
+            quantity: 3,
+        }]
+    );
+}
+
+#[cot_macros::dbtest]
+async fn model_query_in_between_not(test_db: &mut TestDatabase) {
+    seed_sales(test_db).await;
+
+    let quantities = |objects: Vec<SaleModel>| -> Vec<i32> {
+        objects.iter().map(|sale| sale.quantity).collect()
+    };
+
+    let objects = query!(SaleModel, $region.is_in(["north", "south"]))
+        .all(&**test_db)
+        .await
+        .unwrap();
+    assert_eq!(quantities(objects), vec![1, 3, 4]);
+
+    let objects = query!(SaleModel, $region.is_not_in(["north", "south"]))
+        .all(&**test_db)
+        .await
+        .unwrap();
+    assert_eq!(quantities(objects), vec![2, 5, 6]);
+
+    let no_regions: [&str; 0] = [];
+    let objects = query!(SaleModel, $region.is_in(no_regions))
+        .all(&**test_db)
+        .await
+        .unwrap();
+    assert!(objects.is_empty());
+    let count = query!(SaleModel, $region.is_not_in(no_regions))
+        .count(&**test_db)
+        .await
+        .unwrap();
+    assert_eq!(count, 6);
+
+    let objects = query!(SaleModel, $quantity.between(2, 4))
+        .all(&**test_db)
+        .await
+        .unwrap();
+    assert_eq!(quantities(objects), vec![3, 4, 2]);
+
+    let objects = query!(SaleModel, !$quantity.between(2, 4) && $region != "east")
+        .all(&**test_db)
+        .await
+        .unwrap();
+    assert_eq!(quantities(objects), vec![1]);
+}
+
+#[cot_macros::dbtest]
+async fn model_query_update(test_db: &mut TestDatabase) {
+    seed_sales(test_db).await;
+
+    let quantity = <SaleModel as Model>::Fields::quantity;
+    let result = query!(SaleModel, $region == "east")
+        .update(&**test_db, &[quantity.set_expr(quantity.add(10))])
+        .await
+        .unwrap();
+    assert_eq!(result.rows_affected(), RowsNum(3));
+
+    let result = query!(SaleModel, $quantity > 10)
+        .update(
+            &**test_db,
+            &[
+                <SaleModel as Model>::Fields::region.set("west"),
+                <SaleModel as Model>::Fields::price.set(0.0),
+            ],
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.rows_affected(), RowsNum(3));
+
+    // updated rows may be moved around by the database, so order them explicitly
+    let objects = query!(SaleModel; order_by($id))
+        .all(&**test_db)
+        .await
+        .unwrap();
+    let rows: Vec<(&str, i32, f64)> = objects
+        .iter()
+        .map(|sale| (sale.region.as_str(), sale.quantity, sale.price))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("north", 1, 1.5),
+            ("north", 3, 2.5),
+            ("south", 4, 10.0),
+            ("west", 12, 0.0),
+            ("west", 15, 0.0),
+            ("west", 16, 0.0),
+        ]
+    );
+
+    let result = query!(SaleModel, $region == "nowhere")
+        .update(&**test_db, &[quantity.set(0)])
+        .await
+        .unwrap();
+    assert_eq!(result.rows_affected(), RowsNum(0));
+
+    let result = SaleModel::objects().update(&**test_db, &[]).await;
+    assert!(matches!(result, Err(DatabaseError::UpdateNoAssignments)));
+}
+
+#[cot_macros::dbtest]
+async fn model_query_stream(test_db: &mut TestDatabase) {
+    seed_sales(test_db).await;
+
+    let query = query!(SaleModel, $region == "east"; order_by($quantity.desc())).clone();
+    let quantities: Vec<i32> = query
+        .stream(&**test_db)
+        .map_ok(|sale| sale.quantity)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(quantities, vec![6, 5, 2]);
+
+    let expected = SaleModel::objects().all(&**test_db).await.unwrap();
+    let all = SaleModel::objects();
+    let mut stream = all.stream(&**test_db);
+    let mut streamed = Vec::new();
+    while let Some(sale) = stream.try_next().await.unwrap() {
+        streamed.push(sale);
+    }
+    assert_eq!(streamed.len(), expected.len());
+
+    let empty = query!(SaleModel, $region == "west").clone();
+    assert!(empty.stream(&**test_db).next().await.is_none());
+}
+
+#[cot_macros::dbtest]
+async fn model_query_stream_in_transaction(test_db: &mut TestDatabase) {
+    seed_sales(test_db).await;
+    let db = &**test_db;
+
+    let mut transaction = db.begin().await.unwrap();
+    query!(SaleModel, $region == "north")
+        .delete(&mut transaction)
+        .await
+        .unwrap();
+
+    // The stream sees the changes made within the transaction.
+    let all = SaleModel::objects();
+    let regions: Vec<String> = all
+        .stream(&mut transaction)
+        .map_ok(|sale| sale.region)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(regions.len(), 4);
+    assert!(regions.iter().all(|region| region != "north"));
+
+    transaction.rollback().await.unwrap();
+
+    assert_eq!(SaleModel::objects().count(db).await.unwrap(), 6);
+}
+
+#[cot_macros::dbtest]
+async fn model_query_for_update(test_db: &mut TestDatabase) {
+    seed_sales(test_db).await;
+    let db = &**test_db;
+
+    let mut transaction = db.begin().await.unwrap();
+    let mut sale = query!(SaleModel, $region == "south")
+        .for_update()
+        .get(&mut transaction)
+        .await
+        .unwrap()
+        .unwrap();
+    sale.quantity += 1;
+    sale.save(&mut transaction).await.unwrap();
+
+    let sales = query!(SaleModel, $region == "east"; order_by($quantity))
+        .for_update_nowait()
+        .all(&mut transaction)
+        .await
+        .unwrap();
+    assert_eq!(sales.len(), 3);
+
+    let sales = query!(SaleModel, $region == "north")
+        .for_update_skip_locked()
+        .all(&mut transaction)
+        .await
+        .unwrap();
+    assert_eq!(sales.len(), 2);
+    transaction.commit().await.unwrap();
+
+    let sale = query!(SaleModel, $region == "south")
+        .get(db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sale.quantity, 5);
+}
+
 #[cot_macros::dbtest]
 async fn transaction_commit(test_db: &mut TestDatabase) {
     migrate_test_model(&*test_db).await;
@@ -925,6 +1427,86 @@ async fn transaction_nested_commit(test_db: &mut TestDatabase) {
     }
 }
 
+#[cot_macros::dbtest]
+async fn transaction_savepoint(test_db: &mut TestDatabase) {
+    migrate_test_model(&*test_db).await;
+    let db = &**test_db;
+
+    let mut transaction = db.begin().await.unwrap();
+    let mut first = TestModel {
+        id: Auto::auto(),
+        name: "first".to_string(),
+    };
+    first.insert(&mut transaction).await.unwrap();
+
+    let mut savepoint = transaction.savepoint().await.unwrap();
+    let mut discarded = TestModel {
+        id: Auto::auto(),
+        name: "discarded".to_string(),
+    };
+    discarded.insert(&mut savepoint).await.unwrap();
+    savepoint.rollback().await.unwrap();
+
+    let mut savepoint = transaction.savepoint().await.unwrap();
+    let mut second = TestModel {
+        id: Auto::auto(),
+        name: "second".to_string(),
+    };
+    second.insert(&mut savepoint).await.unwrap();
+    savepoint.commit().await.unwrap();
+
+    transaction.commit().await.unwrap();
+
+    let names: Vec<String> = TestModel::objects()
+        .all(db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|model| model.name)
+        .collect();
+    assert_eq!(names.len(), 2);
+    assert!(names.contains(&"first".to_owned()));
+    assert!(names.contains(&"second".to_owned()));
+}
+
+#[cot_macros::dbtest]
+async fn transaction_helper(test_db: &mut TestDatabase) {
+    migrate_test_model(&*test_db).await;
+    let db = &**test_db;
+
+    let id = db
+        .transaction(async |transaction| {
+            let mut model = TestModel {
+                id: Auto::auto(),
+                name: "committed".to_string(),
+            };
+            model.insert(transaction).await?;
+            Ok::<_, DatabaseError>(model.id)
+        })
+        .await
+        .unwrap();
+    assert!(
+        TestModel::objects()
+            .filter(<TestModel as Model>::Fields::id.eq(id))
+            .exists(db)
+            .await
+            .unwrap()
+    );
+
+    let result = db
+        .transaction(async |transaction| {
+            let mut model = TestModel {
+                id: Auto::auto(),
+                name: "rolled back".to_string(),
+            };
+            model.insert(transaction).await?;
+            Err::<(), _>(DatabaseError::UpdateNoAssignments)
+        })
+        .await;
+    assert!(matches!(result, Err(DatabaseError::UpdateNoAssignments)));
+    assert_eq!(TestModel::objects().count(db).await.unwrap(), 1);
+}
+
 #[cot_macros::dbtest]
 async fn transaction_nested_outer_rollback(test_db: &mut TestDatabase) {
     migrate_test_model(&*test_db).await;
@@ -1322,3 +1904,64 @@ async fn transaction_raw_with_parameterized() {
         .await
         .expect("failed to clean up SQLite test database");
 }
+
+#[cfg(feature = "sqlite")]
+#[cot::test]
+#[cfg_attr(
+    miri,
+    ignore = "unsupported operation: can't call foreign function `sqlite3_open_v2`"
+)]
+async fn replicas_serve_reads() {
+    let primary = TestDatabase::new_sqlite()
+        .await
+        .expect("failed to create SQLite test database");
+    let replica = TestDatabase::new_sqlite()
+        .await
+        .expect("failed to create SQLite test database");
+    migrate_test_model(&primary).await;
+    migrate_test_model(&replica).await;
+    replica
+        .raw("INSERT INTO cot__test_model (name) VALUES ('replica')")
+        .await
+        .unwrap();
+
+    let db = primary.database().with_replicas([replica.database()]);
+    let mut model = TestModel {
+        id: Auto::auto(),
+        name: "primary".to_owned(),
+    };
+    model.save(&db).await.unwrap();
+
+    // the reads are run on the replica...
+    let objects = TestModel::objects().all(&db).await.unwrap();
+    assert_eq!(objects.len(), 1);
+    assert_eq!(objects[0].name, "replica");
+    let object = TestModel::objects().get(&db).await.unwrap().unwrap();
+    assert_eq!(object.name, "replica");
+    let count = query!(TestModel, $name == "primary")
+        .count(&db)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+
+    // ...unless they lock the rows, or the primary database is used explicitly
+    let objects = TestModel::objects().for_update().all(&db).await.unwrap();
+    assert_eq!(objects[0].name, "primary");
+    let objects = TestModel::objects().all(&db.primary()).await.unwrap();
+    assert_eq!(objects[0].name, "primary");
+
+    // the transactions are always run on the primary database
+    let mut transaction = db.begin().await.unwrap();
+    let objects = TestModel::objects().all(&mut transaction).await.unwrap();
+    assert_eq!(objects[0].name, "primary");
+    transaction.commit().await.unwrap();
+
+    primary
+        .cleanup()
+        .await
+        .expect("failed to clean up SQLite test database");
+    replica
+        .cleanup()
+        .await
+        .expect("failed to clean up SQLite test database");
+}
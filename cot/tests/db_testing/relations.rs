@@ -1,7 +1,10 @@
-use cot::db::migrations::{Field, Operation};
+use cot::db::migrations::{Constraint, Field, Operation};
+use cot::db::query::QueryBuildingError;
+use cot::db::query::expr::{Expr, ExprEq, ExprNull, FieldRef};
 use cot::db::{
-    Auto, DatabaseError, DatabaseField, ForeignKey, ForeignKeyOnDeletePolicy,
-    ForeignKeyOnUpdatePolicy, Identifier, Model,
+    Auto, Column, CompositePrimaryKeyColumn, DatabaseError, DatabaseField, ForeignKey,
+    ForeignKeyOnDeletePolicy, ForeignKeyOnUpdatePolicy, Identifier, ManyToMany, Model,
+    ModelPrimaryKey,
 };
 use cot::test::TestDatabase;
 use cot_macros::{model, query};
@@ -245,3 +248,817 @@ async fn foreign_keys_cascade(db: &mut TestDatabase) {
         .unwrap();
     assert!(Child::objects().all(&**db).await.unwrap().is_empty());
 }
+
+#[cot_macros::dbtest]
+#[expect(clippy::too_many_lines)]
+async fn foreign_keys_select_related(db: &mut TestDatabase) {
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct Author {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        name: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct Book {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        title: String,
+        author: ForeignKey<Author>,
+        editor: Option<ForeignKey<Author>>,
+    }
+
+    const CREATE_AUTHOR: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__author"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+        ])
+        .build();
+    const CREATE_BOOK: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__book"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("title"), <String as DatabaseField>::TYPE),
+            Field::new(
+                Identifier::new("author"),
+                <ForeignKey<Author> as DatabaseField>::TYPE,
+            )
+            .foreign_key(
+                <Author as Model>::TABLE_NAME,
+                <Author as Model>::PRIMARY_KEY_NAME,
+                ForeignKeyOnDeletePolicy::Restrict,
+                ForeignKeyOnUpdatePolicy::Restrict,
+            ),
+            Field::new(
+                Identifier::new("editor"),
+                <Option<ForeignKey<Author>> as DatabaseField>::TYPE,
+            )
+            .set_null(<Option<ForeignKey<Author>> as DatabaseField>::NULLABLE)
+            .foreign_key(
+                <Author as Model>::TABLE_NAME,
+                <Author as Model>::PRIMARY_KEY_NAME,
+                ForeignKeyOnDeletePolicy::SetNone,
+                ForeignKeyOnUpdatePolicy::SetNone,
+            ),
+        ])
+        .build();
+
+    run_migrations!(db, CREATE_AUTHOR, CREATE_BOOK);
+
+    let mut alice = Author {
+        id: Auto::auto(),
+        name: "alice".to_owned(),
+    };
+    alice.save(&**db).await.unwrap();
+    let mut bob = Author {
+        id: Auto::auto(),
+        name: "bob".to_owned(),
+    };
+    bob.save(&**db).await.unwrap();
+
+    let mut book_1 = Book {
+        id: Auto::auto(),
+        title: "first".to_owned(),
+        author: ForeignKey::from(&alice),
+        editor: Some(ForeignKey::from(&bob)),
+    };
+    book_1.save(&**db).await.unwrap();
+    let mut book_2 = Book {
+        id: Auto::auto(),
+        title: "second".to_owned(),
+        author: ForeignKey::from(&bob),
+        editor: None,
+    };
+    book_2.save(&**db).await.unwrap();
+
+    // select_related fills in the referenced models
+    let books = Book::objects()
+        .select_related(<Book as Model>::Fields::author)
+        .select_related(<Book as Model>::Fields::editor)
+        .order_by(<Book as Model>::Fields::id.asc())
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(books.len(), 2);
+    assert_eq!(books[0].author.model(), Some(&alice));
+    assert_eq!(
+        books[0].editor.as_ref().and_then(ForeignKey::model),
+        Some(&bob)
+    );
+    assert_eq!(books[1].author.model(), Some(&bob));
+    assert_eq!(books[1].editor, None);
+
+    // models are not fetched unless requested
+    let book = Book::objects()
+        .filter(<Book as Model>::Fields::id.eq(book_1.id))
+        .get(&**db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(book.author.model(), None);
+
+    // selecting a column that is not a foreign key is an error
+    let error = Book::objects()
+        .select_related(FieldRef::<ForeignKey<Author>>::new(Identifier::new("id")))
+        .all(&**db)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        DatabaseError::QueryBuildingError(QueryBuildingError::NotAForeignKey(_))
+    ));
+
+    // filtering by the fields of the referenced model
+    let author_name = <Book as Model>::Fields::author.related(<Author as Model>::Fields::name);
+    let books = Book::objects()
+        .filter(author_name.eq("bob"))
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(books.len(), 1);
+    assert_eq!(books[0].title, "second");
+    assert_eq!(books[0].author.model(), None);
+
+    let count = Book::objects()
+        .filter(author_name.eq("alice"))
+        .count(&**db)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+
+    let exists = Book::objects()
+        .filter(author_name.eq("carol"))
+        .exists(&**db)
+        .await
+        .unwrap();
+    assert!(!exists);
+
+    // selecting the fields of the referenced model
+    let titles: Vec<(String, String)> = Book::objects()
+        .order_by(<Book as Model>::Fields::id.asc())
+        .values(&**db, &[Expr::field("title"), author_name.into()])
+        .await
+        .unwrap();
+    assert_eq!(
+        titles,
+        vec![
+            ("first".to_owned(), "alice".to_owned()),
+            ("second".to_owned(), "bob".to_owned()),
+        ]
+    );
+
+    // deleting by the fields of the referenced model
+    Book::objects()
+        .filter(author_name.eq("alice"))
+        .delete(&**db)
+        .await
+        .unwrap();
+    let books = Book::objects().all(&**db).await.unwrap();
+    assert_eq!(books.len(), 1);
+    assert_eq!(books[0].title, "second");
+}
+
+#[cot_macros::dbtest]
+#[expect(clippy::too_many_lines)]
+async fn many_to_many(db: &mut TestDatabase) {
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct Tag {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        name: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct Post {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        title: String,
+        tags: ManyToMany<Tag>,
+    }
+
+    const CREATE_TAG: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__tag"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+        ])
+        .build();
+    const CREATE_POST: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__post"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("title"), <String as DatabaseField>::TYPE),
+        ])
+        .build();
+    const CREATE_POST_TAGS: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__post_tags"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <Auto<i64> as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(
+                Identifier::new("post"),
+                <ForeignKey<Post> as DatabaseField>::TYPE,
+            )
+            .foreign_key(
+                <Post as Model>::TABLE_NAME,
+                <Post as Model>::PRIMARY_KEY_NAME,
+                ForeignKeyOnDeletePolicy::Cascade,
+                ForeignKeyOnUpdatePolicy::Cascade,
+            ),
+            Field::new(
+                Identifier::new("tag"),
+                <ForeignKey<Tag> as DatabaseField>::TYPE,
+            )
+            .foreign_key(
+                <Tag as Model>::TABLE_NAME,
+                <Tag as Model>::PRIMARY_KEY_NAME,
+                ForeignKeyOnDeletePolicy::Cascade,
+                ForeignKeyOnUpdatePolicy::Cascade,
+            ),
+        ])
+        .build();
+    const ADD_POST_TAGS_UNIQUE: Operation = Operation::add_constraint()
+        .table_name(Identifier::new("cot__post_tags"))
+        .constraint(Constraint::unique(
+            Identifier::new("cot__post_tags_post_tag_uniq"),
+            &[Identifier::new("post"), Identifier::new("tag")],
+        ))
+        .build();
+
+    run_migrations!(
+        db,
+        CREATE_TAG,
+        CREATE_POST,
+        CREATE_POST_TAGS,
+        ADD_POST_TAGS_UNIQUE
+    );
+
+    let mut rust = Tag {
+        id: Auto::auto(),
+        name: "rust".to_owned(),
+    };
+    rust.save(&**db).await.unwrap();
+    let mut web = Tag {
+        id: Auto::auto(),
+        name: "web".to_owned(),
+    };
+    web.save(&**db).await.unwrap();
+
+    let mut post = Post {
+        id: Auto::auto(),
+        title: "Hello".to_owned(),
+        tags: ManyToMany::new(),
+    };
+    let error = post.tags.add(&**db, &rust).await.unwrap_err();
+    assert!(matches!(error, DatabaseError::UnsavedModel));
+
+    post.save(&**db).await.unwrap();
+    post.tags.add(&**db, &rust).await.unwrap();
+    post.tags.add(&**db, &web).await.unwrap();
+    // adding the same instance twice is a no-op
+    post.tags.add(&**db, &rust).await.unwrap();
+    assert_eq!(
+        post.tags.all(&**db).await.unwrap(),
+        vec![rust.clone(), web.clone()]
+    );
+
+    // the field is bound when the model is loaded from the database
+    let post_from_db = Post::get_by_primary_key(&**db, post.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        post_from_db.tags.all(&**db).await.unwrap(),
+        vec![rust.clone(), web.clone()]
+    );
+
+    post.tags.remove(&**db, &rust).await.unwrap();
+    assert_eq!(post.tags.all(&**db).await.unwrap(), vec![web.clone()]);
+    // the tag itself is not removed
+    assert_eq!(Tag::objects().count(&**db).await.unwrap(), 2);
+
+    post.tags.clear(&**db).await.unwrap();
+    assert!(post.tags.all(&**db).await.unwrap().is_empty());
+    assert_eq!(Tag::objects().count(&**db).await.unwrap(), 2);
+}
+
+#[cot_macros::dbtest]
+async fn foreign_keys_related_name(db: &mut TestDatabase) {
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct Author {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        name: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct Book {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        #[model(related_name = "books")]
+        author: ForeignKey<Author>,
+        title: String,
+    }
+
+    const CREATE_AUTHOR: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__author"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+        ])
+        .build();
+    const CREATE_BOOK: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__book"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(
+                Identifier::new("author"),
+                <ForeignKey<Author> as DatabaseField>::TYPE,
+            )
+            .foreign_key(
+                <Author as Model>::TABLE_NAME,
+                <Author as Model>::PRIMARY_KEY_NAME,
+                ForeignKeyOnDeletePolicy::Restrict,
+                ForeignKeyOnUpdatePolicy::Restrict,
+            ),
+            Field::new(Identifier::new("title"), <String as DatabaseField>::TYPE),
+        ])
+        .build();
+
+    run_migrations!(db, CREATE_AUTHOR, CREATE_BOOK);
+
+    let mut authors = Vec::new();
+    for name in ["first", "second", "third"] {
+        let mut author = Author {
+            id: Auto::auto(),
+            name: name.to_owned(),
+        };
+        author.save(&**db).await.unwrap();
+        authors.push(author);
+    }
+    let mut books = Vec::new();
+    for (author, title) in [(0, "a"), (1, "b"), (0, "c")] {
+        let mut book = Book {
+            id: Auto::auto(),
+            author: ForeignKey::from(&authors[author]),
+            title: title.to_owned(),
+        };
+        book.save(&**db).await.unwrap();
+        books.push(book);
+    }
+
+    let first_books = authors[0].books().all(&**db).await.unwrap();
+    assert_eq!(first_books, vec![books[0].clone(), books[2].clone()]);
+    let first_book_count = authors[0]
+        .books()
+        .and_filter(<Book as Model>::Fields::title.eq("b"))
+        .count(&**db)
+        .await
+        .unwrap();
+    // the filter is combined with the one of the reverse relation
+    assert_eq!(first_book_count, 0);
+    assert!(authors[2].books().all(&**db).await.unwrap().is_empty());
+
+    let prefetched = Author::prefetch_books(&**db, &authors).await.unwrap();
+    assert_eq!(
+        prefetched,
+        vec![
+            vec![books[0].clone(), books[2].clone()],
+            vec![books[1].clone()],
+            vec![],
+        ]
+    );
+}
+
+#[cot_macros::dbtest]
+async fn foreign_keys_exists_and_null(db: &mut TestDatabase) {
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct Author {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        name: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct Book {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        author: ForeignKey<Author>,
+        subtitle: Option<String>,
+    }
+
+    const CREATE_AUTHOR: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__author"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+        ])
+        .build();
+    const CREATE_BOOK: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__book"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(
+                Identifier::new("author"),
+                <ForeignKey<Author> as DatabaseField>::TYPE,
+            )
+            .foreign_key(
+                <Author as Model>::TABLE_NAME,
+                <Author as Model>::PRIMARY_KEY_NAME,
+                ForeignKeyOnDeletePolicy::Restrict,
+                ForeignKeyOnUpdatePolicy::Restrict,
+            ),
+            Field::new(
+                Identifier::new("subtitle"),
+                <Option<String> as DatabaseField>::TYPE,
+            )
+            .set_null(<Option<String> as DatabaseField>::NULLABLE),
+        ])
+        .build();
+
+    run_migrations!(db, CREATE_AUTHOR, CREATE_BOOK);
+
+    let mut authors = Vec::new();
+    for name in ["first", "second", "third"] {
+        let mut author = Author {
+            id: Auto::auto(),
+            name: name.to_owned(),
+        };
+        author.save(&**db).await.unwrap();
+        authors.push(author);
+    }
+    for (author, subtitle) in [(0, None), (1, Some("b")), (0, Some("c"))] {
+        let mut book = Book {
+            id: Auto::auto(),
+            author: ForeignKey::from(&authors[author]),
+            subtitle: subtitle.map(ToOwned::to_owned),
+        };
+        book.save(&**db).await.unwrap();
+    }
+
+    let count = query!(Book, $subtitle.is_null())
+        .count(&**db)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+    let count = query!(Book, $subtitle.is_not_null())
+        .count(&**db)
+        .await
+        .unwrap();
+    assert_eq!(count, 2);
+
+    let by_author = Expr::eq(Expr::field("author"), Expr::outer_field::<Author, _>("id"));
+    let mut books = Book::objects();
+    books.filter(by_author.clone());
+    let with_books = query!(Author, exists(&books)).all(&**db).await.unwrap();
+    assert_eq!(with_books, vec![authors[0].clone(), authors[1].clone()]);
+
+    let mut books_without_subtitle = Book::objects();
+    books_without_subtitle.filter(Expr::and(
+        by_author,
+        <Book as Model>::Fields::subtitle.is_null(),
+    ));
+    let without = query!(Author, !exists(&books_without_subtitle))
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(without, vec![authors[1].clone(), authors[2].clone()]);
+}
+
+#[cot_macros::dbtest]
+#[expect(clippy::too_many_lines)]
+async fn composite_primary_key(db: &mut TestDatabase) {
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct Student {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        name: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct Enrollment {
+        #[model(primary_key)]
+        student: ForeignKey<Student>,
+        #[model(primary_key)]
+        course: String,
+        grade: i32,
+    }
+
+    const CREATE_STUDENT: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__student"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(Identifier::new("name"), <String as DatabaseField>::TYPE),
+        ])
+        .build();
+    const CREATE_ENROLLMENT: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__enrollment"))
+        .fields(&[
+            Field::new(
+                Identifier::new("student"),
+                <ForeignKey<Student> as DatabaseField>::TYPE,
+            )
+            .primary_key()
+            .foreign_key(
+                <Student as Model>::TABLE_NAME,
+                <Student as Model>::PRIMARY_KEY_NAME,
+                ForeignKeyOnDeletePolicy::Cascade,
+                ForeignKeyOnUpdatePolicy::Cascade,
+            ),
+            Field::new(Identifier::new("course"), <String as DatabaseField>::TYPE).primary_key(),
+            Field::new(Identifier::new("grade"), <i32 as DatabaseField>::TYPE),
+        ])
+        .build();
+
+    run_migrations!(db, CREATE_STUDENT, CREATE_ENROLLMENT);
+
+    assert_eq!(
+        <Enrollment as Model>::PRIMARY_KEY_NAMES,
+        &[Identifier::new("student"), Identifier::new("course")]
+    );
+
+    let mut alice = Student {
+        id: Auto::auto(),
+        name: "alice".to_owned(),
+    };
+    alice.save(&**db).await.unwrap();
+    let mut bob = Student {
+        id: Auto::auto(),
+        name: "bob".to_owned(),
+    };
+    bob.save(&**db).await.unwrap();
+
+    let mut math = Enrollment {
+        student: ForeignKey::from(&alice),
+        course: "math".to_owned(),
+        grade: 3,
+    };
+    math.save(&**db).await.unwrap();
+    let mut physics = Enrollment {
+        student: ForeignKey::from(&alice),
+        course: "physics".to_owned(),
+        grade: 4,
+    };
+    physics.insert(&**db).await.unwrap();
+    let mut bob_math = Enrollment {
+        student: ForeignKey::from(&bob),
+        course: "math".to_owned(),
+        grade: 5,
+    };
+    bob_math.insert(&**db).await.unwrap();
+
+    // the same primary key can't be inserted twice
+    let error = Enrollment {
+        student: ForeignKey::from(&alice),
+        course: "math".to_owned(),
+        grade: 1,
+    }
+    .insert(&**db)
+    .await
+    .unwrap_err();
+    assert!(matches!(error, DatabaseError::UniqueViolation));
+
+    // saving updates the row with the same composite primary key
+    math.grade = 5;
+    math.save(&**db).await.unwrap();
+    physics.grade = 2;
+    physics.update(&**db).await.unwrap();
+
+    let from_db =
+        Enrollment::get_by_primary_key(&**db, ModelPrimaryKey::from_borrowed(math.primary_key()))
+            .await
+            .unwrap()
+            .unwrap();
+    assert_eq!(from_db, math);
+    let from_db =
+        Enrollment::get_by_primary_key(&**db, (ForeignKey::from(&alice), "physics".to_owned()))
+            .await
+            .unwrap()
+            .unwrap();
+    assert_eq!(from_db.grade, 2);
+    let from_db =
+        Enrollment::get_by_primary_key(&**db, (ForeignKey::from(&bob), "math".to_owned()))
+            .await
+            .unwrap()
+            .unwrap();
+    assert_eq!(from_db.grade, 5);
+
+    let mut missing = Enrollment {
+        student: ForeignKey::from(&bob),
+        course: "physics".to_owned(),
+        grade: 1,
+    };
+    let error = missing.update(&**db).await.unwrap_err();
+    assert!(matches!(
+        error,
+        DatabaseError::CompositeRecordNotFound { ref primary_key } if primary_key.len() == 2
+    ));
+
+    // deleting by the fields of the referenced model matches the rows by their
+    // composite primary keys
+    let student_name =
+        <Enrollment as Model>::Fields::student.related(<Student as Model>::Fields::name);
+    Enrollment::objects()
+        .filter(student_name.eq("alice"))
+        .and_filter(<Enrollment as Model>::Fields::course.eq("math"))
+        .delete(&**db)
+        .await
+        .unwrap();
+    let mut remaining: Vec<_> = Enrollment::objects()
+        .all(&**db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|enrollment| (*enrollment.student.primary_key(), enrollment.course))
+        .collect();
+    remaining.sort_by(|(_, lhs), (_, rhs)| lhs.cmp(rhs));
+    assert_eq!(
+        remaining,
+        vec![
+            (bob.id, "math".to_owned()),
+            (alice.id, "physics".to_owned())
+        ]
+    );
+}
+
+#[cot_macros::dbtest]
+#[expect(clippy::too_many_lines)]
+async fn composite_foreign_key(db: &mut TestDatabase) {
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct Course {
+        #[model(primary_key)]
+        code: String,
+        #[model(primary_key)]
+        year: i32,
+        title: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[model]
+    struct Grade {
+        #[model(primary_key)]
+        id: Auto<i32>,
+        #[model(foreign_key(columns(course_code, course_year), on_delete = "cascade"))]
+        course: ForeignKey<Course>,
+        value: i32,
+    }
+
+    const CREATE_COURSE: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__course"))
+        .fields(&[
+            Field::new(Identifier::new("code"), <String as DatabaseField>::TYPE).primary_key(),
+            Field::new(Identifier::new("year"), <i32 as DatabaseField>::TYPE).primary_key(),
+            Field::new(Identifier::new("title"), <String as DatabaseField>::TYPE),
+        ])
+        .build();
+    const CREATE_GRADE: Operation = Operation::create_model()
+        .table_name(Identifier::new("cot__grade"))
+        .fields(&[
+            Field::new(Identifier::new("id"), <Auto<i32> as DatabaseField>::TYPE)
+                .primary_key()
+                .auto(),
+            Field::new(
+                Identifier::new("course_code"),
+                <<<Course as Model>::PrimaryKey as CompositePrimaryKeyColumn<0>>::Type as DatabaseField>::TYPE,
+            ),
+            Field::new(
+                Identifier::new("course_year"),
+                <<<Course as Model>::PrimaryKey as CompositePrimaryKeyColumn<1>>::Type as DatabaseField>::TYPE,
+            ),
+            Field::new(Identifier::new("value"), <i32 as DatabaseField>::TYPE),
+        ])
+        .build();
+    const ADD_GRADE_COURSE_FK: Operation = Operation::add_constraint()
+        .table_name(Identifier::new("cot__grade"))
+        .constraint(Constraint::foreign_key(
+            Identifier::new("cot__grade_course_code_course_year_fk"),
+            &[
+                Identifier::new("course_code"),
+                Identifier::new("course_year"),
+            ],
+            <Course as Model>::TABLE_NAME,
+            <Course as Model>::PRIMARY_KEY_NAMES,
+            ForeignKeyOnDeletePolicy::Cascade,
+            ForeignKeyOnUpdatePolicy::Cascade,
+        ))
+        .build();
+
+    run_migrations!(db, CREATE_COURSE, CREATE_GRADE, ADD_GRADE_COURSE_FK);
+
+    assert_eq!(
+        <Grade as Model>::COLUMNS,
+        &[
+            Column::new(Identifier::new("id")),
+            Column::new(Identifier::new("course_code")),
+            Column::new(Identifier::new("course_year")),
+            Column::new(Identifier::new("value")),
+        ]
+    );
+
+    let mut rust = Course {
+        code: "rust".to_owned(),
+        year: 2024,
+        title: "Rust".to_owned(),
+    };
+    rust.insert(&**db).await.unwrap();
+    let mut rust_next = Course {
+        code: "rust".to_owned(),
+        year: 2025,
+        title: "Rust, again".to_owned(),
+    };
+    rust_next.insert(&**db).await.unwrap();
+
+    let mut first = Grade {
+        id: Auto::auto(),
+        course: ForeignKey::from(&rust),
+        value: 4,
+    };
+    first.save(&**db).await.unwrap();
+    let mut second = Grade {
+        id: Auto::auto(),
+        course: ForeignKey::from(&rust_next),
+        value: 5,
+    };
+    second.save(&**db).await.unwrap();
+
+    // the foreign key is read back from both of its columns
+    let grade = Grade::get_by_primary_key(&**db, first.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(grade.course.primary_key(), (&"rust".to_owned(), &2024));
+    assert_eq!(grade.course.model(), None);
+    let mut course = grade.course.clone();
+    assert_eq!(course.get(&**db).await.unwrap(), &rust);
+
+    // the foreign key constraint spans both of the columns
+    let error = Grade {
+        id: Auto::auto(),
+        course: ForeignKey::PrimaryKey(("rust".to_owned(), 2026)),
+        value: 1,
+    }
+    .save(&**db)
+    .await;
+    assert!(error.is_err());
+
+    // select_related joins the referenced table on both of the columns
+    let grades = Grade::objects()
+        .select_related(<Grade as Model>::Fields::course)
+        .order_by(<Grade as Model>::Fields::id.asc())
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(grades.len(), 2);
+    assert_eq!(grades[0].course.model(), Some(&rust));
+    assert_eq!(grades[1].course.model(), Some(&rust_next));
+
+    let course_title = <Grade as Model>::Fields::course.related(<Course as Model>::Fields::title);
+    let grades = Grade::objects()
+        .filter(course_title.eq("Rust, again"))
+        .all(&**db)
+        .await
+        .unwrap();
+    assert_eq!(grades.len(), 1);
+    assert_eq!(grades[0].value, 5);
+
+    // deleting the referenced model cascades through the constraint
+    Course::objects()
+        .filter(<Course as Model>::Fields::year.eq(2024))
+        .delete(&**db)
+        .await
+        .unwrap();
+    let grades = Grade::objects().all(&**db).await.unwrap();
+    assert_eq!(grades.len(), 1);
+    assert_eq!(grades[0].id, second.id);
+}